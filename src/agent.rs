@@ -1,4 +1,6 @@
 use crate::gh::PullRequest;
+use crate::logging::CommandExt;
+use crate::process::describe_command;
 use anyhow::{Context, Result};
 use std::process::Command;
 
@@ -96,7 +98,7 @@ pub fn list_agent_panes() -> Result<Vec<AgentPane>> {
             "-F",
             "#{session_name}\t#{window_index}\t#{window_name}\t#{pane_id}\t#{pane_index}\t#{pane_current_command}\t#{pane_title}",
         ])
-        .output()
+        .logged_output()
         .context("Failed to list tmux panes")?;
 
     if !output.status.success() {
@@ -126,7 +128,7 @@ pub fn find_agent_pane(pr: &PullRequest) -> Result<Option<AgentPane>> {
 pub fn capture_agent_pane(target: &str) -> Result<String> {
     let output = Command::new("tmux")
         .args(["capture-pane", "-p", "-t", target, "-S", CAPTURE_START_LINE])
-        .output()
+        .logged_output()
         .context("Failed to capture tmux pane")?;
 
     if !output.status.success() {
@@ -174,7 +176,7 @@ pub fn preview_agent_session(pr: &PullRequest) -> AgentPreview {
 fn tmux_display_target(target: &str, format: &str) -> Result<String> {
     let output = Command::new("tmux")
         .args(["display-message", "-p", "-t", target, format])
-        .output()
+        .logged_output()
         .context("Failed to resolve tmux target")?;
 
     if !output.status.success() {
@@ -188,13 +190,16 @@ fn tmux_display_target(target: &str, format: &str) -> Result<String> {
 }
 
 fn tmux_select_target(target: &str) -> Result<()> {
-    let status = Command::new("tmux")
+    let output = Command::new("tmux")
         .args(["select-pane", "-t", target])
-        .status()
+        .logged_output()
         .context("Failed to select tmux pane")?;
 
-    if !status.success() {
-        anyhow::bail!("tmux select-pane failed with {}", status);
+    if !output.status.success() {
+        anyhow::bail!(
+            "tmux select-pane failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
 
     Ok(())
@@ -205,14 +210,26 @@ pub fn switch_or_attach(target: &str) -> Result<()> {
     let window_target = tmux_display_target(target, "#{session_name}:#{window_index}")?;
     tmux_select_target(target)?;
 
-    let args = if std::env::var_os("TMUX").is_some() {
-        vec!["switch-client", "-t", window_target.as_str()]
-    } else {
-        vec!["attach-session", "-t", session_target.as_str()]
-    };
+    if std::env::var_os("TMUX").is_some() {
+        let output = Command::new("tmux")
+            .args(["switch-client", "-t", window_target.as_str()])
+            .logged_output()
+            .context("Failed to switch tmux client")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "tmux switch-client failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        return Ok(());
+    }
 
-    let status = Command::new("tmux")
-        .args(args)
+    // attach-session takes over this terminal until detached, so it inherits stdio and can't go
+    // through the captured-output runner; it is logged here instead.
+    let mut command = Command::new("tmux");
+    command.args(["attach-session", "-t", session_target.as_str()]);
+    tracing::info!(command = %describe_command(&command), "attaching to tmux session");
+    let status = command
         .status()
         .context("Failed to attach to tmux session")?;
 
@@ -240,11 +257,18 @@ mod tests {
             repo_path: PathBuf::from("/tmp/repo"),
             repo_name: "nvidia-lpu/cyborg".to_string(),
             url: "https://github.com/nvidia-lpu/cyborg/pull/199".to_string(),
+            created_at: Utc::now(),
             updated_at: Utc::now(),
             additions: 1,
             deletions: 1,
             is_draft: false,
+            labels: Vec::new(),
+            base_branch: String::new(),
+            head_branch: String::new(),
+            head_oid: String::new(),
+            mergeable: None,
             review_state: ReviewState::Pending,
+            reviewers: Vec::new(),
             details_loaded: true,
         }
     }
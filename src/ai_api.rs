@@ -0,0 +1,440 @@
+use crate::config::{self, AiLaunchApiConfig};
+use crate::gh::{self, PullRequest};
+use crate::logging::CommandExt;
+use crate::private_fs;
+use crate::review_results::{self, ReviewFindings};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+/// Diffs beyond this size are truncated before being sent to the API.
+const MAX_DIFF_CHARS: usize = 200_000;
+
+/// Triage only needs a glimpse of the change.
+const MAX_TRIAGE_DIFF_CHARS: usize = 20_000;
+
+const TRIAGE_INSTRUCTIONS: &str = r#"You are triaging a GitHub pull request before a full code review. Respond with a single JSON object and nothing else:
+{"trivial": <true if the change is safe to skip reviewing (typos, docs, formatting, version bumps), else false>, "reason": "<one short sentence>"}
+When in doubt, answer false."#;
+
+const FINDINGS_INSTRUCTIONS: &str = r#"You are reviewing a GitHub pull request. Respond with a single JSON object and nothing else:
+{"summary": "<one paragraph overall assessment>",
+ "findings": [{"path": "<file path>", "line": <new-file line number or null>, "severity": "high|medium|low", "message": "<what is wrong and how to fix it>"}]}
+Only report real problems (bugs, security issues, missing error handling, unclear code). Use an empty findings list when the change looks good."#;
+
+fn build_system_prompt(review_guide: Option<&str>) -> String {
+    match review_guide {
+        Some(guide) if !guide.trim().is_empty() => {
+            format!(
+                "{}\n\nReview guide:\n{}",
+                FINDINGS_INSTRUCTIONS,
+                guide.trim()
+            )
+        }
+        _ => FINDINGS_INSTRUCTIONS.to_string(),
+    }
+}
+
+fn truncate_diff(diff: &str, max_chars: usize) -> (&str, bool) {
+    match diff.char_indices().nth(max_chars) {
+        Some((end, _)) => (&diff[..end], true),
+        None => (diff, false),
+    }
+}
+
+fn build_user_prompt(pr: &PullRequest, diff: &str, max_chars: usize) -> String {
+    let (diff, truncated) = truncate_diff(diff, max_chars);
+    let mut prompt = format!(
+        "Repository: {}\nPR #{}: {}\n\nDescription:\n{}\n\nDiff:\n{}",
+        pr.repo_name,
+        pr.number,
+        pr.title,
+        if pr.body.trim().is_empty() {
+            "(none)"
+        } else {
+            pr.body.trim()
+        },
+        diff
+    );
+    if truncated {
+        prompt.push_str("\n\n(diff truncated)");
+    }
+    prompt
+}
+
+fn build_request_body(api: &AiLaunchApiConfig, system: &str, user: &str) -> Value {
+    let model = api.model.as_deref().unwrap_or_default();
+    match api.format_key() {
+        "openai" => json!({
+            "model": model,
+            "max_tokens": api.max_tokens,
+            "messages": [
+                {"role": "system", "content": system},
+                {"role": "user", "content": user},
+            ],
+        }),
+        _ => json!({
+            "model": model,
+            "max_tokens": api.max_tokens,
+            "system": system,
+            "messages": [{"role": "user", "content": user}],
+        }),
+    }
+}
+
+fn endpoint_url(api: &AiLaunchApiConfig) -> String {
+    match api.format_key() {
+        "openai" => format!("{}/chat/completions", api.base_url()),
+        _ => format!("{}/messages", api.base_url()),
+    }
+}
+
+fn extract_response_text(format: &str, response: &Value) -> Result<String> {
+    if let Some(message) = response
+        .get("error")
+        .and_then(|error| error.get("message").or(Some(error)))
+    {
+        anyhow::bail!("API error: {}", message);
+    }
+
+    let text = match format {
+        "openai" => response
+            .pointer("/choices/0/message/content")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        _ => response
+            .get("content")
+            .and_then(Value::as_array)
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter_map(|block| block.get("text").and_then(Value::as_str))
+                    .collect::<Vec<_>>()
+                    .join("")
+            }),
+    };
+    text.filter(|text| !text.trim().is_empty())
+        .context("API response did not contain any text")
+}
+
+/// Cut the JSON object out of a reply that may have code fences or prose around it.
+fn json_object(text: &str) -> Result<&str> {
+    let start = text.find('{').context("No JSON object in API response")?;
+    let end = text.rfind('}').context("No JSON object in API response")?;
+    if end < start {
+        anyhow::bail!("No JSON object in API response");
+    }
+    Ok(&text[start..=end])
+}
+
+fn parse_findings(text: &str) -> Result<ReviewFindings> {
+    serde_json::from_str(json_object(text)?).context("Failed to parse review findings")
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct TriageVerdict {
+    pub trivial: bool,
+    #[serde(default)]
+    pub reason: String,
+}
+
+fn parse_triage(text: &str) -> Result<TriageVerdict> {
+    serde_json::from_str(json_object(text)?).context("Failed to parse triage verdict")
+}
+
+fn request_headers(api: &AiLaunchApiConfig) -> Result<Vec<(&'static str, String)>> {
+    let key_env = api.api_key_env();
+    let api_key = std::env::var(&key_env)
+        .with_context(|| format!("API key environment variable {} is not set", key_env))?;
+
+    Ok(match api.format_key() {
+        "openai" => vec![
+            ("content-type", "application/json".to_string()),
+            ("authorization", format!("Bearer {}", api_key)),
+        ],
+        _ => vec![
+            ("content-type", "application/json".to_string()),
+            ("x-api-key", api_key),
+            ("anthropic-version", "2023-06-01".to_string()),
+        ],
+    })
+}
+
+/// Deletes a request body once its request is over, whichever way it ended: the body holds the
+/// whole prompt and diff.
+struct RemoveOnDrop<'a>(&'a Path);
+
+impl Drop for RemoveOnDrop<'_> {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(self.0);
+    }
+}
+
+/// Longest curl waits for a connection, within the request's overall timeout.
+const CONNECT_TIMEOUT_SEC: u64 = 30;
+
+/// Most of an HTTP error response quoted in the error.
+const MAX_ERROR_BODY_CHARS: usize = 2_000;
+
+fn post_json(
+    url: &str,
+    headers: &[(&str, String)],
+    body: &Value,
+    body_path: &Path,
+    timeout_sec: u64,
+) -> Result<Value> {
+    // A body left by an interrupted run, or a symlink planted in its place, is removed rather
+    // than written through; `create_new` then refuses anything that reappears meanwhile.
+    match std::fs::remove_file(body_path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(err).with_context(|| format!("Failed to remove {}", body_path.display()));
+        }
+        _ => {}
+    }
+    let mut file = private_fs::open_options()
+        .write(true)
+        .create_new(true)
+        .open(body_path)
+        .with_context(|| format!("Failed to create {}", body_path.display()))?;
+    let _remove_body = RemoveOnDrop(body_path);
+    file.write_all(&serde_json::to_vec(body)?)
+        .with_context(|| format!("Failed to write {}", body_path.display()))?;
+    drop(file);
+
+    // Headers go through curl's stdin config so API keys never show up in `ps`.
+    let mut config = String::new();
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = \"{}: {}\"\n",
+            name,
+            value.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+    }
+
+    let timeout_sec = timeout_sec.max(1);
+    let output = Command::new("curl")
+        .args(["-sS", "--fail-with-body", "-X", "POST", url])
+        .args(["--max-time", &timeout_sec.to_string()])
+        .args([
+            "--connect-timeout",
+            &CONNECT_TIMEOUT_SEC.min(timeout_sec).to_string(),
+        ])
+        .args(["--config", "-", "--data-binary"])
+        .arg(format!("@{}", body_path.display()))
+        .logged_output_with_input(config.as_bytes())
+        .context("Failed to run curl")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // curl's exit code 28: the request ran out of time
+        if output.status.code() == Some(28) {
+            anyhow::bail!(
+                "API request timed out after {}s (ai.launch.api.timeout_sec): {}",
+                timeout_sec,
+                stderr.trim()
+            );
+        }
+        // With --fail-with-body an HTTP error response is still on stdout
+        let response = String::from_utf8_lossy(&output.stdout);
+        let response = response.trim();
+        if response.is_empty() {
+            anyhow::bail!("API request failed: {}", stderr.trim());
+        }
+        anyhow::bail!(
+            "API request failed: {}: {}",
+            stderr.trim(),
+            response
+                .chars()
+                .take(MAX_ERROR_BODY_CHARS)
+                .collect::<String>()
+        );
+    }
+    serde_json::from_slice(&output.stdout).context("Failed to parse API response")
+}
+
+/// Review a PR through an LLM HTTP API and write `review.json` / `review.md`
+/// under `.reviewer/` in the working directory.
+pub fn run_review(
+    working_dir: &Path,
+    pr: &PullRequest,
+    api: &AiLaunchApiConfig,
+    review_guide: &Path,
+) -> Result<()> {
+    let headers = request_headers(api)?;
+    let diff = gh::get_pr_diff(pr)?;
+    let guide = std::fs::read_to_string(review_guide).ok();
+    let body = build_request_body(
+        api,
+        &build_system_prompt(guide.as_deref()),
+        &build_user_prompt(pr, &diff, MAX_DIFF_CHARS),
+    );
+
+    let results_dir = review_results::results_dir(working_dir);
+    std::fs::create_dir_all(&results_dir)
+        .with_context(|| format!("Failed to create {}", results_dir.display()))?;
+
+    let response = post_json(
+        &endpoint_url(api),
+        &headers,
+        &body,
+        &results_dir.join("request.json"),
+        api.timeout_sec,
+    )?;
+    let text = extract_response_text(api.format_key(), &response)?;
+    let findings = parse_findings(&text)?;
+
+    private_fs::write_atomic(
+        &results_dir.join(review_results::REVIEW_JSON),
+        serde_json::to_string_pretty(&findings)?,
+    )
+    .context("Failed to write review.json")?;
+    private_fs::write_atomic(
+        &results_dir.join(review_results::REVIEW_MD),
+        review_results::render_findings_markdown(pr, &findings),
+    )
+    .context("Failed to write review.md")?;
+    Ok(())
+}
+
+/// Ask a small model whether a PR is trivial enough to skip a full review.
+pub fn triage(pr: &PullRequest, api: &AiLaunchApiConfig, model: &str) -> Result<TriageVerdict> {
+    let headers = request_headers(api)?;
+    let triage_api = AiLaunchApiConfig {
+        model: Some(model.to_string()),
+        max_tokens: 256,
+        ..api.clone()
+    };
+    let diff = gh::get_pr_diff(pr)?;
+    let body = build_request_body(
+        &triage_api,
+        TRIAGE_INSTRUCTIONS,
+        &build_user_prompt(pr, &diff, MAX_TRIAGE_DIFF_CHARS),
+    );
+    let triage_dir = config::config_dir().join("triage");
+    private_fs::create_dir_all(&triage_dir)
+        .with_context(|| format!("Failed to create {}", triage_dir.display()))?;
+    let body_path = triage_dir.join(format!(
+        "{}-{}-{}.json",
+        pr.repo_name.replace('/', "-"),
+        pr.number,
+        std::process::id()
+    ));
+
+    let response = post_json(
+        &endpoint_url(&triage_api),
+        &headers,
+        &body,
+        &body_path,
+        triage_api.timeout_sec,
+    )?;
+    parse_triage(&extract_response_text(triage_api.format_key(), &response)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_request_body, extract_response_text, parse_findings, parse_triage, post_json,
+    };
+    use crate::config::AiLaunchApiConfig;
+    use crate::process::replay::ReplayRunner;
+    use serde_json::json;
+
+    #[test]
+    fn parse_findings_accepts_fenced_json() {
+        let text = "Here you go:\n```json\n{\"summary\": \"Looks risky\", \"findings\": [{\"path\": \"src/lib.rs\", \"line\": 12, \"severity\": \"high\", \"message\": \"Unchecked unwrap\"}]}\n```";
+        let findings = parse_findings(text).expect("findings should parse");
+        assert_eq!(findings.summary, "Looks risky");
+        assert_eq!(findings.findings.len(), 1);
+        assert_eq!(findings.findings[0].path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(findings.findings[0].line, Some(12));
+    }
+
+    #[test]
+    fn post_json_deletes_the_request_body_when_the_request_fails() {
+        let dir = std::env::temp_dir().join(format!("reviewer-api-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let body_path = dir.join("request.json");
+        std::fs::write(&body_path, "left by an interrupted run").unwrap();
+        let runner = ReplayRunner::new();
+        runner.fail("curl", "Could not resolve host");
+        let _guard = runner.install();
+
+        let result = post_json(
+            "https://api.example.com/v1/messages",
+            &[],
+            &json!({"prompt": "the whole diff"}),
+            &body_path,
+            120,
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Could not resolve host"));
+        assert!(runner.commands()[0].contains("--fail-with-body"));
+        assert!(runner.commands()[0].contains("--max-time 120 --connect-timeout 30"));
+        assert!(!body_path.exists());
+
+        // HTTP errors carry the response body; timeouts name the setting
+        runner.reply_with(
+            "curl",
+            22,
+            r#"{"error": {"message": "rate limited"}}"#,
+            "curl: (22) The requested URL returned error: 429",
+        );
+        let error = post_json("https://api.example.com", &[], &json!({}), &body_path, 5)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("error: 429: {\"error\": {\"message\": \"rate limited\"}}"));
+        runner.reply_with("curl", 28, "", "curl: (28) Operation timed out");
+        let error = post_json("https://api.example.com", &[], &json!({}), &body_path, 5)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("timed out after 5s (ai.launch.api.timeout_sec)"));
+        assert!(!body_path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_triage_reads_verdict() {
+        let verdict = parse_triage("{\"trivial\": true, \"reason\": \"README typo\"}")
+            .expect("verdict should parse");
+        assert!(verdict.trivial);
+        assert_eq!(verdict.reason, "README typo");
+        assert!(parse_triage("not sure").is_err());
+    }
+
+    #[test]
+    fn extract_response_text_handles_both_formats() {
+        let anthropic = json!({"content": [{"type": "text", "text": "{\"summary\": \"ok\"}"}]});
+        assert_eq!(
+            extract_response_text("anthropic", &anthropic).unwrap(),
+            "{\"summary\": \"ok\"}"
+        );
+
+        let openai = json!({"choices": [{"message": {"role": "assistant", "content": "hi"}}]});
+        assert_eq!(extract_response_text("openai", &openai).unwrap(), "hi");
+
+        let error = json!({"error": {"message": "invalid key"}});
+        assert!(extract_response_text("openai", &error).is_err());
+    }
+
+    #[test]
+    fn build_request_body_places_system_prompt_per_format() {
+        let mut api = AiLaunchApiConfig {
+            model: Some("test-model".to_string()),
+            ..AiLaunchApiConfig::default()
+        };
+        let body = build_request_body(&api, "sys", "user");
+        assert_eq!(body["system"], "sys");
+        assert_eq!(body["messages"][0]["content"], "user");
+
+        api.format = Some("openai".to_string());
+        let body = build_request_body(&api, "sys", "user");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "user");
+        assert_eq!(body["model"], "test-model");
+    }
+}
@@ -0,0 +1,126 @@
+use crate::config;
+use crate::gh::PullRequest;
+use crate::private_fs;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One line of the local audit log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    pub action: String,
+    pub repo: String,
+    pub pr_number: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(action: &str, pr: &PullRequest) -> Self {
+        Self {
+            at: Utc::now(),
+            action: action.to_string(),
+            repo: pr.repo_name.clone(),
+            pr_number: pr.number,
+            reason: None,
+            comment: None,
+            title: Some(pr.title.clone()),
+            url: Some(pr.url.clone()),
+        }
+    }
+}
+
+pub fn audit_log_path() -> PathBuf {
+    config::config_dir().join("audit.jsonl")
+}
+
+fn append_to(path: &Path, entry: &AuditEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        private_fs::create_dir_all(parent)?;
+    }
+    let mut file = private_fs::open_options()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open audit log {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("Failed to write audit log {}", path.display()))
+}
+
+/// Append an entry to `audit.jsonl` in the config directory.
+pub fn record(entry: &AuditEntry) -> Result<()> {
+    append_to(&audit_log_path(), entry)
+}
+
+fn read_from(path: &Path, since: DateTime<Utc>) -> Result<Vec<AuditEntry>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read audit log {}", path.display()))
+        }
+    };
+    // Skip lines a crash left half-written instead of failing the whole read.
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .filter(|entry| entry.at >= since)
+        .collect())
+}
+
+/// Entries recorded at or after `since`, oldest first.
+pub fn entries_since(since: DateTime<Utc>) -> Result<Vec<AuditEntry>> {
+    read_from(&audit_log_path(), since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append_to, read_from, AuditEntry};
+    use chrono::Utc;
+
+    #[test]
+    fn append_to_writes_one_json_line_per_entry() {
+        let path =
+            std::env::temp_dir().join(format!("reviewer-audit-test-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let entry = AuditEntry {
+            at: Utc::now(),
+            action: "close".to_string(),
+            repo: "org/reviewer".to_string(),
+            pr_number: 7,
+            reason: Some("Stale".to_string()),
+            comment: None,
+            title: None,
+            url: None,
+        };
+        append_to(&path, &entry).unwrap();
+        append_to(&path, &entry).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(serde_json::from_str::<AuditEntry>(lines[0]).unwrap(), entry);
+        assert!(!lines[0].contains("comment"));
+
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, b"{\"at\": \"trunc"))
+            .unwrap();
+        assert_eq!(read_from(&path, entry.at).unwrap().len(), 2);
+        assert!(read_from(&path, entry.at + chrono::Duration::seconds(1))
+            .unwrap()
+            .is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+}
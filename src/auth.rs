@@ -0,0 +1,226 @@
+use crate::config::AuthConfig;
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// The variable `gh` reads its token from; it wins over `gh auth login` credentials.
+const GH_TOKEN_VAR: &str = "GH_TOKEN";
+
+static SETTINGS: RwLock<Option<AuthConfig>> = RwLock::new(None);
+/// Last token printed by `auth.token_command`, and when it was fetched.
+static CACHED: RwLock<Option<(String, Instant)>> = RwLock::new(None);
+/// Held while `auth.token_command` runs, so parallel fetches wait for one refresh.
+static REFRESH: Mutex<()> = Mutex::new(());
+
+/// Apply the `auth` config section; called at startup and on config reload.
+pub fn configure(config: &AuthConfig) {
+    let mut settings = SETTINGS.write().unwrap_or_else(|e| e.into_inner());
+    if settings.as_ref() != Some(config) {
+        *CACHED.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+    *settings = Some(config.clone());
+}
+
+fn settings() -> AuthConfig {
+    SETTINGS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Whether a token source is configured, so `gh auth login` isn't needed.
+pub fn configured() -> bool {
+    let settings = settings();
+    settings.token_env.is_some() || settings.token_command.is_some()
+}
+
+/// The configured token: `token_command`'s output (re-run every `token_refresh_min` minutes, for
+/// short-lived GitHub App installation tokens), else the `token_env` variable. `None` leaves
+/// `gh` to its own `GH_TOKEN`/`GITHUB_TOKEN` or stored login.
+pub fn token() -> Result<Option<String>> {
+    let settings = settings();
+    if let Some(command) = settings.token_command.as_deref() {
+        let max_age = Duration::from_secs(settings.token_refresh_min.saturating_mul(60));
+        let fresh = || {
+            CACHED
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_ref()
+                .filter(|(_, fetched)| fetched.elapsed() < max_age)
+                .map(|(token, _)| token.clone())
+        };
+        if let Some(token) = fresh() {
+            return Ok(Some(token));
+        }
+        let _refresh = REFRESH.lock().unwrap_or_else(|e| e.into_inner());
+        // Another thread may have refreshed it while this one waited
+        if let Some(token) = fresh() {
+            return Ok(Some(token));
+        }
+        let token = run_token_command(command)?;
+        *CACHED.write().unwrap_or_else(|e| e.into_inner()) = Some((token.clone(), Instant::now()));
+        return Ok(Some(token));
+    }
+    let Some(var) = settings.token_env.as_deref() else {
+        return Ok(None);
+    };
+    match std::env::var(var) {
+        Ok(token) if !token.trim().is_empty() => Ok(Some(token.trim().to_string())),
+        _ => bail!(
+            "auth.token_env is set to {}, but that variable is empty or unset",
+            var
+        ),
+    }
+}
+
+fn run_token_command(command: &str) -> Result<String> {
+    let output = shell(command)
+        .output()
+        .with_context(|| format!("Failed to run auth.token_command `{}`", command))?;
+    if !output.status.success() {
+        bail!(
+            "auth.token_command `{}` failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if token.is_empty() {
+        bail!("auth.token_command `{}` printed no token", command);
+    }
+    Ok(token)
+}
+
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+/// Hand the configured token to a `gh` or `git` subprocess. `git` gets it through a credential
+/// helper for the GitHub host that reads `GH_TOKEN`, so the token never appears in `ps`. A token
+/// that can't be resolved is logged and left out, so the tool reports the auth failure in its
+/// own words.
+pub fn apply(command: &mut Command) {
+    let program = command.get_program();
+    let git = program == "git";
+    if program != "gh" && !git {
+        return;
+    }
+    match token() {
+        Ok(Some(token)) => {
+            command.env(GH_TOKEN_VAR, token);
+            if git {
+                add_git_credential_helper(command);
+            }
+        }
+        Ok(None) => {}
+        Err(err) => tracing::warn!(error = %format!("{:#}", err), "no GitHub token for gh/git"),
+    }
+}
+
+/// Answers git's credential requests for `GH_HOST` (default `github.com`) with `GH_TOKEN`. The
+/// empty helper first clears the user's own helpers for that host. Entries go after any
+/// `GIT_CONFIG_COUNT` ones already in the environment.
+fn add_git_credential_helper(command: &mut Command) {
+    let host = std::env::var("GH_HOST").unwrap_or_else(|_| "github.com".to_string());
+    let key = format!("credential.https://{}.helper", host);
+    let first = std::env::var("GIT_CONFIG_COUNT")
+        .ok()
+        .and_then(|count| count.parse::<usize>().ok())
+        .unwrap_or(0);
+    let helpers = [
+        "",
+        "!f() { test \"$1\" = get && echo username=x-access-token && echo \"password=$GH_TOKEN\"; }; f",
+    ];
+    for (offset, helper) in helpers.iter().enumerate() {
+        command.env(format!("GIT_CONFIG_KEY_{}", first + offset), &key);
+        command.env(format!("GIT_CONFIG_VALUE_{}", first + offset), helper);
+    }
+    command.env("GIT_CONFIG_COUNT", (first + helpers.len()).to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, configure, token};
+    use crate::config::AuthConfig;
+    use std::ffi::OsStr;
+    use std::process::Command;
+
+    #[test]
+    fn token_comes_from_the_configured_variable_or_command() {
+        std::env::set_var("REVIEWER_TEST_AUTH_TOKEN", " github_pat_abc\n");
+        configure(&AuthConfig {
+            token_env: Some("REVIEWER_TEST_AUTH_TOKEN".to_string()),
+            ..AuthConfig::default()
+        });
+        assert_eq!(token().unwrap().as_deref(), Some("github_pat_abc"));
+
+        configure(&AuthConfig {
+            token_env: Some("REVIEWER_TEST_AUTH_TOKEN_UNSET".to_string()),
+            ..AuthConfig::default()
+        });
+        assert!(token().is_err());
+
+        configure(&AuthConfig {
+            token_env: Some("REVIEWER_TEST_AUTH_TOKEN".to_string()),
+            token_command: Some("echo ghs_installation".to_string()),
+            ..AuthConfig::default()
+        });
+        assert_eq!(token().unwrap().as_deref(), Some("ghs_installation"));
+
+        let mut git = Command::new("git");
+        git.args(["fetch", "origin"]);
+        apply(&mut git);
+        let envs = git.get_envs().collect::<Vec<_>>();
+        assert!(envs.contains(&(OsStr::new("GH_TOKEN"), Some(OsStr::new("ghs_installation")))));
+        // The helper reads the token from the environment instead of holding it
+        let helper = envs
+            .iter()
+            .filter(|(name, _)| name.to_string_lossy().starts_with("GIT_CONFIG_VALUE_"))
+            .filter_map(|(_, value)| value.map(|value| value.to_string_lossy().into_owned()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(helper.contains("password=$GH_TOKEN"));
+        assert!(!helper.contains("ghs_installation"));
+
+        // Parallel callers share a single run of the command
+        let runs = std::env::temp_dir().join(format!("reviewer-auth-runs-{}", std::process::id()));
+        let _ = std::fs::remove_file(&runs);
+        configure(&AuthConfig {
+            token_command: Some(format!("echo run >> '{}'; echo ghs_once", runs.display())),
+            ..AuthConfig::default()
+        });
+        let tokens = std::thread::scope(|scope| {
+            let handles = (0..8)
+                .map(|_| scope.spawn(|| token().unwrap()))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert!(tokens
+            .iter()
+            .all(|token| token.as_deref() == Some("ghs_once")));
+        assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 1);
+        let _ = std::fs::remove_file(&runs);
+
+        configure(&AuthConfig {
+            token_command: Some("exit 3".to_string()),
+            ..AuthConfig::default()
+        });
+        assert!(token().is_err());
+
+        configure(&AuthConfig::default());
+        assert_eq!(token().unwrap(), None);
+    }
+}
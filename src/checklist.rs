@@ -0,0 +1,67 @@
+/// One checkable line of a repo's review checklist
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub checked: bool,
+}
+
+/// Read `- [ ]`/`- [x]` task items and plain `-`/`*` bullets from a markdown checklist.
+/// Headings and prose are ignored; pre-checked items start checked.
+pub fn parse(markdown: &str) -> Vec<ChecklistItem> {
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let rest = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))?
+                .trim_start();
+            let (checked, text) = if let Some(text) = rest.strip_prefix("[ ]") {
+                (false, text)
+            } else if let Some(text) = rest
+                .strip_prefix("[x]")
+                .or_else(|| rest.strip_prefix("[X]"))
+            {
+                (true, text)
+            } else {
+                (false, rest)
+            };
+            let text = text.trim();
+            (!text.is_empty()).then(|| ChecklistItem {
+                text: text.to_string(),
+                checked,
+            })
+        })
+        .collect()
+}
+
+/// Render the checklist as a markdown task list for a review comment.
+pub fn render_markdown(items: &[ChecklistItem]) -> String {
+    let done = items.iter().filter(|item| item.checked).count();
+    let mut out = format!("Review checklist ({}/{}):\n\n", done, items.len());
+    for item in items {
+        let mark = if item.checked { "x" } else { " " };
+        out.push_str(&format!("- [{}] {}\n", mark, item.text));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, render_markdown};
+
+    #[test]
+    fn parse_reads_task_items_and_bullets() {
+        let markdown = "# Checklist\n\nBefore approving:\n\n- [ ] Tests cover the change\n- [x] Docs updated\n* No secrets in logs\n-  [ ]  \n";
+        let items = parse(markdown);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].text, "Tests cover the change");
+        assert!(!items[0].checked);
+        assert!(items[1].checked);
+        assert_eq!(items[2].text, "No secrets in logs");
+        assert_eq!(
+            render_markdown(&items),
+            "Review checklist (1/3):\n\n- [ ] Tests cover the change\n- [x] Docs updated\n- [ ] No secrets in logs\n"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::drafts::DraftReview;
+use crate::gh::{
+    self, AuthorStats, BranchProtection, ChangedFile, CheckStatus, Comment, Discussion, Label,
+    MergeStatus, PrCommit, PrFeedback, PrTriage, PullRequest, PullRequestPage, RepoPermission,
+    ReviewComment, ReviewEvent, ReviewQueue, ReviewThreadState, SquashMessage,
+};
+use crate::gh_version::{self, Capability};
+use crate::repos::{self, ScanProgress};
+use crate::tui::AppMode;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Which PR list to fetch, and for whom
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListQuery {
+    pub mode: AppMode,
+    pub my_approvals: bool,
+    pub username: String,
+    pub include_drafts: bool,
+    pub exclude_users: Vec<String>,
+    pub repos_root: PathBuf,
+}
+
+/// The GitHub operations the TUI performs. `GhCli` runs them through the `gh` CLI;
+/// tests substitute canned data.
+pub trait GhClient: Send + Sync {
+    /// Whether the installed `gh` has `capability`.
+    fn supports(&self, capability: Capability) -> bool;
+    /// One page of the PR list, starting after `cursor`.
+    fn fetch_page(&self, query: &ListQuery, cursor: Option<&str>) -> PullRequestPage;
+    /// How far the local repo scan behind the `Watching` list is while a page is fetched.
+    fn scan_progress(&self) -> Option<ScanProgress>;
+    fn fetch_pr_details(&self, pr: &PullRequest) -> Result<PullRequest>;
+    fn get_pr_diff(&self, pr: &PullRequest) -> Result<String>;
+    fn get_pr_files(&self, pr: &PullRequest) -> Result<Vec<ChangedFile>>;
+    fn get_pr_file_diff(&self, pr: &PullRequest, path: &str) -> Result<String>;
+    fn get_gitattributes(&self, pr: &PullRequest) -> Result<Option<String>>;
+    fn get_repo_file(&self, pr: &PullRequest, path: &str) -> Result<Option<String>>;
+    fn get_pr_comments(&self, pr: &PullRequest) -> Result<Vec<Comment>>;
+    fn get_review_comments(&self, pr: &PullRequest) -> Result<Vec<ReviewComment>>;
+    /// Thread resolution, keyed by the id of each thread's first review comment.
+    fn get_review_threads(&self, pr: &PullRequest) -> Result<BTreeMap<u64, ReviewThreadState>>;
+    fn set_review_thread_resolved(&self, thread_id: &str, resolved: bool) -> Result<()>;
+    fn get_pr_feedback(&self, pr: &PullRequest) -> Result<PrFeedback>;
+    fn get_pr_checks(&self, pr: &PullRequest) -> Result<Vec<CheckStatus>>;
+    /// Open a page, e.g. a CI check's, in the browser.
+    fn open_url(&self, url: &str) -> Result<()>;
+    fn get_branch_protection(&self, pr: &PullRequest) -> Result<Option<BranchProtection>>;
+    fn get_author_stats(&self, pr: &PullRequest) -> Result<AuthorStats>;
+    fn get_review_queues(&self, logins: &[String]) -> Result<Vec<ReviewQueue>>;
+    /// Comment and unresolved-thread counts, aligned with `prs`.
+    fn get_discussions(&self, prs: &[PullRequest]) -> Result<Vec<Option<Discussion>>>;
+    fn get_pr_triage(&self, pr: &PullRequest) -> Result<PrTriage>;
+    fn get_milestones(&self, pr: &PullRequest) -> Result<Vec<String>>;
+    fn set_milestone(&self, pr: &PullRequest, milestone: Option<&str>) -> Result<()>;
+    fn get_repo_labels(&self, pr: &PullRequest) -> Result<Vec<Label>>;
+    fn edit_labels(&self, pr: &PullRequest, add: &[String], remove: &[String]) -> Result<()>;
+    fn add_to_project(&self, pr: &PullRequest, project: &str) -> Result<()>;
+    fn get_repo_permission(&self, repo_name: &str) -> Result<RepoPermission>;
+    fn request_reviewer(&self, pr: &PullRequest, login: &str) -> Result<()>;
+    fn add_pr_comment(&self, pr: &PullRequest, comment: &str) -> Result<()>;
+    /// Reply to the review thread whose first comment is `comment_id`.
+    fn reply_to_review_comment(
+        &self,
+        pr: &PullRequest,
+        comment_id: u64,
+        comment: &str,
+    ) -> Result<()>;
+    /// `start_line` makes it a multi-line comment ending at `line`.
+    fn add_line_comment(
+        &self,
+        pr: &PullRequest,
+        file_path: &str,
+        start_line: Option<u32>,
+        line: u32,
+        side: &str,
+        comment: &str,
+    ) -> Result<()>;
+    /// Submit queued line comments as one review.
+    fn submit_review(
+        &self,
+        pr: &PullRequest,
+        draft: &DraftReview,
+        event: ReviewEvent,
+    ) -> Result<()>;
+    fn approve_pr(&self, pr: &PullRequest, comment: Option<&str>) -> Result<()>;
+    fn close_pr(&self, pr: &PullRequest, comment: Option<&str>) -> Result<()>;
+    fn check_merge_status(&self, pr: &PullRequest) -> MergeStatus;
+    fn get_pr_commits(&self, pr: &PullRequest) -> Result<Vec<PrCommit>>;
+    fn get_commit_diff(&self, pr: &PullRequest, sha: &str) -> Result<String>;
+    /// `message` replaces GitHub's default squash commit message.
+    fn merge_pr(
+        &self,
+        pr: &PullRequest,
+        delete_branch: bool,
+        message: Option<&SquashMessage>,
+    ) -> Result<String>;
+    fn enable_auto_merge(&self, pr: &PullRequest) -> Result<()>;
+}
+
+/// The real client, shelling out to `gh`
+pub struct GhCli;
+
+impl GhClient for GhCli {
+    fn supports(&self, capability: Capability) -> bool {
+        gh_version::supports(capability)
+    }
+
+    fn fetch_page(&self, query: &ListQuery, cursor: Option<&str>) -> PullRequestPage {
+        let ListQuery {
+            username,
+            include_drafts,
+            exclude_users,
+            ..
+        } = query;
+        match query.mode {
+            AppMode::Review => {
+                crate::fetch_involved_prs(username, *include_drafts, cursor, exclude_users)
+            }
+            AppMode::MyPrs if query.my_approvals => {
+                crate::fetch_my_approvals(username, *include_drafts, cursor, exclude_users)
+            }
+            AppMode::MyPrs => crate::fetch_my_prs(username, *include_drafts, cursor, exclude_users),
+            AppMode::Watching => crate::fetch_watching_prs(
+                &query.repos_root,
+                username,
+                *include_drafts,
+                cursor,
+                exclude_users,
+            ),
+            // Dependency PRs are fetched in one page.
+            AppMode::Dependencies if cursor.is_some() => PullRequestPage::default(),
+            AppMode::Dependencies => crate::fetch_dependency_prs(username, *include_drafts),
+        }
+    }
+
+    fn scan_progress(&self) -> Option<ScanProgress> {
+        repos::scan_progress()
+    }
+
+    fn fetch_pr_details(&self, pr: &PullRequest) -> Result<PullRequest> {
+        gh::fetch_pr_details(pr)
+    }
+
+    fn get_pr_diff(&self, pr: &PullRequest) -> Result<String> {
+        gh::get_pr_diff(pr)
+    }
+
+    fn get_pr_files(&self, pr: &PullRequest) -> Result<Vec<ChangedFile>> {
+        gh::get_pr_files(pr)
+    }
+
+    fn get_pr_file_diff(&self, pr: &PullRequest, path: &str) -> Result<String> {
+        gh::get_pr_file_diff(pr, path)
+    }
+
+    fn get_gitattributes(&self, pr: &PullRequest) -> Result<Option<String>> {
+        gh::get_gitattributes(pr)
+    }
+
+    fn get_repo_file(&self, pr: &PullRequest, path: &str) -> Result<Option<String>> {
+        gh::get_repo_file(pr, path)
+    }
+
+    fn get_pr_comments(&self, pr: &PullRequest) -> Result<Vec<Comment>> {
+        gh::get_pr_comments(pr)
+    }
+
+    fn get_review_comments(&self, pr: &PullRequest) -> Result<Vec<ReviewComment>> {
+        gh::get_review_comments(pr)
+    }
+
+    fn get_review_threads(&self, pr: &PullRequest) -> Result<BTreeMap<u64, ReviewThreadState>> {
+        gh::get_review_threads(pr)
+    }
+
+    fn set_review_thread_resolved(&self, thread_id: &str, resolved: bool) -> Result<()> {
+        gh::set_review_thread_resolved(thread_id, resolved)
+    }
+
+    fn get_pr_feedback(&self, pr: &PullRequest) -> Result<PrFeedback> {
+        gh::get_pr_feedback(pr)
+    }
+
+    fn get_pr_checks(&self, pr: &PullRequest) -> Result<Vec<CheckStatus>> {
+        gh::get_pr_checks(pr)
+    }
+
+    fn open_url(&self, url: &str) -> Result<()> {
+        gh::open_url(url)
+    }
+
+    fn get_branch_protection(&self, pr: &PullRequest) -> Result<Option<BranchProtection>> {
+        gh::get_branch_protection(pr)
+    }
+
+    fn get_author_stats(&self, pr: &PullRequest) -> Result<AuthorStats> {
+        gh::get_author_stats(pr)
+    }
+
+    fn get_review_queues(&self, logins: &[String]) -> Result<Vec<ReviewQueue>> {
+        gh::get_review_queues(logins)
+    }
+
+    fn get_discussions(&self, prs: &[PullRequest]) -> Result<Vec<Option<Discussion>>> {
+        gh::get_discussions(prs)
+    }
+
+    fn get_pr_triage(&self, pr: &PullRequest) -> Result<PrTriage> {
+        gh::get_pr_triage(pr)
+    }
+
+    fn get_milestones(&self, pr: &PullRequest) -> Result<Vec<String>> {
+        gh::get_milestones(pr)
+    }
+
+    fn set_milestone(&self, pr: &PullRequest, milestone: Option<&str>) -> Result<()> {
+        gh::set_milestone(pr, milestone)
+    }
+
+    fn get_repo_labels(&self, pr: &PullRequest) -> Result<Vec<Label>> {
+        gh::get_repo_labels(pr)
+    }
+
+    fn edit_labels(&self, pr: &PullRequest, add: &[String], remove: &[String]) -> Result<()> {
+        gh::edit_labels(pr, add, remove)
+    }
+
+    fn add_to_project(&self, pr: &PullRequest, project: &str) -> Result<()> {
+        gh::add_to_project(pr, project)
+    }
+
+    fn get_repo_permission(&self, repo_name: &str) -> Result<RepoPermission> {
+        gh::get_repo_permission(repo_name)
+    }
+
+    fn request_reviewer(&self, pr: &PullRequest, login: &str) -> Result<()> {
+        gh::request_reviewer(pr, login)
+    }
+
+    fn add_pr_comment(&self, pr: &PullRequest, comment: &str) -> Result<()> {
+        gh::add_pr_comment(pr, comment)
+    }
+
+    fn reply_to_review_comment(
+        &self,
+        pr: &PullRequest,
+        comment_id: u64,
+        comment: &str,
+    ) -> Result<()> {
+        gh::reply_to_review_comment(pr, comment_id, comment)
+    }
+
+    fn add_line_comment(
+        &self,
+        pr: &PullRequest,
+        file_path: &str,
+        start_line: Option<u32>,
+        line: u32,
+        side: &str,
+        comment: &str,
+    ) -> Result<()> {
+        gh::add_line_comment(pr, file_path, start_line, line, side, comment)
+    }
+
+    fn submit_review(
+        &self,
+        pr: &PullRequest,
+        draft: &DraftReview,
+        event: ReviewEvent,
+    ) -> Result<()> {
+        gh::submit_review(pr, draft, event)
+    }
+
+    fn approve_pr(&self, pr: &PullRequest, comment: Option<&str>) -> Result<()> {
+        gh::approve_pr(pr, comment)
+    }
+
+    fn close_pr(&self, pr: &PullRequest, comment: Option<&str>) -> Result<()> {
+        gh::close_pr(pr, comment)
+    }
+
+    fn check_merge_status(&self, pr: &PullRequest) -> MergeStatus {
+        gh::check_merge_status(pr)
+    }
+
+    fn get_pr_commits(&self, pr: &PullRequest) -> Result<Vec<PrCommit>> {
+        gh::get_pr_commits(pr)
+    }
+
+    fn get_commit_diff(&self, pr: &PullRequest, sha: &str) -> Result<String> {
+        gh::get_commit_diff(pr, sha)
+    }
+
+    fn merge_pr(
+        &self,
+        pr: &PullRequest,
+        delete_branch: bool,
+        message: Option<&SquashMessage>,
+    ) -> Result<String> {
+        gh::merge_pr(pr, delete_branch, message)
+    }
+
+    fn enable_auto_merge(&self, pr: &PullRequest) -> Result<()> {
+        gh::enable_auto_merge(pr)
+    }
+}
@@ -0,0 +1,219 @@
+use crate::config::{self, CloneCacheConfig};
+use crate::logging::CommandExt;
+use crate::private_fs;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Written into each clone on use; holds the unix time it was last used, for eviction.
+const LAST_USED_FILE: &str = "reviewer-last-used";
+/// Between new clones, the cache size is rechecked at most this often: it walks every clone.
+const EVICT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+static SETTINGS: RwLock<Option<CloneCacheConfig>> = RwLock::new(None);
+/// Serializes clones and evictions, so two background threads never clone the same repo. Holds
+/// when the cache size was last checked.
+static CACHE_LOCK: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Apply the `clone_cache` config section; called at startup and on config reload.
+pub fn configure(config: &CloneCacheConfig) {
+    *SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = Some(config.clone());
+}
+
+fn settings() -> CloneCacheConfig {
+    SETTINGS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+pub fn enabled() -> bool {
+    settings().enabled
+}
+
+/// Where managed bare clones live
+fn cache_dir() -> PathBuf {
+    config::config_dir().join("clones")
+}
+
+/// A bare clone of `repo_name` (`owner/name`) for repos with no checkout under the repos root,
+/// cloned on first use. Least recently used clones are evicted beyond `max_size_mb`, checked
+/// after each new clone and otherwise every [`EVICT_INTERVAL`].
+pub fn repo_path(repo_name: &str) -> Result<PathBuf> {
+    let settings = settings();
+    if !settings.enabled {
+        anyhow::bail!("clone_cache is disabled");
+    }
+    let (owner, name) = repo_name
+        .split_once('/')
+        .with_context(|| format!("Invalid repo name: {}", repo_name))?;
+
+    let mut last_evicted = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let root = cache_dir();
+    let path = root.join(owner).join(format!("{}.git", name));
+    let cloned = !path.exists();
+    if cloned {
+        std::fs::create_dir_all(root.join(owner))?;
+        let output = Command::new("gh")
+            .args(["repo", "clone", repo_name])
+            .arg(&path)
+            .args(["--", "--bare"])
+            .logged_output()
+            .with_context(|| format!("Failed to clone {}", repo_name))?;
+        if !output.status.success() {
+            let _ = std::fs::remove_dir_all(&path);
+            anyhow::bail!(
+                "Failed to clone {}: {}",
+                repo_name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    mark_used(&path)?;
+    if eviction_due(cloned, &mut last_evicted, Instant::now()) {
+        for evicted in evict(&root, settings.max_size_mb * 1024 * 1024, &path) {
+            tracing::info!(path = %evicted.display(), "evicted cached clone");
+        }
+    }
+    Ok(path)
+}
+
+/// Whether to check the cache size now: right after a clone grew it, or when the last check
+/// is over [`EVICT_INTERVAL`] old. Records the check in `last`.
+fn eviction_due(cloned: bool, last: &mut Option<Instant>, now: Instant) -> bool {
+    let due = cloned || last.is_none_or(|last| now.duration_since(last) >= EVICT_INTERVAL);
+    if due {
+        *last = Some(now);
+    }
+    due
+}
+
+fn mark_used(path: &Path) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+    private_fs::write(&path.join(LAST_USED_FILE), now.to_string())
+        .with_context(|| format!("Failed to update {}", path.display()))
+}
+
+fn last_used(path: &Path) -> i64 {
+    std::fs::read_to_string(path.join(LAST_USED_FILE))
+        .ok()
+        .and_then(|text| text.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|meta| meta.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// The `<owner>/<name>.git` clones under `root`
+fn cached_clones(root: &Path) -> Vec<PathBuf> {
+    let Ok(owners) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    owners
+        .filter_map(|entry| entry.ok())
+        .filter_map(|owner| std::fs::read_dir(owner.path()).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.extension().is_some_and(|ext| ext == "git"))
+        .collect()
+}
+
+/// Remove least recently used clones until the cache fits in `max_bytes`, never touching `keep`.
+fn evict(root: &Path, max_bytes: u64, keep: &Path) -> Vec<PathBuf> {
+    let mut clones: Vec<(i64, u64, PathBuf)> = cached_clones(root)
+        .into_iter()
+        .map(|path| (last_used(&path), dir_size(&path), path))
+        .collect();
+    let mut total: u64 = clones.iter().map(|(_, size, _)| size).sum();
+    clones.sort();
+
+    let mut evicted = Vec::new();
+    for (_, size, path) in clones {
+        if total <= max_bytes {
+            break;
+        }
+        if path == keep {
+            continue;
+        }
+        if std::fs::remove_dir_all(&path).is_ok() {
+            total = total.saturating_sub(size);
+            evicted.push(path);
+        }
+    }
+    evicted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evict, eviction_due, EVICT_INTERVAL, LAST_USED_FILE};
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, Instant};
+
+    fn fake_clone(root: &Path, repo: &str, last_used: i64, bytes: usize) -> PathBuf {
+        let path = root.join(format!("{}.git", repo));
+        std::fs::create_dir_all(path.join("objects")).unwrap();
+        std::fs::write(path.join("objects").join("pack"), vec![0u8; bytes]).unwrap();
+        std::fs::write(path.join(LAST_USED_FILE), last_used.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn eviction_runs_after_a_clone_or_once_per_interval() {
+        let start = Instant::now();
+        let mut last = None;
+        assert!(eviction_due(false, &mut last, start));
+        assert!(!eviction_due(
+            false,
+            &mut last,
+            start + Duration::from_secs(60)
+        ));
+        assert!(eviction_due(
+            true,
+            &mut last,
+            start + Duration::from_secs(120)
+        ));
+        assert!(!eviction_due(false, &mut last, start + EVICT_INTERVAL));
+        assert!(eviction_due(
+            false,
+            &mut last,
+            start + Duration::from_secs(120) + EVICT_INTERVAL
+        ));
+    }
+
+    #[test]
+    fn evict_removes_least_recently_used_clones_over_the_limit() {
+        let root =
+            std::env::temp_dir().join(format!("reviewer-clone-cache-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let oldest = fake_clone(&root, "acme/widgets", 100, 4000);
+        let older = fake_clone(&root, "acme/gadgets", 200, 4000);
+        let newest = fake_clone(&root, "other/tools", 300, 4000);
+        let current = fake_clone(&root, "acme/legacy", 50, 4000);
+
+        // Everything fits: nothing is evicted.
+        assert!(evict(&root, 1_000_000, &current).is_empty());
+
+        // `current` is the oldest but was just used, so it stays.
+        let evicted = evict(&root, 9000, &current);
+        assert_eq!(evicted, vec![oldest.clone(), older.clone()]);
+        assert!(!oldest.exists() && !older.exists());
+        assert!(newest.exists() && current.exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,7 +1,9 @@
-use anyhow::{Context, Result};
+use crate::private_fs;
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 fn default_poll_interval_sec() -> u64 {
@@ -41,6 +43,82 @@ impl Default for AiLaunchTmuxConfig {
     }
 }
 
+/// Settings for the `terminal` launch backend, which opens the AI tool in a terminal emulator.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AiLaunchTerminalConfig {
+    /// Terminal app. Defaults to Terminal on macOS; on Linux to `$TERMINAL` or the first
+    /// known terminal on `PATH`.
+    pub app: Option<String>,
+    /// Launch mode: auto, new-instance, same-space, new-tab or new-window.
+    pub mode: Option<String>,
+}
+
+fn default_api_max_tokens() -> u32 {
+    4096
+}
+
+fn default_api_timeout_sec() -> u64 {
+    300
+}
+
+/// Settings for the `api` launch backend, which calls an LLM HTTP API directly.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AiLaunchApiConfig {
+    /// Request format: "anthropic" (default) or "openai" (any OpenAI-compatible endpoint).
+    pub format: Option<String>,
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    /// Environment variable holding the API key.
+    pub api_key_env: Option<String>,
+    #[serde(default = "default_api_max_tokens")]
+    pub max_tokens: u32,
+    /// Longest a request may take before it is abandoned, so a stalled endpoint can't hold up a
+    /// daemon poll.
+    #[serde(default = "default_api_timeout_sec")]
+    pub timeout_sec: u64,
+}
+
+impl Default for AiLaunchApiConfig {
+    fn default() -> Self {
+        Self {
+            format: None,
+            base_url: None,
+            model: None,
+            api_key_env: None,
+            max_tokens: default_api_max_tokens(),
+            timeout_sec: default_api_timeout_sec(),
+        }
+    }
+}
+
+impl AiLaunchApiConfig {
+    pub fn format_key(&self) -> &str {
+        self.format.as_deref().unwrap_or("anthropic")
+    }
+
+    pub fn base_url(&self) -> String {
+        if let Some(base_url) = &self.base_url {
+            return base_url.trim_end_matches('/').to_string();
+        }
+        match self.format_key() {
+            "openai" => "https://api.openai.com/v1".to_string(),
+            _ => "https://api.anthropic.com/v1".to_string(),
+        }
+    }
+
+    pub fn api_key_env(&self) -> String {
+        if let Some(env) = &self.api_key_env {
+            return env.clone();
+        }
+        match self.format_key() {
+            "openai" => "OPENAI_API_KEY".to_string(),
+            _ => "ANTHROPIC_API_KEY".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct AiLaunchConfig {
@@ -48,6 +126,10 @@ pub struct AiLaunchConfig {
     #[serde(default)]
     pub tmux: AiLaunchTmuxConfig,
     #[serde(default)]
+    pub terminal: AiLaunchTerminalConfig,
+    #[serde(default)]
+    pub api: AiLaunchApiConfig,
+    #[serde(default)]
     pub steps: Vec<AiLaunchStepConfig>,
     #[serde(default)]
     pub self_review_steps: Vec<AiLaunchStepConfig>,
@@ -62,8 +144,16 @@ impl AiLaunchConfig {
         self.backend_key() == "tmux"
     }
 
+    pub fn uses_api(&self) -> bool {
+        self.backend_key() == "api"
+    }
+
+    pub fn uses_terminal(&self) -> bool {
+        self.backend_key() == "terminal"
+    }
+
     pub fn is_configured(&self) -> bool {
-        self.uses_tmux() || !self.steps.is_empty()
+        self.uses_tmux() || self.uses_api() || self.uses_terminal() || !self.steps.is_empty()
     }
 }
 
@@ -77,7 +167,32 @@ pub struct AiConfig {
     pub skill: Option<String>,
     pub prompt_template: Option<String>,
     #[serde(default)]
+    pub repo_prompt_templates: Vec<RepoPromptTemplate>,
+    #[serde(default)]
     pub launch: AiLaunchConfig,
+    #[serde(default)]
+    pub profiles: Vec<AiProfile>,
+}
+
+/// A named alternative to the `ai` provider settings, offered when re-running a review with
+/// `Ctrl+r`. Unset fields keep the `ai` values.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AiProfile {
+    pub name: String,
+    pub provider: Option<String>,
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub skill: Option<String>,
+    pub prompt_template: Option<String>,
+}
+
+/// Prompt template used instead of `ai.prompt_template` for matching repos.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RepoPromptTemplate {
+    pub repo: String,
+    pub prompt_template: String,
 }
 
 impl AiConfig {
@@ -103,11 +218,72 @@ impl AiConfig {
         }
     }
 
+    /// These settings with `profile` layered on top. Switching provider drops the base
+    /// command and args, which belong to the old provider.
+    pub fn with_profile(&self, profile: &AiProfile) -> AiConfig {
+        let mut ai = self.clone();
+        if let Some(provider) = &profile.provider {
+            if provider != self.provider_key() {
+                ai.command = None;
+                ai.args.clear();
+            }
+            ai.provider = Some(provider.clone());
+        }
+        if let Some(command) = &profile.command {
+            ai.command = Some(command.clone());
+        }
+        if let Some(args) = &profile.args {
+            ai.args = args.clone();
+        }
+        if let Some(skill) = &profile.skill {
+            ai.skill = Some(skill.clone());
+        }
+        if let Some(prompt_template) = &profile.prompt_template {
+            ai.prompt_template = Some(prompt_template.clone());
+        }
+        ai
+    }
+
+    /// What a review can be re-run with, labelled: these settings, each profile, then the
+    /// built-in providers not already covered.
+    pub fn choices(&self) -> Vec<(String, AiConfig)> {
+        let mut choices = vec![(format!("{} (default)", self.display_name()), self.clone())];
+        for profile in &self.profiles {
+            let ai = self.with_profile(profile);
+            choices.push((format!("{}: {}", profile.name, ai.display_name()), ai));
+        }
+        for provider in ["claude", "codex"] {
+            if choices.iter().any(|(_, ai)| ai.provider_key() == provider) {
+                continue;
+            }
+            let ai = self.with_profile(&AiProfile {
+                provider: Some(provider.to_string()),
+                ..AiProfile::default()
+            });
+            choices.push((ai.display_name(), ai));
+        }
+        choices
+    }
+
     pub fn skill_name(&self) -> String {
         self.skill
             .clone()
             .unwrap_or_else(|| "code-review".to_string())
     }
+
+    /// Prompt template for a repo: the first matching `repo_prompt_templates`
+    /// entry, falling back to `prompt_template`.
+    pub fn prompt_template_for(&self, repo: &str) -> Option<&str> {
+        let repo = repo.trim().to_lowercase();
+        self.repo_prompt_templates
+            .iter()
+            .find(|entry| {
+                let pattern = entry.repo.trim().to_lowercase();
+                !pattern.is_empty() && crate::filters::wildcard_match(&pattern, &repo)
+            })
+            .map(|entry| entry.prompt_template.as_str())
+            .or(self.prompt_template.as_deref())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -117,37 +293,1187 @@ pub struct AutoApproveRule {
     pub user: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DaemonConfig {
+    #[serde(default = "default_poll_interval_sec")]
+    pub poll_interval_sec: u64,
+    #[serde(default)]
+    pub exclude_repos: Vec<String>,
+    #[serde(default)]
+    pub initialized: bool,
+    #[serde(default)]
+    pub include_drafts: bool,
+    #[serde(default)]
+    pub repo_subpath_filters: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub auto_approve: Vec<AutoApproveRule>,
+    #[serde(default = "default_only_new_prs_on_start")]
+    pub only_new_prs_on_start: bool,
+    #[serde(default)]
+    pub triage: TriageConfig,
+    #[serde(default)]
+    pub batch: BatchConfig,
+    #[serde(default)]
+    pub escalation: EscalationConfig,
+}
+
+/// What the daemon does about a PR that has waited too long for a first review.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationAction {
+    /// Post the nudge as a PR comment.
+    #[default]
+    Comment,
+    /// Show the nudge as a desktop notification only.
+    Notify,
+}
+
+/// Nudge PRs that have gone unreviewed for too long, in opted-in repos only.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EscalationConfig {
+    /// `owner/repo` patterns (`*`/`?` wildcards) to escalate in; empty turns escalation off.
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// Hours since the PR opened (or since the last nudge) before nudging.
+    #[serde(default = "default_escalation_after_hours")]
+    pub after_hours: u64,
+    #[serde(default)]
+    pub action: EscalationAction,
+    /// Nudge text with `{age}`, `{reviewers}`, `{author}`, `{title}` and `{url}` placeholders.
+    #[serde(default = "default_escalation_template")]
+    pub template: String,
+    /// Nudges per PR, ever.
+    #[serde(default = "default_escalation_max_per_pr")]
+    pub max_per_pr: u32,
+    /// Nudges per poll across all PRs.
+    #[serde(default = "default_escalation_max_per_poll")]
+    pub max_per_poll: usize,
+}
+
+fn default_escalation_after_hours() -> u64 {
+    48
+}
+
+fn default_escalation_template() -> String {
+    "Friendly nudge for {reviewers}: this PR has been waiting {age} for a first review. \
+     Could you take a look when you get a chance? Thanks!"
+        .to_string()
+}
+
+fn default_escalation_max_per_pr() -> u32 {
+    1
+}
+
+fn default_escalation_max_per_poll() -> usize {
+    3
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            repos: Vec::new(),
+            after_hours: default_escalation_after_hours(),
+            action: EscalationAction::default(),
+            template: default_escalation_template(),
+            max_per_pr: default_escalation_max_per_pr(),
+            max_per_poll: default_escalation_max_per_poll(),
+        }
+    }
+}
+
+impl EscalationConfig {
+    pub fn applies_to(&self, repo: &str) -> bool {
+        let repo = repo.trim().to_lowercase();
+        self.repos.iter().any(|pattern| {
+            let pattern = pattern.trim().to_lowercase();
+            !pattern.is_empty() && crate::filters::wildcard_match(&pattern, &repo)
+        })
+    }
+}
+
+/// Queue new reviews and launch them together at fixed local times.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BatchConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Local `HH:MM` times at which the queue is drained.
+    #[serde(default)]
+    pub times: Vec<String>,
+}
+
+impl BatchConfig {
+    pub fn parsed_times(&self) -> Result<Vec<chrono::NaiveTime>> {
+        self.times
+            .iter()
+            .map(|time| {
+                chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M").with_context(|| {
+                    format!("Invalid daemon.batch time '{}'. Expected HH:MM.", time)
+                })
+            })
+            .collect()
+    }
+}
+
+fn default_trivial_max_lines() -> u64 {
+    5
+}
+
+fn default_trivial_paths() -> Vec<String> {
+    ["*.md", "*.rst", "*.txt", "docs/*", "LICENSE*", "CODEOWNERS"]
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// Cheap pre-screen run by the daemon before launching a full review.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TriageConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// PRs with at most this many changed lines are trivial.
+    #[serde(default = "default_trivial_max_lines")]
+    pub trivial_max_lines: u64,
+    /// PRs that only touch files matching these globs are trivial.
+    #[serde(default = "default_trivial_paths")]
+    pub trivial_paths: Vec<String>,
+    /// Small model asked about PRs the heuristics do not mark trivial, using the
+    /// `ai.launch.api` format, endpoint, and key.
+    pub model: Option<String>,
+}
+
+impl Default for TriageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trivial_max_lines: default_trivial_max_lines(),
+            trivial_paths: default_trivial_paths(),
+            model: None,
+        }
+    }
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_sec: default_poll_interval_sec(),
+            exclude_repos: Vec::new(),
+            initialized: false,
+            include_drafts: false,
+            repo_subpath_filters: HashMap::new(),
+            auto_approve: Vec::new(),
+            only_new_prs_on_start: default_only_new_prs_on_start(),
+            triage: TriageConfig::default(),
+            batch: BatchConfig::default(),
+            escalation: EscalationConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ConfirmConfig {
+    #[serde(default = "default_true")]
+    pub approve: bool,
+    #[serde(default = "default_true")]
+    pub merge: bool,
+    #[serde(default = "default_true")]
+    pub close: bool,
+    /// Repo patterns (`owner/name`, supports `*`/`?`) where merge requires typing the PR number.
+    #[serde(default)]
+    pub typed_merge_repos: Vec<String>,
+    /// Canned comments offered (with `Tab`) in the close dialog.
+    #[serde(default = "default_close_reasons")]
+    pub close_reasons: Vec<String>,
+}
+
+fn default_close_reasons() -> Vec<String> {
+    [
+        "Superseded by #",
+        "Closing as stale. Please reopen when it is ready for review again.",
+        "Closing as this is out of scope for the project.",
+    ]
+    .iter()
+    .map(|reason| reason.to_string())
+    .collect()
+}
+
+impl Default for ConfirmConfig {
+    fn default() -> Self {
+        Self {
+            approve: true,
+            merge: true,
+            close: true,
+            typed_merge_repos: Vec::new(),
+            close_reasons: default_close_reasons(),
+        }
+    }
+}
+
+impl ConfirmConfig {
+    pub fn merge_requires_typed(&self, repo: &str) -> bool {
+        let repo = repo.trim().to_lowercase();
+        self.typed_merge_repos.iter().any(|pattern| {
+            let pattern = pattern.trim().to_lowercase();
+            !pattern.is_empty() && crate::filters::wildcard_match(&pattern, &repo)
+        })
+    }
+}
+
+fn default_large_pr_lines() -> u64 {
+    10_000
+}
+
+fn default_context_step() -> usize {
+    10
+}
+
+fn default_generated_files() -> Vec<String> {
+    [
+        "*.lock",
+        "package-lock.json",
+        "pnpm-lock.yaml",
+        "go.sum",
+        "*.pb.go",
+        "*_pb2.py",
+        "*_pb2.pyi",
+        "*.pb.h",
+        "*.pb.cc",
+        "*.min.js",
+        "*.min.css",
+        "**/vendor/**",
+    ]
+    .iter()
+    .map(|pattern| pattern.to_string())
+    .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DiffConfig {
+    /// Collapse generated files into a one-line stub in the TUI diff view.
+    #[serde(default = "default_true")]
+    pub collapse_generated: bool,
+    /// File globs treated as generated, in addition to `linguist-generated` in `.gitattributes`.
+    #[serde(default = "default_generated_files")]
+    pub generated_files: Vec<String>,
+    /// PRs with more changed lines than this list files first and load each diff on demand (0 disables).
+    #[serde(default = "default_large_pr_lines")]
+    pub large_pr_lines: u64,
+    /// Context lines added around each hunk per press of `e` in the Diff tab.
+    #[serde(default = "default_context_step")]
+    pub context_step: usize,
+}
+
+impl Default for DiffConfig {
+    fn default() -> Self {
+        Self {
+            collapse_generated: true,
+            generated_files: default_generated_files(),
+            large_pr_lines: default_large_pr_lines(),
+            context_step: default_context_step(),
+        }
+    }
+}
+
+impl DiffConfig {
+    pub fn is_large_pr(&self, changed_lines: u64) -> bool {
+        self.large_pr_lines > 0 && changed_lines > self.large_pr_lines
+    }
+}
+
+fn default_checklist_path() -> String {
+    ".github/review_checklist.md".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ChecklistConfig {
+    /// Markdown checklist read from the PR head of each repo.
+    #[serde(default = "default_checklist_path")]
+    pub path: String,
+    /// Include the checked state in the approval comment.
+    #[serde(default)]
+    pub include_in_review: bool,
+}
+
+impl Default for ChecklistConfig {
+    fn default() -> Self {
+        Self {
+            path: default_checklist_path(),
+            include_in_review: false,
+        }
+    }
+}
+
+fn default_queue_cache_sec() -> u64 {
+    300
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TeamConfig {
+    /// Teammates offered when balancing review requests (GitHub logins).
+    #[serde(default)]
+    pub members: Vec<String>,
+    /// How long per-teammate review queue counts are reused before searching again.
+    #[serde(default = "default_queue_cache_sec")]
+    pub queue_cache_sec: u64,
+}
+
+impl Default for TeamConfig {
+    fn default() -> Self {
+        Self {
+            members: Vec::new(),
+            queue_cache_sec: default_queue_cache_sec(),
+        }
+    }
+}
+
+fn default_clone_cache_max_size_mb() -> u64 {
+    5120
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CloneCacheConfig {
+    /// Keep bare clones under the config dir for repos with no checkout under `repos_root`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Least recently used clones are removed once the cache grows past this size.
+    #[serde(default = "default_clone_cache_max_size_mb")]
+    pub max_size_mb: u64,
+}
+
+impl Default for CloneCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_size_mb: default_clone_cache_max_size_mb(),
+        }
+    }
+}
+
+fn default_token_refresh_min() -> u64 {
+    50
+}
+
+/// Where `gh` gets its token when `gh auth login` isn't an option (CI, containers, bots).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Environment variable holding a token (classic or fine-grained PAT), e.g. `REVIEWER_TOKEN`.
+    pub token_env: Option<String>,
+    /// Shell command printing a token, e.g. a script minting GitHub App installation tokens.
+    /// Takes precedence over `token_env`.
+    pub token_command: Option<String>,
+    /// How long a token from `token_command` is reused before the command runs again.
+    #[serde(default = "default_token_refresh_min")]
+    pub token_refresh_min: u64,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            token_env: None,
+            token_command: None,
+            token_refresh_min: default_token_refresh_min(),
+        }
+    }
+}
+
+/// What happens to a PR in the list once I approve it
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovedPrs {
+    /// Drop it from the list right away.
+    #[default]
+    Remove,
+    /// Keep it in place, greyed out.
+    Dim,
+    /// Move it to an approved section at the bottom of the list.
+    Section,
+}
+
+/// What the list is sorted by, cycled with `O` in the list.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ListSort {
+    /// Most recently updated first.
+    #[default]
+    Updated,
+    /// Most recently opened first.
+    Created,
+    /// Fewest changed lines first.
+    Size,
+    /// Alphabetically by `owner/repo`.
+    Repo,
+    /// Awaiting review first, then changes requested, approved and drafts.
+    ReviewState,
+}
+
+impl ListSort {
+    pub const ALL: [ListSort; 5] = [
+        ListSort::Updated,
+        ListSort::Created,
+        ListSort::Size,
+        ListSort::Repo,
+        ListSort::ReviewState,
+    ];
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|sort| *sort == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ListSort::Updated => "updated",
+            ListSort::Created => "created",
+            ListSort::Size => "size",
+            ListSort::Repo => "repo",
+            ListSort::ReviewState => "review state",
+        }
+    }
+}
+
+/// One `list.order` rule. PRs the rule ranks higher go first; ties fall through to the next rule.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum ListOrderRule {
+    /// `label:<pattern>`: PRs with a matching label first.
+    Label(String),
+    /// `author:<pattern>`: PRs by a matching author first.
+    Author(String),
+    /// `repo:<pattern>`: PRs in a matching `owner/repo` first.
+    Repo(String),
+    /// `teammates`: PRs by `team.members` first.
+    Teammates,
+    /// `smallest`: fewest changed lines first.
+    Smallest,
+    /// `largest`: most changed lines first.
+    Largest,
+    /// `oldest`: earliest opened first.
+    Oldest,
+    /// `newest`: latest opened first.
+    Newest,
+}
+
+impl TryFrom<String> for ListOrderRule {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+        let pattern = |rest: &str| -> Result<String, String> {
+            let rest = rest.trim();
+            if rest.is_empty() {
+                Err(format!("list.order rule '{trimmed}' needs a pattern"))
+            } else {
+                Ok(rest.to_string())
+            }
+        };
+        if let Some(rest) = trimmed.strip_prefix("label:") {
+            return pattern(rest).map(Self::Label);
+        }
+        if let Some(rest) = trimmed.strip_prefix("author:") {
+            return pattern(rest.trim().trim_start_matches('@')).map(Self::Author);
+        }
+        if let Some(rest) = trimmed.strip_prefix("repo:") {
+            return pattern(rest).map(Self::Repo);
+        }
+        match trimmed {
+            "teammates" => Ok(Self::Teammates),
+            "smallest" => Ok(Self::Smallest),
+            "largest" => Ok(Self::Largest),
+            "oldest" => Ok(Self::Oldest),
+            "newest" => Ok(Self::Newest),
+            other => Err(format!(
+                "Unknown list.order rule '{other}'. Expected label:<pattern>, author:<pattern>, \
+                 repo:<pattern>, teammates, smallest, largest, oldest or newest"
+            )),
+        }
+    }
+}
+
+impl From<ListOrderRule> for String {
+    fn from(rule: ListOrderRule) -> Self {
+        match rule {
+            ListOrderRule::Label(pattern) => format!("label:{pattern}"),
+            ListOrderRule::Author(pattern) => format!("author:{pattern}"),
+            ListOrderRule::Repo(pattern) => format!("repo:{pattern}"),
+            ListOrderRule::Teammates => "teammates".to_string(),
+            ListOrderRule::Smallest => "smallest".to_string(),
+            ListOrderRule::Largest => "largest".to_string(),
+            ListOrderRule::Oldest => "oldest".to_string(),
+            ListOrderRule::Newest => "newest".to_string(),
+        }
+    }
+}
+
+/// The `list.filter` typed after `f` in the list: space-separated terms that must all hold.
+/// Repeating a key (`repo:api repo:web`) accepts either value.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct ListFilter {
+    /// `repo:<pattern>`: `owner/repo` patterns, `*`/`?` wildcards.
+    pub repos: Vec<String>,
+    /// `author:<pattern>`: author logins, `@` optional.
+    pub authors: Vec<String>,
+    /// `label:<pattern>`: label names.
+    pub labels: Vec<String>,
+    /// `draft:yes` or `draft:no`.
+    pub draft: Option<bool>,
+    /// `size:<N`: fewer than N changed lines.
+    pub below_lines: Option<u64>,
+    /// `size:>N`: more than N changed lines.
+    pub above_lines: Option<u64>,
+}
+
+impl ListFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl TryFrom<String> for ListFilter {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut filter = Self::default();
+        for term in value.split_whitespace() {
+            let Some((key, rest)) = term.split_once(':').filter(|(_, rest)| !rest.is_empty())
+            else {
+                return Err(format!(
+                    "Filter term '{term}' needs a key and a value, e.g. repo:acme/* or size:<200"
+                ));
+            };
+            let lines = |limit: &str| {
+                limit
+                    .parse::<u64>()
+                    .map_err(|_| format!("Filter term '{term}' needs a line count"))
+            };
+            match key {
+                "repo" => filter.repos.push(rest.to_string()),
+                "author" => filter
+                    .authors
+                    .push(rest.trim_start_matches('@').to_string()),
+                "label" => filter.labels.push(rest.to_string()),
+                "draft" => {
+                    filter.draft = Some(match rest {
+                        "yes" => true,
+                        "no" => false,
+                        _ => return Err(format!("Filter term '{term}' must be draft:yes or draft:no")),
+                    })
+                }
+                "size" => match (rest.strip_prefix('<'), rest.strip_prefix('>')) {
+                    (Some(limit), _) => filter.below_lines = Some(lines(limit)?),
+                    (_, Some(limit)) => filter.above_lines = Some(lines(limit)?),
+                    _ => return Err(format!("Filter term '{term}' must be size:<N or size:>N")),
+                },
+                other => {
+                    return Err(format!(
+                        "Unknown filter key '{other}'. Expected repo:, author:, label:, draft: or size:"
+                    ))
+                }
+            }
+        }
+        Ok(filter)
+    }
+}
+
+impl From<ListFilter> for String {
+    fn from(filter: ListFilter) -> Self {
+        let mut terms: Vec<String> = Vec::new();
+        terms.extend(filter.repos.iter().map(|repo| format!("repo:{repo}")));
+        terms.extend(
+            filter
+                .authors
+                .iter()
+                .map(|author| format!("author:{author}")),
+        );
+        terms.extend(filter.labels.iter().map(|label| format!("label:{label}")));
+        if let Some(draft) = filter.draft {
+            terms.push(format!("draft:{}", if draft { "yes" } else { "no" }));
+        }
+        if let Some(lines) = filter.above_lines {
+            terms.push(format!("size:>{lines}"));
+        }
+        if let Some(lines) = filter.below_lines {
+            terms.push(format!("size:<{lines}"));
+        }
+        terms.join(" ")
+    }
+}
+
+impl std::fmt::Display for ListFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(self.clone()))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ListConfig {
+    #[serde(default)]
+    pub approved: ApprovedPrs,
+    /// What every list is sorted by; `list.order` rules and views rank above it.
+    #[serde(default)]
+    pub sort: ListSort,
+    /// How the Review list is sorted. Empty keeps the `sort` order.
+    #[serde(default)]
+    pub order: Vec<ListOrderRule>,
+    /// Show each repo's PRs together under a header, toggled with `r`.
+    #[serde(default)]
+    pub group_by_repo: bool,
+    /// Show the highlighted PR's description and diff stat next to the list, toggled with `p`.
+    #[serde(default)]
+    pub preview: bool,
+    /// Bot authors hidden from the Review list until `B` reveals them (`*`/`?` wildcards;
+    /// `dependabot`, `app/dependabot` and `dependabot[bot]` are the same author).
+    #[serde(default = "default_list_bots")]
+    pub bots: Vec<String>,
+    /// Filter the list starts with, set with `f`, e.g. `repo:acme/api author:bob`.
+    #[serde(default)]
+    pub filter: ListFilter,
+}
+
+fn default_list_bots() -> Vec<String> {
+    ["dependabot[bot]", "renovate[bot]", "github-actions[bot]"]
+        .iter()
+        .map(|bot| bot.to_string())
+        .collect()
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        Self {
+            approved: ApprovedPrs::default(),
+            sort: ListSort::default(),
+            order: Vec::new(),
+            group_by_repo: false,
+            preview: false,
+            bots: default_list_bots(),
+            filter: ListFilter::default(),
+        }
+    }
+}
+
+/// Per-repo exceptions to `--drafts`/`daemon.include_drafts`, for teams that review through draft
+/// PRs (or never want theirs shown).
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DraftsConfig {
+    /// `owner/repo` patterns (`*`/`?` wildcards) whose drafts are always included.
+    #[serde(default)]
+    pub always: Vec<String>,
+    /// `owner/repo` patterns whose drafts are never included; wins over `always`.
+    #[serde(default)]
+    pub never: Vec<String>,
+}
+
+impl DraftsConfig {
+    /// Whether drafts in `repo_name` are included, given the global `default`.
+    pub fn include_for(&self, repo_name: &str, default: bool) -> bool {
+        let repo_name = repo_name.to_ascii_lowercase();
+        let matches = |patterns: &[String]| {
+            patterns.iter().any(|pattern| {
+                crate::filters::wildcard_match(&pattern.trim().to_ascii_lowercase(), &repo_name)
+            })
+        };
+        if matches(&self.never) {
+            false
+        } else {
+            default || matches(&self.always)
+        }
+    }
+
+    /// Whether drafts must be fetched at all, because some repo may include them.
+    pub fn any_included(&self, default: bool) -> bool {
+        default || !self.always.is_empty()
+    }
+}
+
+/// Timezone timestamps are shown in
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DateTimezone {
+    #[default]
+    Local,
+    Utc,
+}
+
+/// How ages in the list and badges are shown
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DateStyle {
+    /// `3d`, `2h ago`
+    #[default]
+    Relative,
+    /// The timestamp itself, in `dates.format`
+    Absolute,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DatesConfig {
+    #[serde(default)]
+    pub timezone: DateTimezone,
+    /// Starting style for list ages; `T` toggles it for the session.
+    #[serde(default)]
+    pub style: DateStyle,
+    /// strftime pattern for timestamps, e.g. `%d.%m.%Y %H:%M` or `%m/%d/%Y %I:%M %p`.
+    #[serde(default = "default_date_format")]
+    pub format: String,
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d %H:%M".to_string()
+}
+
+impl Default for DatesConfig {
+    fn default() -> Self {
+        Self {
+            timezone: DateTimezone::default(),
+            style: DateStyle::default(),
+            format: default_date_format(),
+        }
+    }
+}
+
+/// List tab a saved view opens on
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewTab {
+    #[default]
+    Review,
+    MyPrs,
+    Watching,
+    Dependencies,
+}
+
+/// A named list preset, picked with `V` in the list or `--view <name>`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ViewConfig {
+    pub name: String,
+    #[serde(default)]
+    pub tab: ViewTab,
+    /// Only PRs in a matching `owner/repo` (`*`/`?` wildcards); empty shows every repo.
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// Only PRs with at least one matching label; empty shows every PR.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Sort rules used instead of `list.order`, on any tab.
+    pub order: Option<Vec<ListOrderRule>>,
+    /// Include or exclude drafts regardless of `--drafts`.
+    pub drafts: Option<bool>,
+}
+
+/// Filters passed to GitHub with every PR query, so unrelated PRs are never downloaded.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FetchConfig {
+    /// Only fetch PRs targeting this base branch.
+    pub base: Option<String>,
+    /// Only fetch PRs carrying all of these labels.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only fetch PRs opened by one of these logins.
+    #[serde(default)]
+    pub authors: Vec<String>,
+}
+
+/// Actions `keys.list` can rebind, with the key each one is on by default.
+pub const LIST_KEY_ACTIONS: &[(&str, &str)] = &[
+    ("quit", "q"),
+    ("next_tab", "tab"),
+    ("prev_tab", "shift+tab"),
+    ("down", "j"),
+    ("up", "k"),
+    ("page_down", "ctrl+d"),
+    ("page_up", "ctrl+u"),
+    ("first", "g"),
+    ("last", "G"),
+    ("open", "enter"),
+    ("refresh", "R"),
+    ("group_by_repo", "r"),
+    ("fold_repo", "space"),
+    ("preview", "p"),
+    ("drafts", "d"),
+    ("bots", "B"),
+    ("summary", "S"),
+    ("date_style", "T"),
+    ("snooze", "z"),
+    ("show_snoozed", "Z"),
+    ("assign", "w"),
+    ("my_approvals", "v"),
+    ("views", "V"),
+    ("nudge", "b"),
+    ("group_approve", "a"),
+    ("search_all", "s"),
+    ("search", "/"),
+    ("filter", "f"),
+    ("sort", "O"),
+    ("next_match", "n"),
+    ("prev_match", "N"),
+    ("browser", "o"),
+    ("copy_url", "y"),
+    ("messages", "!"),
+    ("remove_closed", "X"),
+];
+
+/// Actions `keys.detail` can rebind, with the key each one is on by default.
+pub const DETAIL_KEY_ACTIONS: &[(&str, &str)] = &[
+    ("back", "q"),
+    ("next_tab", "tab"),
+    ("prev_tab", "shift+tab"),
+    ("down", "j"),
+    ("up", "k"),
+    ("page_down", "ctrl+d"),
+    ("page_up", "ctrl+u"),
+    ("select", "enter"),
+    ("reload", "R"),
+    ("attach", "A"),
+    ("left_column", "h"),
+    ("right_column", "l"),
+    ("select_lines", "v"),
+    ("next_file", "]"),
+    ("prev_file", "["),
+    ("next_hunk", "}"),
+    ("prev_hunk", "{"),
+    ("comment", "c"),
+    ("resolve", "u"),
+    ("review", "s"),
+    ("approve", "a"),
+    ("close", "x"),
+    ("merge", "m"),
+    ("ai_review", "r"),
+    ("ai_review_with", "ctrl+r"),
+    ("checklist", "C"),
+    ("assign", "w"),
+    ("nudge", "b"),
+    ("milestone", "M"),
+    ("labels", "L"),
+    ("reviewers", "W"),
+    ("project", "P"),
+    ("search", "/"),
+    ("search_contents", "?"),
+    ("next_pr", "n"),
+    ("prev_pr", "p"),
+    ("next_match", "n"),
+    ("prev_match", "N"),
+    ("goto_line", ":"),
+    ("path_filter", "F"),
+    ("generated_files", "H"),
+    ("expand_context", "e"),
+    ("file_view", "f"),
+    ("blame", "B"),
+    ("editor", "E"),
+    ("tree", "t"),
+    ("delta", "D"),
+    ("browser", "o"),
+    ("copy_url", "y"),
+    ("messages", "!"),
+    ("remove_closed", "X"),
+];
+
+/// A key with its Ctrl/Alt modifiers, written like `a`, `G`, `ctrl+r`, `shift+tab` or `enter`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// A pressed key. Shift is dropped: it is already in the character (`G`) or the code
+    /// (`BackTab`).
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self {
+            code,
+            modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+
+    /// How the help bar writes the key, e.g. `Ctrl+r` or `Enter`.
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Insert => "Ins".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            other => format!("{other:?}"),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        label + &key
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+        let (prefix, key) = match trimmed.rsplit_once('+') {
+            // `+` on its own, or after a modifier as in `ctrl++`
+            Some((prefix, "")) => (prefix.strip_suffix('+').unwrap_or(prefix), "+"),
+            Some((prefix, key)) => (prefix, key),
+            None => ("", trimmed),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        for modifier in prefix.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" | "meta" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => return Err(format!("Unknown modifier '{modifier}' in key '{trimmed}'")),
+            }
+        }
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "tab" if shift => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => {
+                        return Err(format!(
+                            "Unknown key '{trimmed}'. Expected a character, or tab, enter, esc, \
+                             space, backspace, delete, insert, arrows (up/down/left/right), \
+                             home, end, pageup, pagedown or f1-f12, optionally after ctrl+, \
+                             alt+ or shift+"
+                        ))
+                    }
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl From<KeyChord> for String {
+    fn from(chord: KeyChord) -> Self {
+        let key = match chord.code {
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Tab => "tab".to_string(),
+            KeyCode::BackTab => "shift+tab".to_string(),
+            KeyCode::Enter => "enter".to_string(),
+            KeyCode::Esc => "esc".to_string(),
+            KeyCode::Backspace => "backspace".to_string(),
+            KeyCode::Delete => "delete".to_string(),
+            KeyCode::Insert => "insert".to_string(),
+            KeyCode::Up => "up".to_string(),
+            KeyCode::Down => "down".to_string(),
+            KeyCode::Left => "left".to_string(),
+            KeyCode::Right => "right".to_string(),
+            KeyCode::Home => "home".to_string(),
+            KeyCode::End => "end".to_string(),
+            KeyCode::PageUp => "pageup".to_string(),
+            KeyCode::PageDown => "pagedown".to_string(),
+            KeyCode::F(n) => format!("f{n}"),
+            other => format!("{other:?}").to_ascii_lowercase(),
+        };
+        let mut chord_text = String::new();
+        if chord.modifiers.contains(KeyModifiers::CONTROL) {
+            chord_text.push_str("ctrl+");
+        }
+        if chord.modifiers.contains(KeyModifiers::ALT) {
+            chord_text.push_str("alt+");
+        }
+        chord_text + &key
+    }
+}
+
+/// A key press after `keys` rebinding: the default key of the action it runs, and the action
+/// when only one can run. Keys two actions share by default (`n` for `next_pr` and `next_match`
+/// in a PR) carry no action, and the key handling picks one by context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    pub chord: KeyChord,
+    pub action: Option<&'static str>,
+}
+
+/// Where a key binding applies: the PR list, or an open PR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyScope {
+    List,
+    Detail,
+}
+
+/// Keys bound to each action, by action name.
+pub type KeyBindings = BTreeMap<String, Vec<KeyChord>>;
+
+/// Keys rebound by action name, per screen. Bound keys replace the action's default key,
+/// which then does nothing unless bound to another action.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct KeysConfig {
+    /// Keys in the PR list, e.g. `{"next_tab": ["l"], "prev_tab": ["h"]}`.
+    #[serde(default)]
+    pub list: KeyBindings,
+    /// Keys in an open PR.
+    #[serde(default)]
+    pub detail: KeyBindings,
+}
+
+impl KeysConfig {
+    fn scope(&self, scope: KeyScope) -> (&KeyBindings, &'static [(&'static str, &'static str)]) {
+        match scope {
+            KeyScope::List => (&self.list, LIST_KEY_ACTIONS),
+            KeyScope::Detail => (&self.detail, DETAIL_KEY_ACTIONS),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (scope, name) in [(KeyScope::List, "list"), (KeyScope::Detail, "detail")] {
+            let (bindings, actions) = self.scope(scope);
+            let mut bound: Vec<(KeyChord, &str)> = Vec::new();
+            for (action, chords) in bindings {
+                if !actions.iter().any(|(known, _)| known == action) {
+                    let known: Vec<&str> = actions.iter().map(|(known, _)| *known).collect();
+                    bail!(
+                        "Unknown keys.{name} action '{action}'. Expected one of: {}",
+                        known.join(", ")
+                    );
+                }
+                for chord in chords {
+                    if let Some((_, other)) = bound.iter().find(|(bound, _)| bound == chord) {
+                        bail!(
+                            "keys.{name} binds {} to both {other} and {action}",
+                            String::from(*chord)
+                        );
+                    }
+                    bound.push((*chord, action));
+                }
+            }
+            // A bound key must not shadow the default key of an action left where it was
+            for (chord, action) in &bound {
+                let shadowed = actions.iter().find(|(other, key)| {
+                    other != action
+                        && bindings.get(*other).is_none_or(Vec::is_empty)
+                        && KeyChord::try_from(key.to_string()).ok() == Some(*chord)
+                });
+                if let Some((other, _)) = shadowed {
+                    bail!(
+                        "keys.{name} binds {} to {action}, but it is the default key of \
+                         {other}; rebind {other} too",
+                        String::from(*chord)
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The default key of the action `pressed` is bound to, so the built-in key handling runs
+    /// it; `None` when `pressed` is the default of rebound actions only.
+    pub fn resolve(&self, scope: KeyScope, pressed: KeyChord) -> Option<KeyPress> {
+        let (bindings, actions) = self.scope(scope);
+        let default = |key: &str| KeyChord::try_from(key.to_string()).ok();
+        if let Some((bound, _)) = bindings
+            .iter()
+            .find(|(_, chords)| chords.contains(&pressed))
+        {
+            let (action, key) = actions.iter().find(|(known, _)| known == bound)?;
+            return Some(KeyPress {
+                chord: default(key)?,
+                action: Some(action),
+            });
+        }
+        let sharing: Vec<&'static str> = actions
+            .iter()
+            .filter(|(_, key)| default(key) == Some(pressed))
+            .map(|(action, _)| *action)
+            .collect();
+        let kept: Vec<&'static str> = sharing
+            .iter()
+            .copied()
+            .filter(|action| bindings.get(*action).is_none_or(Vec::is_empty))
+            .collect();
+        match kept.as_slice() {
+            [] if !sharing.is_empty() => None,
+            [action] => Some(KeyPress {
+                chord: pressed,
+                action: Some(action),
+            }),
+            _ => Some(KeyPress {
+                chord: pressed,
+                action: None,
+            }),
+        }
+    }
+
+    /// The keys shown in the help bar instead of the built-in ones, by their default's label.
+    pub fn help_labels(&self, scope: KeyScope) -> Vec<(String, String)> {
+        let (bindings, actions) = self.scope(scope);
+        actions
+            .iter()
+            .filter_map(|(action, key)| {
+                let chords = bindings.get(*action).filter(|chords| !chords.is_empty())?;
+                let default = KeyChord::try_from(key.to_string()).ok()?;
+                let labels: Vec<String> = chords.iter().map(KeyChord::label).collect();
+                Some((default.label(), labels.join("/")))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
-pub struct DaemonConfig {
-    #[serde(default = "default_poll_interval_sec")]
-    pub poll_interval_sec: u64,
-    #[serde(default)]
-    pub exclude_repos: Vec<String>,
-    #[serde(default)]
-    pub initialized: bool,
-    #[serde(default)]
-    pub include_drafts: bool,
-    #[serde(default)]
-    pub repo_subpath_filters: HashMap<String, Vec<String>>,
-    #[serde(default)]
-    pub auto_approve: Vec<AutoApproveRule>,
-    #[serde(default = "default_only_new_prs_on_start")]
-    pub only_new_prs_on_start: bool,
+pub struct ProjectConfig {
+    /// Title of the project board `P` adds PRs to.
+    pub board: Option<String>,
 }
 
-impl Default for DaemonConfig {
-    fn default() -> Self {
-        Self {
-            poll_interval_sec: default_poll_interval_sec(),
-            exclude_repos: Vec::new(),
-            initialized: false,
-            include_drafts: false,
-            repo_subpath_filters: HashMap::new(),
-            auto_approve: Vec::new(),
-            only_new_prs_on_start: default_only_new_prs_on_start(),
-        }
-    }
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EditorConfig {
+    /// Opens a file at a line, with `{file}` and `{line}` placeholders (e.g. `code -g {file}:{line}`).
+    /// Unset means `$VISUAL`/`$EDITOR` invoked as `<editor> +{line} {file}`.
+    pub command: Option<String>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -162,6 +1488,63 @@ pub struct Config {
     pub ai: AiConfig,
     #[serde(default)]
     pub daemon: DaemonConfig,
+    #[serde(default)]
+    pub confirm: ConfirmConfig,
+    #[serde(default)]
+    pub diff: DiffConfig,
+    #[serde(default)]
+    pub checklist: ChecklistConfig,
+    #[serde(default)]
+    pub team: TeamConfig,
+    #[serde(default)]
+    pub editor: EditorConfig,
+    #[serde(default)]
+    pub clone_cache: CloneCacheConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub project: ProjectConfig,
+    #[serde(default)]
+    pub fetch: FetchConfig,
+    #[serde(default)]
+    pub list: ListConfig,
+    #[serde(default)]
+    pub views: Vec<ViewConfig>,
+    #[serde(default)]
+    pub drafts: DraftsConfig,
+    #[serde(default)]
+    pub dates: DatesConfig,
+    #[serde(default)]
+    pub keys: KeysConfig,
+    /// Disable approving, commenting, merging, closing and other writes from the TUI.
+    #[serde(default)]
+    pub read_only: bool,
+    /// High-contrast, screen-reader-friendly TUI: text markers instead of color-only signals and
+    /// no background fills.
+    #[serde(default)]
+    pub accessible: bool,
+}
+
+impl Config {
+    /// Look up a saved view by name, ignoring case.
+    pub fn view(&self, name: &str) -> Result<&ViewConfig> {
+        if let Some(view) = self
+            .views
+            .iter()
+            .find(|view| view.name.eq_ignore_ascii_case(name))
+        {
+            return Ok(view);
+        }
+        if self.views.is_empty() {
+            bail!("Unknown view '{}': no views are configured", name);
+        }
+        let names: Vec<&str> = self.views.iter().map(|view| view.name.as_str()).collect();
+        bail!(
+            "Unknown view '{}'. Configured views: {}",
+            name,
+            names.join(", ")
+        )
+    }
 }
 
 pub fn config_path() -> PathBuf {
@@ -194,7 +1577,9 @@ pub fn config_dir() -> PathBuf {
 }
 
 fn parse_config(contents: &str) -> Result<Config> {
-    serde_json::from_str(contents).context("Invalid reviewer config JSON")
+    let config: Config = serde_json::from_str(contents).context("Invalid reviewer config JSON")?;
+    config.keys.validate()?;
+    Ok(config)
 }
 
 fn ensure_object(value: &mut Value) -> &mut Map<String, Value> {
@@ -238,7 +1623,14 @@ fn merge_with_existing_config(existing: Value, updated: Value) -> Value {
     };
 
     let existing_object = ensure_object(&mut existing);
-    for field in ["repos_root", "exclude", "exclude_users"] {
+    for field in [
+        "repos_root",
+        "exclude",
+        "exclude_users",
+        "read_only",
+        "accessible",
+        "views",
+    ] {
         if let Some(value) = updated_object.get(field) {
             existing_object.insert(field.to_string(), value.clone());
         }
@@ -254,7 +1646,9 @@ fn merge_with_existing_config(existing: Value, updated: Value) -> Value {
             "args",
             "skill",
             "prompt_template",
+            "repo_prompt_templates",
             "launch",
+            "profiles",
         ],
     );
 
@@ -270,6 +1664,101 @@ fn merge_with_existing_config(existing: Value, updated: Value) -> Value {
             "repo_subpath_filters",
             "auto_approve",
             "only_new_prs_on_start",
+            "triage",
+            "batch",
+            "escalation",
+        ],
+    );
+
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "confirm",
+        &[
+            "approve",
+            "merge",
+            "close",
+            "typed_merge_repos",
+            "close_reasons",
+        ],
+    );
+
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "diff",
+        &[
+            "collapse_generated",
+            "generated_files",
+            "large_pr_lines",
+            "context_step",
+        ],
+    );
+
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "checklist",
+        &["path", "include_in_review"],
+    );
+
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "team",
+        &["members", "queue_cache_sec"],
+    );
+
+    merge_known_subobject(existing_object, updated_object, "editor", &["command"]);
+
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "clone_cache",
+        &["enabled", "max_size_mb"],
+    );
+
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "auth",
+        &["token_env", "token_command", "token_refresh_min"],
+    );
+
+    merge_known_subobject(existing_object, updated_object, "project", &["board"]);
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "fetch",
+        &["base", "labels", "authors"],
+    );
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "drafts",
+        &["always", "never"],
+    );
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "dates",
+        &["timezone", "style", "format"],
+    );
+
+    merge_known_subobject(existing_object, updated_object, "keys", &["list", "detail"]);
+
+    merge_known_subobject(
+        existing_object,
+        updated_object,
+        "list",
+        &[
+            "approved",
+            "sort",
+            "order",
+            "group_by_repo",
+            "preview",
+            "bots",
+            "filter",
         ],
     );
 
@@ -284,17 +1773,183 @@ pub fn load_config() -> Result<Config> {
     let contents = std::fs::read_to_string(&path)
         .with_context(|| format!("Failed to read config file: {}", path.display()))?;
     parse_config(&contents).with_context(|| {
+        let backup = private_fs::backup_path(&path);
+        let restore = if backup.exists() {
+            format!(
+                " The version before the last save is in {}.",
+                backup.display()
+            )
+        } else {
+            String::new()
+        };
         format!(
-            "Invalid config file {}. Check for typos/unknown fields and JSON syntax.",
-            path.display()
+            "Invalid config file {}. Check for typos/unknown fields and JSON syntax.{}",
+            path.display(),
+            restore
         )
     })
 }
 
+fn file_modified(path: &std::path::Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+fn differs<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() != serde_json::to_value(b).ok()
+}
+
+/// A config file change picked up by [`ConfigWatcher`].
+#[derive(Debug)]
+pub struct ConfigReload {
+    pub previous: Config,
+    pub config: Config,
+}
+
+impl ConfigReload {
+    /// Settings long-running processes apply without a restart.
+    pub fn changed_sections(&self) -> Vec<&'static str> {
+        let (old, new) = (&self.previous, &self.config);
+        let mut sections = Vec::new();
+        if old.daemon.poll_interval_sec != new.daemon.poll_interval_sec {
+            sections.push("poll interval");
+        }
+        if differs(&old.exclude, &new.exclude)
+            || differs(&old.exclude_users, &new.exclude_users)
+            || differs(&old.daemon.exclude_repos, &new.daemon.exclude_repos)
+            || differs(
+                &old.daemon.repo_subpath_filters,
+                &new.daemon.repo_subpath_filters,
+            )
+        {
+            sections.push("exclusions");
+        }
+        if differs(&old.ai, &new.ai) {
+            sections.push("AI settings");
+        }
+        if old.daemon.include_drafts != new.daemon.include_drafts
+            || differs(&old.daemon.auto_approve, &new.daemon.auto_approve)
+            || differs(&old.daemon.triage, &new.daemon.triage)
+            || differs(&old.daemon.batch, &new.daemon.batch)
+            || differs(&old.daemon.escalation, &new.daemon.escalation)
+        {
+            sections.push("daemon rules");
+        }
+        if differs(&old.confirm, &new.confirm) {
+            sections.push("confirmations");
+        }
+        if differs(&old.diff, &new.diff) {
+            sections.push("diff settings");
+        }
+        if differs(&old.checklist, &new.checklist) {
+            sections.push("checklist");
+        }
+        if differs(&old.team, &new.team) {
+            sections.push("team");
+        }
+        if differs(&old.editor, &new.editor) {
+            sections.push("editor");
+        }
+        if differs(&old.clone_cache, &new.clone_cache) {
+            sections.push("clone cache");
+        }
+        if differs(&old.auth, &new.auth) {
+            sections.push("auth");
+        }
+        if differs(&old.project, &new.project) {
+            sections.push("project");
+        }
+        if differs(&old.fetch, &new.fetch) {
+            sections.push("fetch filters");
+        }
+        if differs(&old.list, &new.list) {
+            sections.push("list");
+        }
+        if differs(&old.views, &new.views) {
+            sections.push("views");
+        }
+        if differs(&old.dates, &new.dates) {
+            sections.push("date formatting");
+        }
+        if differs(&old.drafts, &new.drafts) {
+            sections.push("draft overrides");
+        }
+        if old.keys != new.keys {
+            sections.push("key bindings");
+        }
+        if old.read_only != new.read_only {
+            sections.push("read-only mode");
+        }
+        if old.accessible != new.accessible {
+            sections.push("accessible mode");
+        }
+        sections
+    }
+
+    /// Status line describing what was reloaded, or `None` when nothing relevant changed.
+    pub fn summary(&self) -> Option<String> {
+        let sections = self.changed_sections();
+        let restart = self.previous.repos_root != self.config.repos_root;
+        if sections.is_empty() && !restart {
+            return None;
+        }
+        let mut summary = if sections.is_empty() {
+            "Config changed".to_string()
+        } else {
+            format!("Reloaded config: {}", sections.join(", "))
+        };
+        if restart {
+            summary.push_str(" (restart to apply repos_root)");
+        }
+        Some(summary)
+    }
+}
+
+/// Watches the config file's modification time so long-running processes can
+/// pick up edits without a restart.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<std::time::SystemTime>,
+    current: Config,
+}
+
+impl ConfigWatcher {
+    pub fn new(current: &Config) -> Self {
+        let path = config_path();
+        Self {
+            modified: file_modified(&path),
+            path,
+            current: current.clone(),
+        }
+    }
+
+    /// Re-read the config if the file changed since the last call. Invalid
+    /// edits are reported once and the previous config stays in effect.
+    pub fn poll(&mut self) -> Option<Result<ConfigReload>> {
+        let modified = file_modified(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(self.reload())
+    }
+
+    /// Re-read the config even if the file looks unchanged.
+    pub fn reload(&mut self) -> Result<ConfigReload> {
+        self.modified = file_modified(&self.path);
+        load_config().map(|config| ConfigReload {
+            previous: std::mem::replace(&mut self.current, config.clone()),
+            config,
+        })
+    }
+}
+
 pub fn save_config(config: &Config) -> Result<()> {
     let path = config_path();
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+        private_fs::create_dir_all(parent)?;
     }
     let updated = serde_json::to_value(config)?;
     let existing = if path.exists() {
@@ -312,13 +1967,16 @@ pub fn save_config(config: &Config) -> Result<()> {
 
     let merged = merge_with_existing_config(existing, updated);
     let json = serde_json::to_string_pretty(&merged)?;
-    std::fs::write(&path, json)?;
+    private_fs::write_atomic(&path, json)?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{merge_with_existing_config, parse_config, Config};
+    use super::{
+        merge_with_existing_config, parse_config, Config, ConfigReload, EscalationAction, KeyChord,
+        KeyScope, KeysConfig, ListFilter, ListOrderRule, ViewTab,
+    };
     use serde_json::json;
 
     #[test]
@@ -339,6 +1997,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn keys_rebind_actions_and_free_their_default_keys() {
+        let json = r#"
+        {
+          "keys": {
+            "list": { "next_tab": ["l"], "prev_tab": ["h"] },
+            "detail": { "approve": ["A", "ctrl+a"], "attach": ["ctrl+t"] }
+          }
+        }
+        "#;
+        let keys = parse_config(json).unwrap().keys;
+        let chord = |key: &str| KeyChord::try_from(key.to_string()).unwrap();
+        let resolve = |scope, key| keys.resolve(scope, chord(key)).map(|press| press.chord);
+
+        assert_eq!(resolve(KeyScope::List, "l"), Some(chord("tab")));
+        assert_eq!(resolve(KeyScope::List, "h"), Some(chord("shift+tab")));
+        assert_eq!(resolve(KeyScope::List, "tab"), None);
+        assert_eq!(resolve(KeyScope::List, "j"), Some(chord("j")));
+        // Scopes are separate: h/l still pick diff columns in a PR
+        assert_eq!(resolve(KeyScope::Detail, "l"), Some(chord("l")));
+        // `A` attached an agent by default; with attach moved, it approves
+        assert_eq!(resolve(KeyScope::Detail, "A"), Some(chord("a")));
+        assert_eq!(resolve(KeyScope::Detail, "ctrl+a"), Some(chord("a")));
+        assert_eq!(resolve(KeyScope::Detail, "a"), None);
+        assert_eq!(
+            keys.help_labels(KeyScope::Detail),
+            vec![
+                ("A".to_string(), "Ctrl+t".to_string()),
+                ("a".to_string(), "A/Ctrl+a".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_shared_by_two_actions_keep_the_one_not_rebound() {
+        let keys: KeysConfig = serde_json::from_str(r#"{"detail": {"next_pr": ["J"]}}"#).unwrap();
+        let press = |key: &str| {
+            keys.resolve(
+                KeyScope::Detail,
+                KeyChord::try_from(key.to_string()).unwrap(),
+            )
+            .map(|press| press.action)
+        };
+
+        assert_eq!(press("J"), Some(Some("next_pr")));
+        assert_eq!(press("n"), Some(Some("next_match")));
+        // Without rebinding, `n` is left to the key handling to pick by context
+        let defaults = KeysConfig::default();
+        let n = KeyChord::try_from("n".to_string()).unwrap();
+        assert_eq!(defaults.resolve(KeyScope::Detail, n).unwrap().action, None);
+    }
+
+    #[test]
+    fn key_chords_parse_and_print_in_one_form() {
+        for (text, printed) in [
+            ("a", "a"),
+            ("G", "G"),
+            ("shift+g", "G"),
+            ("Ctrl+r", "ctrl+r"),
+            ("shift+tab", "shift+tab"),
+            ("space", "space"),
+            ("+", "+"),
+            ("alt++", "alt++"),
+            ("PgDn", "pagedown"),
+            ("f5", "f5"),
+        ] {
+            let chord = KeyChord::try_from(text.to_string()).unwrap();
+            assert_eq!(String::from(chord), printed, "{text}");
+            assert_eq!(KeyChord::try_from(printed.to_string()), Ok(chord), "{text}");
+        }
+        let err = KeyChord::try_from("hyper+a".to_string()).unwrap_err();
+        assert!(err.contains("Unknown modifier 'hyper'"), "{err}");
+        let err = KeyChord::try_from("enterr".to_string()).unwrap_err();
+        assert!(err.contains("Unknown key 'enterr'"), "{err}");
+    }
+
+    #[test]
+    fn parse_config_rejects_bad_key_bindings() {
+        for (json, expected) in [
+            (
+                r#"{"keys": {"list": {"approve": ["A"]}}}"#,
+                "Unknown keys.list action 'approve'",
+            ),
+            (
+                r#"{"keys": {"detail": {"approve": ["x"], "merge": ["x"]}}}"#,
+                "keys.detail binds x to both approve and merge",
+            ),
+            (
+                r#"{"keys": {"list": {"quit": ["ctl+q"]}}}"#,
+                "Unknown modifier 'ctl'",
+            ),
+            (
+                r#"{"keys": {"detail": {"next_tab": ["l"]}}}"#,
+                "keys.detail binds l to next_tab, but it is the default key of right_column",
+            ),
+            (
+                r#"{"keys": {"detail": {"next_match": ["n"]}}}"#,
+                "it is the default key of next_pr",
+            ),
+        ] {
+            let msg = format!("{:#}", parse_config(json).unwrap_err());
+            assert!(msg.contains(expected), "{json}: {msg}");
+        }
+    }
+
     #[test]
     fn parse_config_rejects_unknown_nested_field() {
         let json = r#"
@@ -521,4 +2284,222 @@ mod tests {
         let cfg = Config::default();
         assert!(cfg.daemon.only_new_prs_on_start);
     }
+
+    #[test]
+    fn confirm_defaults_to_all_dialogs_enabled() {
+        let cfg = Config::default();
+        assert!(cfg.confirm.approve);
+        assert!(cfg.confirm.merge);
+        assert!(cfg.confirm.close);
+        assert!(!cfg.confirm.merge_requires_typed("org/reviewer"));
+    }
+
+    #[test]
+    fn confirm_typed_merge_repos_match_case_insensitive_patterns() {
+        let cfg =
+            parse_config(r#"{"confirm": {"approve": false, "typed_merge_repos": ["Org/prod-*"]}}"#)
+                .expect("config should parse");
+
+        assert!(!cfg.confirm.approve);
+        assert!(cfg.confirm.merge);
+        assert!(cfg.confirm.merge_requires_typed("org/prod-api"));
+        assert!(!cfg.confirm.merge_requires_typed("org/staging-api"));
+    }
+
+    #[test]
+    fn config_reload_reports_changed_sections() {
+        let previous = parse_config(r#"{"repos_root": "/tmp/a"}"#).expect("config should parse");
+        let config = parse_config(
+            r#"{"repos_root": "/tmp/b", "exclude_users": ["dependabot"], "daemon": {"poll_interval_sec": 30}}"#,
+        )
+        .expect("config should parse");
+        let reload = ConfigReload { previous, config };
+
+        assert_eq!(
+            reload.changed_sections(),
+            vec!["poll interval", "exclusions"]
+        );
+        assert_eq!(
+            reload.summary().as_deref(),
+            Some("Reloaded config: poll interval, exclusions (restart to apply repos_root)")
+        );
+
+        let unchanged = ConfigReload {
+            previous: Config::default(),
+            config: Config::default(),
+        };
+        assert_eq!(unchanged.summary(), None);
+    }
+
+    #[test]
+    fn ai_repo_prompt_templates_override_default_template() {
+        let cfg = parse_config(
+            r#"{"ai": {"prompt_template": "default", "repo_prompt_templates": [
+                {"repo": "Org/security-*", "prompt_template": "strict"},
+                {"repo": "org/*", "prompt_template": "org"}
+            ]}}"#,
+        )
+        .expect("config should parse");
+
+        assert_eq!(
+            cfg.ai.prompt_template_for("org/security-api"),
+            Some("strict")
+        );
+        assert_eq!(cfg.ai.prompt_template_for("org/docs"), Some("org"));
+        assert_eq!(cfg.ai.prompt_template_for("other/docs"), Some("default"));
+    }
+
+    #[test]
+    fn diff_generated_files_default_and_override() {
+        let cfg = parse_config("{}").expect("config should parse");
+        assert!(cfg.diff.collapse_generated);
+        assert!(cfg.diff.generated_files.contains(&"*.lock".to_string()));
+
+        let cfg = parse_config(r#"{"diff": {"generated_files": ["*.snap"]}}"#)
+            .expect("config should parse");
+        assert!(cfg.diff.collapse_generated);
+        assert_eq!(cfg.diff.generated_files, vec!["*.snap".to_string()]);
+    }
+
+    #[test]
+    fn ai_choices_layer_profiles_over_the_base_settings() {
+        let cfg = parse_config(
+            r#"{"ai": {
+                "provider": "claude",
+                "command": "claude-wrapper",
+                "args": ["--fast"],
+                "profiles": [
+                    {"name": "deep", "args": ["--model", "big"], "skill": "security-review"},
+                    {"name": "second opinion", "provider": "codex"}
+                ]
+            }}"#,
+        )
+        .expect("config should parse");
+
+        let choices = cfg.ai.choices();
+        let labels: Vec<&str> = choices.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec!["Claude (default)", "deep: Claude", "second opinion: Codex"]
+        );
+        let deep = &choices[1].1;
+        assert_eq!(deep.command_name(), "claude-wrapper");
+        assert_eq!(deep.args, vec!["--model", "big"]);
+        assert_eq!(deep.skill_name(), "security-review");
+        let codex = &choices[2].1;
+        assert_eq!(codex.command_name(), "codex");
+        assert!(codex.args.is_empty());
+        assert_eq!(codex.skill_name(), "code-review");
+    }
+
+    #[test]
+    fn list_order_rules_parse_and_reject_unknown_rules() {
+        let cfg = parse_config(
+            r#"{"list": {"order": ["label:security*", "author:@alice", "teammates", "smallest", "oldest"]}}"#,
+        )
+        .expect("config should parse");
+        assert_eq!(
+            cfg.list.order,
+            vec![
+                ListOrderRule::Label("security*".to_string()),
+                ListOrderRule::Author("alice".to_string()),
+                ListOrderRule::Teammates,
+                ListOrderRule::Smallest,
+                ListOrderRule::Oldest,
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&cfg.list.order[0]).unwrap(),
+            serde_json::json!("label:security*")
+        );
+
+        let err = parse_config(r#"{"list": {"order": ["biggest"]}}"#).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("Unknown list.order rule 'biggest'"),
+            "{err:#}"
+        );
+        let err = parse_config(r#"{"list": {"order": ["label:"]}}"#).unwrap_err();
+        assert!(format!("{err:#}").contains("needs a pattern"), "{err:#}");
+    }
+
+    #[test]
+    fn list_filter_parses_composed_terms_and_round_trips() {
+        let cfg = parse_config(
+            r#"{"list": {"filter": "repo:acme/api author:@bob label:bug repo:acme/web draft:no size:<200"}}"#,
+        )
+        .expect("config should parse");
+        let filter = &cfg.list.filter;
+        assert_eq!(filter.repos, vec!["acme/api", "acme/web"]);
+        assert_eq!(filter.authors, vec!["bob"]);
+        assert_eq!(filter.labels, vec!["bug"]);
+        assert_eq!(filter.draft, Some(false));
+        assert_eq!((filter.below_lines, filter.above_lines), (Some(200), None));
+        assert_eq!(
+            filter.to_string(),
+            "repo:acme/api repo:acme/web author:bob label:bug draft:no size:<200"
+        );
+        assert!(parse_config(r#"{"list": {}}"#)
+            .unwrap()
+            .list
+            .filter
+            .is_empty());
+
+        for (bad, message) in [
+            ("team:core", "Unknown filter key 'team'"),
+            ("repo:", "needs a key and a value"),
+            ("size:big", "must be size:<N or size:>N"),
+            ("size:<lots", "needs a line count"),
+            ("draft:maybe", "must be draft:yes or draft:no"),
+        ] {
+            let err = ListFilter::try_from(bad.to_string()).unwrap_err();
+            assert!(err.contains(message), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn views_parse_and_resolve_by_name() {
+        let cfg = parse_config(
+            r#"{"views": [{"name": "Platform hotlist", "tab": "my_prs", "repos": ["acme/platform-*"], "labels": ["hot"], "order": ["smallest"], "drafts": false}]}"#,
+        )
+        .expect("config should parse");
+        let view = cfg.view("platform HOTLIST").expect("view should resolve");
+        assert_eq!(view.tab, ViewTab::MyPrs);
+        assert_eq!(view.repos, vec!["acme/platform-*"]);
+        assert_eq!(view.order, Some(vec![ListOrderRule::Smallest]));
+        assert_eq!(view.drafts, Some(false));
+
+        let err = cfg.view("nope").unwrap_err();
+        assert!(
+            format!("{err:#}").contains("Configured views: Platform hotlist"),
+            "{err:#}"
+        );
+        assert!(parse_config(r#"{"views": [{"name": "x", "sort": []}]}"#).is_err());
+    }
+
+    #[test]
+    fn list_bots_default_to_common_automation_accounts() {
+        let defaults = vec!["dependabot[bot]", "renovate[bot]", "github-actions[bot]"];
+        assert_eq!(Config::default().list.bots, defaults);
+        let cfg = parse_config(r#"{"list": {"approved": "dim"}}"#).expect("config should parse");
+        assert_eq!(cfg.list.bots, defaults);
+        let cfg = parse_config(r#"{"list": {"bots": []}}"#).expect("config should parse");
+        assert!(cfg.list.bots.is_empty());
+    }
+
+    #[test]
+    fn daemon_escalation_is_opt_in_per_repo() {
+        let cfg = parse_config(r#"{"daemon": {"escalation": {"after_hours": 24}}}"#)
+            .expect("config should parse");
+        assert!(!cfg.daemon.escalation.applies_to("acme/widgets"));
+        assert_eq!(cfg.daemon.escalation.max_per_pr, 1);
+
+        let cfg = parse_config(
+            r#"{"daemon": {"escalation": {"repos": ["Acme/*"], "action": "notify"}}}"#,
+        )
+        .expect("config should parse");
+        assert!(cfg.daemon.escalation.applies_to("acme/widgets"));
+        assert!(!cfg.daemon.escalation.applies_to("other/widgets"));
+        assert_eq!(cfg.daemon.escalation.action, EscalationAction::Notify);
+        assert!(parse_config(r#"{"daemon": {"escalation": {"action": "email"}}}"#).is_err());
+    }
 }
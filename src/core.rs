@@ -0,0 +1,1921 @@
+//! The daemon's polling engine: which repos and PRs are watched, the state kept between polls,
+//! and the triggers launched for new PRs. Nothing here prints; a poll reports what it does as
+//! [`PollEvent`]s, so the daemon, the TUI and tests can each decide what to show.
+
+use crate::ai_api;
+use crate::audit;
+use crate::config;
+use crate::config::{
+    AiConfig, AiLaunchApiConfig, AutoApproveRule, Config, DraftsConfig, EscalationAction,
+    EscalationConfig, TriageConfig,
+};
+use crate::filters::{author_excluded, normalize_user_patterns, path_glob_match, wildcard_match};
+use crate::gh::{self, PullRequest, ReviewerState};
+use crate::notify;
+use crate::private_fs;
+use crate::repos;
+use crate::run_artifacts::{self, RunRecord};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone)]
+pub(crate) struct RepoDescriptor {
+    pub(crate) path: PathBuf,
+    pub(crate) name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReviewTriggerKind {
+    Review,
+    SelfReview,
+}
+
+impl ReviewTriggerKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Review => "review",
+            Self::SelfReview => "self-review",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TriggerAction {
+    Review(ReviewTriggerKind),
+    AutoApprove,
+}
+
+impl TriggerAction {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Review(kind) => kind.label(),
+            Self::AutoApprove => "auto-approve",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DaemonReviewCandidate {
+    pr: PullRequest,
+    trigger_kind: ReviewTriggerKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerStatus {
+    Seeded,
+    Success,
+    Failed,
+    /// Triage marked the PR trivial, so no review was launched.
+    Skipped,
+    /// Waiting for the next `daemon.batch` drain or `daemon flush`.
+    Queued,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageVerdict {
+    Trivial,
+    NeedsReview,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TriageDecision {
+    pub verdict: TriageVerdict,
+    pub reason: String,
+    pub decided_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewedPrRecord {
+    pub repo: String,
+    pub pr_number: u64,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub latest_updated_at: DateTime<Utc>,
+    pub triggered_at: Option<DateTime<Utc>>,
+    pub trigger_status: TriggerStatus,
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub triage: Option<TriageDecision>,
+    /// AI provider of the latest review, daemon- or hand-launched.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Artifacts of the latest daemon trigger (prompt, commands, launcher output).
+    #[serde(default)]
+    pub last_run_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DaemonState {
+    #[serde(default)]
+    pub prs: HashMap<String, ReviewedPrRecord>,
+    #[serde(default)]
+    pub last_poll_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_flush_at: Option<DateTime<Utc>>,
+    /// Escalation nudges sent per PR, keyed like `prs`.
+    #[serde(default)]
+    pub nudges: HashMap<String, NudgeRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NudgeRecord {
+    pub count: u32,
+    pub last_nudged_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct PollSummary {
+    pub monitored_repos: usize,
+    pub open_prs: usize,
+    pub new_prs: usize,
+    pub triggered: usize,
+    pub skipped: usize,
+    pub queued: usize,
+    pub failed: usize,
+    pub nudged: usize,
+}
+
+/// Something a poll did or could not do, reported as it happens. PRs are named `repo#number`
+/// and actions by their label (`review`, `self-review`, `auto-approve`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollEvent {
+    /// A repo's open PRs could not be listed.
+    ListFailed {
+        repo: String,
+        reason: String,
+    },
+    /// A PR's changed files could not be listed, so the subpath filter let it through.
+    SubpathCheckFailed {
+        pr: String,
+        reason: String,
+    },
+    /// A PR not seen before.
+    NewPr {
+        action: &'static str,
+        pr: String,
+        title: String,
+    },
+    Triaged {
+        pr: String,
+        trivial: bool,
+        reason: String,
+    },
+    /// Held for the next `daemon.batch` drain.
+    Queued {
+        action: &'static str,
+        pr: String,
+    },
+    /// Launching a queued trigger, or retrying a failed one.
+    Relaunching {
+        action: &'static str,
+        pr: String,
+        queued: bool,
+    },
+    Triggered {
+        action: &'static str,
+        pr: String,
+    },
+    TriggerFailed {
+        action: &'static str,
+        pr: String,
+        retry: bool,
+        error: String,
+    },
+    Nudged {
+        pr: String,
+        title: String,
+    },
+    NudgeFailed {
+        pr: String,
+        error: String,
+    },
+    /// A problem that isn't about one PR's trigger, such as an invalid launcher config.
+    Warning(String),
+}
+
+impl PollEvent {
+    /// Whether this reports something that went wrong.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::ListFailed { .. }
+                | Self::SubpathCheckFailed { .. }
+                | Self::TriggerFailed { .. }
+                | Self::NudgeFailed { .. }
+                | Self::Warning(_)
+        )
+    }
+}
+
+impl std::fmt::Display for PollEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ListFailed { repo, reason } => {
+                write!(f, "Failed to list PRs for {repo}: {reason}")
+            }
+            Self::SubpathCheckFailed { pr, reason } => write!(
+                f,
+                "Failed to evaluate daemon subpath filter for {pr}: {reason}. Triggering review anyway."
+            ),
+            Self::NewPr { action, pr, title } => {
+                write!(f, "New PR detected for {action}: {pr} - {title}")
+            }
+            Self::Triaged {
+                pr,
+                trivial,
+                reason,
+            } => write!(
+                f,
+                "Triage for {pr}: {} ({reason})",
+                if *trivial { "trivial" } else { "needs review" }
+            ),
+            Self::Queued { action, pr } => {
+                write!(f, "Queued {action} for {pr} until the next batch")
+            }
+            Self::Relaunching { action, pr, queued } => write!(
+                f,
+                "{} {action} trigger for {pr}",
+                if *queued {
+                    "Launching queued"
+                } else {
+                    "Retrying failed"
+                }
+            ),
+            Self::Triggered { action, pr } => write!(f, "Triggered {action} for {pr}"),
+            Self::TriggerFailed {
+                action,
+                pr,
+                retry: true,
+                error,
+            } => write!(f, "Failed to retry {action} trigger for {pr}: {error}"),
+            Self::TriggerFailed {
+                action, pr, error, ..
+            } => write!(f, "Failed to trigger {action} for {pr}: {error}"),
+            Self::Nudged { pr, title } => write!(f, "Nudged {pr} - {title}"),
+            Self::NudgeFailed { pr, error } => write!(f, "Failed to nudge {pr}: {error}"),
+            Self::Warning(message) => f.write_str(message),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RepoSubpathFilterStatus {
+    pub repo: String,
+    pub subpaths: Vec<String>,
+}
+
+pub(crate) type RepoSubpathFilterMap = HashMap<String, Vec<String>>;
+
+pub fn state_path() -> PathBuf {
+    config::config_dir().join("daemon_state.json")
+}
+
+/// Exclusive hold on the daemon state, taken by each process that changes it (the daemon's
+/// polls, `daemon flush`, `daemon init`, manual re-runs in the TUI) from load until save, so
+/// none overwrites another's changes. Released when dropped.
+pub struct StateLock {
+    _file: std::fs::File,
+}
+
+/// Wait for, then take, the [`StateLock`]. Its lock file sits beside the state file.
+pub fn lock_state() -> Result<StateLock> {
+    let path = state_path().with_extension("json.lock");
+    if let Some(parent) = path.parent() {
+        private_fs::create_dir_all(parent)?;
+    }
+    let file = private_fs::open_options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.lock()
+        .with_context(|| format!("Failed to lock {}", path.display()))?;
+    Ok(StateLock { _file: file })
+}
+
+/// The saved daemon state; empty before the first poll.
+pub fn load_state() -> DaemonState {
+    private_fs::read_json_or_backup(&state_path()).unwrap_or_default()
+}
+
+/// Records for PRs the daemon has acted on (reviewed, tried to, queued, or triaged as trivial),
+/// keyed by `repo#number`. PRs only seeded at `daemon init` are left out.
+pub fn acted_on_prs() -> HashMap<String, ReviewedPrRecord> {
+    load_state()
+        .prs
+        .into_iter()
+        .filter(|(_, record)| record.trigger_status != TriggerStatus::Seeded)
+        .collect()
+}
+
+/// Note a review re-run by hand from the TUI on a PR the daemon tracks, so its state (and the
+/// list badge) reflect the latest run and provider. Returns whether the PR was tracked.
+pub fn record_manual_trigger(
+    repo: &str,
+    pr_number: u64,
+    provider: &str,
+    outcome: Result<(), String>,
+) -> Result<bool> {
+    // Waits out a daemon poll in progress, so its save can't drop this record
+    let _lock = lock_state()?;
+    let mut state = load_state();
+    let Some(record) = state.prs.get_mut(&pr_key(repo, pr_number)) else {
+        return Ok(false);
+    };
+    match outcome {
+        Ok(()) => {
+            record.triggered_at = Some(Utc::now());
+            record.trigger_status = TriggerStatus::Success;
+            record.last_error = None;
+            record.provider = Some(provider.to_string());
+        }
+        // Leave the status alone, so a failed manual run doesn't make the daemon retry.
+        Err(err) => record.last_error = Some(err),
+    }
+    save_state(&state)?;
+    Ok(true)
+}
+
+pub fn save_state(state: &DaemonState) -> Result<()> {
+    let path = state_path();
+    if let Some(parent) = path.parent() {
+        private_fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(state)?;
+    private_fs::write_atomic(&path, json)?;
+    Ok(())
+}
+
+fn pr_key(repo: &str, pr_number: u64) -> String {
+    format!("{repo}#{pr_number}")
+}
+
+pub(crate) fn discover_repos(repos_root: &Path, exclude_dirs: &[String]) -> Vec<RepoDescriptor> {
+    repos::scan_unique_repos(repos_root, 3, exclude_dirs)
+        .unique_repos
+        .into_iter()
+        .filter_map(|repo| {
+            repo.name_with_owner.map(|name| RepoDescriptor {
+                path: repo.path,
+                name,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn monitored_repo_set(exclude_repos: &[String]) -> HashSet<String> {
+    exclude_repos.iter().cloned().collect()
+}
+
+pub(crate) fn normalize_repo_names(mut repos: Vec<String>) -> Vec<String> {
+    repos.sort();
+    repos.dedup();
+    repos
+}
+
+fn normalize_subpath(path: &str) -> Option<String> {
+    let normalized = path.trim().trim_matches('/').trim();
+    if normalized.is_empty() {
+        return None;
+    }
+    Some(normalized.to_string())
+}
+
+pub(crate) fn normalize_subpaths(paths: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = paths
+        .iter()
+        .filter_map(|path| normalize_subpath(path))
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+pub(crate) fn normalize_auto_approve_rules(rules: &[AutoApproveRule]) -> Vec<AutoApproveRule> {
+    let mut normalized = Vec::new();
+    let mut seen = HashSet::new();
+
+    for rule in rules {
+        let repo = rule.repo.trim().to_lowercase();
+        let user = rule.user.trim().to_lowercase();
+        if repo.is_empty() || user.is_empty() {
+            continue;
+        }
+
+        if !seen.insert((repo.clone(), user.clone())) {
+            continue;
+        }
+
+        normalized.push(AutoApproveRule { repo, user });
+    }
+
+    normalized.sort_by(|a, b| (&a.repo, &a.user).cmp(&(&b.repo, &b.user)));
+    normalized
+}
+
+pub(crate) fn normalize_repo_subpath_filters(
+    repo_subpath_filters: &RepoSubpathFilterMap,
+) -> RepoSubpathFilterMap {
+    let mut normalized = HashMap::new();
+    for (repo, subpaths) in repo_subpath_filters {
+        let repo_name = repo.trim();
+        if repo_name.is_empty() {
+            continue;
+        }
+        normalized.insert(repo_name.to_string(), normalize_subpaths(subpaths));
+    }
+    normalized
+}
+
+pub(crate) fn normalize_repo_subpath_filter_status(
+    repo_subpath_filters: &RepoSubpathFilterMap,
+) -> Vec<RepoSubpathFilterStatus> {
+    let mut normalized: Vec<RepoSubpathFilterStatus> =
+        normalize_repo_subpath_filters(repo_subpath_filters)
+            .into_iter()
+            .filter(|(_, subpaths)| !subpaths.is_empty())
+            .map(|(repo, subpaths)| RepoSubpathFilterStatus { repo, subpaths })
+            .collect();
+    normalized.sort_by(|a, b| a.repo.cmp(&b.repo));
+    normalized
+}
+
+fn path_matches_subpath(path: &str, subpath: &str) -> bool {
+    let normalized_path = path.trim_start_matches('/');
+    normalized_path == subpath
+        || normalized_path
+            .strip_prefix(subpath)
+            .map(|rest| rest.starts_with('/'))
+            .unwrap_or(false)
+}
+
+fn pr_touches_any_subpath(changed_files: &[String], subpaths: &[String]) -> bool {
+    changed_files.iter().any(|path| {
+        subpaths
+            .iter()
+            .any(|subpath| path_matches_subpath(path, subpath))
+    })
+}
+
+/// Changed files per `(repo, PR number)`, with the head SHA they were listed at.
+type ChangedFilesCache = HashMap<(String, u64), (String, Vec<String>)>;
+
+/// Subpath filters run on every poll; a PR's files only change when its head does.
+static CHANGED_FILES: Mutex<Option<ChangedFilesCache>> = Mutex::new(None);
+
+/// Files changed by `pr`, reusing the last listing while its head SHA is the same.
+fn cached_pr_changed_files(pr: &PullRequest) -> Result<Vec<String>> {
+    let key = (pr.repo_name.clone(), pr.number);
+    if !pr.head_oid.is_empty() {
+        let cache = CHANGED_FILES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, files)) = cache
+            .as_ref()
+            .and_then(|cache| cache.get(&key))
+            .filter(|(head, _)| *head == pr.head_oid)
+        {
+            return Ok(files.clone());
+        }
+    }
+    let files = gh::get_pr_changed_files(pr)?;
+    if !pr.head_oid.is_empty() {
+        CHANGED_FILES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(HashMap::new)
+            .insert(key, (pr.head_oid.clone(), files.clone()));
+    }
+    Ok(files)
+}
+
+/// Forget cached files of `repo`'s PRs that are no longer open.
+fn prune_changed_files_cache(repo: &str, open: &[PullRequest]) {
+    if let Some(cache) = CHANGED_FILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+    {
+        cache.retain(|(cached_repo, number), _| {
+            cached_repo != repo || open.iter().any(|pr| pr.number == *number)
+        });
+    }
+}
+
+/// PRs touching the repo's subpaths, and a [`PollEvent::SubpathCheckFailed`] for each PR let
+/// through because its files could not be listed.
+fn apply_repo_subpath_filter(
+    repo: &RepoDescriptor,
+    prs: Vec<PullRequest>,
+    repo_subpath_filters: &RepoSubpathFilterMap,
+) -> (Vec<PullRequest>, Vec<PollEvent>) {
+    let subpaths = match repo_subpath_filters.get(&repo.name) {
+        Some(subpaths) if !subpaths.is_empty() => subpaths,
+        _ => return (prs, Vec::new()),
+    };
+
+    prune_changed_files_cache(&repo.name, &prs);
+    let mut events = Vec::new();
+    let kept = prs
+        .into_iter()
+        .filter(|pr| match cached_pr_changed_files(pr) {
+            Ok(changed_files) => pr_touches_any_subpath(&changed_files, subpaths),
+            Err(err) => {
+                events.push(PollEvent::SubpathCheckFailed {
+                    pr: pr_key(&pr.repo_name, pr.number),
+                    reason: err.to_string(),
+                });
+                true
+            }
+        })
+        .collect();
+    (kept, events)
+}
+
+fn classify_trigger_kind(pr: &PullRequest, username: &str) -> Option<ReviewTriggerKind> {
+    if pr.author == username {
+        if pr.is_draft {
+            return None;
+        }
+        return Some(ReviewTriggerKind::SelfReview);
+    }
+    Some(ReviewTriggerKind::Review)
+}
+
+fn should_auto_approve(pr: &PullRequest, auto_approve_rules: &[AutoApproveRule]) -> bool {
+    let repo = pr.repo_name.trim().to_lowercase();
+    let author = pr.author.trim().to_lowercase();
+
+    auto_approve_rules.iter().any(|rule| {
+        let repo_pattern = rule.repo.trim().to_lowercase();
+        let user_pattern = rule.user.trim().to_lowercase();
+        if repo_pattern.is_empty() || user_pattern.is_empty() {
+            return false;
+        }
+
+        wildcard_match(&repo_pattern, &repo) && wildcard_match(&user_pattern, &author)
+    })
+}
+
+fn select_trigger_action(
+    pr: &PullRequest,
+    trigger_kind: ReviewTriggerKind,
+    auto_approve_rules: &[AutoApproveRule],
+) -> TriggerAction {
+    if trigger_kind == ReviewTriggerKind::Review && should_auto_approve(pr, auto_approve_rules) {
+        TriggerAction::AutoApprove
+    } else {
+        TriggerAction::Review(trigger_kind)
+    }
+}
+
+fn candidate_action_allowed(
+    candidate: &DaemonReviewCandidate,
+    action: TriggerAction,
+    excluded_users: &[String],
+) -> bool {
+    action == TriggerAction::AutoApprove
+        || !author_excluded(
+            &candidate.pr.author,
+            candidate.pr.author_kind.as_deref(),
+            excluded_users,
+        )
+}
+
+fn trigger_action(
+    pr: &PullRequest,
+    repos_root: &Path,
+    ai: &AiConfig,
+    action: TriggerAction,
+    run: &mut RunRecord,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> Result<()> {
+    match action {
+        TriggerAction::Review(trigger_kind) => {
+            trigger_review(pr, repos_root, ai, trigger_kind, run)
+        }
+        TriggerAction::AutoApprove => {
+            run.commands.push(format!(
+                "gh pr review {} --repo {} --approve",
+                pr.number, pr.repo_name
+            ));
+            gh::approve_pr(pr, None).with_context(|| {
+                format!("Failed to auto-approve {}#{}", pr.repo_name, pr.number)
+            })?;
+            if let Err(err) = audit::record(&audit::AuditEntry::new("auto-approve", pr)) {
+                on_event(PollEvent::Warning(format!(
+                    "Failed to write audit log: {:#}",
+                    err
+                )));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Run `action` for `pr` and save its run artifacts. Returns the outcome and the run directory.
+fn run_trigger(
+    pr: &PullRequest,
+    repos_root: &Path,
+    ai: &AiConfig,
+    action: TriggerAction,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> (Result<()>, Option<PathBuf>) {
+    let provider = matches!(action, TriggerAction::Review(_)).then(|| ai.provider_key());
+    let mut run = RunRecord::start(pr, action.label(), provider);
+    let outcome = trigger_action(pr, repos_root, ai, action, &mut run, on_event);
+    run.finish(&outcome);
+    let run_dir = match run.save(&run_artifacts::runs_dir()) {
+        Ok(dir) => Some(dir),
+        Err(err) => {
+            on_event(PollEvent::Warning(format!(
+                "Failed to save run artifacts for {}#{}: {:#}",
+                pr.repo_name, pr.number, err
+            )));
+            None
+        }
+    };
+    (outcome, run_dir)
+}
+
+fn collect_open_prs(
+    repos: &[RepoDescriptor],
+    excluded_repos: &HashSet<String>,
+    repo_subpath_filters: &RepoSubpathFilterMap,
+    username: &str,
+    include_drafts: bool,
+    drafts: &DraftsConfig,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> Vec<DaemonReviewCandidate> {
+    let (prs, errors) = collect_monitored_prs(
+        repos,
+        excluded_repos,
+        repo_subpath_filters,
+        username,
+        include_drafts,
+        drafts,
+        on_event,
+    );
+    for error in errors {
+        on_event(PollEvent::ListFailed {
+            repo: error.repo,
+            reason: error.reason,
+        });
+    }
+    prs.into_iter()
+        .filter_map(|pr| {
+            classify_trigger_kind(&pr, username)
+                .map(|trigger_kind| DaemonReviewCandidate { pr, trigger_kind })
+        })
+        .collect()
+}
+
+fn collect_monitored_prs(
+    repos: &[RepoDescriptor],
+    excluded_repos: &HashSet<String>,
+    repo_subpath_filters: &RepoSubpathFilterMap,
+    username: &str,
+    include_drafts: bool,
+    drafts: &DraftsConfig,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> (Vec<PullRequest>, Vec<gh::RepoFetchError>) {
+    let results = repos
+        .par_iter()
+        .filter(|repo| !excluded_repos.contains(&repo.name))
+        .map(|repo| {
+            let include_drafts = drafts.include_for(&repo.name, include_drafts);
+            gh::fetch_prs_for_repo_with_authored(&repo.path, username, include_drafts)
+                .map(|prs| apply_repo_subpath_filter(repo, prs, repo_subpath_filters))
+                .map_err(|err| gh::RepoFetchError {
+                    repo: repo.name.clone(),
+                    reason: format!("{:#}", err),
+                })
+        })
+        .collect::<Vec<_>>();
+    let mut prs = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok((repo_prs, events)) => {
+                prs.extend(repo_prs);
+                events.into_iter().for_each(&mut *on_event);
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+    (prs, errors)
+}
+
+/// Open PRs across the watched repos, newest first, and the repos that could not be listed.
+pub fn list_watched_prs(
+    cfg: &Config,
+    repos_root: &Path,
+    username: &str,
+    include_drafts: bool,
+) -> (Vec<PullRequest>, Vec<gh::RepoFetchError>) {
+    let repos = discover_repos(repos_root, &cfg.exclude);
+    let excluded_repos = monitored_repo_set(&cfg.daemon.exclude_repos);
+    let repo_subpath_filters = normalize_repo_subpath_filters(&cfg.daemon.repo_subpath_filters);
+    let (mut prs, errors) = collect_monitored_prs(
+        &repos,
+        &excluded_repos,
+        &repo_subpath_filters,
+        username,
+        include_drafts,
+        &cfg.drafts,
+        // Repo failures are returned; the rest would only clutter the caller's screen
+        &mut |event| tracing::warn!("{event}"),
+    );
+    prs.sort_by(|a, b| {
+        b.updated_at
+            .cmp(&a.updated_at)
+            .then_with(|| a.repo_name.cmp(&b.repo_name))
+            .then_with(|| a.number.cmp(&b.number))
+    });
+    (prs, errors)
+}
+
+fn build_seed_record(pr: &PullRequest, now: DateTime<Utc>) -> ReviewedPrRecord {
+    ReviewedPrRecord {
+        repo: pr.repo_name.clone(),
+        pr_number: pr.number,
+        first_seen_at: now,
+        last_seen_at: now,
+        latest_updated_at: pr.updated_at,
+        triggered_at: None,
+        trigger_status: TriggerStatus::Seeded,
+        last_error: None,
+        triage: None,
+        provider: None,
+        last_run_dir: None,
+    }
+}
+
+/// Reason a PR is trivial according to the path and size heuristics, if it is.
+fn heuristic_triage(changed_files: &[gh::ChangedFile], triage: &TriageConfig) -> Option<String> {
+    if changed_files.is_empty() {
+        return None;
+    }
+    if changed_files.iter().all(|file| {
+        triage
+            .trivial_paths
+            .iter()
+            .any(|pattern| path_glob_match(pattern, &file.path))
+    }) {
+        return Some(format!(
+            "only touches trivial paths ({} files)",
+            changed_files.len()
+        ));
+    }
+
+    let changed_lines: u64 = changed_files
+        .iter()
+        .map(|file| file.additions + file.deletions)
+        .sum();
+    (changed_lines <= triage.trivial_max_lines)
+        .then(|| format!("only {} changed lines", changed_lines))
+}
+
+/// Classify a new PR as trivial or needing review. Errors fall back to needs-review.
+fn triage_pr(pr: &PullRequest, triage: &TriageConfig, api: &AiLaunchApiConfig) -> TriageDecision {
+    let decision = |verdict, reason: String| TriageDecision {
+        verdict,
+        reason,
+        decided_at: Utc::now(),
+    };
+
+    match gh::get_pr_files(pr) {
+        Ok(files) => {
+            if let Some(reason) = heuristic_triage(&files, triage) {
+                return decision(TriageVerdict::Trivial, reason);
+            }
+        }
+        Err(err) => {
+            return decision(
+                TriageVerdict::NeedsReview,
+                format!("could not list changed files: {:#}", err),
+            );
+        }
+    }
+
+    let Some(model) = triage
+        .model
+        .as_deref()
+        .filter(|model| !model.trim().is_empty())
+    else {
+        return decision(
+            TriageVerdict::NeedsReview,
+            "not trivial by path or size".to_string(),
+        );
+    };
+    match ai_api::triage(pr, api, model) {
+        Ok(verdict) if verdict.trivial => decision(TriageVerdict::Trivial, verdict.reason),
+        Ok(verdict) => decision(TriageVerdict::NeedsReview, verdict.reason),
+        Err(err) => decision(
+            TriageVerdict::NeedsReview,
+            format!("triage model failed: {:#}", err),
+        ),
+    }
+}
+
+fn trigger_review(
+    pr: &PullRequest,
+    repos_root: &Path,
+    ai: &AiConfig,
+    trigger_kind: ReviewTriggerKind,
+    run: &mut RunRecord,
+) -> Result<()> {
+    let worktree_path = gh::create_pr_worktree(pr, repos_root).with_context(|| {
+        format!(
+            "Failed to create worktree for {}#{}",
+            pr.repo_name, pr.number
+        )
+    })?;
+    run.worktree = Some(worktree_path.clone());
+    let launched = gh::AiLaunch::prepare(&worktree_path, pr, ai).and_then(|launch| {
+        run.prompt = Some(launch.prompt.clone());
+        run.commands = launch.commands();
+        launch.run(&mut run.evidence)
+    });
+    launched.with_context(|| {
+        format!(
+            "Failed to launch AI {} for {}#{}",
+            trigger_kind.label(),
+            pr.repo_name,
+            pr.number
+        )
+    })?;
+    Ok(())
+}
+
+fn ai_config_for_trigger_kind(ai: &AiConfig, trigger_kind: ReviewTriggerKind) -> Option<AiConfig> {
+    if ai.launch.uses_tmux() || ai.launch.uses_api() || ai.launch.uses_terminal() {
+        return Some(ai.clone());
+    }
+
+    if !ai.launch.is_configured() {
+        return None;
+    }
+
+    if trigger_kind == ReviewTriggerKind::SelfReview && !ai.launch.self_review_steps.is_empty() {
+        let mut self_review_ai = ai.clone();
+        self_review_ai.launch.steps = ai.launch.self_review_steps.clone();
+        return Some(self_review_ai);
+    }
+
+    Some(ai.clone())
+}
+
+fn ai_config_for_action<'a>(
+    action: TriggerAction,
+    review_ai: &'a Option<AiConfig>,
+    self_review_ai: &'a Option<AiConfig>,
+    default_ai: &'a AiConfig,
+) -> Option<&'a AiConfig> {
+    match action {
+        TriggerAction::AutoApprove => Some(default_ai),
+        TriggerAction::Review(ReviewTriggerKind::Review) => review_ai.as_ref(),
+        TriggerAction::Review(ReviewTriggerKind::SelfReview) => self_review_ai.as_ref(),
+    }
+}
+
+pub(crate) fn seed_existing_open_prs(
+    state: &mut DaemonState,
+    repos: &[RepoDescriptor],
+    cfg: &Config,
+    username: &str,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> usize {
+    let excluded_repos = monitored_repo_set(&cfg.daemon.exclude_repos);
+    let excluded_users = normalize_user_patterns(&cfg.exclude_users);
+    let repo_subpath_filters = normalize_repo_subpath_filters(&cfg.daemon.repo_subpath_filters);
+    let prs = collect_open_prs(
+        repos,
+        &excluded_repos,
+        &repo_subpath_filters,
+        username,
+        cfg.daemon.include_drafts,
+        &cfg.drafts,
+        on_event,
+    )
+    .into_iter()
+    .filter(|candidate| {
+        !author_excluded(
+            &candidate.pr.author,
+            candidate.pr.author_kind.as_deref(),
+            &excluded_users,
+        )
+    })
+    .collect::<Vec<_>>();
+    let now = Utc::now();
+    let mut seeded = 0usize;
+
+    for candidate in prs {
+        let pr = candidate.pr;
+        let key = pr_key(&pr.repo_name, pr.number);
+        if let Some(existing) = state.prs.get_mut(&key) {
+            existing.last_seen_at = now;
+            existing.latest_updated_at = pr.updated_at;
+            continue;
+        }
+        state.prs.insert(key, build_seed_record(&pr, now));
+        seeded += 1;
+    }
+
+    state.last_poll_at = Some(now);
+    seeded
+}
+
+/// Whether a scheduled batch time falls after the last flush and at or before `now`.
+fn batch_flush_due<Tz: TimeZone>(
+    times: &[NaiveTime],
+    last_flush: DateTime<Tz>,
+    now: DateTime<Tz>,
+) -> bool {
+    if times.is_empty() || now <= last_flush {
+        return false;
+    }
+    if now.clone() - last_flush.clone() >= chrono::Duration::days(1) {
+        return true;
+    }
+    let timezone = now.timezone();
+    [last_flush.date_naive(), now.date_naive()]
+        .iter()
+        .flat_map(|date| times.iter().map(move |time| date.and_time(*time)))
+        .filter_map(|scheduled| scheduled.and_local_timezone(timezone.clone()).earliest())
+        .any(|scheduled| scheduled > last_flush && scheduled <= now)
+}
+
+fn batch_drain_due(
+    cfg: &Config,
+    state: &mut DaemonState,
+    now: DateTime<Utc>,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> bool {
+    if !cfg.daemon.batch.enabled {
+        return false;
+    }
+    let times = match cfg.daemon.batch.parsed_times() {
+        Ok(times) => times,
+        Err(err) => {
+            on_event(PollEvent::Warning(format!(
+                "Batch queue will not drain on schedule: {:#}",
+                err
+            )));
+            return false;
+        }
+    };
+    let Some(last_flush) = state.last_flush_at else {
+        // First batched poll: start the schedule from now.
+        state.last_flush_at = Some(now);
+        return false;
+    };
+    batch_flush_due(
+        &times,
+        last_flush.with_timezone(&Local),
+        now.with_timezone(&Local),
+    )
+}
+
+/// Poll the watched repos once at `now`: record new PRs in `state`, triage, queue or launch their
+/// triggers, retry failed ones and nudge PRs waiting too long. With `force_flush`, queued reviews
+/// launch regardless of the batch schedule. Loading and saving `state` is up to the caller.
+pub fn poll(
+    cfg: &Config,
+    repos_root: &Path,
+    username: &str,
+    state: &mut DaemonState,
+    now: DateTime<Utc>,
+    force_flush: bool,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> PollSummary {
+    let repos = discover_repos(repos_root, &cfg.exclude);
+    let excluded_repos = monitored_repo_set(&cfg.daemon.exclude_repos);
+    let repo_subpath_filters = normalize_repo_subpath_filters(&cfg.daemon.repo_subpath_filters);
+    let monitored_repos = repos
+        .iter()
+        .filter(|repo| !excluded_repos.contains(&repo.name))
+        .count();
+    let mut unlisted_repos = HashSet::new();
+    let open_prs = collect_open_prs(
+        &repos,
+        &excluded_repos,
+        &repo_subpath_filters,
+        username,
+        cfg.daemon.include_drafts,
+        &cfg.drafts,
+        &mut |event| {
+            if let PollEvent::ListFailed { repo, .. } = &event {
+                unlisted_repos.insert(repo.clone());
+            }
+            on_event(event)
+        },
+    );
+    let auto_approve_rules = normalize_auto_approve_rules(&cfg.daemon.auto_approve);
+    let excluded_users = normalize_user_patterns(&cfg.exclude_users);
+    let candidate_actions = open_prs
+        .into_iter()
+        .map(|candidate| {
+            let action =
+                select_trigger_action(&candidate.pr, candidate.trigger_kind, &auto_approve_rules);
+            (candidate, action)
+        })
+        .filter(|(candidate, action)| candidate_action_allowed(candidate, *action, &excluded_users))
+        .collect::<Vec<_>>();
+    let mut review_ai = ai_config_for_trigger_kind(&cfg.ai, ReviewTriggerKind::Review);
+    let mut self_review_ai = ai_config_for_trigger_kind(&cfg.ai, ReviewTriggerKind::SelfReview);
+    let has_review_actions = candidate_actions
+        .iter()
+        .any(|(_, action)| *action == TriggerAction::Review(ReviewTriggerKind::Review));
+    let has_self_review_actions = candidate_actions
+        .iter()
+        .any(|(_, action)| *action == TriggerAction::Review(ReviewTriggerKind::SelfReview));
+    if has_review_actions {
+        if let Some(ai_cfg) = review_ai.as_ref() {
+            if let Err(err) = gh::validate_ai_launch_config(ai_cfg) {
+                on_event(PollEvent::Warning(format!(
+                    "Skipping review triggers this poll: invalid ai.launch config: {:#}",
+                    err
+                )));
+                review_ai = None;
+            }
+        } else {
+            on_event(PollEvent::Warning(
+                "Skipping review triggers this poll: ai.launch is not configured. Configure ai.launch.steps or ai.launch.backend.".to_string(),
+            ));
+        }
+    }
+    if has_self_review_actions {
+        if let Some(ai_cfg) = self_review_ai.as_ref() {
+            if let Err(err) = gh::validate_ai_launch_config(ai_cfg) {
+                on_event(PollEvent::Warning(format!(
+                    "Skipping self-review triggers this poll: invalid launcher config: {:#}",
+                    err
+                )));
+                self_review_ai = None;
+            }
+        } else {
+            on_event(PollEvent::Warning(
+                "Skipping self-review triggers this poll: ai.launch is not configured. Configure ai.launch.steps, ai.launch.self_review_steps, or ai.launch.backend.".to_string(),
+            ));
+        }
+    }
+    let open_pr_count = candidate_actions.len();
+    let open_keys = candidate_actions
+        .iter()
+        .map(|(candidate, _)| pr_key(&candidate.pr.repo_name, candidate.pr.number))
+        .collect::<HashSet<_>>();
+    let escalation_prs = if cfg.daemon.escalation.repos.is_empty() {
+        Vec::new()
+    } else {
+        candidate_actions
+            .iter()
+            .map(|(candidate, _)| candidate.pr.clone())
+            .collect()
+    };
+
+    let mut new_prs = 0usize;
+    let mut triggered = 0usize;
+    let mut skipped = 0usize;
+    let mut queued = 0usize;
+    let mut failed = 0usize;
+    let draining = force_flush || batch_drain_due(cfg, state, now, on_event);
+    if draining {
+        state.last_flush_at = Some(now);
+    }
+
+    for (candidate, action) in candidate_actions {
+        let DaemonReviewCandidate { pr, .. } = candidate;
+        let ai_for_action = ai_config_for_action(action, &review_ai, &self_review_ai, &cfg.ai);
+        let key = pr_key(&pr.repo_name, pr.number);
+        let action_label = action.label();
+        if let Some(existing) = state.prs.get_mut(&key) {
+            existing.last_seen_at = now;
+            existing.latest_updated_at = pr.updated_at;
+
+            let was_queued = existing.trigger_status == TriggerStatus::Queued;
+            if existing.trigger_status != TriggerStatus::Failed && !(was_queued && draining) {
+                continue;
+            }
+            let Some(ai_config) = ai_for_action else {
+                // Missing or invalid launcher config for this action; do not retry yet.
+                continue;
+            };
+
+            on_event(PollEvent::Relaunching {
+                action: action_label,
+                pr: key.clone(),
+                queued: was_queued,
+            });
+            let (outcome, run_dir) = run_trigger(&pr, repos_root, ai_config, action, on_event);
+            existing.last_run_dir = run_dir;
+            match outcome {
+                Ok(()) => {
+                    existing.triggered_at = Some(Utc::now());
+                    existing.trigger_status = TriggerStatus::Success;
+                    existing.last_error = None;
+                    existing.provider = Some(ai_config.provider_key().to_string());
+                    triggered += 1;
+                    on_event(PollEvent::Triggered {
+                        action: action_label,
+                        pr: key.clone(),
+                    });
+                }
+                Err(err) => {
+                    existing.trigger_status = TriggerStatus::Failed;
+                    existing.last_error = Some(format!("{:#}", err));
+                    failed += 1;
+                    on_event(PollEvent::TriggerFailed {
+                        action: action_label,
+                        pr: key.clone(),
+                        retry: true,
+                        error: format!("{:#}", err),
+                    });
+                }
+            }
+            continue;
+        }
+        let Some(ai_config) = ai_for_action else {
+            // Missing or invalid launcher config for this action; keep PR unseen for future polls.
+            continue;
+        };
+
+        new_prs += 1;
+        on_event(PollEvent::NewPr {
+            action: action_label,
+            pr: key.clone(),
+            title: pr.title.clone(),
+        });
+
+        let mut record = build_seed_record(&pr, now);
+        if cfg.daemon.triage.enabled && action == TriggerAction::Review(ReviewTriggerKind::Review) {
+            let decision = triage_pr(&pr, &cfg.daemon.triage, &cfg.ai.launch.api);
+            let verdict = decision.verdict;
+            on_event(PollEvent::Triaged {
+                pr: key.clone(),
+                trivial: verdict == TriageVerdict::Trivial,
+                reason: decision.reason.clone(),
+            });
+            record.triage = Some(decision);
+            if verdict == TriageVerdict::Trivial {
+                record.trigger_status = TriggerStatus::Skipped;
+                skipped += 1;
+                state.prs.insert(key, record);
+                continue;
+            }
+        }
+
+        if cfg.daemon.batch.enabled && !draining && matches!(action, TriggerAction::Review(_)) {
+            record.trigger_status = TriggerStatus::Queued;
+            queued += 1;
+            on_event(PollEvent::Queued {
+                action: action_label,
+                pr: key.clone(),
+            });
+            state.prs.insert(key, record);
+            continue;
+        }
+
+        let (outcome, run_dir) = run_trigger(&pr, repos_root, ai_config, action, on_event);
+        record.last_run_dir = run_dir;
+        match outcome {
+            Ok(()) => {
+                record.triggered_at = Some(Utc::now());
+                record.trigger_status = TriggerStatus::Success;
+                record.provider = Some(ai_config.provider_key().to_string());
+                triggered += 1;
+                on_event(PollEvent::Triggered {
+                    action: action_label,
+                    pr: key.clone(),
+                });
+            }
+            Err(err) => {
+                record.trigger_status = TriggerStatus::Failed;
+                record.last_error = Some(format!("{:#}", err));
+                failed += 1;
+                on_event(PollEvent::TriggerFailed {
+                    action: action_label,
+                    pr: key.clone(),
+                    retry: false,
+                    error: format!("{:#}", err),
+                });
+            }
+        }
+
+        state.prs.insert(key, record);
+    }
+
+    let nudged = escalate_waiting_prs(
+        &cfg.daemon.escalation,
+        state,
+        escalation_prs,
+        username,
+        now,
+        on_event,
+    );
+    prune_nudges(state, &open_keys, &unlisted_repos);
+
+    state.last_poll_at = Some(now);
+
+    PollSummary {
+        monitored_repos,
+        open_prs: open_pr_count,
+        new_prs,
+        triggered,
+        skipped,
+        queued,
+        failed,
+        nudged,
+    }
+}
+
+/// Whether `pr` has waited `after_hours` without any review (since it opened, or since the last
+/// nudge) in an opted-in repo, and still has nudges left.
+fn nudge_due(
+    pr: &PullRequest,
+    previous: Option<&NudgeRecord>,
+    escalation: &EscalationConfig,
+    username: &str,
+    now: DateTime<Utc>,
+) -> bool {
+    if pr.is_draft || !escalation.applies_to(&pr.repo_name) {
+        return false;
+    }
+    // Any review counts, and a PR waiting on me is the daemon's own review to launch.
+    if pr.reviewers.iter().any(|reviewer| {
+        reviewer.state != ReviewerState::Pending || reviewer.login.eq_ignore_ascii_case(username)
+    }) {
+        return false;
+    }
+    let waiting_since = match previous {
+        Some(previous) if previous.count >= escalation.max_per_pr => return false,
+        Some(previous) => previous.last_nudged_at,
+        None => pr.created_at,
+    };
+    now.signed_duration_since(waiting_since)
+        >= chrono::Duration::hours(escalation.after_hours.min(i64::MAX as u64) as i64)
+}
+
+fn render_nudge(template: &str, pr: &PullRequest, now: DateTime<Utc>) -> String {
+    let hours = now.signed_duration_since(pr.created_at).num_hours().max(0);
+    let age = if hours >= 48 {
+        format!("{} days", hours / 24)
+    } else {
+        format!("{} hours", hours)
+    };
+    let reviewers = pr
+        .reviewers
+        .iter()
+        .map(|reviewer| format!("@{}", reviewer.login))
+        .collect::<Vec<_>>();
+    let reviewers = if reviewers.is_empty() {
+        "reviewers".to_string()
+    } else {
+        reviewers.join(", ")
+    };
+    template
+        .replace("{age}", &age)
+        .replace("{reviewers}", &reviewers)
+        .replace("{author}", &pr.author)
+        .replace("{title}", &pr.title)
+        .replace("{url}", &pr.url)
+}
+
+/// Forget nudges sent to PRs that are no longer open or watched, so `daemon status` counts
+/// only live ones. Repos whose listing failed this poll keep theirs until they list again.
+fn prune_nudges(state: &mut DaemonState, open: &HashSet<String>, unlisted_repos: &HashSet<String>) {
+    state.nudges.retain(|key, _| {
+        open.contains(key)
+            || key
+                .rsplit_once('#')
+                .is_some_and(|(repo, _)| unlisted_repos.contains(repo))
+    });
+}
+
+/// Nudge the longest-waiting unreviewed PRs, at most `max_per_poll` of them. Failed nudges are
+/// not recorded, so they are retried next poll. Returns how many were sent.
+fn escalate_waiting_prs(
+    escalation: &EscalationConfig,
+    state: &mut DaemonState,
+    mut prs: Vec<PullRequest>,
+    username: &str,
+    now: DateTime<Utc>,
+    on_event: &mut dyn FnMut(PollEvent),
+) -> usize {
+    prs.sort_by_key(|pr| pr.created_at);
+    let mut nudged = 0usize;
+    for pr in &prs {
+        if nudged >= escalation.max_per_poll {
+            break;
+        }
+        let key = pr_key(&pr.repo_name, pr.number);
+        if !nudge_due(pr, state.nudges.get(&key), escalation, username, now) {
+            continue;
+        }
+        let message = render_nudge(&escalation.template, pr, now);
+        let outcome = match escalation.action {
+            EscalationAction::Comment => gh::add_pr_comment(pr, &message),
+            EscalationAction::Notify => notify::send(
+                &format!("{}#{} is waiting for review", pr.repo_name, pr.number),
+                &message,
+            ),
+        };
+        match outcome {
+            Ok(()) => {
+                let count = state.nudges.get(&key).map_or(0, |previous| previous.count);
+                state.nudges.insert(
+                    key.clone(),
+                    NudgeRecord {
+                        count: count + 1,
+                        last_nudged_at: now,
+                    },
+                );
+                nudged += 1;
+                on_event(PollEvent::Nudged {
+                    pr: key,
+                    title: pr.title.clone(),
+                });
+            }
+            Err(err) => {
+                on_event(PollEvent::NudgeFailed {
+                    pr: key,
+                    error: format!("{:#}", err),
+                });
+            }
+        }
+    }
+    nudged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gh::ReviewState;
+
+    fn make_test_pr(author: &str, is_draft: bool) -> PullRequest {
+        PullRequest {
+            number: 42,
+            title: "Test PR".to_string(),
+            author: author.to_string(),
+            author_kind: Some("User".to_string()),
+            body: String::new(),
+            repo_path: PathBuf::from("/tmp/repo"),
+            repo_name: "org/reviewer".to_string(),
+            url: "https://example.com/pr/42".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            additions: 1,
+            deletions: 1,
+            is_draft,
+            labels: Vec::new(),
+            base_branch: String::new(),
+            head_branch: String::new(),
+            head_oid: String::new(),
+            mergeable: None,
+            review_state: ReviewState::Pending,
+            reviewers: Vec::new(),
+            details_loaded: true,
+        }
+    }
+
+    #[test]
+    fn normalize_subpaths_trims_and_dedups() {
+        let paths = vec![
+            " src ".to_string(),
+            "/src/".to_string(),
+            "services/api".to_string(),
+            "".to_string(),
+            "   ".to_string(),
+        ];
+
+        assert_eq!(
+            normalize_subpaths(&paths),
+            vec!["services/api".to_string(), "src".to_string()]
+        );
+    }
+
+    #[test]
+    fn path_matches_subpath_enforces_path_boundaries() {
+        assert!(path_matches_subpath("src/main.rs", "src"));
+        assert!(path_matches_subpath("src", "src"));
+        assert!(!path_matches_subpath("src2/main.rs", "src"));
+        assert!(!path_matches_subpath("nested/src/main.rs", "src"));
+    }
+
+    #[test]
+    fn pr_touches_any_subpath_matches_any_changed_file() {
+        let changed_files = vec![
+            "docs/readme.md".to_string(),
+            "services/api/handler.rs".to_string(),
+        ];
+
+        assert!(pr_touches_any_subpath(
+            &changed_files,
+            &["services/api".to_string(), "frontend".to_string()]
+        ));
+        assert!(!pr_touches_any_subpath(
+            &changed_files,
+            &["frontend".to_string(), "infra".to_string()]
+        ));
+    }
+
+    #[test]
+    fn normalize_repo_subpath_filters_skips_blank_repo_keys() {
+        let mut filters = HashMap::new();
+        filters.insert("  ".to_string(), vec!["src".to_string()]);
+        filters.insert(
+            "org/repo".to_string(),
+            vec!["/src/".to_string(), "".to_string()],
+        );
+
+        let normalized = normalize_repo_subpath_filters(&filters);
+        assert_eq!(normalized.len(), 1);
+        assert_eq!(normalized.get("org/repo"), Some(&vec!["src".to_string()]));
+    }
+
+    #[test]
+    fn normalize_auto_approve_rules_trims_lowercases_and_dedups() {
+        let rules = vec![
+            AutoApproveRule {
+                repo: " Org/Repo ".to_string(),
+                user: " Alice ".to_string(),
+            },
+            AutoApproveRule {
+                repo: "org/repo".to_string(),
+                user: "alice".to_string(),
+            },
+            AutoApproveRule {
+                repo: "org/other".to_string(),
+                user: "".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            normalize_auto_approve_rules(&rules),
+            vec![AutoApproveRule {
+                repo: "org/repo".to_string(),
+                user: "alice".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn wildcard_match_supports_star_and_question() {
+        assert!(wildcard_match("org/*", "org/reviewer"));
+        assert!(wildcard_match("*bot", "dependabot"));
+        assert!(wildcard_match("renovate[bo?]", "renovate[bot]"));
+        assert!(!wildcard_match("org/*", "other/reviewer"));
+        assert!(!wildcard_match("*bot", "alice"));
+    }
+
+    #[test]
+    fn heuristic_triage_marks_docs_only_and_tiny_prs_trivial() {
+        let file = |path: &str, additions, deletions| gh::ChangedFile {
+            path: path.to_string(),
+            additions,
+            deletions,
+            ..gh::ChangedFile::default()
+        };
+        let triage = TriageConfig::default();
+
+        let docs = vec![file("README.md", 40, 3), file("docs/setup.md", 12, 0)];
+        assert!(heuristic_triage(&docs, &triage).is_some());
+
+        let tiny = vec![file("src/lib.rs", 2, 1)];
+        assert!(heuristic_triage(&tiny, &triage).is_some());
+
+        let code = vec![file("src/lib.rs", 30, 4), file("README.md", 1, 0)];
+        assert!(heuristic_triage(&code, &triage).is_none());
+        assert!(heuristic_triage(&[], &triage).is_none());
+    }
+
+    #[test]
+    fn batch_flush_due_only_after_a_scheduled_time_passes() {
+        let times = vec![
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+        ];
+        let at = |day, hour, minute| Utc.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap();
+
+        assert!(!batch_flush_due(&times, at(2, 9, 30), at(2, 13, 59)));
+        assert!(batch_flush_due(&times, at(2, 9, 30), at(2, 14, 0)));
+        assert!(batch_flush_due(&times, at(2, 15, 0), at(3, 9, 5)));
+        assert!(!batch_flush_due(&times, at(2, 15, 0), at(2, 23, 0)));
+        assert!(batch_flush_due(&times, at(2, 15, 0), at(4, 8, 0)));
+        assert!(!batch_flush_due(&[], at(2, 8, 0), at(2, 10, 0)));
+    }
+
+    #[test]
+    fn should_auto_approve_supports_case_insensitive_patterns() {
+        let mut pr = make_test_pr("Dependabot[Bot]", false);
+        pr.repo_name = "Org/Reviewer".to_string();
+
+        let rules = vec![AutoApproveRule {
+            repo: "org/*".to_string(),
+            user: "*bot]".to_string(),
+        }];
+
+        assert!(should_auto_approve(&pr, &rules));
+    }
+
+    #[test]
+    fn escalation_nudges_oldest_unreviewed_prs_within_caps() {
+        let runner = crate::process::replay::ReplayRunner::new();
+        runner.reply("gh pr comment", "");
+        let _guard = runner.install();
+        let now = Utc::now();
+        let pr = |number: u64, hours: i64, reviewers: &[(&str, ReviewerState)]| {
+            let mut pr = make_test_pr("alice", false);
+            pr.number = number;
+            pr.repo_name = "acme/widgets".to_string();
+            pr.created_at = now - chrono::Duration::hours(hours);
+            pr.reviewers = reviewers
+                .iter()
+                .map(|(login, state)| gh::ReviewerStatus {
+                    login: login.to_string(),
+                    state: *state,
+                    stale: false,
+                })
+                .collect();
+            pr
+        };
+        let mut draft = pr(5, 200, &[]);
+        draft.is_draft = true;
+        let mut other_repo = pr(6, 200, &[]);
+        other_repo.repo_name = "other/repo".to_string();
+        let prs = vec![
+            pr(1, 72, &[("bob", ReviewerState::Pending)]),
+            pr(2, 120, &[]),
+            pr(3, 120, &[("carol", ReviewerState::Commented)]),
+            pr(4, 120, &[("me", ReviewerState::Pending)]),
+            pr(7, 2, &[]),
+            draft,
+            other_repo,
+        ];
+        let escalation = EscalationConfig {
+            repos: vec!["acme/*".to_string()],
+            template: "Ping {reviewers}: waiting {age} on '{title}'".to_string(),
+            max_per_poll: 1,
+            ..EscalationConfig::default()
+        };
+        let mut state = DaemonState::default();
+
+        assert_eq!(
+            escalate_waiting_prs(&escalation, &mut state, prs.clone(), "me", now, &mut |_| {}),
+            1
+        );
+        assert_eq!(
+            escalate_waiting_prs(&escalation, &mut state, prs.clone(), "me", now, &mut |_| {}),
+            1
+        );
+        assert_eq!(
+            escalate_waiting_prs(&escalation, &mut state, prs, "me", now, &mut |_| {}),
+            0
+        );
+        assert_eq!(
+            runner.commands(),
+            vec![
+                "gh pr comment 2 --repo acme/widgets --body Ping reviewers: waiting 5 days on 'Test PR'",
+                "gh pr comment 1 --repo acme/widgets --body Ping @bob: waiting 3 days on 'Test PR'",
+            ]
+        );
+        assert_eq!(state.nudges["acme/widgets#1"].count, 1);
+    }
+
+    #[test]
+    fn prune_nudges_keeps_open_prs_and_repos_that_failed_to_list() {
+        let record = || NudgeRecord {
+            count: 1,
+            last_nudged_at: Utc::now(),
+        };
+        let mut state = DaemonState::default();
+        for key in ["acme/widgets#1", "acme/widgets#2", "other/repo#3"] {
+            state.nudges.insert(key.to_string(), record());
+        }
+        let open = HashSet::from(["acme/widgets#1".to_string()]);
+
+        prune_nudges(
+            &mut state,
+            &open,
+            &HashSet::from(["other/repo".to_string()]),
+        );
+        let mut kept = state.nudges.keys().cloned().collect::<Vec<_>>();
+        kept.sort();
+        assert_eq!(kept, vec!["acme/widgets#1", "other/repo#3"]);
+
+        prune_nudges(&mut state, &open, &HashSet::new());
+        assert_eq!(
+            state.nudges.keys().collect::<Vec<_>>(),
+            vec!["acme/widgets#1"]
+        );
+    }
+
+    #[test]
+    fn collect_open_prs_applies_per_repo_draft_overrides() {
+        let runner = crate::process::replay::ReplayRunner::new();
+        runner.reply(
+            "gh pr list",
+            r#"[
+                {"number": 7, "title": "Ready", "author": {"login": "alice"}, "url": "https://github.com/acme/widgets/pull/7",
+                 "updatedAt": "2026-03-10T00:00:00Z", "isDraft": false},
+                {"number": 9, "title": "WIP", "author": {"login": "alice"}, "url": "https://github.com/acme/widgets/pull/9",
+                 "updatedAt": "2026-03-10T00:00:00Z", "isDraft": true}
+            ]"#,
+        );
+        let _guard = runner.install();
+        let repos = vec![RepoDescriptor {
+            path: PathBuf::from("/tmp/widgets"),
+            name: "acme/widgets".to_string(),
+        }];
+        let numbers = |include_drafts: bool, drafts: &DraftsConfig| {
+            collect_open_prs(
+                &repos,
+                &HashSet::new(),
+                &HashMap::new(),
+                "me",
+                include_drafts,
+                drafts,
+                &mut |_| {},
+            )
+            .iter()
+            .map(|candidate| candidate.pr.number)
+            .collect::<Vec<_>>()
+        };
+
+        let always = DraftsConfig {
+            always: vec!["acme/*".to_string()],
+            never: Vec::new(),
+        };
+        assert_eq!(numbers(false, &DraftsConfig::default()), vec![7]);
+        assert_eq!(numbers(false, &always), vec![7, 9]);
+        let never = DraftsConfig {
+            always: vec!["acme/*".to_string()],
+            never: vec!["Acme/Widgets".to_string()],
+        };
+        assert_eq!(numbers(true, &never), vec![7]);
+    }
+
+    #[test]
+    fn collect_open_prs_applies_exclusions_and_subpath_filters() {
+        let runner = crate::process::replay::ReplayRunner::new();
+        runner
+            .reply(
+                "gh pr list",
+                r#"[
+                {"number": 7, "title": "Touch api", "author": {"login": "alice"}, "url": "https://github.com/acme/widgets/pull/7",
+                 "updatedAt": "2026-03-10T00:00:00Z", "isDraft": false},
+                {"number": 8, "title": "Touch docs", "author": {"login": "me"}, "url": "https://github.com/acme/widgets/pull/8",
+                 "updatedAt": "2026-03-10T00:00:00Z", "isDraft": false}
+            ]"#,
+            )
+            .reply(
+                "gh pr view 7 --repo acme/widgets --json files",
+                r#"{"files": [{"path": "api/server.rs"}]}"#,
+            )
+            .reply(
+                "gh pr view 8 --repo acme/widgets --json files",
+                r#"{"files": [{"path": "docs/index.md"}]}"#,
+            );
+        let _guard = runner.install();
+
+        let repos = vec![
+            RepoDescriptor {
+                path: PathBuf::from("/tmp/widgets"),
+                name: "acme/widgets".to_string(),
+            },
+            RepoDescriptor {
+                path: PathBuf::from("/tmp/legacy"),
+                name: "acme/legacy".to_string(),
+            },
+        ];
+        let excluded = monitored_repo_set(&["acme/legacy".to_string()]);
+        let mut filters = HashMap::new();
+        filters.insert("acme/widgets".to_string(), vec!["api".to_string()]);
+        let filters = normalize_repo_subpath_filters(&filters);
+
+        let candidates = collect_open_prs(
+            &repos,
+            &excluded,
+            &filters,
+            "me",
+            false,
+            &DraftsConfig::default(),
+            &mut |_| {},
+        );
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].pr.number, 7);
+        assert_eq!(candidates[0].trigger_kind, ReviewTriggerKind::Review);
+        assert_eq!(
+            runner
+                .commands()
+                .iter()
+                .filter(|command| command.starts_with("gh pr list"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn subpath_filter_reuses_changed_files_until_the_head_moves() {
+        let runner = crate::process::replay::ReplayRunner::new();
+        runner.reply(
+            "gh pr view 42 --repo acme/cached --json files",
+            r#"{"files": [{"path": "api/server.rs"}]}"#,
+        );
+        let _guard = runner.install();
+        let repo = RepoDescriptor {
+            path: PathBuf::from("/tmp/cached"),
+            name: "acme/cached".to_string(),
+        };
+        let mut filters = HashMap::new();
+        filters.insert("acme/cached".to_string(), vec!["api".to_string()]);
+        let filters = normalize_repo_subpath_filters(&filters);
+        let mut pr = make_test_pr("alice", false);
+        pr.repo_name = "acme/cached".to_string();
+        pr.head_oid = "aaa".to_string();
+        let lookups = || {
+            runner
+                .commands()
+                .iter()
+                .filter(|command| command.starts_with("gh pr view 42"))
+                .count()
+        };
+
+        for _ in 0..2 {
+            let (kept, events) = apply_repo_subpath_filter(&repo, vec![pr.clone()], &filters);
+            assert_eq!(kept.len(), 1);
+            assert!(events.is_empty());
+        }
+        assert_eq!(lookups(), 1);
+
+        pr.head_oid = "bbb".to_string();
+        apply_repo_subpath_filter(&repo, vec![pr.clone()], &filters);
+        assert_eq!(lookups(), 2);
+
+        // A closed PR is forgotten, so reopening it lists its files again
+        apply_repo_subpath_filter(&repo, Vec::new(), &filters);
+        apply_repo_subpath_filter(&repo, vec![pr], &filters);
+        assert_eq!(lookups(), 3);
+    }
+
+    #[test]
+    fn subpath_check_failures_let_the_pr_through_and_are_reported() {
+        let runner = crate::process::replay::ReplayRunner::new();
+        runner.fail("gh pr view 42 --repo acme/flaky --json files", "HTTP 502");
+        let _guard = runner.install();
+        let repo = RepoDescriptor {
+            path: PathBuf::from("/tmp/flaky"),
+            name: "acme/flaky".to_string(),
+        };
+        let mut filters = HashMap::new();
+        filters.insert("acme/flaky".to_string(), vec!["api".to_string()]);
+        let filters = normalize_repo_subpath_filters(&filters);
+        let mut pr = make_test_pr("alice", false);
+        pr.repo_name = "acme/flaky".to_string();
+
+        let (kept, events) = apply_repo_subpath_filter(&repo, vec![pr], &filters);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_failure());
+        let message = events[0].to_string();
+        assert!(
+            message.starts_with("Failed to evaluate daemon subpath filter for acme/flaky#42:"),
+            "{message}"
+        );
+        assert!(message.ends_with("Triggering review anyway."), "{message}");
+
+        let triggered = PollEvent::Triggered {
+            action: "review",
+            pr: "acme/flaky#42".to_string(),
+        };
+        assert!(!triggered.is_failure());
+        assert_eq!(triggered.to_string(), "Triggered review for acme/flaky#42");
+    }
+
+    #[test]
+    fn classify_trigger_kind_marks_other_authors_as_review() {
+        let pr = make_test_pr("alice", false);
+        assert_eq!(
+            classify_trigger_kind(&pr, "bob"),
+            Some(ReviewTriggerKind::Review)
+        );
+    }
+
+    #[test]
+    fn classify_trigger_kind_marks_authored_prs_as_self_review() {
+        let pr = make_test_pr("alice", false);
+        assert_eq!(
+            classify_trigger_kind(&pr, "alice"),
+            Some(ReviewTriggerKind::SelfReview)
+        );
+    }
+
+    #[test]
+    fn classify_trigger_kind_skips_authored_draft_prs() {
+        let pr = make_test_pr("alice", true);
+        assert_eq!(classify_trigger_kind(&pr, "alice"), None);
+    }
+
+    #[test]
+    fn ai_config_for_trigger_kind_requires_review_steps_for_review_action() {
+        let ai = AiConfig::default();
+        assert!(ai_config_for_trigger_kind(&ai, ReviewTriggerKind::Review).is_none());
+    }
+
+    #[test]
+    fn ai_config_for_trigger_kind_accepts_tmux_backend_without_steps() {
+        let mut ai = AiConfig::default();
+        ai.launch.backend = Some("tmux".to_string());
+
+        assert!(ai_config_for_trigger_kind(&ai, ReviewTriggerKind::Review).is_some());
+        assert!(ai_config_for_trigger_kind(&ai, ReviewTriggerKind::SelfReview).is_some());
+    }
+
+    #[test]
+    fn ai_config_for_trigger_kind_self_review_falls_back_to_review_steps() {
+        let mut ai = AiConfig::default();
+        ai.launch.steps = vec![crate::config::AiLaunchStepConfig {
+            command: "maestro".to_string(),
+            args: vec!["start".to_string()],
+        }];
+
+        let self_ai = ai_config_for_trigger_kind(&ai, ReviewTriggerKind::SelfReview)
+            .expect("self-review should use review steps when dedicated steps are absent");
+        assert_eq!(self_ai.launch.steps.len(), 1);
+        assert_eq!(self_ai.launch.steps[0].command, "maestro");
+    }
+
+    #[test]
+    fn ai_config_for_trigger_kind_self_review_prefers_self_review_steps() {
+        let mut ai = AiConfig::default();
+        ai.launch.steps = vec![crate::config::AiLaunchStepConfig {
+            command: "maestro".to_string(),
+            args: vec!["start".to_string()],
+        }];
+        ai.launch.self_review_steps = vec![crate::config::AiLaunchStepConfig {
+            command: "custom-self".to_string(),
+            args: vec!["run".to_string()],
+        }];
+
+        let self_ai = ai_config_for_trigger_kind(&ai, ReviewTriggerKind::SelfReview)
+            .expect("self-review should use dedicated self-review steps when configured");
+        assert_eq!(self_ai.launch.steps.len(), 1);
+        assert_eq!(self_ai.launch.steps[0].command, "custom-self");
+    }
+
+    #[test]
+    fn select_trigger_action_uses_auto_approve_for_matching_review_rule() {
+        let pr = make_test_pr("Alice", false);
+        let rules = vec![AutoApproveRule {
+            repo: "org/reviewer".to_string(),
+            user: "alice".to_string(),
+        }];
+
+        assert_eq!(
+            select_trigger_action(&pr, ReviewTriggerKind::Review, &rules),
+            TriggerAction::AutoApprove
+        );
+    }
+
+    #[test]
+    fn select_trigger_action_keeps_self_review_for_matching_rule() {
+        let pr = make_test_pr("alice", false);
+        let rules = vec![AutoApproveRule {
+            repo: "org/reviewer".to_string(),
+            user: "alice".to_string(),
+        }];
+
+        assert_eq!(
+            select_trigger_action(&pr, ReviewTriggerKind::SelfReview, &rules),
+            TriggerAction::Review(ReviewTriggerKind::SelfReview)
+        );
+    }
+
+    #[test]
+    fn excluded_user_filter_keeps_auto_approve_actions() {
+        let mut pr = make_test_pr("lpu-renovate", false);
+        pr.author_kind = Some("Bot".to_string());
+        let candidate = DaemonReviewCandidate {
+            pr,
+            trigger_kind: ReviewTriggerKind::Review,
+        };
+        let excluded_users = normalize_user_patterns(&["@apps/*".to_string()]);
+
+        assert!(candidate_action_allowed(
+            &candidate,
+            TriggerAction::AutoApprove,
+            &excluded_users
+        ));
+        assert!(!candidate_action_allowed(
+            &candidate,
+            TriggerAction::Review(ReviewTriggerKind::Review),
+            &excluded_users
+        ));
+    }
+}
@@ -1,7 +1,16 @@
-use crate::config::{self, AiConfig, AutoApproveRule, Config};
-use crate::filters::{author_excluded, normalize_user_patterns, wildcard_match};
-use crate::gh::{self, PullRequest};
-use crate::repos;
+use crate::auth;
+use crate::clone_cache;
+use crate::config::{self, AutoApproveRule, Config, ConfigWatcher};
+use crate::core::{
+    self, load_state, lock_state, normalize_auto_approve_rules, normalize_repo_names,
+    normalize_repo_subpath_filter_status, normalize_repo_subpath_filters, normalize_subpaths,
+    save_state, seed_existing_open_prs, state_path, PollEvent, PollSummary, RepoDescriptor,
+    RepoSubpathFilterMap, RepoSubpathFilterStatus, TriggerStatus,
+};
+use crate::filters::normalize_user_patterns;
+use crate::gh;
+use crate::private_fs;
+use crate::run_artifacts;
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
@@ -15,99 +24,27 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Line;
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
 use ratatui::{Frame, Terminal};
-use rayon::prelude::*;
-use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::ffi::OsString;
 use std::fs;
 use std::io::{self, Stdout};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
 use std::thread;
-use std::time::{Duration, SystemTime};
-
-#[derive(Debug, Clone)]
-struct RepoDescriptor {
-    path: PathBuf,
-    name: String,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ReviewTriggerKind {
-    Review,
-    SelfReview,
-}
-
-impl ReviewTriggerKind {
-    fn label(self) -> &'static str {
-        match self {
-            Self::Review => "review",
-            Self::SelfReview => "self-review",
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TriggerAction {
-    Review(ReviewTriggerKind),
-    AutoApprove,
-}
-
-impl TriggerAction {
-    fn label(self) -> &'static str {
-        match self {
-            Self::Review(kind) => kind.label(),
-            Self::AutoApprove => "auto-approve",
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct DaemonReviewCandidate {
-    pr: PullRequest,
-    trigger_kind: ReviewTriggerKind,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub enum TriggerStatus {
-    Seeded,
-    Success,
-    Failed,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ReviewedPrRecord {
-    pub repo: String,
-    pub pr_number: u64,
-    pub first_seen_at: DateTime<Utc>,
-    pub last_seen_at: DateTime<Utc>,
-    pub latest_updated_at: DateTime<Utc>,
-    pub triggered_at: Option<DateTime<Utc>>,
-    pub trigger_status: TriggerStatus,
-    pub last_error: Option<String>,
-}
-
-#[derive(Debug, Default, Serialize, Deserialize)]
-pub struct DaemonState {
-    #[serde(default)]
-    pub prs: HashMap<String, ReviewedPrRecord>,
-    #[serde(default)]
-    pub last_poll_at: Option<DateTime<Utc>>,
-}
-
+use std::time::{Duration, Instant, SystemTime};
 #[derive(Debug)]
-pub struct PollSummary {
-    pub monitored_repos: usize,
-    pub open_prs: usize,
-    pub new_prs: usize,
-    pub triggered: usize,
-    pub failed: usize,
+pub struct FailedTriggerStatus {
+    pub pr: String,
+    pub error: Option<String>,
+    pub run_dir: Option<PathBuf>,
 }
 
 #[derive(Debug)]
 pub struct DaemonStatus {
     pub state_path: PathBuf,
+    pub runs_dir: PathBuf,
+    pub running_pid: Option<u32>,
     pub initialized: bool,
     pub poll_interval_sec: u64,
     pub include_drafts: bool,
@@ -120,16 +57,16 @@ pub struct DaemonStatus {
     pub seeded_count: usize,
     pub success_count: usize,
     pub failed_count: usize,
+    pub failed_triggers: Vec<FailedTriggerStatus>,
+    pub skipped_count: usize,
+    pub queued_count: usize,
+    pub batch_times: Vec<String>,
+    pub last_flush_at: Option<DateTime<Utc>>,
     pub last_poll_at: Option<DateTime<Utc>>,
+    pub escalation_repos: Vec<String>,
+    pub nudged_count: usize,
 }
 
-#[derive(Debug, Clone)]
-pub struct RepoSubpathFilterStatus {
-    pub repo: String,
-    pub subpaths: Vec<String>,
-}
-
-type RepoSubpathFilterMap = HashMap<String, Vec<String>>;
 type RepoSelectionConfig = (Vec<String>, RepoSubpathFilterMap);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -184,8 +121,113 @@ impl DaemonAutoRestartWatcher {
     }
 }
 
-pub fn state_path() -> PathBuf {
-    config::config_dir().join("daemon_state.json")
+pub fn pid_path() -> PathBuf {
+    config::config_dir().join("daemon.pid")
+}
+
+/// Removes the pid file when the daemon loop exits, including on errors.
+struct PidFile(PathBuf);
+
+impl PidFile {
+    fn create() -> Result<Self> {
+        let path = pid_path();
+        if let Some(parent) = path.parent() {
+            private_fs::create_dir_all(parent)?;
+        }
+        private_fs::write(&path, std::process::id().to_string())
+            .with_context(|| format!("Failed to write pid file {}", path.display()))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn read_pid_file() -> Option<u32> {
+    fs::read_to_string(pid_path()).ok()?.trim().parse().ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DaemonSignal {
+    /// SIGHUP: re-read the config file.
+    Reload,
+    /// SIGTERM/SIGINT: stop after the current poll.
+    Shutdown,
+    /// SIGUSR1: poll now instead of waiting for the interval.
+    PollNow,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum NextStep {
+    Poll,
+    Shutdown,
+}
+
+/// Forward daemon signals to a channel. Without signal support the receiver is
+/// disconnected and the scheduler falls back to plain sleeps.
+fn listen_for_signals() -> Receiver<DaemonSignal> {
+    let (tx, rx) = mpsc::channel();
+
+    #[cfg(unix)]
+    {
+        use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM, SIGUSR1};
+        match signal_hook::iterator::Signals::new([SIGHUP, SIGINT, SIGTERM, SIGUSR1]) {
+            Ok(mut signals) => {
+                thread::spawn(move || {
+                    for signal in signals.forever() {
+                        let event = match signal {
+                            SIGHUP => DaemonSignal::Reload,
+                            SIGUSR1 => DaemonSignal::PollNow,
+                            _ => DaemonSignal::Shutdown,
+                        };
+                        if tx.send(event).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            Err(err) => eprintln!("Daemon signal handling disabled: {:#}", err),
+        }
+    }
+    #[cfg(not(unix))]
+    drop(tx);
+
+    rx
+}
+
+/// Wait until the next poll is due, handling signals that arrive meanwhile.
+fn wait_for_next_poll(
+    signals: &Receiver<DaemonSignal>,
+    cfg: &mut Config,
+    config_watcher: &mut ConfigWatcher,
+    poll_interval: Duration,
+) -> NextStep {
+    let deadline = Instant::now() + poll_interval;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return NextStep::Poll;
+        }
+        match signals.recv_timeout(remaining) {
+            Ok(DaemonSignal::Reload) => {
+                println!("Received SIGHUP, reloading config.");
+                reload_daemon_config(cfg, config_watcher, true);
+            }
+            Ok(DaemonSignal::PollNow) => {
+                println!("Received SIGUSR1, polling now.");
+                return NextStep::Poll;
+            }
+            Ok(DaemonSignal::Shutdown) => return NextStep::Shutdown,
+            Err(RecvTimeoutError::Timeout) => return NextStep::Poll,
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(remaining);
+                return NextStep::Poll;
+            }
+        }
+    }
 }
 
 fn resolve_restart_binary_path() -> Result<PathBuf> {
@@ -269,404 +311,44 @@ fn restart_daemon_process(executable: &Path, args: &[OsString]) -> Result<()> {
     }
 }
 
-fn load_state() -> DaemonState {
-    let path = state_path();
-    if !path.exists() {
-        return DaemonState::default();
-    }
-    std::fs::read_to_string(&path)
-        .ok()
-        .and_then(|s| serde_json::from_str(&s).ok())
-        .unwrap_or_default()
-}
-
-fn save_state(state: &DaemonState) -> Result<()> {
-    let path = state_path();
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    let json = serde_json::to_string_pretty(state)?;
-    std::fs::write(path, json)?;
-    Ok(())
-}
-
-fn pr_key(repo: &str, pr_number: u64) -> String {
-    format!("{repo}#{pr_number}")
-}
-
-fn discover_repos(repos_root: &Path, exclude_dirs: &[String]) -> Vec<RepoDescriptor> {
-    repos::scan_unique_repos(repos_root, 3, exclude_dirs)
-        .unique_repos
-        .into_iter()
-        .filter_map(|repo| {
-            repo.name_with_owner.map(|name| RepoDescriptor {
-                path: repo.path,
-                name,
-            })
-        })
-        .collect()
-}
-
-fn monitored_repo_set(exclude_repos: &[String]) -> HashSet<String> {
-    exclude_repos.iter().cloned().collect()
-}
-
-fn normalize_repo_names(mut repos: Vec<String>) -> Vec<String> {
-    repos.sort();
-    repos.dedup();
-    repos
-}
-
-fn normalize_subpath(path: &str) -> Option<String> {
-    let normalized = path.trim().trim_matches('/').trim();
-    if normalized.is_empty() {
-        return None;
-    }
-    Some(normalized.to_string())
-}
-
-fn normalize_subpaths(paths: &[String]) -> Vec<String> {
-    let mut normalized: Vec<String> = paths
-        .iter()
-        .filter_map(|path| normalize_subpath(path))
-        .collect();
-    normalized.sort();
-    normalized.dedup();
-    normalized
-}
-
-fn normalize_auto_approve_rules(rules: &[AutoApproveRule]) -> Vec<AutoApproveRule> {
-    let mut normalized = Vec::new();
-    let mut seen = HashSet::new();
-
-    for rule in rules {
-        let repo = rule.repo.trim().to_lowercase();
-        let user = rule.user.trim().to_lowercase();
-        if repo.is_empty() || user.is_empty() {
-            continue;
-        }
-
-        if !seen.insert((repo.clone(), user.clone())) {
-            continue;
-        }
-
-        normalized.push(AutoApproveRule { repo, user });
-    }
-
-    normalized.sort_by(|a, b| (&a.repo, &a.user).cmp(&(&b.repo, &b.user)));
-    normalized
-}
-
-fn normalize_repo_subpath_filters(
-    repo_subpath_filters: &RepoSubpathFilterMap,
-) -> RepoSubpathFilterMap {
-    let mut normalized = HashMap::new();
-    for (repo, subpaths) in repo_subpath_filters {
-        let repo_name = repo.trim();
-        if repo_name.is_empty() {
-            continue;
-        }
-        normalized.insert(repo_name.to_string(), normalize_subpaths(subpaths));
-    }
-    normalized
-}
-
-fn normalize_repo_subpath_filter_status(
-    repo_subpath_filters: &RepoSubpathFilterMap,
-) -> Vec<RepoSubpathFilterStatus> {
-    let mut normalized: Vec<RepoSubpathFilterStatus> =
-        normalize_repo_subpath_filters(repo_subpath_filters)
-            .into_iter()
-            .filter(|(_, subpaths)| !subpaths.is_empty())
-            .map(|(repo, subpaths)| RepoSubpathFilterStatus { repo, subpaths })
-            .collect();
-    normalized.sort_by(|a, b| a.repo.cmp(&b.repo));
-    normalized
-}
-
-fn path_matches_subpath(path: &str, subpath: &str) -> bool {
-    let normalized_path = path.trim_start_matches('/');
-    normalized_path == subpath
-        || normalized_path
-            .strip_prefix(subpath)
-            .map(|rest| rest.starts_with('/'))
-            .unwrap_or(false)
-}
-
-fn pr_touches_any_subpath(changed_files: &[String], subpaths: &[String]) -> bool {
-    changed_files.iter().any(|path| {
-        subpaths
-            .iter()
-            .any(|subpath| path_matches_subpath(path, subpath))
-    })
-}
-
-fn apply_repo_subpath_filter(
-    repo: &RepoDescriptor,
-    prs: Vec<PullRequest>,
-    repo_subpath_filters: &RepoSubpathFilterMap,
-) -> Vec<PullRequest> {
-    let subpaths = match repo_subpath_filters.get(&repo.name) {
-        Some(subpaths) if !subpaths.is_empty() => subpaths,
-        _ => return prs,
-    };
-
-    prs.into_iter()
-        .filter(|pr| match gh::get_pr_changed_files(pr) {
-            Ok(changed_files) => pr_touches_any_subpath(&changed_files, subpaths),
-            Err(err) => {
-                eprintln!(
-                    "Failed to evaluate daemon subpath filter for {}#{}: {}. Triggering review anyway.",
-                    pr.repo_name, pr.number, err
-                );
-                true
-            }
-        })
-        .collect()
-}
-
-fn classify_trigger_kind(pr: &PullRequest, username: &str) -> Option<ReviewTriggerKind> {
-    if pr.author == username {
-        if pr.is_draft {
-            return None;
-        }
-        return Some(ReviewTriggerKind::SelfReview);
-    }
-    Some(ReviewTriggerKind::Review)
-}
-
-fn should_auto_approve(pr: &PullRequest, auto_approve_rules: &[AutoApproveRule]) -> bool {
-    let repo = pr.repo_name.trim().to_lowercase();
-    let author = pr.author.trim().to_lowercase();
-
-    auto_approve_rules.iter().any(|rule| {
-        let repo_pattern = rule.repo.trim().to_lowercase();
-        let user_pattern = rule.user.trim().to_lowercase();
-        if repo_pattern.is_empty() || user_pattern.is_empty() {
-            return false;
-        }
-
-        wildcard_match(&repo_pattern, &repo) && wildcard_match(&user_pattern, &author)
-    })
-}
-
-fn select_trigger_action(
-    pr: &PullRequest,
-    trigger_kind: ReviewTriggerKind,
-    auto_approve_rules: &[AutoApproveRule],
-) -> TriggerAction {
-    if trigger_kind == ReviewTriggerKind::Review && should_auto_approve(pr, auto_approve_rules) {
-        TriggerAction::AutoApprove
-    } else {
-        TriggerAction::Review(trigger_kind)
-    }
-}
-
-fn candidate_action_allowed(
-    candidate: &DaemonReviewCandidate,
-    action: TriggerAction,
-    excluded_users: &[String],
-) -> bool {
-    action == TriggerAction::AutoApprove
-        || !author_excluded(
-            &candidate.pr.author,
-            candidate.pr.author_kind.as_deref(),
-            excluded_users,
-        )
-}
-
-fn trigger_action(
-    pr: &PullRequest,
-    repos_root: &Path,
-    ai: &AiConfig,
-    action: TriggerAction,
-) -> Result<()> {
-    match action {
-        TriggerAction::Review(trigger_kind) => trigger_review(pr, repos_root, ai, trigger_kind),
-        TriggerAction::AutoApprove => gh::approve_pr(pr, None)
-            .with_context(|| format!("Failed to auto-approve {}#{}", pr.repo_name, pr.number)),
-    }
-}
-
-fn collect_open_prs(
-    repos: &[RepoDescriptor],
-    excluded_repos: &HashSet<String>,
-    repo_subpath_filters: &RepoSubpathFilterMap,
-    username: &str,
-    include_drafts: bool,
-) -> Vec<DaemonReviewCandidate> {
-    collect_monitored_prs(
-        repos,
-        excluded_repos,
-        repo_subpath_filters,
-        username,
-        include_drafts,
-    )
-    .into_iter()
-    .filter_map(|pr| {
-        classify_trigger_kind(&pr, username)
-            .map(|trigger_kind| DaemonReviewCandidate { pr, trigger_kind })
-    })
-    .collect()
+pub fn poll_once(cfg: &Config, repos_root: &Path, username: &str) -> Result<PollSummary> {
+    poll(cfg, repos_root, username, false)
 }
 
-fn collect_monitored_prs(
-    repos: &[RepoDescriptor],
-    excluded_repos: &HashSet<String>,
-    repo_subpath_filters: &RepoSubpathFilterMap,
-    username: &str,
-    include_drafts: bool,
-) -> Vec<PullRequest> {
-    repos
-        .par_iter()
-        .filter(|repo| !excluded_repos.contains(&repo.name))
-        .flat_map(|repo| {
-            let prs = gh::fetch_prs_for_repo_with_authored(&repo.path, username, include_drafts);
-            apply_repo_subpath_filter(repo, prs, repo_subpath_filters)
-                .into_iter()
-                .collect::<Vec<_>>()
-        })
-        .collect()
+/// Launch every queued review now, regardless of the batch schedule.
+pub fn flush(cfg: &Config, repos_root: &Path, username: &str) -> Result<PollSummary> {
+    poll(cfg, repos_root, username, true)
 }
 
-pub fn list_watched_prs(
-    cfg: &Config,
-    repos_root: &Path,
-    username: &str,
-    include_drafts: bool,
-) -> Vec<PullRequest> {
-    let repos = discover_repos(repos_root, &cfg.exclude);
-    let excluded_repos = monitored_repo_set(&cfg.daemon.exclude_repos);
-    let repo_subpath_filters = normalize_repo_subpath_filters(&cfg.daemon.repo_subpath_filters);
-    let mut prs = collect_monitored_prs(
-        &repos,
-        &excluded_repos,
-        &repo_subpath_filters,
+/// One poll against the saved state, printing what happens as it goes. The state stays locked
+/// throughout, so a `daemon flush` and the running daemon take turns instead of each saving
+/// over the other's launches.
+fn poll(cfg: &Config, repos_root: &Path, username: &str, force_flush: bool) -> Result<PollSummary> {
+    let _lock = lock_state()?;
+    let mut state = load_state();
+    let summary = core::poll(
+        cfg,
+        repos_root,
         username,
-        include_drafts,
+        &mut state,
+        Utc::now(),
+        force_flush,
+        &mut print_event,
     );
-    prs.sort_by(|a, b| {
-        b.updated_at
-            .cmp(&a.updated_at)
-            .then_with(|| a.repo_name.cmp(&b.repo_name))
-            .then_with(|| a.number.cmp(&b.number))
-    });
-    prs
-}
-
-fn build_seed_record(pr: &PullRequest, now: DateTime<Utc>) -> ReviewedPrRecord {
-    ReviewedPrRecord {
-        repo: pr.repo_name.clone(),
-        pr_number: pr.number,
-        first_seen_at: now,
-        last_seen_at: now,
-        latest_updated_at: pr.updated_at,
-        triggered_at: None,
-        trigger_status: TriggerStatus::Seeded,
-        last_error: None,
-    }
-}
-
-fn trigger_review(
-    pr: &PullRequest,
-    repos_root: &Path,
-    ai: &AiConfig,
-    trigger_kind: ReviewTriggerKind,
-) -> Result<()> {
-    let worktree_path = gh::create_pr_worktree(pr, repos_root).with_context(|| {
-        format!(
-            "Failed to create worktree for {}#{}",
-            pr.repo_name, pr.number
-        )
-    })?;
-    gh::launch_ai(&worktree_path, pr, ai).with_context(|| {
-        format!(
-            "Failed to launch AI {} for {}#{}",
-            trigger_kind.label(),
-            pr.repo_name,
-            pr.number
-        )
-    })?;
-    Ok(())
-}
-
-fn ai_config_for_trigger_kind(ai: &AiConfig, trigger_kind: ReviewTriggerKind) -> Option<AiConfig> {
-    if ai.launch.uses_tmux() {
-        return Some(ai.clone());
-    }
-
-    if !ai.launch.is_configured() {
-        return None;
-    }
-
-    if trigger_kind == ReviewTriggerKind::SelfReview && !ai.launch.self_review_steps.is_empty() {
-        let mut self_review_ai = ai.clone();
-        self_review_ai.launch.steps = ai.launch.self_review_steps.clone();
-        return Some(self_review_ai);
-    }
-
-    Some(ai.clone())
-}
-
-fn ai_config_for_action<'a>(
-    action: TriggerAction,
-    review_ai: &'a Option<AiConfig>,
-    self_review_ai: &'a Option<AiConfig>,
-    default_ai: &'a AiConfig,
-) -> Option<&'a AiConfig> {
-    match action {
-        TriggerAction::AutoApprove => Some(default_ai),
-        TriggerAction::Review(ReviewTriggerKind::Review) => review_ai.as_ref(),
-        TriggerAction::Review(ReviewTriggerKind::SelfReview) => self_review_ai.as_ref(),
-    }
+    save_state(&state)?;
+    Ok(summary)
 }
 
-fn seed_existing_open_prs(
-    state: &mut DaemonState,
-    repos: &[RepoDescriptor],
-    cfg: &Config,
-    username: &str,
-) -> usize {
-    let excluded_repos = monitored_repo_set(&cfg.daemon.exclude_repos);
-    let excluded_users = normalize_user_patterns(&cfg.exclude_users);
-    let repo_subpath_filters = normalize_repo_subpath_filters(&cfg.daemon.repo_subpath_filters);
-    let prs = collect_open_prs(
-        repos,
-        &excluded_repos,
-        &repo_subpath_filters,
-        username,
-        cfg.daemon.include_drafts,
-    )
-    .into_iter()
-    .filter(|candidate| {
-        !author_excluded(
-            &candidate.pr.author,
-            candidate.pr.author_kind.as_deref(),
-            &excluded_users,
-        )
-    })
-    .collect::<Vec<_>>();
-    let now = Utc::now();
-    let mut seeded = 0usize;
-
-    for candidate in prs {
-        let pr = candidate.pr;
-        let key = pr_key(&pr.repo_name, pr.number);
-        if let Some(existing) = state.prs.get_mut(&key) {
-            existing.last_seen_at = now;
-            existing.latest_updated_at = pr.updated_at;
-            continue;
-        }
-        state.prs.insert(key, build_seed_record(&pr, now));
-        seeded += 1;
+fn print_event(event: PollEvent) {
+    if event.is_failure() {
+        eprintln!("{event}");
+    } else {
+        println!("{event}");
     }
-
-    state.last_poll_at = Some(now);
-    seeded
 }
 
 pub fn init(cfg: &mut Config, repos_root: &Path, username: &str) -> Result<()> {
-    let repos = discover_repos(repos_root, &cfg.exclude);
+    let repos = core::discover_repos(repos_root, &cfg.exclude);
     if repos.is_empty() {
         cfg.daemon.initialized = true;
         config::save_config(cfg)?;
@@ -686,8 +368,9 @@ pub fn init(cfg: &mut Config, repos_root: &Path, username: &str) -> Result<()> {
 
     let monitored_count = repos.len().saturating_sub(cfg.daemon.exclude_repos.len());
     if cfg.daemon.only_new_prs_on_start {
+        let _lock = lock_state()?;
         let mut state = load_state();
-        let seeded = seed_existing_open_prs(&mut state, &repos, cfg, username);
+        let seeded = seed_existing_open_prs(&mut state, &repos, cfg, username, &mut print_event);
         save_state(&state)?;
         println!(
             "Daemon initialized. Monitoring {} repos ({} excluded). Seeded {} existing PRs as already seen.",
@@ -706,182 +389,48 @@ pub fn init(cfg: &mut Config, repos_root: &Path, username: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn poll_once(cfg: &Config, repos_root: &Path, username: &str) -> Result<PollSummary> {
-    let repos = discover_repos(repos_root, &cfg.exclude);
-    let excluded_repos = monitored_repo_set(&cfg.daemon.exclude_repos);
-    let repo_subpath_filters = normalize_repo_subpath_filters(&cfg.daemon.repo_subpath_filters);
-    let monitored_repos = repos
-        .iter()
-        .filter(|repo| !excluded_repos.contains(&repo.name))
-        .count();
-    let open_prs = collect_open_prs(
-        &repos,
-        &excluded_repos,
-        &repo_subpath_filters,
-        username,
-        cfg.daemon.include_drafts,
-    );
-    let auto_approve_rules = normalize_auto_approve_rules(&cfg.daemon.auto_approve);
-    let excluded_users = normalize_user_patterns(&cfg.exclude_users);
-    let candidate_actions = open_prs
-        .into_iter()
-        .map(|candidate| {
-            let action =
-                select_trigger_action(&candidate.pr, candidate.trigger_kind, &auto_approve_rules);
-            (candidate, action)
-        })
-        .filter(|(candidate, action)| candidate_action_allowed(candidate, *action, &excluded_users))
-        .collect::<Vec<_>>();
-    let mut review_ai = ai_config_for_trigger_kind(&cfg.ai, ReviewTriggerKind::Review);
-    let mut self_review_ai = ai_config_for_trigger_kind(&cfg.ai, ReviewTriggerKind::SelfReview);
-    let has_review_actions = candidate_actions
-        .iter()
-        .any(|(_, action)| *action == TriggerAction::Review(ReviewTriggerKind::Review));
-    let has_self_review_actions = candidate_actions
-        .iter()
-        .any(|(_, action)| *action == TriggerAction::Review(ReviewTriggerKind::SelfReview));
-    if has_review_actions {
-        if let Some(ai_cfg) = review_ai.as_ref() {
-            if let Err(err) = gh::validate_ai_launch_config(ai_cfg) {
-                eprintln!(
-                    "Skipping review triggers this poll: invalid ai.launch config: {:#}",
-                    err
-                );
-                review_ai = None;
-            }
-        } else {
-            eprintln!(
-                "Skipping review triggers this poll: ai.launch is not configured. Configure ai.launch.steps or ai.launch.backend."
-            );
-        }
-    }
-    if has_self_review_actions {
-        if let Some(ai_cfg) = self_review_ai.as_ref() {
-            if let Err(err) = gh::validate_ai_launch_config(ai_cfg) {
-                eprintln!(
-                    "Skipping self-review triggers this poll: invalid launcher config: {:#}",
-                    err
-                );
-                self_review_ai = None;
-            }
-        } else {
-            eprintln!(
-                "Skipping self-review triggers this poll: ai.launch is not configured. Configure ai.launch.steps, ai.launch.self_review_steps, or ai.launch.backend."
-            );
+/// Apply config file edits between polls. `--exclude` flags given on the
+/// command line stay in effect across reloads.
+fn reload_daemon_config(cfg: &mut Config, watcher: &mut ConfigWatcher, force: bool) {
+    let result = if force {
+        Some(watcher.reload())
+    } else {
+        watcher.poll()
+    };
+    let reload = match result {
+        None => return,
+        Some(Ok(reload)) => reload,
+        Some(Err(err)) => {
+            eprintln!("Ignoring config change: {:#}", err);
+            return;
         }
+    };
+    if let Err(err) = reload.config.daemon.batch.parsed_times() {
+        eprintln!("Ignoring config change: {:#}", err);
+        return;
     }
-    let open_pr_count = candidate_actions.len();
 
-    let now = Utc::now();
-    let mut state = load_state();
-    let mut new_prs = 0usize;
-    let mut triggered = 0usize;
-    let mut failed = 0usize;
-
-    for (candidate, action) in candidate_actions {
-        let DaemonReviewCandidate { pr, .. } = candidate;
-        let ai_for_action = ai_config_for_action(action, &review_ai, &self_review_ai, &cfg.ai);
-        let key = pr_key(&pr.repo_name, pr.number);
-        if let Some(existing) = state.prs.get_mut(&key) {
-            existing.last_seen_at = now;
-            existing.latest_updated_at = pr.updated_at;
-
-            if existing.trigger_status != TriggerStatus::Failed {
-                continue;
-            }
-            let Some(ai_config) = ai_for_action else {
-                // Missing or invalid launcher config for this action; do not retry yet.
-                continue;
-            };
-
-            println!(
-                "Retrying failed {} trigger for {}#{}",
-                action.label(),
-                pr.repo_name,
-                pr.number
-            );
-            match trigger_action(&pr, repos_root, ai_config, action) {
-                Ok(()) => {
-                    existing.triggered_at = Some(Utc::now());
-                    existing.trigger_status = TriggerStatus::Success;
-                    existing.last_error = None;
-                    triggered += 1;
-                    println!(
-                        "Triggered {} for {}#{}",
-                        action.label(),
-                        pr.repo_name,
-                        pr.number
-                    );
-                }
-                Err(err) => {
-                    existing.trigger_status = TriggerStatus::Failed;
-                    existing.last_error = Some(format!("{:#}", err));
-                    failed += 1;
-                    eprintln!(
-                        "Failed to retry {} trigger for {}#{}: {:#}",
-                        action.label(),
-                        pr.repo_name,
-                        pr.number,
-                        err
-                    );
-                }
-            }
-            continue;
-        }
-        let Some(ai_config) = ai_for_action else {
-            // Missing or invalid launcher config for this action; keep PR unseen for future polls.
-            continue;
-        };
-
-        new_prs += 1;
-        println!(
-            "New PR detected for {}: {}#{} - {}",
-            action.label(),
-            pr.repo_name,
-            pr.number,
-            pr.title
-        );
-
-        let mut record = build_seed_record(&pr, now);
-        match trigger_action(&pr, repos_root, ai_config, action) {
-            Ok(()) => {
-                record.triggered_at = Some(Utc::now());
-                record.trigger_status = TriggerStatus::Success;
-                triggered += 1;
-                println!(
-                    "Triggered {} for {}#{}",
-                    action.label(),
-                    pr.repo_name,
-                    pr.number
-                );
-            }
-            Err(err) => {
-                record.trigger_status = TriggerStatus::Failed;
-                record.last_error = Some(format!("{:#}", err));
-                failed += 1;
-                eprintln!(
-                    "Failed to trigger {} for {}#{}: {:#}",
-                    action.label(),
-                    pr.repo_name,
-                    pr.number,
-                    err
-                );
-            }
+    let cli_exclude = cfg
+        .exclude
+        .iter()
+        .filter(|dir| !reload.previous.exclude.contains(dir))
+        .cloned()
+        .collect::<Vec<_>>();
+    let summary = reload.summary();
+    *cfg = reload.config;
+    clone_cache::configure(&cfg.clone_cache);
+    gh::configure_fetch(&cfg.fetch);
+    auth::configure(&cfg.auth);
+    for dir in cli_exclude {
+        if !cfg.exclude.contains(&dir) {
+            cfg.exclude.push(dir);
         }
-
-        state.prs.insert(key, record);
     }
-
-    state.last_poll_at = Some(now);
-    save_state(&state)?;
-
-    Ok(PollSummary {
-        monitored_repos,
-        open_prs: open_pr_count,
-        new_prs,
-        triggered,
-        failed,
-    })
+    match summary {
+        Some(summary) => println!("{}", summary),
+        None if force => println!("Config unchanged."),
+        None => {}
+    }
 }
 
 pub fn run(
@@ -909,6 +458,24 @@ pub fn run(
         subpath_filter_count,
         cfg.daemon.only_new_prs_on_start
     );
+    if cfg.daemon.batch.enabled {
+        cfg.daemon.batch.parsed_times()?;
+        if cfg.daemon.batch.times.is_empty() {
+            println!("Batch queue enabled with no times; run `reviewer daemon flush` to launch queued reviews.");
+        } else {
+            println!(
+                "Batch queue enabled. Queued reviews launch at {}.",
+                cfg.daemon.batch.times.join(", ")
+            );
+        }
+    }
+    let mut cfg = cfg.clone();
+    let mut config_watcher = ConfigWatcher::new(&cfg);
+    let (signals, _pid_file) = if once {
+        (mpsc::channel().1, None)
+    } else {
+        (listen_for_signals(), Some(PidFile::create()?))
+    };
     let mut auto_restart_watcher = if once {
         None
     } else {
@@ -922,14 +489,17 @@ pub fn run(
     };
 
     loop {
-        let summary = poll_once(cfg, repos_root, username)?;
+        let summary = poll_once(&cfg, repos_root, username)?;
         println!(
-            "Poll complete: {} repos, {} open PRs, {} new, {} triggered, {} failed.",
+            "Poll complete: {} repos, {} open PRs, {} new, {} triggered, {} skipped, {} queued, {} failed, {} nudged.",
             summary.monitored_repos,
             summary.open_prs,
             summary.new_prs,
             summary.triggered,
-            summary.failed
+            summary.skipped,
+            summary.queued,
+            summary.failed,
+            summary.nudged
         );
 
         if once {
@@ -946,7 +516,22 @@ pub fn run(
                 ),
             }
         }
-        thread::sleep(Duration::from_secs(poll_interval_sec));
+        reload_daemon_config(&mut cfg, &mut config_watcher, false);
+        let poll_interval_sec = poll_interval_override
+            .unwrap_or(cfg.daemon.poll_interval_sec)
+            .max(10);
+        match wait_for_next_poll(
+            &signals,
+            &mut cfg,
+            &mut config_watcher,
+            Duration::from_secs(poll_interval_sec),
+        ) {
+            NextStep::Poll => {}
+            NextStep::Shutdown => {
+                println!("Shutting down daemon.");
+                break;
+            }
+        }
     }
 
     Ok(())
@@ -957,11 +542,23 @@ pub fn status(cfg: &Config) -> DaemonStatus {
     let mut seeded_count = 0usize;
     let mut success_count = 0usize;
     let mut failed_count = 0usize;
-    for record in state.prs.values() {
+    let mut skipped_count = 0usize;
+    let mut queued_count = 0usize;
+    let mut failed_triggers = Vec::new();
+    for (key, record) in &state.prs {
         match record.trigger_status {
             TriggerStatus::Seeded => seeded_count += 1,
             TriggerStatus::Success => success_count += 1,
-            TriggerStatus::Failed => failed_count += 1,
+            TriggerStatus::Failed => {
+                failed_count += 1;
+                failed_triggers.push(FailedTriggerStatus {
+                    pr: key.clone(),
+                    error: record.last_error.clone(),
+                    run_dir: record.last_run_dir.clone(),
+                });
+            }
+            TriggerStatus::Skipped => skipped_count += 1,
+            TriggerStatus::Queued => queued_count += 1,
         }
     }
 
@@ -970,9 +567,12 @@ pub fn status(cfg: &Config) -> DaemonStatus {
     let repo_subpath_filters =
         normalize_repo_subpath_filter_status(&cfg.daemon.repo_subpath_filters);
     let auto_approve_rules = normalize_auto_approve_rules(&cfg.daemon.auto_approve);
+    failed_triggers.sort_by(|a, b| a.pr.cmp(&b.pr));
 
     DaemonStatus {
         state_path: state_path(),
+        runs_dir: run_artifacts::runs_dir(),
+        running_pid: read_pid_file(),
         initialized: cfg.daemon.initialized,
         poll_interval_sec: cfg.daemon.poll_interval_sec,
         include_drafts: cfg.daemon.include_drafts,
@@ -985,7 +585,18 @@ pub fn status(cfg: &Config) -> DaemonStatus {
         seeded_count,
         success_count,
         failed_count,
+        failed_triggers,
+        skipped_count,
+        queued_count,
+        batch_times: if cfg.daemon.batch.enabled {
+            cfg.daemon.batch.times.clone()
+        } else {
+            Vec::new()
+        },
+        last_flush_at: state.last_flush_at,
         last_poll_at: state.last_poll_at,
+        escalation_repos: cfg.daemon.escalation.repos.clone(),
+        nudged_count: state.nudges.len(),
     }
 }
 
@@ -1412,11 +1023,7 @@ impl RepoSelector {
             ..
         } = self;
 
-        for ((repo, included), subpaths) in repos
-            .into_iter()
-            .zip(included.into_iter())
-            .zip(subpath_filters.into_iter())
-        {
+        for ((repo, included), subpaths) in repos.into_iter().zip(included).zip(subpath_filters) {
             if !included {
                 excluded_repos.push(repo.clone());
             }
@@ -1672,8 +1279,6 @@ fn run_repo_selector(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::gh::ReviewState;
-    use chrono::Utc;
     use std::ffi::OsString;
     use std::fs;
     use std::sync::atomic::{AtomicU64, Ordering};
@@ -1695,249 +1300,31 @@ mod tests {
         ))
     }
 
-    fn make_test_pr(author: &str, is_draft: bool) -> PullRequest {
-        PullRequest {
-            number: 42,
-            title: "Test PR".to_string(),
-            author: author.to_string(),
-            author_kind: Some("User".to_string()),
-            body: String::new(),
-            repo_path: PathBuf::from("/tmp/repo"),
-            repo_name: "org/reviewer".to_string(),
-            url: "https://example.com/pr/42".to_string(),
-            updated_at: Utc::now(),
-            additions: 1,
-            deletions: 1,
-            is_draft,
-            review_state: ReviewState::Pending,
-            details_loaded: true,
-        }
-    }
-
     #[test]
-    fn normalize_subpaths_trims_and_dedups() {
-        let paths = vec![
-            " src ".to_string(),
-            "/src/".to_string(),
-            "services/api".to_string(),
-            "".to_string(),
-            "   ".to_string(),
-        ];
+    fn wait_for_next_poll_handles_poll_now_and_shutdown_signals() {
+        let mut cfg = Config::default();
+        let mut watcher = ConfigWatcher::new(&cfg);
+        let interval = Duration::from_secs(60);
+        let (tx, rx) = mpsc::channel();
 
+        tx.send(DaemonSignal::PollNow).unwrap();
         assert_eq!(
-            normalize_subpaths(&paths),
-            vec!["services/api".to_string(), "src".to_string()]
-        );
-    }
-
-    #[test]
-    fn path_matches_subpath_enforces_path_boundaries() {
-        assert!(path_matches_subpath("src/main.rs", "src"));
-        assert!(path_matches_subpath("src", "src"));
-        assert!(!path_matches_subpath("src2/main.rs", "src"));
-        assert!(!path_matches_subpath("nested/src/main.rs", "src"));
-    }
-
-    #[test]
-    fn pr_touches_any_subpath_matches_any_changed_file() {
-        let changed_files = vec![
-            "docs/readme.md".to_string(),
-            "services/api/handler.rs".to_string(),
-        ];
-
-        assert!(pr_touches_any_subpath(
-            &changed_files,
-            &["services/api".to_string(), "frontend".to_string()]
-        ));
-        assert!(!pr_touches_any_subpath(
-            &changed_files,
-            &["frontend".to_string(), "infra".to_string()]
-        ));
-    }
-
-    #[test]
-    fn normalize_repo_subpath_filters_skips_blank_repo_keys() {
-        let mut filters = HashMap::new();
-        filters.insert("  ".to_string(), vec!["src".to_string()]);
-        filters.insert(
-            "org/repo".to_string(),
-            vec!["/src/".to_string(), "".to_string()],
+            wait_for_next_poll(&rx, &mut cfg, &mut watcher, interval),
+            NextStep::Poll
         );
-
-        let normalized = normalize_repo_subpath_filters(&filters);
-        assert_eq!(normalized.len(), 1);
-        assert_eq!(normalized.get("org/repo"), Some(&vec!["src".to_string()]));
-    }
-
-    #[test]
-    fn normalize_auto_approve_rules_trims_lowercases_and_dedups() {
-        let rules = vec![
-            AutoApproveRule {
-                repo: " Org/Repo ".to_string(),
-                user: " Alice ".to_string(),
-            },
-            AutoApproveRule {
-                repo: "org/repo".to_string(),
-                user: "alice".to_string(),
-            },
-            AutoApproveRule {
-                repo: "org/other".to_string(),
-                user: "".to_string(),
-            },
-        ];
-
+        tx.send(DaemonSignal::Shutdown).unwrap();
         assert_eq!(
-            normalize_auto_approve_rules(&rules),
-            vec![AutoApproveRule {
-                repo: "org/repo".to_string(),
-                user: "alice".to_string(),
-            }]
+            wait_for_next_poll(&rx, &mut cfg, &mut watcher, interval),
+            NextStep::Shutdown
         );
-    }
-
-    #[test]
-    fn wildcard_match_supports_star_and_question() {
-        assert!(wildcard_match("org/*", "org/reviewer"));
-        assert!(wildcard_match("*bot", "dependabot"));
-        assert!(wildcard_match("renovate[bo?]", "renovate[bot]"));
-        assert!(!wildcard_match("org/*", "other/reviewer"));
-        assert!(!wildcard_match("*bot", "alice"));
-    }
-
-    #[test]
-    fn should_auto_approve_supports_case_insensitive_patterns() {
-        let mut pr = make_test_pr("Dependabot[Bot]", false);
-        pr.repo_name = "Org/Reviewer".to_string();
-
-        let rules = vec![AutoApproveRule {
-            repo: "org/*".to_string(),
-            user: "*bot]".to_string(),
-        }];
-
-        assert!(should_auto_approve(&pr, &rules));
-    }
 
-    #[test]
-    fn classify_trigger_kind_marks_other_authors_as_review() {
-        let pr = make_test_pr("alice", false);
-        assert_eq!(
-            classify_trigger_kind(&pr, "bob"),
-            Some(ReviewTriggerKind::Review)
-        );
-    }
-
-    #[test]
-    fn classify_trigger_kind_marks_authored_prs_as_self_review() {
-        let pr = make_test_pr("alice", false);
+        drop(tx);
         assert_eq!(
-            classify_trigger_kind(&pr, "alice"),
-            Some(ReviewTriggerKind::SelfReview)
+            wait_for_next_poll(&rx, &mut cfg, &mut watcher, Duration::from_millis(10)),
+            NextStep::Poll
         );
     }
 
-    #[test]
-    fn classify_trigger_kind_skips_authored_draft_prs() {
-        let pr = make_test_pr("alice", true);
-        assert_eq!(classify_trigger_kind(&pr, "alice"), None);
-    }
-
-    #[test]
-    fn ai_config_for_trigger_kind_requires_review_steps_for_review_action() {
-        let ai = AiConfig::default();
-        assert!(ai_config_for_trigger_kind(&ai, ReviewTriggerKind::Review).is_none());
-    }
-
-    #[test]
-    fn ai_config_for_trigger_kind_accepts_tmux_backend_without_steps() {
-        let mut ai = AiConfig::default();
-        ai.launch.backend = Some("tmux".to_string());
-
-        assert!(ai_config_for_trigger_kind(&ai, ReviewTriggerKind::Review).is_some());
-        assert!(ai_config_for_trigger_kind(&ai, ReviewTriggerKind::SelfReview).is_some());
-    }
-
-    #[test]
-    fn ai_config_for_trigger_kind_self_review_falls_back_to_review_steps() {
-        let mut ai = AiConfig::default();
-        ai.launch.steps = vec![crate::config::AiLaunchStepConfig {
-            command: "maestro".to_string(),
-            args: vec!["start".to_string()],
-        }];
-
-        let self_ai = ai_config_for_trigger_kind(&ai, ReviewTriggerKind::SelfReview)
-            .expect("self-review should use review steps when dedicated steps are absent");
-        assert_eq!(self_ai.launch.steps.len(), 1);
-        assert_eq!(self_ai.launch.steps[0].command, "maestro");
-    }
-
-    #[test]
-    fn ai_config_for_trigger_kind_self_review_prefers_self_review_steps() {
-        let mut ai = AiConfig::default();
-        ai.launch.steps = vec![crate::config::AiLaunchStepConfig {
-            command: "maestro".to_string(),
-            args: vec!["start".to_string()],
-        }];
-        ai.launch.self_review_steps = vec![crate::config::AiLaunchStepConfig {
-            command: "custom-self".to_string(),
-            args: vec!["run".to_string()],
-        }];
-
-        let self_ai = ai_config_for_trigger_kind(&ai, ReviewTriggerKind::SelfReview)
-            .expect("self-review should use dedicated self-review steps when configured");
-        assert_eq!(self_ai.launch.steps.len(), 1);
-        assert_eq!(self_ai.launch.steps[0].command, "custom-self");
-    }
-
-    #[test]
-    fn select_trigger_action_uses_auto_approve_for_matching_review_rule() {
-        let pr = make_test_pr("Alice", false);
-        let rules = vec![AutoApproveRule {
-            repo: "org/reviewer".to_string(),
-            user: "alice".to_string(),
-        }];
-
-        assert_eq!(
-            select_trigger_action(&pr, ReviewTriggerKind::Review, &rules),
-            TriggerAction::AutoApprove
-        );
-    }
-
-    #[test]
-    fn select_trigger_action_keeps_self_review_for_matching_rule() {
-        let pr = make_test_pr("alice", false);
-        let rules = vec![AutoApproveRule {
-            repo: "org/reviewer".to_string(),
-            user: "alice".to_string(),
-        }];
-
-        assert_eq!(
-            select_trigger_action(&pr, ReviewTriggerKind::SelfReview, &rules),
-            TriggerAction::Review(ReviewTriggerKind::SelfReview)
-        );
-    }
-
-    #[test]
-    fn excluded_user_filter_keeps_auto_approve_actions() {
-        let mut pr = make_test_pr("lpu-renovate", false);
-        pr.author_kind = Some("Bot".to_string());
-        let candidate = DaemonReviewCandidate {
-            pr,
-            trigger_kind: ReviewTriggerKind::Review,
-        };
-        let excluded_users = normalize_user_patterns(&["@apps/*".to_string()]);
-
-        assert!(candidate_action_allowed(
-            &candidate,
-            TriggerAction::AutoApprove,
-            &excluded_users
-        ));
-        assert!(!candidate_action_allowed(
-            &candidate,
-            TriggerAction::Review(ReviewTriggerKind::Review),
-            &excluded_users
-        ));
-    }
-
     #[test]
     fn daemon_restart_harness_skips_restart_when_binary_is_unchanged() {
         let fake_bin = restart_harness_temp_path();
@@ -0,0 +1,116 @@
+//! Timestamp rendering for the TUI and daemon output, following the `dates` config: timezone,
+//! relative or absolute ages, and the strftime pattern for absolute timestamps.
+
+use crate::config::{DateStyle, DateTimezone, DatesConfig};
+use chrono::{DateTime, Local, Utc};
+use std::fmt::Write;
+
+/// `at` in the configured timezone and format. A pattern chrono can't render falls back to
+/// `%Y-%m-%d %H:%M` instead of failing.
+pub fn absolute(dates: &DatesConfig, at: &DateTime<Utc>) -> String {
+    let render = |format: &str| {
+        let mut out = String::new();
+        let written = match dates.timezone {
+            DateTimezone::Local => write!(out, "{}", at.with_timezone(&Local).format(format)),
+            DateTimezone::Utc => write!(out, "{}", at.format(format)),
+        };
+        written.ok().map(|()| out)
+    };
+    render(&dates.format)
+        .or_else(|| render(&DatesConfig::default().format))
+        .unwrap_or_default()
+}
+
+/// Compact age for list rows (`2h`, `3d`, `1w`), or the absolute timestamp.
+pub fn age(dates: &DatesConfig, at: &DateTime<Utc>, now: DateTime<Utc>) -> String {
+    match dates.style {
+        DateStyle::Relative => relative(at, now),
+        DateStyle::Absolute => absolute(dates, at),
+    }
+}
+
+/// Age in a sentence: `2h ago`, `just now`, or `on <timestamp>`.
+pub fn ago(dates: &DatesConfig, at: &DateTime<Utc>, now: DateTime<Utc>) -> String {
+    match dates.style {
+        DateStyle::Relative => match relative(at, now).as_str() {
+            "now" => "just now".to_string(),
+            age => format!("{} ago", age),
+        },
+        DateStyle::Absolute => format!("on {}", absolute(dates, at)),
+    }
+}
+
+/// How long something took: `45s`, `3m 12s`, `1h 05m`.
+pub fn duration(duration: chrono::Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Format a datetime as a human-readable age (e.g., "2h", "3d", "1w")
+fn relative(at: &DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let duration = now.signed_duration_since(*at);
+
+    let hours = duration.num_hours();
+    let days = duration.num_days();
+    let weeks = days / 7;
+    let months = days / 30;
+
+    if months > 0 {
+        format!("{}mo", months)
+    } else if weeks > 0 {
+        format!("{}w", weeks)
+    } else if days > 0 {
+        format!("{}d", days)
+    } else if hours > 0 {
+        format!("{}h", hours)
+    } else {
+        "now".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates(style: DateStyle, format: &str) -> DatesConfig {
+        DatesConfig {
+            timezone: DateTimezone::Utc,
+            style,
+            format: format.to_string(),
+        }
+    }
+
+    #[test]
+    fn ages_follow_style_timezone_and_format() {
+        let now: DateTime<Utc> = "2026-10-18T12:00:00Z".parse().unwrap();
+        let at = now - chrono::Duration::hours(50);
+
+        let relative = dates(DateStyle::Relative, "%d.%m.%Y %H:%M");
+        assert_eq!(age(&relative, &at, now), "2d");
+        assert_eq!(ago(&relative, &at, now), "2d ago");
+        assert_eq!(ago(&relative, &now, now), "just now");
+        assert_eq!(absolute(&relative, &at), "16.10.2026 10:00");
+
+        let absolute_12h = dates(DateStyle::Absolute, "%m/%d/%Y %I:%M %p");
+        assert_eq!(age(&absolute_12h, &at, now), "10/16/2026 10:00 AM");
+        assert_eq!(ago(&absolute_12h, &at, now), "on 10/16/2026 10:00 AM");
+
+        let broken = dates(DateStyle::Absolute, "%Q %Y");
+        assert_eq!(absolute(&broken, &at), "2026-10-16 10:00");
+    }
+
+    #[test]
+    fn durations_use_the_two_largest_units() {
+        assert_eq!(duration(chrono::Duration::seconds(45)), "45s");
+        assert_eq!(duration(chrono::Duration::seconds(192)), "3m 12s");
+        assert_eq!(duration(chrono::Duration::seconds(3900)), "1h 05m");
+        assert_eq!(duration(chrono::Duration::seconds(-5)), "0s");
+    }
+}
@@ -0,0 +1,173 @@
+use crate::gh::PullRequest;
+
+/// Bot accounts whose PRs show up in the Dependencies tab.
+pub const DEPENDENCY_BOTS: &[&str] = &["dependabot", "renovate"];
+
+/// Lines of release notes kept per PR in the group confirmation.
+const MAX_NOTE_LINES: usize = 6;
+
+/// Package and versions parsed from a dependency update PR title
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyUpdate {
+    pub package: String,
+    pub from: Option<String>,
+    pub to: String,
+}
+
+impl DependencyUpdate {
+    pub fn versions(&self) -> String {
+        match &self.from {
+            Some(from) => format!("{} → {}", from, self.to),
+            None => format!("→ {}", self.to),
+        }
+    }
+}
+
+pub fn is_dependency_bot(login: &str) -> bool {
+    let login = login.to_ascii_lowercase();
+    let login = login.trim_start_matches("app/");
+    DEPENDENCY_BOTS.iter().any(|bot| login.starts_with(bot))
+}
+
+/// Parse Dependabot ("Bump serde from 1.0.1 to 1.0.2 in /api") and Renovate
+/// ("Update rust crate serde to v1.0.2") titles, with or without a `chore(deps):` prefix.
+pub fn parse_title(title: &str) -> Option<DependencyUpdate> {
+    let title = title.trim();
+    let title = match title.split_once(": ") {
+        Some((prefix, rest)) if !prefix.contains(' ') => rest,
+        _ => title,
+    };
+    let lower = title.to_ascii_lowercase();
+
+    if lower.starts_with("bump ") {
+        let rest = &title["bump ".len()..];
+        let (package, versions) = rest.split_once(" from ")?;
+        let (from, to) = versions.split_once(" to ")?;
+        let to = to.split(" in ").next().unwrap_or(to);
+        return Some(DependencyUpdate {
+            package: package.trim().to_string(),
+            from: Some(from.trim().to_string()),
+            to: to.trim().to_string(),
+        });
+    }
+
+    if lower.starts_with("update ") {
+        let rest = &title["update ".len()..];
+        let (descriptor, to) = rest.rsplit_once(" to ")?;
+        let descriptor = descriptor
+            .strip_suffix(" action")
+            .or_else(|| descriptor.strip_suffix(" digest"))
+            .unwrap_or(descriptor);
+        let package = descriptor.split_whitespace().last()?;
+        return Some(DependencyUpdate {
+            package: package.to_string(),
+            from: None,
+            to: to.trim().to_string(),
+        });
+    }
+
+    None
+}
+
+/// Grouping key: the parsed package, or the whole title for PRs that do not parse.
+pub fn group_key(pr: &PullRequest) -> String {
+    parse_title(&pr.title)
+        .map(|update| update.package)
+        .unwrap_or_else(|| pr.title.clone())
+        .to_ascii_lowercase()
+}
+
+/// Release notes, changelog, and compatibility lines from a bot PR body, without HTML.
+pub fn release_notes(body: &str) -> Vec<String> {
+    let mut in_notes = false;
+    let mut notes = Vec::new();
+    for line in body.lines() {
+        let text = strip_tags(line);
+        let text = text.trim().trim_start_matches('#').trim();
+        if text.is_empty() || text.starts_with("---") || text.starts_with('|') {
+            continue;
+        }
+        let lower = text.to_ascii_lowercase();
+        if lower.contains("compatibility") {
+            notes.push(badge_text(text));
+            continue;
+        }
+        if lower.starts_with("release notes") || lower.starts_with("changelog") {
+            in_notes = true;
+            continue;
+        }
+        if lower.starts_with("commits") || lower.starts_with("configuration") {
+            in_notes = false;
+        }
+        if in_notes && notes.len() < MAX_NOTE_LINES {
+            notes.push(text.to_string());
+        }
+    }
+    notes.truncate(MAX_NOTE_LINES);
+    notes
+}
+
+/// `[![alt](image)](link)` badges read as `alt: link`.
+fn badge_text(text: &str) -> String {
+    let badge = text.strip_prefix("[![").and_then(|rest| {
+        let (alt, rest) = rest.split_once("](")?;
+        let (_, link) = rest.split_once(")](")?;
+        Some(format!(
+            "{}: {}",
+            alt,
+            link.split(')').next().unwrap_or(link)
+        ))
+    });
+    badge.unwrap_or_else(|| text.to_string())
+}
+
+fn strip_tags(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for ch in line.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_dependency_bot, parse_title, release_notes};
+
+    #[test]
+    fn parse_title_reads_dependabot_and_renovate_titles() {
+        let bump = parse_title("build(deps): Bump serde from 1.0.1 to 1.0.2 in /api").unwrap();
+        assert_eq!(bump.package, "serde");
+        assert_eq!(bump.versions(), "1.0.1 → 1.0.2");
+
+        let crate_update = parse_title("chore(deps): update rust crate tokio to v1.40.0").unwrap();
+        assert_eq!(crate_update.package, "tokio");
+        assert_eq!(crate_update.to, "v1.40.0");
+
+        let action = parse_title("Update actions/checkout action to v4").unwrap();
+        assert_eq!(action.package, "actions/checkout");
+        assert!(parse_title("Fix flaky test").is_none());
+
+        assert!(is_dependency_bot("dependabot[bot]"));
+        assert!(is_dependency_bot("app/renovate"));
+        assert!(!is_dependency_bot("daulet"));
+    }
+
+    #[test]
+    fn release_notes_keeps_changelog_and_compatibility_lines() {
+        let body = "Bumps serde.\n<details>\n<summary>Release notes</summary>\n<h2>v1.0.2</h2>\n<ul><li>Fix derive on enums</li></ul>\n</details>\n<details><summary>Commits</summary>\n<li>abc123 Release</li>\n</details>\n\n[![Dependabot compatibility score](https://x)](https://y)\n";
+        assert_eq!(
+            release_notes(body),
+            vec![
+                "v1.0.2",
+                "Fix derive on enums",
+                "Dependabot compatibility score: https://y"
+            ]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::logging::CommandExt;
+use crate::process::describe_command;
 use ansi_to_tui::IntoText;
 use ratatui::{
     style::{Color, Modifier, Style},
@@ -15,16 +17,22 @@ use syntect::{
 
 const DELTA_DIFF_SIZE_LIMIT: usize = 100_000;
 
+/// Narrowest terminal delta renders side-by-side in; below it delta switches to unified mode.
+pub const SIDE_BY_SIDE_MIN_WIDTH: u16 = 100;
+
+/// Whether delta renders side-by-side at this terminal width
+pub fn side_by_side(width: u16) -> bool {
+    width >= SIDE_BY_SIDE_MIN_WIDTH
+}
+
 /// Check if delta is available on the system (cached)
 fn is_delta_available() -> bool {
     static DELTA_AVAILABLE: OnceLock<bool> = OnceLock::new();
     *DELTA_AVAILABLE.get_or_init(|| {
         Command::new("delta")
             .arg("--version")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .map(|s| s.success())
+            .logged_output()
+            .map(|output| output.status.success())
             .unwrap_or(false)
     })
 }
@@ -38,14 +46,20 @@ fn run_delta(diff: &str, width: u16) -> Option<String> {
         return None;
     }
 
-    let mut child = Command::new("delta")
-        .args([
-            "--dark",
-            "--paging=never",
-            "--line-numbers",
-            "--side-by-side",
-            &format!("--width={width}"),
-        ])
+    let mut command = Command::new("delta");
+    command.args([
+        "--dark",
+        "--paging=never",
+        "--line-numbers",
+        &format!("--width={width}"),
+    ]);
+    if side_by_side(width) {
+        command.arg("--side-by-side");
+    }
+    // Not `logged_output_with_input`: that writes all of stdin before reading and has no timeout,
+    // which deadlocks or hangs the render thread on large diffs. Logged here instead.
+    tracing::debug!(command = %describe_command(&command), bytes = diff.len(), "running delta");
+    let mut child = command
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
@@ -102,6 +116,167 @@ pub fn is_too_large_for_delta(diff: &str) -> bool {
     diff.len() > DELTA_DIFF_SIZE_LIMIT
 }
 
+/// Decides which files count as generated: config globs first, then
+/// `linguist-generated` entries from `.gitattributes` (later lines win).
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedFileMatcher {
+    globs: Vec<String>,
+    attributes: Vec<(String, bool)>,
+}
+
+impl GeneratedFileMatcher {
+    pub fn new(globs: &[String], gitattributes: Option<&str>) -> Self {
+        let attributes = gitattributes
+            .map(parse_linguist_generated)
+            .unwrap_or_default();
+        Self {
+            globs: globs.to_vec(),
+            attributes,
+        }
+    }
+
+    pub fn is_generated(&self, path: &str) -> bool {
+        let mut generated = self
+            .globs
+            .iter()
+            .any(|glob| crate::filters::path_glob_match(glob, path));
+        for (pattern, value) in &self.attributes {
+            if crate::filters::path_glob_match(pattern, path) {
+                generated = *value;
+            }
+        }
+        generated
+    }
+}
+
+/// Extract `linguist-generated` settings from `.gitattributes` contents.
+fn parse_linguist_generated(contents: &str) -> Vec<(String, bool)> {
+    let mut attributes = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let Some(pattern) = parts.next() else {
+            continue;
+        };
+        for attr in parts {
+            let value = match attr {
+                "linguist-generated" | "linguist-generated=true" => true,
+                "-linguist-generated" | "!linguist-generated" | "linguist-generated=false" => false,
+                _ => continue,
+            };
+            attributes.push((pattern.to_string(), value));
+        }
+    }
+    attributes
+}
+
+/// Replace the body of every generated file in a unified diff with a one-line
+/// stub. Returns the collapsed diff and how many files were collapsed.
+pub fn collapse_generated_files(diff: &str, matcher: &GeneratedFileMatcher) -> (String, usize) {
+    let mut result = String::with_capacity(diff.len());
+    let mut collapsed = 0;
+    for section in file_sections(diff) {
+        if !matcher.is_generated(section_path(section).unwrap_or_default()) {
+            result.push_str(section);
+            continue;
+        }
+        collapsed += 1;
+        // `\` lines carry no line numbers, so the stub can't be commented on.
+        result.push_str(&fold_section(
+            section,
+            "Generated file hidden",
+            "press H to show",
+        ));
+    }
+    (result, collapsed)
+}
+
+/// One file's diff folded to its header and a stub with its size, for files the reviewer
+/// collapsed in the Diff tab.
+pub fn collapsed_file_stub(section: &str) -> String {
+    fold_section(section, "Collapsed", "Enter to expand")
+}
+
+/// `section`'s `diff --git` line and what its extended header says about the file (renames,
+/// added or deleted files, mode changes), then a `\` line with `label`, the size of the hunks
+/// left out, and `hint`. The `index`, `---` and `+++` lines add nothing to a stub and are dropped.
+fn fold_section(section: &str, label: &str, hint: &str) -> String {
+    let header_len = file_header_lines(section).count();
+    let mut folded = String::new();
+    for line in section.lines().take(header_len).filter(|line| {
+        !["index ", "--- ", "+++ "]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+    }) {
+        folded.push_str(line);
+        folded.push('\n');
+    }
+    let (mut additions, mut deletions) = (0, 0);
+    for line in section.lines().skip(header_len) {
+        if line.starts_with('+') {
+            additions += 1;
+        } else if line.starts_with('-') {
+            deletions += 1;
+        }
+    }
+    folded.push_str(&format!(
+        "\\ {} (+{} -{}), {}\n",
+        label, additions, deletions, hint
+    ));
+    folded
+}
+
+/// `diff`'s text split into one slice per file, each starting at its `diff --git` line. Text
+/// before the first file is a section of its own.
+fn file_sections(diff: &str) -> Vec<&str> {
+    let mut starts = vec![0];
+    let mut offset = 0;
+    for line in diff.split_inclusive('\n') {
+        if offset > 0 && line.starts_with("diff --git ") {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+    starts.push(diff.len());
+    starts
+        .windows(2)
+        .map(|bounds| &diff[bounds[0]..bounds[1]])
+        .filter(|section| !section.is_empty())
+        .collect()
+}
+
+/// The `diff --git` line opening `section` and the extended header lines after it, up to the
+/// first hunk.
+fn file_header_lines(section: &str) -> impl Iterator<Item = &str> {
+    let mut lines = section.lines();
+    let first = lines.next().filter(|line| line.starts_with("diff --git "));
+    let rest = first
+        .is_some()
+        .then_some(lines.take_while(|line| is_file_header_line(line)));
+    first.into_iter().chain(rest.into_iter().flatten())
+}
+
+/// The file a one-file diff section changes. `rename to` and `+++ b/` name it exactly, then
+/// `--- a/` for a deleted file; only without those is the `diff --git` line split.
+pub fn section_path(section: &str) -> Option<&str> {
+    let header = file_header_lines(section).collect::<Vec<_>>();
+    let named = |prefixes: &[&str]| {
+        header.iter().find_map(|line| {
+            prefixes
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+                .then(|| file_header_path(line))
+                .flatten()
+        })
+    };
+    named(&["rename to ", "+++ b/"])
+        .or_else(|| named(&["--- a/"]))
+        .or_else(|| header.first().and_then(|line| git_header_path(line)))
+}
+
 /// Convert a Line with borrowed content to owned content
 fn line_to_owned(line: Line<'_>) -> Line<'static> {
     Line::from(
@@ -221,6 +396,7 @@ pub fn parse_diff_enhanced(diff: &str) -> Vec<EnhancedDiffLine> {
     let mut current_file: Option<String> = None;
     let mut old_line_num: u32 = 0;
     let mut new_line_num: u32 = 0;
+    let mut in_file_header = true;
 
     // Collect hunks for word-level diff computation
     let mut pending_removed: Vec<(usize, String)> = Vec::new();
@@ -239,28 +415,30 @@ pub fn parse_diff_enhanced(diff: &str) -> Vec<EnhancedDiffLine> {
             pending_removed.clear();
             pending_added.clear();
 
-            // Extract file path from "diff --git a/path b/path"
-            if let Some(b_path) = line.split(" b/").nth(1) {
-                current_file = Some(b_path.to_string());
-            }
+            current_file = git_header_path(line).map(str::to_string);
+            in_file_header = true;
             line_type = DiffLineType::FileHeader;
             old_num = None;
             new_num = None;
-        } else if line.starts_with("---") {
-            compute_word_changes(&mut result, &pending_removed, &pending_added);
-            pending_removed.clear();
-            pending_added.clear();
-            line_type = DiffLineType::OldFile;
-            old_num = None;
-            new_num = None;
-        } else if line.starts_with("+++") {
-            line_type = DiffLineType::NewFile;
+        } else if in_file_header && is_file_header_line(line) {
+            // Only before the first hunk: a removed `-- x` line also starts with `---`
+            if let Some(path) = file_header_path(line) {
+                current_file = Some(path.to_string());
+            }
+            line_type = if line.starts_with("---") {
+                DiffLineType::OldFile
+            } else if line.starts_with("+++") {
+                DiffLineType::NewFile
+            } else {
+                DiffLineType::FileHeader
+            };
             old_num = None;
             new_num = None;
         } else if line.starts_with("@@") {
             compute_word_changes(&mut result, &pending_removed, &pending_added);
             pending_removed.clear();
             pending_added.clear();
+            in_file_header = false;
 
             // Parse hunk header: @@ -old_start,old_count +new_start,new_count @@
             if let Some((old_start, new_start)) = parse_hunk_header(line) {
@@ -327,7 +505,86 @@ pub fn parse_diff_enhanced(diff: &str) -> Vec<EnhancedDiffLine> {
     result
 }
 
-fn parse_hunk_header(line: &str) -> Option<(u32, u32)> {
+/// Target path of a `diff --git a/<old> b/<new>` line. When both halves name the same file
+/// the line is split down the middle, so paths containing ` b/` stay intact; renames fall
+/// back to the last ` b/`, and the `+++`/`rename to` headers that follow settle them.
+pub fn git_header_path(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("diff --git ")?;
+    let mid = rest.len() / 2;
+    if rest.len() % 2 == 1 && rest.is_char_boundary(mid) && rest[mid..].starts_with(' ') {
+        if let (Some(old), Some(new)) = (
+            rest[..mid].strip_prefix("a/"),
+            rest[mid + 1..].strip_prefix("b/"),
+        ) {
+            if old == new {
+                return Some(new);
+            }
+        }
+    }
+    rest.rfind(" b/").map(|pos| &rest[pos + 3..])
+}
+
+/// Path named by an extended file header: `rename to <path>`, `+++ b/<path>` or
+/// `--- a/<path>`, which is the only name a deleted file has. `/dev/null` names nothing.
+pub fn file_header_path(line: &str) -> Option<&str> {
+    let path = line
+        .strip_prefix("rename to ")
+        .or_else(|| line.strip_prefix("+++ b/"))
+        .or_else(|| line.strip_prefix("--- a/"))?;
+    // git appends a tab to ---/+++ names containing spaces
+    Some(path.trim_end_matches('\t'))
+}
+
+/// Whether `line` belongs to the extended header between `diff --git` and the first hunk.
+pub fn is_file_header_line(line: &str) -> bool {
+    const PREFIXES: [&str; 14] = [
+        "--- ",
+        "+++ ",
+        "index ",
+        "new file mode ",
+        "deleted file mode ",
+        "old mode ",
+        "new mode ",
+        "similarity index ",
+        "dissimilarity index ",
+        "rename from ",
+        "rename to ",
+        "copy from ",
+        "copy to ",
+        "Binary files ",
+    ];
+    PREFIXES.iter().any(|prefix| line.starts_with(prefix))
+}
+
+/// Whether a file's patch shows `line` on the old (`LEFT`) or new (`RIGHT`) side: review
+/// comments can only land on added, removed or context lines inside a hunk.
+pub fn patch_has_line(patch: &str, line: u32, side: &str) -> bool {
+    let old_side = side == "LEFT";
+    let mut position: Option<(u32, u32)> = None;
+    for patch_line in patch.lines() {
+        if patch_line.starts_with("@@") {
+            position = parse_hunk_header(patch_line);
+            continue;
+        }
+        let Some((old, new)) = position.as_mut() else {
+            continue; // File headers before the first hunk
+        };
+        let (on_old, on_new) = match patch_line.chars().next() {
+            Some('+') => (false, true),
+            Some('-') => (true, false),
+            Some('\\') => continue,
+            _ => (true, true),
+        };
+        if (old_side && on_old && *old == line) || (!old_side && on_new && *new == line) {
+            return true;
+        }
+        *old += u32::from(on_old);
+        *new += u32::from(on_new);
+    }
+    false
+}
+
+pub fn parse_hunk_header(line: &str) -> Option<(u32, u32)> {
     // @@ -old_start,old_count +new_start,new_count @@ optional context
     let parts: Vec<&str> = line.split_whitespace().collect();
     if parts.len() < 3 {
@@ -621,3 +878,385 @@ pub fn render_diff<'a>(diff: &str, highlighter: &SyntaxHighlighter) -> Vec<Line<
         .map(|line| render_diff_line(line, highlighter, width))
         .collect()
 }
+
+/// One hunk of a single-file patch. Starts are 1-based positions of the hunk's first line,
+/// so an empty side (`-0,0`) starts right after the line named in its header.
+struct Hunk {
+    old_start: usize,
+    new_start: usize,
+    heading: String,
+    lines: Vec<String>,
+}
+
+impl Hunk {
+    fn parse(header: &str) -> Option<Self> {
+        let rest = header.strip_prefix("@@ -")?;
+        let (ranges, heading) = rest.split_once(" @@")?;
+        let (old, new) = ranges.split_once(" +")?;
+        let position = |range: &str| -> Option<usize> {
+            let (start, count) = range.split_once(',').unwrap_or((range, "1"));
+            let start: usize = start.parse().ok()?;
+            Some(if count == "0" { start + 1 } else { start })
+        };
+        Some(Self {
+            old_start: position(old)?,
+            new_start: position(new)?,
+            heading: heading.to_string(),
+            lines: Vec::new(),
+        })
+    }
+
+    fn count(&self, side: char) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.is_empty() || line.starts_with([' ', side]))
+            .count()
+    }
+
+    /// Position of the first new-file line after the hunk
+    fn new_end(&self) -> usize {
+        self.new_start + self.count('+')
+    }
+
+    fn header(&self) -> String {
+        let range = |start: usize, count: usize| {
+            let start = if count == 0 { start - 1 } else { start };
+            format!("{},{}", start, count)
+        };
+        format!(
+            "@@ -{} +{} @@{}",
+            range(self.old_start, self.count('-')),
+            range(self.new_start, self.count('+')),
+            self.heading
+        )
+    }
+}
+
+/// Widen every hunk of a single-file `patch` by `context` unchanged lines taken from the
+/// file's new contents, merging hunks that come to touch. Returns `None` when the patch
+/// does not fit `new_file`.
+pub fn expand_context(patch: &str, new_file: &str, context: usize) -> Option<String> {
+    let file_lines: Vec<&str> = new_file.lines().collect();
+    let mut header = Vec::new();
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for line in patch.lines() {
+        if let Some(hunk) = line.starts_with("@@").then(|| Hunk::parse(line)).flatten() {
+            hunks.push(hunk);
+        } else if let Some(hunk) = hunks.last_mut() {
+            hunk.lines.push(line.to_string());
+        } else {
+            header.push(line);
+        }
+    }
+
+    let ceilings: Vec<usize> = hunks
+        .iter()
+        .skip(1)
+        .map(|hunk| hunk.new_start)
+        .chain([file_lines.len() + 1])
+        .collect();
+    let as_context = |lines: &[&str]| lines.iter().map(|line| format!(" {}", line)).collect();
+    let mut merged: Vec<Hunk> = Vec::new();
+    for (mut hunk, ceiling) in hunks.into_iter().zip(ceilings) {
+        let floor = merged.last().map_or(1, Hunk::new_end);
+        let before = context.min(hunk.new_start.checked_sub(floor)?);
+        let leading: Vec<String> =
+            as_context(file_lines.get(hunk.new_start - 1 - before..hunk.new_start - 1)?);
+        hunk.lines.splice(0..0, leading);
+        hunk.new_start -= before;
+        hunk.old_start = hunk.old_start.checked_sub(before)?;
+
+        let hunk = match merged.last_mut() {
+            Some(prev) if prev.new_end() == hunk.new_start => {
+                prev.lines.append(&mut hunk.lines);
+                prev
+            }
+            _ => {
+                merged.push(hunk);
+                merged.last_mut()?
+            }
+        };
+        // A "\ No newline at end of file" marker means the hunk already reaches the end.
+        if !hunk.lines.iter().any(|line| line.starts_with('\\')) {
+            let end = hunk.new_end();
+            let after = context.min(ceiling.checked_sub(end)?);
+            let trailing: Vec<String> = as_context(file_lines.get(end - 1..end - 1 + after)?);
+            hunk.lines.extend(trailing);
+        }
+    }
+
+    let mut out = String::new();
+    for line in header {
+        out.push_str(line);
+        out.push('\n');
+    }
+    for hunk in &merged {
+        out.push_str(&hunk.header());
+        out.push('\n');
+        for line in &hunk.lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    Some(out)
+}
+
+/// Widest `+`/`-` bar of a `diff_stat` row
+const STAT_BAR_WIDTH: usize = 40;
+
+/// Per-file added and removed line counts, in the style of `git diff --stat`, with a
+/// `N files changed, X insertions(+), Y deletions(-)` summary.
+pub fn diff_stat(diff: &str) -> String {
+    let mut files: Vec<(&str, usize, usize)> = Vec::new();
+    let mut in_hunk = false;
+    for line in diff.lines() {
+        if let Some(path) = git_header_path(line) {
+            files.push((path, 0, 0));
+            in_hunk = false;
+        } else if line.starts_with("@@") {
+            in_hunk = true;
+        } else if let (true, Some(file)) = (in_hunk, files.last_mut()) {
+            if line.starts_with('+') {
+                file.1 += 1;
+            } else if line.starts_with('-') {
+                file.2 += 1;
+            }
+        }
+    }
+
+    let path_width = files.iter().map(|(path, ..)| path.len()).max().unwrap_or(0);
+    let most = files.iter().map(|(_, a, d)| a + d).max().unwrap_or(0);
+    let count_width = most.to_string().len();
+    let mut out = String::new();
+    for (path, added, removed) in &files {
+        // Scale bars down only when the largest file would overflow them
+        let scale = |n: usize| {
+            if most <= STAT_BAR_WIDTH {
+                n
+            } else {
+                (n * STAT_BAR_WIDTH).div_ceil(most)
+            }
+        };
+        out.push_str(&format!(
+            " {:<path_width$} | {:>count_width$} {}{}\n",
+            path,
+            added + removed,
+            "+".repeat(scale(*added)),
+            "-".repeat(scale(*removed)),
+        ));
+    }
+    let (added, removed) = files.iter().fold((0, 0), |(a, d), (_, added, removed)| {
+        (a + added, d + removed)
+    });
+    out.push_str(&format!(
+        " {} file{} changed, {} insertion{}(+), {} deletion{}(-)\n",
+        files.len(),
+        if files.len() == 1 { "" } else { "s" },
+        added,
+        if added == 1 { "" } else { "s" },
+        removed,
+        if removed == 1 { "" } else { "s" },
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        collapse_generated_files, collapsed_file_stub, diff_stat, expand_context, file_header_path,
+        git_header_path, parse_diff_enhanced, patch_has_line, section_path, DiffLineType,
+        GeneratedFileMatcher,
+    };
+
+    #[test]
+    fn expand_context_widens_hunks_and_merges_neighbours() {
+        let new_file: String = (1..=30)
+            .map(|n| match n {
+                5 => "LINE5\n".to_string(),
+                20 => "LINE20\n".to_string(),
+                n => format!("line{}\n", n),
+            })
+            .collect();
+        let patch = "diff --git a/f.txt b/f.txt
+--- a/f.txt
++++ b/f.txt
+@@ -2,7 +2,7 @@ fn top
+ line2
+ line3
+ line4
+-line5
++LINE5
+ line6
+ line7
+ line8
+@@ -17,7 +17,7 @@
+ line17
+ line18
+ line19
+-line20
++LINE20
+ line21
+ line22
+ line23
+";
+        let widened = expand_context(patch, &new_file, 2).unwrap();
+        let headers: Vec<&str> = widened.lines().filter(|l| l.starts_with("@@")).collect();
+        assert_eq!(headers, ["@@ -1,10 +1,10 @@ fn top", "@@ -15,11 +15,11 @@"]);
+        assert!(widened.starts_with("diff --git a/f.txt b/f.txt\n"));
+        assert!(widened.contains("+LINE5\n line6\n line7\n line8\n line9\n line10\n@@"));
+
+        let merged = expand_context(patch, &new_file, 5).unwrap();
+        let headers: Vec<&str> = merged.lines().filter(|l| l.starts_with("@@")).collect();
+        assert_eq!(headers, ["@@ -1,28 +1,28 @@ fn top"]);
+        assert!(merged.ends_with(" line28\n"));
+
+        assert_eq!(expand_context(patch, "too\nshort\n", 2), None);
+    }
+
+    #[test]
+    fn collapse_generated_files_stubs_globs_and_linguist_generated() {
+        let diff = "diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -1,2 +1,2 @@
+-old
++new
++extra
+diff --git a/src/api.pb.go b/src/api.pb.go
+--- a/src/api.pb.go
++++ b/src/api.pb.go
+@@ -1 +1 @@
+-a
++b
+diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-x
++y
+";
+        let matcher = GeneratedFileMatcher::new(
+            &["*.lock".to_string()],
+            Some("# generated code\n*.pb.go linguist-generated=true\nsrc/main.rs -linguist-generated\n"),
+        );
+
+        let (collapsed, count) = collapse_generated_files(diff, &matcher);
+        assert_eq!(count, 2);
+        assert!(collapsed.contains(
+            "diff --git a/Cargo.lock b/Cargo.lock\n\\ Generated file hidden (+2 -1), press H to show\n"
+        ));
+        assert!(collapsed.contains(
+            "diff --git a/src/api.pb.go b/src/api.pb.go\n\\ Generated file hidden (+1 -1), press H to show\n"
+        ));
+        assert!(collapsed.contains("+++ b/src/main.rs\n@@ -1 +1 @@\n-x\n+y\n"));
+        assert!(!collapsed.contains("+extra"));
+    }
+
+    #[test]
+    fn collapsed_file_stub_keeps_the_header_and_size() {
+        let section = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,3 @@\n a\n-b\n+c\n+d\n";
+        assert_eq!(
+            collapsed_file_stub(section),
+            "diff --git a/src/lib.rs b/src/lib.rs\n\\ Collapsed (+2 -1), Enter to expand\n"
+        );
+
+        let renamed = "diff --git a/old b/x.rs b/new b/x.rs\nsimilarity index 90%\nrename from old b/x.rs\nrename to new b/x.rs\n--- a/old b/x.rs\n+++ b/new b/x.rs\n@@ -1 +1 @@\n---x\n+++y\n";
+        let stub = collapsed_file_stub(renamed);
+        assert_eq!(
+            stub,
+            "diff --git a/old b/x.rs b/new b/x.rs\nsimilarity index 90%\nrename from old b/x.rs\nrename to new b/x.rs\n\\ Collapsed (+1 -1), Enter to expand\n"
+        );
+        assert_eq!(section_path(renamed), Some("new b/x.rs"));
+
+        let added = "diff --git a/new.rs b/new.rs\nnew file mode 100644\n--- /dev/null\n+++ b/new.rs\n@@ -0,0 +1 @@\n+fn new() {}\n";
+        assert_eq!(
+            collapsed_file_stub(added),
+            "diff --git a/new.rs b/new.rs\nnew file mode 100644\n\\ Collapsed (+1 -0), Enter to expand\n"
+        );
+        assert_eq!(section_path(added), Some("new.rs"));
+
+        let deleted = "diff --git a/a b/gone.rs b/a b/gone.rs\ndeleted file mode 100644\n--- a/a b/gone.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-fn gone() {}\n";
+        assert!(collapsed_file_stub(deleted).contains("deleted file mode 100644\n"));
+        assert_eq!(section_path(deleted), Some("a b/gone.rs"));
+    }
+
+    #[test]
+    fn linguist_generated_can_unmark_config_globs() {
+        let matcher = GeneratedFileMatcher::new(
+            &["*.lock".to_string()],
+            Some("flake.lock -linguist-generated\n"),
+        );
+        assert!(matcher.is_generated("Cargo.lock"));
+        assert!(!matcher.is_generated("nix/flake.lock"));
+    }
+
+    #[test]
+    fn default_vendor_glob_matches_vendored_code_at_any_depth() {
+        let matcher =
+            GeneratedFileMatcher::new(&crate::config::DiffConfig::default().generated_files, None);
+        assert!(matcher.is_generated("vendor/github.com/x/y.go"));
+        assert!(matcher.is_generated("services/api/vendor/lib/z.go"));
+        assert!(!matcher.is_generated("src/vendor.rs"));
+        assert!(!matcher.is_generated("vendored/a.go"));
+    }
+
+    #[test]
+    fn patch_has_line_checks_the_side_within_hunks() {
+        let patch = "--- a/x.rs\n+++ b/x.rs\n@@ -1,3 +1,3 @@\n keep\n-old\n+new\n keep\n@@ -10,1 +10,2 @@\n ten\n+eleven\n";
+        assert!(patch_has_line(patch, 1, "RIGHT"));
+        assert!(patch_has_line(patch, 2, "LEFT"));
+        assert!(patch_has_line(patch, 2, "RIGHT"));
+        assert!(patch_has_line(patch, 3, "LEFT"));
+        assert!(patch_has_line(patch, 11, "RIGHT"));
+        assert!(!patch_has_line(patch, 11, "LEFT"));
+        assert!(!patch_has_line(patch, 5, "RIGHT"));
+        assert!(!patch_has_line(patch, 12, "RIGHT"));
+    }
+
+    #[test]
+    fn git_header_path_handles_spaces_renames_and_dev_null() {
+        assert_eq!(
+            git_header_path("diff --git a/docs/a b/c.md b/docs/a b/c.md"),
+            Some("docs/a b/c.md")
+        );
+        assert_eq!(
+            git_header_path("diff --git a/old/name.rs b/new/name.rs"),
+            Some("new/name.rs")
+        );
+        assert_eq!(git_header_path("--- a/x.rs"), None);
+
+        assert_eq!(
+            file_header_path("rename to new/name.rs"),
+            Some("new/name.rs")
+        );
+        assert_eq!(
+            file_header_path("+++ b/with space.rs\t"),
+            Some("with space.rs")
+        );
+        assert_eq!(file_header_path("--- a/gone.rs"), Some("gone.rs"));
+        assert_eq!(file_header_path("+++ /dev/null"), None);
+        assert_eq!(file_header_path("--- /dev/null"), None);
+    }
+
+    #[test]
+    fn parse_diff_enhanced_keeps_removed_double_dash_lines_in_hunks() {
+        let diff = "diff --git a/q.sql b/q.sql\n--- a/q.sql\n+++ b/q.sql\n@@ -1,2 +1,2 @@\n--- old comment\n+++ new comment\n select 1;";
+        let lines = parse_diff_enhanced(diff);
+        assert_eq!(lines[1].line_type, DiffLineType::OldFile);
+        assert_eq!(lines[2].line_type, DiffLineType::NewFile);
+        assert_eq!(lines[4].line_type, DiffLineType::Removed);
+        assert_eq!(lines[4].old_line_num, Some(1));
+        assert_eq!(lines[5].line_type, DiffLineType::Added);
+        assert_eq!(lines[5].new_line_num, Some(1));
+        assert_eq!(lines[6].new_line_num, Some(2));
+    }
+
+    #[test]
+    fn diff_stat_counts_hunk_lines_per_file() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,3 @@\n fn main() {\n+    parse_config();\n--- not a header\n }\ndiff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-old\n+new\n";
+        assert_eq!(
+            diff_stat(diff),
+            " src/lib.rs | 2 +-\n README.md  | 2 +-\n 2 files changed, 2 insertions(+), 2 deletions(-)\n"
+        );
+    }
+}
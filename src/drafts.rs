@@ -0,0 +1,144 @@
+use crate::config;
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+/// One comment of an unsubmitted review
+#[derive(Debug, Clone, PartialEq)]
+pub struct DraftComment {
+    pub path: String,
+    pub start_line: Option<u32>,
+    pub line: u32,
+    /// `LEFT` (old file) or `RIGHT` (new file)
+    pub side: String,
+    pub body: String,
+}
+
+/// A pending review: the overall body plus line comments
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DraftReview {
+    pub body: String,
+    pub comments: Vec<DraftComment>,
+}
+
+/// Default export location: `drafts/<owner>-<repo>-<number>.md` in the config directory.
+pub fn default_path(repo: &str, pr_number: u64) -> PathBuf {
+    config::config_dir()
+        .join("drafts")
+        .join(format!("{}-{}.md", repo.replace('/', "-"), pr_number))
+}
+
+impl DraftReview {
+    /// Markdown with one `## \`path:line\` SIDE` section per comment.
+    pub fn to_markdown(&self, repo: &str, pr_number: u64) -> String {
+        let mut out = format!("# Draft review for {}#{}\n\n", repo, pr_number);
+        if !self.body.trim().is_empty() {
+            out.push_str(self.body.trim());
+            out.push_str("\n\n");
+        }
+        for comment in &self.comments {
+            let lines = match comment.start_line {
+                Some(start) if start != comment.line => format!("{}-{}", start, comment.line),
+                _ => comment.line.to_string(),
+            };
+            out.push_str(&format!(
+                "## `{}:{}` {}\n\n{}\n\n",
+                comment.path,
+                lines,
+                comment.side,
+                comment.body.trim()
+            ));
+        }
+        out
+    }
+
+    pub fn parse_markdown(markdown: &str) -> Result<Self> {
+        let mut draft = DraftReview::default();
+        let mut current: Option<DraftComment> = None;
+        let mut text = Vec::new();
+
+        let flush = |current: Option<DraftComment>, text: &mut Vec<&str>, draft: &mut Self| {
+            let body = text.join("\n").trim().to_string();
+            text.clear();
+            match current {
+                Some(mut comment) => {
+                    comment.body = body;
+                    draft.comments.push(comment);
+                }
+                None => draft.body = body,
+            }
+        };
+
+        for line in markdown.lines() {
+            if line.starts_with("# ") && current.is_none() && text.is_empty() {
+                continue;
+            }
+            if let Some(heading) = line.strip_prefix("## ") {
+                let comment = parse_heading(heading)
+                    .with_context(|| format!("Invalid draft comment heading '{}'", line))?;
+                flush(current.take(), &mut text, &mut draft);
+                current = Some(comment);
+                continue;
+            }
+            text.push(line);
+        }
+        flush(current, &mut text, &mut draft);
+        Ok(draft)
+    }
+}
+
+/// Parse `` `path:line` SIDE `` or `` `path:start-end` SIDE ``; the side defaults to `RIGHT`.
+fn parse_heading(heading: &str) -> Result<DraftComment> {
+    let heading = heading.trim();
+    let rest = heading.strip_prefix('`').context("expected `path:line`")?;
+    let (location, side) = rest.split_once('`').context("unterminated `path:line`")?;
+    let (path, lines) = location.rsplit_once(':').context("missing line number")?;
+    let (start_line, line) = match lines.split_once('-') {
+        Some((start, end)) => (Some(start.parse()?), end.parse()?),
+        None => (None, lines.parse()?),
+    };
+    let side = match side.trim().to_ascii_uppercase().as_str() {
+        "" | "RIGHT" => "RIGHT",
+        "LEFT" => "LEFT",
+        other => bail!("side must be LEFT or RIGHT, got '{}'", other),
+    };
+    Ok(DraftComment {
+        path: path.to_string(),
+        start_line,
+        line,
+        side: side.to_string(),
+        body: String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DraftComment, DraftReview};
+
+    #[test]
+    fn markdown_round_trips_body_and_comments() {
+        let draft = DraftReview {
+            body: "Mostly good, a few nits.".to_string(),
+            comments: vec![
+                DraftComment {
+                    path: "src/lib.rs".to_string(),
+                    start_line: None,
+                    line: 42,
+                    side: "RIGHT".to_string(),
+                    body: "Unchecked unwrap.\n\nConsider `?`.".to_string(),
+                },
+                DraftComment {
+                    path: "src/old.rs".to_string(),
+                    start_line: Some(3),
+                    line: 5,
+                    side: "LEFT".to_string(),
+                    body: "Why was this removed?".to_string(),
+                },
+            ],
+        };
+        let markdown = draft.to_markdown("org/reviewer", 7);
+        assert!(markdown.starts_with("# Draft review for org/reviewer#7\n"));
+        assert_eq!(DraftReview::parse_markdown(&markdown).unwrap(), draft);
+
+        assert!(DraftReview::parse_markdown("## src/lib.rs:42\n\nx").is_err());
+    }
+}
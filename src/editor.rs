@@ -0,0 +1,127 @@
+use crate::config::EditorConfig;
+use crate::private_fs;
+use crate::process::describe_command;
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// `$VISUAL`, then `$EDITOR`, then `vi`.
+fn env_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// The environment's editor, opened with the `+line file` convention most editors share.
+fn default_template() -> String {
+    format!("{} +{{line}} {{file}}", env_editor())
+}
+
+/// The command opening `file` (relative to the worktree `dir`) at `line` in the configured editor.
+fn command(config: &EditorConfig, dir: &Path, file: &str, line: u32) -> Result<Command> {
+    let template = config.command.clone().unwrap_or_else(default_template);
+    build(&template, dir, file, line)
+}
+
+/// Run the editor on `file` at `line` and wait for it, with the terminal handed over to it.
+pub fn open(config: &EditorConfig, dir: &Path, file: &str, line: u32) -> Result<()> {
+    let mut command = command(config, dir, file, line)?;
+    tracing::info!(command = %describe_command(&command), "opening editor");
+    let status = command.status().context("Failed to start the editor")?;
+    if !status.success() {
+        anyhow::bail!("editor exited with {}", status);
+    }
+    Ok(())
+}
+
+/// Edit `draft` in `$VISUAL`/`$EDITOR` through a temporary Markdown file and return what was
+/// saved, without trailing whitespace. Blocks until the editor exits.
+pub fn compose(draft: &str) -> Result<String> {
+    compose_with(&env_editor(), draft)
+}
+
+fn compose_with(editor: &str, draft: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!(
+        "reviewer-comment-{}-{}.md",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    private_fs::write(&path, draft)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    let result = edit_file(editor, &path);
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+fn edit_file(editor: &str, path: &Path) -> Result<String> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("$EDITOR is empty")?;
+    let mut command = Command::new(program);
+    command.args(parts).arg(path);
+    tracing::info!(command = %describe_command(&command), "composing comment");
+    let status = command.status().context("Failed to start the editor")?;
+    if !status.success() {
+        anyhow::bail!("editor exited with {}", status);
+    }
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(text.trim_end().to_string())
+}
+
+fn build(template: &str, dir: &Path, file: &str, line: u32) -> Result<Command> {
+    let file = dir.join(file).display().to_string();
+    let line = line.to_string();
+    let mut parts = template
+        .split_whitespace()
+        .map(|part| part.replace("{file}", &file).replace("{line}", &line));
+    let program = parts.next().context("editor.command is empty")?;
+    let mut command = Command::new(program);
+    command.args(parts).current_dir(dir);
+    if !template.contains("{file}") {
+        command.arg(&file);
+    }
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, compose_with, describe_command};
+    use std::path::Path;
+
+    #[test]
+    fn build_fills_file_and_line_placeholders() {
+        let dir = Path::new("/work/acme-widgets-pr-7");
+        let code = build("code -g {file}:{line}", dir, "src/lib.rs", 12).unwrap();
+        assert_eq!(
+            describe_command(&code),
+            "code -g /work/acme-widgets-pr-7/src/lib.rs:12"
+        );
+        assert_eq!(code.get_current_dir(), Some(dir));
+
+        let nvim = build("nvim +{line} {file}", dir, "src/lib.rs", 3).unwrap();
+        assert_eq!(
+            describe_command(&nvim),
+            "nvim +3 /work/acme-widgets-pr-7/src/lib.rs"
+        );
+
+        let bare = build("subl", dir, "README.md", 1).unwrap();
+        assert_eq!(
+            describe_command(&bare),
+            "subl /work/acme-widgets-pr-7/README.md"
+        );
+        assert!(build("  ", dir, "README.md", 1).is_err());
+    }
+
+    #[test]
+    fn compose_returns_the_saved_file_and_fails_with_the_editor() {
+        let draft = "## Summary\n\nFirst paragraph.\n\nSecond paragraph.\n\n";
+        assert_eq!(
+            compose_with("true", draft).unwrap(),
+            "## Summary\n\nFirst paragraph.\n\nSecond paragraph."
+        );
+        assert!(compose_with("false", draft).is_err());
+        assert!(compose_with(" ", draft).is_err());
+    }
+}
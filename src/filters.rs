@@ -40,6 +40,26 @@ pub fn wildcard_match(pattern: &str, text: &str) -> bool {
     pat_idx == pattern.len()
 }
 
+/// Match a file glob against a repo-relative path. Patterns without a `/` also
+/// match the bare file name, like `.gitignore`/`.gitattributes` patterns.
+pub fn path_glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches('/');
+    if wildcard_match(pattern, path) {
+        return true;
+    }
+    // A leading `**/` also matches at the top level, as in `.gitattributes`.
+    if let Some(rest) = pattern.strip_prefix("**/") {
+        if path_glob_match(rest, path) {
+            return true;
+        }
+    }
+    !pattern.contains('/')
+        && path
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| wildcard_match(pattern, name))
+}
+
 fn normalize_user_pattern(pattern: &str) -> Option<String> {
     let normalized = pattern
         .trim()
@@ -102,10 +122,31 @@ pub fn author_excluded(author: &str, author_kind: Option<&str>, patterns: &[Stri
     })
 }
 
+/// A bot login without the `app/` prefix and `[bot]` suffix GitHub adds depending on the API.
+fn bare_bot_login(login: &str) -> String {
+    let login = login.trim().trim_start_matches('@').to_ascii_lowercase();
+    let login = login
+        .strip_prefix("apps/")
+        .or_else(|| login.strip_prefix("app/"))
+        .unwrap_or(&login);
+    login.strip_suffix("[bot]").unwrap_or(login).to_string()
+}
+
+/// Whether `author` is one of the bot `patterns`, however GitHub spelled the login.
+pub fn author_is_listed_bot(author: &str, patterns: &[String]) -> bool {
+    let author = bare_bot_login(author);
+    !author.is_empty()
+        && patterns.iter().any(|pattern| {
+            let pattern = bare_bot_login(pattern);
+            !pattern.is_empty() && wildcard_match(&pattern, &author)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        api_excludable_author_logins, author_excluded, normalize_user_patterns, wildcard_match,
+        api_excludable_author_logins, author_excluded, author_is_listed_bot,
+        normalize_user_patterns, wildcard_match,
     };
 
     #[test]
@@ -153,6 +194,18 @@ mod tests {
         assert!(!author_excluded("alice", Some("User"), &patterns));
     }
 
+    #[test]
+    fn author_is_listed_bot_ignores_app_prefix_and_bot_suffix() {
+        let patterns = vec!["dependabot[bot]".to_string(), "acme-*".to_string()];
+
+        assert!(author_is_listed_bot("dependabot", &patterns));
+        assert!(author_is_listed_bot("app/Dependabot", &patterns));
+        assert!(author_is_listed_bot("dependabot[bot]", &patterns));
+        assert!(author_is_listed_bot("acme-release[bot]", &patterns));
+        assert!(!author_is_listed_bot("renovate[bot]", &patterns));
+        assert!(!author_is_listed_bot("alice", &patterns));
+    }
+
     #[test]
     fn author_excluded_apps_namespace_only_matches_app_actors() {
         let patterns = vec!["@apps/*".to_string()];
@@ -1,15 +1,72 @@
 use crate::agent;
-use crate::config::{self, AiConfig};
+use crate::ai_api;
+use crate::auth;
+use crate::clone_cache;
+use crate::config::{self, AiConfig, FetchConfig};
+use crate::drafts::{DraftComment, DraftReview};
 use crate::filters;
+use crate::gh_version::{self, Capability};
+use crate::logging::CommandExt;
+use crate::review_results;
+use crate::shell_escape;
+use crate::terminal;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::{Arc, Mutex, RwLock};
 
 const DEFAULT_PR_LIST_LIMIT: usize = 100;
 const FIRST_PAGE_PR_LIST_LIMIT: usize = 30;
 
+static FETCH: RwLock<Option<FetchConfig>> = RwLock::new(None);
+
+/// Apply the `fetch` config section; called at startup and on config reload.
+pub fn configure_fetch(config: &FetchConfig) {
+    *FETCH.write().unwrap_or_else(|e| e.into_inner()) = Some(config.clone());
+}
+
+fn fetch_settings() -> FetchConfig {
+    FETCH
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Search qualifiers for the `fetch` filters, so GitHub drops unwanted PRs before sending them.
+/// `with_authors` is false where the query already pins the author.
+fn fetch_qualifiers(fetch: &FetchConfig, with_authors: bool) -> Vec<String> {
+    let quote = |value: &str| {
+        if value.contains([' ', ',', '"']) {
+            format!("\"{}\"", value.replace('"', ""))
+        } else {
+            value.to_string()
+        }
+    };
+    let mut qualifiers = Vec::new();
+    if let Some(base) = fetch.base.as_deref().filter(|base| !base.is_empty()) {
+        qualifiers.push(format!("base:{}", quote(base)));
+    }
+    qualifiers.extend(
+        fetch
+            .labels
+            .iter()
+            .map(|label| format!("label:{}", quote(label))),
+    );
+    if with_authors {
+        qualifiers.extend(
+            fetch
+                .authors
+                .iter()
+                .map(|author| format!("author:{}", author.trim_start_matches('@'))),
+        );
+    }
+    qualifiers
+}
+
 #[derive(Debug, Deserialize)]
 struct RepoInfo {
     #[serde(rename = "nameWithOwner")]
@@ -40,6 +97,37 @@ impl Author {
 pub struct Review {
     pub author: Option<Author>,
     pub state: Option<String>,
+    #[serde(default)]
+    pub commit: Option<ReviewCommit>,
+}
+
+/// The commit a review was submitted against.
+#[derive(Debug, Deserialize)]
+pub struct ReviewCommit {
+    pub oid: String,
+}
+
+/// A pending review request for a user or team.
+#[derive(Debug, Deserialize)]
+struct ReviewRequest {
+    login: Option<String>,
+    name: Option<String>,
+}
+
+/// Latest review state for a single reviewer on a PR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewerState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Pending,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewerStatus {
+    pub login: String,
+    pub state: ReviewerState,
+    pub stale: bool, // The review predates the PR's current head commit
 }
 
 #[derive(Debug, Deserialize)]
@@ -53,6 +141,7 @@ pub struct Comment {
 /// A review comment on a specific line in the diff
 #[derive(Debug, Clone, Deserialize)]
 pub struct ReviewComment {
+    pub id: u64,
     pub user: Option<Author>,
     pub body: String,
     pub path: String,
@@ -66,6 +155,8 @@ pub struct ReviewComment {
     pub created_at: DateTime<Utc>,
     #[serde(rename = "in_reply_to_id")]
     pub in_reply_to_id: Option<u64>,
+    #[serde(default)]
+    pub side: Option<String>, // "LEFT" for the old file, "RIGHT" (the default) for the new one
 }
 
 #[derive(Debug, Deserialize)]
@@ -75,15 +166,42 @@ struct PrData {
     author: Option<Author>,
     body: Option<String>,
     url: String,
+    #[serde(rename = "createdAt", default)]
+    created_at: Option<DateTime<Utc>>,
     #[serde(rename = "updatedAt")]
     updated_at: DateTime<Utc>,
     additions: Option<u64>,
     deletions: Option<u64>,
+    #[serde(default)]
+    labels: Option<Vec<Label>>,
     reviews: Option<Vec<Review>>,
+    #[serde(rename = "reviewRequests", default)]
+    review_requests: Option<Vec<ReviewRequest>>,
     #[serde(rename = "isDraft")]
     is_draft: Option<bool>,
     #[serde(rename = "reviewDecision")]
     review_decision: Option<String>,
+    #[serde(rename = "baseRefName", default)]
+    base_ref_name: Option<String>,
+    #[serde(rename = "headRefName", default)]
+    head_ref_name: Option<String>,
+    #[serde(rename = "headRefOid", default)]
+    head_ref_oid: Option<String>,
+    #[serde(default)]
+    mergeable: Option<String>,
+}
+
+/// A PR or repo label, with GitHub's hex color (`d73a4a`, no `#`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Label {
+    pub name: String,
+    #[serde(default)]
+    pub color: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchLabels {
+    nodes: Vec<Label>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -100,11 +218,44 @@ struct SearchPrData {
     author: Option<Author>,
     body: Option<String>,
     url: String,
+    #[serde(rename = "createdAt", default)]
+    created_at: Option<DateTime<Utc>>,
     #[serde(rename = "updatedAt")]
     updated_at: DateTime<Utc>,
+    additions: Option<u64>,
+    deletions: Option<u64>,
+    #[serde(default)]
+    labels: Option<SearchLabels>,
     #[serde(rename = "isDraft")]
     is_draft: Option<bool>,
+    #[serde(rename = "baseRefName", default)]
+    base_ref_name: Option<String>,
+    #[serde(rename = "headRefName", default)]
+    head_ref_name: Option<String>,
     repository: SearchRepository,
+    #[serde(rename = "latestReviews", default)]
+    latest_reviews: Option<SearchReviews>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchReviews {
+    nodes: Vec<Review>,
+}
+
+impl SearchPrData {
+    /// Whether `username`'s most recent review on the PR is an approval.
+    fn approved_by(&self, username: &str) -> bool {
+        self.latest_reviews.as_ref().is_some_and(|reviews| {
+            reviews.nodes.iter().any(|review| {
+                review
+                    .author
+                    .as_ref()
+                    .and_then(|a| a.login.as_deref())
+                    .is_some_and(|login| login.eq_ignore_ascii_case(username))
+                    && review.state.as_deref() == Some("APPROVED")
+            })
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -161,11 +312,21 @@ pub struct PullRequest {
     pub repo_path: PathBuf,
     pub repo_name: String,
     pub url: String,
+    pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub additions: u64,
     pub deletions: u64,
     pub is_draft: bool,
+    pub labels: Vec<Label>,
+    /// Branch the PR merges into, and the one it merges from; empty when unknown.
+    pub base_branch: String,
+    pub head_branch: String,
+    /// Head commit SHA; empty when unknown.
+    pub head_oid: String,
+    /// Whether it merges without conflicts; `None` until details load or GitHub has checked.
+    pub mergeable: Option<bool>,
     pub review_state: ReviewState,
+    pub reviewers: Vec<ReviewerStatus>,
     pub details_loaded: bool,
 }
 
@@ -174,16 +335,30 @@ pub struct PullRequestPage {
     pub prs: Vec<PullRequest>,
     pub end_cursor: Option<String>,
     pub has_next_page: bool,
+    /// Repos whose PRs could not be listed, so an auth or network failure isn't shown as an
+    /// empty list.
+    pub errors: Vec<RepoFetchError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoFetchError {
+    pub repo: String,
+    pub reason: String,
 }
 
 pub fn get_current_user() -> Result<String> {
     let output = Command::new("gh")
         .args(["api", "user", "--jq", ".login"])
-        .output()
+        .logged_output()
         .context("Failed to run gh cli")?;
 
     if !output.status.success() {
-        anyhow::bail!("gh auth failed - is gh cli authenticated?");
+        if auth::configured() {
+            anyhow::bail!("gh auth failed - check the token from the config's auth section");
+        }
+        anyhow::bail!(
+            "gh auth failed - run `gh auth login`, set GH_TOKEN, or configure auth.token_env/auth.token_command"
+        );
     }
 
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -193,7 +368,7 @@ fn get_repo_info(repo_path: &PathBuf) -> Option<RepoInfo> {
     let output = Command::new("gh")
         .args(["repo", "view", "--json", "nameWithOwner"])
         .current_dir(repo_path)
-        .output()
+        .logged_output()
         .ok()?;
 
     if !output.status.success() {
@@ -207,25 +382,31 @@ pub fn repo_name_with_owner(repo_path: &PathBuf) -> Option<String> {
     get_repo_info(repo_path).map(|info| info.name_with_owner)
 }
 
-fn get_open_prs(repo_path: &PathBuf, limit: usize) -> Vec<PrData> {
+fn get_open_prs(repo_path: &PathBuf, limit: usize) -> Result<Vec<PrData>> {
     let limit_arg = limit.to_string();
-    let output = Command::new("gh")
+    let mut command = Command::new("gh");
+    command
         .args([
             "pr",
             "list",
             "--json",
-            "number,title,author,body,url,updatedAt,additions,deletions,reviews,isDraft,reviewDecision",
+            "number,title,author,body,url,createdAt,updatedAt,additions,deletions,reviews,reviewRequests,isDraft,reviewDecision,labels,headRefOid",
             "--limit",
         ])
-        .arg(&limit_arg)
+        .arg(&limit_arg);
+    let qualifiers = fetch_qualifiers(&fetch_settings(), true);
+    if !qualifiers.is_empty() {
+        command.arg("--search").arg(qualifiers.join(" "));
+    }
+    let output = command
         .current_dir(repo_path)
-        .output()
-        .ok();
+        .logged_output()
+        .context("Failed to run gh pr list")?;
 
-    match output {
-        Some(o) if o.status.success() => serde_json::from_slice(&o.stdout).unwrap_or_default(),
-        _ => Vec::new(),
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
     }
+    serde_json::from_slice(&output.stdout).context("Failed to parse gh pr list output")
 }
 
 fn has_user_approved(pr: &PrData, username: &str) -> bool {
@@ -256,6 +437,75 @@ fn review_state_from_fields(is_draft: bool, review_decision: Option<&str>) -> Re
     }
 }
 
+/// Collapse the chronological review list into the latest state per reviewer.
+/// Outstanding review requests override earlier reviews, since the reviewer was re-requested.
+/// With `head_oid` known, a review of an older commit is marked stale.
+fn reviewer_statuses(
+    reviews: &[Review],
+    review_requests: &[ReviewRequest],
+    pr_author: &str,
+    head_oid: Option<&str>,
+) -> Vec<ReviewerStatus> {
+    let mut statuses: Vec<ReviewerStatus> = Vec::new();
+
+    for review in reviews {
+        let Some(login) = review.author.as_ref().and_then(|a| a.login.as_deref()) else {
+            continue;
+        };
+        if login == pr_author {
+            continue;
+        }
+        let state = match review.state.as_deref() {
+            Some("APPROVED") => ReviewerState::Approved,
+            Some("CHANGES_REQUESTED") => ReviewerState::ChangesRequested,
+            Some("COMMENTED") => ReviewerState::Commented,
+            Some("DISMISSED") => ReviewerState::Pending,
+            _ => continue,
+        };
+        let stale = state != ReviewerState::Pending
+            && head_oid.is_some_and(|head| {
+                review
+                    .commit
+                    .as_ref()
+                    .is_some_and(|commit| commit.oid != head)
+            });
+        match statuses.iter_mut().find(|status| status.login == login) {
+            // A plain comment does not replace an earlier approval or change request.
+            Some(existing)
+                if state == ReviewerState::Commented
+                    && existing.state != ReviewerState::Pending => {}
+            Some(existing) => {
+                existing.state = state;
+                existing.stale = stale;
+            }
+            None => statuses.push(ReviewerStatus {
+                login: login.to_string(),
+                state,
+                stale,
+            }),
+        }
+    }
+
+    for request in review_requests {
+        let Some(login) = request.login.as_deref().or(request.name.as_deref()) else {
+            continue;
+        };
+        match statuses.iter_mut().find(|status| status.login == login) {
+            Some(existing) => {
+                existing.state = ReviewerState::Pending;
+                existing.stale = false;
+            }
+            None => statuses.push(ReviewerStatus {
+                login: login.to_string(),
+                state: ReviewerState::Pending,
+                stale: false,
+            }),
+        }
+    }
+
+    statuses
+}
+
 fn determine_review_state(pr_data: &PrData) -> ReviewState {
     review_state_from_fields(
         pr_data.is_draft.unwrap_or(false),
@@ -263,6 +513,15 @@ fn determine_review_state(pr_data: &PrData) -> ReviewState {
     )
 }
 
+/// GitHub's `mergeable` field: `MERGEABLE`, `CONFLICTING`, or `UNKNOWN` while it computes.
+fn mergeable(state: Option<&str>) -> Option<bool> {
+    match state {
+        Some("MERGEABLE") => Some(true),
+        Some("CONFLICTING") => Some(false),
+        _ => None,
+    }
+}
+
 fn pr_data_to_pull_request(pr_data: PrData, repo_path: PathBuf, repo_name: String) -> PullRequest {
     let pr_author = pr_data
         .author
@@ -272,6 +531,12 @@ fn pr_data_to_pull_request(pr_data: PrData, repo_path: PathBuf, repo_name: Strin
         .unwrap_or("unknown");
     let author_kind = pr_data.author.as_ref().and_then(Author::actor_kind);
     let review_state = determine_review_state(&pr_data);
+    let reviewers = reviewer_statuses(
+        pr_data.reviews.as_deref().unwrap_or_default(),
+        pr_data.review_requests.as_deref().unwrap_or_default(),
+        pr_author,
+        pr_data.head_ref_oid.as_deref(),
+    );
 
     PullRequest {
         number: pr_data.number,
@@ -282,11 +547,18 @@ fn pr_data_to_pull_request(pr_data: PrData, repo_path: PathBuf, repo_name: Strin
         repo_path,
         repo_name,
         url: pr_data.url,
+        created_at: pr_data.created_at.unwrap_or(pr_data.updated_at),
         updated_at: pr_data.updated_at,
         additions: pr_data.additions.unwrap_or(0),
         deletions: pr_data.deletions.unwrap_or(0),
         is_draft: pr_data.is_draft.unwrap_or(false),
+        labels: pr_data.labels.unwrap_or_default(),
+        base_branch: pr_data.base_ref_name.unwrap_or_default(),
+        head_branch: pr_data.head_ref_name.unwrap_or_default(),
+        head_oid: pr_data.head_ref_oid.unwrap_or_default(),
+        mergeable: mergeable(pr_data.mergeable.as_deref()),
         review_state,
+        reviewers,
         details_loaded: true,
     }
 }
@@ -311,11 +583,21 @@ fn search_pr_data_to_pull_request(pr_data: SearchPrData) -> PullRequest {
         repo_path: PathBuf::new(),
         repo_name: pr_data.repository.name_with_owner,
         url: pr_data.url,
+        created_at: pr_data.created_at.unwrap_or(pr_data.updated_at),
         updated_at: pr_data.updated_at,
-        additions: 0,
-        deletions: 0,
+        additions: pr_data.additions.unwrap_or(0),
+        deletions: pr_data.deletions.unwrap_or(0),
         is_draft,
+        labels: pr_data
+            .labels
+            .map(|labels| labels.nodes)
+            .unwrap_or_default(),
+        base_branch: pr_data.base_ref_name.unwrap_or_default(),
+        head_branch: pr_data.head_ref_name.unwrap_or_default(),
+        head_oid: String::new(),
+        mergeable: None,
         review_state,
+        reviewers: Vec::new(),
         details_loaded: false,
     }
 }
@@ -336,11 +618,8 @@ fn fetch_prs_for_repo_with_mode(
     include_drafts: bool,
     mode: RepoPrFetchMode,
     limit: usize,
-) -> Vec<PullRequest> {
-    let prs_data = get_open_prs(repo_path, limit);
-    if prs_data.is_empty() {
-        return Vec::new();
-    }
+) -> Result<Vec<PullRequest>> {
+    let prs_data = get_open_prs(repo_path, limit)?;
 
     let mut repo_name_fallback: Option<String> = None;
     let mut prs = Vec::new();
@@ -383,14 +662,14 @@ fn fetch_prs_for_repo_with_mode(
         ));
     }
 
-    prs
+    Ok(prs)
 }
 
 pub fn fetch_prs_for_repo_with_authored(
     repo_path: &PathBuf,
     username: &str,
     include_drafts: bool,
-) -> Vec<PullRequest> {
+) -> Result<Vec<PullRequest>> {
     fetch_prs_for_repo_with_mode(
         repo_path,
         username,
@@ -414,10 +693,10 @@ pub fn fetch_pr_for_review(
             "--repo",
             repo_name,
             "--json",
-            "number,title,author,body,url,updatedAt,additions,deletions,isDraft,reviewDecision",
+            "number,title,author,body,url,createdAt,updatedAt,additions,deletions,reviews,reviewRequests,isDraft,reviewDecision,labels,headRefOid",
         ])
         .current_dir(repo_path)
-        .output()
+        .logged_output()
         .context("Failed to fetch PR details")?;
 
     if !output.status.success() {
@@ -450,9 +729,9 @@ pub fn fetch_pr_details(pr: &PullRequest) -> Result<PullRequest> {
             "--repo",
             &pr.repo_name,
             "--json",
-            "number,title,author,body,url,updatedAt,additions,deletions,isDraft,reviewDecision",
+            "number,title,author,body,url,createdAt,updatedAt,additions,deletions,reviews,reviewRequests,isDraft,reviewDecision,labels,baseRefName,headRefName,headRefOid,mergeable",
         ])
-        .output()
+        .logged_output()
         .context("Failed to fetch PR details")?;
 
     if !output.status.success() {
@@ -497,7 +776,7 @@ pub fn get_pr_changed_files(pr: &PullRequest) -> Result<Vec<String>> {
             "--json",
             "files",
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR changed files")?;
 
     if !output.status.success() {
@@ -521,6 +800,8 @@ pub fn get_pr_changed_files(pr: &PullRequest) -> Result<Vec<String>> {
 enum SearchScope {
     Involved,
     Authored,
+    ApprovedByMe,
+    DependencyBot(&'static str),
 }
 
 /// Search for the first page of open PRs involving the current user.
@@ -540,6 +821,46 @@ pub fn search_involved_prs(
     )
 }
 
+/// Search for open PRs by others that the current user approved but are not merged yet.
+pub fn search_my_approvals(
+    username: &str,
+    include_drafts: bool,
+    after: Option<&str>,
+    exclude_users: &[String],
+) -> PullRequestPage {
+    search_prs_with_limit(
+        username,
+        include_drafts,
+        FIRST_PAGE_PR_LIST_LIMIT,
+        SearchScope::ApprovedByMe,
+        after,
+        exclude_users,
+    )
+}
+
+/// Search open dependency update PRs involving the current user, from every known bot.
+/// `exclude_users` is ignored here since bots are commonly excluded from the other tabs.
+pub fn search_dependency_prs(username: &str, include_drafts: bool) -> PullRequestPage {
+    let prs = crate::deps::DEPENDENCY_BOTS
+        .iter()
+        .flat_map(|bot| {
+            search_prs_with_limit(
+                username,
+                include_drafts,
+                DEFAULT_PR_LIST_LIMIT,
+                SearchScope::DependencyBot(bot),
+                None,
+                &[],
+            )
+            .prs
+        })
+        .collect();
+    PullRequestPage {
+        prs,
+        ..PullRequestPage::default()
+    }
+}
+
 /// Search for the first page of open PRs authored by the current user.
 pub fn search_my_prs(
     username: &str,
@@ -562,12 +883,21 @@ fn search_qualifiers(
     include_drafts: bool,
     scope: SearchScope,
     exclude_users: &[String],
+    fetch: &FetchConfig,
 ) -> Vec<String> {
     let mut qualifiers = vec!["is:pr".to_string(), "is:open".to_string()];
     qualifiers.push(match scope {
         SearchScope::Involved => format!("involves:{username}"),
         SearchScope::Authored => format!("author:{username}"),
+        SearchScope::ApprovedByMe => format!("reviewed-by:{username}"),
+        SearchScope::DependencyBot(_) => format!("involves:{username}"),
     });
+    if let SearchScope::DependencyBot(bot) = scope {
+        qualifiers.push(format!("author:app/{bot}"));
+    }
+    if matches!(scope, SearchScope::ApprovedByMe) {
+        qualifiers.push(format!("-author:{username}"));
+    }
     if !include_drafts {
         qualifiers.push("draft:false".to_string());
     }
@@ -576,6 +906,13 @@ fn search_qualifiers(
             .into_iter()
             .flat_map(|author| [format!("-author:{author}"), format!("-author:app/{author}")]),
     );
+    // Dependency bot PRs have their own author and usually their own labels.
+    if !matches!(scope, SearchScope::DependencyBot(_)) {
+        qualifiers.extend(fetch_qualifiers(
+            fetch,
+            !matches!(scope, SearchScope::Authored),
+        ));
+    }
     qualifiers.push("sort:updated-desc".to_string());
     qualifiers
 }
@@ -588,7 +925,13 @@ fn search_prs_with_limit(
     after: Option<&str>,
     exclude_users: &[String],
 ) -> PullRequestPage {
-    let qualifiers = search_qualifiers(username, include_drafts, scope, exclude_users);
+    let qualifiers = search_qualifiers(
+        username,
+        include_drafts,
+        scope,
+        exclude_users,
+        &fetch_settings(),
+    );
     let search_query = qualifiers.join(" ");
     let query_literal = serde_json::to_string(&search_query).unwrap_or_default();
     let first = limit.min(100);
@@ -596,6 +939,13 @@ fn search_prs_with_limit(
         .and_then(|cursor| serde_json::to_string(cursor).ok())
         .map(|cursor| format!(", after: {cursor}"))
         .unwrap_or_default();
+    // Only the approvals view needs review states, to keep the latest review per reviewer.
+    let review_fields = match scope {
+        SearchScope::ApprovedByMe => {
+            "latestReviews(first: 50) { nodes { author { login } state } }"
+        }
+        SearchScope::Involved | SearchScope::Authored | SearchScope::DependencyBot(_) => "",
+    };
     let query = format!(
         r#"query {{
             search(query: {query_literal}, type: ISSUE, first: {first}{after_arg}) {{
@@ -609,11 +959,23 @@ fn search_prs_with_limit(
                         }}
                         body
                         url
+                        createdAt
                         updatedAt
+                        additions
+                        deletions
+                        labels(first: 20) {{
+                            nodes {{
+                                name
+                                color
+                            }}
+                        }}
                         isDraft
+                        baseRefName
+                        headRefName
                         repository {{
                             nameWithOwner
                         }}
+                        {review_fields}
                     }}
                 }}
                 pageInfo {{
@@ -628,7 +990,7 @@ fn search_prs_with_limit(
     let output = Command::new("gh")
         .args(["api", "graphql", "-f"])
         .arg(query_arg)
-        .output()
+        .logged_output()
         .ok();
 
     let response: SearchResponse = match output {
@@ -644,17 +1006,63 @@ fn search_prs_with_limit(
             let SearchNodes { nodes, page_info } = data.search;
             let prs = nodes
                 .into_iter()
-                .map(search_pr_data_to_pull_request)
+                .filter(|node| match scope {
+                    SearchScope::ApprovedByMe => node.approved_by(username),
+                    SearchScope::Involved
+                    | SearchScope::Authored
+                    | SearchScope::DependencyBot(_) => true,
+                })
+                .map(|node| {
+                    let mut pr = search_pr_data_to_pull_request(node);
+                    if matches!(scope, SearchScope::ApprovedByMe) {
+                        pr.review_state = ReviewState::Approved;
+                    }
+                    pr
+                })
                 .collect();
             PullRequestPage {
                 prs,
                 end_cursor: page_info.end_cursor,
                 has_next_page: page_info.has_next_page,
+                ..PullRequestPage::default()
             }
         })
         .unwrap_or_default()
 }
 
+/// Fetch `.gitattributes` from the PR head, or `None` if the repo has none.
+pub fn get_gitattributes(pr: &PullRequest) -> Result<Option<String>> {
+    get_repo_file(pr, ".gitattributes")
+}
+
+/// Fetch a file from the PR head, or `None` if it does not exist there.
+pub fn get_repo_file(pr: &PullRequest, path: &str) -> Result<Option<String>> {
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "-H",
+            "Accept: application/vnd.github.raw",
+            &format!(
+                "repos/{}/contents/{}?ref=refs/pull/{}/head",
+                pr.repo_name,
+                path.trim_start_matches('/'),
+                pr.number
+            ),
+        ])
+        .logged_output()
+        .with_context(|| format!("Failed to fetch {}", path))?;
+
+    if output.status.success() {
+        return Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("Not Found") || stderr.contains("404") {
+        return Ok(None);
+    }
+    anyhow::bail!("Failed to fetch {}: {}", path, stderr.trim())
+}
+
 pub fn get_pr_diff(pr: &PullRequest) -> Result<String> {
     let output = Command::new("gh")
         .args([
@@ -664,7 +1072,7 @@ pub fn get_pr_diff(pr: &PullRequest) -> Result<String> {
             "--repo",
             &pr.repo_name,
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR diff")?;
 
     if output.status.success() {
@@ -689,15 +1097,43 @@ struct PrRefs {
 }
 
 fn get_pr_diff_local(pr: &PullRequest) -> Result<String> {
-    if pr.repo_path.as_os_str().is_empty() {
+    let repo_path = if !pr.repo_path.as_os_str().is_empty() {
+        pr.repo_path.clone()
+    } else if clone_cache::enabled() {
+        clone_cache::repo_path(&pr.repo_name)?
+    } else {
         anyhow::bail!(
             "Diff is too large for gh to fetch directly and no local clone is associated with {}#{}",
             pr.repo_name,
             pr.number
         );
+    };
+
+    let refs = get_pr_refs(pr, &repo_path)?;
+    fetch_pr_commits(pr, &repo_path, &[&refs.head_ref_oid])?;
+
+    // Generate diff locally
+    let diff_output = Command::new("git")
+        .args([
+            "diff",
+            &format!("{}...{}", refs.base_ref_oid, refs.head_ref_oid),
+        ])
+        .current_dir(&repo_path)
+        .logged_output()
+        .context("Failed to generate local diff")?;
+
+    if !diff_output.status.success() {
+        anyhow::bail!(
+            "Failed to generate diff: {}",
+            String::from_utf8_lossy(&diff_output.stderr)
+        );
     }
 
-    // Get the base and head commit SHAs
+    Ok(String::from_utf8_lossy(&diff_output.stdout).to_string())
+}
+
+/// The base and head commit SHAs of a PR
+fn get_pr_refs(pr: &PullRequest, repo_path: &std::path::Path) -> Result<PrRefs> {
     let output = Command::new("gh")
         .args([
             "pr",
@@ -708,8 +1144,8 @@ fn get_pr_diff_local(pr: &PullRequest) -> Result<String> {
             "--json",
             "baseRefOid,headRefOid",
         ])
-        .current_dir(&pr.repo_path)
-        .output()
+        .current_dir(repo_path)
+        .logged_output()
         .context("Failed to get PR refs")?;
 
     if !output.status.success() {
@@ -719,13 +1155,16 @@ fn get_pr_diff_local(pr: &PullRequest) -> Result<String> {
         );
     }
 
-    let refs: PrRefs = serde_json::from_slice(&output.stdout).context("Failed to parse PR refs")?;
+    serde_json::from_slice(&output.stdout).context("Failed to parse PR refs")
+}
 
-    // Fetch the head commit
+/// Fetch `commits` into the local clone, falling back to the PR's head ref.
+fn fetch_pr_commits(pr: &PullRequest, repo_path: &std::path::Path, commits: &[&str]) -> Result<()> {
     let fetch_output = Command::new("git")
-        .args(["fetch", "origin", &refs.head_ref_oid])
-        .current_dir(&pr.repo_path)
-        .output()
+        .args(["fetch", "origin"])
+        .args(commits)
+        .current_dir(repo_path)
+        .logged_output()
         .context("Failed to fetch head ref")?;
 
     if !fetch_output.status.success() {
@@ -733,28 +1172,246 @@ fn get_pr_diff_local(pr: &PullRequest) -> Result<String> {
         let pr_ref = format!("refs/pull/{}/head", pr.number);
         let _ = Command::new("git")
             .args(["fetch", "origin", &pr_ref])
-            .current_dir(&pr.repo_path)
-            .output();
+            .current_dir(repo_path)
+            .logged_output();
     }
+    Ok(())
+}
 
-    // Generate diff locally
-    let diff_output = Command::new("git")
+/// The commit that last touched a line, from `git blame`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    pub commit: String,
+    pub author: String,
+    pub authored_at: DateTime<Utc>,
+    pub summary: String,
+}
+
+/// Blame `line` of `path` as it was before the PR, using the local clone under `repos_root`.
+pub fn blame_line(
+    pr: &PullRequest,
+    repos_root: &std::path::Path,
+    path: &str,
+    line: u32,
+) -> Result<BlameLine> {
+    let repo_path = resolve_worktree_repo_path(pr, repos_root)?;
+    let refs = get_pr_refs(pr, &repo_path)?;
+    fetch_pr_commits(pr, &repo_path, &[&refs.base_ref_oid, &refs.head_ref_oid])?;
+
+    // Old line numbers in the PR diff count from the merge base, not the base branch tip.
+    let merge_base = Command::new("git")
+        .args(["merge-base", &refs.base_ref_oid, &refs.head_ref_oid])
+        .current_dir(&repo_path)
+        .logged_output()
+        .context("Failed to find the merge base")?;
+    if !merge_base.status.success() {
+        anyhow::bail!(
+            "Failed to find the merge base: {}",
+            String::from_utf8_lossy(&merge_base.stderr).trim()
+        );
+    }
+    let merge_base = String::from_utf8_lossy(&merge_base.stdout)
+        .trim()
+        .to_string();
+
+    let output = Command::new("git")
+        .args(["blame", "--porcelain", "-L", &format!("{},{}", line, line)])
+        .arg(&merge_base)
+        .args(["--", path])
+        .current_dir(&repo_path)
+        .logged_output()
+        .context("Failed to run git blame")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git blame failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_blame_porcelain(&String::from_utf8_lossy(&output.stdout))
+        .with_context(|| format!("Unexpected git blame output for {}:{}", path, line))
+}
+
+fn parse_blame_porcelain(output: &str) -> Option<BlameLine> {
+    let mut lines = output.lines();
+    let commit = lines.next()?.split_whitespace().next()?.to_string();
+    let mut author = None;
+    let mut authored_at = None;
+    let mut summary = String::new();
+    for line in lines {
+        if line.starts_with('\t') {
+            break;
+        }
+        if let Some(name) = line.strip_prefix("author ") {
+            author = Some(name.to_string());
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            authored_at = DateTime::from_timestamp(time.parse().ok()?, 0);
+        } else if let Some(text) = line.strip_prefix("summary ") {
+            summary = text.to_string();
+        }
+    }
+    Some(BlameLine {
+        commit,
+        author: author?,
+        authored_at: authored_at?,
+        summary,
+    })
+}
+
+/// A file changed by a PR, without its patch
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChangedFile {
+    #[serde(rename = "filename")]
+    pub path: String,
+    /// `added`, `removed`, `modified`, `renamed`, `copied`, `changed` or `unchanged`
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub additions: u64,
+    #[serde(default)]
+    pub deletions: u64,
+    /// Where a renamed or copied file came from
+    #[serde(default)]
+    pub previous_filename: Option<String>,
+}
+
+fn parse_changed_files(output: &[u8]) -> Result<Vec<ChangedFile>> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).context("Failed to parse changed file"))
+        .collect()
+}
+
+/// List the files changed by a PR. Patches are dropped by `--jq` so very large
+/// PRs never hold the whole diff in memory.
+pub fn get_pr_files(pr: &PullRequest) -> Result<Vec<ChangedFile>> {
+    let output = Command::new("gh")
         .args([
-            "diff",
-            &format!("{}...{}", refs.base_ref_oid, refs.head_ref_oid),
+            "api",
+            "--paginate",
+            &format!("repos/{}/pulls/{}/files", pr.repo_name, pr.number),
+            "--jq",
+            ".[] | {filename, status, additions, deletions, previous_filename}",
         ])
-        .current_dir(&pr.repo_path)
-        .output()
-        .context("Failed to generate local diff")?;
+        .logged_output()
+        .context("Failed to list PR files")?;
 
-    if !diff_output.status.success() {
+    if !output.status.success() {
         anyhow::bail!(
-            "Failed to generate diff: {}",
-            String::from_utf8_lossy(&diff_output.stderr)
+            "Failed to list PR files: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
         );
     }
 
-    Ok(String::from_utf8_lossy(&diff_output.stdout).to_string())
+    parse_changed_files(&output.stdout)
+}
+
+/// A changed file together with its patch, as listed by the PR files API
+#[derive(Debug, Clone, Deserialize)]
+struct FilePatch {
+    #[serde(flatten)]
+    file: ChangedFile,
+    /// Missing for binary files and patches too large for the API
+    #[serde(default)]
+    patch: Option<String>,
+}
+
+/// Patches of the last PR whose files were opened, keyed by repo, number and head SHA.
+/// Only one PR is kept so a huge diff is not held after moving on.
+type FilePatchCache = ((String, u64, String), Arc<[FilePatch]>);
+
+static FILE_PATCHES: Mutex<Option<FilePatchCache>> = Mutex::new(None);
+
+/// All of `pr`'s file patches, listed once per head SHA and shared until the head moves.
+fn cached_file_patches(pr: &PullRequest) -> Result<Arc<[FilePatch]>> {
+    let key = (pr.repo_name.clone(), pr.number, pr.head_oid.clone());
+    let cache = FILE_PATCHES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, patches)) = cache.as_ref().filter(|(cached, _)| *cached == key) {
+        return Ok(Arc::clone(patches));
+    }
+    drop(cache);
+
+    let patches: Arc<[FilePatch]> = list_file_patches(pr, None)?.into();
+    *FILE_PATCHES.lock().unwrap_or_else(|e| e.into_inner()) = Some((key, Arc::clone(&patches)));
+    Ok(patches)
+}
+
+/// `pr`'s changed files with their patches, or only `only`'s entry when given.
+fn list_file_patches(pr: &PullRequest, only: Option<&str>) -> Result<Vec<FilePatch>> {
+    let fields = "{filename, status, additions, deletions, previous_filename, patch}";
+    let jq = match only {
+        Some(path) => format!(
+            ".[] | select(.filename == {}) | {}",
+            serde_json::to_string(path).context("Failed to encode file path")?,
+            fields
+        ),
+        None => format!(".[] | {}", fields),
+    };
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "--paginate",
+            &format!("repos/{}/pulls/{}/files", pr.repo_name, pr.number),
+            "--jq",
+            &jq,
+        ])
+        .logged_output()
+        .context("Failed to get file diff")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list PR file diffs: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).context("Failed to parse file diff"))
+        .collect()
+}
+
+/// The git extended header for `file`: renames name both paths, and added or removed
+/// files read from or write to `/dev/null`.
+fn file_diff_header(file: &ChangedFile) -> String {
+    let path = &file.path;
+    match (file.status.as_str(), file.previous_filename.as_deref()) {
+        ("renamed", Some(from)) => format!(
+            "diff --git a/{from} b/{path}\nrename from {from}\nrename to {path}\n--- a/{from}\n+++ b/{path}\n"
+        ),
+        ("added", _) => {
+            format!("diff --git a/{path} b/{path}\nnew file mode 100644\n--- /dev/null\n+++ b/{path}\n")
+        }
+        ("removed", _) => format!(
+            "diff --git a/{path} b/{path}\ndeleted file mode 100644\n--- a/{path}\n+++ /dev/null\n"
+        ),
+        _ => format!("diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n"),
+    }
+}
+
+/// Fetch the unified diff for a single file of a PR. Without a known head SHA a cached
+/// listing could predate a push, so only that file is fetched and nothing is cached.
+pub fn get_pr_file_diff(pr: &PullRequest, path: &str) -> Result<String> {
+    let patches = if pr.head_oid.is_empty() {
+        list_file_patches(pr, Some(path)).map(Arc::from)
+    } else {
+        cached_file_patches(pr)
+    }
+    .with_context(|| format!("Failed to get diff for {}", path))?;
+    let Some(file) = patches.iter().find(|patch| patch.file.path == path) else {
+        anyhow::bail!("{} is not changed by {}#{}", path, pr.repo_name, pr.number);
+    };
+    let patch = file.patch.as_deref().unwrap_or_default();
+    if patch.trim().is_empty() {
+        anyhow::bail!("No text diff available for {} (binary or too large)", path);
+    }
+
+    Ok(format!(
+        "{}{}\n",
+        file_diff_header(&file.file),
+        patch.trim_end_matches('\n')
+    ))
 }
 
 pub fn get_pr_comments(pr: &PullRequest) -> Result<Vec<Comment>> {
@@ -770,7 +1427,7 @@ pub fn get_pr_comments(pr: &PullRequest) -> Result<Vec<Comment>> {
             "--jq",
             ".comments",
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR comments")?;
 
     if !output.status.success() {
@@ -786,7 +1443,7 @@ pub fn get_review_comments(pr: &PullRequest) -> Result<Vec<ReviewComment>> {
     let api_path = format!("repos/{}/pulls/{}/comments", pr.repo_name, pr.number);
     let output = Command::new("gh")
         .args(["api", &api_path])
-        .output()
+        .logged_output()
         .context("Failed to get review comments")?;
 
     if !output.status.success() {
@@ -797,23 +1454,214 @@ pub fn get_review_comments(pr: &PullRequest) -> Result<Vec<ReviewComment>> {
     Ok(comments)
 }
 
-pub fn add_pr_comment(pr: &PullRequest, comment: &str) -> Result<()> {
-    let output = Command::new("gh")
-        .args([
-            "pr",
-            "comment",
-            &pr.number.to_string(),
-            "--repo",
-            &pr.repo_name,
-            "--body",
-            comment,
-        ])
-        .output()
-        .context("Failed to add comment")?;
+#[derive(Debug, Deserialize)]
+struct PendingReviewData {
+    id: u64,
+    state: String,
+    #[serde(default)]
+    body: Option<String>,
+}
 
-    if !output.status.success() {
-        anyhow::bail!(
-            "Failed to add comment: {}",
+#[derive(Debug, Deserialize)]
+struct PendingCommentData {
+    path: String,
+    #[serde(default)]
+    line: Option<u32>,
+    #[serde(default)]
+    original_line: Option<u32>,
+    #[serde(default)]
+    start_line: Option<u32>,
+    #[serde(default)]
+    side: Option<String>,
+    body: String,
+}
+
+fn pending_comments_to_draft(comments: Vec<PendingCommentData>) -> Vec<DraftComment> {
+    comments
+        .into_iter()
+        .filter_map(|comment| {
+            // File-level comments have no line and cannot be re-created as line comments.
+            let line = comment.line.or(comment.original_line)?;
+            Some(DraftComment {
+                path: comment.path,
+                start_line: comment.start_line,
+                line,
+                side: comment.side.unwrap_or_else(|| "RIGHT".to_string()),
+                body: comment.body,
+            })
+        })
+        .collect()
+}
+
+/// The current user's pending (unsubmitted) review on the PR, if any.
+/// GitHub only returns a pending review to its author.
+pub fn get_pending_review(pr: &PullRequest) -> Result<Option<DraftReview>> {
+    let api_path = format!("repos/{}/pulls/{}/reviews", pr.repo_name, pr.number);
+    let output = Command::new("gh")
+        .args(["api", "--paginate", &api_path, "--jq", ".[]"])
+        .logged_output()
+        .context("Failed to list reviews")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list reviews: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let pending = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<PendingReviewData>()
+        .filter_map(|review| review.ok())
+        .find(|review| review.state == "PENDING");
+    let Some(review) = pending else {
+        return Ok(None);
+    };
+
+    let comments_path = format!("{}/{}/comments", api_path, review.id);
+    let output = Command::new("gh")
+        .args(["api", "--paginate", &comments_path, "--jq", ".[]"])
+        .logged_output()
+        .context("Failed to list pending review comments")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list pending review comments: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let comments = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<PendingCommentData>()
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse pending review comments")?;
+
+    Ok(Some(DraftReview {
+        body: review.body.unwrap_or_default(),
+        comments: pending_comments_to_draft(comments),
+    }))
+}
+
+/// How a submitted review counts on the PR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewEvent {
+    Comment,
+    Approve,
+    RequestChanges,
+}
+
+impl ReviewEvent {
+    pub fn api_name(self) -> &'static str {
+        match self {
+            ReviewEvent::Comment => "COMMENT",
+            ReviewEvent::Approve => "APPROVE",
+            ReviewEvent::RequestChanges => "REQUEST_CHANGES",
+        }
+    }
+}
+
+/// Reviews endpoint payload for `draft`. Without an `event`, GitHub leaves the review pending.
+fn review_payload(draft: &DraftReview, event: Option<ReviewEvent>) -> serde_json::Value {
+    let comments = draft
+        .comments
+        .iter()
+        .map(|comment| {
+            let mut value = serde_json::json!({
+                "path": comment.path,
+                "line": comment.line,
+                "side": comment.side,
+                "body": comment.body,
+            });
+            if let Some(start_line) = comment.start_line.filter(|start| *start != comment.line) {
+                value["start_line"] = start_line.into();
+                value["start_side"] = comment.side.clone().into();
+            }
+            value
+        })
+        .collect::<Vec<_>>();
+    let mut payload = serde_json::json!({
+        "body": draft.body,
+        "comments": comments,
+    });
+    if let Some(event) = event {
+        payload["event"] = event.api_name().into();
+    }
+    payload
+}
+
+/// Submit `draft` as a single review carrying all of its line comments.
+pub fn submit_review(pr: &PullRequest, draft: &DraftReview, event: ReviewEvent) -> Result<()> {
+    let api_path = format!("repos/{}/pulls/{}/reviews", pr.repo_name, pr.number);
+    let payload = review_payload(draft, Some(event));
+    let output = Command::new("gh")
+        .args(["api", &api_path, "-X", "POST", "--input", "-"])
+        .logged_output_with_input(payload.to_string().as_bytes())
+        .context("Failed to run gh command")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to submit review: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Create a pending review from a draft. Without an `event`, GitHub leaves it unsubmitted.
+pub fn create_pending_review(pr: &PullRequest, draft: &DraftReview) -> Result<()> {
+    let api_path = format!("repos/{}/pulls/{}/reviews", pr.repo_name, pr.number);
+    let payload = review_payload(draft, None);
+
+    let output = Command::new("gh")
+        .args(["api", &api_path, "-X", "POST", "--input", "-"])
+        .logged_output_with_input(payload.to_string().as_bytes())
+        .context("Failed to run gh command")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("pending review") {
+            anyhow::bail!(
+                "You already have a pending review on {}#{}; submit or discard it first",
+                pr.repo_name,
+                pr.number
+            );
+        }
+        anyhow::bail!("Failed to create pending review: {}", stderr.trim());
+    }
+    Ok(())
+}
+
+pub fn add_pr_comment(pr: &PullRequest, comment: &str) -> Result<()> {
+    let output = Command::new("gh")
+        .args([
+            "pr",
+            "comment",
+            &pr.number.to_string(),
+            "--repo",
+            &pr.repo_name,
+            "--body",
+            comment,
+        ])
+        .logged_output()
+        .context("Failed to add comment")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to add comment: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(())
+}
+
+/// Reply to the review thread started by `comment_id`, via `in_reply_to` on the
+/// pull request comments endpoint
+pub fn reply_to_review_comment(pr: &PullRequest, comment_id: u64, comment: &str) -> Result<()> {
+    let api_path = format!("repos/{}/pulls/{}/comments", pr.repo_name, pr.number);
+    let payload = serde_json::json!({ "body": comment, "in_reply_to": comment_id });
+    let output = Command::new("gh")
+        .args(["api", &api_path, "-X", "POST", "--input", "-"])
+        .logged_output_with_input(payload.to_string().as_bytes())
+        .context("Failed to run gh command")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to reply: {}",
             String::from_utf8_lossy(&output.stderr)
         );
     }
@@ -823,52 +1671,61 @@ pub fn add_pr_comment(pr: &PullRequest, comment: &str) -> Result<()> {
 
 /// Add a line-level comment to a PR using the reviews API
 /// `side` should be "LEFT" for removed lines (old file) or "RIGHT" for added/context lines (new file)
+/// Review payload for a comment on `line`, or on `start_line` through `line` when it spans
+/// several lines (both on `side`).
+fn line_comment_payload(
+    file_path: &str,
+    start_line: Option<u32>,
+    line: u32,
+    side: &str,
+    comment: &str,
+) -> serde_json::Value {
+    let draft = DraftReview {
+        body: String::new(),
+        comments: vec![DraftComment {
+            path: file_path.to_string(),
+            start_line,
+            line,
+            side: side.to_string(),
+            body: comment.to_string(),
+        }],
+    };
+    review_payload(&draft, Some(ReviewEvent::Comment))
+}
+
 pub fn add_line_comment(
     pr: &PullRequest,
     file_path: &str,
+    start_line: Option<u32>,
     line: u32,
     side: &str,
     comment: &str,
 ) -> Result<()> {
     // Use the reviews endpoint with a comments array
     let api_path = format!("repos/{}/pulls/{}/reviews", pr.repo_name, pr.number);
+    let payload = line_comment_payload(file_path, start_line, line, side, comment);
 
-    // Build complete JSON payload
-    let payload = serde_json::json!({
-        "event": "COMMENT",
-        "body": "",
-        "comments": [{
-            "path": file_path,
-            "line": line,
-            "side": side,
-            "body": comment
-        }]
-    });
-
-    let mut child = Command::new("gh")
+    // JSON payload goes in on stdin
+    let output = Command::new("gh")
         .args(["api", &api_path, "-X", "POST", "--input", "-"])
-        .stdin(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .context("Failed to spawn gh command")?;
-
-    // Write JSON payload to stdin
-    if let Some(mut stdin) = child.stdin.take() {
-        use std::io::Write;
-        stdin
-            .write_all(payload.to_string().as_bytes())
-            .context("Failed to write to gh stdin")?;
-    }
-
-    let output = child.wait_with_output().context("Failed to wait for gh")?;
+        .logged_output_with_input(payload.to_string().as_bytes())
+        .context("Failed to run gh command")?;
 
     if !output.status.success() {
+        // This runs on TUI background threads, so it is logged rather than printed
         let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("Line comment API failed: {}", stderr);
-        eprintln!("Payload was: {}", payload);
+        let stderr = stderr.trim();
+        tracing::warn!(
+            error = %stderr,
+            payload = %payload,
+            "line comment API failed, falling back to a PR comment"
+        );
         // If line comment fails, fall back to a general comment with file:line reference
-        let fallback_comment = format!("**{}:{}**\n\n{}", file_path, line, comment);
+        let lines = match start_line.filter(|start| *start < line) {
+            Some(start_line) => format!("{}-{}", start_line, line),
+            None => line.to_string(),
+        };
+        let fallback_comment = format!("**{}:{}**\n\n{}", file_path, lines, comment);
         return add_pr_comment(pr, &fallback_comment).context(format!(
             "Line comment failed ({}), fallback also failed",
             stderr
@@ -898,7 +1755,7 @@ pub fn approve_pr(pr: &PullRequest, comment: Option<&str>) -> Result<()> {
 
     let output = Command::new("gh")
         .args(&args)
-        .output()
+        .logged_output()
         .context("Failed to approve PR")?;
 
     if !output.status.success() {
@@ -926,7 +1783,7 @@ pub fn close_pr(pr: &PullRequest, comment: Option<&str>) -> Result<()> {
             "--repo",
             &pr.repo_name,
         ])
-        .output()
+        .logged_output()
         .context("Failed to close PR")?;
 
     if !output.status.success() {
@@ -950,7 +1807,7 @@ pub fn open_pr_in_browser(pr: &PullRequest) -> Result<()> {
             &pr.repo_name,
             "--web",
         ])
-        .output()
+        .logged_output()
         .context("Failed to open PR in browser")?;
 
     if !output.status.success() {
@@ -963,12 +1820,53 @@ pub fn open_pr_in_browser(pr: &PullRequest) -> Result<()> {
     Ok(())
 }
 
+/// Open a URL, e.g. a CI check's page, in the default browser.
+pub fn open_url(url: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+
+    #[cfg(target_os = "linux")]
+    let mut command = Command::new("xdg-open");
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+
+    let output = command
+        .arg(url)
+        .logged_output()
+        .context("Failed to open browser")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to open {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(())
+}
+
 /// CI check status
 #[derive(Debug, Clone)]
 pub struct CheckStatus {
-    #[allow(dead_code)] // Kept for potential future detailed CI view
     pub name: String,
     pub status: CheckState,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub link: Option<String>, // The check's page, on GitHub Actions or the external CI
+}
+
+impl CheckStatus {
+    /// How long the check ran, or has been running so far; `None` if it hasn't started.
+    pub fn duration(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        let started = self.started_at?;
+        Some(self.completed_at.unwrap_or(now) - started)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -981,6 +1879,7 @@ pub enum CheckState {
 
 /// Get CI/status checks for a PR
 pub fn get_pr_checks(pr: &PullRequest) -> Result<Vec<CheckStatus>> {
+    gh_version::require(Capability::PrChecksJson)?;
     let output = Command::new("gh")
         .args([
             "pr",
@@ -989,9 +1888,9 @@ pub fn get_pr_checks(pr: &PullRequest) -> Result<Vec<CheckStatus>> {
             "--repo",
             &pr.repo_name,
             "--json",
-            "name,state",
+            "name,state,startedAt,completedAt,link",
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR checks")?;
 
     if !output.status.success() {
@@ -999,15 +1898,51 @@ pub fn get_pr_checks(pr: &PullRequest) -> Result<Vec<CheckStatus>> {
         return Ok(Vec::new());
     }
 
+    Ok(parse_pr_checks(&output.stdout))
+}
+
+/// The overall result of a PR's checks, or `None` when it has none. As with required checks, a
+/// check passes if any run of that name succeeded or was skipped; any other failing check fails
+/// the rollup, and otherwise any pending one keeps it pending.
+pub fn checks_rollup(checks: &[CheckStatus]) -> Option<CheckState> {
+    if checks.is_empty() {
+        return None;
+    }
+    let passed = |name: &str| {
+        checks.iter().any(|check| {
+            check.name == name && matches!(check.status, CheckState::Success | CheckState::Neutral)
+        })
+    };
+    let open: Vec<&CheckState> = checks
+        .iter()
+        .filter(|check| !passed(&check.name))
+        .map(|check| &check.status)
+        .collect();
+    Some(if open.contains(&&CheckState::Failure) {
+        CheckState::Failure
+    } else if open.contains(&&CheckState::Pending) {
+        CheckState::Pending
+    } else {
+        CheckState::Success
+    })
+}
+
+fn parse_pr_checks(output: &[u8]) -> Vec<CheckStatus> {
     #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
     struct CheckData {
         name: String,
         state: Option<String>,
+        started_at: Option<DateTime<Utc>>,
+        completed_at: Option<DateTime<Utc>>,
+        link: Option<String>,
     }
 
-    let checks: Vec<CheckData> = serde_json::from_slice(&output.stdout).unwrap_or_default();
+    // `gh` reports times that haven't happened yet as 0001-01-01
+    let happened = |at: Option<DateTime<Utc>>| at.filter(|at| at.timestamp() > 0);
+    let checks: Vec<CheckData> = serde_json::from_slice(output).unwrap_or_default();
 
-    Ok(checks
+    checks
         .into_iter()
         .map(|c| {
             let status = match c.state.as_deref() {
@@ -1019,29 +1954,237 @@ pub fn get_pr_checks(pr: &PullRequest) -> Result<Vec<CheckStatus>> {
             CheckStatus {
                 name: c.name,
                 status,
+                started_at: happened(c.started_at),
+                completed_at: happened(c.completed_at),
+                link: c.link.filter(|link| !link.is_empty()),
             }
         })
-        .collect())
+        .collect()
 }
 
-/// Result of checking if a PR can be merged
-#[derive(Debug)]
-pub struct MergeStatus {
-    pub can_merge: bool,
-    pub reason: Option<String>,
+/// A comment within an unresolved review thread
+#[derive(Debug, Clone)]
+pub struct FeedbackComment {
+    pub author: String,
+    pub body: String,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
-/// Check if a PR can be merged (no unresolved threads, mergeable state)
-pub fn check_merge_status(pr: &PullRequest) -> MergeStatus {
-    // Use GraphQL to check reviewThreads for unresolved comments
+/// An unresolved review thread anchored to a file/line
+#[derive(Debug, Clone)]
+pub struct UnresolvedThread {
+    pub path: String,
+    pub line: Option<u32>,
+    pub is_outdated: bool,
+    pub comments: Vec<FeedbackComment>,
+}
+
+/// A reviewer's current "changes requested" review
+#[derive(Debug, Clone)]
+pub struct ChangeRequest {
+    pub author: String,
+    pub body: String,
+}
+
+/// Outstanding review feedback on a PR: change requests and unresolved threads
+#[derive(Debug, Clone, Default)]
+pub struct PrFeedback {
+    pub change_requests: Vec<ChangeRequest>,
+    pub threads: Vec<UnresolvedThread>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackAuthor {
+    login: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackCommentNode {
+    author: Option<FeedbackAuthor>,
+    body: String,
+    #[serde(rename = "createdAt")]
+    created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackCommentNodes {
+    nodes: Vec<FeedbackCommentNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackThreadNode {
+    #[serde(rename = "isResolved")]
+    is_resolved: bool,
+    #[serde(rename = "isOutdated", default)]
+    is_outdated: bool,
+    path: String,
+    line: Option<u32>,
+    #[serde(rename = "originalLine")]
+    original_line: Option<u32>,
+    comments: FeedbackCommentNodes,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackThreadNodes {
+    nodes: Vec<FeedbackThreadNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackReviewNode {
+    author: Option<FeedbackAuthor>,
+    state: String,
+    body: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackReviewNodes {
+    nodes: Vec<FeedbackReviewNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackPullRequest {
+    #[serde(rename = "latestOpinionatedReviews")]
+    latest_opinionated_reviews: Option<FeedbackReviewNodes>,
+    #[serde(rename = "reviewThreads")]
+    review_threads: Option<FeedbackThreadNodes>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackRepository {
+    #[serde(rename = "pullRequest")]
+    pull_request: Option<FeedbackPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackData {
+    repository: Option<FeedbackRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackResponse {
+    data: Option<FeedbackData>,
+}
+
+fn feedback_author(author: Option<FeedbackAuthor>) -> String {
+    author
+        .and_then(|a| a.login)
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn parse_pr_feedback(json: &[u8]) -> Result<PrFeedback> {
+    let response: FeedbackResponse =
+        serde_json::from_slice(json).context("Failed to parse review feedback response")?;
+    let pull_request = response
+        .data
+        .and_then(|d| d.repository)
+        .and_then(|r| r.pull_request)
+        .context("PR not found in review feedback response")?;
+
+    let change_requests = pull_request
+        .latest_opinionated_reviews
+        .map(|reviews| reviews.nodes)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|review| review.state == "CHANGES_REQUESTED")
+        .map(|review| ChangeRequest {
+            author: feedback_author(review.author),
+            body: review.body,
+        })
+        .collect();
+
+    let threads = pull_request
+        .review_threads
+        .map(|threads| threads.nodes)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|thread| !thread.is_resolved)
+        .map(|thread| UnresolvedThread {
+            path: thread.path,
+            line: thread.line.or(thread.original_line),
+            is_outdated: thread.is_outdated,
+            comments: thread
+                .comments
+                .nodes
+                .into_iter()
+                .map(|comment| FeedbackComment {
+                    author: feedback_author(comment.author),
+                    body: comment.body,
+                    created_at: comment.created_at,
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(PrFeedback {
+        change_requests,
+        threads,
+    })
+}
+
+/// A review thread's GraphQL id and resolution, keyed in `get_review_threads` by the REST
+/// id of its first comment so it lines up with `get_review_comments`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewThreadState {
+    pub id: String,
+    pub is_resolved: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadCommentIdNode {
+    #[serde(rename = "databaseId")]
+    database_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadCommentIdNodes {
+    nodes: Vec<ThreadCommentIdNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadStateNode {
+    id: String,
+    #[serde(rename = "isResolved")]
+    is_resolved: bool,
+    comments: ThreadCommentIdNodes,
+}
+
+fn parse_review_threads(json: &[u8]) -> Result<BTreeMap<u64, ReviewThreadState>> {
+    let response: serde_json::Value =
+        serde_json::from_slice(json).context("Failed to parse review threads response")?;
+    let nodes = response
+        .pointer("/data/repository/pullRequest/reviewThreads/nodes")
+        .cloned()
+        .context("PR not found in review threads response")?;
+    let nodes: Vec<ThreadStateNode> =
+        serde_json::from_value(nodes).context("Failed to parse review threads")?;
+    Ok(nodes
+        .into_iter()
+        .filter_map(|thread| {
+            let first = thread.comments.nodes.first()?.database_id?;
+            Some((
+                first,
+                ReviewThreadState {
+                    id: thread.id,
+                    is_resolved: thread.is_resolved,
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Resolution state of a PR's review threads, by the REST id of each thread's first comment
+pub fn get_review_threads(pr: &PullRequest) -> Result<BTreeMap<u64, ReviewThreadState>> {
     let query = format!(
         r#"query {{
             repository(owner: "{}", name: "{}") {{
                 pullRequest(number: {}) {{
-                    mergeable
                     reviewThreads(first: 100) {{
                         nodes {{
+                            id
                             isResolved
+                            comments(first: 1) {{
+                                nodes {{ databaseId }}
+                            }}
                         }}
                     }}
                 }}
@@ -1054,26 +2197,1008 @@ pub fn check_merge_status(pr: &PullRequest) -> MergeStatus {
 
     let output = Command::new("gh")
         .args(["api", "graphql", "-f", &format!("query={}", query)])
-        .output()
-        .ok();
+        .logged_output()
+        .context("Failed to fetch review threads")?;
 
-    #[derive(Deserialize)]
-    struct ReviewThread {
-        #[serde(rename = "isResolved")]
-        is_resolved: bool,
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch review threads: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
 
-    #[derive(Deserialize)]
-    struct ReviewThreadsNodes {
-        nodes: Vec<ReviewThread>,
-    }
+    parse_review_threads(&output.stdout)
+}
 
-    #[derive(Deserialize)]
-    struct PrInfo {
-        mergeable: Option<String>,
-        #[serde(rename = "reviewThreads")]
-        review_threads: Option<ReviewThreadsNodes>,
-    }
+/// Resolve or unresolve a review thread by its GraphQL id
+pub fn set_review_thread_resolved(thread_id: &str, resolved: bool) -> Result<()> {
+    let mutation = if resolved {
+        "resolveReviewThread"
+    } else {
+        "unresolveReviewThread"
+    };
+    let query = format!(
+        "mutation($threadId: ID!) {{ {}(input: {{threadId: $threadId}}) {{ thread {{ isResolved }} }} }}",
+        mutation
+    );
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "graphql",
+            "-f",
+            &format!("query={}", query),
+            "-f",
+            &format!("threadId={}", thread_id),
+        ])
+        .logged_output()
+        .context("Failed to update review thread")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to {} thread: {}",
+            if resolved { "resolve" } else { "unresolve" },
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetch outstanding review feedback (change requests + unresolved threads) for a PR
+pub fn get_pr_feedback(pr: &PullRequest) -> Result<PrFeedback> {
+    let query = format!(
+        r#"query {{
+            repository(owner: "{}", name: "{}") {{
+                pullRequest(number: {}) {{
+                    latestOpinionatedReviews(first: 50) {{
+                        nodes {{
+                            author {{ login }}
+                            state
+                            body
+                        }}
+                    }}
+                    reviewThreads(first: 100) {{
+                        nodes {{
+                            isResolved
+                            isOutdated
+                            path
+                            line
+                            originalLine
+                            comments(first: 50) {{
+                                nodes {{
+                                    author {{ login }}
+                                    body
+                                    createdAt
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        }}"#,
+        pr.repo_name.split('/').next().unwrap_or(""),
+        pr.repo_name.split('/').nth(1).unwrap_or(""),
+        pr.number
+    );
+
+    let output = Command::new("gh")
+        .args(["api", "graphql", "-f", &format!("query={}", query)])
+        .logged_output()
+        .context("Failed to fetch review feedback")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch review feedback: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_pr_feedback(&output.stdout)
+}
+
+/// Protection rules on a PR's base branch, with the PR's current approvals
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchProtection {
+    pub branch: String,
+    pub required_approvals: u32,
+    pub approvals: u32,
+    pub required_checks: Vec<String>,
+    pub enforce_admins: bool,
+}
+
+impl BranchProtection {
+    /// "needs 2 approvals, has 1" when the PR is short of required approvals
+    pub fn approval_shortfall(&self) -> Option<String> {
+        (self.approvals < self.required_approvals).then(|| {
+            format!(
+                "needs {} approval{}, has {}",
+                self.required_approvals,
+                if self.required_approvals == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+                self.approvals
+            )
+        })
+    }
+
+    /// Required checks that haven't passed on the PR, as `name: missing|failing|pending`, in the
+    /// rule's order. A check passes if any run of that name succeeded or was skipped.
+    pub fn unmet_checks(&self, checks: &[CheckStatus]) -> Vec<String> {
+        self.required_checks
+            .iter()
+            .filter_map(|required| {
+                let runs: Vec<&CheckState> = checks
+                    .iter()
+                    .filter(|check| &check.name == required)
+                    .map(|check| &check.status)
+                    .collect();
+                let problem = if runs.is_empty() {
+                    "missing"
+                } else if runs
+                    .iter()
+                    .any(|state| matches!(state, CheckState::Success | CheckState::Neutral))
+                {
+                    return None;
+                } else if runs.contains(&&CheckState::Pending) {
+                    "pending"
+                } else {
+                    "failing"
+                };
+                Some(format!("{}: {}", required, problem))
+            })
+            .collect()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!(
+            "{}: {}/{} approvals",
+            self.branch, self.approvals, self.required_approvals
+        )];
+        if !self.required_checks.is_empty() {
+            parts.push(format!("checks: {}", self.required_checks.join(", ")));
+        }
+        if self.enforce_admins {
+            parts.push("enforced for admins".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+/// GraphQL selection shared by the protection and merge status queries
+const BRANCH_PROTECTION_FIELDS: &str = r#"
+    baseRefName
+    baseRef {
+        branchProtectionRule {
+            requiresApprovingReviews
+            requiredApprovingReviewCount
+            requiresStatusChecks
+            requiredStatusCheckContexts
+            isAdminEnforced
+        }
+    }
+    latestOpinionatedReviews(first: 100) {
+        nodes { state }
+    }"#;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProtectionRuleData {
+    #[serde(default)]
+    requires_approving_reviews: bool,
+    required_approving_review_count: Option<u32>,
+    #[serde(default)]
+    requires_status_checks: bool,
+    #[serde(default)]
+    required_status_check_contexts: Vec<String>,
+    #[serde(default)]
+    is_admin_enforced: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BaseRefData {
+    branch_protection_rule: Option<ProtectionRuleData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewStateNode {
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewStateNodes {
+    nodes: Vec<ReviewStateNode>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProtectionPrData {
+    #[serde(default)]
+    base_ref_name: String,
+    base_ref: Option<BaseRefData>,
+    latest_opinionated_reviews: Option<ReviewStateNodes>,
+}
+
+impl ProtectionPrData {
+    /// `None` when the base branch has no protection rule.
+    fn into_protection(self) -> Option<BranchProtection> {
+        let rule = self.base_ref?.branch_protection_rule?;
+        let approvals = self
+            .latest_opinionated_reviews
+            .map(|reviews| {
+                reviews
+                    .nodes
+                    .iter()
+                    .filter(|review| review.state == "APPROVED")
+                    .count() as u32
+            })
+            .unwrap_or(0);
+        Some(BranchProtection {
+            branch: self.base_ref_name,
+            required_approvals: if rule.requires_approving_reviews {
+                rule.required_approving_review_count.unwrap_or(0)
+            } else {
+                0
+            },
+            approvals,
+            required_checks: if rule.requires_status_checks {
+                rule.required_status_check_contexts
+            } else {
+                Vec::new()
+            },
+            enforce_admins: rule.is_admin_enforced,
+        })
+    }
+}
+
+fn parse_branch_protection(json: &[u8]) -> Result<Option<BranchProtection>> {
+    #[derive(Deserialize)]
+    struct Response {
+        data: Option<Data>,
+    }
+    #[derive(Deserialize)]
+    struct Data {
+        repository: Option<Repository>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Repository {
+        pull_request: Option<ProtectionPrData>,
+    }
+
+    let response: Response =
+        serde_json::from_slice(json).context("Failed to parse branch protection response")?;
+    Ok(response
+        .data
+        .and_then(|data| data.repository)
+        .and_then(|repo| repo.pull_request)
+        .and_then(ProtectionPrData::into_protection))
+}
+
+/// Fetch protection rules for a PR's base branch (`None` if unprotected)
+pub fn get_branch_protection(pr: &PullRequest) -> Result<Option<BranchProtection>> {
+    let (owner, name) = pr.repo_name.split_once('/').unwrap_or((&pr.repo_name, ""));
+    let query = format!(
+        "query {{ repository(owner: \"{}\", name: \"{}\") {{ pullRequest(number: {}) {{ {} }} }} }}",
+        owner, name, pr.number, BRANCH_PROTECTION_FIELDS
+    );
+    let output = Command::new("gh")
+        .args(["api", "graphql", "-f", &format!("query={}", query)])
+        .logged_output()
+        .context("Failed to fetch branch protection")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch branch protection: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_branch_protection(&output.stdout)
+}
+
+/// An author's PR history in one repo, from their most recent PRs
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorStats {
+    pub total_prs: u64,
+    pub merged_prs: u64,
+    /// PRs opened in the last 30 days
+    pub recent_prs: usize,
+    /// Median additions + deletions of the sampled PRs
+    pub median_lines: Option<u64>,
+    /// Share of decided PRs (merged, closed, or reviewed) that were approved or merged
+    pub approval_rate: Option<f64>,
+}
+
+impl AuthorStats {
+    /// "42 PRs (38 merged) | 5 in 30d | ~150 lines | 86% approved"
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("{} PRs ({} merged)", self.total_prs, self.merged_prs),
+            format!("{} in 30d", self.recent_prs),
+        ];
+        if let Some(lines) = self.median_lines {
+            parts.push(format!("~{} lines", lines));
+        }
+        if let Some(rate) = self.approval_rate {
+            parts.push(format!("{:.0}% approved", rate * 100.0));
+        }
+        parts.join(" | ")
+    }
+}
+
+/// Recent PRs sampled for the author summary.
+const AUTHOR_STATS_SAMPLE: usize = 30;
+
+fn parse_author_stats(json: &[u8], now: DateTime<Utc>) -> Result<AuthorStats> {
+    #[derive(Deserialize)]
+    struct Response {
+        data: Option<Data>,
+    }
+    #[derive(Deserialize)]
+    struct Data {
+        all: Search,
+        merged: Search,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Search {
+        issue_count: u64,
+        #[serde(default)]
+        nodes: Vec<Node>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Node {
+        created_at: DateTime<Utc>,
+        #[serde(default)]
+        additions: u64,
+        #[serde(default)]
+        deletions: u64,
+        #[serde(default)]
+        state: String,
+        #[serde(default)]
+        merged: bool,
+        review_decision: Option<String>,
+    }
+
+    let response: Response =
+        serde_json::from_slice(json).context("Failed to parse author history response")?;
+    let data = response
+        .data
+        .context("Author history response had no data")?;
+    let nodes = data.all.nodes;
+
+    let recent_prs = nodes
+        .iter()
+        .filter(|node| now.signed_duration_since(node.created_at) <= chrono::Duration::days(30))
+        .count();
+    let mut sizes = nodes
+        .iter()
+        .map(|node| node.additions + node.deletions)
+        .collect::<Vec<_>>();
+    sizes.sort_unstable();
+    let median_lines = sizes.get(sizes.len() / 2).copied();
+
+    let decided = nodes
+        .iter()
+        .filter(|node| {
+            node.state != "OPEN"
+                || matches!(
+                    node.review_decision.as_deref(),
+                    Some("APPROVED" | "CHANGES_REQUESTED")
+                )
+        })
+        .collect::<Vec<_>>();
+    let approved = decided
+        .iter()
+        .filter(|node| node.merged || node.review_decision.as_deref() == Some("APPROVED"))
+        .count();
+    let approval_rate = (!decided.is_empty()).then(|| approved as f64 / decided.len() as f64);
+
+    Ok(AuthorStats {
+        total_prs: data.all.issue_count,
+        merged_prs: data.merged.issue_count,
+        recent_prs,
+        median_lines,
+        approval_rate,
+    })
+}
+
+/// Summarize the PR author's history in the PR's repo from two search queries.
+pub fn get_author_stats(pr: &PullRequest) -> Result<AuthorStats> {
+    let base = format!("repo:{} author:{} is:pr", pr.repo_name, pr.author);
+    let literal = |query: String| serde_json::to_string(&query).unwrap_or_default();
+    let query = format!(
+        "query {{ all: search(query: {}, type: ISSUE, first: {}) {{ issueCount nodes {{ ... on PullRequest {{ createdAt additions deletions state merged reviewDecision }} }} }} merged: search(query: {}, type: ISSUE, first: 0) {{ issueCount }} }}",
+        literal(format!("{} sort:created-desc", base)),
+        AUTHOR_STATS_SAMPLE,
+        literal(format!("{} is:merged", base)),
+    );
+    let output = Command::new("gh")
+        .args(["api", "graphql", "-f", &format!("query={}", query)])
+        .logged_output()
+        .context("Failed to fetch author history")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch author history: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_author_stats(&output.stdout, Utc::now())
+}
+
+/// Open review requests waiting on one teammate
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewQueue {
+    pub login: String,
+    pub open_requests: u64,
+}
+
+fn parse_review_queues(json: &[u8], logins: &[String]) -> Result<Vec<ReviewQueue>> {
+    #[derive(Deserialize)]
+    struct Response {
+        data: Option<HashMap<String, Option<Count>>>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Count {
+        issue_count: u64,
+    }
+
+    let response: Response =
+        serde_json::from_slice(json).context("Failed to parse review queue response")?;
+    let data = response.data.unwrap_or_default();
+    logins
+        .iter()
+        .enumerate()
+        .map(|(idx, login)| {
+            let count = data
+                .get(&format!("u{}", idx))
+                .and_then(Option::as_ref)
+                .with_context(|| format!("No review queue returned for @{}", login))?;
+            Ok(ReviewQueue {
+                login: login.clone(),
+                open_requests: count.issue_count,
+            })
+        })
+        .collect()
+}
+
+/// Count open PRs requesting review from each login, in one aliased search query.
+pub fn get_review_queues(logins: &[String]) -> Result<Vec<ReviewQueue>> {
+    if logins.is_empty() {
+        return Ok(Vec::new());
+    }
+    let searches = logins
+        .iter()
+        .enumerate()
+        .map(|(idx, login)| {
+            let search = format!("is:pr is:open review-requested:{}", login);
+            format!(
+                "u{}: search(query: {}, type: ISSUE, first: 0) {{ issueCount }}",
+                idx,
+                serde_json::to_string(&search).unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "graphql",
+            "-f",
+            &format!("query=query {{ {} }}", searches),
+        ])
+        .logged_output()
+        .context("Failed to fetch review queues")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch review queues: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_review_queues(&output.stdout, logins)
+}
+
+/// Comment activity on a PR, shown as badges in the list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Discussion {
+    /// Conversation comments plus comments in review threads
+    pub comments: u64,
+    pub unresolved_threads: u64,
+}
+
+/// PRs per aliased discussion query, to stay well inside GraphQL node limits
+const DISCUSSION_BATCH: usize = 50;
+
+fn parse_discussions(json: &[u8], count: usize) -> Result<Vec<Option<Discussion>>> {
+    #[derive(Deserialize)]
+    struct Response {
+        data: Option<HashMap<String, Option<Repository>>>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Repository {
+        pull_request: Option<PullRequestNode>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PullRequestNode {
+        comments: Count,
+        review_threads: Threads,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Count {
+        total_count: u64,
+    }
+    #[derive(Deserialize)]
+    struct Threads {
+        nodes: Vec<Thread>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Thread {
+        is_resolved: bool,
+        comments: Count,
+    }
+
+    let response: Response =
+        serde_json::from_slice(json).context("Failed to parse discussion response")?;
+    let data = response.data.unwrap_or_default();
+    Ok((0..count)
+        .map(|idx| {
+            let pr = data
+                .get(&format!("p{}", idx))?
+                .as_ref()?
+                .pull_request
+                .as_ref()?;
+            let threads = &pr.review_threads.nodes;
+            Some(Discussion {
+                comments: pr.comments.total_count
+                    + threads
+                        .iter()
+                        .map(|thread| thread.comments.total_count)
+                        .sum::<u64>(),
+                unresolved_threads: threads.iter().filter(|thread| !thread.is_resolved).count()
+                    as u64,
+            })
+        })
+        .collect())
+}
+
+/// Comment and unresolved-thread counts for each PR, in one aliased GraphQL query per
+/// `DISCUSSION_BATCH` PRs. PRs GitHub no longer returns come back as `None`.
+pub fn get_discussions(prs: &[PullRequest]) -> Result<Vec<Option<Discussion>>> {
+    let mut discussions = Vec::with_capacity(prs.len());
+    for batch in prs.chunks(DISCUSSION_BATCH) {
+        let aliases = batch
+            .iter()
+            .enumerate()
+            .map(|(idx, pr)| {
+                let (owner, name) = pr.repo_name.split_once('/').unwrap_or_default();
+                format!(
+                    "p{}: repository(owner: {}, name: {}) {{ pullRequest(number: {}) {{ comments {{ totalCount }} reviewThreads(first: 100) {{ nodes {{ isResolved comments {{ totalCount }} }} }} }} }}",
+                    idx,
+                    serde_json::to_string(owner).unwrap_or_default(),
+                    serde_json::to_string(name).unwrap_or_default(),
+                    pr.number
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let output = Command::new("gh")
+            .args([
+                "api",
+                "graphql",
+                "-f",
+                &format!("query=query {{ {} }}", aliases),
+            ])
+            .logged_output()
+            .context("Failed to fetch PR discussions")?;
+
+        // A PR that was deleted fails its alias, but the rest of the batch still has data.
+        if !output.status.success() && output.stdout.is_empty() {
+            anyhow::bail!(
+                "Failed to fetch PR discussions: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        discussions.extend(parse_discussions(&output.stdout, batch.len())?);
+    }
+    Ok(discussions)
+}
+
+/// The viewer's access level on a repo, as GitHub's `viewerPermission` reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RepoPermission {
+    Read,
+    Triage,
+    Write,
+    Maintain,
+    Admin,
+}
+
+impl RepoPermission {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "READ" => Some(Self::Read),
+            "TRIAGE" => Some(Self::Triage),
+            "WRITE" => Some(Self::Write),
+            "MAINTAIN" => Some(Self::Maintain),
+            "ADMIN" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
+    /// Whether merging, and closing other people's PRs, is allowed.
+    pub fn can_write(self) -> bool {
+        self >= Self::Write
+    }
+}
+
+/// The viewer's permission on `repo_name` (`owner/name`)
+pub fn get_repo_permission(repo_name: &str) -> Result<RepoPermission> {
+    let output = Command::new("gh")
+        .args([
+            "repo",
+            "view",
+            repo_name,
+            "--json",
+            "viewerPermission",
+            "--jq",
+            ".viewerPermission",
+        ])
+        .logged_output()
+        .context("Failed to fetch repo permission")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch permission on {}: {}",
+            repo_name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let value = String::from_utf8_lossy(&output.stdout);
+    RepoPermission::parse(&value)
+        .with_context(|| format!("Unknown permission on {}: {}", repo_name, value.trim()))
+}
+
+/// Milestone and project boards a PR is filed under
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrTriage {
+    pub milestone: Option<String>,
+    /// Board titles, with the item's status if set (e.g. `Roadmap (Todo)`).
+    /// `None` when the token lacks the `read:project` scope.
+    pub projects: Option<Vec<String>>,
+}
+
+fn parse_pr_triage(json: &[u8], with_projects: bool) -> Result<PrTriage> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Response {
+        milestone: Option<Milestone>,
+        #[serde(default)]
+        project_items: Vec<ProjectItem>,
+    }
+    #[derive(Deserialize)]
+    struct Milestone {
+        title: String,
+    }
+    #[derive(Deserialize)]
+    struct ProjectItem {
+        title: String,
+        status: Option<ProjectStatus>,
+    }
+    #[derive(Deserialize)]
+    struct ProjectStatus {
+        name: String,
+    }
+
+    let response: Response =
+        serde_json::from_slice(json).context("Failed to parse milestone and projects")?;
+    let projects = response
+        .project_items
+        .into_iter()
+        .map(
+            |item| match item.status.filter(|status| !status.name.is_empty()) {
+                Some(status) => format!("{} ({})", item.title, status.name),
+                None => item.title,
+            },
+        )
+        .collect();
+    Ok(PrTriage {
+        milestone: response.milestone.map(|milestone| milestone.title),
+        projects: with_projects.then_some(projects),
+    })
+}
+
+/// The PR's milestone and project boards. Projects need the `read:project` token scope;
+/// without it only the milestone is returned.
+pub fn get_pr_triage(pr: &PullRequest) -> Result<PrTriage> {
+    let view = |fields: &str| {
+        Command::new("gh")
+            .args([
+                "pr",
+                "view",
+                &pr.number.to_string(),
+                "--repo",
+                &pr.repo_name,
+                "--json",
+                fields,
+            ])
+            .logged_output()
+            .context("Failed to fetch milestone and projects")
+    };
+
+    let output = view("milestone,projectItems")?;
+    if output.status.success() {
+        return parse_pr_triage(&output.stdout, true);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.contains("read:project") {
+        anyhow::bail!("Failed to fetch milestone and projects: {}", stderr.trim());
+    }
+
+    let output = view("milestone")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch milestone: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_pr_triage(&output.stdout, false)
+}
+
+/// Titles of the repo's open milestones, soonest due first
+pub fn get_milestones(pr: &PullRequest) -> Result<Vec<String>> {
+    let output = Command::new("gh")
+        .args([
+            "api",
+            &format!(
+                "repos/{}/milestones?state=open&sort=due_on&direction=asc&per_page=100",
+                pr.repo_name
+            ),
+            "--jq",
+            ".[].title",
+        ])
+        .logged_output()
+        .context("Failed to fetch milestones")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch milestones: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Set the PR's milestone, or clear it with `None`
+pub fn set_milestone(pr: &PullRequest, milestone: Option<&str>) -> Result<()> {
+    let mut command = Command::new("gh");
+    command.args([
+        "pr",
+        "edit",
+        &pr.number.to_string(),
+        "--repo",
+        &pr.repo_name,
+    ]);
+    match milestone {
+        Some(title) => command.args(["--milestone", title]),
+        None => command.arg("--remove-milestone"),
+    };
+    let output = command.logged_output().context("Failed to set milestone")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to set milestone: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// The repo's labels, for picking which a PR carries
+pub fn get_repo_labels(pr: &PullRequest) -> Result<Vec<Label>> {
+    let output = Command::new("gh")
+        .args([
+            "label",
+            "list",
+            "--repo",
+            &pr.repo_name,
+            "--json",
+            "name,color",
+            "--limit",
+            "500",
+        ])
+        .logged_output()
+        .context("Failed to fetch labels")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch labels: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    serde_json::from_slice(&output.stdout).context("Failed to parse labels response")
+}
+
+/// Add and remove labels on the PR in one edit
+pub fn edit_labels(pr: &PullRequest, add: &[String], remove: &[String]) -> Result<()> {
+    let mut command = Command::new("gh");
+    command.args([
+        "pr",
+        "edit",
+        &pr.number.to_string(),
+        "--repo",
+        &pr.repo_name,
+    ]);
+    if !add.is_empty() {
+        command.args(["--add-label", &add.join(",")]);
+    }
+    if !remove.is_empty() {
+        command.args(["--remove-label", &remove.join(",")]);
+    }
+    let output = command.logged_output().context("Failed to edit labels")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to edit labels: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Add the PR to the project board titled `project`
+pub fn add_to_project(pr: &PullRequest, project: &str) -> Result<()> {
+    let output = Command::new("gh")
+        .args([
+            "pr",
+            "edit",
+            &pr.number.to_string(),
+            "--repo",
+            &pr.repo_name,
+            "--add-project",
+            project,
+        ])
+        .logged_output()
+        .context("Failed to add PR to project")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to add PR to {}: {}",
+            project,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Request a review from `login` on the PR through the review-requests API, which also
+/// re-requests a reviewer who has already reviewed (`gh pr edit --add-reviewer` skips them).
+pub fn request_reviewer(pr: &PullRequest, login: &str) -> Result<()> {
+    let output = Command::new("gh")
+        .args([
+            "api",
+            &format!(
+                "repos/{}/pulls/{}/requested_reviewers",
+                pr.repo_name, pr.number
+            ),
+            "-X",
+            "POST",
+            "-f",
+            &format!("reviewers[]={}", login),
+        ])
+        .logged_output()
+        .context("Failed to request reviewer")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to request review from @{}: {}",
+            login,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(())
+}
+
+/// What happened to a PR, judging by a failed `gh` call against it: `merged`, `closed`, or
+/// `deleted` when GitHub no longer knows it. `None` for any other failure.
+pub fn closed_pr_state(error: &str) -> Option<&'static str> {
+    let error = error.to_lowercase();
+    let any = |needles: &[&str]| needles.iter().any(|needle| error.contains(needle));
+    if any(&[
+        "already merged",
+        "was merged",
+        "is merged",
+        "merged pull request",
+    ]) {
+        Some("merged")
+    } else if any(&[
+        "already closed",
+        "is closed",
+        "closed pull request",
+        "pull request is not open",
+    ]) {
+        Some("closed")
+    } else if any(&["could not resolve to a pullrequest", "http 404"]) {
+        Some("deleted")
+    } else {
+        None
+    }
+}
+
+/// Result of checking if a PR can be merged
+#[derive(Debug)]
+pub struct MergeStatus {
+    pub can_merge: bool,
+    pub reason: Option<String>,
+    pub protection: Option<BranchProtection>,
+}
+
+/// Check if a PR can be merged (no unresolved threads, mergeable state)
+pub fn check_merge_status(pr: &PullRequest) -> MergeStatus {
+    // Use GraphQL to check reviewThreads for unresolved comments
+    let query = format!(
+        r#"query {{
+            repository(owner: "{}", name: "{}") {{
+                pullRequest(number: {}) {{
+                    state
+                    mergeable
+                    reviewThreads(first: 100) {{
+                        nodes {{
+                            isResolved
+                        }}
+                    }}
+                    {}
+                }}
+            }}
+        }}"#,
+        pr.repo_name.split('/').next().unwrap_or(""),
+        pr.repo_name.split('/').nth(1).unwrap_or(""),
+        pr.number,
+        BRANCH_PROTECTION_FIELDS
+    );
+
+    let output = Command::new("gh")
+        .args(["api", "graphql", "-f", &format!("query={}", query)])
+        .logged_output()
+        .ok();
+
+    #[derive(Deserialize)]
+    struct ReviewThread {
+        #[serde(rename = "isResolved")]
+        is_resolved: bool,
+    }
+
+    #[derive(Deserialize)]
+    struct ReviewThreadsNodes {
+        nodes: Vec<ReviewThread>,
+    }
+
+    #[derive(Deserialize)]
+    struct PrInfo {
+        state: Option<String>,
+        mergeable: Option<String>,
+        #[serde(rename = "reviewThreads")]
+        review_threads: Option<ReviewThreadsNodes>,
+        #[serde(flatten)]
+        protection: ProtectionPrData,
+    }
 
     #[derive(Deserialize)]
     struct RepoData {
@@ -1102,46 +3227,245 @@ pub fn check_merge_status(pr: &PullRequest) -> MergeStatus {
 
     match pr_info {
         Some(info) => {
+            let protection = info.protection.into_protection();
+            let blocked = |reason: String| MergeStatus {
+                can_merge: false,
+                reason: Some(reason),
+                protection: protection.clone(),
+            };
+
+            // Merged or closed by someone else since the list loaded
+            match info.state.as_deref() {
+                Some("MERGED") => return blocked("PR was already merged".to_string()),
+                Some("CLOSED") => return blocked("PR is closed".to_string()),
+                _ => {}
+            }
+
             // Check for unresolved threads
             if let Some(threads) = info.review_threads {
                 let unresolved_count = threads.nodes.iter().filter(|t| !t.is_resolved).count();
                 if unresolved_count > 0 {
-                    return MergeStatus {
-                        can_merge: false,
-                        reason: Some(format!("{} unresolved review thread(s)", unresolved_count)),
-                    };
+                    return blocked(format!("{} unresolved review thread(s)", unresolved_count));
                 }
             }
 
+            // Check required approvals from branch protection
+            if let Some(shortfall) = protection.as_ref().and_then(|p| p.approval_shortfall()) {
+                return blocked(shortfall);
+            }
+
             // Check mergeable state
             match info.mergeable.as_deref() {
                 Some("MERGEABLE") => MergeStatus {
                     can_merge: true,
                     reason: None,
+                    protection,
                 },
-                Some("CONFLICTING") => MergeStatus {
-                    can_merge: false,
-                    reason: Some("PR has merge conflicts".to_string()),
-                },
-                Some("UNKNOWN") => MergeStatus {
-                    can_merge: false,
-                    reason: Some("Merge status unknown, try again".to_string()),
-                },
-                _ => MergeStatus {
-                    can_merge: false,
-                    reason: Some("PR is not mergeable".to_string()),
-                },
+                Some("CONFLICTING") => blocked("PR has merge conflicts".to_string()),
+                Some("UNKNOWN") => blocked("Merge status unknown, try again".to_string()),
+                _ => blocked("PR is not mergeable".to_string()),
             }
         }
         None => MergeStatus {
             can_merge: false,
             reason: Some("Failed to check merge status".to_string()),
+            protection: None,
         },
     }
 }
 
+/// Turn on auto-merge so the PR merges once required checks pass (squash, else merge commit).
+pub fn enable_auto_merge(pr: &PullRequest) -> Result<()> {
+    let pr_number = pr.number.to_string();
+    let mut stderr = String::new();
+    for method in ["--squash", "--merge"] {
+        let output = Command::new("gh")
+            .args([
+                "pr",
+                "merge",
+                &pr_number,
+                "--repo",
+                &pr.repo_name,
+                "--auto",
+                method,
+            ])
+            .logged_output()
+            .context("Failed to enable auto-merge")?;
+        if output.status.success() {
+            return Ok(());
+        }
+        stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    }
+    anyhow::bail!("Failed to enable auto-merge: {}", stderr)
+}
+
 /// Merge a PR using squash merge (preferred), falling back to regular merge
-pub fn merge_pr(pr: &PullRequest, delete_branch: bool) -> Result<String> {
+/// One commit of a PR, for the Commits tab and the squash commit message
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrCommit {
+    pub sha: String,
+    pub date: Option<DateTime<Utc>>, // When it was authored
+    pub message: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub login: Option<String>, // GitHub account of the author, if the email maps to one
+}
+
+fn parse_pr_commits(output: &[u8]) -> Result<Vec<PrCommit>> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).context("Failed to parse commit"))
+        .collect()
+}
+
+/// List a PR's commits, oldest first
+pub fn get_pr_commits(pr: &PullRequest) -> Result<Vec<PrCommit>> {
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "--paginate",
+            &format!("repos/{}/pulls/{}/commits", pr.repo_name, pr.number),
+            "--jq",
+            ".[] | {sha: .sha, date: .commit.author.date, message: .commit.message, name: .commit.author.name, email: .commit.author.email, login: .author.login}",
+        ])
+        .logged_output()
+        .context("Failed to list PR commits")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list PR commits: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_pr_commits(&output.stdout)
+}
+
+/// Fetch the diff a single commit introduced, e.g. one pushed since the last review.
+pub fn get_commit_diff(pr: &PullRequest, sha: &str) -> Result<String> {
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "-H",
+            "Accept: application/vnd.github.diff",
+            &format!("repos/{}/commits/{}", pr.repo_name, sha),
+        ])
+        .logged_output()
+        .context("Failed to get commit diff")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to get diff of {}: {}",
+            sha,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Subject and body of a squash merge commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct SquashMessage {
+    pub title: String,
+    pub body: String,
+}
+
+const CO_AUTHOR_TRAILER: &str = "Co-authored-by:";
+
+/// Trailer keys are case-insensitive to git, so `co-authored-by:` counts too.
+fn is_co_author_trailer(line: &str) -> bool {
+    line.trim_start()
+        .get(..CO_AUTHOR_TRAILER.len())
+        .is_some_and(|key| key.eq_ignore_ascii_case(CO_AUTHOR_TRAILER))
+}
+
+impl SquashMessage {
+    /// GitHub's default shape: `<PR title> (#N)`, then each commit as a `*` bullet. The
+    /// `Co-authored-by` trailers of the commits, and one for every commit author other than
+    /// the PR author, are gathered at the end so nobody drops out of the history.
+    pub fn from_commits(pr: &PullRequest, commits: &[PrCommit]) -> Self {
+        let mut bullets = Vec::new();
+        let mut co_authors: Vec<String> = Vec::new();
+        let mut add_co_author = |trailer: String| {
+            if !co_authors
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(&trailer))
+            {
+                co_authors.push(trailer);
+            }
+        };
+        for commit in commits {
+            let mut lines = Vec::new();
+            for line in commit.message.trim().lines() {
+                if is_co_author_trailer(line) {
+                    add_co_author(line.trim().to_string());
+                } else {
+                    lines.push(line);
+                }
+            }
+            let message = lines.join("\n").trim().to_string();
+            if !message.is_empty() {
+                bullets.push(format!("* {}", message));
+            }
+            let by_pr_author = commit.login.as_deref() == Some(pr.author.as_str());
+            if let (false, Some(name), Some(email)) = (by_pr_author, &commit.name, &commit.email) {
+                add_co_author(format!("{} {} <{}>", CO_AUTHOR_TRAILER, name, email));
+            }
+        }
+        let mut body = bullets.join("\n\n");
+        if !co_authors.is_empty() {
+            body.push_str("\n\n");
+            body.push_str(&co_authors.join("\n"));
+        }
+        SquashMessage {
+            title: format!("{} (#{})", pr.title, pr.number),
+            body: body.trim().to_string(),
+        }
+    }
+
+    /// The message as edited text: the title, a blank line, then the body.
+    pub fn to_text(&self) -> String {
+        format!("{}\n\n{}", self.title, self.body)
+    }
+
+    /// Read back edited text. Co-author trailers of `self` that the edit dropped are added
+    /// back; `None` when the title was left empty.
+    pub fn edited(&self, text: &str) -> Option<Self> {
+        let (title, body) = text.trim().split_once('\n').unwrap_or((text.trim(), ""));
+        let title = title.trim();
+        if title.is_empty() {
+            return None;
+        }
+        let mut body = body.trim().to_string();
+        let missing: Vec<&str> = self
+            .body
+            .lines()
+            .filter(|line| {
+                is_co_author_trailer(line) && !body.to_lowercase().contains(&line.to_lowercase())
+            })
+            .collect();
+        if !missing.is_empty() {
+            if !body.is_empty() {
+                body.push_str("\n\n");
+            }
+            body.push_str(&missing.join("\n"));
+        }
+        Some(SquashMessage {
+            title: title.to_string(),
+            body,
+        })
+    }
+}
+
+/// Merge a PR, squashing with `message` (GitHub's default message when `None`) if the repo
+/// allows it, otherwise with a merge commit. Returns the method used.
+pub fn merge_pr(
+    pr: &PullRequest,
+    delete_branch: bool,
+    message: Option<&SquashMessage>,
+) -> Result<String> {
     let pr_number = pr.number.to_string();
 
     // Try squash merge first
@@ -1157,10 +3481,13 @@ pub fn merge_pr(pr: &PullRequest, delete_branch: bool) -> Result<String> {
     if delete_branch {
         args.push("--delete-branch");
     }
+    if let Some(message) = message {
+        args.extend(["--subject", &message.title, "--body", &message.body]);
+    }
 
     let output = Command::new("gh")
         .args(&args)
-        .output()
+        .logged_output()
         .context("Failed to merge PR")?;
 
     if output.status.success() {
@@ -1183,7 +3510,7 @@ pub fn merge_pr(pr: &PullRequest, delete_branch: bool) -> Result<String> {
 
     let output = Command::new("gh")
         .args(&args)
-        .output()
+        .logged_output()
         .context("Failed to merge PR")?;
 
     if !output.status.success() {
@@ -1196,25 +3523,56 @@ pub fn merge_pr(pr: &PullRequest, delete_branch: bool) -> Result<String> {
     Ok("merge".to_string())
 }
 
-/// Create a worktree for a PR and return the path
+fn pr_worktree_name(pr: &PullRequest) -> String {
+    format!("{}-pr-{}", pr.repo_name.replace('/', "-"), pr.number)
+}
+
+/// Worktree directories previously created for a PR (canonical and timestamp-suffixed)
+pub fn existing_pr_worktrees(pr: &PullRequest, repos_root: &std::path::Path) -> Vec<PathBuf> {
+    let worktree_name = pr_worktree_name(pr);
+    let suffixed_prefix = format!("{worktree_name}-");
+    let Ok(entries) = std::fs::read_dir(repos_root.join(".worktrees")) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name == worktree_name || name.starts_with(&suffixed_prefix)
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Create a worktree for a PR, or bring the existing one up to the PR head, and return the path
 pub fn create_pr_worktree(
     pr: &PullRequest,
     repos_root: &std::path::Path,
 ) -> Result<std::path::PathBuf> {
     let worktree_base = repos_root.join(".worktrees");
     std::fs::create_dir_all(&worktree_base)?;
+    let _lock = lock_pr_worktree(pr, &worktree_base)?;
+    // The lock only covers setup; a launched session is tracked by its pid marker
+    if let Some(pid) = running_pr_session(&pr_session_marker(pr, &worktree_base)) {
+        anyhow::bail!(
+            "Review already in progress for {}#{}: an AI session (pid {}) is still running in its worktree",
+            pr.repo_name,
+            pr.number,
+            pid
+        );
+    }
     let repo_path = resolve_worktree_repo_path(pr, repos_root)?;
 
-    let worktree_name = format!("{}-pr-{}", pr.repo_name.replace('/', "-"), pr.number);
+    let worktree_name = pr_worktree_name(pr);
     let canonical_path = worktree_base.join(&worktree_name);
-    cleanup_worktree_path(&repo_path, &canonical_path);
 
     // Fetch the PR head ref
     let pr_ref = format!("refs/pull/{}/head", pr.number);
     let fetch_output = Command::new("git")
         .args(["fetch", "origin", &pr_ref])
         .current_dir(&repo_path)
-        .output()
+        .logged_output()
         .context("Failed to fetch PR ref")?;
 
     if !fetch_output.status.success() {
@@ -1223,6 +3581,14 @@ pub fn create_pr_worktree(
             String::from_utf8_lossy(&fetch_output.stderr)
         );
     }
+    let head = git_rev_parse(&repo_path, "FETCH_HEAD")?;
+
+    if canonical_path.exists() {
+        if fast_forward_worktree(&canonical_path, &head) {
+            return Ok(canonical_path);
+        }
+        cleanup_worktree_path(&repo_path, &canonical_path);
+    }
 
     // Prefer canonical path, then fall back to timestamp-suffixed paths when a previous
     // worktree is still active or metadata is stale.
@@ -1238,7 +3604,7 @@ pub fn create_pr_worktree(
             cleanup_worktree_path(&repo_path, &candidate);
         }
 
-        match git_worktree_add(&repo_path, &candidate, "FETCH_HEAD") {
+        match git_worktree_add(&repo_path, &candidate, &head) {
             Ok(()) => return Ok(candidate),
             Err(err) => errors.push(format!("{} => {}", candidate.display(), err)),
         }
@@ -1252,6 +3618,98 @@ pub fn create_pr_worktree(
     );
 }
 
+/// Take the PR's worktree lock, shared by every reviewer process (daemon, TUI, `trigger`).
+/// The lock is released when the returned file is dropped.
+fn lock_pr_worktree(pr: &PullRequest, worktree_base: &std::path::Path) -> Result<std::fs::File> {
+    let lock_dir = worktree_base.join(".locks");
+    std::fs::create_dir_all(&lock_dir)?;
+    let lock_path = lock_dir.join(format!("{}.lock", pr_worktree_name(pr)));
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+    match file.try_lock() {
+        Ok(()) => Ok(file),
+        Err(std::fs::TryLockError::WouldBlock) => anyhow::bail!(
+            "Review already in progress for {}#{}: its worktree is being set up elsewhere",
+            pr.repo_name,
+            pr.number
+        ),
+        Err(std::fs::TryLockError::Error(err)) => {
+            Err(err).with_context(|| format!("Failed to lock {}", lock_path.display()))
+        }
+    }
+}
+
+/// Where an AI session launched in the PR's worktree records its pid, next to the lock.
+fn pr_session_marker(pr: &PullRequest, worktree_base: &std::path::Path) -> PathBuf {
+    worktree_base
+        .join(".locks")
+        .join(format!("{}.session", pr_worktree_name(pr)))
+}
+
+/// The session marker for a launch in `working_dir`, when that is one of the PR's worktrees.
+fn session_marker_for(pr: &PullRequest, working_dir: &std::path::Path) -> Option<PathBuf> {
+    let worktree_base = working_dir.parent()?;
+    (worktree_base.file_name()? == ".worktrees").then(|| pr_session_marker(pr, worktree_base))
+}
+
+/// The pid of the AI session still running in the PR's worktree. A marker left by a session
+/// that has ended is removed.
+fn running_pr_session(marker: &std::path::Path) -> Option<u32> {
+    let pid = std::fs::read_to_string(marker)
+        .ok()?
+        .trim()
+        .parse::<u32>()
+        .ok();
+    if let Some(pid) = pid.filter(|pid| process_alive(*pid)) {
+        return Some(pid);
+    }
+    let _ = std::fs::remove_file(marker);
+    None
+}
+
+fn process_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+    if cfg!(target_os = "windows") {
+        return Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+            .logged_output()
+            .is_ok_and(|output| {
+                String::from_utf8_lossy(&output.stdout).contains(&pid.to_string())
+            });
+    }
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .logged_output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Removes the session marker written for an in-process review when the review ends.
+struct SessionMarker(Option<PathBuf>);
+
+impl SessionMarker {
+    fn write(marker: Option<PathBuf>) -> Result<Self> {
+        if let Some(marker) = &marker {
+            std::fs::write(marker, std::process::id().to_string())
+                .with_context(|| format!("Failed to write {}", marker.display()))?;
+        }
+        Ok(Self(marker))
+    }
+}
+
+impl Drop for SessionMarker {
+    fn drop(&mut self) {
+        if let Some(marker) = &self.0 {
+            let _ = std::fs::remove_file(marker);
+        }
+    }
+}
+
 fn resolve_worktree_repo_path(
     pr: &PullRequest,
     repos_root: &std::path::Path,
@@ -1272,6 +3730,9 @@ fn resolve_worktree_repo_path(
         }
     }
 
+    if clone_cache::enabled() {
+        return clone_cache::repo_path(&pr.repo_name);
+    }
     anyhow::bail!(
         "No local clone found for {} under {}. Use `reviewer trigger --repo-path` for PRs that need a worktree.",
         pr.repo_name,
@@ -1303,14 +3764,60 @@ fn cleanup_worktree_path(repo_path: &std::path::Path, worktree_path: &std::path:
         .args(["worktree", "remove", "--force"])
         .arg(worktree_path)
         .current_dir(repo_path)
-        .output();
+        .logged_output();
     let _ = Command::new("git")
         .args(["worktree", "prune"])
         .current_dir(repo_path)
-        .output();
+        .logged_output();
     let _ = std::fs::remove_dir_all(worktree_path);
 }
 
+fn git_rev_parse(repo_path: &std::path::Path, revision: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", revision])
+        .current_dir(repo_path)
+        .logged_output()
+        .with_context(|| format!("Failed to resolve {}", revision))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to resolve {}: {}",
+            revision,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Move an existing worktree to `head` if that is a fast-forward. False means it has to be
+/// recreated: it isn't a worktree, the PR was force-pushed, or the last session left edits.
+/// The last session's results are deleted so they aren't shown as the new review's.
+fn fast_forward_worktree(worktree_path: &std::path::Path, head: &str) -> bool {
+    if !worktree_path.join(".git").exists() {
+        return false;
+    }
+    let results = review_results::results_dir(worktree_path);
+    if results.exists() && std::fs::remove_dir_all(&results).is_err() {
+        return false;
+    }
+    let clean = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(worktree_path)
+        .logged_output()
+        .is_ok_and(|output| output.status.success() && output.stdout.trim_ascii().is_empty());
+    if !clean {
+        return false;
+    }
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(worktree_path)
+            .logged_output()
+            .is_ok_and(|output| output.status.success())
+    };
+    git(&["merge-base", "--is-ancestor", "HEAD", head]) && git(&["merge", "--ff-only", head])
+}
+
 fn git_worktree_add(
     repo_path: &std::path::Path,
     worktree_path: &std::path::Path,
@@ -1321,7 +3828,7 @@ fn git_worktree_add(
         .arg(worktree_path)
         .arg(revision)
         .current_dir(repo_path)
-        .output()
+        .logged_output()
         .context("Failed to create worktree")?;
 
     if !output.status.success() {
@@ -1330,38 +3837,46 @@ fn git_worktree_add(
     Ok(())
 }
 
-#[cfg(any(target_os = "macos", target_os = "linux"))]
-fn unix_shell_escape(value: &str) -> String {
-    format!("'{}'", value.replace('\'', "'\\''"))
-}
-
-#[cfg(target_os = "windows")]
-fn windows_cmd_escape(value: &str) -> String {
-    format!("\"{}\"", value.replace('"', "\\\""))
-}
-
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 fn build_unix_command(command: &str, args: &[String], prompt: &str) -> String {
     let mut parts = Vec::with_capacity(args.len() + 2);
-    parts.push(unix_shell_escape(command));
+    parts.push(shell_escape::posix(command));
     for arg in args {
-        parts.push(unix_shell_escape(arg));
+        parts.push(shell_escape::posix(arg));
     }
-    parts.push(unix_shell_escape(prompt));
+    parts.push(shell_escape::posix(prompt));
     parts.join(" ")
 }
 
 #[cfg(target_os = "windows")]
 fn build_windows_command(command: &str, args: &[String], prompt: &str) -> String {
     let mut parts = Vec::with_capacity(args.len() + 2);
-    parts.push(windows_cmd_escape(command));
+    parts.push(shell_escape::windows_cmd(command));
     for arg in args {
-        parts.push(windows_cmd_escape(arg));
+        parts.push(shell_escape::windows_cmd(arg));
     }
-    parts.push(windows_cmd_escape(prompt));
+    parts.push(shell_escape::windows_cmd(prompt));
     parts.join(" ")
 }
 
+/// Run `command_line` through `sh`, which records its pid in `marker` and then becomes the
+/// tool, so the pid lives exactly as long as the session.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn with_session_marker(command_line: &str, marker: &std::path::Path) -> String {
+    let script = format!(
+        "echo $$ > {} && exec {}",
+        shell_escape::posix(&marker.display().to_string()),
+        command_line
+    );
+    format!("sh -c {}", shell_escape::posix(&script))
+}
+
+/// `cmd` has no pid of its own to record, so Windows sessions go unmarked.
+#[cfg(target_os = "windows")]
+fn with_session_marker(command_line: &str, _marker: &std::path::Path) -> String {
+    command_line.to_string()
+}
+
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 fn build_shell_command(command: &str, args: &[String], prompt: &str) -> String {
     build_unix_command(command, args, prompt)
@@ -1422,15 +3937,17 @@ struct LaunchContext<'a> {
     provider: &'a str,
     skill_name: &'a str,
     skill_invocation: &'a str,
+    /// Pid marker the launched session writes, when it runs in the PR's worktree.
+    session_marker: Option<&'a std::path::Path>,
 }
 
 impl LaunchTemplateValues {
     fn from_context(context: LaunchContext<'_>) -> Self {
         let workdir = context.working_dir.display().to_string();
         #[cfg(any(target_os = "macos", target_os = "linux"))]
-        let workdir_shell = unix_shell_escape(&workdir);
+        let workdir_shell = shell_escape::posix(&workdir);
         #[cfg(target_os = "windows")]
-        let workdir_shell = windows_cmd_escape(&workdir);
+        let workdir_shell = shell_escape::windows_cmd(&workdir);
 
         Self {
             provider: context.provider.to_string(),
@@ -1443,7 +3960,13 @@ impl LaunchTemplateValues {
             skill_name: context.skill_name.to_string(),
             skill_invocation: context.skill_invocation.to_string(),
             tool: context.tool.to_string(),
-            tool_command: build_shell_command(context.tool, context.tool_args, context.prompt),
+            tool_command: match context.session_marker {
+                Some(marker) => with_session_marker(
+                    &build_shell_command(context.tool, context.tool_args, context.prompt),
+                    marker,
+                ),
+                None => build_shell_command(context.tool, context.tool_args, context.prompt),
+            },
             workdir,
             workdir_shell,
             session_title: launch_session_title(context.pr),
@@ -1452,30 +3975,56 @@ impl LaunchTemplateValues {
     }
 }
 
-fn render_launch_template(template: &str, values: &LaunchTemplateValues) -> String {
-    let mut rendered = template.to_string();
-    for (key, value) in [
-        ("{skill_name}", values.skill_name.as_str()),
-        ("{skill_invocation}", values.skill_invocation.as_str()),
-        ("{session_title}", values.session_title.as_str()),
-        ("{timestamp_ms}", values.timestamp_ms.as_str()),
-        ("{workdir_shell}", values.workdir_shell.as_str()),
-        ("{tool_command}", values.tool_command.as_str()),
-        ("{provider}", values.provider.as_str()),
-        ("{repo_slug}", values.repo_slug.as_str()),
-        ("{repo}", values.repo.as_str()),
-        ("{pr_number}", values.pr_number.as_str()),
-        ("{title}", values.title.as_str()),
-        ("{prompt}", values.prompt.as_str()),
-        ("{review_guide}", values.review_guide.as_str()),
-        ("{tool}", values.tool.as_str()),
-        ("{workdir}", values.workdir.as_str()),
-    ] {
-        rendered = rendered.replace(key, value);
+/// Replace each `{name}` in `template` that `lookup` knows with its value, in one left-to-right
+/// pass. Inserted values are never scanned again, so a PR title holding `{tool_command}` or a
+/// quote stays the literal text it was. Unknown `{...}` is kept as is.
+fn fill_placeholders<'v>(template: &str, lookup: impl Fn(&str) -> Option<&'v str>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after
+            .find('}')
+            .and_then(|close| lookup(&after[..close]).map(|value| (close, value)));
+        match value {
+            Some((close, value)) => {
+                rendered.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
     }
+    rendered.push_str(rest);
     rendered
 }
 
+fn render_launch_template(template: &str, values: &LaunchTemplateValues) -> String {
+    fill_placeholders(template, |name| {
+        Some(match name {
+            "skill_name" => values.skill_name.as_str(),
+            "skill_invocation" => values.skill_invocation.as_str(),
+            "session_title" => values.session_title.as_str(),
+            "timestamp_ms" => values.timestamp_ms.as_str(),
+            "workdir_shell" => values.workdir_shell.as_str(),
+            "tool_command" => values.tool_command.as_str(),
+            "provider" => values.provider.as_str(),
+            "repo_slug" => values.repo_slug.as_str(),
+            "repo" => values.repo.as_str(),
+            "pr_number" => values.pr_number.as_str(),
+            "title" => values.title.as_str(),
+            "prompt" => values.prompt.as_str(),
+            "review_guide" => values.review_guide.as_str(),
+            "tool" => values.tool.as_str(),
+            "workdir" => values.workdir.as_str(),
+            _ => return None,
+        })
+    })
+}
+
 pub fn validate_ai_launch_config(ai: &AiConfig) -> Result<()> {
     match ai.launch.backend_key() {
         "tmux" => {
@@ -1487,6 +4036,35 @@ pub fn validate_ai_launch_config(ai: &AiConfig) -> Result<()> {
                 }
             }
         }
+        "api" => {
+            let api = &ai.launch.api;
+            if !matches!(api.format_key(), "anthropic" | "openai") {
+                anyhow::bail!(
+                    "Unsupported ai.launch.api.format '{}'. Expected 'anthropic' or 'openai'.",
+                    api.format_key()
+                );
+            }
+            if api
+                .model
+                .as_deref()
+                .is_none_or(|model| model.trim().is_empty())
+            {
+                anyhow::bail!("ai.launch.api.model is required for the api backend");
+            }
+            let key_env = api.api_key_env();
+            if std::env::var(&key_env).map_or(true, |key| key.trim().is_empty()) {
+                anyhow::bail!("API key environment variable {} is not set", key_env);
+            }
+        }
+        "terminal" => {
+            if cfg!(target_os = "windows") {
+                anyhow::bail!("ai.launch.backend \"terminal\" supports macOS and Linux only");
+            }
+            terminal::parse_terminal_launch_mode(
+                ai.launch.terminal.mode.as_deref().unwrap_or_default(),
+            )
+            .context("Invalid ai.launch.terminal.mode")?;
+        }
         "steps" => {
             if ai.launch.steps.is_empty() {
                 anyhow::bail!(
@@ -1502,7 +4080,7 @@ pub fn validate_ai_launch_config(ai: &AiConfig) -> Result<()> {
         }
         other => {
             anyhow::bail!(
-                "Unsupported ai.launch.backend '{}'. Expected 'steps' or 'tmux'.",
+                "Unsupported ai.launch.backend '{}'. Expected 'steps', 'tmux', 'terminal' or 'api'.",
                 other
             );
         }
@@ -1525,7 +4103,7 @@ fn tmux_session_name(ai: &AiConfig) -> String {
 fn tmux_run(args: &[&str]) -> Result<std::process::Output> {
     let output = Command::new("tmux")
         .args(args)
-        .output()
+        .logged_output()
         .context("Failed to run tmux")?;
     Ok(output)
 }
@@ -1533,7 +4111,7 @@ fn tmux_run(args: &[&str]) -> Result<std::process::Output> {
 fn tmux_has_session(session: &str) -> bool {
     Command::new("tmux")
         .args(["has-session", "-t", session])
-        .output()
+        .logged_output()
         .map(|output| output.status.success())
         .unwrap_or(false)
 }
@@ -1614,47 +4192,114 @@ fn launch_with_tmux(
     pr: &PullRequest,
     ai: &AiConfig,
     values: &LaunchTemplateValues,
+    evidence: &mut Vec<String>,
 ) -> Result<()> {
     validate_ai_launch_config(ai)?;
 
     let session = tmux_session_name(ai);
     let window_name = agent::pr_agent_slug(pr);
-    if ai.launch.tmux.reuse_existing && agent::find_agent_pane(pr)?.is_some() {
-        return Ok(());
+    if ai.launch.tmux.reuse_existing {
+        if let Some(pane) = agent::find_agent_pane(pr)? {
+            evidence.push(format!("reused existing tmux pane {}", pane.target));
+            return Ok(());
+        }
     }
 
     let pane_id = tmux_create_pane(&session, &window_name, working_dir)?;
+    evidence.push(format!("created tmux pane {pane_id} in session {session}"));
     tmux_set_pane_title(&pane_id, &window_name)?;
     tmux_send_command(&pane_id, &values.tool_command)?;
+    evidence.push(format!("sent command to tmux pane {pane_id}"));
     Ok(())
 }
 
+fn launch_with_terminal(
+    working_dir: &std::path::Path,
+    ai: &AiConfig,
+    values: &LaunchTemplateValues,
+    evidence: &mut Vec<String>,
+) -> Result<()> {
+    validate_ai_launch_config(ai)?;
+
+    let settings = &ai.launch.terminal;
+    let mode = terminal::parse_terminal_launch_mode(settings.mode.as_deref().unwrap_or_default())?;
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = working_dir;
+        let command_line = format!("cd {} && {}", values.workdir_shell, values.tool_command);
+        let app = settings.app.as_deref().unwrap_or("Terminal");
+        terminal::launch_macos_terminal(app, &command_line, mode)?;
+        evidence.push(format!("opened {app} ({})", mode.as_str()));
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        terminal::launch_linux_terminal(
+            settings.app.as_deref(),
+            working_dir,
+            &values.tool_command,
+            mode,
+        )?;
+        evidence.push(format!("opened terminal ({})", mode.as_str()));
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = (working_dir, values, mode, evidence);
+        anyhow::bail!("ai.launch.backend \"terminal\" supports macOS and Linux only")
+    }
+}
+
+/// `ai.launch.steps` with placeholders filled in, as `(command, args)` pairs.
+fn render_launch_steps(
+    ai: &AiConfig,
+    values: &LaunchTemplateValues,
+) -> Result<Vec<(String, Vec<String>)>> {
+    ai.launch
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| {
+            let command = render_launch_template(&step.command, values)
+                .trim()
+                .to_string();
+            if command.is_empty() {
+                anyhow::bail!("ai.launch.steps[{idx}] command is empty after template rendering");
+            }
+            let args = step
+                .args
+                .iter()
+                .map(|arg| render_launch_template(arg, values))
+                .collect();
+            Ok((command, args))
+        })
+        .collect()
+}
+
 fn launch_with_steps(
     working_dir: &std::path::Path,
     ai: &AiConfig,
     values: &LaunchTemplateValues,
+    evidence: &mut Vec<String>,
 ) -> Result<()> {
     validate_ai_launch_config(ai)?;
 
-    let total = ai.launch.steps.len();
-    for (idx, step) in ai.launch.steps.iter().enumerate() {
+    let steps = render_launch_steps(ai, values)?;
+    let total = steps.len();
+    for (idx, (command, args)) in steps.iter().enumerate() {
         let step_number = idx + 1;
-        let command = render_launch_template(&step.command, values);
-        let command = command.trim();
-        if command.is_empty() {
-            anyhow::bail!("ai.launch.steps[{idx}] command is empty after template rendering");
-        }
-        let args: Vec<String> = step
-            .args
-            .iter()
-            .map(|arg| render_launch_template(arg, values))
-            .collect();
-
         let output = Command::new(command)
-            .args(&args)
+            .args(args)
             .current_dir(working_dir)
-            .output()
+            .logged_output()
             .with_context(|| format!("Failed to run ai.launch step {step_number}/{total}"))?;
+        evidence.push(format!(
+            "step {step_number}/{total} ({command}) exited with {}",
+            output.status
+        ));
         if !output.status.success() {
             anyhow::bail!(
                 "ai.launch step {step_number}/{total} failed ({command}): {}",
@@ -1666,86 +4311,294 @@ fn launch_with_steps(
     Ok(())
 }
 
+/// PR details that are only fetched when a prompt template asks for them.
+#[derive(Debug, Default)]
+struct PromptPrDetails {
+    base_branch: String,
+    changed_files: Vec<String>,
+}
+
+impl PromptPrDetails {
+    fn needed_by(template: &str) -> bool {
+        template.contains("{base_branch}") || template.contains("{changed_files}")
+    }
+
+    fn fetch(pr: &PullRequest) -> Result<Self> {
+        let output = Command::new("gh")
+            .args([
+                "pr",
+                "view",
+                &pr.number.to_string(),
+                "--repo",
+                &pr.repo_name,
+                "--json",
+                "baseRefName",
+                "--jq",
+                ".baseRefName",
+            ])
+            .logged_output()
+            .context("Failed to get PR base branch")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to get PR base branch: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(Self {
+            base_branch: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            changed_files: get_pr_files(pr)?
+                .into_iter()
+                .map(|file| file.path)
+                .collect(),
+        })
+    }
+}
+
 fn render_prompt(
     template: &str,
     pr: &PullRequest,
     review_guide: &std::path::Path,
+    results_dir: &std::path::Path,
     skill_invocation: &str,
+    details: &PromptPrDetails,
 ) -> String {
-    template
-        .replace("{pr_number}", &pr.number.to_string())
-        .replace("{repo}", &pr.repo_name)
-        .replace("{title}", &pr.title)
-        .replace("{review_guide}", &review_guide.display().to_string())
-        .replace("{skill}", skill_invocation)
+    let pr_number = pr.number.to_string();
+    let changed_files = details.changed_files.join(", ");
+    let review_guide = review_guide.display().to_string();
+    let results_dir = results_dir.display().to_string();
+    fill_placeholders(template, |name| {
+        Some(match name {
+            "pr_number" => pr_number.as_str(),
+            "repo" => pr.repo_name.as_str(),
+            "title" => pr.title.as_str(),
+            "author" => pr.author.as_str(),
+            "url" => pr.url.as_str(),
+            "base_branch" => details.base_branch.as_str(),
+            "changed_files" => changed_files.as_str(),
+            "review_guide" => review_guide.as_str(),
+            "results_dir" => results_dir.as_str(),
+            "skill" => skill_invocation,
+            _ => return None,
+        })
+    })
 }
 
-/// Launch a code review assistant CLI in a directory with a review prompt
-pub fn launch_ai(working_dir: &std::path::Path, pr: &PullRequest, ai: &AiConfig) -> Result<()> {
-    let provider = ai.provider_key();
-    let tool = ai.command_name();
+/// A review launch with its prompt and launcher commands rendered, ready to run.
+pub struct AiLaunch<'a> {
+    working_dir: &'a std::path::Path,
+    pr: &'a PullRequest,
+    ai: &'a AiConfig,
+    review_guide: std::path::PathBuf,
+    pub prompt: String,
+    values: LaunchTemplateValues,
+    session_marker: Option<PathBuf>,
+}
 
-    // Get platform-appropriate config directory for review guide reference
-    let config_dir = config::config_dir();
-    let review_guide = config_dir.join("review_guide.md");
+impl<'a> AiLaunch<'a> {
+    pub fn prepare(
+        working_dir: &'a std::path::Path,
+        pr: &'a PullRequest,
+        ai: &'a AiConfig,
+    ) -> Result<Self> {
+        let provider = ai.provider_key();
+        let tool = ai.command_name();
+
+        // Get platform-appropriate config directory for review guide reference
+        let config_dir = config::config_dir();
+        let review_guide = config_dir.join("review_guide.md");
+
+        let skill_name = ai.skill_name();
+        let skill_invocation = if provider == "codex" {
+            format!("${}", skill_name)
+        } else {
+            format!("{} skill", skill_name)
+        };
 
-    let skill_name = ai.skill_name();
-    let skill_invocation = if provider == "codex" {
-        format!("${}", skill_name)
-    } else {
-        format!("{} skill", skill_name)
-    };
+        let results_dir = review_results::results_dir(working_dir);
+        let default_prompt = format!(
+            "Review PR #{} in repo {}. Title: \"{}\". \
+             Use {} to analyze changes, present each issue for approval, \
+             and submit approved comments using gh CLI. Follow guidelines in {}. \
+             Also write your findings to {}/{}, one bullet per issue starting with its `path:line`.",
+            pr.number,
+            pr.repo_name,
+            pr.title.replace('"', "\\\""),
+            skill_invocation,
+            review_guide.display(),
+            results_dir.display(),
+            review_results::REVIEW_MD
+        );
 
-    let default_prompt = format!(
-        "Review PR #{} in repo {}. Title: \"{}\". \
-         Use {} to analyze changes, present each issue for approval, \
-         and submit approved comments using gh CLI. Follow guidelines in {}",
-        pr.number,
-        pr.repo_name,
-        pr.title.replace('"', "\\\""),
-        skill_invocation,
-        review_guide.display()
-    );
+        let prompt = match ai.prompt_template_for(&pr.repo_name) {
+            Some(template) => {
+                let details = if PromptPrDetails::needed_by(template) {
+                    PromptPrDetails::fetch(pr)?
+                } else {
+                    PromptPrDetails::default()
+                };
+                render_prompt(
+                    template,
+                    pr,
+                    &review_guide,
+                    &results_dir,
+                    &skill_invocation,
+                    &details,
+                )
+            }
+            None => default_prompt,
+        };
 
-    let prompt = ai
-        .prompt_template
-        .as_deref()
-        .map(|template| render_prompt(template, pr, &review_guide, &skill_invocation))
-        .unwrap_or(default_prompt);
-
-    let values = LaunchTemplateValues::from_context(LaunchContext {
-        working_dir,
-        tool: &tool,
-        tool_args: &ai.args,
-        prompt: &prompt,
-        review_guide: &review_guide,
-        pr,
-        provider,
-        skill_name: &skill_name,
-        skill_invocation: &skill_invocation,
-    });
+        let session_marker = session_marker_for(pr, working_dir);
+        let values = LaunchTemplateValues::from_context(LaunchContext {
+            working_dir,
+            tool: &tool,
+            tool_args: &ai.args,
+            prompt: &prompt,
+            review_guide: &review_guide,
+            pr,
+            provider,
+            skill_name: &skill_name,
+            skill_invocation: &skill_invocation,
+            session_marker: session_marker.as_deref(),
+        });
 
-    match ai.launch.backend_key() {
-        "tmux" => launch_with_tmux(working_dir, pr, ai, &values),
-        "steps" => launch_with_steps(working_dir, ai, &values),
-        other => anyhow::bail!(
-            "Unsupported ai.launch.backend '{}'. Expected 'steps' or 'tmux'.",
-            other
-        ),
+        Ok(Self {
+            working_dir,
+            pr,
+            ai,
+            review_guide,
+            prompt,
+            values,
+            session_marker,
+        })
+    }
+
+    /// What `run` will execute, one line per command, for logs and run artifacts.
+    pub fn commands(&self) -> Vec<String> {
+        let launch = &self.ai.launch;
+        match launch.backend_key() {
+            "tmux" => vec![format!(
+                "tmux session {}, window {}: {}",
+                tmux_session_name(self.ai),
+                agent::pr_agent_slug(self.pr),
+                self.values.tool_command
+            )],
+            "terminal" => vec![format!(
+                "terminal {} ({}): {}",
+                launch.terminal.app.as_deref().unwrap_or("default"),
+                launch.terminal.mode.as_deref().unwrap_or("auto"),
+                self.values.tool_command
+            )],
+            "api" => vec![format!(
+                "{} API, model {}, at {}",
+                launch.api.format_key(),
+                launch.api.model.as_deref().unwrap_or("(unset)"),
+                launch.api.base_url()
+            )],
+            _ => match render_launch_steps(self.ai, &self.values) {
+                Ok(steps) => steps
+                    .into_iter()
+                    .map(|(command, args)| format!("{command} {args:?}"))
+                    .collect(),
+                Err(err) => vec![format!("{err:#}")],
+            },
+        }
+    }
+
+    /// Run the launcher, appending what each command reported to `evidence`.
+    pub fn run(&self, evidence: &mut Vec<String>) -> Result<()> {
+        let (working_dir, ai, values) = (self.working_dir, self.ai, &self.values);
+        match ai.launch.backend_key() {
+            "tmux" => launch_with_tmux(working_dir, self.pr, ai, values, evidence),
+            "steps" => launch_with_steps(working_dir, ai, values, evidence),
+            "terminal" => launch_with_terminal(working_dir, ai, values, evidence),
+            "api" => {
+                validate_ai_launch_config(ai)?;
+                // The review runs in this process, which holds the marker until it ends
+                let _marker = SessionMarker::write(self.session_marker.clone())?;
+                ai_api::run_review(working_dir, self.pr, &ai.launch.api, &self.review_guide)?;
+                evidence.push(format!(
+                    "API review written to {}",
+                    review_results::results_dir(working_dir).display()
+                ));
+                Ok(())
+            }
+            other => anyhow::bail!(
+                "Unsupported ai.launch.backend '{}'. Expected 'steps', 'tmux', 'terminal' or 'api'.",
+                other
+            ),
+        }
     }
 }
 
+/// Launch a code review assistant CLI in a directory with a review prompt
+pub fn launch_ai(working_dir: &std::path::Path, pr: &PullRequest, ai: &AiConfig) -> Result<()> {
+    AiLaunch::prepare(working_dir, pr, ai)?.run(&mut Vec::new())
+}
+
 #[cfg(all(test, any(target_os = "macos", target_os = "linux")))]
 mod tests {
     use super::{
-        build_shell_command, launch_with_steps, render_launch_template, search_qualifiers,
-        validate_ai_launch_config, LaunchContext, LaunchTemplateValues, PullRequest, SearchScope,
+        build_shell_command, launch_with_steps, line_comment_payload, parse_pr_checks,
+        render_launch_template, render_prompt, review_payload, search_qualifiers,
+        validate_ai_launch_config, CheckState, LaunchContext, LaunchTemplateValues, PrCommit,
+        PromptPrDetails, PullRequest, ReviewEvent, SearchScope, SquashMessage,
     };
-    use crate::config::AiConfig;
+    use crate::config::{AiConfig, FetchConfig};
+    use crate::drafts::{DraftComment, DraftReview};
+    use crate::process::replay::ReplayRunner;
     use chrono::Utc;
     use std::path::{Path, PathBuf};
     use std::process::Command;
 
+    #[test]
+    fn line_comment_payload_adds_start_line_for_ranges() {
+        let single = line_comment_payload("src/lib.rs", None, 12, "RIGHT", "nit");
+        assert_eq!(
+            single["comments"][0],
+            serde_json::json!({"path": "src/lib.rs", "line": 12, "side": "RIGHT", "body": "nit"})
+        );
+        assert_eq!(
+            line_comment_payload("src/lib.rs", Some(12), 12, "RIGHT", "nit"),
+            single
+        );
+
+        let range = line_comment_payload("src/lib.rs", Some(10), 14, "LEFT", "why?");
+        assert_eq!(
+            range["comments"][0],
+            serde_json::json!({
+                "path": "src/lib.rs",
+                "start_line": 10,
+                "start_side": "LEFT",
+                "line": 14,
+                "side": "LEFT",
+                "body": "why?"
+            })
+        );
+    }
+
+    #[test]
+    fn review_payload_sets_event_only_when_submitting() {
+        let draft = DraftReview {
+            body: "A few nits".to_string(),
+            comments: vec![DraftComment {
+                path: "src/lib.rs".to_string(),
+                start_line: None,
+                line: 3,
+                side: "RIGHT".to_string(),
+                body: "nit".to_string(),
+            }],
+        };
+        let pending = review_payload(&draft, None);
+        assert_eq!(pending.get("event"), None);
+        assert_eq!(pending["comments"].as_array().unwrap().len(), 1);
+
+        let submitted = review_payload(&draft, Some(ReviewEvent::RequestChanges));
+        assert_eq!(submitted["event"], "REQUEST_CHANGES");
+        assert_eq!(submitted["body"], "A few nits");
+    }
+
     #[test]
     fn build_shell_command_escapes_special_characters() {
         let cmd = build_shell_command("printf", &[String::from("%s")], "it's $HOME");
@@ -1772,15 +4625,566 @@ mod tests {
             repo_path: PathBuf::from("/tmp/repo"),
             repo_name: repo.to_string(),
             url: "https://example.com".to_string(),
+            created_at: Utc::now(),
             updated_at: Utc::now(),
             additions: 1,
             deletions: 1,
             is_draft: false,
+            labels: Vec::new(),
+            base_branch: String::new(),
+            head_branch: String::new(),
+            head_oid: String::new(),
+            mergeable: None,
             review_state: super::ReviewState::Pending,
+            reviewers: Vec::new(),
             details_loaded: true,
         }
     }
 
+    fn review(login: &str, state: &str) -> super::Review {
+        super::Review {
+            author: Some(super::Author {
+                kind: None,
+                rest_type: None,
+                is_bot: None,
+                login: Some(login.to_string()),
+            }),
+            state: Some(state.to_string()),
+            commit: None,
+        }
+    }
+
+    #[test]
+    fn reviewer_statuses_keeps_latest_decisive_state_per_reviewer() {
+        let reviews = vec![
+            review("alice", "CHANGES_REQUESTED"),
+            review("alice", "COMMENTED"),
+            review("bob", "COMMENTED"),
+            review("carol", "APPROVED"),
+            review("carol", "CHANGES_REQUESTED"),
+            review("author", "COMMENTED"),
+        ];
+        let requests = vec![super::ReviewRequest {
+            login: Some("dave".to_string()),
+            name: None,
+        }];
+
+        let statuses = super::reviewer_statuses(&reviews, &requests, "author", None);
+        let states: Vec<(&str, super::ReviewerState)> = statuses
+            .iter()
+            .map(|status| (status.login.as_str(), status.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("alice", super::ReviewerState::ChangesRequested),
+                ("bob", super::ReviewerState::Commented),
+                ("carol", super::ReviewerState::ChangesRequested),
+                ("dave", super::ReviewerState::Pending),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_branch_protection_counts_approvals_against_rule() {
+        let json = br#"{"data": {"repository": {"pullRequest": {
+            "baseRefName": "main",
+            "baseRef": {"branchProtectionRule": {
+                "requiresApprovingReviews": true,
+                "requiredApprovingReviewCount": 2,
+                "requiresStatusChecks": true,
+                "requiredStatusCheckContexts": ["ci", "lint"],
+                "isAdminEnforced": true
+            }},
+            "latestOpinionatedReviews": {"nodes": [{"state": "APPROVED"}, {"state": "CHANGES_REQUESTED"}]}
+        }}}}"#;
+        let protection = super::parse_branch_protection(json)
+            .expect("response should parse")
+            .expect("branch is protected");
+        assert_eq!(protection.required_approvals, 2);
+        assert_eq!(protection.approvals, 1);
+        assert_eq!(
+            protection.approval_shortfall().as_deref(),
+            Some("needs 2 approvals, has 1")
+        );
+        assert_eq!(
+            protection.summary(),
+            "main: 1/2 approvals, checks: ci, lint, enforced for admins"
+        );
+
+        let unprotected = br#"{"data": {"repository": {"pullRequest": {
+            "baseRefName": "main", "baseRef": {"branchProtectionRule": null}
+        }}}}"#;
+        assert_eq!(super::parse_branch_protection(unprotected).unwrap(), None);
+    }
+
+    #[test]
+    fn unmet_checks_and_rollup_treat_a_passing_rerun_as_passed() {
+        let protection = super::BranchProtection {
+            branch: "main".to_string(),
+            required_approvals: 0,
+            approvals: 0,
+            required_checks: ["ci", "lint", "docs", "e2e", "deploy"]
+                .map(str::to_string)
+                .to_vec(),
+            enforce_admins: false,
+        };
+        let check = |name: &str, status: CheckState| super::CheckStatus {
+            name: name.to_string(),
+            status,
+            started_at: None,
+            completed_at: None,
+            link: None,
+        };
+        let checks = [
+            // A re-run that passed makes up for the failed run
+            check("ci", CheckState::Failure),
+            check("ci", CheckState::Success),
+            check("lint", CheckState::Failure),
+            check("docs", CheckState::Neutral),
+            check("e2e", CheckState::Pending),
+        ];
+        assert_eq!(
+            protection.unmet_checks(&checks),
+            ["lint: failing", "e2e: pending", "deploy: missing"]
+        );
+        assert_eq!(super::checks_rollup(&checks), Some(CheckState::Failure));
+        assert_eq!(
+            super::checks_rollup(&checks[..2]),
+            Some(CheckState::Success)
+        );
+        assert_eq!(
+            super::checks_rollup(&checks[3..]),
+            Some(CheckState::Pending)
+        );
+        assert_eq!(super::checks_rollup(&[]), None);
+    }
+
+    #[test]
+    fn parse_review_queues_maps_aliases_to_logins() {
+        let logins = vec!["alice".to_string(), "bob".to_string()];
+        let json = br#"{"data": {"u0": {"issueCount": 7}, "u1": {"issueCount": 2}}}"#;
+        let queues = super::parse_review_queues(json, &logins).unwrap();
+        assert_eq!(queues[0].login, "alice");
+        assert_eq!(queues[0].open_requests, 7);
+        assert_eq!(queues[1].open_requests, 2);
+
+        let missing = br#"{"data": {"u0": {"issueCount": 7}, "u1": null}}"#;
+        assert!(super::parse_review_queues(missing, &logins).is_err());
+    }
+
+    const PR_LIST_FIXTURE: &str = r#"[
+        {"number": 7, "title": "Add cache", "author": {"login": "alice"}, "body": "", "url": "https://github.com/acme/widgets/pull/7",
+         "updatedAt": "2026-03-10T00:00:00Z", "additions": 10, "deletions": 2, "reviews": [], "reviewRequests": [], "isDraft": false, "reviewDecision": null},
+        {"number": 8, "title": "WIP", "author": {"login": "bob"}, "body": "", "url": "https://github.com/acme/widgets/pull/8",
+         "updatedAt": "2026-03-10T00:00:00Z", "additions": 1, "deletions": 0, "reviews": [], "reviewRequests": [], "isDraft": true, "reviewDecision": null},
+        {"number": 9, "title": "Done", "author": {"login": "carol"}, "body": "", "url": "https://github.com/acme/widgets/pull/9",
+         "updatedAt": "2026-03-10T00:00:00Z", "additions": 1, "deletions": 0, "reviews": [{"author": {"login": "me"}, "state": "APPROVED"}], "reviewRequests": [], "isDraft": false, "reviewDecision": "APPROVED"},
+        {"number": 10, "title": "Mine", "author": {"login": "me"}, "body": "", "url": "https://github.com/acme/widgets/pull/10",
+         "updatedAt": "2026-03-10T00:00:00Z", "additions": 3, "deletions": 3, "reviews": [], "reviewRequests": [], "isDraft": false, "reviewDecision": null}
+    ]"#;
+
+    #[test]
+    fn fetch_prs_for_repo_skips_drafts_and_approved_prs() {
+        let runner = ReplayRunner::new();
+        runner.reply("gh pr list", PR_LIST_FIXTURE);
+        let _guard = runner.install();
+
+        let prs =
+            super::fetch_prs_for_repo_with_authored(&PathBuf::from("/tmp"), "me", false).unwrap();
+        let numbers = prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![7, 10]);
+        assert_eq!(prs[0].repo_name, "acme/widgets");
+        assert_eq!(runner.commands().len(), 1);
+
+        let with_drafts =
+            super::fetch_prs_for_repo_with_authored(&PathBuf::from("/tmp"), "me", true).unwrap();
+        assert_eq!(with_drafts.len(), 3);
+    }
+
+    #[test]
+    fn fetch_pr_details_reads_branches_and_mergeability() {
+        let runner = ReplayRunner::new();
+        runner.reply(
+            "gh pr view 7 --repo acme/widgets",
+            r#"{"number": 7, "title": "Add cache", "author": {"login": "alice"}, "url": "https://github.com/acme/widgets/pull/7",
+                "updatedAt": "2026-03-10T00:00:00Z", "baseRefName": "main", "headRefName": "alice/cache", "mergeable": "CONFLICTING"}"#,
+        );
+        let _guard = runner.install();
+
+        let pr = super::fetch_pr_details(&make_test_pr(7, "Add cache", "acme/widgets")).unwrap();
+        assert_eq!(
+            (pr.base_branch.as_str(), pr.head_branch.as_str()),
+            ("main", "alice/cache")
+        );
+        assert_eq!(pr.mergeable, Some(false));
+        assert_eq!(super::mergeable(Some("UNKNOWN")), None);
+        assert_eq!(super::mergeable(Some("MERGEABLE")), Some(true));
+    }
+
+    #[test]
+    fn get_pr_file_diff_lists_patches_once_per_head_and_keeps_file_headers() {
+        let runner = ReplayRunner::new();
+        runner.reply(
+            "gh api --paginate repos/acme/file-diffs/pulls/7/files",
+            concat!(
+                r#"{"filename": "src/new.rs", "status": "renamed", "previous_filename": "src/old.rs", "patch": "@@ -1 +1 @@\n-a\n+b"}"#,
+                "\n",
+                r#"{"filename": "src/added.rs", "status": "added", "patch": "@@ -0,0 +1 @@\n+fn added() {}"}"#,
+                "\n",
+                r#"{"filename": "src/gone.rs", "status": "removed", "patch": "@@ -1 +0,0 @@\n-fn gone() {}"}"#,
+                "\n",
+                r#"{"filename": "logo.png", "status": "modified"}"#,
+            ),
+        );
+        let _guard = runner.install();
+        let mut pr = make_test_pr(7, "Add cache", "acme/file-diffs");
+        pr.head_oid = "abc123".to_string();
+
+        assert_eq!(
+            super::get_pr_file_diff(&pr, "src/new.rs").unwrap(),
+            "diff --git a/src/old.rs b/src/new.rs\nrename from src/old.rs\nrename to src/new.rs\n--- a/src/old.rs\n+++ b/src/new.rs\n@@ -1 +1 @@\n-a\n+b\n"
+        );
+        assert_eq!(
+            super::get_pr_file_diff(&pr, "src/added.rs").unwrap(),
+            "diff --git a/src/added.rs b/src/added.rs\nnew file mode 100644\n--- /dev/null\n+++ b/src/added.rs\n@@ -0,0 +1 @@\n+fn added() {}\n"
+        );
+        assert_eq!(
+            super::get_pr_file_diff(&pr, "src/gone.rs").unwrap(),
+            "diff --git a/src/gone.rs b/src/gone.rs\ndeleted file mode 100644\n--- a/src/gone.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-fn gone() {}\n"
+        );
+        let binary = super::get_pr_file_diff(&pr, "logo.png").unwrap_err();
+        assert!(binary.to_string().contains("No text diff available"));
+        assert_eq!(runner.commands().len(), 1);
+
+        pr.head_oid = "def456".to_string();
+        super::get_pr_file_diff(&pr, "src/new.rs").unwrap();
+        assert_eq!(runner.commands().len(), 2);
+
+        // Without a head SHA only the requested file is fetched, every time
+        pr.head_oid.clear();
+        super::get_pr_file_diff(&pr, "src/new.rs").unwrap();
+        super::get_pr_file_diff(&pr, "src/new.rs").unwrap();
+        let commands = runner.commands();
+        assert_eq!(commands.len(), 4);
+        assert!(commands[3].contains(r#"select(.filename == "src/new.rs")"#));
+    }
+
+    #[test]
+    fn add_line_comment_falls_back_to_a_pr_comment_and_reports_both_failures() {
+        let runner = ReplayRunner::new();
+        runner.fail(
+            "gh api repos/acme/widgets/pulls/7/reviews",
+            "line must be part of the diff",
+        );
+        runner.reply("gh pr comment", "");
+        let _guard = runner.install();
+        let pr = make_test_pr(7, "Add cache", "acme/widgets");
+
+        super::add_line_comment(&pr, "src/lib.rs", None, 12, "RIGHT", "nit").unwrap();
+        assert!(runner.commands()[1].starts_with("gh pr comment 7 --repo acme/widgets"));
+
+        runner.fail("gh pr comment", "HTTP 502");
+        let error =
+            super::add_line_comment(&pr, "src/lib.rs", None, 12, "RIGHT", "nit").unwrap_err();
+        assert!(
+            format!("{:#}", error).contains("Line comment failed (line must be part of the diff)")
+        );
+    }
+
+    #[test]
+    fn request_reviewer_posts_to_the_review_requests_api() {
+        let runner = ReplayRunner::new();
+        runner.reply(
+            "gh api repos/acme/widgets/pulls/7/requested_reviewers",
+            "{}",
+        );
+        let _guard = runner.install();
+
+        super::request_reviewer(&make_test_pr(7, "Add cache", "acme/widgets"), "bob").unwrap();
+        assert_eq!(
+            runner.commands(),
+            vec![
+                "gh api repos/acme/widgets/pulls/7/requested_reviewers -X POST -f reviewers[]=bob"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn fetch_prs_for_repo_reports_gh_failures() {
+        let runner = ReplayRunner::new();
+        runner.fail("gh pr list", "HTTP 401: Bad credentials");
+        let _guard = runner.install();
+
+        let err = super::fetch_prs_for_repo_with_authored(&PathBuf::from("/tmp"), "me", false)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "HTTP 401: Bad credentials");
+    }
+
+    #[test]
+    fn squash_message_lists_commits_and_keeps_co_authors() {
+        let pr = make_test_pr(7, "Add cache", "acme/widgets");
+        let commit = |message: &str, name: &str, login: Option<&str>| PrCommit {
+            sha: "0123456".to_string(),
+            date: None,
+            message: message.to_string(),
+            name: Some(name.to_string()),
+            email: Some(format!("{}@example.com", name.to_lowercase())),
+            login: login.map(str::to_string),
+        };
+        let commits = [
+            commit("Add cache\n\nKeyed by path.", "Alice", Some("alice")),
+            commit(
+                "Fix eviction\n\nCo-authored-by: Carol <carol@example.com>",
+                "Bob",
+                Some("bob"),
+            ),
+            commit(
+                "Tweak\n\nco-authored-by: carol <CAROL@example.com>",
+                "Dan",
+                None,
+            ),
+        ];
+
+        let message = SquashMessage::from_commits(&pr, &commits);
+        assert_eq!(message.title, "Add cache (#7)");
+        assert_eq!(
+            message.body,
+            "* Add cache\n\nKeyed by path.\n\n* Fix eviction\n\n* Tweak\n\n\
+             Co-authored-by: Carol <carol@example.com>\n\
+             Co-authored-by: Bob <bob@example.com>\n\
+             Co-authored-by: Dan <dan@example.com>"
+        );
+
+        // Editing keeps the title and body but not at the cost of a co-author.
+        let edited = message
+            .edited("Cache parsed configs\n\nCo-authored-by: Bob <bob@example.com>\n")
+            .unwrap();
+        assert_eq!(edited.title, "Cache parsed configs");
+        assert_eq!(
+            edited.body,
+            "Co-authored-by: Bob <bob@example.com>\n\n\
+             Co-authored-by: Carol <carol@example.com>\n\
+             Co-authored-by: Dan <dan@example.com>"
+        );
+        assert_eq!(message.edited("\n\n  \n"), None);
+    }
+
+    #[test]
+    fn pr_checks_keep_times_that_happened_and_links() {
+        let checks = parse_pr_checks(
+            br#"[
+                {"name": "lint", "state": "FAILURE", "startedAt": "2026-10-18T12:00:00Z",
+                 "completedAt": "2026-10-18T12:03:12Z", "link": "https://ci.example/lint"},
+                {"name": "deploy", "state": "QUEUED", "startedAt": "0001-01-01T00:00:00Z",
+                 "completedAt": "0001-01-01T00:00:00Z", "link": ""}
+            ]"#,
+        );
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].status, CheckState::Failure);
+        let now = Utc::now();
+        assert_eq!(
+            checks[0].duration(now),
+            Some(chrono::Duration::seconds(192))
+        );
+        assert_eq!(checks[0].link.as_deref(), Some("https://ci.example/lint"));
+        assert_eq!(checks[1].status, CheckState::Pending);
+        assert_eq!(checks[1].duration(now), None);
+        assert_eq!(checks[1].link, None);
+    }
+
+    #[test]
+    fn squash_merge_posts_the_given_message() {
+        let pr = make_test_pr(7, "Add cache", "acme/widgets");
+        let runner = ReplayRunner::new();
+        runner.reply("gh pr merge 7 --repo acme/widgets --squash", "");
+        let _guard = runner.install();
+
+        let message = SquashMessage {
+            title: "Add cache (#7)".to_string(),
+            body: "* Add cache".to_string(),
+        };
+        assert_eq!(
+            super::merge_pr(&pr, false, Some(&message)).unwrap(),
+            "squash"
+        );
+        assert_eq!(
+            runner.commands(),
+            ["gh pr merge 7 --repo acme/widgets --squash --subject Add cache (#7) --body * Add cache"]
+        );
+    }
+
+    #[test]
+    fn merge_flow_checks_status_then_falls_back_to_merge_commit() {
+        let pr = make_test_pr(7, "Add cache", "acme/widgets");
+        let runner = ReplayRunner::new();
+        runner
+            .reply(
+                "gh api graphql",
+                r#"{"data": {"repository": {"pullRequest": {
+                    "mergeable": "MERGEABLE",
+                    "reviewThreads": {"nodes": [{"isResolved": true}]},
+                    "baseRefName": "main", "baseRef": {"branchProtectionRule": null}
+                }}}}"#,
+            )
+            .fail(
+                "gh pr merge 7 --repo acme/widgets --squash",
+                "squash merges are disabled",
+            )
+            .reply("gh pr merge 7 --repo acme/widgets --merge", "");
+        let _guard = runner.install();
+
+        let status = super::check_merge_status(&pr);
+        assert!(status.can_merge, "{:?}", status.reason);
+        assert_eq!(super::merge_pr(&pr, true, None).unwrap(), "merge");
+        assert_eq!(
+            runner.commands()[1..],
+            [
+                "gh pr merge 7 --repo acme/widgets --squash --delete-branch",
+                "gh pr merge 7 --repo acme/widgets --merge --delete-branch",
+            ]
+        );
+
+        runner.reply(
+            "gh api graphql",
+            r#"{"data": {"repository": {"pullRequest": {
+                "mergeable": "MERGEABLE",
+                "reviewThreads": {"nodes": [{"isResolved": false}, {"isResolved": false}]}
+            }}}}"#,
+        );
+        let blocked = super::check_merge_status(&pr);
+        assert!(!blocked.can_merge);
+        assert_eq!(
+            blocked.reason.as_deref(),
+            Some("2 unresolved review thread(s)")
+        );
+    }
+
+    #[test]
+    fn parse_author_stats_summarizes_recent_prs() {
+        let json = br#"{"data": {
+            "all": {"issueCount": 12, "nodes": [
+                {"createdAt": "2026-03-10T00:00:00Z", "additions": 40, "deletions": 10, "state": "OPEN", "merged": false, "reviewDecision": null},
+                {"createdAt": "2026-03-01T00:00:00Z", "additions": 100, "deletions": 20, "state": "MERGED", "merged": true, "reviewDecision": "APPROVED"},
+                {"createdAt": "2026-01-05T00:00:00Z", "additions": 300, "deletions": 0, "state": "CLOSED", "merged": false, "reviewDecision": "CHANGES_REQUESTED"}
+            ]},
+            "merged": {"issueCount": 9}
+        }}"#;
+        let now = "2026-03-15T00:00:00Z".parse().unwrap();
+        let stats = super::parse_author_stats(json, now).unwrap();
+        assert_eq!(stats.total_prs, 12);
+        assert_eq!(stats.merged_prs, 9);
+        assert_eq!(stats.recent_prs, 2);
+        assert_eq!(stats.median_lines, Some(120));
+        assert_eq!(stats.approval_rate, Some(0.5));
+        assert_eq!(
+            stats.summary(),
+            "12 PRs (9 merged) | 2 in 30d | ~120 lines | 50% approved"
+        );
+    }
+
+    #[test]
+    fn parse_changed_files_reads_jq_lines() {
+        let output = br#"{"filename":"src/lib.rs","status":"modified","additions":10,"deletions":2,"previous_filename":null}
+{"filename":"assets/logo.png","status":"renamed","additions":0,"deletions":0,"previous_filename":"logo.png"}
+"#;
+        let files = super::parse_changed_files(output).expect("files should parse");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!(files[0].status, "modified");
+        assert_eq!(files[0].additions, 10);
+        assert_eq!(files[0].previous_filename, None);
+        assert_eq!(files[1].path, "assets/logo.png");
+        assert_eq!(files[1].previous_filename.as_deref(), Some("logo.png"));
+    }
+
+    #[test]
+    fn parse_review_threads_keys_state_by_first_comment() {
+        let json = br#"{"data":{"repository":{"pullRequest":{"reviewThreads":{"nodes":[
+            {"id":"PRRT_a","isResolved":true,"comments":{"nodes":[{"databaseId":11}]}},
+            {"id":"PRRT_b","isResolved":false,"comments":{"nodes":[{"databaseId":21}]}},
+            {"id":"PRRT_c","isResolved":false,"comments":{"nodes":[]}}
+        ]}}}}}"#;
+
+        let threads = super::parse_review_threads(json).expect("threads should parse");
+        assert_eq!(threads.len(), 2);
+        assert_eq!(
+            threads[&11],
+            super::ReviewThreadState {
+                id: "PRRT_a".to_string(),
+                is_resolved: true
+            }
+        );
+        assert!(!threads[&21].is_resolved);
+    }
+
+    #[test]
+    fn parse_pr_feedback_keeps_unresolved_threads_and_change_requests() {
+        let json = br#"{"data":{"repository":{"pullRequest":{
+            "latestOpinionatedReviews":{"nodes":[
+                {"author":{"login":"alice"},"state":"CHANGES_REQUESTED","body":"Please add tests"},
+                {"author":{"login":"bob"},"state":"APPROVED","body":""}
+            ]},
+            "reviewThreads":{"nodes":[
+                {"isResolved":false,"isOutdated":true,"path":"src/lib.rs","line":null,"originalLine":12,
+                 "comments":{"nodes":[{"author":{"login":"alice"},"body":"Handle the error","createdAt":"2024-01-01T00:00:00Z"}]}},
+                {"isResolved":true,"isOutdated":false,"path":"src/main.rs","line":3,"originalLine":3,
+                 "comments":{"nodes":[]}}
+            ]}
+        }}}}"#;
+
+        let feedback = super::parse_pr_feedback(json).expect("feedback should parse");
+        assert_eq!(feedback.change_requests.len(), 1);
+        assert_eq!(feedback.change_requests[0].author, "alice");
+        assert_eq!(feedback.threads.len(), 1);
+        assert_eq!(feedback.threads[0].path, "src/lib.rs");
+        assert_eq!(feedback.threads[0].line, Some(12));
+        assert!(feedback.threads[0].is_outdated);
+        assert_eq!(feedback.threads[0].comments[0].body, "Handle the error");
+    }
+
+    #[test]
+    fn reviewer_statuses_marks_re_requested_reviewers_pending() {
+        let reviews = vec![review("alice", "APPROVED")];
+        let requests = vec![super::ReviewRequest {
+            login: Some("alice".to_string()),
+            name: None,
+        }];
+
+        let statuses = super::reviewer_statuses(&reviews, &requests, "author", None);
+        assert_eq!(statuses[0].state, super::ReviewerState::Pending);
+    }
+
+    #[test]
+    fn reviewer_statuses_marks_reviews_of_an_older_head_stale() {
+        let on = |login: &str, state: &str, oid: &str| super::Review {
+            commit: Some(super::ReviewCommit {
+                oid: oid.to_string(),
+            }),
+            ..review(login, state)
+        };
+        let reviews = vec![
+            on("alice", "APPROVED", "aaa"),
+            on("bob", "CHANGES_REQUESTED", "bbb"),
+            review("carol", "COMMENTED"),
+        ];
+
+        let statuses = super::reviewer_statuses(&reviews, &[], "author", Some("bbb"));
+        let stale: Vec<(&str, bool)> = statuses
+            .iter()
+            .map(|status| (status.login.as_str(), status.stale))
+            .collect();
+        assert_eq!(
+            stale,
+            vec![("alice", true), ("bob", false), ("carol", false)]
+        );
+
+        // Without the head commit nothing is known to be stale
+        let statuses = super::reviewer_statuses(&reviews, &[], "author", None);
+        assert!(statuses.iter().all(|status| !status.stale));
+    }
+
     #[test]
     fn search_qualifiers_adds_negative_authors_for_exact_excludes() {
         let qualifiers = search_qualifiers(
@@ -1793,6 +5197,7 @@ mod tests {
                 "@apps/*".to_string(),
                 "github-*".to_string(),
             ],
+            &FetchConfig::default(),
         );
 
         assert!(qualifiers.contains(&"-author:dependabot".to_string()));
@@ -1803,6 +5208,72 @@ mod tests {
         assert!(!qualifiers.contains(&"-author:github-*".to_string()));
     }
 
+    #[test]
+    fn fetch_filters_become_search_qualifiers() {
+        let fetch = FetchConfig {
+            base: Some("main".to_string()),
+            labels: vec!["needs review".to_string(), "backend".to_string()],
+            authors: vec!["@alice".to_string(), "bob".to_string()],
+        };
+        assert_eq!(
+            super::fetch_qualifiers(&fetch, true),
+            vec![
+                "base:main",
+                "label:\"needs review\"",
+                "label:backend",
+                "author:alice",
+                "author:bob",
+            ]
+        );
+        assert!(super::fetch_qualifiers(&FetchConfig::default(), true).is_empty());
+
+        let involved = search_qualifiers("daulet", true, SearchScope::Involved, &[], &fetch);
+        assert!(involved.contains(&"base:main".to_string()));
+        assert!(involved.contains(&"author:alice".to_string()));
+        assert_eq!(involved.last().unwrap(), "sort:updated-desc");
+
+        // My own PRs are already pinned to me, and bot PRs to the bot
+        let authored = search_qualifiers("daulet", true, SearchScope::Authored, &[], &fetch);
+        assert!(authored.contains(&"label:backend".to_string()));
+        assert!(!authored.contains(&"author:alice".to_string()));
+        let bot = search_qualifiers(
+            "daulet",
+            true,
+            SearchScope::DependencyBot("dependabot"),
+            &[],
+            &fetch,
+        );
+        assert!(!bot.contains(&"base:main".to_string()));
+    }
+
+    #[test]
+    fn approvals_search_keeps_prs_whose_latest_review_is_mine_and_approved() {
+        let qualifiers = search_qualifiers(
+            "daulet",
+            true,
+            SearchScope::ApprovedByMe,
+            &[],
+            &FetchConfig::default(),
+        );
+        assert!(qualifiers.contains(&"reviewed-by:daulet".to_string()));
+        assert!(qualifiers.contains(&"-author:daulet".to_string()));
+
+        let node = |state: &str| {
+            serde_json::from_str::<super::SearchPrData>(&format!(
+                r#"{{"number": 1, "title": "t", "url": "u", "updatedAt": "2026-01-01T00:00:00Z",
+                    "repository": {{"nameWithOwner": "org/reviewer"}},
+                    "latestReviews": {{"nodes": [
+                        {{"author": {{"login": "alice"}}, "state": "APPROVED"}},
+                        {{"author": {{"login": "Daulet"}}, "state": "{}"}}
+                    ]}}}}"#,
+                state
+            ))
+            .unwrap()
+        };
+        assert!(node("APPROVED").approved_by("daulet"));
+        assert!(!node("CHANGES_REQUESTED").approved_by("daulet"));
+    }
+
     #[test]
     fn render_launch_template_replaces_placeholders() {
         let pr = make_test_pr(42, "Fix launch", "org/reviewer");
@@ -1816,6 +5287,7 @@ mod tests {
             provider: "codex",
             skill_name: "code-review",
             skill_invocation: "$code-review",
+            session_marker: None,
         });
         let rendered = render_launch_template(
             "{repo}|{pr_number}|{tool}|{prompt}|{skill_invocation}|{session_title}",
@@ -1825,6 +5297,79 @@ mod tests {
             .contains("org/reviewer|42|codex|Review this|$code-review|review-org-reviewer-pr-42-"));
     }
 
+    #[test]
+    fn render_prompt_replaces_pr_placeholders() {
+        let pr = make_test_pr(7, "Tighten auth", "org/reviewer");
+        let details = PromptPrDetails {
+            base_branch: "main".to_string(),
+            changed_files: vec!["src/auth.rs".to_string(), "README.md".to_string()],
+        };
+        let rendered = render_prompt(
+            "{repo}#{pr_number} by {author} into {base_branch}: {changed_files} ({url}) {results_dir}",
+            &pr,
+            Path::new("/tmp/review_guide.md"),
+            Path::new("/tmp/repo/.reviewer"),
+            "$code-review",
+            &details,
+        );
+        assert_eq!(
+            rendered,
+            format!(
+                "org/reviewer#7 by {} into main: src/auth.rs, README.md ({}) /tmp/repo/.reviewer",
+                pr.author, pr.url
+            )
+        );
+        assert!(PromptPrDetails::needed_by("files: {changed_files}"));
+        assert!(!PromptPrDetails::needed_by("{repo} {author}"));
+    }
+
+    #[test]
+    fn hostile_pr_title_stays_inert_in_launched_command() {
+        let workdir = std::env::temp_dir().join(format!(
+            "reviewer-injection-test-{}-it's $(here)",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&workdir).unwrap();
+        let canary = workdir.join("pwned");
+        let title = "Fix `touch pwned` $(touch pwned) \"; touch pwned; echo \" '; touch pwned; ' \
+                     {title}'; touch pwned; ' {prompt} {tool_command} {workdir_shell} {author";
+        let pr = make_test_pr(7, title, "org/reviewer");
+        let prompt = render_prompt(
+            "Review {title} by {author}",
+            &pr,
+            Path::new("/tmp/review_guide.md"),
+            Path::new("/tmp/repo/.reviewer"),
+            "$code-review",
+            &PromptPrDetails::default(),
+        );
+        let values = LaunchTemplateValues::from_context(LaunchContext {
+            working_dir: &workdir,
+            tool: "printf",
+            tool_args: &["%s".to_string()],
+            prompt: &prompt,
+            review_guide: Path::new("/tmp/review_guide.md"),
+            pr: &pr,
+            provider: "codex",
+            skill_name: "code-review",
+            skill_invocation: "$code-review",
+            session_marker: None,
+        });
+        let script = render_launch_template("cd {workdir_shell} && exec {tool_command}", &values);
+        assert!(prompt.contains("{title}'; touch pwned; ' {prompt} {tool_command}"));
+
+        let output = Command::new("sh").args(["-c", &script]).output().unwrap();
+        let canary_exists = canary.exists();
+        let _ = std::fs::remove_dir_all(&workdir);
+
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(String::from_utf8_lossy(&output.stdout), prompt);
+        assert!(!canary_exists, "PR title ran a command");
+    }
+
     #[test]
     fn launch_with_steps_requires_non_empty_steps() {
         let pr = make_test_pr(1, "Title", "org/reviewer");
@@ -1838,9 +5383,15 @@ mod tests {
             provider: "codex",
             skill_name: "code-review",
             skill_invocation: "$code-review",
+            session_marker: None,
         });
-        let err = launch_with_steps(Path::new("/tmp/repo"), &AiConfig::default(), &values)
-            .expect_err("expected launch config error");
+        let err = launch_with_steps(
+            Path::new("/tmp/repo"),
+            &AiConfig::default(),
+            &values,
+            &mut Vec::new(),
+        )
+        .expect_err("expected launch config error");
         let msg = format!("{err:#}");
         assert!(
             msg.contains("ai.launch.steps is empty"),
@@ -1855,4 +5406,286 @@ mod tests {
 
         validate_ai_launch_config(&ai).expect("tmux launch backend should not require steps");
     }
+
+    #[test]
+    fn validate_ai_launch_config_checks_terminal_mode() {
+        let mut ai = AiConfig::default();
+        ai.launch.backend = Some("terminal".to_string());
+        validate_ai_launch_config(&ai).expect("terminal backend should default to auto mode");
+
+        ai.launch.terminal.mode = Some("split-pane".to_string());
+        let err = validate_ai_launch_config(&ai).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("Invalid terminal launch mode 'split-pane'"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn closed_pr_state_recognizes_merged_closed_and_missing_prs() {
+        use super::closed_pr_state;
+        assert_eq!(
+            closed_pr_state("Pull request acme/widgets#7 was already merged"),
+            Some("merged")
+        );
+        assert_eq!(closed_pr_state("PR was already merged"), Some("merged"));
+        assert_eq!(
+            closed_pr_state("GraphQL: Pull request is closed (addPullRequestReview)"),
+            Some("closed")
+        );
+        assert_eq!(
+            closed_pr_state("GraphQL: Could not resolve to a PullRequest with the number of 7."),
+            Some("deleted")
+        );
+        assert_eq!(closed_pr_state("HTTP 502: Bad Gateway"), None);
+    }
+
+    #[test]
+    fn parse_blame_porcelain_reads_commit_author_and_summary() {
+        use super::parse_blame_porcelain;
+        let output = "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b 12 12 1\n\
+                      author Alice Example\n\
+                      author-mail <alice@example.com>\n\
+                      author-time 1700000000\n\
+                      author-tz +0000\n\
+                      summary Parse config lazily\n\
+                      filename src/lib.rs\n\
+                      \tlet config = load();\n";
+        let blame = parse_blame_porcelain(output).unwrap();
+        assert_eq!(blame.commit, "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b");
+        assert_eq!(blame.author, "Alice Example");
+        assert_eq!(blame.authored_at.timestamp(), 1_700_000_000);
+        assert_eq!(blame.summary, "Parse config lazily");
+        assert_eq!(parse_blame_porcelain(""), None);
+    }
+
+    #[test]
+    fn create_pr_worktree_fast_forwards_an_existing_worktree() {
+        let root =
+            std::env::temp_dir().join(format!("reviewer-worktree-test-{}", std::process::id()));
+        let worktree = root.join(".worktrees").join("acme-widgets-pr-7");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(worktree.join(".git"), "gitdir: elsewhere\n").unwrap();
+        let mut pr = make_test_pr(7, "Add cache", "acme/widgets");
+        pr.repo_path = root.clone();
+
+        let runner = ReplayRunner::new();
+        runner
+            .reply("git fetch origin refs/pull/7/head", "")
+            .reply("git rev-parse FETCH_HEAD", "abc123\n")
+            .reply("git status --porcelain", "")
+            .reply("git merge-base --is-ancestor HEAD abc123", "")
+            .reply("git merge --ff-only abc123", "")
+            .reply("git worktree", "");
+        let _guard = runner.install();
+
+        assert_eq!(super::create_pr_worktree(&pr, &root).unwrap(), worktree);
+        assert!(!runner
+            .commands()
+            .iter()
+            .any(|command| command.starts_with("git worktree")));
+
+        // A force-pushed PR no longer fast-forwards, so the worktree is recreated.
+        runner.fail("git merge-base --is-ancestor HEAD abc123", "");
+        let before = runner.commands().len();
+        assert_eq!(super::create_pr_worktree(&pr, &root).unwrap(), worktree);
+        let commands = runner.commands().split_off(before);
+        assert!(!commands.contains(&"git merge --ff-only abc123".to_string()));
+        assert_eq!(
+            commands.last().unwrap(),
+            &format!("git worktree add {} abc123", worktree.display())
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn create_pr_worktree_drops_the_last_sessions_results_and_edits() {
+        let root = std::env::temp_dir().join(format!(
+            "reviewer-worktree-stale-test-{}",
+            std::process::id()
+        ));
+        let worktree = root.join(".worktrees").join("acme-widgets-pr-7");
+        let results = crate::review_results::results_dir(&worktree);
+        std::fs::create_dir_all(&results).unwrap();
+        std::fs::write(worktree.join(".git"), "gitdir: elsewhere\n").unwrap();
+        std::fs::write(
+            results.join(crate::review_results::REVIEW_MD),
+            "- old finding\n",
+        )
+        .unwrap();
+        let mut pr = make_test_pr(7, "Add cache", "acme/widgets");
+        pr.repo_path = root.clone();
+
+        let runner = ReplayRunner::new();
+        runner
+            .reply("git fetch origin refs/pull/7/head", "")
+            .reply("git rev-parse FETCH_HEAD", "abc123\n")
+            .reply("git status --porcelain", "")
+            .reply("git merge-base --is-ancestor HEAD abc123", "")
+            .reply("git merge --ff-only abc123", "")
+            .reply("git worktree", "");
+        let _guard = runner.install();
+
+        // Reused, but without the previous review's results
+        assert_eq!(super::create_pr_worktree(&pr, &root).unwrap(), worktree);
+        assert!(!results.exists());
+        assert!(runner
+            .commands()
+            .contains(&"git merge --ff-only abc123".to_string()));
+
+        // Files the last agent left behind mean starting over
+        runner.reply("git status --porcelain", " M src/lib.rs\n?? notes.txt\n");
+        let before = runner.commands().len();
+        assert_eq!(super::create_pr_worktree(&pr, &root).unwrap(), worktree);
+        let commands = runner.commands().split_off(before);
+        assert!(!commands
+            .iter()
+            .any(|command| command.starts_with("git merge")));
+        assert_eq!(
+            commands.last().unwrap(),
+            &format!("git worktree add {} abc123", worktree.display())
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn create_pr_worktree_refuses_while_another_process_holds_the_lock() {
+        let root = std::env::temp_dir().join(format!(
+            "reviewer-worktree-lock-test-{}",
+            std::process::id()
+        ));
+        let base = root.join(".worktrees");
+        std::fs::create_dir_all(&base).unwrap();
+        let pr = make_test_pr(7, "Add cache", "acme/widgets");
+
+        let held = super::lock_pr_worktree(&pr, &base).unwrap();
+        let err = super::create_pr_worktree(&pr, &root).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Review already in progress for acme/widgets#7"),
+            "{:#}",
+            err
+        );
+        drop(held);
+        assert!(super::lock_pr_worktree(&pr, &base).is_ok());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn create_pr_worktree_refuses_while_a_launched_session_runs_in_it() {
+        let root = std::env::temp_dir().join(format!(
+            "reviewer-worktree-session-test-{}",
+            std::process::id()
+        ));
+        let base = root.join(".worktrees");
+        std::fs::create_dir_all(base.join(".locks")).unwrap();
+        let pr = make_test_pr(7, "Add cache", "acme/widgets");
+        let worktree = base.join("acme-widgets-pr-7");
+        let marker = super::session_marker_for(&pr, &worktree).unwrap();
+        assert_eq!(marker, super::pr_session_marker(&pr, &base));
+        assert_eq!(super::session_marker_for(&pr, &root), None);
+
+        // The launched command records the session's pid before becoming the tool
+        let command = super::with_session_marker("'codex' 'Review'", &marker);
+        assert_eq!(
+            command,
+            format!(
+                "sh -c 'echo $$ > '\\''{}'\\'' && exec '\\''codex'\\'' '\\''Review'\\'''",
+                marker.display()
+            )
+        );
+
+        // This process stands in for a session that is still running
+        std::fs::write(&marker, std::process::id().to_string()).unwrap();
+        let err = super::create_pr_worktree(&pr, &root).unwrap_err();
+        assert!(
+            err.to_string().starts_with(&format!(
+                "Review already in progress for acme/widgets#7: an AI session (pid {})",
+                std::process::id()
+            )),
+            "{:#}",
+            err
+        );
+
+        // A session that has ended no longer blocks, and its marker is cleared
+        let mut ended = Command::new("true").spawn().unwrap();
+        ended.wait().unwrap();
+        std::fs::write(&marker, ended.id().to_string()).unwrap();
+        assert_eq!(super::running_pr_session(&marker), None);
+        assert!(!marker.exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn parse_discussions_counts_comments_and_unresolved_threads() {
+        let json = br#"{"data": {
+            "p0": {"pullRequest": {
+                "comments": {"totalCount": 2},
+                "reviewThreads": {"nodes": [
+                    {"isResolved": true, "comments": {"totalCount": 3}},
+                    {"isResolved": false, "comments": {"totalCount": 1}}
+                ]}
+            }},
+            "p1": {"pullRequest": null}
+        }}"#;
+        let discussions = super::parse_discussions(json, 3).unwrap();
+        assert_eq!(
+            discussions,
+            vec![
+                Some(super::Discussion {
+                    comments: 6,
+                    unresolved_threads: 1,
+                }),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_pr_triage_reads_milestone_and_project_status() {
+        let json = br#"{
+            "milestone": {"number": 3, "title": "v1.0", "dueOn": null},
+            "projectItems": [
+                {"title": "Roadmap", "status": {"optionId": "a1", "name": "In review"}},
+                {"title": "Backlog", "status": {"optionId": "", "name": ""}}
+            ]
+        }"#;
+        assert_eq!(
+            super::parse_pr_triage(json, true).unwrap(),
+            super::PrTriage {
+                milestone: Some("v1.0".to_string()),
+                projects: Some(vec![
+                    "Roadmap (In review)".to_string(),
+                    "Backlog".to_string()
+                ]),
+            }
+        );
+        assert_eq!(
+            super::parse_pr_triage(br#"{"milestone": null}"#, false).unwrap(),
+            super::PrTriage::default()
+        );
+    }
+
+    #[test]
+    fn get_repo_permission_parses_viewer_permission() {
+        let runner = ReplayRunner::new();
+        runner
+            .reply("gh repo view acme/widgets", "TRIAGE\n")
+            .reply("gh repo view acme/gadgets", "MAINTAIN\n")
+            .reply("gh repo view acme/legacy", "NONE\n");
+        let _guard = runner.install();
+
+        let triage = super::get_repo_permission("acme/widgets").unwrap();
+        assert_eq!(triage, super::RepoPermission::Triage);
+        assert!(!triage.can_write());
+        assert!(super::get_repo_permission("acme/gadgets")
+            .unwrap()
+            .can_write());
+        assert!(super::get_repo_permission("acme/legacy").is_err());
+        assert_eq!(
+            runner.commands()[0],
+            "gh repo view acme/widgets --json viewerPermission --jq .viewerPermission"
+        );
+    }
 }
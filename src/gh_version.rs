@@ -0,0 +1,166 @@
+use crate::logging::CommandExt;
+use anyhow::Result;
+use std::fmt;
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// A `gh` release, as reported by `gh --version`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GhVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl GhVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse the first line of `gh --version`, e.g. `gh version 2.40.1 (2023-12-13)`.
+    pub fn parse(output: &str) -> Option<Self> {
+        let version = output
+            .lines()
+            .next()?
+            .strip_prefix("gh version ")?
+            .split_whitespace()
+            .next()?;
+        let mut parts = version
+            .split(['.', '-'])
+            .map(|part| part.parse::<u32>().ok());
+        Some(Self::new(
+            parts.next()??,
+            parts.next()??,
+            parts.next().flatten().unwrap_or(0),
+        ))
+    }
+}
+
+impl fmt::Display for GhVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Oldest release the core commands (`pr view --json`, `api --jq`, `pr merge --auto`) work with
+pub const MINIMUM: GhVersion = GhVersion::new(2, 0, 0);
+
+/// Features that need a newer `gh` than `MINIMUM`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    PrChecksJson, // `gh pr checks --json`, for the CI status in the detail header and Checks tab
+}
+
+impl Capability {
+    const ALL: [Capability; 1] = [Capability::PrChecksJson];
+
+    pub fn since(self) -> GhVersion {
+        match self {
+            Capability::PrChecksJson => GhVersion::new(2, 40, 0),
+        }
+    }
+
+    fn feature(self) -> &'static str {
+        match self {
+            Capability::PrChecksJson => "CI check status",
+        }
+    }
+
+    /// Whether `installed` has the feature. An unknown version gets the benefit of the doubt.
+    pub fn supported_by(self, installed: Option<GhVersion>) -> bool {
+        installed.is_none_or(|version| version >= self.since())
+    }
+
+    pub fn upgrade_hint(self, installed: GhVersion) -> String {
+        format!(
+            "{} needs gh {} or newer (installed: {}); upgrade gh to enable it",
+            self.feature(),
+            self.since(),
+            installed
+        )
+    }
+}
+
+/// The installed `gh` version, detected once per process
+pub fn installed() -> Option<GhVersion> {
+    static INSTALLED: OnceLock<Option<GhVersion>> = OnceLock::new();
+    *INSTALLED.get_or_init(|| {
+        let output = Command::new("gh").arg("--version").logged_output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let version = GhVersion::parse(&String::from_utf8_lossy(&output.stdout));
+        if version.is_none() {
+            tracing::warn!("could not parse `gh --version` output");
+        }
+        version
+    })
+}
+
+pub fn supports(capability: Capability) -> bool {
+    capability.supported_by(installed())
+}
+
+/// Fail with an upgrade hint, instead of gh's own error, when `capability` is missing.
+pub fn require(capability: Capability) -> Result<()> {
+    match installed() {
+        Some(version) if !capability.supported_by(Some(version)) => {
+            anyhow::bail!(capability.upgrade_hint(version))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// What to tell the user at startup about their `gh`, if anything.
+pub fn startup_warning() -> Option<String> {
+    installed().and_then(warning_for)
+}
+
+fn warning_for(installed: GhVersion) -> Option<String> {
+    if installed < MINIMUM {
+        return Some(format!(
+            "gh {} is older than {}, the oldest release reviewer supports; upgrade gh",
+            installed, MINIMUM
+        ));
+    }
+    let missing: Vec<String> = Capability::ALL
+        .iter()
+        .filter(|capability| !capability.supported_by(Some(installed)))
+        .map(|capability| capability.upgrade_hint(installed))
+        .collect();
+    (!missing.is_empty()).then(|| missing.join("; "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{warning_for, Capability, GhVersion};
+
+    #[test]
+    fn parses_gh_version_output_and_gates_capabilities() {
+        let output =
+            "gh version 2.39.2 (2023-11-30)\nhttps://github.com/cli/cli/releases/tag/v2.39.2\n";
+        let old = GhVersion::parse(output).unwrap();
+        assert_eq!(old, GhVersion::new(2, 39, 2));
+        assert_eq!(
+            GhVersion::parse("gh version 2.45.0-rc.1 (2024-03-01)"),
+            Some(GhVersion::new(2, 45, 0))
+        );
+        assert_eq!(GhVersion::parse("git version 2.43.0"), None);
+
+        assert!(!Capability::PrChecksJson.supported_by(Some(old)));
+        assert!(Capability::PrChecksJson.supported_by(Some(GhVersion::new(2, 40, 0))));
+        assert!(Capability::PrChecksJson.supported_by(None));
+        assert_eq!(
+            warning_for(old).as_deref(),
+            Some("CI check status needs gh 2.40.0 or newer (installed: 2.39.2); upgrade gh to enable it")
+        );
+        assert!(warning_for(GhVersion::new(1, 14, 0))
+            .unwrap()
+            .contains("older than 2.0.0"));
+        assert_eq!(warning_for(GhVersion::new(2, 62, 0)), None);
+    }
+}
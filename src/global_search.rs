@@ -0,0 +1,133 @@
+use crate::diff::{self, DiffLineType};
+use crate::gh::PullRequest;
+
+/// Diff matches kept per PR, so a common term does not flood the results.
+const MAX_DIFF_MATCHES_PER_PR: usize = 20;
+const MAX_SNIPPET_CHARS: usize = 120;
+
+/// Where in a PR the term matched
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchLocation {
+    Title,
+    Body,
+    Diff { path: String, line: Option<u32> },
+}
+
+/// One hit of a search across every PR in the list
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalMatch {
+    pub repo: String,
+    pub number: u64,
+    pub location: MatchLocation,
+    pub snippet: String,
+}
+
+impl GlobalMatch {
+    fn new(pr: &PullRequest, location: MatchLocation, text: &str) -> Self {
+        let text = text.trim();
+        let snippet = match text.char_indices().nth(MAX_SNIPPET_CHARS) {
+            Some((end, _)) => format!("{}…", &text[..end]),
+            None => text.to_string(),
+        };
+        Self {
+            repo: pr.repo_name.clone(),
+            number: pr.number,
+            location,
+            snippet,
+        }
+    }
+
+    pub fn location_label(&self) -> String {
+        match &self.location {
+            MatchLocation::Title => "title".to_string(),
+            MatchLocation::Body => "body".to_string(),
+            MatchLocation::Diff {
+                path,
+                line: Some(line),
+            } => format!("{}:{}", path, line),
+            MatchLocation::Diff { path, line: None } => path.clone(),
+        }
+    }
+}
+
+/// Case-insensitive matches in the PR title and each body line.
+pub fn search_text(pr: &PullRequest, term: &str) -> Vec<GlobalMatch> {
+    let term = term.to_lowercase();
+    let mut matches = Vec::new();
+    if pr.title.to_lowercase().contains(&term) {
+        matches.push(GlobalMatch::new(pr, MatchLocation::Title, &pr.title));
+    }
+    matches.extend(
+        pr.body
+            .lines()
+            .filter(|line| line.to_lowercase().contains(&term))
+            .map(|line| GlobalMatch::new(pr, MatchLocation::Body, line)),
+    );
+    matches
+}
+
+/// Case-insensitive matches on changed and context lines of the PR diff.
+pub fn search_diff(pr: &PullRequest, diff_text: &str, term: &str) -> Vec<GlobalMatch> {
+    let term = term.to_lowercase();
+    diff::parse_diff_enhanced(diff_text)
+        .into_iter()
+        .filter(|line| {
+            matches!(
+                line.line_type,
+                DiffLineType::Added | DiffLineType::Removed | DiffLineType::Context
+            ) && line.content.to_lowercase().contains(&term)
+        })
+        .filter_map(|line| {
+            let location = MatchLocation::Diff {
+                path: line.file_path?,
+                line: line.new_line_num.or(line.old_line_num),
+            };
+            Some(GlobalMatch::new(pr, location, &line.content))
+        })
+        .take(MAX_DIFF_MATCHES_PER_PR)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{search_diff, search_text, MatchLocation};
+    use crate::gh::{PullRequest, ReviewState};
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    #[test]
+    fn search_finds_term_in_title_body_and_diff_lines() {
+        let pr = PullRequest {
+            number: 7,
+            title: "Speed up parse_config".to_string(),
+            author: "alice".to_string(),
+            author_kind: None,
+            body: "Motivation\n\nparse_config was slow.".to_string(),
+            repo_path: PathBuf::new(),
+            repo_name: "org/reviewer".to_string(),
+            url: String::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            additions: 0,
+            deletions: 0,
+            is_draft: false,
+            labels: Vec::new(),
+            base_branch: String::new(),
+            head_branch: String::new(),
+            head_oid: String::new(),
+            mergeable: None,
+            review_state: ReviewState::Pending,
+            reviewers: Vec::new(),
+            details_loaded: false,
+        };
+        let text_matches = search_text(&pr, "PARSE_CONFIG");
+        assert_eq!(text_matches.len(), 2);
+        assert_eq!(text_matches[0].location, MatchLocation::Title);
+        assert_eq!(text_matches[1].snippet, "parse_config was slow.");
+
+        let diff = "diff --git a/src/config.rs b/src/config.rs\n--- a/src/config.rs\n+++ b/src/config.rs\n@@ -10,2 +10,3 @@\n fn load() {\n+    parse_config(&s)?;\n }\n";
+        let diff_matches = search_diff(&pr, diff, "parse_config");
+        assert_eq!(diff_matches.len(), 1);
+        assert_eq!(diff_matches[0].location_label(), "src/config.rs:11");
+    }
+}
@@ -3,7 +3,7 @@ use clap::Parser;
 use std::path::PathBuf;
 
 #[cfg(target_os = "macos")]
-use crate::{config, terminal};
+use crate::{config, shell_escape, terminal};
 #[cfg(target_os = "macos")]
 use anyhow::Context;
 #[cfg(target_os = "macos")]
@@ -387,7 +387,7 @@ fn build_harness_command(
     hold_seconds: u64,
     marker_file: &Path,
 ) -> String {
-    let marker_file_escaped = unix_shell_escape(&marker_file.display().to_string());
+    let marker_file_escaped = shell_escape::posix(&marker_file.display().to_string());
     format!(
         "printf '%s\\n' 'HARNESS_MARKER={marker}' 'HARNESS_RUN={run_index}' > {marker_file}; date -u '+%Y-%m-%dT%H:%M:%SZ' >> {marker_file}; sleep {hold_seconds}",
         marker_file = marker_file_escaped
@@ -558,11 +558,6 @@ fn diff_snapshots(before: &ProcessSnapshot, after: &ProcessSnapshot) -> Snapshot
     }
 }
 
-#[cfg(target_os = "macos")]
-fn unix_shell_escape(value: &str) -> String {
-    format!("'{}'", value.replace('\'', "'\\''"))
-}
-
 #[cfg(target_os = "macos")]
 fn slug(value: &str) -> String {
     let mut slug = String::with_capacity(value.len());
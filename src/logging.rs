@@ -0,0 +1,116 @@
+use crate::private_fs;
+use crate::process::{self, describe_command};
+use anyhow::{Context, Result};
+use std::io;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable holding a `tracing` filter, e.g. `reviewer=debug`.
+pub const LOG_ENV: &str = "REVIEWER_LOG";
+
+fn verbosity_filter(verbose: u8) -> &'static str {
+    match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Install the global subscriber. Logging stays off unless `--verbose` or
+/// `REVIEWER_LOG` is given; the TUI passes a log file so output never lands on
+/// the alternate screen.
+pub fn init(verbose: u8, log_file: Option<&Path>) -> Result<()> {
+    let env_filter = std::env::var(LOG_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty());
+    if verbose == 0 && env_filter.is_none() {
+        return Ok(());
+    }
+
+    let filter = match env_filter {
+        Some(directives) => EnvFilter::try_new(&directives)
+            .with_context(|| format!("Invalid {} filter '{}'", LOG_ENV, directives))?,
+        None => EnvFilter::new(verbosity_filter(verbose)),
+    };
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match log_file {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                private_fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            let file = private_fs::open_options()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open log file {}", path.display()))?;
+            builder
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .init();
+        }
+        None => builder.with_writer(io::stderr).init(),
+    }
+    Ok(())
+}
+
+/// `Command::output` that logs the command line, duration, and exit code.
+pub trait CommandExt {
+    fn logged_output(&mut self) -> io::Result<Output>;
+    /// Like `logged_output`, writing `input` to the command's stdin.
+    fn logged_output_with_input(&mut self, input: &[u8]) -> io::Result<Output>;
+}
+
+impl CommandExt for Command {
+    fn logged_output(&mut self) -> io::Result<Output> {
+        run_logged(self, None)
+    }
+
+    fn logged_output_with_input(&mut self, input: &[u8]) -> io::Result<Output> {
+        run_logged(self, Some(input))
+    }
+}
+
+fn run_logged(command: &mut Command, stdin: Option<&[u8]>) -> io::Result<Output> {
+    let description = describe_command(command);
+    let started = Instant::now();
+    let result = process::output(command, stdin);
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        Ok(output) => tracing::debug!(
+            command = %description,
+            elapsed_ms,
+            exit_code = ?output.status.code(),
+            "subprocess finished"
+        ),
+        Err(err) => tracing::warn!(
+            command = %description,
+            elapsed_ms,
+            error = %err,
+            "subprocess failed to start"
+        ),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe_command, verbosity_filter};
+    use std::process::Command;
+
+    #[test]
+    fn describe_command_joins_program_and_args() {
+        let mut command = Command::new("gh");
+        command.args(["pr", "view", "42", "--repo", "org/reviewer"]);
+        assert_eq!(
+            describe_command(&command),
+            "gh pr view 42 --repo org/reviewer"
+        );
+        assert_eq!(verbosity_filter(2), "debug");
+    }
+}
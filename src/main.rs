@@ -1,11 +1,33 @@
 mod agent;
+mod ai_api;
+mod audit;
+mod auth;
+mod checklist;
+mod client;
+mod clone_cache;
 mod config;
+mod core;
 mod daemon;
+mod dates;
+mod deps;
 mod diff;
+mod drafts;
+mod editor;
 mod filters;
 mod gh;
+mod gh_version;
+mod global_search;
 mod harness;
+mod logging;
+mod notify;
+mod private_fs;
+mod process;
 mod repos;
+mod review_results;
+mod run_artifacts;
+mod session_summary;
+mod shell_escape;
+mod snooze;
 mod terminal;
 mod tui;
 
@@ -34,6 +56,14 @@ struct Args {
     )]
     version: bool,
 
+    /// Log diagnostics (repeat for more detail). `REVIEWER_LOG` overrides the filter.
+    #[arg(long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Write logs to this file instead of stderr (the TUI defaults to reviewer.log in the config dir)
+    #[arg(long, value_name = "PATH", global = true)]
+    log_file: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 
@@ -57,6 +87,14 @@ struct Args {
     /// Save excluded directories to config
     #[arg(long)]
     save_exclude: bool,
+
+    /// Browse without approving, commenting, merging, or closing anything (also `read_only` in config)
+    #[arg(long)]
+    read_only: bool,
+
+    /// Open the TUI on a saved view from `views` in config
+    #[arg(long, value_name = "NAME")]
+    view: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -67,6 +105,91 @@ enum Commands {
     Harness(harness::HarnessArgs),
     /// Trigger an AI review session for a specific PR
     Trigger(TriggerArgs),
+    /// Export or import your pending (unsubmitted) review comments as markdown
+    Draft(DraftArgs),
+    /// Print a PR's diff to stdout
+    Diff(DiffArgs),
+    /// Print a PR's CI checks, exiting non-zero unless they all passed
+    Checks(ChecksArgs),
+}
+
+#[derive(Parser)]
+struct ChecksArgs {
+    /// PR URL or shorthand, e.g. org/repo#123
+    #[arg(value_name = "PR")]
+    target: String,
+    /// Poll until no check is pending, then exit with the result
+    #[arg(long)]
+    watch: bool,
+    /// Seconds between polls with --watch
+    #[arg(long, value_name = "SECONDS", default_value_t = 10)]
+    interval: u64,
+}
+
+#[derive(Parser)]
+struct DiffArgs {
+    /// PR URL or shorthand, e.g. org/repo#123
+    #[arg(value_name = "PR")]
+    target: String,
+    /// Render through delta (the default when stdout is a terminal and delta is installed)
+    #[arg(long, conflicts_with_all = ["raw", "stat"])]
+    delta: bool,
+    /// Print the unified diff as-is (the default when piped)
+    #[arg(long, conflicts_with = "stat")]
+    raw: bool,
+    /// Print added and removed line counts per file
+    #[arg(long)]
+    stat: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffFormat {
+    Delta,
+    Raw,
+    Stat,
+}
+
+impl DiffArgs {
+    /// The requested format; without a flag, delta for a terminal that has it, else raw.
+    fn format(&self, stdout_is_terminal: bool, delta_available: bool) -> DiffFormat {
+        if self.stat {
+            DiffFormat::Stat
+        } else if self.raw {
+            DiffFormat::Raw
+        } else if self.delta || (stdout_is_terminal && delta_available) {
+            DiffFormat::Delta
+        } else {
+            DiffFormat::Raw
+        }
+    }
+}
+
+#[derive(Parser)]
+struct DraftArgs {
+    #[command(subcommand)]
+    command: DraftCommand,
+}
+
+#[derive(Subcommand)]
+enum DraftCommand {
+    /// Write your pending review on a PR to a markdown file
+    Export {
+        /// PR URL or shorthand, e.g. org/repo#123
+        #[arg(value_name = "PR")]
+        target: String,
+        /// Output file (defaults to drafts/<owner>-<repo>-<number>.md in the config dir)
+        #[arg(long, short, value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Recreate a pending review on a PR from an exported markdown file
+    Import {
+        /// PR URL or shorthand, e.g. org/repo#123
+        #[arg(value_name = "PR")]
+        target: String,
+        /// Input file (defaults to the export location)
+        #[arg(long, short, value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
 }
 
 #[derive(Parser)]
@@ -90,6 +213,8 @@ enum DaemonCommand {
     },
     /// Show daemon status and counters
     Status,
+    /// Launch all reviews queued by `daemon.batch` now
+    Flush,
 }
 
 #[derive(Parser)]
@@ -132,6 +257,27 @@ pub fn fetch_my_prs(
     )
 }
 
+pub fn fetch_my_approvals(
+    username: &str,
+    include_drafts: bool,
+    after: Option<&str>,
+    exclude_users: &[String],
+) -> gh::PullRequestPage {
+    filter_excluded_pr_authors(
+        gh::search_my_approvals(username, include_drafts, after, exclude_users),
+        exclude_users,
+    )
+}
+
+/// Dependency update PRs, sorted so PRs bumping the same package sit together.
+pub fn fetch_dependency_prs(username: &str, include_drafts: bool) -> gh::PullRequestPage {
+    let mut page = gh::search_dependency_prs(username, include_drafts);
+    page.prs.retain(|pr| deps::is_dependency_bot(&pr.author));
+    page.prs
+        .sort_by_cached_key(|pr| (deps::group_key(pr), pr.repo_name.clone(), pr.number));
+    page
+}
+
 pub fn fetch_watching_prs(
     repos_root: &Path,
     username: &str,
@@ -151,12 +297,12 @@ pub fn fetch_watching_prs(
         }
     };
 
-    let prs = daemon::list_watched_prs(&cfg, repos_root, username, include_drafts);
+    let (prs, errors) = core::list_watched_prs(&cfg, repos_root, username, include_drafts);
     filter_excluded_pr_authors(
         gh::PullRequestPage {
             prs,
-            end_cursor: None,
-            has_next_page: false,
+            errors,
+            ..gh::PullRequestPage::default()
         },
         exclude_users,
     )
@@ -272,13 +418,15 @@ fn resolve_tui_repos_root(cfg: &config::Config, root_override: Option<PathBuf>)
 }
 
 fn run_tui(
-    ai: config::AiConfig,
+    cfg: &config::Config,
     repos_root: PathBuf,
     username: String,
     include_drafts: bool,
     my_mode: bool,
-    exclude_users: Vec<String>,
+    read_only: bool,
+    view: Option<&str>,
 ) -> Result<()> {
+    let view = view.map(|name| cfg.view(name).cloned()).transpose()?;
     println!("Launching TUI...");
     let mode = if my_mode {
         tui::AppMode::MyPrs
@@ -286,12 +434,13 @@ fn run_tui(
         tui::AppMode::Review
     };
     tui::run(
+        cfg,
         repos_root,
         username,
         include_drafts,
-        ai,
         mode,
-        exclude_users,
+        read_only,
+        view,
     )?;
 
     Ok(())
@@ -307,12 +456,48 @@ fn print_daemon_status(cfg: &config::Config) {
         status.only_new_prs_on_start
     );
     println!("State file: {}", status.state_path.display());
+    println!("Run artifacts: {}", status.runs_dir.display());
+    match status.running_pid {
+        Some(pid) => println!("Running daemon PID: {}", pid),
+        None => println!("Running daemon PID: none"),
+    }
     println!("Tracked PRs: {}", status.reviewed_count);
     println!("  Triggered successfully: {}", status.success_count);
     println!("  Failed to trigger: {}", status.failed_count);
+    for failed in &status.failed_triggers {
+        println!(
+            "    - {}: {}",
+            failed.pr,
+            failed.error.as_deref().unwrap_or("unknown error")
+        );
+        if let Some(run_dir) = &failed.run_dir {
+            println!("      run: {}", run_dir.display());
+        }
+    }
+    println!("  Skipped as trivial by triage: {}", status.skipped_count);
+    println!("  Queued for next batch: {}", status.queued_count);
+    if !status.batch_times.is_empty() {
+        println!("Batch times: {}", status.batch_times.join(", "));
+        match status.last_flush_at {
+            Some(last_flush) => println!(
+                "Last batch flush: {}",
+                dates::absolute(&cfg.dates, &last_flush)
+            ),
+            None => println!("Last batch flush: never"),
+        }
+    }
     println!("  Seeded (already open on init): {}", status.seeded_count);
+    if status.escalation_repos.is_empty() {
+        println!("Escalation: off");
+    } else {
+        println!(
+            "Escalation: {} ({} PRs nudged)",
+            status.escalation_repos.join(", "),
+            status.nudged_count
+        );
+    }
     if let Some(last_poll) = status.last_poll_at {
-        println!("Last poll: {}", last_poll);
+        println!("Last poll: {}", dates::absolute(&cfg.dates, &last_poll));
     } else {
         println!("Last poll: never");
     }
@@ -379,6 +564,9 @@ fn run_daemon_command(
         DaemonCommand::Run { once, interval } => {
             let username = gh::get_current_user()?;
             println!("Authenticated as: {}\n", username);
+            if let Some(warning) = gh_version::startup_warning() {
+                eprintln!("Warning: {}", warning);
+            }
             let repos_root = resolve_repos_root(cfg, root_override)?;
             if !cfg.daemon.initialized {
                 println!("Daemon not initialized. Starting first-time setup...");
@@ -386,6 +574,19 @@ fn run_daemon_command(
             }
             daemon::run(cfg, &repos_root, &username, interval, once)
         }
+        DaemonCommand::Flush => {
+            if !cfg.daemon.initialized {
+                bail!("Daemon is not initialized. Run `reviewer daemon init` first.");
+            }
+            let username = gh::get_current_user()?;
+            let repos_root = resolve_repos_root(cfg, root_override)?;
+            let summary = daemon::flush(cfg, &repos_root, &username)?;
+            println!(
+                "Flush complete: {} triggered, {} skipped, {} failed.",
+                summary.triggered, summary.skipped, summary.failed
+            );
+            Ok(())
+        }
     }
 }
 
@@ -571,13 +772,8 @@ fn resolve_trigger_repo(
 
     let repo_name =
         expected_repo.context("Either a target repo or --repo-path must be provided")?;
-    for candidate in trigger_repo_candidates(repos_root, repo_name) {
-        if !candidate.is_dir() || !candidate.join(".git").exists() {
-            continue;
-        }
-        if gh::repo_name_with_owner(&candidate).as_deref() == Some(repo_name) {
-            return Ok((candidate, repo_name.to_string()));
-        }
+    if let Some(candidate) = find_local_clone(repos_root, repo_name) {
+        return Ok((candidate, repo_name.to_string()));
     }
 
     bail!(
@@ -587,6 +783,17 @@ fn resolve_trigger_repo(
     );
 }
 
+/// A clone of `repo_name` under `repos_root`, checked against its GitHub remote.
+fn find_local_clone(repos_root: &Path, repo_name: &str) -> Option<PathBuf> {
+    trigger_repo_candidates(repos_root, repo_name)
+        .into_iter()
+        .find(|candidate| {
+            candidate.is_dir()
+                && candidate.join(".git").exists()
+                && gh::repo_name_with_owner(candidate).as_deref() == Some(repo_name)
+        })
+}
+
 fn run_trigger_command(
     cfg: &mut config::Config,
     root_override: Option<PathBuf>,
@@ -616,6 +823,175 @@ fn run_trigger_command(
     Ok(())
 }
 
+fn run_draft_command(draft_args: DraftArgs) -> Result<()> {
+    let (target, file, export) = match draft_args.command {
+        DraftCommand::Export { target, file } => (target, file, true),
+        DraftCommand::Import { target, file } => (target, file, false),
+    };
+    let target = parse_trigger_target(&target)?;
+    let path = file.unwrap_or_else(|| drafts::default_path(&target.repo, target.pr));
+    let cwd = std::env::current_dir().context("Failed to read current directory")?;
+    let pr = gh::fetch_pr_for_review(&cwd, &target.repo, target.pr)?;
+
+    if export {
+        let draft = gh::get_pending_review(&pr)?.with_context(|| {
+            format!(
+                "No pending review of yours on {}#{}",
+                target.repo, target.pr
+            )
+        })?;
+        if let Some(parent) = path.parent() {
+            private_fs::create_dir_all(parent)?;
+        }
+        private_fs::write_atomic(&path, draft.to_markdown(&target.repo, target.pr))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!(
+            "Exported {} pending comment(s) to {}",
+            draft.comments.len(),
+            path.display()
+        );
+    } else {
+        let markdown = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let draft = drafts::DraftReview::parse_markdown(&markdown)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        gh::create_pending_review(&pr, &draft)?;
+        println!(
+            "Imported {} comment(s) into a pending review on {}#{}",
+            draft.comments.len(),
+            target.repo,
+            target.pr
+        );
+    }
+    Ok(())
+}
+
+/// Print a PR's diff the way the TUI fetches it: `gh pr diff`, or for PRs too large for that, a
+/// local `git diff` in a clone under the repos root (or the clone cache).
+fn run_diff_command(
+    cfg: &config::Config,
+    root_override: Option<PathBuf>,
+    diff_args: DiffArgs,
+) -> Result<()> {
+    use std::io::IsTerminal;
+
+    let target = parse_trigger_target(&diff_args.target)?;
+    let cwd = std::env::current_dir().context("Failed to read current directory")?;
+    let mut pr = gh::fetch_pr_for_review(&cwd, &target.repo, target.pr)?;
+    pr.repo_path = root_override
+        .or_else(|| cfg.repos_root.as_ref().map(PathBuf::from))
+        .and_then(|root| find_local_clone(&root, &target.repo))
+        .unwrap_or_default();
+    let diff = gh::get_pr_diff(&pr)?;
+
+    let format = diff_args.format(io::stdout().is_terminal(), diff::delta_available());
+    let output = match format {
+        DiffFormat::Stat => diff::diff_stat(&diff),
+        DiffFormat::Raw => diff,
+        DiffFormat::Delta => {
+            if !diff::delta_available() {
+                bail!("delta is not installed; use --raw or install git-delta");
+            }
+            let width = crossterm::terminal::size().map_or(120, |(width, _)| width);
+            match diff::process_with_delta(&diff, width) {
+                Some(rendered) => rendered,
+                None => {
+                    eprintln!("delta could not render this diff; printing it raw");
+                    diff
+                }
+            }
+        }
+    };
+    let mut stdout = io::stdout().lock();
+    match stdout.write_all(output.as_bytes()) {
+        // Piped into `head` and the like
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        written => written.context("Failed to write the diff"),
+    }
+}
+
+/// Exit code for checks that are still running, as `gh pr checks` uses
+const CHECKS_PENDING_EXIT: i32 = 8;
+
+/// One line per check: state, name, how long it ran, and its link.
+fn format_checks(checks: &[gh::CheckStatus], now: chrono::DateTime<chrono::Utc>) -> String {
+    let name_width = checks
+        .iter()
+        .map(|check| check.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    checks
+        .iter()
+        .map(|check| {
+            let (symbol, word) = match check.status {
+                gh::CheckState::Success => ("✓", "pass"),
+                gh::CheckState::Failure => ("✗", "fail"),
+                gh::CheckState::Pending => ("●", "pending"),
+                gh::CheckState::Neutral => ("-", "skipped"),
+            };
+            let duration = check.duration(now).map(dates::duration).unwrap_or_default();
+            let line = format!(
+                "{} {:<7}  {:<name_width$}  {:>7}  {}",
+                symbol,
+                word,
+                check.name,
+                duration,
+                check.link.as_deref().unwrap_or_default()
+            );
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// Print a PR's checks and exit with their rollup: 0 when all passed, 1 on a failure, and
+/// [`CHECKS_PENDING_EXIT`] while some are running. `--watch` polls until none are pending.
+fn run_checks_command(checks_args: ChecksArgs) -> Result<()> {
+    let target = parse_trigger_target(&checks_args.target)?;
+    let cwd = std::env::current_dir().context("Failed to read current directory")?;
+    let pr = gh::fetch_pr_for_review(&cwd, &target.repo, target.pr)?;
+    let interval = std::time::Duration::from_secs(checks_args.interval.max(1));
+
+    let mut last_progress = None;
+    let (checks, rollup) = loop {
+        let checks = gh::get_pr_checks(&pr)?;
+        let rollup = gh::checks_rollup(&checks);
+        if !checks_args.watch || rollup != Some(gh::CheckState::Pending) {
+            break (checks, rollup);
+        }
+        let done = checks
+            .iter()
+            .filter(|check| check.status != gh::CheckState::Pending)
+            .count();
+        if last_progress != Some(done) {
+            eprintln!(
+                "{}/{} checks done on {}#{}, waiting...",
+                done,
+                checks.len(),
+                target.repo,
+                target.pr
+            );
+            last_progress = Some(done);
+        }
+        std::thread::sleep(interval);
+    };
+
+    let Some(rollup) = rollup else {
+        bail!("No checks reported for {}#{}", target.repo, target.pr);
+    };
+    print!("{}", format_checks(&checks, chrono::Utc::now()));
+    let (summary, code) = match rollup {
+        gh::CheckState::Failure => ("Checks failed", 1),
+        gh::CheckState::Pending => ("Checks still running", CHECKS_PENDING_EXIT),
+        gh::CheckState::Success | gh::CheckState::Neutral => ("All checks passed", 0),
+    };
+    println!("{} on {}#{}", summary, target.repo, target.pr);
+    if code != 0 {
+        io::stdout().flush()?;
+        std::process::exit(code);
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
     if args.version {
@@ -623,7 +999,23 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    let log_file = args.log_file.clone().or_else(|| {
+        args.command
+            .is_none()
+            .then(|| config::config_dir().join("reviewer.log"))
+    });
+    logging::init(args.verbose, log_file.as_deref())?;
+    private_fs::warn_if_exposed(&config::config_dir());
+    if let Some(log_file) = log_file.as_deref() {
+        if !log_file.starts_with(config::config_dir()) {
+            private_fs::warn_if_exposed(log_file);
+        }
+    }
+
     let mut cfg = config::load_config()?;
+    clone_cache::configure(&cfg.clone_cache);
+    gh::configure_fetch(&cfg.fetch);
+    auth::configure(&cfg.auth);
     let effective_exclude = merge_excludes(&cfg.exclude, &args.exclude);
     if args.save_exclude && !args.exclude.is_empty() {
         cfg.exclude = effective_exclude.clone();
@@ -639,17 +1031,21 @@ fn main() -> Result<()> {
         Some(Commands::Trigger(trigger_args)) => {
             run_trigger_command(&mut cfg, args.root, trigger_args)
         }
+        Some(Commands::Draft(draft_args)) => run_draft_command(draft_args),
+        Some(Commands::Diff(diff_args)) => run_diff_command(&cfg, args.root, diff_args),
+        Some(Commands::Checks(checks_args)) => run_checks_command(checks_args),
         None => {
             let username = gh::get_current_user()?;
             println!("Authenticated as: {}\n", username);
             let repos_root = resolve_tui_repos_root(&cfg, args.root)?;
             run_tui(
-                cfg.ai.clone(),
+                &cfg,
                 repos_root,
                 username,
                 args.drafts,
                 args.my,
-                cfg.exclude_users.clone(),
+                args.read_only,
+                args.view.as_deref(),
             )
         }
     }
@@ -657,7 +1053,10 @@ fn main() -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_trigger_target, resolve_trigger_args, Args, Commands};
+    use super::{
+        format_checks, parse_trigger_target, resolve_trigger_args, Args, Commands, DiffFormat,
+    };
+    use crate::gh;
     use clap::Parser;
 
     #[test]
@@ -696,6 +1095,69 @@ mod tests {
         assert!(parsed.is_ok());
     }
 
+    #[test]
+    fn diff_defaults_to_delta_only_on_a_terminal_that_has_it() {
+        let diff_args = |extra: &[&str]| {
+            let parsed = Args::try_parse_from(
+                ["reviewer", "diff", "org/repo#123"]
+                    .iter()
+                    .chain(extra)
+                    .copied(),
+            );
+            match parsed.map(|args| args.command) {
+                Ok(Some(Commands::Diff(diff_args))) => Ok(diff_args),
+                Ok(_) => panic!("expected diff command"),
+                Err(err) => Err(err),
+            }
+        };
+
+        let plain = diff_args(&[]).unwrap();
+        assert_eq!(plain.format(true, true), DiffFormat::Delta);
+        assert_eq!(plain.format(false, true), DiffFormat::Raw);
+        assert_eq!(plain.format(true, false), DiffFormat::Raw);
+        assert_eq!(
+            diff_args(&["--delta"]).unwrap().format(false, true),
+            DiffFormat::Delta
+        );
+        assert_eq!(
+            diff_args(&["--raw"]).unwrap().format(true, true),
+            DiffFormat::Raw
+        );
+        assert_eq!(
+            diff_args(&["--stat"]).unwrap().format(true, true),
+            DiffFormat::Stat
+        );
+        assert!(diff_args(&["--raw", "--stat"]).is_err());
+    }
+
+    #[test]
+    fn checks_lists_state_name_duration_and_link() {
+        let now = chrono::Utc::now();
+        let check = |name: &str, status: gh::CheckState, link: Option<&str>| gh::CheckStatus {
+            name: name.to_string(),
+            status,
+            started_at: Some(now - chrono::Duration::seconds(75)),
+            completed_at: None,
+            link: link.map(str::to_string),
+        };
+        let checks = [
+            check("build", gh::CheckState::Success, Some("https://ci/1")),
+            check("e2e-tests", gh::CheckState::Pending, None),
+        ];
+        assert_eq!(
+            format_checks(&checks, now),
+            "✓ pass     build       1m 15s  https://ci/1\n● pending  e2e-tests   1m 15s\n"
+        );
+
+        let parsed =
+            Args::try_parse_from(["reviewer", "checks", "org/repo#123", "--watch"]).unwrap();
+        let Some(Commands::Checks(checks_args)) = parsed.command else {
+            panic!("expected checks command");
+        };
+        assert!(checks_args.watch);
+        assert_eq!(checks_args.interval, 10);
+    }
+
     #[test]
     fn parse_trigger_target_accepts_github_url() {
         let target = parse_trigger_target("https://github.com/nvidia-lpu/cyborg/pull/199").unwrap();
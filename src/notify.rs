@@ -0,0 +1,43 @@
+//! Desktop notifications for the daemon: Notification Center through `osascript` on macOS and
+//! `notify-send` on Linux.
+
+use crate::logging::CommandExt;
+use anyhow::{Context, Result};
+use std::process::Command;
+
+/// Show a desktop notification. Title and body are passed as arguments, never as script text.
+pub fn send(title: &str, body: &str) -> Result<()> {
+    let mut command = notification_command(title, body)?;
+    let output = command
+        .logged_output()
+        .context("Failed to send desktop notification")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to send desktop notification: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn notification_command(title: &str, body: &str) -> Result<Command> {
+    let script = r#"on run argv
+            display notification (item 2 of argv) with title (item 1 of argv)
+        end run"#;
+    let mut command = Command::new("osascript");
+    command.args(["-e", script, title, body]);
+    Ok(command)
+}
+
+#[cfg(target_os = "linux")]
+fn notification_command(title: &str, body: &str) -> Result<Command> {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name=reviewer", "--", title, body]);
+    Ok(command)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn notification_command(_title: &str, _body: &str) -> Result<Command> {
+    anyhow::bail!("Desktop notifications are only supported on macOS and Linux")
+}
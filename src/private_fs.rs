@@ -0,0 +1,296 @@
+use serde::de::DeserializeOwned;
+use std::ffi::OsString;
+use std::fs::{DirBuilder, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Numbers [`write_atomic`]'s temp files, so threads of one process never share one.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Config, state, logs, drafts and prompts can hold private repo data, so reviewer creates them
+/// for the owner only: directories `0700`, files `0600`. Elsewhere than unix these are plain
+/// `std::fs` calls.
+pub fn create_dir_all(path: &Path) -> io::Result<()> {
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(path)
+}
+
+/// `OpenOptions` that create missing files as `0600`.
+pub fn open_options() -> OpenOptions {
+    let mut options = OpenOptions::new();
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+}
+
+/// Like `std::fs::write`, creating the file as `0600`. An existing file keeps its mode;
+/// [`exposed_paths`] reports those that are too open.
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut file = open_options()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    file.write_all(contents.as_ref())
+}
+
+/// The file `path` ends up at through any symlinks; `path` itself when it is not a link. A
+/// dangling link resolves to where it points, so writing creates its target.
+fn symlink_target(path: &Path) -> PathBuf {
+    let is_link = std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink());
+    if !is_link {
+        return path.to_path_buf();
+    }
+    std::fs::canonicalize(path)
+        .or_else(|_| {
+            std::fs::read_link(path).map(|target| {
+                path.parent()
+                    .map_or_else(|| target.clone(), |parent| parent.join(&target))
+            })
+        })
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Where [`write_atomic`] keeps the previous version of `path`: `<name>.bak` beside it.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Replace `path` so a crash or full disk leaves either the old or the new contents, never a
+/// truncated mix: write a `0600` temp file beside it, fsync, copy the old file to
+/// [`backup_path`], then rename over it. A symlinked `path` (say, a config kept in a dotfiles
+/// repo) stays a link: the file it points to is the one replaced.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = &symlink_target(path);
+    let mut temp_name = OsString::from(".");
+    temp_name.push(path.file_name().unwrap_or_default());
+    temp_name.push(format!(
+        ".tmp-{}-{}",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp = path.with_file_name(temp_name);
+
+    let written = open_options()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&temp)
+        .and_then(|mut file| {
+            file.write_all(contents.as_ref())?;
+            file.sync_all()
+        })
+        .and_then(|()| match std::fs::copy(path, backup_path(path)) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            copied => copied.map(|_| ()),
+        })
+        .and_then(|()| std::fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp);
+        return written;
+    }
+    // Persist the rename itself; not every platform can open a directory for this
+    #[cfg(unix)]
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        let _ = File::open(parent).and_then(|dir| dir.sync_all());
+    }
+    Ok(())
+}
+
+/// Parse the JSON at `path`, falling back to its [`backup_path`] when the file is missing or
+/// unreadable.
+pub fn read_json_or_backup<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+    };
+    read(path).or_else(|| {
+        let backup = read(&backup_path(path));
+        if backup.is_some() && path.exists() {
+            tracing::warn!(path = %path.display(), "unreadable, using its backup");
+        }
+        backup
+    })
+}
+
+/// `dir` and its entries other users can read, with their modes. Entries are only checked when
+/// others can get into `dir` at all.
+#[cfg(unix)]
+pub fn exposed_paths(dir: &Path) -> Vec<(PathBuf, u32)> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = |path: &Path| {
+        std::fs::metadata(path)
+            .ok()
+            .map(|meta| meta.permissions().mode() & 0o777)
+    };
+    let Some(dir_mode) = mode(dir) else {
+        return Vec::new();
+    };
+    let mut exposed = Vec::new();
+    if dir_mode & 0o044 != 0 {
+        exposed.push((dir.to_path_buf(), dir_mode));
+    }
+    if dir_mode & 0o011 == 0 {
+        return exposed;
+    }
+    let mut entries = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    entries.sort();
+    for path in entries {
+        if let Some(mode) = mode(&path).filter(|mode| mode & 0o044 != 0) {
+            exposed.push((path, mode));
+        }
+    }
+    exposed
+}
+
+#[cfg(not(unix))]
+pub fn exposed_paths(_dir: &Path) -> Vec<(PathBuf, u32)> {
+    Vec::new()
+}
+
+/// Startup warning for a config dir (or log file) that group or world users can read.
+pub fn warn_if_exposed(path: &Path) {
+    let exposed = if path.is_dir() {
+        exposed_paths(path)
+    } else {
+        exposed_file(path).into_iter().collect()
+    };
+    for (path, mode) in exposed {
+        eprintln!(
+            "Warning: {} is readable by other users (mode {:o}); it may hold private repo data. \
+             Run `chmod {} {}` to restrict it.",
+            path.display(),
+            mode,
+            if path.is_dir() { "700" } else { "600" },
+            path.display()
+        );
+    }
+}
+
+#[cfg(unix)]
+fn exposed_file(path: &Path) -> Option<(PathBuf, u32)> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode() & 0o777;
+    (mode & 0o044 != 0).then(|| (path.to_path_buf(), mode))
+}
+
+#[cfg(not(unix))]
+fn exposed_file(_path: &Path) -> Option<(PathBuf, u32)> {
+    None
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{
+        backup_path, create_dir_all, exposed_paths, read_json_or_backup, write, write_atomic,
+    };
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn creates_owner_only_files_and_reports_open_ones() {
+        let root =
+            std::env::temp_dir().join(format!("reviewer-private-fs-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let dir = root.join("reviewer");
+        create_dir_all(&dir).unwrap();
+        write(&dir.join("state.json"), "{}").unwrap();
+        let mode =
+            |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&dir), 0o700);
+        assert_eq!(mode(&dir.join("state.json")), 0o600);
+        assert!(exposed_paths(&dir).is_empty());
+
+        // A readable file behind a private dir is out of reach; once the dir opens up it is not
+        std::fs::write(dir.join("config.json"), "{}").unwrap();
+        let public = std::fs::Permissions::from_mode(0o644);
+        std::fs::set_permissions(dir.join("config.json"), public).unwrap();
+        assert!(exposed_paths(&dir).is_empty());
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(
+            exposed_paths(&dir),
+            vec![(dir.clone(), 0o755), (dir.join("config.json"), 0o644)]
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn atomic_writes_keep_the_previous_version_as_a_backup() {
+        let dir =
+            std::env::temp_dir().join(format!("reviewer-atomic-write-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+
+        write_atomic(&path, r#"{"v": 1}"#).unwrap();
+        assert!(!backup_path(&path).exists());
+        write_atomic(&path, r#"{"v": 2}"#).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"{"v": 2}"#);
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path)).unwrap(),
+            r#"{"v": 1}"#
+        );
+        let mut names = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["state.json", "state.json.bak"]);
+
+        // A truncated file (e.g. written by an older version) is read from its backup
+        std::fs::write(&path, r#"{"v": "#).unwrap();
+        let value: serde_json::Value = read_json_or_backup(&path).unwrap();
+        assert_eq!(value["v"], 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn atomic_writes_go_through_symlinks_and_never_share_temp_files() {
+        let dir =
+            std::env::temp_dir().join(format!("reviewer-atomic-link-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        create_dir_all(&dir.join("dotfiles")).unwrap();
+        let target = dir.join("dotfiles").join("config.json");
+        let link = dir.join("config.json");
+        std::fs::write(&target, r#"{"v": 1}"#).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_atomic(&link, r#"{"v": 2}"#).unwrap();
+        assert!(std::fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), r#"{"v": 2}"#);
+        assert!(backup_path(&target).exists());
+
+        // Threads saving the same file at once each finish with a whole file
+        std::thread::scope(|scope| {
+            for n in 0..8 {
+                let link = &link;
+                scope.spawn(move || write_atomic(link, format!(r#"{{"v": {n}}}"#)).unwrap());
+            }
+        });
+        let value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&target).unwrap()).unwrap();
+        assert!(value["v"].is_u64());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,215 @@
+use std::io::{self, Write};
+use std::process::{Command, Output, Stdio};
+
+/// Runs the `gh`/`git` subprocesses the rest of the crate builds. Production code always
+/// uses `SystemRunner`; tests install a `ReplayRunner` with canned output.
+pub trait CommandRunner: Send + Sync {
+    fn output(&self, command: &mut Command, stdin: Option<&[u8]>) -> io::Result<Output>;
+}
+
+/// Executes commands for real
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn output(&self, command: &mut Command, stdin: Option<&[u8]>) -> io::Result<Output> {
+        let Some(input) = stdin else {
+            return command.output();
+        };
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut pipe) = child.stdin.take() {
+            pipe.write_all(input)?;
+        }
+        child.wait_with_output()
+    }
+}
+
+/// Run `command` (feeding `stdin`, if any) through the active runner.
+pub fn output(command: &mut Command, stdin: Option<&[u8]>) -> io::Result<Output> {
+    crate::auth::apply(command);
+    #[cfg(test)]
+    if let Some(runner) = replay::installed() {
+        return runner.output(command, stdin);
+    }
+    SystemRunner.output(command, stdin)
+}
+
+/// `program arg1 arg2 ...`, as used in logs and fixture matching.
+pub fn describe_command(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|part| part.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+pub mod replay {
+    use super::{describe_command, CommandRunner};
+    use std::io;
+    use std::process::{Command, ExitStatus, Output};
+    use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+    static INSTALLED: RwLock<Option<Arc<ReplayRunner>>> = RwLock::new(None);
+    /// Runners are process-wide (rayon workers must see them), so tests using one run serially.
+    static EXCLUSIVE: Mutex<()> = Mutex::new(());
+
+    pub(super) fn installed() -> Option<Arc<ReplayRunner>> {
+        INSTALLED.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// One recorded invocation
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Call {
+        pub command: String,
+        pub stdin: Option<String>,
+    }
+
+    struct Fixture {
+        prefix: String,
+        exit_code: i32,
+        stdout: Vec<u8>,
+        stderr: Vec<u8>,
+    }
+
+    /// Replays canned output for commands whose command line starts with a fixture's prefix,
+    /// and records every invocation. Later fixtures win, and a command with no fixture fails
+    /// to start, so a test never reaches the network.
+    #[derive(Default)]
+    pub struct ReplayRunner {
+        fixtures: Mutex<Vec<Fixture>>,
+        calls: Mutex<Vec<Call>>,
+    }
+
+    impl ReplayRunner {
+        pub fn new() -> Arc<Self> {
+            Arc::new(Self::default())
+        }
+
+        pub fn reply(&self, prefix: &str, stdout: &str) -> &Self {
+            self.reply_with(prefix, 0, stdout, "")
+        }
+
+        pub fn fail(&self, prefix: &str, stderr: &str) -> &Self {
+            self.reply_with(prefix, 1, "", stderr)
+        }
+
+        pub fn reply_with(
+            &self,
+            prefix: &str,
+            exit_code: i32,
+            stdout: &str,
+            stderr: &str,
+        ) -> &Self {
+            self.fixtures.lock().unwrap().push(Fixture {
+                prefix: prefix.to_string(),
+                exit_code,
+                stdout: stdout.as_bytes().to_vec(),
+                stderr: stderr.as_bytes().to_vec(),
+            });
+            self
+        }
+
+        pub fn calls(&self) -> Vec<Call> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        pub fn commands(&self) -> Vec<String> {
+            self.calls().into_iter().map(|call| call.command).collect()
+        }
+
+        /// Make this runner handle every subprocess until the guard drops.
+        pub fn install(self: &Arc<Self>) -> InstallGuard {
+            let lock = EXCLUSIVE.lock().unwrap_or_else(|e| e.into_inner());
+            *INSTALLED.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::clone(self));
+            InstallGuard { _lock: lock }
+        }
+    }
+
+    impl CommandRunner for ReplayRunner {
+        fn output(&self, command: &mut Command, stdin: Option<&[u8]>) -> io::Result<Output> {
+            let line = describe_command(command);
+            self.calls.lock().unwrap().push(Call {
+                command: line.clone(),
+                stdin: stdin.map(|input| String::from_utf8_lossy(input).into_owned()),
+            });
+            let fixtures = self.fixtures.lock().unwrap();
+            let fixture = fixtures
+                .iter()
+                .rev()
+                .find(|fixture| line.starts_with(&fixture.prefix))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("no fixture for `{}`", line),
+                    )
+                })?;
+            Ok(Output {
+                status: exit_status(fixture.exit_code),
+                stdout: fixture.stdout.clone(),
+                stderr: fixture.stderr.clone(),
+            })
+        }
+    }
+
+    pub struct InstallGuard {
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl Drop for InstallGuard {
+        fn drop(&mut self) {
+            *INSTALLED.write().unwrap_or_else(|e| e.into_inner()) = None;
+        }
+    }
+
+    #[cfg(unix)]
+    fn exit_status(code: i32) -> ExitStatus {
+        std::os::unix::process::ExitStatusExt::from_raw(code << 8)
+    }
+
+    #[cfg(windows)]
+    fn exit_status(code: i32) -> ExitStatus {
+        std::os::windows::process::ExitStatusExt::from_raw(code as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::output;
+    use super::replay::ReplayRunner;
+    use std::process::Command;
+
+    #[test]
+    fn replay_runner_matches_prefixes_and_records_calls() {
+        let runner = ReplayRunner::new();
+        runner
+            .reply("gh pr list", "[]")
+            .fail("gh pr merge 7", "not mergeable");
+        let _guard = runner.install();
+
+        let listed = output(
+            Command::new("gh").args(["pr", "list", "--limit", "5"]),
+            None,
+        )
+        .unwrap();
+        assert!(listed.status.success());
+        assert_eq!(listed.stdout, b"[]");
+
+        let merged = output(
+            Command::new("gh").args(["pr", "merge", "7", "--squash"]),
+            Some(b"{}"),
+        )
+        .unwrap();
+        assert!(!merged.status.success());
+        assert_eq!(merged.stderr, b"not mergeable");
+
+        assert!(output(&mut Command::new("git"), None).is_err());
+        let calls = runner.calls();
+        assert_eq!(calls[1].command, "gh pr merge 7 --squash");
+        assert_eq!(calls[1].stdin.as_deref(), Some("{}"));
+        assert_eq!(calls[2].command, "git");
+    }
+}
@@ -1,8 +1,38 @@
 use crate::gh;
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
 use walkdir::WalkDir;
 
+/// How far the running [`scan_unique_repos`] is, for UIs that would otherwise sit silent
+/// through one `gh repo view` per clone.
+static PROGRESS: Mutex<Option<ScanProgress>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanProgress {
+    pub scanned: usize,
+    pub total: usize,
+}
+
+/// Progress of the repo scan in flight, if any.
+pub fn scan_progress() -> Option<ScanProgress> {
+    *PROGRESS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn set_progress(progress: Option<ScanProgress>) {
+    *PROGRESS.lock().unwrap_or_else(|e| e.into_inner()) = progress;
+}
+
+/// Clears the scan progress when the scan ends, however it ends.
+struct ProgressGuard;
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        set_progress(None);
+    }
+}
+
 fn is_git_repo(path: &Path) -> bool {
     path.join(".git").is_dir()
 }
@@ -94,13 +124,25 @@ where
 }
 
 pub fn scan_unique_repos(root: &Path, max_depth: usize, exclude: &[String]) -> RepoScanResult {
+    let started = Instant::now();
     let repo_paths = find_repos(root, max_depth, exclude);
 
+    let _guard = ProgressGuard;
+    set_progress(Some(ScanProgress {
+        scanned: 0,
+        total: repo_paths.len(),
+    }));
     let discovered: Vec<DiscoveredRepo> = repo_paths
         .par_iter()
-        .map(|path| DiscoveredRepo {
-            path: path.clone(),
-            name_with_owner: gh::repo_name_with_owner(path),
+        .map(|path| {
+            let repo = DiscoveredRepo {
+                path: path.clone(),
+                name_with_owner: gh::repo_name_with_owner(path),
+            };
+            if let Some(progress) = PROGRESS.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+                progress.scanned += 1;
+            }
+            repo
         })
         .collect();
 
@@ -109,15 +151,47 @@ pub fn scan_unique_repos(root: &Path, max_depth: usize, exclude: &[String]) -> R
         |repo| repo.logical_key(),
         |repo| repo.path.to_string_lossy().to_string(),
     );
+    tracing::debug!(
+        root = %root.display(),
+        found = repo_paths.len(),
+        unique = unique_repos.len(),
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "scanned repos"
+    );
 
     RepoScanResult { unique_repos }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{dedupe_by_key, DiscoveredRepo};
+    use super::{dedupe_by_key, scan_progress, scan_unique_repos, DiscoveredRepo};
+    use crate::process::replay::ReplayRunner;
     use std::path::PathBuf;
 
+    #[test]
+    fn scan_dedupes_clones_and_clears_its_progress() {
+        let root = std::env::temp_dir().join(format!("reviewer-repo-scan-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for clone in ["widgets", "widgets-copy", "vendor/old"] {
+            std::fs::create_dir_all(root.join(clone).join(".git")).unwrap();
+        }
+        let runner = ReplayRunner::new();
+        runner.reply("gh repo view", r#"{"nameWithOwner": "acme/widgets"}"#);
+        let _guard = runner.install();
+
+        let scan = scan_unique_repos(&root, 3, &["vendor".to_string()]);
+        assert_eq!(
+            scan.unique_repos,
+            vec![DiscoveredRepo {
+                path: root.join("widgets"),
+                name_with_owner: Some("acme/widgets".to_string()),
+            }]
+        );
+        assert_eq!(runner.commands().len(), 2);
+        assert_eq!(scan_progress(), None);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn dedupe_by_key_keeps_one_entry_per_key() {
         let items = vec![
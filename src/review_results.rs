@@ -0,0 +1,159 @@
+use crate::gh::{self, PullRequest};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// AI sessions write `review.json` and/or `review.md` here, relative to the PR worktree.
+pub const RESULTS_DIR: &str = ".reviewer";
+pub const REVIEW_JSON: &str = "review.json";
+pub const REVIEW_MD: &str = "review.md";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReviewFinding {
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub severity: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReviewFindings {
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub findings: Vec<ReviewFinding>,
+}
+
+/// Results loaded from a PR worktree
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewResults {
+    pub source: PathBuf,
+    pub modified: SystemTime,
+    pub summary: String,
+    pub findings: Vec<ReviewFinding>,
+}
+
+pub fn results_dir(working_dir: &Path) -> PathBuf {
+    working_dir.join(RESULTS_DIR)
+}
+
+pub fn render_findings_markdown(pr: &PullRequest, findings: &ReviewFindings) -> String {
+    let mut out = format!(
+        "# Review of {}#{}: {}\n\n",
+        pr.repo_name, pr.number, pr.title
+    );
+    if !findings.summary.trim().is_empty() {
+        out.push_str(findings.summary.trim());
+        out.push_str("\n\n");
+    }
+    if findings.findings.is_empty() {
+        out.push_str("No findings.\n");
+        return out;
+    }
+    out.push_str("## Findings\n\n");
+    for finding in &findings.findings {
+        let location = match (&finding.path, finding.line) {
+            (Some(path), Some(line)) => format!("`{}:{}` ", path, line),
+            (Some(path), None) => format!("`{}` ", path),
+            _ => String::new(),
+        };
+        let severity = if finding.severity.is_empty() {
+            String::new()
+        } else {
+            format!("**{}** ", finding.severity)
+        };
+        out.push_str(&format!(
+            "- {}{}{}\n",
+            severity,
+            location,
+            finding.message.trim()
+        ));
+    }
+    out
+}
+
+/// Pull a backticked `path:line` reference out of a markdown line.
+fn markdown_location(line: &str) -> Option<(String, u32)> {
+    line.split('`').skip(1).step_by(2).find_map(|token| {
+        let (path, line_number) = token.rsplit_once(':')?;
+        let line_number = line_number.parse().ok()?;
+        (!path.is_empty() && !path.contains(char::is_whitespace))
+            .then(|| (path.to_string(), line_number))
+    })
+}
+
+/// Treat each markdown line with a `path:line` reference as a finding.
+fn parse_markdown_findings(markdown: &str) -> Vec<ReviewFinding> {
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let (path, line_number) = markdown_location(line)?;
+            let message = line
+                .trim()
+                .trim_start_matches(['-', '*'])
+                .trim()
+                .to_string();
+            Some(ReviewFinding {
+                path: Some(path),
+                line: Some(line_number),
+                severity: String::new(),
+                message,
+            })
+        })
+        .collect()
+}
+
+fn load_results(dir: &Path) -> Option<ReviewResults> {
+    let json_path = dir.join(REVIEW_JSON);
+    let md_path = dir.join(REVIEW_MD);
+    let modified_at = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+    if let Some(findings) = std::fs::read(&json_path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<ReviewFindings>(&bytes).ok())
+    {
+        return Some(ReviewResults {
+            modified: modified_at(&json_path)?,
+            source: json_path,
+            summary: findings.summary,
+            findings: findings.findings,
+        });
+    }
+
+    let markdown = std::fs::read_to_string(&md_path).ok()?;
+    Some(ReviewResults {
+        modified: modified_at(&md_path)?,
+        findings: parse_markdown_findings(&markdown),
+        summary: markdown,
+        source: md_path,
+    })
+}
+
+/// Find the newest review results written to any of the PR's worktrees.
+pub fn find_for_pr(pr: &PullRequest, repos_root: &Path) -> Option<ReviewResults> {
+    gh::existing_pr_worktrees(pr, repos_root)
+        .iter()
+        .filter_map(|worktree| load_results(&results_dir(worktree)))
+        .max_by_key(|results| results.modified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_markdown_findings;
+
+    #[test]
+    fn parse_markdown_findings_reads_path_line_references() {
+        let markdown = "# Review\n\nLooks mostly fine.\n\n## Findings\n\n- **high** `src/lib.rs:42` Unchecked unwrap\n* `README.md` typo\n- see `cfg:debug` flag\n";
+        let findings = parse_markdown_findings(markdown);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(findings[0].line, Some(42));
+        assert_eq!(
+            findings[0].message,
+            "**high** `src/lib.rs:42` Unchecked unwrap"
+        );
+    }
+}
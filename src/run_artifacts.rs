@@ -0,0 +1,188 @@
+//! Per-trigger run directories for daemon actions, so failed triggers can be debugged after the
+//! fact. Each run lands in `<config dir>/runs/<owner>-<repo>-pr-<n>/<timestamp>/` with a
+//! `run.json` summary and, for reviews, the `prompt.txt` that was sent.
+
+use crate::config;
+use crate::gh::PullRequest;
+use crate::private_fs;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Older run directories of a PR beyond this many are pruned when a new run is saved.
+const RUNS_KEPT_PER_PR: usize = 5;
+
+pub fn runs_dir() -> PathBuf {
+    config::config_dir().join("runs")
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Success,
+    Failed,
+}
+
+/// Everything known about one trigger, filled in as it progresses.
+#[derive(Debug, Serialize)]
+pub struct RunRecord {
+    pub repo: String,
+    pub pr_number: u64,
+    pub title: String,
+    pub action: String,
+    pub provider: Option<String>,
+    pub worktree: Option<PathBuf>,
+    /// Launcher commands, as rendered before running them.
+    pub commands: Vec<String>,
+    /// What the launcher reported: exit statuses, tmux panes, output locations.
+    pub evidence: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub status: Option<RunStatus>,
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub prompt: Option<String>,
+}
+
+impl RunRecord {
+    pub fn start(pr: &PullRequest, action: &str, provider: Option<&str>) -> Self {
+        Self {
+            repo: pr.repo_name.clone(),
+            pr_number: pr.number,
+            title: pr.title.clone(),
+            action: action.to_string(),
+            provider: provider.map(str::to_string),
+            worktree: None,
+            commands: Vec::new(),
+            evidence: Vec::new(),
+            started_at: Utc::now(),
+            finished_at: None,
+            status: None,
+            error: None,
+            prompt: None,
+        }
+    }
+
+    pub fn finish(&mut self, outcome: &Result<()>) {
+        self.finished_at = Some(Utc::now());
+        match outcome {
+            Ok(()) => self.status = Some(RunStatus::Success),
+            Err(err) => {
+                self.status = Some(RunStatus::Failed);
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    /// Write the run under `root` and prune the PR's oldest runs. Returns the run directory.
+    pub fn save(&self, root: &Path) -> Result<PathBuf> {
+        let pr_dir = root.join(format!(
+            "{}-pr-{}",
+            self.repo.replace('/', "-"),
+            self.pr_number
+        ));
+        let run_dir = pr_dir.join(self.started_at.format("%Y%m%dT%H%M%S%.3fZ").to_string());
+        private_fs::create_dir_all(&run_dir)
+            .with_context(|| format!("Failed to create run directory {}", run_dir.display()))?;
+
+        private_fs::write(
+            &run_dir.join("run.json"),
+            serde_json::to_string_pretty(self)?,
+        )
+        .with_context(|| format!("Failed to write run.json in {}", run_dir.display()))?;
+        if let Some(prompt) = &self.prompt {
+            private_fs::write(&run_dir.join("prompt.txt"), prompt)
+                .with_context(|| format!("Failed to write prompt.txt in {}", run_dir.display()))?;
+        }
+
+        prune_runs(&pr_dir);
+        Ok(run_dir)
+    }
+}
+
+fn prune_runs(pr_dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(pr_dir) else {
+        return;
+    };
+    let mut runs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    // Directory names are timestamps, so name order is chronological.
+    runs.sort();
+    let excess = runs.len().saturating_sub(RUNS_KEPT_PER_PR);
+    for old in &runs[..excess] {
+        let _ = std::fs::remove_dir_all(old);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gh::ReviewState;
+
+    fn make_pr() -> PullRequest {
+        PullRequest {
+            number: 7,
+            title: "Speed up parse_config".to_string(),
+            author: "alice".to_string(),
+            author_kind: Some("User".to_string()),
+            body: String::new(),
+            repo_path: PathBuf::from("/tmp/repo"),
+            repo_name: "acme/widgets".to_string(),
+            url: "https://example.com".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            additions: 1,
+            deletions: 1,
+            is_draft: false,
+            labels: Vec::new(),
+            base_branch: String::new(),
+            head_branch: String::new(),
+            head_oid: String::new(),
+            mergeable: None,
+            review_state: ReviewState::Pending,
+            reviewers: Vec::new(),
+            details_loaded: true,
+        }
+    }
+
+    #[test]
+    fn save_writes_run_json_and_prompt_and_prunes_old_runs() {
+        let root = std::env::temp_dir().join(format!(
+            "reviewer-run-artifacts-test-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        let pr = make_pr();
+
+        let mut dirs = Vec::new();
+        for minute in 0..RUNS_KEPT_PER_PR + 2 {
+            let mut run = RunRecord::start(&pr, "review", Some("codex"));
+            run.started_at = "2026-10-17T09:00:00Z".parse::<DateTime<Utc>>().unwrap()
+                + chrono::Duration::minutes(minute as i64);
+            run.prompt = Some("Review PR #7".to_string());
+            run.commands.push("osascript [\"-e\", \"...\"]".to_string());
+            run.finish(&Err(anyhow::anyhow!("step 1/1 exited with 1")));
+            dirs.push(run.save(&root).unwrap());
+        }
+
+        let latest = dirs.last().unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(latest.join("run.json")).unwrap())
+                .unwrap();
+        let prompt = std::fs::read_to_string(latest.join("prompt.txt")).unwrap();
+        let kept = std::fs::read_dir(latest.parent().unwrap()).unwrap().count();
+        let first_pruned = !dirs[0].exists();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(latest.starts_with(root.join("acme-widgets-pr-7")));
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["error"], "step 1/1 exited with 1");
+        assert_eq!(json["provider"], "codex");
+        assert_eq!(prompt, "Review PR #7");
+        assert_eq!(kept, RUNS_KEPT_PER_PR);
+        assert!(first_pruned);
+    }
+}
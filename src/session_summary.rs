@@ -0,0 +1,214 @@
+//! Markdown summary of a TUI session for standup notes, built from the audit log entries
+//! recorded since the session started.
+
+use crate::audit::{self, AuditEntry};
+use crate::config;
+use crate::private_fs;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use std::path::PathBuf;
+
+/// Summary sections, in the order they are rendered: (audit action, heading).
+const SECTIONS: &[(&str, &str)] = &[
+    ("merge", "Merged"),
+    ("approve", "Approved"),
+    ("auto-merge", "Auto-merge enabled"),
+    ("close", "Closed"),
+    ("comment", "Comments"),
+    ("request-review", "Review requests"),
+    ("auto-approve", "Auto-approved by the daemon"),
+    ("view", "Viewed"),
+];
+
+pub fn summaries_dir() -> PathBuf {
+    config::config_dir().join("summaries")
+}
+
+fn pr_link(entry: &AuditEntry) -> String {
+    let url = entry
+        .url
+        .clone()
+        .unwrap_or_else(|| format!("https://github.com/{}/pull/{}", entry.repo, entry.pr_number));
+    let link = format!("[{}#{}]({})", entry.repo, entry.pr_number, url);
+    match &entry.title {
+        Some(title) => format!("{} {}", link, title),
+        None => link,
+    }
+}
+
+/// First line of a comment, shortened to keep the summary scannable.
+fn excerpt(comment: &str) -> String {
+    const MAX_CHARS: usize = 80;
+    let line = comment.lines().next().unwrap_or("").trim();
+    if line.chars().count() > MAX_CHARS {
+        format!("{}…", line.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+fn item(entry: &AuditEntry) -> String {
+    let mut item = format!("- {}", pr_link(entry));
+    match entry.action.as_str() {
+        "request-review" => {
+            if let Some(login) = &entry.reason {
+                item.push_str(&format!(" → @{}", login));
+            }
+        }
+        "comment" => {
+            if let Some(location) = &entry.reason {
+                item.push_str(&format!(" (`{}`)", location));
+            }
+            if let Some(comment) = entry.comment.as_deref().map(excerpt) {
+                item.push_str(&format!(": \"{}\"", comment));
+            }
+        }
+        "merge" | "close" => {
+            if let Some(reason) = &entry.reason {
+                item.push_str(&format!(" ({})", excerpt(reason)));
+            }
+        }
+        _ => {}
+    }
+    item
+}
+
+/// Render `entries` grouped by action. PRs appear once per section, except for comments.
+pub fn render(entries: &[AuditEntry], started_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let (start, end) = (started_at.with_timezone(&Local), now.with_timezone(&Local));
+    let mut markdown = format!(
+        "# Review session {} {}–{}\n",
+        start.format("%Y-%m-%d"),
+        start.format("%H:%M"),
+        end.format("%H:%M")
+    );
+    let known = |action: &str| SECTIONS.iter().any(|(known, _)| *known == action);
+    let mut other: Vec<&str> = entries
+        .iter()
+        .map(|entry| entry.action.as_str())
+        .filter(|action| !known(action))
+        .collect();
+    other.sort();
+    other.dedup();
+    let sections = SECTIONS
+        .iter()
+        .copied()
+        .chain(other.into_iter().map(|action| (action, action)));
+
+    for (action, heading) in sections {
+        let mut seen = std::collections::HashSet::new();
+        let items: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.action == action)
+            .filter(|entry| action == "comment" || seen.insert((&entry.repo, entry.pr_number)))
+            .map(item)
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        markdown.push_str(&format!("\n## {} ({})\n\n", heading, items.len()));
+        for item in items {
+            markdown.push_str(&item);
+            markdown.push('\n');
+        }
+    }
+    markdown
+}
+
+/// Render the session since `started_at` and save it under [`summaries_dir`]. Returns `None`
+/// when nothing was recorded.
+pub fn write_since(started_at: DateTime<Utc>) -> Result<Option<(PathBuf, String)>> {
+    let entries = audit::entries_since(started_at)?;
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    let markdown = render(&entries, started_at, Utc::now());
+    let dir = summaries_dir();
+    private_fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!(
+        "{}.md",
+        started_at.with_timezone(&Local).format("%Y-%m-%d-%H%M%S")
+    ));
+    private_fs::write(&path, &markdown)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(Some((path, markdown)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(action: &str, pr_number: u64) -> AuditEntry {
+        AuditEntry {
+            at: Utc::now(),
+            action: action.to_string(),
+            repo: "acme/widgets".to_string(),
+            pr_number,
+            reason: None,
+            comment: None,
+            title: Some(format!("PR {}", pr_number)),
+            url: Some(format!(
+                "https://github.com/acme/widgets/pull/{}",
+                pr_number
+            )),
+        }
+    }
+
+    #[test]
+    fn render_groups_actions_and_dedups_prs_except_comments() {
+        let mut merge = entry("merge", 7);
+        merge.reason = Some("squash".to_string());
+        let mut comment = entry("comment", 9);
+        comment.comment = Some("Can we cache this?\nOtherwise LGTM".to_string());
+        comment.reason = Some("src/lib.rs:12".to_string());
+        let mut request = entry("request-review", 9);
+        request.reason = Some("bob".to_string());
+        let entries = vec![
+            entry("view", 7),
+            entry("view", 9),
+            entry("view", 7),
+            entry("approve", 7),
+            merge,
+            comment,
+            entry("comment", 9),
+            request,
+            entry("set-label", 9),
+        ];
+        let now = Utc::now();
+
+        let markdown = render(&entries, now, now);
+        let body = markdown.split_once('\n').unwrap().1;
+        assert!(markdown.starts_with("# Review session "));
+        assert_eq!(
+            body,
+            "
+## Merged (1)
+
+- [acme/widgets#7](https://github.com/acme/widgets/pull/7) PR 7 (squash)
+
+## Approved (1)
+
+- [acme/widgets#7](https://github.com/acme/widgets/pull/7) PR 7
+
+## Comments (2)
+
+- [acme/widgets#9](https://github.com/acme/widgets/pull/9) PR 9 (`src/lib.rs:12`): \"Can we cache this?\"
+- [acme/widgets#9](https://github.com/acme/widgets/pull/9) PR 9
+
+## Review requests (1)
+
+- [acme/widgets#9](https://github.com/acme/widgets/pull/9) PR 9 → @bob
+
+## Viewed (2)
+
+- [acme/widgets#7](https://github.com/acme/widgets/pull/7) PR 7
+- [acme/widgets#9](https://github.com/acme/widgets/pull/9) PR 9
+
+## set-label (1)
+
+- [acme/widgets#9](https://github.com/acme/widgets/pull/9) PR 9
+"
+        );
+    }
+}
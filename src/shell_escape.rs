@@ -0,0 +1,234 @@
+//! Quoting for untrusted values (PR titles, prompts, paths) that end up inside a shell command
+//! line or an AppleScript string literal. Anything that builds such a string goes through here,
+//! so a PR title like `$(rm -rf ~)` or `"; do shell script "..."` stays inert text.
+
+/// Quote `value` as a single POSIX shell word. Nothing inside is expanded.
+#[cfg(any(target_os = "macos", target_os = "linux", test))]
+pub fn posix(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Characters `cmd.exe` interprets even inside double quotes or when toggling quote state.
+#[cfg(any(target_os = "windows", test))]
+const CMD_METACHARACTERS: &[char] = &['(', ')', '%', '!', '^', '"', '<', '>', '&', '|'];
+
+/// Quote `value` as a single argument of a command line run through `cmd.exe`.
+///
+/// The value is first quoted with the MSVC argv rules (backslashes before a quote are doubled),
+/// then every cmd metacharacter is `^`-escaped, so `%VAR%`, `&` and stray quotes stay literal.
+/// A line break ends a cmd command no matter how it is quoted, so each one (`\r\n`, `\r` or
+/// `\n`) becomes a space.
+#[cfg(any(target_os = "windows", test))]
+pub fn windows_cmd(value: &str) -> String {
+    let value = value.replace("\r\n", " ").replace(['\r', '\n'], " ");
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    let mut backslashes = 0;
+    for ch in value.chars() {
+        match ch {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                quoted.extend(std::iter::repeat_n('\\', backslashes));
+                quoted.push(ch);
+                backslashes = 0;
+            }
+        }
+    }
+    quoted.extend(std::iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+
+    let mut escaped = String::with_capacity(quoted.len() * 2);
+    for ch in quoted.chars() {
+        if CMD_METACHARACTERS.contains(&ch) {
+            escaped.push('^');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Escape `value` for use between the double quotes of an AppleScript string literal.
+#[cfg(any(target_os = "macos", test))]
+pub fn applescript(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fragments that break naive quoting in at least one of the target languages.
+    const HOSTILE: &[&str] = &[
+        "'",
+        "\"",
+        "`",
+        "$(",
+        ")",
+        "${HOME}",
+        "$HOME",
+        "\\",
+        "\\\"",
+        ";",
+        "&",
+        "&&",
+        "|",
+        "<",
+        ">",
+        "%PATH%",
+        "!",
+        "^",
+        "(",
+        "\n",
+        "\r",
+        "\t",
+        " ",
+        "*",
+        "?",
+        "~",
+        "#",
+        "rm -rf ~",
+        "é",
+        "🤖",
+        "\" & do shell script \"id\" & \"",
+    ];
+
+    /// Deterministic pseudo-random strings stitched together from hostile fragments.
+    fn hostile_inputs(count: usize) -> Vec<String> {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as usize
+        };
+        let mut inputs: Vec<String> = HOSTILE.iter().map(|part| part.to_string()).collect();
+        inputs.push(String::new());
+        while inputs.len() < count {
+            let len = next() % 8 + 1;
+            inputs.push((0..len).map(|_| HOSTILE[next() % HOSTILE.len()]).collect());
+        }
+        inputs
+    }
+
+    #[test]
+    fn posix_round_trips_hostile_input_through_sh() {
+        let inputs = hostile_inputs(300);
+        let script = format!(
+            "printf '%s\\0' {}",
+            inputs
+                .iter()
+                .map(|input| posix(input))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        let output = std::process::Command::new("sh")
+            .args(["-c", &script])
+            .output()
+            .expect("sh should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let words: Vec<&str> = stdout.split_terminator('\0').collect();
+        assert_eq!(words, inputs);
+    }
+
+    /// Undo `^` escapes, then split with the MSVC argv rules.
+    fn parse_windows_cmd(line: &str) -> Vec<String> {
+        let mut unescaped = String::new();
+        let mut chars = line.chars();
+        while let Some(ch) = chars.next() {
+            if ch == '^' {
+                unescaped.extend(chars.next());
+            } else {
+                assert!(
+                    !CMD_METACHARACTERS.contains(&ch),
+                    "unescaped {ch:?} in {line}"
+                );
+                unescaped.push(ch);
+            }
+        }
+
+        let mut args = Vec::new();
+        let mut current = String::new();
+        let mut in_quotes = false;
+        let mut started = false;
+        let mut chars = unescaped.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => {
+                    let mut count = 1;
+                    while chars.peek() == Some(&'\\') {
+                        chars.next();
+                        count += 1;
+                    }
+                    if chars.peek() == Some(&'"') {
+                        current.extend(std::iter::repeat_n('\\', count / 2));
+                        if count % 2 == 1 {
+                            chars.next();
+                            current.push('"');
+                        }
+                    } else {
+                        current.extend(std::iter::repeat_n('\\', count));
+                    }
+                    started = true;
+                }
+                '"' => {
+                    in_quotes = !in_quotes;
+                    started = true;
+                }
+                ' ' | '\t' if !in_quotes => {
+                    if started {
+                        args.push(std::mem::take(&mut current));
+                        started = false;
+                    }
+                }
+                _ => {
+                    current.push(ch);
+                    started = true;
+                }
+            }
+        }
+        if started {
+            args.push(current);
+        }
+        args
+    }
+
+    #[test]
+    fn windows_cmd_keeps_hostile_input_a_single_literal_argument() {
+        for input in hostile_inputs(300) {
+            let escaped = windows_cmd(&input);
+            assert!(!escaped.contains(['\r', '\n']), "{escaped:?}");
+            let expected = input.replace("\r\n", " ").replace(['\r', '\n'], " ");
+            assert_eq!(parse_windows_cmd(&escaped), vec![expected], "{escaped}");
+        }
+        assert_eq!(windows_cmd("C:\\dir\\"), "^\"C:\\dir\\\\^\"");
+        assert_eq!(windows_cmd("fix\r\n& calc\nnow"), "^\"fix ^& calc now^\"");
+    }
+
+    #[test]
+    fn applescript_never_closes_the_string_literal() {
+        for input in hostile_inputs(300) {
+            let escaped = applescript(&input);
+            let mut unescaped = String::new();
+            let mut chars = escaped.chars();
+            while let Some(ch) = chars.next() {
+                match ch {
+                    '\\' => unescaped.extend(chars.next()),
+                    '"' => panic!("unescaped quote in {escaped}"),
+                    _ => unescaped.push(ch),
+                }
+            }
+            assert_eq!(unescaped, input);
+        }
+    }
+}
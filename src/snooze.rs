@@ -0,0 +1,141 @@
+use crate::config;
+use crate::gh::PullRequest;
+use crate::private_fs;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Local hour at which "tomorrow" and "next week" snoozes wake up.
+const WAKE_HOUR: u32 = 9;
+
+/// How long to hide a PR from the list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnoozeDuration {
+    FourHours,
+    Tomorrow,
+    NextWeek,
+}
+
+impl SnoozeDuration {
+    pub const ALL: [SnoozeDuration; 3] = [
+        SnoozeDuration::FourHours,
+        SnoozeDuration::Tomorrow,
+        SnoozeDuration::NextWeek,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SnoozeDuration::FourHours => "4 hours",
+            SnoozeDuration::Tomorrow => "tomorrow 09:00",
+            SnoozeDuration::NextWeek => "next Monday 09:00",
+        }
+    }
+
+    /// Wake-up time, with day-based snoozes landing on `WAKE_HOUR` in `now`'s timezone.
+    pub fn until<Tz: TimeZone>(self, now: DateTime<Tz>) -> DateTime<Utc> {
+        let days_ahead = match self {
+            SnoozeDuration::FourHours => return (now + Duration::hours(4)).with_timezone(&Utc),
+            SnoozeDuration::Tomorrow => 1,
+            SnoozeDuration::NextWeek => 7 - i64::from(now.weekday().num_days_from_monday()),
+        };
+        let day = now.date_naive() + Duration::days(days_ahead);
+        let wake = day.and_time(NaiveTime::from_hms_opt(WAKE_HOUR, 0, 0).expect("valid time"));
+        now.timezone()
+            .from_local_datetime(&wake)
+            .earliest()
+            .map(|at| at.with_timezone(&Utc))
+            .unwrap_or_else(|| (now + Duration::days(days_ahead)).with_timezone(&Utc))
+    }
+}
+
+/// PRs hidden from the TUI list, keyed by `owner/repo#number`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snoozes {
+    #[serde(default)]
+    pub prs: BTreeMap<String, DateTime<Utc>>,
+}
+
+fn snooze_key(pr: &PullRequest) -> String {
+    format!("{}#{}", pr.repo_name, pr.number)
+}
+
+pub fn snooze_path() -> PathBuf {
+    config::config_dir().join("snoozed.json")
+}
+
+impl Snoozes {
+    fn load_from(path: &Path) -> Self {
+        private_fs::read_json_or_backup(path).unwrap_or_default()
+    }
+
+    /// Load `snoozed.json`, dropping snoozes that already expired.
+    pub fn load() -> Self {
+        let mut snoozes = Self::load_from(&snooze_path());
+        snoozes.prune(Utc::now());
+        snoozes
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            private_fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        private_fs::write_atomic(path, json)
+            .with_context(|| format!("Failed to write snoozes to {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&snooze_path())
+    }
+
+    /// When the PR wakes up, if it is still snoozed at `now`.
+    pub fn until(&self, pr: &PullRequest, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.prs
+            .get(&snooze_key(pr))
+            .copied()
+            .filter(|until| *until > now)
+    }
+
+    pub fn snooze(&mut self, pr: &PullRequest, until: DateTime<Utc>) {
+        self.prs.insert(snooze_key(pr), until);
+    }
+
+    pub fn wake(&mut self, pr: &PullRequest) -> bool {
+        self.prs.remove(&snooze_key(pr)).is_some()
+    }
+
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        self.prs.retain(|_, until| *until > now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnoozeDuration;
+    use chrono::{FixedOffset, TimeZone};
+
+    #[test]
+    fn snooze_durations_wake_at_local_morning() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        // Wednesday afternoon.
+        let now = tz.with_ymd_and_hms(2026, 3, 18, 15, 30, 0).unwrap();
+
+        let four_hours = SnoozeDuration::FourHours.until(now);
+        assert_eq!(
+            four_hours.with_timezone(&tz),
+            tz.with_ymd_and_hms(2026, 3, 18, 19, 30, 0).unwrap()
+        );
+        let tomorrow = SnoozeDuration::Tomorrow.until(now);
+        assert_eq!(
+            tomorrow.with_timezone(&tz),
+            tz.with_ymd_and_hms(2026, 3, 19, 9, 0, 0).unwrap()
+        );
+        let next_week = SnoozeDuration::NextWeek.until(now);
+        assert_eq!(
+            next_week.with_timezone(&tz),
+            tz.with_ymd_and_hms(2026, 3, 23, 9, 0, 0).unwrap()
+        );
+    }
+}
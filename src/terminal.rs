@@ -1,9 +1,15 @@
 #[cfg(target_os = "macos")]
-use anyhow::{Context, Result};
-#[cfg(target_os = "macos")]
+use crate::logging::CommandExt;
+#[cfg(target_os = "linux")]
+use crate::process::describe_command;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use crate::shell_escape;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use anyhow::Context;
+use anyhow::Result;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 use std::process::Command;
 
-#[cfg(target_os = "macos")]
 pub const TERMINAL_LAUNCH_MODE_VALUES: &[&str] = &[
     "auto",
     "new-instance",
@@ -12,7 +18,6 @@ pub const TERMINAL_LAUNCH_MODE_VALUES: &[&str] = &[
     "new-window",
 ];
 
-#[cfg(target_os = "macos")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TerminalLaunchMode {
     Auto,
@@ -22,7 +27,7 @@ pub enum TerminalLaunchMode {
     NewWindow,
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 impl TerminalLaunchMode {
     pub fn as_str(self) -> &'static str {
         match self {
@@ -35,7 +40,6 @@ impl TerminalLaunchMode {
     }
 }
 
-#[cfg(target_os = "macos")]
 pub fn parse_terminal_launch_mode(value: &str) -> Result<TerminalLaunchMode> {
     match value.trim().to_ascii_lowercase().as_str() {
         "" | "auto" => Ok(TerminalLaunchMode::Auto),
@@ -51,23 +55,23 @@ pub fn parse_terminal_launch_mode(value: &str) -> Result<TerminalLaunchMode> {
     }
 }
 
+// Scripts below receive the command line as `item 1 of argv` instead of having it spliced
+// into the source, so nothing in it (a PR title, say) can end the string literal.
+
 #[cfg(target_os = "macos")]
 fn launch_macos_terminal_applescript(app: &str, command_line: &str) -> Result<()> {
-    let escaped_command = command_line.replace('"', "\\\"");
     let script = format!(
-        r#"tell application "{app}"
-            activate
-            do script "{command}"
-        end tell"#,
-        app = app,
-        command = escaped_command
+        r#"on run argv
+            tell application "{app}"
+                activate
+                do script (item 1 of argv)
+            end tell
+        end run"#,
+        app = shell_escape::applescript(app)
     );
     let output = Command::new("osascript")
-        .args(["-e", &script])
-        .stdin(std::process::Stdio::null())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .output()
+        .args(["-e", &script, command_line])
+        .logged_output()
         .with_context(|| format!("Failed to launch {}", app))?;
     if !output.status.success() {
         anyhow::bail!(
@@ -79,20 +83,12 @@ fn launch_macos_terminal_applescript(app: &str, command_line: &str) -> Result<()
     Ok(())
 }
 
-#[cfg(target_os = "macos")]
-fn escape_applescript_string(value: &str) -> String {
-    value.replace('\\', "\\\\").replace('"', "\\\"")
-}
-
 #[cfg(target_os = "macos")]
 fn launch_macos_ghostty_new_tab(app: &str, command_line: &str) -> Result<()> {
     // Ensure Ghostty is running/active before sending keybindings.
     let activate_output = Command::new("open")
         .args(["-a", app])
-        .stdin(std::process::Stdio::null())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .output()
+        .logged_output()
         .with_context(|| format!("Failed to activate {}", app))?;
     if !activate_output.status.success() {
         anyhow::bail!(
@@ -102,10 +98,10 @@ fn launch_macos_ghostty_new_tab(app: &str, command_line: &str) -> Result<()> {
         );
     }
 
-    let escaped_command = escape_applescript_string(command_line);
-    let script_app = escape_applescript_string(app.trim().trim_end_matches(".app").trim());
+    let script_app = shell_escape::applescript(app.trim().trim_end_matches(".app").trim());
     let script = format!(
-        r#"set launchCmd to "{command}"
+        r#"on run argv
+set launchCmd to item 1 of argv
 set oldClipboard to ""
 try
     set oldClipboard to (the clipboard as text)
@@ -131,14 +127,14 @@ end tell
 delay 0.2
 try
     set the clipboard to oldClipboard
-end try"#,
-        app_name = script_app,
-        command = escaped_command
+end try
+end run"#,
+        app_name = script_app
     );
 
     let output = Command::new("osascript")
-        .args(["-e", &script])
-        .output()
+        .args(["-e", &script, command_line])
+        .logged_output()
         .context("Failed to send Ghostty new-tab AppleScript")?;
     if !output.status.success() {
         anyhow::bail!(
@@ -152,25 +148,26 @@ end try"#,
 
 #[cfg(target_os = "macos")]
 fn launch_macos_terminal_terminal_new_tab(command_line: &str) -> Result<()> {
-    let escaped_command = command_line.replace('"', "\\\"");
-    let script = format!(
-        r#"tell application "Terminal"
-            activate
-            if (count of windows) is 0 then
-                do script "{command}"
-            else
-                do script "{command}" in front window
-            end if
-        end tell"#,
-        command = escaped_command
-    );
-    Command::new("osascript")
-        .args(["-e", &script])
-        .stdin(std::process::Stdio::null())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .spawn()
+    let script = r#"on run argv
+            tell application "Terminal"
+                activate
+                if (count of windows) is 0 then
+                    do script (item 1 of argv)
+                else
+                    do script (item 1 of argv) in front window
+                end if
+            end tell
+        end run"#;
+    let output = Command::new("osascript")
+        .args(["-e", script, command_line])
+        .logged_output()
         .context("Failed to launch Terminal new tab")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to launch Terminal new tab: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
     Ok(())
 }
 
@@ -194,10 +191,7 @@ fn launch_macos_terminal_open(
 
     let output = Command::new("open")
         .args(args)
-        .stdin(std::process::Stdio::null())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .output()
+        .logged_output()
         .with_context(|| format!("Failed to launch {}", app))?;
     if !output.status.success() {
         anyhow::bail!(
@@ -209,11 +203,7 @@ fn launch_macos_terminal_open(
 
     // Best-effort activation for third-party terminals (Ghostty, iTerm, etc.).
     // Some apps start command execution only after the window becomes active.
-    let script_app = app
-        .trim()
-        .trim_end_matches(".app")
-        .trim()
-        .replace('"', "\\\"");
+    let script_app = shell_escape::applescript(app.trim().trim_end_matches(".app").trim());
     let _ = Command::new("osascript")
         .args([
             "-e",
@@ -221,10 +211,7 @@ fn launch_macos_terminal_open(
             "-e",
             &format!("tell application \"{}\" to activate", script_app),
         ])
-        .stdin(std::process::Stdio::null())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .spawn();
+        .logged_output();
 
     Ok(())
 }
@@ -288,3 +275,268 @@ pub fn launch_macos_terminal(
         }
     }
 }
+
+/// Terminals probed on `PATH` when neither `ai.launch.terminal.app` nor `$TERMINAL` is set.
+#[cfg(target_os = "linux")]
+const LINUX_TERMINAL_CANDIDATES: &[&str] = &[
+    "x-terminal-emulator",
+    "gnome-terminal",
+    "konsole",
+    "xfce4-terminal",
+    "kitty",
+    "wezterm",
+    "alacritty",
+    "ghostty",
+    "xterm",
+];
+
+#[cfg(target_os = "linux")]
+fn detect_linux_terminal(
+    configured: Option<&str>,
+    env_terminal: Option<String>,
+    on_path: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let configured = configured
+        .map(str::trim)
+        .filter(|app| !app.is_empty())
+        .map(str::to_string);
+    let env_terminal = env_terminal
+        .map(|app| app.trim().to_string())
+        .filter(|app| !app.is_empty());
+    configured.or(env_terminal).or_else(|| {
+        LINUX_TERMINAL_CANDIDATES
+            .iter()
+            .find(|candidate| on_path(candidate))
+            .map(|candidate| candidate.to_string())
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn is_on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Build the argument list that opens `command_line` in `app` according to `mode`.
+///
+/// Tabs are only requested from terminals that can add one from the command line;
+/// the rest fall back to a new window, like the non-Terminal apps on macOS.
+#[cfg(target_os = "linux")]
+fn linux_terminal_args(
+    app: &str,
+    working_dir: &std::path::Path,
+    command_line: &str,
+    mode: TerminalLaunchMode,
+) -> Vec<String> {
+    let workdir = working_dir.display().to_string();
+    let name = std::path::Path::new(app)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let wants_tab = matches!(
+        mode,
+        TerminalLaunchMode::NewTab | TerminalLaunchMode::SameSpace
+    );
+    let new_instance = mode == TerminalLaunchMode::NewInstance;
+    let shell = |args: &mut Vec<String>, command_line: &str| {
+        args.extend(["bash", "-lc", command_line].map(String::from));
+    };
+
+    let mut args: Vec<String> = Vec::new();
+    match name.as_str() {
+        "gnome-terminal" => {
+            args.push(if wants_tab { "--tab" } else { "--window" }.to_string());
+            args.push(format!("--working-directory={workdir}"));
+            args.push("--".to_string());
+            shell(&mut args, command_line);
+        }
+        "konsole" => {
+            if wants_tab {
+                args.push("--new-tab".to_string());
+            } else if new_instance {
+                args.push("--separate".to_string());
+            }
+            args.extend(["--workdir".to_string(), workdir, "-e".to_string()]);
+            shell(&mut args, command_line);
+        }
+        "xfce4-terminal" => {
+            if new_instance {
+                args.push("--disable-server".to_string());
+            }
+            args.push(if wants_tab { "--tab" } else { "--window" }.to_string());
+            args.push(format!("--working-directory={workdir}"));
+            args.push("-x".to_string());
+            shell(&mut args, command_line);
+        }
+        "kitty" => {
+            if wants_tab {
+                // Needs `allow_remote_control` in kitty.conf.
+                args.extend(["@", "launch", "--type=tab"].map(String::from));
+                args.push(format!("--cwd={workdir}"));
+            } else {
+                if !new_instance {
+                    args.push("--single-instance".to_string());
+                }
+                args.extend(["--directory".to_string(), workdir]);
+            }
+            shell(&mut args, command_line);
+        }
+        "wezterm" => {
+            if wants_tab {
+                args.extend(["cli", "spawn"].map(String::from));
+            } else {
+                args.push("start".to_string());
+                if new_instance {
+                    args.push("--always-new-process".to_string());
+                }
+            }
+            args.extend(["--cwd".to_string(), workdir, "--".to_string()]);
+            shell(&mut args, command_line);
+        }
+        "alacritty" => {
+            args.extend(["--working-directory".to_string(), workdir, "-e".to_string()]);
+            shell(&mut args, command_line);
+        }
+        "ghostty" => {
+            if new_instance {
+                args.push("--gtk-single-instance=false".to_string());
+            }
+            args.push(format!("--working-directory={workdir}"));
+            args.push("-e".to_string());
+            shell(&mut args, command_line);
+        }
+        _ => {
+            // xterm, x-terminal-emulator and unknown terminals only reliably accept `-e`.
+            args.push("-e".to_string());
+            let cd = format!("cd {} && {command_line}", shell_escape::posix(&workdir));
+            shell(&mut args, &cd);
+        }
+    }
+    args
+}
+
+/// Open `command_line` in a Linux terminal emulator, starting in `working_dir`.
+///
+/// `app` overrides detection; otherwise `$TERMINAL` and then the first known terminal on
+/// `PATH` is used. The shell stays open after the command exits so its output can be read.
+#[cfg(target_os = "linux")]
+pub fn launch_linux_terminal(
+    app: Option<&str>,
+    working_dir: &std::path::Path,
+    command_line: &str,
+    mode: TerminalLaunchMode,
+) -> Result<()> {
+    let Some(app) = detect_linux_terminal(app, std::env::var("TERMINAL").ok(), is_on_path) else {
+        anyhow::bail!(
+            "No terminal emulator found. Set ai.launch.terminal.app or $TERMINAL (tried {})",
+            LINUX_TERMINAL_CANDIDATES.join(", ")
+        );
+    };
+    let command_line = format!("{command_line}; exec bash");
+    let args = linux_terminal_args(&app, working_dir, &command_line, mode);
+
+    // The terminal outlives this call, so it is spawned and left running rather than waited on
+    // through the captured-output runner; it is logged here instead.
+    let mut command = Command::new(&app);
+    command.args(&args);
+    tracing::info!(command = %describe_command(&command), "launching terminal");
+    command
+        .current_dir(working_dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to launch {} ({})", app, mode.as_str()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_terminal_launch_mode_accepts_known_modes() {
+        assert_eq!(
+            parse_terminal_launch_mode("").unwrap(),
+            TerminalLaunchMode::Auto
+        );
+        assert_eq!(
+            parse_terminal_launch_mode(" New-Tab ").unwrap(),
+            TerminalLaunchMode::NewTab
+        );
+        let err = parse_terminal_launch_mode("split").unwrap_err();
+        assert!(err.to_string().contains("new-window"), "{err}");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn detect_linux_terminal_prefers_config_then_env_then_path() {
+        let on_path = |program: &str| program == "konsole" || program == "xterm";
+        assert_eq!(
+            detect_linux_terminal(Some("kitty"), Some("wezterm".into()), on_path).as_deref(),
+            Some("kitty")
+        );
+        assert_eq!(
+            detect_linux_terminal(Some(" "), Some("wezterm".into()), on_path).as_deref(),
+            Some("wezterm")
+        );
+        assert_eq!(
+            detect_linux_terminal(None, None, on_path).as_deref(),
+            Some("konsole")
+        );
+        assert_eq!(detect_linux_terminal(None, None, |_| false), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_terminal_args_map_modes_and_working_dir() {
+        let dir = std::path::Path::new("/tmp/pr 7");
+        assert_eq!(
+            linux_terminal_args("gnome-terminal", dir, "codex", TerminalLaunchMode::NewTab),
+            [
+                "--tab",
+                "--working-directory=/tmp/pr 7",
+                "--",
+                "bash",
+                "-lc",
+                "codex"
+            ]
+        );
+        assert_eq!(
+            linux_terminal_args(
+                "/usr/bin/konsole",
+                dir,
+                "codex",
+                TerminalLaunchMode::NewInstance
+            ),
+            [
+                "--separate",
+                "--workdir",
+                "/tmp/pr 7",
+                "-e",
+                "bash",
+                "-lc",
+                "codex"
+            ]
+        );
+        assert_eq!(
+            linux_terminal_args("wezterm", dir, "codex", TerminalLaunchMode::SameSpace)[..2],
+            ["cli", "spawn"]
+        );
+        assert_eq!(
+            linux_terminal_args("alacritty", dir, "codex", TerminalLaunchMode::NewTab),
+            [
+                "--working-directory",
+                "/tmp/pr 7",
+                "-e",
+                "bash",
+                "-lc",
+                "codex"
+            ]
+        );
+        assert_eq!(
+            linux_terminal_args("xterm", dir, "codex", TerminalLaunchMode::Auto),
+            ["-e", "bash", "-lc", "cd '/tmp/pr 7' && codex"]
+        );
+    }
+}
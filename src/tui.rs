@@ -1,12 +1,34 @@
 use crate::agent::{self, AgentPreview};
-use crate::config::{self, AiConfig};
+use crate::audit::{self, AuditEntry};
+use crate::auth;
+use crate::checklist::{self, ChecklistItem};
+use crate::client::{GhCli, GhClient, ListQuery};
+use crate::clone_cache;
+use crate::config::{
+    self, AiConfig, ApprovedPrs, ChecklistConfig, Config, ConfigWatcher, ConfirmConfig, DateStyle,
+    DatesConfig, DiffConfig, DraftsConfig, EditorConfig, KeyChord, KeyPress, KeyScope, KeysConfig,
+    ListConfig, ListFilter, ListOrderRule, ListSort, ProjectConfig, TeamConfig, ViewConfig,
+    ViewTab,
+};
+use crate::core::{self, ReviewedPrRecord, TriggerStatus};
+use crate::dates;
+use crate::deps;
 use crate::diff::{self, SyntaxHighlighter};
-use crate::gh::{self, Comment, PullRequest, ReviewComment, ReviewState};
-use anyhow::Result;
-use chrono::{DateTime, Utc};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crate::drafts::{self, DraftComment, DraftReview};
+use crate::editor;
+use crate::filters;
+use crate::gh::{self, Comment, PullRequest, ReviewComment, ReviewEvent, ReviewState};
+use crate::gh_version::{self, Capability};
+use crate::global_search::{self, GlobalMatch, MatchLocation};
+use crate::private_fs;
+use crate::review_results::{self, ReviewResults};
+use crate::session_summary;
+use crate::snooze::{SnoozeDuration, Snoozes};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Tabs, Wrap},
@@ -16,29 +38,55 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::io;
 use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
 use std::thread;
 
-/// Format a datetime as a human-readable age (e.g., "2h", "3d", "1w")
-fn format_age(dt: &DateTime<Utc>) -> String {
+/// List badge for a PR the daemon has handled, e.g. `🤖 AI review 2h ago`.
+fn daemon_badge(record: &ReviewedPrRecord, dates: &DatesConfig) -> Span<'static> {
     let now = Utc::now();
-    let duration = now.signed_duration_since(*dt);
-
-    let hours = duration.num_hours();
-    let days = duration.num_days();
-    let weeks = days / 7;
-    let months = days / 30;
-
-    if months > 0 {
-        format!("{}mo", months)
-    } else if weeks > 0 {
-        format!("{}w", weeks)
-    } else if days > 0 {
-        format!("{}d", days)
-    } else if hours > 0 {
-        format!("{}h", hours)
-    } else {
-        "now".to_string()
-    }
+    let ago = |at: &DateTime<Utc>| dates::ago(dates, at, now);
+    let (text, color) = match record.trigger_status {
+        TriggerStatus::Success => (
+            format!(
+                "AI review{} {}",
+                record
+                    .provider
+                    .as_ref()
+                    .map(|provider| format!(" ({})", provider))
+                    .unwrap_or_default(),
+                ago(record.triggered_at.as_ref().unwrap_or(&record.last_seen_at))
+            ),
+            Color::Cyan,
+        ),
+        TriggerStatus::Failed => (
+            format!("AI review failed {}", ago(&record.last_seen_at)),
+            Color::Red,
+        ),
+        TriggerStatus::Queued => ("AI review queued".to_string(), Color::DarkGray),
+        TriggerStatus::Skipped => (
+            match &record.triage {
+                Some(triage) => format!("skipped as trivial {}", ago(&triage.decided_at)),
+                None => "skipped as trivial".to_string(),
+            },
+            Color::DarkGray,
+        ),
+        TriggerStatus::Seeded => return Span::raw(""),
+    };
+    Span::styled(format!("🤖 {}", text), Style::default().fg(color))
+}
+
+/// One-line blame for the status bar, e.g. `src/lib.rs:12 last changed 3mo ago by alice in 1a2b3c4: Fix parser`.
+fn blame_summary(path: &str, line: u32, blame: &gh::BlameLine, dates: &DatesConfig) -> String {
+    let age = dates::ago(dates, &blame.authored_at, Utc::now());
+    format!(
+        "{}:{} last changed {} by {} in {}: {}",
+        path,
+        line,
+        age,
+        blame.author,
+        &blame.commit[..blame.commit.len().min(7)],
+        blame.summary
+    )
 }
 
 /// Strip ANSI escape codes from a string for searching
@@ -80,17 +128,82 @@ fn format_search_status(idx: usize, total: usize, query: &str) -> String {
     format!("Match {}/{} for '{}'", idx + 1, total, query)
 }
 
+/// Check whether `text` contains `query`, optionally ignoring case.
+fn text_matches_query(text: &str, query: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        text.contains(query)
+    } else {
+        text.to_lowercase().contains(&query.to_lowercase())
+    }
+}
+
 /// Match list-search query against PR metadata (title/repo/author/number).
-fn pr_matches_list_query(pr: &PullRequest, query_lower: &str) -> bool {
-    if query_lower.is_empty() {
+fn pr_matches_list_query(pr: &PullRequest, query: &str, case_sensitive: bool) -> bool {
+    if query.is_empty() {
         return false;
     }
 
-    pr.title.to_lowercase().contains(query_lower)
-        || pr.repo_name.to_lowercase().contains(query_lower)
-        || pr.author.to_lowercase().contains(query_lower)
-        || pr.number.to_string().contains(query_lower)
-        || format!("#{}", pr.number).contains(query_lower)
+    text_matches_query(&pr.title, query, case_sensitive)
+        || text_matches_query(&pr.repo_name, query, case_sensitive)
+        || text_matches_query(&pr.author, query, case_sensitive)
+        || pr.number.to_string().contains(query)
+        || format!("#{}", pr.number).contains(query)
+}
+
+const SEARCH_HISTORY_LIMIT: usize = 50;
+const STATUS_LOG_LIMIT: usize = 500;
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// Detail view page size until the first render reports the real viewport height.
+const DEFAULT_PAGE_LINES: u16 = 20;
+
+/// Per-session history of submitted search queries, recalled with Up/Down in the prompt.
+#[derive(Debug, Default)]
+struct SearchHistory {
+    entries: Vec<String>,
+    cursor: Option<usize>,
+}
+
+impl SearchHistory {
+    fn record(&mut self, query: &str) {
+        self.cursor = None;
+        if query.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.push(query.to_string());
+        if self.entries.len() > SEARCH_HISTORY_LIMIT {
+            self.entries.remove(0);
+        }
+    }
+
+    fn reset_cursor(&mut self) {
+        self.cursor = None;
+    }
+
+    /// Step back to an older query. Returns the query to place in the prompt.
+    fn older(&mut self) -> Option<String> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let idx = match self.cursor {
+            None => self.entries.len() - 1,
+            Some(idx) => idx.saturating_sub(1),
+        };
+        self.cursor = Some(idx);
+        Some(self.entries[idx].clone())
+    }
+
+    /// Step forward to a newer query. Stepping past the newest entry clears the prompt.
+    fn newer(&mut self) -> Option<String> {
+        let idx = self.cursor?;
+        if idx + 1 < self.entries.len() {
+            self.cursor = Some(idx + 1);
+            Some(self.entries[idx + 1].clone())
+        } else {
+            self.cursor = None;
+            Some(String::new())
+        }
+    }
 }
 
 /// Represents a line in the parsed diff with its location info
@@ -119,6 +232,18 @@ pub struct DeltaLineInfo {
     pub file_path: Option<String>,
     pub old_line_number: Option<u32>,
     pub new_line_number: Option<u32>,
+    pub hunk_header: bool, // The `<num>: <context>` row delta draws for an `@@` line
+}
+
+/// Line and side a comment on this delta row lands on: the selected column when that side
+/// has a line number, otherwise the other side (e.g. the left column of an added line).
+fn delta_comment_target(info: &DeltaLineInfo, column: CommentSide) -> Option<(u32, CommentSide)> {
+    let left = info.old_line_number.map(|line| (line, CommentSide::Left));
+    let right = info.new_line_number.map(|line| (line, CommentSide::Right));
+    match column {
+        CommentSide::Left => left.or(right),
+        CommentSide::Right => right.or(left),
+    }
 }
 
 /// Parse delta output to extract file paths and line numbers
@@ -131,10 +256,8 @@ fn parse_delta_output(delta_output: &str, raw_diff: &str) -> Vec<DeltaLineInfo>
     // Extract all file paths from the raw diff
     let mut known_files: Vec<String> = Vec::new();
     for line in raw_diff.lines() {
-        if line.starts_with("diff --git") {
-            if let Some(b_path) = line.split(" b/").nth(1) {
-                known_files.push(b_path.to_string());
-            }
+        if let Some(path) = diff::git_header_path(line) {
+            known_files.push(path.to_string());
         }
     }
 
@@ -157,6 +280,7 @@ fn parse_delta_output(delta_output: &str, raw_diff: &str) -> Vec<DeltaLineInfo>
 
         let mut old_num: Option<u32> = None;
         let mut new_num: Option<u32> = None;
+        let mut hunk_header = false;
 
         if let Some(separator_pos) = clean.find('⋮') {
             // Unified mode with ⋮ separator
@@ -200,6 +324,7 @@ fn parse_delta_output(delta_output: &str, raw_diff: &str) -> Vec<DeltaLineInfo>
             if let Ok(line_num) = before_colon.parse::<u32>() {
                 old_num = Some(line_num);
                 new_num = Some(line_num);
+                hunk_header = true;
             }
         }
 
@@ -207,6 +332,7 @@ fn parse_delta_output(delta_output: &str, raw_diff: &str) -> Vec<DeltaLineInfo>
             file_path: current_file.clone(),
             old_line_number: old_num,
             new_line_number: new_num,
+            hunk_header,
         });
     }
 
@@ -219,20 +345,24 @@ fn parse_diff(diff: &str) -> Vec<DiffLine> {
     let mut current_file: Option<String> = None;
     let mut old_line_num: u32 = 0;
     let mut new_line_num: u32 = 0;
+    let mut in_file_header = true;
 
     for line in diff.lines() {
         if line.starts_with("diff --git") {
-            // Extract file path from "diff --git a/path b/path"
-            if let Some(b_path) = line.split(" b/").nth(1) {
-                current_file = Some(b_path.to_string());
-            }
+            current_file = diff::git_header_path(line).map(str::to_string);
+            in_file_header = true;
             result.push(DiffLine {
                 file_path: current_file.clone(),
                 line_number: None,
                 old_line_number: None,
                 line_type: DiffLineType::Header,
             });
-        } else if line.starts_with("+++") || line.starts_with("---") {
+        } else if in_file_header && diff::is_file_header_line(line) {
+            // `+++ b/new` and `rename to` name renamed files, `--- a/old` deleted ones.
+            // Past the first hunk a `---` line is a removed `-- ...` line instead.
+            if let Some(path) = diff::file_header_path(line) {
+                current_file = Some(path.to_string());
+            }
             result.push(DiffLine {
                 file_path: current_file.clone(),
                 line_number: None,
@@ -240,28 +370,10 @@ fn parse_diff(diff: &str) -> Vec<DiffLine> {
                 line_type: DiffLineType::Header,
             });
         } else if line.starts_with("@@") {
-            // Parse hunk header: @@ -old_start,count +new_start,count @@
-            if let Some(minus_part) = line.split('-').nth(1) {
-                if let Some(start_str) = minus_part
-                    .split(',')
-                    .next()
-                    .or_else(|| minus_part.split(' ').next())
-                {
-                    if let Ok(start) = start_str.parse::<u32>() {
-                        old_line_num = start;
-                    }
-                }
-            }
-            if let Some(plus_part) = line.split('+').nth(1) {
-                if let Some(start_str) = plus_part
-                    .split(',')
-                    .next()
-                    .or_else(|| plus_part.split(' ').next())
-                {
-                    if let Ok(start) = start_str.parse::<u32>() {
-                        new_line_num = start;
-                    }
-                }
+            in_file_header = false;
+            if let Some((old_start, new_start)) = diff::parse_hunk_header(line) {
+                old_line_num = old_start;
+                new_line_num = new_start;
             }
             result.push(DiffLine {
                 file_path: current_file.clone(),
@@ -269,7 +381,14 @@ fn parse_diff(diff: &str) -> Vec<DiffLine> {
                 old_line_number: None,
                 line_type: DiffLineType::Hunk,
             });
-        } else if line.starts_with('+') && !line.starts_with("+++") {
+        } else if in_file_header {
+            result.push(DiffLine {
+                file_path: current_file.clone(),
+                line_number: None,
+                old_line_number: None,
+                line_type: DiffLineType::Other,
+            });
+        } else if line.starts_with('+') {
             result.push(DiffLine {
                 file_path: current_file.clone(),
                 line_number: Some(new_line_num),
@@ -277,7 +396,7 @@ fn parse_diff(diff: &str) -> Vec<DiffLine> {
                 line_type: DiffLineType::Added,
             });
             new_line_num += 1;
-        } else if line.starts_with('-') && !line.starts_with("---") {
+        } else if line.starts_with('-') {
             result.push(DiffLine {
                 file_path: current_file.clone(),
                 line_number: None,
@@ -346,34 +465,79 @@ impl DiffTreeNode {
 /// Split a unified diff into per-file sections keyed by the target file path.
 fn parse_diff_file_sections(diff: &str) -> Vec<FileDiffSection> {
     let mut sections = Vec::new();
-    let mut current_path: Option<String> = None;
-    let mut current_diff = String::new();
+    let mut current_diff: Option<String> = None;
 
-    for line in diff.lines() {
-        if line.starts_with("diff --git ") {
-            if let Some(path) = current_path.take() {
+    let mut finish = |section: Option<String>| {
+        if let Some(section) = section {
+            if let Some(path) = diff::section_path(&section) {
                 sections.push(FileDiffSection {
-                    path,
-                    diff: std::mem::take(&mut current_diff),
+                    path: path.to_string(),
+                    diff: section,
                 });
             }
-            current_path = line.split(" b/").nth(1).map(|path| path.to_string());
         }
+    };
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            finish(current_diff.replace(String::new()));
+        }
+        if let Some(current) = current_diff.as_mut() {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+    finish(current_diff);
+
+    sections
+}
 
-        if current_path.is_some() {
-            current_diff.push_str(line);
-            current_diff.push('\n');
+/// File-path filter for the diff view, set with `:only <glob>...`.
+/// Patterns prefixed with `!` exclude matching files.
+#[derive(Debug, Clone, PartialEq)]
+struct DiffPathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl DiffPathFilter {
+    fn parse(spec: &str) -> Option<Self> {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        for pattern in spec.split([' ', ',']).filter(|p| !p.is_empty()) {
+            match pattern.strip_prefix('!') {
+                Some(excluded) if !excluded.is_empty() => exclude.push(excluded.to_string()),
+                Some(_) => {}
+                None => include.push(pattern.to_string()),
+            }
+        }
+        if include.is_empty() && exclude.is_empty() {
+            None
+        } else {
+            Some(Self { include, exclude })
         }
     }
 
-    if let Some(path) = current_path {
-        sections.push(FileDiffSection {
-            path,
-            diff: current_diff,
-        });
+    fn matches(&self, path: &str) -> bool {
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| filters::path_glob_match(pattern, path));
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| filters::path_glob_match(pattern, path))
     }
 
-    sections
+    fn label(&self) -> String {
+        self.include
+            .iter()
+            .cloned()
+            .chain(self.exclude.iter().map(|pattern| format!("!{}", pattern)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 fn flatten_diff_tree(node: &DiffTreeNode, depth: usize, out: &mut Vec<DiffTreeItem>) {
@@ -412,16 +576,97 @@ fn build_diff_tree_items(sections: &[FileDiffSection]) -> Vec<DiffTreeItem> {
     items
 }
 
+/// A PR head's diff before generated files are collapsed, with the matcher
+/// built from its `.gitattributes`, so toggling `H` re-filters without a refetch.
+struct GeneratedDiffSource {
+    head: String,
+    diff: String,
+    matcher: diff::GeneratedFileMatcher,
+}
+
 enum AsyncResult {
     Details(usize, Result<PullRequest, String>), // (pr_index, fully populated PR details)
-    Diff(usize, String, Option<String>, bool), // (pr_index, diff_content, delta_output, delta_too_large)
-    Comments(usize, Vec<Comment>),             // (pr_index, comments)
-    ReviewComments(usize, Vec<ReviewComment>), // (pr_index, review comments with diff context)
-    Checks(usize, Vec<gh::CheckStatus>),       // (pr_index, CI checks)
-    AiLaunch(Result<String, String>),          // worktree path or error
-    AgentPreview(usize, AgentPreview),         // (pr_index, tmux preview)
-    Refresh(AppMode, gh::PullRequestPage),     // refreshed first page
-    NextPage(AppMode, String, gh::PullRequestPage), // (mode, requested cursor, appended next page)
+    Diff(
+        usize,
+        String,
+        Option<String>,
+        bool,
+        usize,
+        Option<Arc<GeneratedDiffSource>>,
+    ), // (pr_index, diff_content, delta_output, delta_too_large, generated_collapsed, unfiltered source)
+    Delta(usize, u16, Option<String>), // (pr_index, width, delta output re-rendered for a new terminal width)
+    FileList(usize, Result<Vec<gh::ChangedFile>, String>), // (pr_index, changed files of a large PR)
+    FileDiff(usize, String, Result<String, String>),       // (pr_index, path, single-file diff)
+    Comments(usize, Vec<Comment>),                         // (pr_index, comments)
+    ReviewComments(
+        usize,
+        Vec<ReviewComment>,
+        BTreeMap<u64, gh::ReviewThreadState>,
+    ), // (pr_index, review comments with diff context, thread resolution by first comment)
+    Feedback(usize, Result<gh::PrFeedback, String>), // (pr_index, outstanding review feedback)
+    Checks(usize, Vec<gh::CheckStatus>),             // (pr_index, CI checks)
+    ChangedFiles(usize, Result<Vec<gh::ChangedFile>, String>), // (pr_index, files for the Files tab)
+    ChangedFileDiff(usize, String, Result<String, String>), // (pr_index, path, diff of that file)
+    Commits(usize, Result<Vec<gh::PrCommit>, String>),      // (pr_index, commits, oldest first)
+    CommitDiff(usize, String, Result<String, String>),      // (pr_index, sha, diff of that commit)
+    Protection(usize, Result<Option<gh::BranchProtection>, String>), // (pr_index, base branch rules)
+    Checklist(usize, Result<Vec<ChecklistItem>, String>),            // (pr_index, repo checklist)
+    AuthorStats(String, Result<gh::AuthorStats, String>), // (repo#author key, author history)
+    ReviewQueues(Result<Vec<gh::ReviewQueue>, String>),   // open review requests per teammate
+    GlobalSearch(u64, Vec<GlobalMatch>), // (search generation, diff matches for one PR)
+    AiLaunch(String, Result<String, String>), // (AI display name, worktree path or error)
+    AgentPreview(usize, AgentPreview),   // (pr_index, tmux preview)
+    Refresh(u64, gh::PullRequestPage),   // (list generation, refreshed first page)
+    NextPage(u64, String, gh::PullRequestPage), // (list generation, requested cursor, appended next page)
+    Action(u64, Result<String, String>),        // (pending action id, merge method or error)
+    Refetched(Option<usize>, Result<PullRequest, String>), // (list position it was removed from, PR after an action)
+    FileContents(usize, String, Option<u32>, Result<Option<String>, String>), // (pr_index, path, focused line, file at the PR head)
+    Blame(usize, String, u32, Result<gh::BlameLine, String>), // (pr_index, path, old line, last commit to touch it)
+    EditorWorktree(String, u32, Result<PathBuf, String>), // (path, line, worktree created to open it in)
+    Discussions(Result<Vec<(String, gh::Discussion)>, String>), // (repo#number, comment counts) for listed PRs
+    Triage(usize, Result<gh::PrTriage, String>), // (pr_index, milestone and project boards)
+    Milestones(usize, Result<Vec<String>, String>), // (pr_index, the repo's open milestones)
+    Labels(usize, Result<Vec<gh::Label>, String>), // (pr_index, the repo's labels)
+    Permission(String, Result<gh::RepoPermission, String>), // (repo, my access level on it)
+}
+
+impl AsyncResult {
+    fn label(&self) -> &'static str {
+        match self {
+            AsyncResult::Details(..) => "details",
+            AsyncResult::Diff(..) => "diff",
+            AsyncResult::Delta(..) => "delta",
+            AsyncResult::FileList(..) => "file_list",
+            AsyncResult::FileDiff(..) => "file_diff",
+            AsyncResult::Comments(..) => "comments",
+            AsyncResult::ReviewComments(..) => "review_comments",
+            AsyncResult::Feedback(..) => "feedback",
+            AsyncResult::Checks(..) => "checks",
+            AsyncResult::ChangedFiles(..) => "changed_files",
+            AsyncResult::ChangedFileDiff(..) => "changed_file_diff",
+            AsyncResult::Commits(..) => "commits",
+            AsyncResult::CommitDiff(..) => "commit_diff",
+            AsyncResult::Protection(..) => "protection",
+            AsyncResult::Checklist(..) => "checklist",
+            AsyncResult::AuthorStats(..) => "author_stats",
+            AsyncResult::ReviewQueues(..) => "review_queues",
+            AsyncResult::GlobalSearch(..) => "global_search",
+            AsyncResult::AiLaunch(..) => "ai_launch",
+            AsyncResult::AgentPreview(..) => "agent_preview",
+            AsyncResult::Refresh(..) => "refresh",
+            AsyncResult::NextPage(..) => "next_page",
+            AsyncResult::Action(..) => "action",
+            AsyncResult::Refetched(..) => "refetched",
+            AsyncResult::FileContents(..) => "file_contents",
+            AsyncResult::Blame(..) => "blame",
+            AsyncResult::EditorWorktree(..) => "editor_worktree",
+            AsyncResult::Discussions(..) => "discussions",
+            AsyncResult::Triage(..) => "triage",
+            AsyncResult::Milestones(..) => "milestones",
+            AsyncResult::Labels(..) => "labels",
+            AsyncResult::Permission(..) => "permission",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -434,10 +679,31 @@ pub enum View {
 pub enum DetailTab {
     Description,
     Diff,
+    Files,
     Comments,
+    Commits,
+    Checks,
+    Feedback,
+    Ai,
     Agent,
 }
 
+impl DetailTab {
+    fn title(self) -> &'static str {
+        match self {
+            DetailTab::Description => "Description",
+            DetailTab::Diff => "Diff",
+            DetailTab::Files => "Files",
+            DetailTab::Comments => "Comments",
+            DetailTab::Commits => "Commits",
+            DetailTab::Checks => "Checks",
+            DetailTab::Feedback => "Feedback",
+            DetailTab::Ai => "AI",
+            DetailTab::Agent => "Agent",
+        }
+    }
+}
+
 /// App mode - determines what PRs are shown
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppMode {
@@ -447,6 +713,19 @@ pub enum AppMode {
     MyPrs,
     /// Watching mode: PRs from daemon-configured watched repos
     Watching,
+    /// Dependencies mode: Dependabot/Renovate PRs grouped by package
+    Dependencies,
+}
+
+impl From<ViewTab> for AppMode {
+    fn from(tab: ViewTab) -> Self {
+        match tab {
+            ViewTab::Review => Self::Review,
+            ViewTab::MyPrs => Self::MyPrs,
+            ViewTab::Watching => Self::Watching,
+            ViewTab::Dependencies => Self::Dependencies,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -455,11 +734,26 @@ pub enum InputMode {
     Comment,
     LineComment, // Comment on a specific line in diff
     ConfirmApprove,
-    ConfirmClose, // Confirm close with optional comment
-    ConfirmMerge, // Confirm merge (squash)
-    Search,       // Searching in diff
-    ListSearch,   // Searching in PR list
-    GotoLine,     // Jump to specific line
+    ConfirmClose,  // Confirm close with optional comment
+    ConfirmMerge,  // Confirm merge (squash)
+    Search,        // Searching in diff
+    ListSearch,    // Searching in PR list
+    ListFilter,    // Typing the repo/author/label/draft/size filter for the list
+    GotoLine,      // Jump to specific line
+    Checklist,     // Review checklist overlay
+    Snooze,        // Pick how long to hide a PR from the list
+    Workload,      // Teammate review queues, to pick who reviews the PR
+    ConfirmGroup,  // Approve + auto-merge every PR in a dependency group
+    GlobalSearch,  // Typing a term to search every PR in the list
+    GlobalResults, // Matches across all PRs in the list
+    StatusLog,     // Every status and error message from this session
+    FileView,      // A whole file at the PR head
+    Milestone,     // Pick the PR's milestone
+    Labels,        // Check the labels the PR should carry
+    Reviewers,     // Request or re-request a reviewer on the PR
+    AiChoice,      // Pick the AI provider/profile to re-run the review with
+    ViewPicker,    // Pick a saved list view
+    ReviewSummary, // Submit the queued line comments as one review
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -469,12 +763,212 @@ enum SearchScope {
     TreeContents,
 }
 
+/// A file to open in the editor once the TUI has suspended
+#[derive(Debug, Clone)]
+struct EditorLaunch {
+    worktree: PathBuf,
+    file: String,
+    line: u32,
+}
+
+/// A comment handed to `$EDITOR` from the comment input with Tab
+#[derive(Debug, Clone)]
+struct CommentDraft {
+    pr: PullRequest,
+    line: Option<LineCommentContext>, // Set for line comments
+    reply: Option<ReplyTarget>,       // Set for replies to a review thread
+    text: String,
+}
+
+/// The review thread a comment replies to
+#[derive(Debug, Clone)]
+struct ReplyTarget {
+    comment_id: u64,  // First comment of the thread
+    location: String, // path:line, for titles and status messages
+}
+
+/// Threads whose latest comment is not `username`'s, by the id of their first comment.
+fn threads_awaiting_reply(comments: &[ReviewComment], username: &str) -> Vec<u64> {
+    review_threads(comments)
+        .into_iter()
+        .filter(|thread| {
+            let latest = comments
+                .iter()
+                .filter(|c| c.id == thread.id || c.in_reply_to_id == Some(thread.id))
+                .max_by_key(|c| c.created_at);
+            latest
+                .and_then(|c| c.user.as_ref())
+                .and_then(|user| user.login.as_deref())
+                != Some(username)
+        })
+        .map(|thread| thread.id)
+        .collect()
+}
+
+/// Someone the reviewers panel can request: a reviewer of the PR, a teammate, or the login
+/// being typed.
+#[derive(Debug, Clone, PartialEq)]
+struct ReviewerRow {
+    login: String,
+    state: Option<gh::ReviewerState>, // `None` until they are requested or review
+    stale: bool,
+}
+
+/// The PR's reviewers, then teammates not on it yet, matching `query`. A query naming nobody
+/// listed becomes a last row of its own, so any login can be requested.
+fn reviewer_rows(
+    pr: &PullRequest,
+    team: &[String],
+    username: &str,
+    query: &str,
+) -> Vec<ReviewerRow> {
+    let query = query.trim_start_matches('@');
+    let mut rows: Vec<ReviewerRow> = pr
+        .reviewers
+        .iter()
+        .map(|reviewer| ReviewerRow {
+            login: reviewer.login.clone(),
+            state: Some(reviewer.state),
+            stale: reviewer.stale,
+        })
+        .collect();
+    for login in team {
+        if !rows.iter().any(|row| row.login.eq_ignore_ascii_case(login)) {
+            rows.push(ReviewerRow {
+                login: login.clone(),
+                state: None,
+                stale: false,
+            });
+        }
+    }
+    let needle = query.to_lowercase();
+    rows.retain(|row| {
+        row.login != username
+            && row.login != pr.author
+            && row.login.to_lowercase().contains(&needle)
+    });
+    if !query.is_empty() && !rows.iter().any(|row| row.login.eq_ignore_ascii_case(query)) {
+        rows.push(ReviewerRow {
+            login: query.to_string(),
+            state: None,
+            stale: false,
+        });
+    }
+    rows
+}
+
+/// Reviewers of my PR whose review is in (commented or changes requested), so there's
+/// something to re-request.
+fn rerequest_candidates(pr: &PullRequest, username: &str) -> Vec<String> {
+    pr.reviewers
+        .iter()
+        .filter(|reviewer| {
+            reviewer.login != username
+                && matches!(
+                    reviewer.state,
+                    gh::ReviewerState::Commented | gh::ReviewerState::ChangesRequested
+                )
+        })
+        .map(|reviewer| reviewer.login.clone())
+        .collect()
+}
+
+/// Top-level review comments, one per thread, in the order the Comments tab lists them.
+fn review_threads(comments: &[ReviewComment]) -> Vec<&ReviewComment> {
+    comments
+        .iter()
+        .filter(|comment| comment.in_reply_to_id.is_none())
+        .collect()
+}
+
+/// Threads anchored on a diff row with the given file and old/new line numbers. A comment
+/// sits on the old line when GitHub says `LEFT`, otherwise on the new one.
+fn threads_on_row<'a>(
+    threads: &[&'a ReviewComment],
+    path: &str,
+    old_line: Option<u32>,
+    new_line: Option<u32>,
+) -> Vec<&'a ReviewComment> {
+    threads
+        .iter()
+        .filter(|comment| {
+            let line = if comment.side.as_deref() == Some("LEFT") {
+                old_line
+            } else {
+                new_line
+            };
+            comment.path == path && comment.line.is_some() && comment.line == line
+        })
+        .copied()
+        .collect()
+}
+
+/// The author line and body of a review thread's comment and its replies.
+fn review_thread_lines(
+    app: &App,
+    comment: &ReviewComment,
+    all: &[ReviewComment],
+) -> Vec<Line<'static>> {
+    let login = |comment: &ReviewComment| {
+        comment
+            .user
+            .as_ref()
+            .and_then(|a| a.login.clone())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+    let mut lines = vec![Line::styled(
+        format!(
+            "  💬 @{} ({})",
+            login(comment),
+            dates::absolute(&app.dates, &comment.created_at)
+        ),
+        Style::default().fg(Color::Cyan).bold(),
+    )];
+    for body_line in comment.body.lines() {
+        lines.push(Line::raw(format!("     {}", body_line)));
+    }
+    for reply in all.iter().filter(|r| r.in_reply_to_id == Some(comment.id)) {
+        lines.push(Line::styled(
+            format!(
+                "     ↳ @{} ({})",
+                login(reply),
+                dates::absolute(&app.dates, &reply.created_at)
+            ),
+            Style::default().fg(Color::Cyan),
+        ));
+        for body_line in reply.body.lines() {
+            lines.push(Line::raw(format!("       {}", body_line)));
+        }
+    }
+    lines
+}
+
+/// A complete file at the PR head, opened from the diff with `f`
+#[derive(Debug, Clone)]
+struct FileView {
+    path: String,
+    lines: Vec<String>,
+    focus: Option<u32>, // New-file line the diff cursor was on
+    scroll: usize,
+}
+
 /// Context for a line-level comment
 #[derive(Debug, Clone)]
 pub struct LineCommentContext {
     pub file_path: String,
     pub line_number: u32,
     pub side: CommentSide,
+    pub start_line: Option<u32>, // First line of a multi-line comment selected with `v`
+}
+
+impl LineCommentContext {
+    /// `path:line`, or `path:start-end` for a multi-line comment
+    fn location(&self) -> String {
+        match self.start_line {
+            Some(start_line) => format!("{}:{}-{}", self.file_path, start_line, self.line_number),
+            None => format!("{}:{}", self.file_path, self.line_number),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -483,6 +977,209 @@ pub enum CommentSide {
     Right, // New file (added/context lines)
 }
 
+/// A GitHub write that runs on a background thread
+#[derive(Debug, Clone)]
+enum PrAction {
+    Approve(Option<String>), // Review body
+    Close {
+        comment: Option<String>,
+        reason: Option<String>, // Close reason template the comment came from
+    },
+    Merge(Option<gh::SquashMessage>), // Squash commit message, GitHub's default if `None`
+    Comment(String),
+    LineComment(LineCommentContext, String),
+    Reply(ReplyTarget, String),
+    RerequestReview(Vec<String>), // Reviewer logins
+    SetThreadResolved {
+        thread_id: String, // GraphQL node id
+        location: String,
+        resolved: bool,
+    },
+    SetMilestone(Option<String>), // `None` clears it
+    SetLabels(Vec<gh::Label>),    // Every label the PR should carry
+    AddToProject(String),
+    SubmitReview(DraftReview, ReviewEvent),
+}
+
+impl PrAction {
+    /// Run against GitHub, returning the merge method for merges.
+    fn run(&self, gh: &dyn GhClient, pr: &PullRequest) -> Result<String> {
+        match self {
+            PrAction::Approve(body) => gh.approve_pr(pr, body.as_deref()).map(|()| String::new()),
+            PrAction::Close { comment, .. } => {
+                gh.close_pr(pr, comment.as_deref()).map(|()| String::new())
+            }
+            PrAction::Merge(message) => gh.merge_pr(pr, true, message.as_ref()),
+            PrAction::Comment(body) => gh.add_pr_comment(pr, body).map(|()| String::new()),
+            PrAction::Reply(target, body) => gh
+                .reply_to_review_comment(pr, target.comment_id, body)
+                .map(|()| String::new()),
+            PrAction::RerequestReview(logins) => logins
+                .iter()
+                .try_for_each(|login| gh.request_reviewer(pr, login))
+                .map(|()| String::new()),
+            PrAction::SetThreadResolved {
+                thread_id,
+                resolved,
+                ..
+            } => gh
+                .set_review_thread_resolved(thread_id, *resolved)
+                .map(|()| String::new()),
+            PrAction::LineComment(ctx, body) => {
+                let side = match ctx.side {
+                    CommentSide::Left => "LEFT",
+                    CommentSide::Right => "RIGHT",
+                };
+                check_comment_targets(
+                    gh,
+                    pr,
+                    [(
+                        ctx.file_path.as_str(),
+                        ctx.start_line,
+                        ctx.line_number,
+                        side,
+                    )],
+                )?;
+                gh.add_line_comment(
+                    pr,
+                    &ctx.file_path,
+                    ctx.start_line,
+                    ctx.line_number,
+                    side,
+                    body,
+                )
+                .map(|()| String::new())
+            }
+            PrAction::SetMilestone(milestone) => gh
+                .set_milestone(pr, milestone.as_deref())
+                .map(|()| String::new()),
+            PrAction::SetLabels(labels) => {
+                let has = |labels: &[gh::Label], name: &str| labels.iter().any(|l| l.name == name);
+                let add: Vec<String> = labels
+                    .iter()
+                    .filter(|label| !has(&pr.labels, &label.name))
+                    .map(|label| label.name.clone())
+                    .collect();
+                let remove: Vec<String> = pr
+                    .labels
+                    .iter()
+                    .filter(|label| !has(labels, &label.name))
+                    .map(|label| label.name.clone())
+                    .collect();
+                gh.edit_labels(pr, &add, &remove).map(|()| String::new())
+            }
+            PrAction::AddToProject(project) => {
+                gh.add_to_project(pr, project).map(|()| String::new())
+            }
+            PrAction::SubmitReview(draft, event) => {
+                check_comment_targets(
+                    gh,
+                    pr,
+                    draft.comments.iter().map(|comment| {
+                        (
+                            comment.path.as_str(),
+                            comment.start_line,
+                            comment.line,
+                            comment.side.as_str(),
+                        )
+                    }),
+                )?;
+                gh.submit_review(pr, draft, *event).map(|()| String::new())
+            }
+        }
+    }
+
+    fn progress_message(&self, pr: &PullRequest) -> String {
+        match self {
+            PrAction::Approve(_) => format!("Approving PR #{}...", pr.number),
+            PrAction::Close { .. } => format!("Closing PR #{}...", pr.number),
+            PrAction::Merge(_) => format!("Merging PR #{}...", pr.number),
+            PrAction::Comment(_) | PrAction::LineComment(..) => {
+                format!("Posting comment on #{}...", pr.number)
+            }
+            PrAction::Reply(target, _) => format!("Replying on {}...", target.location),
+            PrAction::RerequestReview(_) => format!("Re-requesting review on #{}...", pr.number),
+            PrAction::SetThreadResolved {
+                location, resolved, ..
+            } => format!(
+                "{} thread on {}...",
+                if *resolved {
+                    "Resolving"
+                } else {
+                    "Unresolving"
+                },
+                location
+            ),
+            PrAction::SetMilestone(_) => format!("Setting milestone on #{}...", pr.number),
+            PrAction::SetLabels(_) => format!("Updating labels on #{}...", pr.number),
+            PrAction::AddToProject(project) => {
+                format!("Adding #{} to {}...", pr.number, project)
+            }
+            PrAction::SubmitReview(..) => format!("Submitting review on #{}...", pr.number),
+        }
+    }
+
+    fn failure_label(&self) -> &'static str {
+        match self {
+            PrAction::Approve(_) => "Approve failed",
+            PrAction::Close { .. } => "Close failed",
+            PrAction::Merge(_) => "Merge failed",
+            PrAction::Comment(_) | PrAction::LineComment(..) => "Comment failed",
+            PrAction::Reply(..) => "Reply failed",
+            PrAction::RerequestReview(_) => "Re-requesting review failed",
+            PrAction::SetThreadResolved { resolved: true, .. } => "Resolving thread failed",
+            PrAction::SetThreadResolved {
+                resolved: false, ..
+            } => "Unresolving thread failed",
+            PrAction::SetMilestone(_) => "Setting milestone failed",
+            PrAction::SetLabels(_) => "Updating labels failed",
+            PrAction::AddToProject(_) => "Adding to project failed",
+            PrAction::SubmitReview(..) => "Review failed",
+        }
+    }
+}
+
+/// Check that each (path, start line, line, side) comment target is on a line of the PR's
+/// diff as GitHub has it, so a stale or unchanged line is reported instead of the reviews
+/// API rejecting it. A patch that can't be fetched, or that GitHub omits (binary or
+/// oversized files), is left for the API to judge.
+fn check_comment_targets<'a>(
+    gh: &dyn GhClient,
+    pr: &PullRequest,
+    targets: impl IntoIterator<Item = (&'a str, Option<u32>, u32, &'a str)>,
+) -> Result<()> {
+    let mut patches: BTreeMap<&str, Option<String>> = BTreeMap::new();
+    for (path, start_line, line, side) in targets {
+        let patch = patches.entry(path).or_insert_with(|| {
+            gh.get_pr_file_diff(pr, path)
+                .ok()
+                .filter(|patch| !patch.trim().is_empty())
+        });
+        let Some(patch) = patch else {
+            continue;
+        };
+        for line in start_line.into_iter().chain([line]) {
+            if !diff::patch_has_line(patch, line, side) {
+                let old = if side == "LEFT" { " (old)" } else { "" };
+                anyhow::bail!(
+                    "{}:{}{} is not part of the diff; comment on a changed or context line",
+                    path,
+                    line,
+                    old
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An action in flight, with what is needed to undo its optimistic UI update
+struct PendingAction {
+    action: PrAction,
+    pr: PullRequest,
+    removed_at: Option<usize>, // List position the PR was removed from while the action runs
+}
+
 pub struct App {
     pub prs: Vec<PullRequest>,
     pub repos_root: PathBuf,
@@ -494,46 +1191,167 @@ pub struct App {
     pub view: View,
     pub detail_tab: DetailTab,
     pub scroll_offset: u16,
+    diff_cursor: u16,           // Focused diff line, moving within the viewport
+    content_lines: Option<u16>, // Rendered height of the detail content, once loaded
+    viewport_height: u16,       // Content rows visible in the detail view, for paging
     pub diff_cache: Option<String>,
     pub delta_cache: Option<String>, // Pre-processed delta output (ANSI)
     pub use_delta: bool,             // Whether to use delta for rendering
+    delta_width: u16,                // Terminal width delta_cache was rendered for
+    pending_resize: Option<(u16, std::time::Instant)>, // Latest resize, re-rendered once it settles
     pub diff_lines: Vec<DiffLine>,   // Parsed diff with line info
     pub delta_line_info: Vec<DeltaLineInfo>, // Parsed delta output line info
     diff_tree_enabled: bool,         // Whether tree mode is enabled in Diff tab
     delta_too_large: bool,           // Delta fallback happened because diff is too large
-    file_diff_sections: Vec<FileDiffSection>, // Per-file sections from unified diff
+    per_file_diff: bool, // Large PR: only the file list is loaded, diffs are fetched per file
+    loading_file_diff: Option<String>, // File whose diff is being fetched in per-file mode
+    loading_file_context: usize, // Extra context lines requested with `loading_file_diff`
+    file_context_lines: usize, // Extra context lines shown around the selected file's hunks
+    loading_file_view: Option<String>, // File whose full contents are being fetched
+    file_view: Option<FileView>, // Full file open in the viewer
+    file_line_stats: BTreeMap<String, (u64, u64)>, // Per-file (additions, deletions) in per-file mode
+    file_diff_sections: Vec<FileDiffSection>,      // Per-file sections from unified diff
     file_tree_items: Vec<DiffTreeItem>, // Hierarchical file tree for navigating diff files
-    file_tree_state: ListState,      // Selection state for file tree
+    file_tree_state: ListState,         // Selection state for file tree
     selected_file_diff_path: Option<String>, // Currently selected file when viewing a single-file diff
     filtered_diff_cache: Option<String>, // Current single-file diff content (if selected from tree)
     filtered_diff_lines: Vec<DiffLine>,  // Parsed line info for current single-file diff
+    path_filter: Option<DiffPathFilter>, // File filter set via `:only`, kept across PRs
+    path_filter_enabled: bool,           // Toggled with `F` without forgetting the patterns
+    collapsed_files: BTreeSet<String>, // Files folded to a stub in the full diff, for `collapsed_files_pr`
+    collapsed_files_pr: String,        // PR key the collapsed files belong to
     pub comments_cache: Option<Vec<Comment>>,
     pub review_comments_cache: Option<Vec<ReviewComment>>,
+    pub feedback_cache: Option<Result<gh::PrFeedback, String>>,
     pub checks_cache: Option<Vec<gh::CheckStatus>>,
+    checks_state: ListState, // Check selected in the Checks tab
+    changed_files_cache: Option<Result<Vec<gh::ChangedFile>, String>>,
+    changed_files_state: ListState, // File selected in the Files tab
+    changed_file_diff: Option<(String, Result<String, String>)>, // (path, diff) shown instead of the file list
+    commits_cache: Option<Result<Vec<gh::PrCommit>, String>>,
+    commits_state: ListState, // Commit selected in the Commits tab
+    commit_diff: Option<(String, Result<String, String>)>, // (sha, diff) shown instead of the commit list
+    protection_cache: Option<Option<gh::BranchProtection>>, // Base branch protection, `Some(None)` if unprotected
+    merge_protection: Option<gh::BranchProtection>,         // Protection shown in the merge dialog
+    merge_message: Option<gh::SquashMessage>, // Squash commit message previewed in the merge dialog
+    merge_unmet_checks: Vec<String>, // Required checks that haven't passed; the dialog refuses to merge
+    close_reason_idx: Option<usize>, // Close reason template picked with Tab in the close dialog
+    editing_approve_message: bool,   // Typing the optional message in the approve dialog
+    checklist_cache: Option<Vec<ChecklistItem>>, // Repo review checklist with this review's checks
+    author_stats_cache: BTreeMap<String, Option<gh::AuthorStats>>, // Per repo#author, kept for the session
+    checklist_state: ListState,
+    snoozes: Snoozes,   // PRs hidden from the list until a chosen time
+    show_snoozed: bool, // Toggled with `Z` to list snoozed PRs again
+    my_approvals: bool, // My PRs tab lists PRs I approved that are not merged yet
+    show_bots: bool,    // Review tab keeps PRs by `list.bots` authors
+    dependency_group: Vec<PullRequest>, // PRs bumping the selected package, pending confirmation
+    global_search_diffs: bool, // Global search also fetches and greps each PR's diff
+    global_search_term: String,
+    global_search_generation: u64, // Drops diff results from a superseded search
+    global_search_pending: usize,  // Diffs still being searched
+    global_matches: Vec<GlobalMatch>,
+    global_match_state: ListState,
+    review_queues: Option<(std::time::Instant, Vec<gh::ReviewQueue>)>, // Cached per-teammate counts
+    workload_state: ListState,
     pub agent_preview_cache: Option<AgentPreview>,
+    ai_results: Option<ReviewResults>, // Results an AI session wrote to the PR worktree
+    ai_results_checked_at: Option<std::time::Instant>,
+    ai_finding_state: ListState,
+    pending_diff_jump: Option<(String, Option<u32>)>, // AI finding to jump to once the diff loads
     pub input_mode: InputMode,
     pub input_buffer: String,
     pub line_comment_ctx: Option<LineCommentContext>, // For line-level comments
+    reply_target: Option<ReplyTarget>,                // Review thread the comment input replies to
+    selected_thread: Option<usize>,                   // Review thread selected in the Comments tab
+    inline_comments_expanded: bool, // Review threads on the focused diff line are shown under it
+    review_thread_states: BTreeMap<u64, gh::ReviewThreadState>, // Resolution by first comment id
+    scroll_to_thread: bool,         // Scroll the selected thread into view on next draw
+    rerequest_warned: bool, // R was pressed with threads awaiting a reply; the next R goes ahead
+    pending_reviews: BTreeMap<String, DraftReview>, // repo#number -> line comments queued after `s`, submitted as one review
+    review_event: ReviewEvent, // What the review summary dialog submits the review as
+    diff_column: CommentSide,  // Side-by-side column (h/l) that line comments target
+    diff_selection_anchor: Option<u16>, // Diff row where a `v` selection for a multi-line comment started
     pub ai: AiConfig,
+    confirm: ConfirmConfig,
+    diff_config: DiffConfig,
+    checklist_config: ChecklistConfig,
+    team: TeamConfig,
+    editor: EditorConfig,
+    project: ProjectConfig,
+    keys: KeysConfig, // Rebound keys for the list and PR view
+    list_config: ListConfig,
+    collapsed_repos: BTreeSet<String>, // Repos folded to their header while grouping by repo
+    folded_prs: Vec<PullRequest>,      // PRs of collapsed repos, kept out of `prs` until expanded
+    previewed_pr: Option<String>,      // PR whose details the preview pane last asked for
+    drafts_config: DraftsConfig,       // Per-repo exceptions to `include_drafts`
+    dates: DatesConfig,                // Timestamp rendering; `T` flips `style` for the session
+    views: Vec<ViewConfig>,
+    active_view: Option<ViewConfig>, // Saved view filtering and sorting the list, if any
+    view_state: ListState,           // Selection in the view picker; row 0 is "no view"
+    approved_prs: BTreeSet<String>, // repo#number of PRs approved this session, kept per `list.approved`
+    viewed_prs: BTreeSet<String>,   // repo#number of PRs opened this session, audited once each
+    session_started_at: DateTime<Utc>, // Start of the audit log window `S` summarizes
+    daemon_reviews: BTreeMap<String, ReviewedPrRecord>, // repo#number -> what the daemon did, re-read on refresh
+    read_only: bool, // Mutating actions are refused (`read_only` config or --read-only)
+    read_only_forced: bool, // Set by --read-only, so a config reload can't lift it
+    accessible: bool, // Text markers instead of color-only signals, no background fills
+    triage_cache: Option<gh::PrTriage>, // Milestone and project boards of the open PR
+    loading_triage: bool,
+    milestone_choices: Option<Vec<Option<String>>>, // Open milestones, then `None` to clear
+    milestone_state: ListState,
+    label_choices: Option<Vec<gh::Label>>, // The repo's labels, once loaded
+    label_picks: BTreeSet<String>,         // Names checked in the label picker
+    label_state: ListState,
+    reviewer_query: String, // Filter, or a login to request, typed in the reviewers panel
+    reviewer_state: ListState,
+    show_generated: bool, // Reveal generated files instead of collapsing them
+    generated_collapsed: usize, // Generated files collapsed in the current diff
+    generated_source: Option<Arc<GeneratedDiffSource>>, // Unfiltered diff `H` re-filters locally
+    merge_requires_typed: bool, // Merge dialog expects the PR number to be typed
     // Search state
     pub search_query: String,
     pub search_matches: Vec<usize>, // Line indices that match
     pub search_match_idx: usize,    // Current match index
     search_scope: SearchScope,
+    search_case_sensitive: bool,
+    list_search_history: SearchHistory,
+    diff_search_history: SearchHistory,
     pub status_message: Option<String>,
     pub status_time: Option<std::time::Instant>,
+    status_log: Vec<(DateTime<Local>, String)>, // Oldest first, capped at STATUS_LOG_LIMIT
+    status_log_scroll: u16,
+    pending_actions: BTreeMap<u64, PendingAction>, // Approve/comment/merge/close calls in flight
+    closed_prs: BTreeMap<String, &'static str>, // repo#number -> merged/closed/deleted, seen from failed actions
+    discussions: BTreeMap<String, gh::Discussion>, // repo#number -> comment and unresolved-thread counts
+    repo_permissions: BTreeMap<String, gh::RepoPermission>, // repo -> my access level, fetched once per session
+    loading_permissions: BTreeSet<String>,
+    next_action_id: u64,
     pub should_quit: bool,
     // Async loading
+    gh: Arc<dyn GhClient>,
     async_tx: Sender<AsyncResult>,
     async_rx: Receiver<AsyncResult>,
     loading_diff: bool,
     loading_comments: bool,
     loading_review_comments: bool,
+    loading_feedback: bool,
     loading_checks: bool,
+    loading_changed_files: bool,
+    loading_changed_file_diff: Option<String>, // Path of the Files tab file whose diff is being fetched
+    loading_commits: bool,
+    loading_commit_diff: Option<String>, // Sha of the commit whose diff is being fetched
+    loading_protection: bool,
+    loading_checklist: bool,
+    loading_author_stats: bool,
+    loading_review_queues: bool,
     loading_details: bool,
     loading_agent_preview: bool,
     loading_next_page: bool,
     refreshing: bool,
+    refresh_query: Option<ListQuery>, // What the in-flight refresh fetches
+    refresh_queued: bool, // The list changed under the in-flight refresh; fetch again when it lands
+    list_generation: u64, // Bumped per list fetch; results from older ones are dropped
+    fetch_errors: Vec<gh::RepoFetchError>, // Repos the list could not be fetched from
     next_page_cursor: Option<String>,
     has_next_page: bool,
     // Screen state
@@ -541,19 +1359,45 @@ pub struct App {
     needs_redraw: bool,
     // AI launch state
     launching_ai: bool,
+    ai_choices: Vec<(String, AiConfig)>, // Labelled setups offered by Ctrl+r
+    ai_choice_state: ListState,
     pending_agent_attach_target: Option<String>,
+    creating_editor_worktree: bool,
+    pending_editor_launch: Option<EditorLaunch>,
+    pending_comment_draft: Option<CommentDraft>,
+    pending_merge_message: Option<String>, // Squash commit message handed to `$EDITOR`
     // Syntax highlighter for diff rendering
     syntax_highlighter: SyntaxHighlighter,
+    // Config hot-reload
+    config_watcher: ConfigWatcher,
+    config_checked_at: std::time::Instant,
 }
 
 impl App {
     pub fn new(
+        cfg: &Config,
+        repos_root: PathBuf,
+        username: String,
+        include_drafts: bool,
+        mode: AppMode,
+    ) -> Self {
+        Self::with_client(
+            cfg,
+            repos_root,
+            username,
+            include_drafts,
+            mode,
+            Arc::new(GhCli),
+        )
+    }
+
+    fn with_client(
+        cfg: &Config,
         repos_root: PathBuf,
         username: String,
         include_drafts: bool,
-        exclude_users: Vec<String>,
-        ai: AiConfig,
         mode: AppMode,
+        gh: Arc<dyn GhClient>,
     ) -> Self {
         let (async_tx, async_rx) = mpsc::channel();
         Self {
@@ -561,69 +1405,214 @@ impl App {
             repos_root,
             username,
             include_drafts,
-            exclude_users,
+            exclude_users: cfg.exclude_users.clone(),
             mode,
             list_state: ListState::default(),
             view: View::List,
             detail_tab: DetailTab::Description,
             scroll_offset: 0,
+            diff_cursor: 0,
+            content_lines: None,
+            viewport_height: DEFAULT_PAGE_LINES,
             diff_cache: None,
             delta_cache: None,
-            use_delta: true, // Use delta by default if available
+            use_delta: !cfg.accessible, // Delta's side-by-side view marks changes by color alone
+            delta_width: 0,
+            pending_resize: None,
             diff_lines: Vec::new(),
             delta_line_info: Vec::new(),
             diff_tree_enabled: false,
             delta_too_large: false,
+            per_file_diff: false,
+            loading_file_diff: None,
+            loading_file_context: 0,
+            file_context_lines: 0,
+            loading_file_view: None,
+            file_view: None,
+            file_line_stats: BTreeMap::new(),
             file_diff_sections: Vec::new(),
             file_tree_items: Vec::new(),
             file_tree_state: ListState::default(),
             selected_file_diff_path: None,
             filtered_diff_cache: None,
             filtered_diff_lines: Vec::new(),
+            path_filter: None,
+            path_filter_enabled: false,
+            collapsed_files: BTreeSet::new(),
+            collapsed_files_pr: String::new(),
             comments_cache: None,
             review_comments_cache: None,
+            feedback_cache: None,
             checks_cache: None,
+            protection_cache: None,
+            merge_protection: None,
+            merge_message: None,
+            merge_unmet_checks: Vec::new(),
+            close_reason_idx: None,
+            editing_approve_message: false,
+            checklist_cache: None,
+            author_stats_cache: BTreeMap::new(),
+            checklist_state: ListState::default(),
+            checks_state: ListState::default(),
+            changed_files_cache: None,
+            changed_files_state: ListState::default(),
+            changed_file_diff: None,
+            commits_cache: None,
+            commits_state: ListState::default(),
+            commit_diff: None,
+            snoozes: Snoozes::load(),
+            show_snoozed: false,
+            my_approvals: false,
+            show_bots: false,
+            dependency_group: Vec::new(),
+            global_search_diffs: false,
+            global_search_term: String::new(),
+            global_search_generation: 0,
+            global_search_pending: 0,
+            global_matches: Vec::new(),
+            global_match_state: ListState::default(),
+            review_queues: None,
+            workload_state: ListState::default(),
             agent_preview_cache: None,
+            ai_results: None,
+            ai_results_checked_at: None,
+            ai_finding_state: ListState::default(),
+            pending_diff_jump: None,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
             line_comment_ctx: None,
-            ai,
+            reply_target: None,
+            selected_thread: None,
+            inline_comments_expanded: false,
+            review_thread_states: BTreeMap::new(),
+            scroll_to_thread: false,
+            rerequest_warned: false,
+            pending_reviews: BTreeMap::new(),
+            review_event: ReviewEvent::Comment,
+            diff_column: CommentSide::Right,
+            diff_selection_anchor: None,
+            ai: cfg.ai.clone(),
+            confirm: cfg.confirm.clone(),
+            diff_config: cfg.diff.clone(),
+            checklist_config: cfg.checklist.clone(),
+            team: cfg.team.clone(),
+            editor: cfg.editor.clone(),
+            project: cfg.project.clone(),
+            keys: cfg.keys.clone(),
+            list_config: cfg.list.clone(),
+            collapsed_repos: BTreeSet::new(),
+            folded_prs: Vec::new(),
+            previewed_pr: None,
+            drafts_config: cfg.drafts.clone(),
+            dates: cfg.dates.clone(),
+            views: cfg.views.clone(),
+            active_view: None,
+            view_state: ListState::default(),
+            approved_prs: BTreeSet::new(),
+            viewed_prs: BTreeSet::new(),
+            session_started_at: Utc::now(),
+            daemon_reviews: BTreeMap::new(),
+            read_only: cfg.read_only,
+            read_only_forced: false,
+            accessible: cfg.accessible,
+            triage_cache: None,
+            loading_triage: false,
+            milestone_choices: None,
+            milestone_state: ListState::default(),
+            label_choices: None,
+            label_picks: BTreeSet::new(),
+            label_state: ListState::default(),
+            reviewer_query: String::new(),
+            reviewer_state: ListState::default(),
+            show_generated: false,
+            generated_collapsed: 0,
+            generated_source: None,
+            merge_requires_typed: false,
             search_query: String::new(),
             search_matches: Vec::new(),
             search_match_idx: 0,
             search_scope: SearchScope::Diff,
+            search_case_sensitive: false,
+            list_search_history: SearchHistory::default(),
+            diff_search_history: SearchHistory::default(),
             status_message: None,
             status_time: None,
+            status_log: Vec::new(),
+            status_log_scroll: 0,
+            pending_actions: BTreeMap::new(),
+            closed_prs: BTreeMap::new(),
+            discussions: BTreeMap::new(),
+            repo_permissions: BTreeMap::new(),
+            loading_permissions: BTreeSet::new(),
+            next_action_id: 0,
             should_quit: false,
+            gh,
             async_tx,
             async_rx,
             loading_diff: false,
             loading_comments: false,
             loading_review_comments: false,
+            loading_feedback: false,
             loading_checks: false,
+            loading_changed_files: false,
+            loading_changed_file_diff: None,
+            loading_commits: false,
+            loading_commit_diff: None,
+            loading_protection: false,
+            loading_checklist: false,
+            loading_author_stats: false,
+            loading_review_queues: false,
             loading_details: false,
             loading_agent_preview: false,
             loading_next_page: false,
             refreshing: false,
+            refresh_query: None,
+            refresh_queued: false,
+            list_generation: 0,
+            fetch_errors: Vec::new(),
             next_page_cursor: None,
             has_next_page: false,
             needs_clear: true,
             needs_redraw: true,
             launching_ai: false,
+            ai_choices: Vec::new(),
+            ai_choice_state: ListState::default(),
             pending_agent_attach_target: None,
+            creating_editor_worktree: false,
+            pending_editor_launch: None,
+            pending_comment_draft: None,
+            pending_merge_message: None,
             syntax_highlighter: SyntaxHighlighter::new(),
+            config_watcher: ConfigWatcher::new(cfg),
+            config_checked_at: std::time::Instant::now(),
         }
     }
 
     fn set_status(&mut self, msg: String) {
+        self.log_status(msg.clone());
         self.status_message = Some(msg);
         self.status_time = Some(std::time::Instant::now());
     }
 
+    /// Toast `what: err`, keeping the full error chain for the status log.
+    fn set_error(&mut self, what: &str, err: &anyhow::Error) {
+        self.log_status(format!("{}: {:#}", what, err));
+        self.status_message = Some(format!("{}: {}", what, err));
+        self.status_time = Some(std::time::Instant::now());
+    }
+
+    fn log_status(&mut self, msg: String) {
+        if self.status_log.len() >= STATUS_LOG_LIMIT {
+            self.status_log.remove(0);
+        }
+        self.status_log.push((Local::now(), msg));
+    }
+
     fn check_status_timeout(&mut self) -> bool {
         if let (Some(time), Some(_)) = (self.status_time, &self.status_message) {
-            // Auto-dismiss after 3 seconds, but not while refreshing
-            if !self.refreshing && time.elapsed().as_secs() >= 3 {
+            // Auto-dismiss after 3 seconds, but not while refreshing or acting on a PR
+            if !self.refreshing && self.pending_actions.is_empty() && time.elapsed().as_secs() >= 3
+            {
                 self.status_message = None;
                 self.status_time = None;
                 return true;
@@ -666,6 +1655,71 @@ impl App {
         }
     }
 
+    /// Pick up config file edits every couple of seconds.
+    fn poll_config_reload(&mut self) -> bool {
+        if self.config_checked_at.elapsed().as_secs() < 2 {
+            return false;
+        }
+        self.config_checked_at = std::time::Instant::now();
+
+        let reload = match self.config_watcher.poll() {
+            None => return false,
+            Some(Ok(reload)) => reload,
+            Some(Err(err)) => {
+                self.set_status(format!("Ignoring config change: {:#}", err));
+                return true;
+            }
+        };
+        let Some(summary) = reload.summary() else {
+            return false;
+        };
+        let config = reload.config;
+        self.exclude_users = config.exclude_users;
+        self.ai = config.ai;
+        self.confirm = config.confirm;
+        self.diff_config = config.diff;
+        self.checklist_config = config.checklist;
+        if self.team.members != config.team.members {
+            self.review_queues = None;
+        }
+        self.team = config.team;
+        self.editor = config.editor;
+        self.project = config.project;
+        self.keys = config.keys;
+        let filter_changed = self.list_config.filter != config.list.filter
+            || self.list_config.sort != config.list.sort
+            || reload.previous.fetch != config.fetch;
+        self.list_config = config.list;
+        self.drafts_config = config.drafts;
+        self.dates = config.dates;
+        self.views = config.views;
+        // Pick up edits to the active view; drop it if it was removed.
+        self.active_view = self.active_view.take().and_then(|active| {
+            self.views
+                .iter()
+                .find(|view| view.name == active.name)
+                .cloned()
+        });
+        self.order_review_queue(true);
+        self.order_approved_last(true);
+        self.group_by_repo(true);
+        self.read_only = self.read_only_forced || config.read_only;
+        if self.accessible != config.accessible {
+            self.accessible = config.accessible;
+            self.use_delta = !config.accessible;
+            self.needs_clear = true;
+        }
+        clone_cache::configure(&config.clone_cache);
+        gh::configure_fetch(&config.fetch);
+        auth::configure(&config.auth);
+        self.apply_excluded_user_filter_to_loaded_prs();
+        if filter_changed {
+            self.refresh();
+        }
+        self.set_status(summary);
+        true
+    }
+
     fn pagination_row_count(&self) -> usize {
         usize::from(self.has_next_page || self.loading_next_page)
     }
@@ -685,12 +1739,18 @@ impl App {
     fn reset_large_diff_state(&mut self) {
         self.diff_tree_enabled = false;
         self.delta_too_large = false;
+        self.per_file_diff = false;
+        self.loading_file_diff = None;
+        self.file_context_lines = 0;
+        self.loading_file_view = None;
+        self.file_line_stats.clear();
         self.file_diff_sections.clear();
         self.file_tree_items.clear();
         self.file_tree_state = ListState::default();
         self.selected_file_diff_path = None;
         self.filtered_diff_cache = None;
         self.filtered_diff_lines.clear();
+        self.diff_selection_anchor = None;
     }
 
     fn large_diff_file_selector_enabled(&self) -> bool {
@@ -721,27 +1781,203 @@ impl App {
         }
     }
 
-    fn toggle_diff_tree(&mut self) {
-        if self.detail_tab != DetailTab::Diff {
-            return;
-        }
+    fn active_path_filter(&self) -> Option<&DiffPathFilter> {
+        self.path_filter
+            .as_ref()
+            .filter(|_| self.path_filter_enabled)
+    }
 
-        if self.file_tree_items.is_empty() {
-            self.set_status("No files found in diff".to_string());
-            return;
+    /// Point the filtered diff at the files matching the active path filter, with collapsed
+    /// files folded to stubs, or back at the full diff when neither applies.
+    fn load_path_filtered_diff(&mut self) {
+        let filter = self.active_path_filter();
+        let collapsing = !self.collapsed_files.is_empty() && !self.per_file_diff;
+        let filtered = (filter.is_some() || collapsing).then(|| {
+            self.file_diff_sections
+                .iter()
+                .filter(|section| filter.is_none_or(|filter| filter.matches(&section.path)))
+                .map(|section| {
+                    if self.collapsed_files.contains(&section.path) {
+                        diff::collapsed_file_stub(&section.diff)
+                    } else {
+                        section.diff.clone()
+                    }
+                })
+                .collect::<String>()
+        });
+        match filtered {
+            Some(content) => {
+                self.filtered_diff_lines = parse_diff(&content);
+                self.filtered_diff_cache = Some(content);
+            }
+            None => {
+                self.filtered_diff_cache = None;
+                self.filtered_diff_lines.clear();
+            }
         }
+    }
 
-        if self.diff_tree_enabled {
-            self.diff_tree_enabled = false;
-            self.back_to_large_diff_tree();
-            self.clear_search();
-            self.needs_clear = true;
-            self.set_status("File tree hidden".to_string());
+    /// Rebuild the file tree and diff content after the diff or path filter changed.
+    fn apply_path_filter(&mut self) {
+        let visible: Vec<FileDiffSection> = match self.active_path_filter() {
+            Some(filter) => self
+                .file_diff_sections
+                .iter()
+                .filter(|section| filter.matches(&section.path))
+                .cloned()
+                .collect(),
+            None => self.file_diff_sections.clone(),
+        };
+        self.file_tree_items = build_diff_tree_items(&visible);
+        self.file_tree_state = ListState::default();
+        self.selected_file_diff_path = None;
+        self.select_first_file_tree_file();
+        self.load_path_filtered_diff();
+    }
+
+    fn set_path_filter(&mut self, spec: &str) {
+        let Some(filter) = DiffPathFilter::parse(spec) else {
+            self.path_filter = None;
+            self.path_filter_enabled = false;
+            self.refresh_path_filter_view();
+            self.set_status("Path filter cleared".to_string());
+            return;
+        };
+
+        let total = self.file_diff_sections.len();
+        let matching = self
+            .file_diff_sections
+            .iter()
+            .filter(|section| filter.matches(&section.path))
+            .count();
+        let label = filter.label();
+        self.path_filter = Some(filter);
+        self.path_filter_enabled = true;
+        self.refresh_path_filter_view();
+        self.set_status(format!(
+            "Showing {}/{} files matching '{}' (F to toggle)",
+            matching, total, label
+        ));
+    }
+
+    fn toggle_path_filter(&mut self) {
+        let Some(label) = self.path_filter.as_ref().map(DiffPathFilter::label) else {
+            self.set_status("No path filter set. Use :only <glob>".to_string());
+            return;
+        };
+        self.path_filter_enabled = !self.path_filter_enabled;
+        self.refresh_path_filter_view();
+        if self.path_filter_enabled {
+            self.set_status(format!("Path filter '{}' on", label));
+        } else {
+            self.set_status(format!("Path filter '{}' off", label));
+        }
+    }
+
+    fn refresh_path_filter_view(&mut self) {
+        self.apply_path_filter();
+        self.reset_scroll();
+        self.clear_search();
+        self.needs_clear = true;
+    }
+
+    /// The file whose header (or collapsed stub) is under the diff cursor, in the full diff.
+    fn file_header_at_cursor(&self) -> Option<String> {
+        if self.per_file_diff || self.selected_file_diff_path.is_some() {
+            return None;
+        }
+        let idx = usize::from(self.diff_cursor);
+        let using_delta =
+            self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none();
+        let on_header = if using_delta {
+            self.diff_file_starts().contains(&idx)
+        } else {
+            self.active_diff_lines().get(idx).is_some_and(|line| {
+                matches!(line.line_type, DiffLineType::Header | DiffLineType::Other)
+            })
+        };
+        if !on_header {
+            return None;
+        }
+        self.diff_row_location(idx)
+            .map(|(path, ..)| path.to_string())
+    }
+
+    /// `Enter` on a file header: fold the file to a one-line stub, or unfold it. Folded files
+    /// stay folded while this PR is open, and search and `]`/`[` only see their stub.
+    fn toggle_file_collapsed(&mut self, path: String) {
+        let collapsed = if self.collapsed_files.remove(&path) {
+            false
+        } else {
+            self.collapsed_files.insert(path.clone())
+        };
+        self.load_path_filtered_diff();
+        self.clear_search();
+        self.needs_clear = true;
+        let header = self.diff_file_starts().into_iter().find(|&idx| {
+            self.diff_row_location(idx)
+                .is_some_and(|(file, ..)| file == path)
+        });
+        if let Some(header) = header.and_then(|idx| u16::try_from(idx).ok()) {
+            self.focus_diff_line(header);
+        }
+        let verb = if collapsed { "Collapsed" } else { "Expanded" };
+        self.set_status(format!("{} {}", verb, path));
+    }
+
+    fn toggle_generated_files(&mut self) {
+        if !self.diff_config.collapse_generated {
+            self.set_status("Generated file collapsing is disabled in config".to_string());
+            return;
+        }
+        if !self.show_generated && self.generated_collapsed == 0 {
+            self.set_status("No generated files in this diff".to_string());
+            return;
+        }
+
+        self.show_generated = !self.show_generated;
+        self.diff_cache = None;
+        self.delta_cache = None;
+        self.diff_lines.clear();
+        self.delta_line_info.clear();
+        self.generated_collapsed = 0;
+        self.reset_scroll();
+        self.clear_search();
+        self.needs_clear = true;
+        self.load_diff();
+        if self.show_generated {
+            self.set_status("Showing generated files".to_string());
+        } else {
+            self.set_status("Collapsing generated files".to_string());
+        }
+    }
+
+    fn toggle_diff_tree(&mut self) {
+        if self.detail_tab != DetailTab::Diff {
+            return;
+        }
+
+        if self.file_tree_items.is_empty() {
+            self.set_status("No files found in diff".to_string());
+            return;
+        }
+
+        if self.per_file_diff {
+            self.set_status("Large PR: open files from the tree to load their diffs".to_string());
+            return;
+        }
+
+        if self.diff_tree_enabled {
+            self.diff_tree_enabled = false;
+            self.back_to_large_diff_tree();
+            self.clear_search();
+            self.needs_clear = true;
+            self.set_status("File tree hidden".to_string());
         } else {
             self.diff_tree_enabled = true;
             self.back_to_large_diff_tree();
             self.select_first_file_tree_file();
-            self.scroll_offset = 0;
+            self.reset_scroll();
             self.clear_search();
             self.needs_clear = true;
             self.set_status("File tree shown".to_string());
@@ -793,32 +2029,260 @@ impl App {
             return;
         };
 
+        if self.per_file_diff {
+            self.load_file_diff(path);
+            return;
+        }
+
         if let Some(section_diff) = self
             .file_diff_sections
             .iter()
             .find(|section| section.path == path)
             .map(|section| section.diff.clone())
         {
-            self.selected_file_diff_path = Some(path);
-            self.filtered_diff_lines = parse_diff(&section_diff);
-            self.filtered_diff_cache = Some(section_diff);
-            self.scroll_offset = 0;
-            self.clear_search();
-            self.needs_clear = true;
+            self.show_file_diff(path, section_diff);
         } else {
             self.set_status(format!("Unable to load diff for {}", path));
         }
     }
 
+    fn show_file_diff(&mut self, path: String, section_diff: String) {
+        self.selected_file_diff_path = Some(path);
+        self.file_context_lines = 0;
+        self.filtered_diff_lines = parse_diff(&section_diff);
+        self.filtered_diff_cache = Some(section_diff);
+        self.reset_scroll();
+        self.clear_search();
+        self.needs_clear = true;
+    }
+
+    fn load_file_diff(&mut self, path: String) {
+        if self.loading_file_diff.is_some() {
+            return;
+        }
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+        self.set_status(format!("Loading diff for {}...", path));
+        self.loading_file_diff = Some(path.clone());
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let diff = gh
+                .get_pr_file_diff(&pr, &path)
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::FileDiff(idx, path, diff));
+        });
+    }
+
+    /// The file (and new-file line, if any) under the diff cursor, or selected in the tree.
+    fn diff_cursor_location(&self) -> Option<(String, Option<u32>)> {
+        if self.showing_large_diff_tree() {
+            let item = self.file_tree_items.get(self.file_tree_state.selected()?)?;
+            return Some((item.file_path.clone()?, None));
+        }
+        let idx = self.diff_cursor as usize;
+        if self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none() {
+            let info = self.delta_line_info.get(idx)?;
+            Some((info.file_path.clone()?, info.new_line_number))
+        } else {
+            let line = self.active_diff_lines().get(idx)?;
+            Some((line.file_path.clone()?, line.line_number))
+        }
+    }
+
+    /// Re-fetch the current file's diff with `diff.context_step` more context lines per hunk.
+    fn expand_diff_context(&mut self) {
+        if self.loading_file_diff.is_some() {
+            return;
+        }
+        let Some((path, line)) = self.diff_cursor_location() else {
+            self.set_status("Move to a file's diff to expand its context".to_string());
+            return;
+        };
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+        let shown = if self.selected_file_diff_path.as_deref() == Some(path.as_str()) {
+            self.file_context_lines
+        } else {
+            0
+        };
+        let context = shown + self.diff_config.context_step.max(1);
+        if let Some(pos) = self
+            .file_tree_items
+            .iter()
+            .position(|item| item.file_path.as_deref() == Some(path.as_str()))
+        {
+            self.file_tree_state.select(Some(pos));
+        }
+
+        self.set_status(format!(
+            "Loading {} with {} more context lines...",
+            path, context
+        ));
+        self.loading_file_diff = Some(path.clone());
+        self.loading_file_context = context;
+        self.pending_diff_jump = Some((path.clone(), line));
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let diff = expanded_file_diff(gh.as_ref(), &pr, &path, context)
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::FileDiff(idx, path, diff));
+        });
+    }
+
+    /// Fetch the whole file under the diff cursor at the PR head and open it in the viewer.
+    fn open_file_view(&mut self) {
+        if self.loading_file_view.is_some() {
+            return;
+        }
+        let Some((path, line)) = self.diff_cursor_location() else {
+            self.set_status("Move to a file's diff to view the whole file".to_string());
+            return;
+        };
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+
+        self.set_status(format!("Loading {}...", path));
+        self.loading_file_view = Some(path.clone());
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let contents = gh.get_repo_file(&pr, &path).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::FileContents(idx, path, line, contents));
+        });
+    }
+
+    /// The file and old/new line numbers of a diff row, in whichever renderer is showing.
+    fn diff_row_location(&self, idx: usize) -> Option<(&str, Option<u32>, Option<u32>)> {
+        if self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none() {
+            let info = self.delta_line_info.get(idx)?;
+            Some((
+                info.file_path.as_deref()?,
+                info.old_line_number,
+                info.new_line_number,
+            ))
+        } else {
+            let line = self.active_diff_lines().get(idx)?;
+            Some((
+                line.file_path.as_deref()?,
+                line.old_line_number,
+                line.line_number,
+            ))
+        }
+    }
+
+    /// Review threads anchored on the focused diff line.
+    fn focused_review_threads(&self) -> Vec<&ReviewComment> {
+        let (Some(comments), Some((path, old_line, new_line))) = (
+            self.review_comments_cache.as_deref(),
+            self.diff_row_location(self.diff_cursor as usize),
+        ) else {
+            return Vec::new();
+        };
+        threads_on_row(&review_threads(comments), path, old_line, new_line)
+    }
+
+    /// Expand or collapse the review threads under the focused diff line. While expanded they
+    /// follow the cursor to other commented lines.
+    fn toggle_inline_comments(&mut self) {
+        if self.inline_comments_expanded {
+            self.inline_comments_expanded = false;
+        } else if !self.focused_review_threads().is_empty() {
+            self.inline_comments_expanded = true;
+        } else if self.loading_review_comments {
+            self.set_status("Loading review comments...".to_string());
+        } else {
+            self.set_status("No review comments on this line".to_string());
+        }
+    }
+
+    /// The file and old-file line under the diff cursor, i.e. the line as it was before the PR.
+    fn diff_cursor_old_line(&self) -> Option<(String, u32)> {
+        if !self.uses_diff_cursor() {
+            return None;
+        }
+        let idx = self.diff_cursor as usize;
+        if self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none() {
+            let info = self.delta_line_info.get(idx)?;
+            Some((info.file_path.clone()?, info.old_line_number?))
+        } else {
+            let line = self.active_diff_lines().get(idx)?;
+            Some((line.file_path.clone()?, line.old_line_number?))
+        }
+    }
+
+    /// Show who last changed the focused pre-change line, via `git blame` in the local clone.
+    fn blame_focused_line(&mut self) {
+        let Some((path, line)) = self.diff_cursor_old_line() else {
+            self.set_status("Move to a removed or unchanged line to blame it".to_string());
+            return;
+        };
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+
+        self.set_status(format!("Blaming {}:{}...", path, line));
+        let tx = self.async_tx.clone();
+        let repos_root = self.repos_root.clone();
+        thread::spawn(move || {
+            let blame =
+                gh::blame_line(&pr, &repos_root, &path, line).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Blame(idx, path, line, blame));
+        });
+    }
+
+    fn handle_file_view_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let Some(view) = self.file_view.as_mut() else {
+            self.input_mode = InputMode::Normal;
+            return;
+        };
+        let last = view.lines.len().saturating_sub(1);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | 'f') => {
+                self.file_view = None;
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.scroll = (view.scroll + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
+                view.scroll = (view.scroll + 20).min(last)
+            }
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                view.scroll = view.scroll.saturating_sub(20)
+            }
+            KeyCode::PageDown => view.scroll = (view.scroll + 20).min(last),
+            KeyCode::PageUp => view.scroll = view.scroll.saturating_sub(20),
+            KeyCode::Char('g') | KeyCode::Home => view.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => view.scroll = last,
+            _ => {}
+        }
+    }
+
     fn back_to_large_diff_tree(&mut self) {
         if self.selected_file_diff_path.is_none() {
             return;
         }
 
         self.selected_file_diff_path = None;
-        self.filtered_diff_cache = None;
-        self.filtered_diff_lines.clear();
-        self.scroll_offset = 0;
+        self.file_context_lines = 0;
+        self.load_path_filtered_diff();
+        self.reset_scroll();
         self.clear_search();
         self.needs_clear = true;
     }
@@ -899,7 +2363,6 @@ impl App {
         self.mode = mode;
         self.prs.clear();
         self.list_state.select(None);
-        self.refreshing = false;
         self.loading_next_page = false;
         self.has_next_page = false;
         self.next_page_cursor = None;
@@ -912,50 +2375,93 @@ impl App {
         let next = match self.mode {
             AppMode::Review => AppMode::MyPrs,
             AppMode::MyPrs => AppMode::Watching,
-            AppMode::Watching => AppMode::Review,
+            AppMode::Watching => AppMode::Dependencies,
+            AppMode::Dependencies => AppMode::Review,
         };
         self.select_list_tab(next);
     }
 
     fn prev_list_tab(&mut self) {
         let prev = match self.mode {
-            AppMode::Review => AppMode::Watching,
+            AppMode::Review => AppMode::Dependencies,
             AppMode::MyPrs => AppMode::Review,
             AppMode::Watching => AppMode::MyPrs,
+            AppMode::Dependencies => AppMode::Watching,
         };
         self.select_list_tab(prev);
     }
 
     fn enter_detail(&mut self) {
-        if self.selected_pr().is_some() {
+        if let Some(pr) = self.selected_pr() {
+            if !self.viewed_prs.contains(&pr_key(pr)) {
+                record_audit(AuditEntry::new("view", pr));
+                self.viewed_prs.insert(pr_key(pr));
+            }
             self.view = View::Detail;
             self.detail_tab = DetailTab::Description;
-            self.scroll_offset = 0;
+            self.reset_scroll();
             self.diff_cache = None;
             self.delta_cache = None;
             self.diff_lines.clear();
             self.delta_line_info.clear();
+            self.show_generated = false;
+            self.generated_collapsed = 0;
+            let key = self.selected_pr().map(pr_key).unwrap_or_default();
+            if self.collapsed_files_pr != key {
+                self.collapsed_files.clear();
+                self.collapsed_files_pr = key;
+            }
             self.reset_large_diff_state();
             self.comments_cache = None;
             self.review_comments_cache = None;
+            self.selected_thread = None;
+            self.rerequest_warned = false;
+            self.inline_comments_expanded = false;
+            self.feedback_cache = None;
             self.checks_cache = None;
+            self.changed_files_cache = None;
+            self.changed_file_diff = None;
+            self.commits_cache = None;
+            self.commit_diff = None;
+            self.protection_cache = None;
+            self.triage_cache = None;
+            self.checklist_cache = None;
+            self.checklist_state = ListState::default();
             self.agent_preview_cache = None;
             self.loading_diff = false;
             self.loading_comments = false;
             self.loading_review_comments = false;
+            self.loading_feedback = false;
             self.loading_checks = false;
+            self.loading_changed_files = false;
+            self.loading_changed_file_diff = None;
+            self.loading_commits = false;
+            self.loading_commit_diff = None;
+            self.loading_protection = false;
+            self.loading_triage = false;
+            self.loading_checklist = false;
+            self.loading_author_stats = false;
             self.loading_details = false;
             self.loading_agent_preview = false;
             self.needs_clear = true;
-            // Load details and checks asynchronously.
+            self.ai_results = None;
+            self.ai_finding_state = ListState::default();
+            self.pending_diff_jump = None;
+            self.refresh_ai_results();
+            // Load details, checks, branch protection, and the checklist asynchronously.
             self.load_details();
             self.load_checks();
+            self.load_protection();
+            self.load_triage();
+            self.load_permission();
+            self.load_checklist();
+            self.load_author_stats();
         }
     }
 
     fn exit_detail(&mut self) {
         self.view = View::List;
-        self.scroll_offset = 0;
+        self.reset_scroll();
         self.diff_cache = None;
         self.delta_cache = None;
         self.diff_lines.clear();
@@ -963,135 +2469,623 @@ impl App {
         self.reset_large_diff_state();
         self.comments_cache = None;
         self.review_comments_cache = None;
+        self.selected_thread = None;
+        self.rerequest_warned = false;
+        self.inline_comments_expanded = false;
+        self.feedback_cache = None;
         self.checks_cache = None;
+        self.changed_files_cache = None;
+        self.changed_file_diff = None;
+        self.commits_cache = None;
+        self.commit_diff = None;
+        self.protection_cache = None;
+        self.triage_cache = None;
+        self.checklist_cache = None;
         self.agent_preview_cache = None;
         self.loading_diff = false;
         self.loading_comments = false;
         self.loading_review_comments = false;
+        self.loading_feedback = false;
         self.loading_checks = false;
+        self.loading_changed_files = false;
+        self.loading_changed_file_diff = None;
+        self.loading_commits = false;
+        self.loading_commit_diff = None;
+        self.loading_protection = false;
+        self.loading_triage = false;
+        self.loading_checklist = false;
+        self.loading_author_stats = false;
         self.loading_details = false;
         self.loading_agent_preview = false;
         self.needs_clear = true;
+        self.ai_results = None;
+        self.pending_diff_jump = None;
         self.clear_search();
     }
 
-    fn next_tab(&mut self) {
-        self.detail_tab = match self.detail_tab {
-            DetailTab::Description => DetailTab::Diff,
-            DetailTab::Diff => DetailTab::Comments,
-            DetailTab::Comments => DetailTab::Agent,
-            DetailTab::Agent => DetailTab::Description,
-        };
-        self.scroll_offset = 0;
-        self.needs_clear = true;
-        self.load_tab_content();
-    }
+    /// Re-scan the PR's worktrees for review results written by an AI session.
+    /// Returns true when the results changed.
+    fn refresh_ai_results(&mut self) -> bool {
+        self.ai_results_checked_at = Some(std::time::Instant::now());
+        let results = self
+            .selected_pr()
+            .and_then(|pr| review_results::find_for_pr(pr, &self.repos_root));
+        if results == self.ai_results {
+            return false;
+        }
 
-    fn prev_tab(&mut self) {
-        self.detail_tab = match self.detail_tab {
-            DetailTab::Description => DetailTab::Agent,
-            DetailTab::Diff => DetailTab::Description,
-            DetailTab::Comments => DetailTab::Diff,
-            DetailTab::Agent => DetailTab::Comments,
-        };
-        self.scroll_offset = 0;
+        let appeared = self.ai_results.is_none() && results.is_some();
+        self.ai_results = results;
+        self.ai_finding_state = ListState::default();
+        if self
+            .ai_results
+            .as_ref()
+            .is_some_and(|results| !results.findings.is_empty())
+        {
+            self.ai_finding_state.select(Some(0));
+        }
+        if appeared {
+            self.set_status("AI review results available in the AI tab".to_string());
+        } else if self.ai_results.is_none() && self.detail_tab == DetailTab::Ai {
+            self.detail_tab = DetailTab::Description;
+        }
         self.needs_clear = true;
-        self.load_tab_content();
+        true
     }
 
-    fn load_tab_content(&mut self) {
-        match self.detail_tab {
-            DetailTab::Description => self.load_details(),
-            DetailTab::Diff => self.load_diff(),
-            DetailTab::Comments => {
-                self.load_comments();
-                self.load_review_comments();
-            }
-            DetailTab::Agent => self.load_agent_preview(),
+    fn reload_ai_results(&mut self) {
+        if !self.refresh_ai_results() {
+            self.set_status("AI review results unchanged".to_string());
         }
     }
 
-    fn scroll_down(&mut self) {
-        self.scroll_offset = self.scroll_offset.saturating_add(1);
-    }
-
-    fn scroll_up(&mut self) {
-        self.scroll_offset = self.scroll_offset.saturating_sub(1);
-    }
-
-    fn page_down(&mut self) {
-        self.scroll_offset = self.scroll_offset.saturating_add(20);
+    /// Periodically check for new results while a PR is open.
+    fn poll_ai_results(&mut self) -> bool {
+        if self.view != View::Detail {
+            return false;
+        }
+        let due = self
+            .ai_results_checked_at
+            .is_none_or(|checked| checked.elapsed().as_secs() >= 3);
+        due && self.refresh_ai_results()
     }
 
-    fn page_up(&mut self) {
-        self.scroll_offset = self.scroll_offset.saturating_sub(20);
+    fn move_check_selection(&mut self, forward: bool) {
+        let count = self.checks_cache.as_ref().map_or(0, Vec::len);
+        if count == 0 {
+            return;
+        }
+        let current = self.checks_state.selected().unwrap_or(0);
+        let next = if forward {
+            (current + 1).min(count - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+        self.checks_state.select(Some(next));
     }
 
-    fn load_details(&mut self) {
-        if self.loading_details {
+    /// Open the selected check's page, e.g. a failing job's log, in the browser.
+    fn open_selected_check(&mut self) {
+        let Some(check) = self
+            .checks_cache
+            .as_ref()
+            .zip(self.checks_state.selected())
+            .and_then(|(checks, idx)| checks.get(idx))
+        else {
+            return;
+        };
+        let name = check.name.clone();
+        let Some(link) = check.link.clone() else {
+            self.set_status(format!("{} has no page to open", name));
             return;
+        };
+        match self.gh.open_url(&link) {
+            Ok(()) => self.set_status(format!("Opened {} in browser", name)),
+            Err(e) => self.set_error("Failed to open", &e),
         }
-        if let Some(idx) = self.list_state.selected() {
-            if let Some(pr) = self.prs.get(idx) {
-                if pr.details_loaded {
-                    return;
-                }
+    }
 
-                self.loading_details = true;
-                let pr = pr.clone();
-                let tx = self.async_tx.clone();
-                thread::spawn(move || {
-                    let details = gh::fetch_pr_details(&pr).map_err(|e| format!("{:#}", e));
-                    let _ = tx.send(AsyncResult::Details(idx, details));
-                });
-            }
+    /// Fetch the checks again; they change while CI runs.
+    fn reload_checks(&mut self) {
+        if self.loading_checks {
+            return;
         }
+        self.checks_cache = None;
+        self.load_checks();
     }
 
-    fn load_next_page(&mut self) {
-        if self.loading_next_page || !self.has_next_page || self.refreshing {
+    fn move_ai_finding_selection(&mut self, forward: bool) {
+        let count = self
+            .ai_results
+            .as_ref()
+            .map_or(0, |results| results.findings.len());
+        if count == 0 {
+            self.scroll_offset = if forward {
+                self.scroll_offset.saturating_add(1)
+            } else {
+                self.scroll_offset.saturating_sub(1)
+            };
             return;
         }
+        let current = self.ai_finding_state.selected().unwrap_or(0);
+        let next = if forward {
+            (current + 1).min(count - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+        self.ai_finding_state.select(Some(next));
+    }
 
-        let Some(cursor) = self.next_page_cursor.clone() else {
-            self.has_next_page = false;
+    fn jump_to_selected_finding(&mut self) {
+        let Some(finding) = self
+            .ai_finding_state
+            .selected()
+            .and_then(|idx| self.ai_results.as_ref()?.findings.get(idx))
+        else {
             return;
         };
+        let Some(path) = finding.path.clone() else {
+            self.set_status("This finding has no file location".to_string());
+            return;
+        };
+        let line = finding.line;
 
-        self.loading_next_page = true;
-        self.needs_redraw = true;
+        self.open_diff_at(path, line);
+    }
+
+    /// Switch to the Diff tab and scroll to `path:line` once the diff is loaded.
+    fn open_diff_at(&mut self, path: String, line: Option<u32>) {
+        self.detail_tab = DetailTab::Diff;
+        self.reset_scroll();
+        self.needs_clear = true;
+        if self.diff_cache.is_none() {
+            self.pending_diff_jump = Some((path, line));
+            self.load_diff();
+            return;
+        }
+        self.apply_diff_jump(path, line);
+    }
+
+    /// Scroll the diff to `path:line`, opening the file first in tree mode.
+    fn apply_diff_jump(&mut self, path: String, line: Option<u32>) {
+        if self
+            .active_path_filter()
+            .is_some_and(|filter| !filter.matches(&path))
+        {
+            self.path_filter_enabled = false;
+            self.apply_path_filter();
+        }
+
+        if self.diff_tree_enabled && self.selected_file_diff_path.as_deref() != Some(&path) {
+            if self.per_file_diff {
+                self.pending_diff_jump = Some((path.clone(), line));
+                self.load_file_diff(path);
+                return;
+            }
+            let Some(section_diff) = self
+                .file_diff_sections
+                .iter()
+                .find(|section| section.path == path)
+                .map(|section| section.diff.clone())
+            else {
+                self.set_status(format!("{} is not part of this diff", path));
+                return;
+            };
+            self.show_file_diff(path.clone(), section_diff);
+        }
+
+        let using_delta =
+            self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none();
+        let target = if using_delta {
+            let in_file = |info: &&DeltaLineInfo| info.file_path.as_deref() == Some(&path);
+            self.delta_line_info
+                .iter()
+                .position(|info| in_file(&info) && line.is_some() && info.new_line_number == line)
+                .or_else(|| self.delta_line_info.iter().position(|info| in_file(&info)))
+        } else {
+            let lines = self.active_diff_lines();
+            let in_file = |dl: &&DiffLine| dl.file_path.as_deref() == Some(&path);
+            lines
+                .iter()
+                .position(|dl| in_file(&dl) && line.is_some() && dl.line_number == line)
+                .or_else(|| lines.iter().position(|dl| in_file(&dl)))
+        };
+
+        let location = match line {
+            Some(line) => format!("{}:{}", path, line),
+            None => path,
+        };
+        match target {
+            Some(idx) => {
+                self.focus_diff_line(idx as u16);
+                self.set_status(format!("Jumped to {}", location));
+            }
+            None => self.set_status(format!("{} is not part of this diff", location)),
+        }
+    }
+
+    /// Stream the progress of the repo scan behind a `Watching` refresh into the status, which
+    /// would otherwise read "Refreshing" for as long as one `gh repo view` per clone takes.
+    fn poll_scan_progress(&mut self) -> bool {
+        let scanning = self.refreshing
+            && self
+                .refresh_query
+                .as_ref()
+                .is_some_and(|query| query.mode == AppMode::Watching);
+        let Some(progress) = scanning.then(|| self.gh.scan_progress()).flatten() else {
+            return false;
+        };
+        let msg = format!("Scanning repos {}/{}...", progress.scanned, progress.total);
+        if self.status_message.as_deref() == Some(msg.as_str()) {
+            return false;
+        }
+        self.status_message = Some(msg);
+        true
+    }
+
+    /// Re-render delta once the terminal has stopped resizing, so dragging a window edge
+    /// doesn't spawn a delta per intermediate width. Waits for an in-flight diff load,
+    /// which was rendered for the old width.
+    fn poll_resize(&mut self) -> bool {
+        let Some((width, at)) = self.pending_resize else {
+            return false;
+        };
+        if self.loading_diff || at.elapsed() < RESIZE_SETTLE {
+            return false;
+        }
+        self.pending_resize = None;
+        self.rerender_delta_for_width(width)
+    }
+
+    /// Re-run delta at `width`, since its line wrapping and side-by-side columns are laid
+    /// out for the width it was rendered at. Returns whether a re-render was started.
+    fn rerender_delta_for_width(&mut self, width: u16) -> bool {
+        if width == self.delta_width {
+            return false;
+        }
+        let (Some(idx), Some(diff), true) = (
+            self.list_state.selected(),
+            self.diff_cache.clone(),
+            self.delta_cache.is_some(),
+        ) else {
+            return false;
+        };
+        self.delta_width = width;
+        let tx = self.async_tx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(AsyncResult::Delta(
+                idx,
+                width,
+                diff::process_with_delta(&diff, width),
+            ));
+        });
+        true
+    }
+
+    /// Swap in delta output for the new layout, keeping the cursor on the same file line.
+    fn replace_delta_output(&mut self, delta_output: Option<String>) {
+        let Some(diff) = self.diff_cache.as_deref() else {
+            return;
+        };
+        let using_delta =
+            self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none();
+        let focused = self
+            .delta_line_info
+            .get(self.diff_cursor as usize)
+            .filter(|_| using_delta)
+            .cloned();
+        self.delta_line_info = delta_output
+            .as_deref()
+            .map(|delta| parse_delta_output(delta, diff))
+            .unwrap_or_default();
+        self.diff_selection_anchor = None;
+        self.delta_cache = delta_output;
+        let Some(focused) = focused else {
+            return;
+        };
+        let same_line = |info: &DeltaLineInfo| {
+            info.file_path == focused.file_path
+                && ((focused.new_line_number.is_some()
+                    && info.new_line_number == focused.new_line_number)
+                    || (focused.old_line_number.is_some()
+                        && info.old_line_number == focused.old_line_number))
+        };
+        let target = self
+            .delta_line_info
+            .iter()
+            .position(same_line)
+            .unwrap_or_default();
+        self.focus_diff_line(target as u16);
+    }
+
+    /// Tabs shown in the detail view; Feedback only applies to my own PRs.
+    fn available_detail_tabs(&self) -> Vec<DetailTab> {
+        let mut tabs = vec![
+            DetailTab::Description,
+            DetailTab::Diff,
+            DetailTab::Files,
+            DetailTab::Comments,
+            DetailTab::Commits,
+            DetailTab::Checks,
+        ];
+        if self.mode == AppMode::MyPrs {
+            tabs.push(DetailTab::Feedback);
+        }
+        if self.ai_results.is_some() {
+            tabs.push(DetailTab::Ai);
+        }
+        tabs.push(DetailTab::Agent);
+        tabs
+    }
+
+    fn detail_tab_index(&self) -> usize {
+        self.available_detail_tabs()
+            .iter()
+            .position(|tab| *tab == self.detail_tab)
+            .unwrap_or(0)
+    }
+
+    fn next_tab(&mut self) {
+        let tabs = self.available_detail_tabs();
+        let idx = self.detail_tab_index();
+        self.detail_tab = tabs[(idx + 1) % tabs.len()];
+        self.reset_scroll();
+        self.needs_clear = true;
+        self.load_tab_content();
+    }
+
+    fn prev_tab(&mut self) {
+        let tabs = self.available_detail_tabs();
+        let idx = self.detail_tab_index();
+        self.detail_tab = tabs[(idx + tabs.len() - 1) % tabs.len()];
+        self.reset_scroll();
+        self.needs_clear = true;
+        self.load_tab_content();
+    }
+
+    fn load_tab_content(&mut self) {
+        match self.detail_tab {
+            DetailTab::Description => self.load_details(),
+            DetailTab::Diff => self.load_diff(),
+            DetailTab::Comments => {
+                self.load_comments();
+                self.load_review_comments();
+            }
+            DetailTab::Files => self.load_changed_files(),
+            DetailTab::Commits => self.load_commits(),
+            DetailTab::Checks => self.load_checks(),
+            DetailTab::Feedback => self.load_feedback(),
+            DetailTab::Ai => {
+                self.refresh_ai_results();
+            }
+            DetailTab::Agent => self.load_agent_preview(),
+        }
+    }
+
+    /// The diff moves a focused-line cursor; other tabs scroll their text.
+    fn uses_diff_cursor(&self) -> bool {
+        self.detail_tab == DetailTab::Diff && !self.showing_large_diff_tree()
+    }
+
+    fn scroll_down(&mut self) {
+        if self.uses_diff_cursor() {
+            self.move_diff_cursor(self.diff_cursor.saturating_add(1));
+        } else {
+            self.scroll_to(self.scroll_offset.saturating_add(1));
+        }
+    }
+
+    fn scroll_up(&mut self) {
+        if self.uses_diff_cursor() {
+            self.move_diff_cursor(self.diff_cursor.saturating_sub(1));
+        } else {
+            self.scroll_to(self.scroll_offset.saturating_sub(1));
+        }
+    }
+
+    /// Move one screen; in the diff the cursor keeps its row in the viewport.
+    fn page_down(&mut self) {
+        let page = self.viewport_height.max(1);
+        if self.uses_diff_cursor() {
+            let row = self.diff_cursor.saturating_sub(self.scroll_offset);
+            self.scroll_to(self.scroll_offset.saturating_add(page));
+            self.move_diff_cursor(self.scroll_offset.saturating_add(row));
+            if self.scroll_offset == self.max_scroll() {
+                // Already at the end: a further page lands the cursor on the last line.
+                self.move_diff_cursor(self.diff_cursor.saturating_add(page));
+            }
+        } else {
+            self.scroll_to(self.scroll_offset.saturating_add(page));
+        }
+    }
+
+    fn page_up(&mut self) {
+        let page = self.viewport_height.max(1);
+        if self.uses_diff_cursor() {
+            let row = self.diff_cursor.saturating_sub(self.scroll_offset);
+            let at_top = self.scroll_offset == 0;
+            self.scroll_to(self.scroll_offset.saturating_sub(page));
+            let target = if at_top {
+                self.diff_cursor.saturating_sub(page)
+            } else {
+                self.scroll_offset.saturating_add(row)
+            };
+            self.move_diff_cursor(target);
+        } else {
+            self.scroll_to(self.scroll_offset.saturating_sub(page));
+        }
+    }
+
+    fn max_scroll(&self) -> u16 {
+        self.content_lines
+            .map_or(u16::MAX, |lines| lines.saturating_sub(self.viewport_height))
+    }
+
+    fn scroll_to(&mut self, offset: u16) {
+        self.scroll_offset = offset.min(self.max_scroll());
+    }
+
+    /// Put the diff cursor on `line`, scrolling only when it leaves the viewport.
+    fn move_diff_cursor(&mut self, line: u16) {
+        let last = self
+            .content_lines
+            .map_or(u16::MAX, |lines| lines.saturating_sub(1));
+        self.diff_cursor = line.min(last);
+        let height = self.viewport_height.max(1);
+        if self.diff_cursor < self.scroll_offset {
+            self.scroll_offset = self.diff_cursor;
+        } else if self.diff_cursor >= self.scroll_offset.saturating_add(height) {
+            self.scroll_offset = self.diff_cursor.saturating_sub(height - 1);
+        }
+    }
+
+    /// Rows where each file of the showing diff begins: the `diff --git` header in the built-in
+    /// renderer, the row delta first names the file on otherwise.
+    fn diff_file_starts(&self) -> Vec<usize> {
+        if self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none() {
+            let info = &self.delta_line_info;
+            (0..info.len())
+                .filter(|&idx| {
+                    info[idx].file_path.is_some()
+                        && (idx == 0 || info[idx - 1].file_path != info[idx].file_path)
+                })
+                .collect()
+        } else {
+            let lines = self.active_diff_lines();
+            (0..lines.len())
+                .filter(|&idx| {
+                    lines[idx].line_type == DiffLineType::Header
+                        && (idx == 0 || lines[idx - 1].line_type != DiffLineType::Header)
+                })
+                .collect()
+        }
+    }
+
+    /// Rows of the showing diff's hunk headers: `@@` lines, or the rows delta draws for them.
+    fn diff_hunk_starts(&self) -> Vec<usize> {
+        if self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none() {
+            let info = &self.delta_line_info;
+            (0..info.len())
+                .filter(|&idx| info[idx].hunk_header)
+                .collect()
+        } else {
+            let lines = self.active_diff_lines();
+            (0..lines.len())
+                .filter(|&idx| lines[idx].line_type == DiffLineType::Hunk)
+                .collect()
+        }
+    }
+
+    /// `]`/`[`: put the next/previous file's header at the top of the diff, cursor on it.
+    fn jump_to_diff_file(&mut self, forward: bool) {
+        let starts = self.diff_file_starts();
+        self.jump_to_diff_row(&starts, forward, "file");
+    }
+
+    /// `}`/`{`: the same for hunks.
+    fn jump_to_diff_hunk(&mut self, forward: bool) {
+        let starts = self.diff_hunk_starts();
+        self.jump_to_diff_row(&starts, forward, "hunk");
+    }
+
+    fn jump_to_diff_row(&mut self, starts: &[usize], forward: bool, unit: &str) {
+        let cursor = usize::from(self.diff_cursor);
+        let target = if forward {
+            starts.iter().copied().find(|&start| start > cursor)
+        } else {
+            starts.iter().copied().rev().find(|&start| start < cursor)
+        };
+        let Some(target) = target.and_then(|start| u16::try_from(start).ok()) else {
+            let edge = if forward { "last" } else { "first" };
+            self.set_status(format!("Already at the {} {}", edge, unit));
+            return;
+        };
+        self.scroll_to(target);
+        self.diff_cursor = target;
+        self.diff_selection_anchor = None;
+    }
+
+    /// Jump the diff cursor to `line`, showing it at the top unless it is already visible.
+    fn focus_diff_line(&mut self, line: u16) {
+        let height = self.viewport_height.max(1);
+        let visible =
+            line >= self.scroll_offset && line < self.scroll_offset.saturating_add(height);
+        if !visible {
+            self.scroll_offset = line;
+        }
+        self.diff_cursor = line;
+    }
+
+    fn reset_scroll(&mut self) {
+        self.scroll_offset = 0;
+        self.diff_cursor = 0;
+        self.diff_selection_anchor = None;
+    }
+
+    /// Record how many lines the detail view just rendered and how many fit, so scrolling
+    /// stops once the last line is at the bottom and the diff cursor stays on a real line.
+    fn set_scroll_bounds(&mut self, content_lines: usize, viewport_height: u16) {
+        self.content_lines = Some(u16::try_from(content_lines).unwrap_or(u16::MAX));
+        self.viewport_height = viewport_height;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll());
+        if self.uses_diff_cursor() {
+            self.move_diff_cursor(self.diff_cursor);
+        }
+    }
+
+    /// Content still loading: keep the offsets (a pending jump may target them) and allow any.
+    fn clear_scroll_bounds(&mut self, viewport_height: u16) {
+        self.viewport_height = viewport_height;
+        self.content_lines = None;
+    }
+
+    fn load_details(&mut self) {
+        if self.loading_details {
+            return;
+        }
+        if let Some(idx) = self.list_state.selected() {
+            if let Some(pr) = self.prs.get(idx) {
+                if pr.details_loaded {
+                    return;
+                }
+
+                self.loading_details = true;
+                let pr = pr.clone();
+                let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
+                thread::spawn(move || {
+                    let details = gh.fetch_pr_details(&pr).map_err(|e| format!("{:#}", e));
+                    let _ = tx.send(AsyncResult::Details(idx, details));
+                });
+            }
+        }
+    }
+
+    fn load_next_page(&mut self) {
+        if self.loading_next_page || !self.has_next_page || self.refreshing {
+            return;
+        }
+
+        let Some(cursor) = self.next_page_cursor.clone() else {
+            self.has_next_page = false;
+            return;
+        };
+
+        self.loading_next_page = true;
+        self.needs_redraw = true;
         self.reload_exclude_users_from_config();
         let tx = self.async_tx.clone();
-        let username = self.username.clone();
-        let include_drafts = self.include_drafts;
-        let exclude_users = self.exclude_users.clone();
-        let repos_root = self.repos_root.clone();
-        let mode = self.mode;
+        let gh = Arc::clone(&self.gh);
+        let query = self.list_query();
+        let generation = self.list_generation;
 
         thread::spawn(move || {
-            let page = match mode {
-                AppMode::Review => crate::fetch_involved_prs(
-                    &username,
-                    include_drafts,
-                    Some(&cursor),
-                    &exclude_users,
-                ),
-                AppMode::MyPrs => {
-                    crate::fetch_my_prs(&username, include_drafts, Some(&cursor), &exclude_users)
-                }
-                AppMode::Watching => crate::fetch_watching_prs(
-                    &repos_root,
-                    &username,
-                    include_drafts,
-                    Some(&cursor),
-                    &exclude_users,
-                ),
-            };
-            let _ = tx.send(AsyncResult::NextPage(mode, cursor, page));
+            let page = gh.fetch_page(&query, Some(&cursor));
+            let _ = tx.send(AsyncResult::NextPage(generation, cursor, page));
         });
     }
 
-    fn load_next_page_if_pagination_visible(&mut self, list_area_height: u16) {
+    fn load_next_page_if_pagination_visible(&mut self, visible: std::ops::Range<usize>) {
         let Some(pagination_idx) = self.pagination_row_index() else {
             return;
         };
@@ -1099,12 +3093,7 @@ impl App {
             return;
         }
 
-        let content_height = list_area_height.saturating_sub(2) as usize;
-        let visible_item_capacity = content_height.div_ceil(2).max(1);
-        let first_visible = self.list_state.offset();
-        let past_last_visible = first_visible.saturating_add(visible_item_capacity);
-
-        if pagination_idx >= first_visible && pagination_idx < past_last_visible {
+        if visible.contains(&pagination_idx) {
             self.load_next_page();
         }
     }
@@ -1118,14 +3107,68 @@ impl App {
                 self.loading_diff = true;
                 let pr = pr.clone();
                 let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
                 // Get terminal width for delta's side-by-side mode
+                if self
+                    .diff_config
+                    .is_large_pr(pr.additions.saturating_add(pr.deletions))
+                {
+                    thread::spawn(move || {
+                        let files = gh.get_pr_files(&pr).map_err(|e| format!("{:#}", e));
+                        let _ = tx.send(AsyncResult::FileList(idx, files));
+                    });
+                    return;
+                }
                 let width = crossterm::terminal::size().map(|(w, _)| w).unwrap_or(120);
+                self.delta_width = width;
+                let generated_globs = self
+                    .diff_config
+                    .collapse_generated
+                    .then(|| self.diff_config.generated_files.clone());
+                let collapse = generated_globs.is_some() && !self.show_generated;
+                let head = generated_head(&pr);
+                let cached = self
+                    .generated_source
+                    .clone()
+                    .filter(|source| head.as_deref() == Some(source.head.as_str()));
                 thread::spawn(move || {
-                    let diff = gh::get_pr_diff(&pr).unwrap_or_else(|e| e.to_string());
+                    let source = match cached {
+                        Some(source) => Ok(source),
+                        None => gh.get_pr_diff(&pr).map(|diff| {
+                            let matcher = match &generated_globs {
+                                Some(globs) => {
+                                    let gitattributes = gh.get_gitattributes(&pr).ok().flatten();
+                                    diff::GeneratedFileMatcher::new(globs, gitattributes.as_deref())
+                                }
+                                None => diff::GeneratedFileMatcher::default(),
+                            };
+                            Arc::new(GeneratedDiffSource {
+                                head: head.unwrap_or_default(),
+                                diff,
+                                matcher,
+                            })
+                        }),
+                    };
+                    let (diff, generated_collapsed, source) = match source {
+                        Ok(source) if collapse => {
+                            let (diff, collapsed) =
+                                diff::collapse_generated_files(&source.diff, &source.matcher);
+                            (diff, collapsed, Some(source))
+                        }
+                        Ok(source) => (source.diff.clone(), 0, Some(source)),
+                        Err(e) => (e.to_string(), 0, None),
+                    };
                     let delta_too_large = diff::is_too_large_for_delta(&diff);
                     // Process with delta in background
                     let delta_output = diff::process_with_delta(&diff, width);
-                    let _ = tx.send(AsyncResult::Diff(idx, diff, delta_output, delta_too_large));
+                    let _ = tx.send(AsyncResult::Diff(
+                        idx,
+                        diff,
+                        delta_output,
+                        delta_too_large,
+                        generated_collapsed,
+                        source,
+                    ));
                 });
             }
         }
@@ -1140,8 +3183,9 @@ impl App {
                 self.loading_comments = true;
                 let pr = pr.clone();
                 let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
                 thread::spawn(move || {
-                    let comments = gh::get_pr_comments(&pr).unwrap_or_default();
+                    let comments = gh.get_pr_comments(&pr).unwrap_or_default();
                     let _ = tx.send(AsyncResult::Comments(idx, comments));
                 });
             }
@@ -1157,912 +3201,1275 @@ impl App {
                 self.loading_review_comments = true;
                 let pr = pr.clone();
                 let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
                 thread::spawn(move || {
-                    let comments = gh::get_review_comments(&pr).unwrap_or_default();
-                    let _ = tx.send(AsyncResult::ReviewComments(idx, comments));
+                    let comments = gh.get_review_comments(&pr).unwrap_or_default();
+                    // Without GraphQL the threads are still listed, just without resolution
+                    let threads = gh.get_review_threads(&pr).unwrap_or_default();
+                    let _ = tx.send(AsyncResult::ReviewComments(idx, comments, threads));
                 });
             }
         }
     }
 
-    fn load_checks(&mut self) {
-        if self.checks_cache.is_some() || self.loading_checks {
+    fn load_feedback(&mut self) {
+        if self.feedback_cache.is_some() || self.loading_feedback {
             return;
         }
         if let Some(idx) = self.list_state.selected() {
             if let Some(pr) = self.prs.get(idx) {
-                self.loading_checks = true;
+                self.loading_feedback = true;
                 let pr = pr.clone();
                 let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
                 thread::spawn(move || {
-                    let checks = gh::get_pr_checks(&pr).unwrap_or_default();
-                    let _ = tx.send(AsyncResult::Checks(idx, checks));
+                    let feedback = gh.get_pr_feedback(&pr).map_err(|e| format!("{:#}", e));
+                    let _ = tx.send(AsyncResult::Feedback(idx, feedback));
                 });
             }
         }
     }
 
-    fn load_agent_preview(&mut self) {
-        if self.agent_preview_cache.is_some() || self.loading_agent_preview {
-            return;
-        }
-        self.refresh_agent_preview();
-    }
-
-    fn refresh_agent_preview(&mut self) {
-        if self.loading_agent_preview {
+    /// File list for the Files tab; diffs are fetched one file at a time, so huge PRs stay cheap.
+    fn load_changed_files(&mut self) {
+        if self.changed_files_cache.is_some() || self.loading_changed_files {
             return;
         }
         if let Some(idx) = self.list_state.selected() {
             if let Some(pr) = self.prs.get(idx) {
-                self.loading_agent_preview = true;
+                self.loading_changed_files = true;
                 let pr = pr.clone();
                 let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
                 thread::spawn(move || {
-                    let preview = agent::preview_agent_session(&pr);
-                    let _ = tx.send(AsyncResult::AgentPreview(idx, preview));
+                    let files = gh.get_pr_files(&pr).map_err(|e| format!("{:#}", e));
+                    let _ = tx.send(AsyncResult::ChangedFiles(idx, files));
                 });
             }
         }
     }
 
-    fn attach_agent_session(&mut self) {
-        let target = self
-            .agent_preview_cache
+    fn move_changed_file_selection(&mut self, forward: bool) {
+        let count = match &self.changed_files_cache {
+            Some(Ok(files)) => files.len(),
+            _ => return,
+        };
+        if count == 0 {
+            return;
+        }
+        let current = self.changed_files_state.selected().unwrap_or(0);
+        let next = if forward {
+            (current + 1).min(count - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+        self.changed_files_state.select(Some(next));
+    }
+
+    /// Fetch and show the diff of the selected file in the Files tab; `Esc` goes back to the list.
+    fn open_selected_changed_file(&mut self) {
+        if self.loading_changed_file_diff.is_some() {
+            return;
+        }
+        let Some(path) = self
+            .changed_files_cache
             .as_ref()
-            .and_then(|preview| preview.pane.as_ref())
-            .map(|pane| pane.target.clone());
+            .and_then(|files| files.as_ref().ok())
+            .zip(self.changed_files_state.selected())
+            .and_then(|(files, idx)| files.get(idx))
+            .map(|file| file.path.clone())
+        else {
+            return;
+        };
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+        self.loading_changed_file_diff = Some(path.clone());
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let diff = gh
+                .get_pr_file_diff(&pr, &path)
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::ChangedFileDiff(idx, path, diff));
+        });
+    }
 
-        if let Some(target) = target {
-            self.pending_agent_attach_target = Some(target);
-        } else {
-            self.set_status("No agent session found for this PR".to_string());
+    fn close_changed_file_diff(&mut self) {
+        self.changed_file_diff = None;
+        self.reset_scroll();
+        self.needs_clear = true;
+    }
+
+    fn load_commits(&mut self) {
+        if self.commits_cache.is_some() || self.loading_commits {
+            return;
+        }
+        if let Some(idx) = self.list_state.selected() {
+            if let Some(pr) = self.prs.get(idx) {
+                self.loading_commits = true;
+                let pr = pr.clone();
+                let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
+                thread::spawn(move || {
+                    let commits = gh.get_pr_commits(&pr).map_err(|e| format!("{:#}", e));
+                    let _ = tx.send(AsyncResult::Commits(idx, commits));
+                });
+            }
         }
     }
 
-    fn take_pending_agent_attach_target(&mut self) -> Option<String> {
-        self.pending_agent_attach_target.take()
+    fn move_commit_selection(&mut self, forward: bool) {
+        let count = match &self.commits_cache {
+            Some(Ok(commits)) => commits.len(),
+            _ => return,
+        };
+        if count == 0 {
+            return;
+        }
+        let current = self.commits_state.selected().unwrap_or(0);
+        let next = if forward {
+            (current + 1).min(count - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+        self.commits_state.select(Some(next));
     }
 
-    fn poll_async_results(&mut self) -> bool {
-        let mut has_updates = false;
-        while let Ok(result) = self.async_rx.try_recv() {
-            has_updates = true;
-            match result {
-                AsyncResult::Details(idx, result) => {
-                    if self.list_state.selected() == Some(idx) {
-                        match result {
-                            Ok(details) => {
-                                if let Some(pr) = self.prs.get_mut(idx) {
-                                    *pr = details;
-                                }
-                            }
-                            Err(e) => self.set_status(format!("Failed to load PR details: {}", e)),
-                        }
-                    }
-                    self.loading_details = false;
-                }
-                AsyncResult::Diff(idx, diff, delta_output, delta_too_large) => {
-                    // Only update if still viewing the same PR
-                    if self.list_state.selected() == Some(idx) {
-                        self.diff_lines = parse_diff(&diff);
-                        // Parse delta output for line info if available
-                        if let Some(ref delta) = delta_output {
-                            self.delta_line_info = parse_delta_output(delta, &diff);
-                        } else {
-                            self.delta_line_info.clear();
-                        }
-                        let keep_tree_enabled = self.diff_tree_enabled;
-                        self.reset_large_diff_state();
-                        self.delta_too_large = delta_too_large;
-                        self.file_diff_sections = parse_diff_file_sections(&diff);
-                        self.file_tree_items = build_diff_tree_items(&self.file_diff_sections);
-                        if delta_too_large
-                            || (keep_tree_enabled && !self.file_tree_items.is_empty())
-                        {
-                            self.diff_tree_enabled = true;
-                            self.select_first_file_tree_file();
-                        }
-                        self.diff_cache = Some(diff);
-                        self.delta_cache = delta_output;
-                    }
-                    self.loading_diff = false;
-                }
-                AsyncResult::Comments(idx, comments) => {
-                    if self.list_state.selected() == Some(idx) {
-                        self.comments_cache = Some(comments);
-                    }
-                    self.loading_comments = false;
-                }
-                AsyncResult::ReviewComments(idx, comments) => {
-                    if self.list_state.selected() == Some(idx) {
-                        self.review_comments_cache = Some(comments);
-                    }
-                    self.loading_review_comments = false;
-                }
-                AsyncResult::Checks(idx, checks) => {
-                    if self.list_state.selected() == Some(idx) {
-                        self.checks_cache = Some(checks);
-                    }
-                    self.loading_checks = false;
-                }
-                AsyncResult::AgentPreview(idx, preview) => {
-                    if self.list_state.selected() == Some(idx) {
-                        self.agent_preview_cache = Some(preview);
-                    }
-                    self.loading_agent_preview = false;
-                }
-                AsyncResult::AiLaunch(result) => {
-                    self.launching_ai = false;
-                    self.needs_clear = true;
-                    match result {
-                        Ok(path) => {
-                            self.agent_preview_cache = None;
-                            self.set_status(format!(
-                                "Launched {} in {}",
-                                self.ai.display_name(),
-                                path
-                            ));
-                            if self.detail_tab == DetailTab::Agent {
-                                self.load_agent_preview();
-                            }
-                        }
-                        Err(e) => {
-                            self.set_status(format!("Failed: {}", e));
-                        }
-                    }
-                }
-                AsyncResult::Refresh(mode, page) => {
-                    if self.mode != mode {
-                        continue;
-                    }
-                    self.refreshing = false;
-                    self.loading_next_page = false;
-                    self.needs_clear = true;
-                    let count = page.prs.len();
-                    self.prs = page.prs;
-                    self.next_page_cursor = page.end_cursor;
-                    self.has_next_page = page.has_next_page;
-                    // Reset selection
-                    if self.prs.is_empty() {
-                        self.list_state.select(None);
-                    } else {
-                        self.list_state.select(Some(0));
-                    }
-                    let draft_status = if self.include_drafts {
-                        " (incl. drafts)"
-                    } else {
-                        ""
-                    };
-                    self.set_status(format!("Refreshed: {} PRs{}", count, draft_status));
-                }
-                AsyncResult::NextPage(mode, cursor, page) => {
-                    if self.mode != mode {
-                        continue;
-                    }
-                    if self.next_page_cursor.as_deref() != Some(cursor.as_str()) {
-                        continue;
-                    }
-                    self.loading_next_page = false;
-                    self.needs_clear = true;
-                    let added = page.prs.len();
-                    self.prs.extend(page.prs);
-                    self.next_page_cursor = page.end_cursor;
-                    self.has_next_page = page.has_next_page;
-
-                    if self.list_item_count() == 0 {
-                        self.list_state.select(None);
-                    } else if self
-                        .list_state
-                        .selected()
-                        .is_some_and(|idx| idx >= self.list_item_count())
-                    {
-                        self.list_state.select(Some(self.list_item_count() - 1));
-                    }
-
-                    if added > 0 {
-                        self.set_status(format!("Loaded {} more PRs", added));
-                    }
-                }
-            }
+    /// Show the diff of the selected commit in the Commits tab; `Esc` goes back to the list.
+    fn open_selected_commit(&mut self) {
+        if self.loading_commit_diff.is_some() {
+            return;
         }
-        has_updates
+        let Some(sha) = self
+            .commits_cache
+            .as_ref()
+            .and_then(|commits| commits.as_ref().ok())
+            .zip(self.commits_state.selected())
+            .and_then(|(commits, idx)| commits.get(idx))
+            .map(|commit| commit.sha.clone())
+        else {
+            return;
+        };
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+        self.loading_commit_diff = Some(sha.clone());
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let diff = gh
+                .get_commit_diff(&pr, &sha)
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::CommitDiff(idx, sha, diff));
+        });
     }
 
-    fn start_comment(&mut self) {
-        self.input_mode = InputMode::Comment;
-        self.input_buffer.clear();
+    fn close_commit_diff(&mut self) {
+        self.commit_diff = None;
+        self.reset_scroll();
+        self.needs_clear = true;
     }
 
-    fn start_line_comment(&mut self) {
-        // Only works in diff view with a valid line selected
-        if self.detail_tab != DetailTab::Diff {
-            self.start_comment(); // Fall back to general comment
-            return;
-        }
-
-        if self.showing_large_diff_tree() {
-            self.set_status("Select a file first (Enter) to comment on a line".to_string());
+    fn load_checks(&mut self) {
+        if self.checks_cache.is_some() || self.loading_checks {
             return;
         }
-
-        let using_delta =
-            self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none();
-        let line_idx = self.scroll_offset as usize;
-
-        if using_delta {
-            // Use parsed delta line info for accurate file/line lookup
-            if let Some(info) = self.delta_line_info.get(line_idx) {
-                if let Some(file_path) = &info.file_path {
-                    // Prefer new line number (RIGHT side), fall back to old (LEFT side)
-                    if let Some(line_num) = info.new_line_number {
-                        self.line_comment_ctx = Some(LineCommentContext {
-                            file_path: file_path.clone(),
-                            line_number: line_num,
-                            side: CommentSide::Right,
-                        });
-                        self.input_mode = InputMode::LineComment;
-                        self.input_buffer.clear();
-                        return;
-                    }
-                    if let Some(line_num) = info.old_line_number {
-                        self.line_comment_ctx = Some(LineCommentContext {
-                            file_path: file_path.clone(),
-                            line_number: line_num,
-                            side: CommentSide::Left,
-                        });
-                        self.input_mode = InputMode::LineComment;
-                        self.input_buffer.clear();
-                        return;
-                    }
-                }
-            }
-            self.set_status(
-                "Cannot comment on this line. Move to a code line with line numbers.".to_string(),
-            );
+        if !self.gh.supports(Capability::PrChecksJson) {
+            self.checks_cache = Some(Vec::new());
             return;
         }
-
-        // Built-in mode: direct index lookup
-        let active_lines = self.active_diff_lines();
-        if let Some(diff_line) = active_lines.get(line_idx) {
-            if let Some(file_path) = &diff_line.file_path {
-                // For added/context lines, use new file line number (RIGHT side)
-                if let Some(line_num) = diff_line.line_number {
-                    self.line_comment_ctx = Some(LineCommentContext {
-                        file_path: file_path.clone(),
-                        line_number: line_num,
-                        side: CommentSide::Right,
-                    });
-                    self.input_mode = InputMode::LineComment;
-                    self.input_buffer.clear();
-                    return;
-                }
-                // For removed lines, use old file line number (LEFT side)
-                if let Some(line_num) = diff_line.old_line_number {
-                    self.line_comment_ctx = Some(LineCommentContext {
-                        file_path: file_path.clone(),
-                        line_number: line_num,
-                        side: CommentSide::Left,
-                    });
-                    self.input_mode = InputMode::LineComment;
-                    self.input_buffer.clear();
-                    return;
-                }
+        if let Some(idx) = self.list_state.selected() {
+            if let Some(pr) = self.prs.get(idx) {
+                self.loading_checks = true;
+                let pr = pr.clone();
+                let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
+                thread::spawn(move || {
+                    let checks = gh.get_pr_checks(&pr).unwrap_or_default();
+                    let _ = tx.send(AsyncResult::Checks(idx, checks));
+                });
             }
         }
-
-        // Fall back to general comment if no valid line
-        self.set_status(
-            "Cannot comment on this line. Move to an added, removed, or context line.".to_string(),
-        );
     }
 
-    fn submit_line_comment(&mut self) {
-        if self.input_buffer.trim().is_empty() {
-            self.input_mode = InputMode::Normal;
-            self.line_comment_ctx = None;
+    fn load_triage(&mut self) {
+        if self.triage_cache.is_some() || self.loading_triage {
             return;
         }
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+        self.loading_triage = true;
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let triage = gh.get_pr_triage(&pr).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Triage(idx, triage));
+        });
+    }
 
-        if let (Some(pr), Some(ctx)) = (self.selected_pr().cloned(), self.line_comment_ctx.take()) {
-            let side = match ctx.side {
-                CommentSide::Left => "LEFT",
-                CommentSide::Right => "RIGHT",
-            };
-            match gh::add_line_comment(
-                &pr,
-                &ctx.file_path,
-                ctx.line_number,
-                side,
-                &self.input_buffer,
-            ) {
-                Ok(()) => {
-                    let side_label = if ctx.side == CommentSide::Left {
-                        " (old)"
-                    } else {
-                        ""
-                    };
-                    self.set_status(format!(
-                        "Comment added at {}:{}{}",
-                        ctx.file_path, ctx.line_number, side_label
-                    ));
-                }
-                Err(e) => {
-                    self.set_status(format!("Error: {}", e));
-                }
-            }
+    fn load_permission(&mut self) {
+        let Some(repo) = self.selected_pr().map(|pr| pr.repo_name.clone()) else {
+            return;
+        };
+        if self.repo_permissions.contains_key(&repo)
+            || !self.loading_permissions.insert(repo.clone())
+        {
+            return;
         }
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let permission = gh
+                .get_repo_permission(&repo)
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Permission(repo, permission));
+        });
+    }
 
-        self.input_mode = InputMode::Normal;
-        self.input_buffer.clear();
+    /// Whether the selected PR's repo is known to be read-only for me. An unknown permission
+    /// allows everything, leaving GitHub to refuse.
+    fn selected_repo_read_only(&self) -> bool {
+        self.selected_pr()
+            .and_then(|pr| self.repo_permissions.get(&pr.repo_name))
+            .is_some_and(|permission| !permission.can_write())
     }
 
-    fn launch_ai_review(&mut self) {
-        if self.launching_ai {
-            return;
+    /// In read-only mode, refuse `action` (e.g. "approving") with a status message.
+    fn refuse_in_read_only_mode(&mut self, action: &str) -> bool {
+        if self.read_only {
+            self.set_status(format!("Read-only mode: {} is disabled", action));
         }
-        if let Some(pr) = self.selected_pr().cloned() {
-            if let Err(err) = gh::validate_ai_launch_config(&self.ai) {
-                self.set_status(format!("AI launch is not configured: {:#}", err));
-                return;
-            }
+        self.read_only
+    }
 
-            let ai_display_name = self.ai.display_name();
-            self.launching_ai = true;
+    fn refuse_if_read_only(&mut self, needs: &str) -> bool {
+        if !self.selected_repo_read_only() {
+            return false;
+        }
+        if let Some(repo) = self.selected_pr().map(|pr| pr.repo_name.clone()) {
             self.set_status(format!(
-                "Creating worktree and launching {}...",
-                ai_display_name
+                "{} is read-only for you: {} needs write access",
+                repo, needs
             ));
+        }
+        true
+    }
 
-            let tx = self.async_tx.clone();
-            let repos_root = self.repos_root.clone();
-            let ai = self.ai.clone();
-            thread::spawn(move || {
-                let result = gh::create_pr_worktree(&pr, &repos_root)
-                    .and_then(|worktree_path| {
-                        gh::launch_ai(&worktree_path, &pr, &ai)?;
-                        Ok(worktree_path.display().to_string())
-                    })
-                    .map_err(|e| format!("{:#}", e));
-                let _ = tx.send(AsyncResult::AiLaunch(result));
-            });
+    /// Re-fetch the milestone and projects after changing them, if the detail view is open.
+    fn reload_triage(&mut self) {
+        if self.view == View::Detail {
+            self.triage_cache = None;
+            self.load_triage();
         }
     }
 
-    fn submit_comment(&mut self) {
-        if self.input_buffer.trim().is_empty() {
-            self.input_mode = InputMode::Normal;
+    fn start_milestone(&mut self) {
+        if self.refuse_in_read_only_mode("setting the milestone") || self.refuse_if_closed() {
             return;
         }
-
-        if let Some(pr) = self.selected_pr().cloned() {
-            match gh::add_pr_comment(&pr, &self.input_buffer) {
-                Ok(()) => {
-                    self.set_status("Comment added successfully".to_string());
-                    self.comments_cache = None; // Force reload
-                    self.review_comments_cache = None;
-                }
-                Err(e) => {
-                    self.set_status(format!("Error: {}", e));
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+        self.milestone_choices = None;
+        self.milestone_state.select(Some(0));
+        self.input_mode = InputMode::Milestone;
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let milestones = gh.get_milestones(&pr).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Milestones(idx, milestones));
+        });
+    }
+
+    fn handle_milestone_key(&mut self, code: KeyCode) {
+        let len = self.milestone_choices.as_ref().map_or(0, Vec::len);
+        let selected = self.milestone_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | 'M') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.milestone_state.select(Some((selected + 1) % len));
+            }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.milestone_state
+                    .select(Some((selected + len - 1) % len));
+            }
+            KeyCode::Enter => {
+                let Some(choice) = self
+                    .milestone_choices
+                    .as_ref()
+                    .and_then(|choices| choices.get(selected))
+                    .cloned()
+                else {
+                    return;
+                };
+                self.input_mode = InputMode::Normal;
+                if let Some(pr) = self.selected_pr().cloned() {
+                    self.start_action(pr, PrAction::SetMilestone(choice), None);
                 }
             }
+            _ => {}
         }
-
-        self.input_mode = InputMode::Normal;
-        self.input_buffer.clear();
     }
 
-    fn start_approve(&mut self) {
-        if self.selected_pr().is_some() {
-            self.input_mode = InputMode::ConfirmApprove;
+    fn start_labels(&mut self) {
+        if self.refuse_in_read_only_mode("editing labels") || self.refuse_if_closed() {
+            return;
         }
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let Some(pr) = self.prs.get(idx).cloned() else {
+            return;
+        };
+        self.label_choices = None;
+        self.label_picks = pr.labels.iter().map(|label| label.name.clone()).collect();
+        self.label_state.select(Some(0));
+        self.input_mode = InputMode::Labels;
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let labels = gh.get_repo_labels(&pr).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Labels(idx, labels));
+        });
     }
 
-    fn confirm_approve(&mut self) {
-        if let Some(pr) = self.selected_pr().cloned() {
-            match gh::approve_pr(&pr, None) {
-                Ok(()) => {
-                    self.set_status(format!("Approved PR #{}", pr.number));
-                    // Remove from list
-                    if let Some(idx) = self.list_state.selected() {
-                        self.prs.remove(idx);
-                        if self.prs.is_empty() {
-                            self.list_state.select(None);
-                            self.view = View::List;
-                        } else if idx >= self.prs.len() {
-                            self.list_state.select(Some(self.prs.len() - 1));
-                        }
-                        if self.view == View::Detail && !self.prs.is_empty() {
-                            self.diff_cache = None;
-                            self.comments_cache = None;
-                            self.review_comments_cache = None;
-                        } else if self.prs.is_empty() {
-                            self.view = View::List;
-                        }
-                    }
+    fn handle_labels_key(&mut self, code: KeyCode) {
+        let len = self.label_choices.as_ref().map_or(0, Vec::len);
+        let selected = self.label_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | 'L') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.label_state.select(Some((selected + 1) % len));
+            }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.label_state.select(Some((selected + len - 1) % len));
+            }
+            KeyCode::Char(' ') => {
+                let Some(name) = self
+                    .label_choices
+                    .as_ref()
+                    .and_then(|choices| choices.get(selected))
+                    .map(|label| label.name.clone())
+                else {
+                    return;
+                };
+                if !self.label_picks.remove(&name) {
+                    self.label_picks.insert(name);
                 }
-                Err(e) => {
-                    self.set_status(format!("Error: {}", e));
+            }
+            KeyCode::Enter if len > 0 => {
+                self.input_mode = InputMode::Normal;
+                let Some(pr) = self.selected_pr().cloned() else {
+                    return;
+                };
+                let labels: Vec<gh::Label> = self
+                    .label_choices
+                    .iter()
+                    .flatten()
+                    .filter(|label| self.label_picks.contains(&label.name))
+                    .cloned()
+                    .collect();
+                let unchanged = labels.len() == pr.labels.len()
+                    && pr
+                        .labels
+                        .iter()
+                        .all(|label| self.label_picks.contains(&label.name));
+                if unchanged {
+                    self.set_status(format!("Labels of #{} unchanged", pr.number));
+                    return;
                 }
+                self.start_action(pr, PrAction::SetLabels(labels), None);
             }
+            _ => {}
         }
-        self.input_mode = InputMode::Normal;
-    }
-
-    fn cancel_approve(&mut self) {
-        self.input_mode = InputMode::Normal;
     }
 
-    fn start_close(&mut self) {
-        if self.selected_pr().is_some() {
-            self.input_mode = InputMode::ConfirmClose;
-            self.input_buffer.clear();
+    fn start_reviewers(&mut self) {
+        if self.refuse_in_read_only_mode("requesting reviews") || self.refuse_if_closed() {
+            return;
+        }
+        if self.selected_pr().is_none() {
+            return;
         }
+        self.reviewer_query.clear();
+        self.reviewer_state.select(Some(0));
+        self.input_mode = InputMode::Reviewers;
+    }
+
+    /// Rows of the reviewers panel for the selected PR and the typed query.
+    fn reviewer_rows(&self) -> Vec<ReviewerRow> {
+        self.selected_pr().map_or_else(Vec::new, |pr| {
+            reviewer_rows(
+                pr,
+                &team_logins(&self.team),
+                &self.username,
+                &self.reviewer_query,
+            )
+        })
     }
 
-    fn confirm_close(&mut self) {
-        if let Some(pr) = self.selected_pr().cloned() {
-            let comment = if self.input_buffer.trim().is_empty() {
-                None
-            } else {
-                Some(self.input_buffer.as_str())
-            };
-            match gh::close_pr(&pr, comment) {
-                Ok(()) => {
-                    self.set_status(format!("Closed PR #{}", pr.number));
-                    // Remove from list
-                    if let Some(idx) = self.list_state.selected() {
-                        self.prs.remove(idx);
-                        // Adjust selection
-                        if !self.prs.is_empty() {
-                            let new_idx = idx.min(self.prs.len() - 1);
-                            self.list_state.select(Some(new_idx));
-                        } else {
-                            self.list_state.select(None);
-                        }
-                    }
-                    self.exit_detail();
-                }
-                Err(e) => {
-                    self.set_status(format!("Error: {}", e));
+    fn handle_reviewers_key(&mut self, code: KeyCode) {
+        let rows = self.reviewer_rows();
+        let selected = self.reviewer_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc => self.input_mode = InputMode::Normal,
+            KeyCode::Down if !rows.is_empty() => {
+                self.reviewer_state
+                    .select(Some((selected + 1) % rows.len()));
+            }
+            KeyCode::Up if !rows.is_empty() => {
+                self.reviewer_state
+                    .select(Some((selected + rows.len() - 1) % rows.len()));
+            }
+            KeyCode::Backspace => {
+                self.reviewer_query.pop();
+                self.reviewer_state.select(Some(0));
+            }
+            KeyCode::Char(c) if !c.is_whitespace() => {
+                self.reviewer_query.push(c);
+                self.reviewer_state.select(Some(0));
+            }
+            KeyCode::Enter => {
+                let Some(row) = rows.get(selected) else {
+                    return;
+                };
+                let Some(pr) = self.selected_pr().cloned() else {
+                    return;
+                };
+                if row.state == Some(gh::ReviewerState::Pending) {
+                    self.set_status(format!(
+                        "@{} is already requested on #{}",
+                        row.login, pr.number
+                    ));
+                    return;
                 }
+                self.input_mode = InputMode::Normal;
+                self.start_action(pr, PrAction::RerequestReview(vec![row.login.clone()]), None);
             }
+            _ => {}
         }
-        self.input_mode = InputMode::Normal;
-        self.input_buffer.clear();
     }
 
-    fn cancel_close(&mut self) {
-        self.input_mode = InputMode::Normal;
-        self.input_buffer.clear();
+    fn add_to_project(&mut self) {
+        if self.refuse_in_read_only_mode("adding to a project") || self.refuse_if_closed() {
+            return;
+        }
+        let Some(board) = self.project.board.clone() else {
+            self.set_status("Set project.board in the config to add PRs to a project".to_string());
+            return;
+        };
+        if let Some(pr) = self.selected_pr().cloned() {
+            self.start_action(pr, PrAction::AddToProject(board), None);
+        }
     }
 
-    fn start_merge(&mut self) {
-        // Only allow merge in MyPrs mode
-        if self.mode != AppMode::MyPrs {
-            self.set_status("Merge only available in My PRs tab".to_string());
+    fn load_protection(&mut self) {
+        if self.protection_cache.is_some() || self.loading_protection {
             return;
         }
-
-        if let Some(pr) = self.selected_pr() {
-            // Check if PR can be merged
-            let status = gh::check_merge_status(pr);
-            if status.can_merge {
-                self.input_mode = InputMode::ConfirmMerge;
-            } else {
-                let reason = status
-                    .reason
-                    .unwrap_or_else(|| "Unknown reason".to_string());
-                self.set_status(format!("Cannot merge: {}", reason));
+        if let Some(idx) = self.list_state.selected() {
+            if let Some(pr) = self.prs.get(idx) {
+                self.loading_protection = true;
+                let pr = pr.clone();
+                let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
+                thread::spawn(move || {
+                    let protection = gh
+                        .get_branch_protection(&pr)
+                        .map_err(|e| format!("{:#}", e));
+                    let _ = tx.send(AsyncResult::Protection(idx, protection));
+                });
             }
         }
     }
 
-    fn confirm_merge(&mut self) {
-        if let Some(pr) = self.selected_pr().cloned() {
-            match gh::merge_pr(&pr, true) {
-                Ok(merge_type) => {
-                    self.set_status(format!("Merged PR #{} ({})", pr.number, merge_type));
-                    // Remove from list
-                    if let Some(idx) = self.list_state.selected() {
-                        self.prs.remove(idx);
-                        if !self.prs.is_empty() {
-                            let new_idx = idx.min(self.prs.len() - 1);
-                            self.list_state.select(Some(new_idx));
-                        } else {
-                            self.list_state.select(None);
-                        }
-                    }
-                    self.exit_detail();
-                }
-                Err(e) => {
-                    self.set_status(format!("Merge failed: {}", e));
-                }
+    fn load_checklist(&mut self) {
+        if self.checklist_cache.is_some() || self.loading_checklist {
+            return;
+        }
+        if let Some(idx) = self.list_state.selected() {
+            if let Some(pr) = self.prs.get(idx) {
+                self.loading_checklist = true;
+                let pr = pr.clone();
+                let path = self.checklist_config.path.clone();
+                let tx = self.async_tx.clone();
+                let gh = Arc::clone(&self.gh);
+                thread::spawn(move || {
+                    let items = gh
+                        .get_repo_file(&pr, &path)
+                        .map(|markdown| markdown.map(|m| checklist::parse(&m)).unwrap_or_default())
+                        .map_err(|e| format!("{:#}", e));
+                    let _ = tx.send(AsyncResult::Checklist(idx, items));
+                });
             }
         }
-        self.input_mode = InputMode::Normal;
     }
 
-    fn cancel_merge(&mut self) {
-        self.input_mode = InputMode::Normal;
+    /// Author history is shown outside My PRs and the Dependencies tab, where it adds nothing.
+    fn shows_author_stats(&self) -> bool {
+        !matches!(self.mode, AppMode::MyPrs | AppMode::Dependencies)
     }
 
-    fn open_in_browser(&mut self) {
-        if let Some(pr) = self.selected_pr() {
-            match gh::open_pr_in_browser(pr) {
-                Ok(()) => self.set_status(format!("Opened PR #{} in browser", pr.number)),
-                Err(e) => self.set_status(format!("Failed to open: {}", e)),
+    fn load_author_stats(&mut self) {
+        if self.loading_author_stats || !self.shows_author_stats() {
+            return;
+        }
+        if let Some(pr) = self.selected_pr().cloned() {
+            let key = author_stats_key(&pr);
+            if self.author_stats_cache.contains_key(&key) {
+                return;
             }
+            self.loading_author_stats = true;
+            let tx = self.async_tx.clone();
+            let gh = Arc::clone(&self.gh);
+            thread::spawn(move || {
+                let result = gh.get_author_stats(&pr).map_err(|e| format!("{:#}", e));
+                let _ = tx.send(AsyncResult::AuthorStats(key, result));
+            });
         }
     }
 
-    fn copy_pr_url(&mut self) {
-        if let Some(pr) = self.selected_pr() {
-            let url = &pr.url;
-            // Use platform-specific clipboard command
-            #[cfg(target_os = "macos")]
-            let result = std::process::Command::new("pbcopy")
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|mut child| {
-                    use std::io::Write;
-                    if let Some(stdin) = child.stdin.as_mut() {
-                        stdin.write_all(url.as_bytes())?;
-                    }
-                    child.wait()
-                });
+    fn load_agent_preview(&mut self) {
+        if self.agent_preview_cache.is_some() || self.loading_agent_preview {
+            return;
+        }
+        self.refresh_agent_preview();
+    }
 
-            #[cfg(target_os = "linux")]
-            let result = std::process::Command::new("xclip")
-                .args(["-selection", "clipboard"])
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|mut child| {
-                    use std::io::Write;
-                    if let Some(stdin) = child.stdin.as_mut() {
-                        stdin.write_all(url.as_bytes())?;
-                    }
-                    child.wait()
+    fn refresh_agent_preview(&mut self) {
+        if self.loading_agent_preview {
+            return;
+        }
+        if let Some(idx) = self.list_state.selected() {
+            if let Some(pr) = self.prs.get(idx) {
+                self.loading_agent_preview = true;
+                let pr = pr.clone();
+                let tx = self.async_tx.clone();
+                thread::spawn(move || {
+                    let preview = agent::preview_agent_session(&pr);
+                    let _ = tx.send(AsyncResult::AgentPreview(idx, preview));
                 });
+            }
+        }
+    }
 
-            #[cfg(target_os = "windows")]
-            let result = std::process::Command::new("clip")
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|mut child| {
-                    use std::io::Write;
-                    if let Some(stdin) = child.stdin.as_mut() {
-                        stdin.write_all(url.as_bytes())?;
-                    }
-                    child.wait()
-                });
+    fn attach_agent_session(&mut self) {
+        let target = self
+            .agent_preview_cache
+            .as_ref()
+            .and_then(|preview| preview.pane.as_ref())
+            .map(|pane| pane.target.clone());
 
-            match result {
-                Ok(_) => self.set_status("Copied URL to clipboard".to_string()),
-                Err(_) => self.set_status(format!("URL: {}", url)),
-            }
+        if let Some(target) = target {
+            self.pending_agent_attach_target = Some(target);
+        } else {
+            self.set_status("No agent session found for this PR".to_string());
         }
     }
 
-    fn refresh(&mut self) {
-        if self.refreshing {
+    fn take_pending_agent_attach_target(&mut self) -> Option<String> {
+        self.pending_agent_attach_target.take()
+    }
+
+    /// Open the file and line under the diff cursor in the editor, in the PR's worktree.
+    /// An existing worktree is reused; otherwise one is created first.
+    fn open_in_editor(&mut self) {
+        if self.creating_editor_worktree {
+            return;
+        }
+        let Some((file, line)) = self.diff_cursor_location() else {
+            self.set_status("Move to a file's diff to open it in the editor".to_string());
+            return;
+        };
+        let line = line.unwrap_or(1);
+        let Some(pr) = self.selected_pr().cloned() else {
+            return;
+        };
+
+        if let Some(worktree) = gh::existing_pr_worktrees(&pr, &self.repos_root)
+            .into_iter()
+            .find(|path| path.is_dir())
+        {
+            self.pending_editor_launch = Some(EditorLaunch {
+                worktree,
+                file,
+                line,
+            });
             return;
         }
-        self.refreshing = true;
-        self.loading_next_page = false;
-        self.has_next_page = false;
-        self.next_page_cursor = None;
-        self.reload_exclude_users_from_config();
-        self.set_status("Refreshing PR list...".to_string());
 
+        self.creating_editor_worktree = true;
+        self.set_status(format!("Creating worktree to open {}...", file));
         let tx = self.async_tx.clone();
-        let username = self.username.clone();
-        let include_drafts = self.include_drafts;
-        let exclude_users = self.exclude_users.clone();
         let repos_root = self.repos_root.clone();
-        let mode = self.mode;
-
         thread::spawn(move || {
-            let page = match mode {
-                AppMode::Review => {
-                    crate::fetch_involved_prs(&username, include_drafts, None, &exclude_users)
-                }
-                AppMode::MyPrs => {
-                    crate::fetch_my_prs(&username, include_drafts, None, &exclude_users)
-                }
-                AppMode::Watching => crate::fetch_watching_prs(
-                    &repos_root,
-                    &username,
-                    include_drafts,
-                    None,
-                    &exclude_users,
-                ),
-            };
-            let _ = tx.send(AsyncResult::Refresh(mode, page));
+            let worktree = gh::create_pr_worktree(&pr, &repos_root).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::EditorWorktree(file, line, worktree));
         });
     }
 
-    fn toggle_drafts(&mut self) {
-        self.include_drafts = !self.include_drafts;
-        let status = if self.include_drafts {
-            "Including drafts - refreshing..."
-        } else {
-            "Excluding drafts - refreshing..."
-        };
-        self.set_status(status.to_string());
-        self.refresh();
+    fn take_pending_editor_launch(&mut self) -> Option<EditorLaunch> {
+        self.pending_editor_launch.take()
     }
 
-    fn toggle_delta(&mut self) {
-        if !diff::delta_available() {
-            self.set_status("Delta not installed".to_string());
+    /// Hand the comment being typed to `$EDITOR`; the main loop suspends the TUI to run it.
+    fn compose_in_editor(&mut self) {
+        let Some(pr) = self.selected_pr().cloned() else {
             return;
-        }
-        self.use_delta = !self.use_delta;
-        self.back_to_large_diff_tree();
-        let status = if self.use_delta {
-            "Using delta renderer"
-        } else {
-            "Using built-in renderer (line comments enabled)"
         };
-        self.set_status(status.to_string());
+        self.pending_comment_draft = Some(CommentDraft {
+            pr,
+            line: self.line_comment_ctx.take(),
+            reply: self.reply_target.take(),
+            text: std::mem::take(&mut self.input_buffer),
+        });
+        self.input_mode = InputMode::Normal;
     }
 
-    pub fn handle_event(&mut self) -> Result<()> {
-        // Poll for async results (non-blocking)
-        let mut needs_redraw = self.poll_async_results();
-
-        if event::poll(std::time::Duration::from_millis(50))? {
-            match event::read()? {
-                Event::Key(key) => {
-                    if key.kind != KeyEventKind::Press {
-                        return Ok(());
-                    }
-
-                    match self.input_mode {
-                        InputMode::Normal => self.handle_normal_key(key.code, key.modifiers),
-                        InputMode::Comment => self.handle_comment_key(key.code),
-                        InputMode::LineComment => self.handle_line_comment_key(key.code),
-                        InputMode::ConfirmApprove => self.handle_confirm_key(key.code),
-                        InputMode::ConfirmClose => self.handle_close_key(key.code),
-                        InputMode::ConfirmMerge => self.handle_merge_key(key.code),
-                        InputMode::Search => self.handle_search_key(key.code),
-                        InputMode::ListSearch => self.handle_list_search_key(key.code),
-                        InputMode::GotoLine => self.handle_goto_key(key.code),
-                    }
+    fn take_pending_comment_draft(&mut self) -> Option<CommentDraft> {
+        self.pending_comment_draft.take()
+    }
 
-                    needs_redraw = true;
-                }
-                Event::Resize(_, _) => {
-                    self.needs_clear = true;
-                    needs_redraw = true;
-                }
-                _ => {}
+    /// Post what was saved in the editor. If the editor failed, the draft goes back into the input.
+    fn finish_comment_draft(&mut self, draft: CommentDraft, result: Result<String>) {
+        let body = match result {
+            Ok(body) => body,
+            Err(err) => {
+                self.set_status(format!("Failed to open editor: {:#}", err));
+                self.input_mode = if draft.line.is_some() {
+                    InputMode::LineComment
+                } else {
+                    InputMode::Comment
+                };
+                self.line_comment_ctx = draft.line;
+                self.reply_target = draft.reply;
+                self.input_buffer = draft.text;
+                return;
             }
+        };
+        if body.trim().is_empty() {
+            self.set_status("Comment discarded: nothing was saved".to_string());
+            return;
         }
-
-        if needs_redraw {
-            self.needs_redraw = true;
+        match (draft.line, draft.reply) {
+            (Some(ctx), _) => self.post_line_comment(draft.pr, ctx, body),
+            (None, Some(target)) => {
+                self.start_action(draft.pr, PrAction::Reply(target, body), None)
+            }
+            (None, None) => self.start_action(draft.pr, PrAction::Comment(body), None),
         }
-
-        Ok(())
     }
 
-    fn handle_normal_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
-        match self.view {
-            View::List => match code {
-                KeyCode::Char('q') => self.should_quit = true,
-                KeyCode::Tab | KeyCode::Right => self.next_list_tab(),
-                KeyCode::BackTab | KeyCode::Left => self.prev_list_tab(),
-                KeyCode::Char('1') => self.select_list_tab(AppMode::Review),
-                KeyCode::Char('2') => self.select_list_tab(AppMode::MyPrs),
-                KeyCode::Char('3') => self.select_list_tab(AppMode::Watching),
-                // Page navigation with Ctrl+d/u (must be before non-Ctrl)
-                KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => self.next_page(),
-                KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.previous_page()
+    fn poll_async_results(&mut self) -> bool {
+        let mut has_updates = false;
+        while let Ok(result) = self.async_rx.try_recv() {
+            has_updates = true;
+            tracing::debug!(
+                event = result.label(),
+                selected = ?self.list_state.selected(),
+                "async result"
+            );
+            match result {
+                AsyncResult::Details(idx, result) => {
+                    if self.list_state.selected() == Some(idx) {
+                        match result {
+                            Ok(details) => {
+                                if let Some(pr) = self.prs.get_mut(idx) {
+                                    *pr = details;
+                                }
+                            }
+                            Err(e) => self.set_status(format!("Failed to load PR details: {}", e)),
+                        }
+                    }
+                    self.loading_details = false;
                 }
-                // Regular navigation
-                KeyCode::Char('j') | KeyCode::Down => self.next(),
-                KeyCode::Char('k') | KeyCode::Up => self.previous(),
-                KeyCode::PageDown => self.next_page(),
-                KeyCode::PageUp => self.previous_page(),
-                KeyCode::Char('g') => self.go_to_first(),
-                KeyCode::Char('G') => self.go_to_last(),
-                KeyCode::Home => self.go_to_first(),
-                KeyCode::End => self.go_to_last(),
-                KeyCode::Enter => self.enter_detail(),
-                KeyCode::Char('R') => self.refresh(),
-                KeyCode::Char('d') => self.toggle_drafts(),
-                // Search in PR list
-                KeyCode::Char('/') => self.start_list_search(),
-                KeyCode::Char('n') if !self.search_query.is_empty() => {
-                    self.next_list_search_match()
+                AsyncResult::Diff(
+                    idx,
+                    diff,
+                    delta_output,
+                    delta_too_large,
+                    generated_collapsed,
+                    source,
+                ) => {
+                    // Only update if still viewing the same PR
+                    if self.list_state.selected() == Some(idx) {
+                        self.diff_lines = parse_diff(&diff);
+                        // Parse delta output for line info if available
+                        if let Some(ref delta) = delta_output {
+                            self.delta_line_info = parse_delta_output(delta, &diff);
+                        } else {
+                            self.delta_line_info.clear();
+                        }
+                        let keep_tree_enabled = self.diff_tree_enabled;
+                        self.reset_large_diff_state();
+                        self.delta_too_large = delta_too_large;
+                        self.file_diff_sections = parse_diff_file_sections(&diff);
+                        self.apply_path_filter();
+                        if delta_too_large
+                            || (keep_tree_enabled && !self.file_tree_items.is_empty())
+                        {
+                            self.diff_tree_enabled = true;
+                            self.select_first_file_tree_file();
+                        }
+                        self.diff_cache = Some(diff);
+                        self.delta_cache = delta_output;
+                        self.generated_collapsed = generated_collapsed;
+                        self.generated_source = source;
+                        if let Some((path, line)) = self.pending_diff_jump.take() {
+                            self.apply_diff_jump(path, line);
+                        }
+                    }
+                    self.loading_diff = false;
                 }
-                KeyCode::Char('N') if !self.search_query.is_empty() => {
-                    self.prev_list_search_match()
+                AsyncResult::Delta(idx, width, delta_output) => {
+                    // A later resize may have superseded this render
+                    if self.list_state.selected() == Some(idx) && width == self.delta_width {
+                        self.replace_delta_output(delta_output);
+                    }
                 }
-                KeyCode::Char('o') => self.open_in_browser(),
-                KeyCode::Char('y') => self.copy_pr_url(),
-                _ => {}
-            },
-            View::Detail => match code {
-                KeyCode::Char('q') => self.exit_detail(),
-                KeyCode::Esc => {
-                    if self.showing_single_file_diff() {
-                        self.back_to_large_diff_tree();
-                    } else {
-                        self.exit_detail();
+                AsyncResult::FileList(idx, files) => {
+                    if self.list_state.selected() == Some(idx) {
+                        self.diff_lines.clear();
+                        self.delta_line_info.clear();
+                        self.reset_large_diff_state();
+                        match files {
+                            Ok(files) => {
+                                self.per_file_diff = true;
+                                self.diff_tree_enabled = true;
+                                self.file_line_stats = files
+                                    .iter()
+                                    .map(|file| {
+                                        (file.path.clone(), (file.additions, file.deletions))
+                                    })
+                                    .collect();
+                                self.file_diff_sections = files
+                                    .into_iter()
+                                    .map(|file| FileDiffSection {
+                                        path: file.path,
+                                        diff: String::new(),
+                                    })
+                                    .collect();
+                                self.apply_path_filter();
+                                self.diff_cache = Some(String::new());
+                            }
+                            Err(e) => self.diff_cache = Some(e),
+                        }
+                        self.delta_cache = None;
+                        if let Some((path, line)) = self.pending_diff_jump.take() {
+                            self.apply_diff_jump(path, line);
+                        }
                     }
+                    self.loading_diff = false;
                 }
-                KeyCode::Tab => self.next_tab(),
-                KeyCode::BackTab => self.prev_tab(),
-                // Page navigation with Ctrl+d/u
-                KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => self.page_down(),
-                KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => self.page_up(),
-                // Regular navigation
-                KeyCode::Char('j') | KeyCode::Down => {
-                    if self.showing_large_diff_tree() {
-                        self.move_file_tree_selection(true);
-                    } else {
-                        self.scroll_down();
+                AsyncResult::FileDiff(idx, path, diff) => {
+                    if self.list_state.selected() == Some(idx)
+                        && self.loading_file_diff.as_deref() == Some(path.as_str())
+                    {
+                        self.loading_file_diff = None;
+                        let context = std::mem::take(&mut self.loading_file_context);
+                        match diff {
+                            Ok(diff) => {
+                                self.status_message = None;
+                                if context > 0 {
+                                    self.diff_tree_enabled = true;
+                                }
+                                self.show_file_diff(path.clone(), diff);
+                                self.file_context_lines = context;
+                                if let Some((_, line)) = self.pending_diff_jump.take() {
+                                    self.apply_diff_jump(path.clone(), line);
+                                }
+                                if context > 0 {
+                                    self.set_status(format!(
+                                        "Showing {} with {} more context lines",
+                                        path, context
+                                    ));
+                                }
+                            }
+                            Err(e) => {
+                                self.pending_diff_jump = None;
+                                self.set_status(e);
+                            }
+                        }
                     }
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if self.showing_large_diff_tree() {
-                        self.move_file_tree_selection(false);
-                    } else {
-                        self.scroll_up();
+                AsyncResult::FileContents(idx, path, focus, contents) => {
+                    if self.list_state.selected() == Some(idx)
+                        && self.loading_file_view.as_deref() == Some(path.as_str())
+                    {
+                        self.loading_file_view = None;
+                        match contents {
+                            Ok(Some(text)) => {
+                                self.status_message = None;
+                                // Open with a few lines of lead-in above the focused line.
+                                let lines: Vec<String> = text.lines().map(str::to_string).collect();
+                                let scroll = focus
+                                    .map(|line| (line as usize).saturating_sub(6))
+                                    .unwrap_or(0)
+                                    .min(lines.len().saturating_sub(1));
+                                self.file_view = Some(FileView {
+                                    path,
+                                    lines,
+                                    focus,
+                                    scroll,
+                                });
+                                self.input_mode = InputMode::FileView;
+                            }
+                            Ok(None) => {
+                                self.set_status(format!("{} does not exist at the PR head", path))
+                            }
+                            Err(e) => self.set_status(e),
+                        }
                     }
                 }
-                KeyCode::PageDown => self.page_down(),
-                KeyCode::PageUp => self.page_up(),
-                KeyCode::Enter if self.detail_tab == DetailTab::Agent => {
-                    self.attach_agent_session()
+                AsyncResult::Blame(idx, path, line, blame) => {
+                    if self.list_state.selected() == Some(idx) {
+                        match blame {
+                            Ok(blame) => {
+                                self.set_status(blame_summary(&path, line, &blame, &self.dates))
+                            }
+                            Err(e) => self.set_status(e),
+                        }
+                    }
                 }
-                KeyCode::Enter if self.showing_large_diff_tree() => self.open_selected_file_diff(),
-                KeyCode::Char('A') if self.detail_tab == DetailTab::Agent => {
-                    self.attach_agent_session()
+                AsyncResult::Comments(idx, comments) => {
+                    if self.list_state.selected() == Some(idx) {
+                        self.comments_cache = Some(comments);
+                    }
+                    self.loading_comments = false;
                 }
-                KeyCode::Char('R') if self.detail_tab == DetailTab::Agent => {
-                    self.agent_preview_cache = None;
-                    self.refresh_agent_preview();
+                AsyncResult::ReviewComments(idx, comments, threads) => {
+                    if self.list_state.selected() == Some(idx) {
+                        self.review_comments_cache = Some(comments);
+                        self.review_thread_states = threads;
+                    }
+                    self.loading_review_comments = false;
                 }
-                KeyCode::Char('c') => self.start_line_comment(),
-                KeyCode::Char('a') => self.start_approve(),
-                KeyCode::Char('x') => self.start_close(),
-                KeyCode::Char('m') => self.start_merge(),
-                KeyCode::Char('r') => self.launch_ai_review(),
-                // Search (only in Diff tab)
-                KeyCode::Char('/') if self.detail_tab == DetailTab::Diff => {
-                    if self.showing_large_diff_tree() {
-                        self.start_tree_name_search();
-                    } else {
-                        self.start_search();
+                AsyncResult::Feedback(idx, feedback) => {
+                    if self.list_state.selected() == Some(idx) {
+                        self.feedback_cache = Some(feedback);
                     }
+                    self.loading_feedback = false;
                 }
-                KeyCode::Char('?')
-                    if self.detail_tab == DetailTab::Diff && self.showing_large_diff_tree() =>
-                {
-                    self.start_tree_content_search();
+                AsyncResult::ChangedFiles(idx, files) => {
+                    if self.list_state.selected() == Some(idx) {
+                        let first = files.as_ref().is_ok_and(|files| !files.is_empty());
+                        self.changed_files_state.select(first.then_some(0));
+                        self.changed_files_cache = Some(files);
+                    }
+                    self.loading_changed_files = false;
                 }
-                KeyCode::Char('n') if !self.search_query.is_empty() => self.next_search_match(),
-                KeyCode::Char('N') if !self.search_query.is_empty() => self.prev_search_match(),
-                // Goto line (only in Diff tab)
-                KeyCode::Char(':') if self.detail_tab == DetailTab::Diff => {
-                    if self.showing_large_diff_tree() {
-                        self.set_status("Select a file first (Enter) to jump to lines".to_string());
-                    } else {
-                        self.start_goto_line();
+                AsyncResult::ChangedFileDiff(idx, path, diff) => {
+                    if self.list_state.selected() == Some(idx)
+                        && self.loading_changed_file_diff.as_deref() == Some(path.as_str())
+                    {
+                        self.changed_file_diff = Some((path, diff));
+                        self.reset_scroll();
+                        self.needs_clear = true;
                     }
+                    self.loading_changed_file_diff = None;
                 }
-                // Next/prev PR (when not searching)
-                KeyCode::Char('n') if self.search_query.is_empty() => {
-                    self.exit_detail();
-                    self.next();
-                    self.enter_detail();
+                AsyncResult::Commits(idx, commits) => {
+                    if self.list_state.selected() == Some(idx) {
+                        // The newest commit is what a re-review usually starts from
+                        let last = commits.as_ref().map_or(0, Vec::len).checked_sub(1);
+                        self.commits_state.select(last);
+                        self.commits_cache = Some(commits);
+                    }
+                    self.loading_commits = false;
                 }
-                KeyCode::Char('p') => {
-                    self.exit_detail();
-                    self.previous();
-                    self.enter_detail();
+                AsyncResult::CommitDiff(idx, sha, diff) => {
+                    if self.list_state.selected() == Some(idx)
+                        && self.loading_commit_diff.as_deref() == Some(sha.as_str())
+                    {
+                        self.commit_diff = Some((sha, diff));
+                        self.reset_scroll();
+                        self.needs_clear = true;
+                    }
+                    self.loading_commit_diff = None;
                 }
-                // Toggle delta rendering (only in Diff tab)
-                KeyCode::Char('t') if self.detail_tab == DetailTab::Diff => self.toggle_diff_tree(),
-                KeyCode::Char('D') if self.detail_tab == DetailTab::Diff => self.toggle_delta(),
-                KeyCode::Char('o') => self.open_in_browser(),
-                KeyCode::Char('y') => self.copy_pr_url(),
-                _ => {}
-            },
-        }
-    }
-
-    fn handle_confirm_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.confirm_approve(),
-            KeyCode::Char('n' | 'N') | KeyCode::Esc => self.cancel_approve(),
-            _ => {}
-        }
-    }
-
-    fn handle_close_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Enter => self.confirm_close(),
-            KeyCode::Esc => self.cancel_close(),
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                self.input_buffer.push(c);
-            }
-            _ => {}
-        }
-    }
-
-    fn handle_merge_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.confirm_merge(),
-            KeyCode::Char('n' | 'N') | KeyCode::Esc => self.cancel_merge(),
-            _ => {}
-        }
-    }
-
-    fn handle_comment_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Enter => self.submit_comment(),
-            KeyCode::Esc => {
-                self.input_mode = InputMode::Normal;
-                self.input_buffer.clear();
-            }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                self.input_buffer.push(c);
-            }
-            _ => {}
-        }
-    }
-
-    fn handle_line_comment_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Enter => self.submit_line_comment(),
-            KeyCode::Esc => {
-                self.input_mode = InputMode::Normal;
-                self.input_buffer.clear();
-                self.line_comment_ctx = None;
-            }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                self.input_buffer.push(c);
+                AsyncResult::Checks(idx, mut checks) => {
+                    if self.list_state.selected() == Some(idx) {
+                        // Failures first, with the first one selected for `Enter`
+                        checks.sort_by_key(|check| match check.status {
+                            gh::CheckState::Failure => 0,
+                            gh::CheckState::Pending => 1,
+                            gh::CheckState::Success => 2,
+                            gh::CheckState::Neutral => 3,
+                        });
+                        self.checks_state
+                            .select(Some(0).filter(|_| !checks.is_empty()));
+                        self.checks_cache = Some(checks);
+                    }
+                    self.loading_checks = false;
+                }
+                AsyncResult::Protection(idx, result) => {
+                    if self.list_state.selected() == Some(idx) {
+                        // Protection is informational; without access to the rules, show nothing.
+                        self.protection_cache = Some(result.ok().flatten());
+                    }
+                    self.loading_protection = false;
+                }
+                AsyncResult::Checklist(idx, result) => {
+                    if self.list_state.selected() == Some(idx) {
+                        match result {
+                            Ok(items) => self.checklist_cache = Some(items),
+                            Err(err) => {
+                                tracing::warn!(error = %err, "failed to load review checklist");
+                                self.checklist_cache = Some(Vec::new());
+                            }
+                        }
+                    }
+                    self.loading_checklist = false;
+                }
+                AsyncResult::AuthorStats(key, result) => {
+                    // Cached by author rather than PR index, so a late result is still useful.
+                    let stats = result
+                        .map_err(
+                            |err| tracing::warn!(error = %err, "failed to load author history"),
+                        )
+                        .ok();
+                    self.author_stats_cache.insert(key, stats);
+                    self.loading_author_stats = false;
+                }
+                AsyncResult::GlobalSearch(generation, matches) => {
+                    if generation == self.global_search_generation {
+                        self.global_search_pending = self.global_search_pending.saturating_sub(1);
+                        self.global_matches.extend(matches);
+                        if self.global_match_state.selected().is_none()
+                            && !self.global_matches.is_empty()
+                        {
+                            self.global_match_state.select(Some(0));
+                        }
+                    }
+                }
+                AsyncResult::ReviewQueues(result) => {
+                    self.loading_review_queues = false;
+                    match result {
+                        Ok(queues) => {
+                            self.review_queues = Some((std::time::Instant::now(), queues));
+                        }
+                        Err(err) => {
+                            if self.input_mode == InputMode::Workload {
+                                self.input_mode = InputMode::Normal;
+                            }
+                            self.set_status(format!("Failed to load review queues: {}", err));
+                        }
+                    }
+                }
+                AsyncResult::AgentPreview(idx, preview) => {
+                    if self.list_state.selected() == Some(idx) {
+                        self.agent_preview_cache = Some(preview);
+                    }
+                    self.loading_agent_preview = false;
+                }
+                AsyncResult::EditorWorktree(file, line, worktree) => {
+                    self.creating_editor_worktree = false;
+                    match worktree {
+                        Ok(worktree) => {
+                            self.status_message = None;
+                            self.pending_editor_launch = Some(EditorLaunch {
+                                worktree,
+                                file,
+                                line,
+                            });
+                        }
+                        Err(e) => self.set_status(format!("Failed to create worktree: {}", e)),
+                    }
+                }
+                AsyncResult::AiLaunch(ai_display_name, result) => {
+                    self.launching_ai = false;
+                    self.needs_clear = true;
+                    match result {
+                        Ok(path) if self.ai.launch.uses_api() => {
+                            self.refresh_ai_results();
+                            self.set_status(format!(
+                                "API review written to {}",
+                                review_results::results_dir(std::path::Path::new(&path))
+                                    .join(review_results::REVIEW_MD)
+                                    .display()
+                            ));
+                        }
+                        Ok(path) => {
+                            self.agent_preview_cache = None;
+                            self.set_status(format!("Launched {} in {}", ai_display_name, path));
+                            if self.detail_tab == DetailTab::Agent {
+                                self.load_agent_preview();
+                            }
+                        }
+                        Err(e) => {
+                            self.set_status(format!("Failed: {}", e));
+                        }
+                    }
+                }
+                AsyncResult::Refresh(generation, page) => {
+                    if generation != self.list_generation {
+                        // Superseded while in flight: fetch what is wanted now instead
+                        if self.refresh_queued {
+                            self.spawn_refresh();
+                        }
+                        continue;
+                    }
+                    self.refreshing = false;
+                    self.refresh_query = None;
+                    self.loading_next_page = false;
+                    self.needs_clear = true;
+                    self.prs = page.prs;
+                    self.folded_prs.clear();
+                    self.previewed_pr = None;
+                    self.fetch_errors = page.errors;
+                    self.closed_prs.clear();
+                    self.load_discussions(self.prs.clone());
+                    self.order_review_queue(false);
+                    self.order_approved_last(false);
+                    let snoozed = self.hide_snoozed();
+                    let bots = self.hide_bots();
+                    self.hide_excluded_drafts();
+                    self.hide_outside_view();
+                    self.hide_outside_filter();
+                    let count = self.prs.len();
+                    self.group_by_repo(false);
+                    self.next_page_cursor = page.end_cursor;
+                    self.has_next_page = page.has_next_page;
+                    // Reset selection
+                    if self.prs.is_empty() {
+                        self.list_state.select(None);
+                    } else {
+                        self.list_state.select(Some(0));
+                    }
+                    let draft_status = if self.include_drafts {
+                        " (incl. drafts)"
+                    } else {
+                        ""
+                    };
+                    let snoozed_status = if snoozed > 0 {
+                        format!(" ({} snoozed)", snoozed)
+                    } else {
+                        String::new()
+                    };
+                    let bots_status = if bots > 0 {
+                        format!(" ({} by bots hidden, B: show)", bots)
+                    } else {
+                        String::new()
+                    };
+                    let view_status = match &self.active_view {
+                        Some(view) => format!(" in view '{}'", view.name),
+                        None => String::new(),
+                    };
+                    let errors_status = match self.fetch_errors.len() {
+                        0 => String::new(),
+                        1 => " (1 repo failed, R: retry)".to_string(),
+                        failed => format!(" ({} repos failed, R: retry)", failed),
+                    };
+                    self.set_status(format!(
+                        "Refreshed: {} PRs{}{}{}{}{}",
+                        count,
+                        view_status,
+                        draft_status,
+                        snoozed_status,
+                        bots_status,
+                        errors_status
+                    ));
+                }
+                AsyncResult::NextPage(generation, cursor, page) => {
+                    if generation != self.list_generation {
+                        continue;
+                    }
+                    if self.next_page_cursor.as_deref() != Some(cursor.as_str()) {
+                        continue;
+                    }
+                    self.loading_next_page = false;
+                    self.needs_clear = true;
+                    let added = page.prs.len();
+                    self.fetch_errors.extend(page.errors);
+                    self.load_discussions(page.prs.clone());
+                    self.prs.extend(page.prs);
+                    self.order_review_queue(true);
+                    self.order_approved_last(true);
+                    let added = added
+                        - self.hide_snoozed()
+                        - self.hide_bots()
+                        - self.hide_excluded_drafts()
+                        - self.hide_outside_view()
+                        - self.hide_outside_filter();
+                    self.group_by_repo(true);
+                    self.next_page_cursor = page.end_cursor;
+                    self.has_next_page = page.has_next_page;
+
+                    if self.list_item_count() == 0 {
+                        self.list_state.select(None);
+                    } else if self
+                        .list_state
+                        .selected()
+                        .is_some_and(|idx| idx >= self.list_item_count())
+                    {
+                        self.list_state.select(Some(self.list_item_count() - 1));
+                    }
+
+                    if added > 0 {
+                        self.set_status(format!("Loaded {} more PRs", added));
+                    }
+                }
+                AsyncResult::Action(id, result) => {
+                    self.finish_action(id, result);
+                }
+                AsyncResult::Triage(idx, result) => {
+                    if self.list_state.selected() == Some(idx) {
+                        match result {
+                            Ok(triage) => self.triage_cache = Some(triage),
+                            Err(e) => self.log_status(e),
+                        }
+                    }
+                    self.loading_triage = false;
+                }
+                AsyncResult::Permission(repo, result) => {
+                    self.loading_permissions.remove(&repo);
+                    match result {
+                        Ok(permission) => {
+                            self.repo_permissions.insert(repo, permission);
+                        }
+                        Err(e) => self.log_status(e),
+                    }
+                }
+                AsyncResult::Milestones(idx, result) => {
+                    if self.list_state.selected() == Some(idx)
+                        && self.input_mode == InputMode::Milestone
+                    {
+                        match result {
+                            Ok(milestones) => {
+                                let mut choices: Vec<Option<String>> =
+                                    milestones.into_iter().map(Some).collect();
+                                choices.push(None);
+                                self.milestone_choices = Some(choices);
+                            }
+                            Err(e) => {
+                                self.input_mode = InputMode::Normal;
+                                self.set_status(e);
+                            }
+                        }
+                    }
+                }
+                AsyncResult::Labels(idx, result) => {
+                    if self.list_state.selected() == Some(idx)
+                        && self.input_mode == InputMode::Labels
+                    {
+                        match result {
+                            Ok(labels) => self.label_choices = Some(labels),
+                            Err(e) => {
+                                self.input_mode = InputMode::Normal;
+                                self.set_status(e);
+                            }
+                        }
+                    }
+                }
+                AsyncResult::Discussions(result) => match result {
+                    Ok(discussions) => self.discussions.extend(discussions),
+                    Err(e) => self.log_status(format!("Failed to load comment counts: {}", e)),
+                },
+                AsyncResult::Refetched(removed_at, result) => match result {
+                    Ok(pr) => self.apply_refetched_pr(removed_at, pr),
+                    Err(e) => self.log_status(format!("Failed to refresh PR: {}", e)),
+                },
             }
-            _ => {}
         }
+        has_updates
     }
 
-    fn handle_search_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Enter => self.execute_search(),
-            KeyCode::Esc => {
-                self.input_mode = InputMode::Normal;
-                self.input_buffer.clear();
-            }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                self.input_buffer.push(c);
-            }
-            _ => {}
+    /// Fetch comment and unresolved-thread counts for `prs`, for the list badges.
+    fn load_discussions(&mut self, prs: Vec<PullRequest>) {
+        if prs.is_empty() {
+            return;
+        }
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let result = gh
+                .get_discussions(&prs)
+                .map(|discussions| {
+                    prs.iter()
+                        .zip(discussions)
+                        .filter_map(|(pr, discussion)| Some((pr_key(pr), discussion?)))
+                        .collect()
+                })
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Discussions(result));
+        });
+    }
+
+    /// Drop snoozed PRs from the loaded list unless `Z` is showing them.
+    /// Returns how many were hidden.
+    /// Drop PRs by `list.bots` authors from the Review list unless revealed with `B`.
+    /// Returns how many were hidden.
+    fn hide_bots(&mut self) -> usize {
+        if self.mode != AppMode::Review || self.show_bots {
+            return 0;
         }
+        let before = self.prs.len();
+        let bots = &self.list_config.bots;
+        self.prs
+            .retain(|pr| !crate::filters::author_is_listed_bot(&pr.author, bots));
+        before - self.prs.len()
+    }
+
+    /// Drop drafts from repos that don't include them under `include_drafts` and `drafts`.
+    /// Returns how many were hidden.
+    fn hide_excluded_drafts(&mut self) -> usize {
+        let before = self.prs.len();
+        let (drafts, include_drafts) = (&self.drafts_config, self.include_drafts);
+        self.prs
+            .retain(|pr| !pr.is_draft || drafts.include_for(&pr.repo_name, include_drafts));
+        before - self.prs.len()
+    }
+
+    /// Drop PRs outside the active view's repos and labels. Returns how many were hidden.
+    fn hide_outside_view(&mut self) -> usize {
+        let Some(view) = &self.active_view else {
+            return 0;
+        };
+        let before = self.prs.len();
+        self.prs.retain(|pr| pr_in_view(pr, view));
+        before - self.prs.len()
     }
 
-    fn handle_list_search_key(&mut self, code: KeyCode) {
+    /// Drop PRs the `list.filter` doesn't match. Returns how many were hidden.
+    fn hide_outside_filter(&mut self) -> usize {
+        let filter = &self.list_config.filter;
+        if filter.is_empty() {
+            return 0;
+        }
+        let before = self.prs.len();
+        self.prs.retain(|pr| pr_matches_filter(pr, filter));
+        before - self.prs.len()
+    }
+
+    fn start_list_filter(&mut self) {
+        self.input_buffer = self.list_config.filter.to_string();
+        self.input_mode = InputMode::ListFilter;
+    }
+
+    fn handle_list_filter_key(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Enter => self.execute_list_search(),
+            KeyCode::Enter => match ListFilter::try_from(self.input_buffer.clone()) {
+                Ok(filter) => {
+                    self.input_mode = InputMode::Normal;
+                    self.input_buffer.clear();
+                    self.apply_list_filter(filter);
+                }
+                Err(e) => self.set_status(e),
+            },
             KeyCode::Esc => {
                 self.input_mode = InputMode::Normal;
                 self.input_buffer.clear();
@@ -2070,1870 +4477,9735 @@ impl App {
             KeyCode::Backspace => {
                 self.input_buffer.pop();
             }
-            KeyCode::Char(c) => {
-                self.input_buffer.push(c);
-            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
             _ => {}
         }
     }
 
-    fn handle_goto_key(&mut self, code: KeyCode) {
+    /// Make `filter` the list's filter, save it as the default in the config, and reload the list.
+    fn apply_list_filter(&mut self, filter: ListFilter) {
+        if filter == self.list_config.filter {
+            return;
+        }
+        self.list_config.filter = filter.clone();
+        self.save_list_setting("filter", |list| list.filter = filter);
+        self.needs_clear = true;
+        self.refresh();
+    }
+
+    /// Sort the list by the next `list.sort` mode and remember it in the config.
+    fn cycle_list_sort(&mut self) {
+        let sort = self.list_config.sort.next();
+        self.list_config.sort = sort;
+        self.save_list_setting("sort", |list| list.sort = sort);
+        self.needs_clear = true;
+        if sort == ListSort::Updated {
+            // GitHub's order is gone once re-sorted, so fetch it again
+            self.set_status("Sorted by updated - refreshing...".to_string());
+            self.refresh();
+            return;
+        }
+        self.order_review_queue(true);
+        self.order_approved_last(true);
+        self.group_by_repo(true);
+        self.set_status(format!("Sorted by {}", sort.label()));
+    }
+
+    fn save_list_setting(&mut self, what: &str, update: impl FnOnce(&mut ListConfig)) {
+        match save_list_config(update) {
+            // The watcher would otherwise report our own write as a config edit
+            Ok(Some(cfg)) => self.config_watcher = ConfigWatcher::new(&cfg),
+            Ok(None) => {}
+            Err(e) => self.log_status(format!("Failed to save the {what}: {:#}", e)),
+        }
+    }
+
+    fn start_view_picker(&mut self) {
+        if self.views.is_empty() {
+            self.set_status("Add views to the config to save list presets".to_string());
+            return;
+        }
+        let active = self
+            .active_view
+            .as_ref()
+            .and_then(|active| self.views.iter().position(|view| view.name == active.name));
+        self.view_state
+            .select(Some(active.map_or(0, |idx| idx + 1)));
+        self.input_mode = InputMode::ViewPicker;
+    }
+
+    fn handle_view_picker_key(&mut self, code: KeyCode) {
+        let len = self.views.len() + 1;
+        let selected = self.view_state.selected().unwrap_or(0);
         match code {
-            KeyCode::Enter => self.execute_goto_line(),
-            KeyCode::Esc => {
-                self.input_mode = InputMode::Normal;
-                self.input_buffer.clear();
+            KeyCode::Esc | KeyCode::Char('q') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.view_state.select(Some((selected + 1) % len));
             }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.view_state.select(Some((selected + len - 1) % len));
             }
-            KeyCode::Char(c) if c.is_ascii_digit() => {
-                self.input_buffer.push(c);
+            KeyCode::Enter => {
+                self.input_mode = InputMode::Normal;
+                let view = selected
+                    .checked_sub(1)
+                    .and_then(|idx| self.views.get(idx))
+                    .cloned();
+                self.apply_view(view);
             }
             _ => {}
         }
     }
 
-    fn start_search(&mut self) {
-        self.search_scope = SearchScope::Diff;
-        self.input_mode = InputMode::Search;
-        self.input_buffer.clear();
+    /// Make `view` the active one (`None` for the plain list), applying its drafts setting.
+    /// Returns the tab it opens on.
+    fn activate_view(&mut self, view: Option<ViewConfig>) -> AppMode {
+        let tab = match &view {
+            Some(view) => {
+                if let Some(drafts) = view.drafts {
+                    self.include_drafts = drafts;
+                }
+                view.tab.into()
+            }
+            None => self.mode,
+        };
+        self.active_view = view;
+        tab
     }
 
-    fn start_tree_name_search(&mut self) {
-        self.search_scope = SearchScope::TreeFileNames;
-        self.input_mode = InputMode::Search;
-        self.input_buffer.clear();
+    /// Switch to a saved view, or back to the plain list with `None`, and reload the list.
+    fn apply_view(&mut self, view: Option<ViewConfig>) {
+        let tab = self.activate_view(view);
+        if self.mode == tab {
+            self.needs_clear = true;
+            self.refresh();
+        } else {
+            self.select_list_tab(tab);
+        }
     }
 
-    fn start_tree_content_search(&mut self) {
-        self.search_scope = SearchScope::TreeContents;
-        self.input_mode = InputMode::Search;
-        self.input_buffer.clear();
+    fn hide_snoozed(&mut self) -> usize {
+        if self.show_snoozed {
+            return 0;
+        }
+        let now = Utc::now();
+        let before = self.prs.len();
+        let snoozes = &self.snoozes;
+        self.prs.retain(|pr| snoozes.until(pr, now).is_none());
+        before - self.prs.len()
     }
 
-    fn apply_current_search_match(&mut self) {
-        if self.search_matches.is_empty() {
+    fn start_snooze(&mut self) {
+        let Some(pr) = self.selected_pr().cloned() else {
             return;
-        }
-        let idx = self.search_matches[self.search_match_idx];
-        match self.search_scope {
-            SearchScope::Diff => {
-                self.scroll_offset = idx as u16;
+        };
+        if self.snoozes.until(&pr, Utc::now()).is_some() {
+            // Only reachable while `Z` shows snoozed PRs: `z` wakes it up again.
+            self.snoozes.wake(&pr);
+            match self.snoozes.save() {
+                Ok(()) => self.set_status(format!("Unsnoozed PR #{}", pr.number)),
+                Err(e) => self.set_error("Error", &e),
             }
-            SearchScope::TreeFileNames | SearchScope::TreeContents => {
-                self.file_tree_state.select(Some(idx));
+            return;
+        }
+        self.input_mode = InputMode::Snooze;
+    }
+
+    fn confirm_snooze(&mut self, duration: SnoozeDuration) {
+        self.input_mode = InputMode::Normal;
+        let Some(pr) = self.selected_pr().cloned() else {
+            return;
+        };
+        let until = duration.until(Local::now());
+        self.snoozes.snooze(&pr, until);
+        if let Err(e) = self.snoozes.save() {
+            self.set_error("Error", &e);
+            return;
+        }
+        if !self.show_snoozed {
+            if let Some(idx) = self.list_state.selected() {
+                self.prs.remove(idx);
+                let count = self.list_item_count();
+                if count == 0 {
+                    self.list_state.select(None);
+                } else if idx >= count {
+                    self.list_state.select(Some(count - 1));
+                }
             }
         }
+        self.set_status(format!(
+            "Snoozed PR #{} until {}",
+            pr.number,
+            until.with_timezone(&Local).format("%a %H:%M")
+        ));
     }
 
-    fn execute_search(&mut self) {
-        if self.input_buffer.is_empty() {
-            self.input_mode = InputMode::Normal;
+    fn load_review_queues(&mut self, force: bool) {
+        if self.loading_review_queues {
+            return;
+        }
+        let ttl = std::time::Duration::from_secs(self.team.queue_cache_sec);
+        let fresh = self
+            .review_queues
+            .as_ref()
+            .is_some_and(|(fetched_at, _)| fetched_at.elapsed() < ttl);
+        if fresh && !force {
             return;
         }
+        self.loading_review_queues = true;
+        let logins = team_logins(&self.team);
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let queues = gh
+                .get_review_queues(&logins)
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::ReviewQueues(queues));
+        });
+    }
 
-        self.search_query = self.input_buffer.clone();
-        self.search_matches.clear();
-        self.search_match_idx = 0;
-        let query_lower = self.search_query.to_lowercase();
+    /// Teammates who could review the selected PR, least loaded first.
+    fn workload_candidates(&self) -> Vec<gh::ReviewQueue> {
+        let (Some(pr), Some((_, queues))) = (self.selected_pr(), self.review_queues.as_ref())
+        else {
+            return Vec::new();
+        };
+        let mut candidates = queues
+            .iter()
+            .filter(|queue| !queue.login.eq_ignore_ascii_case(&pr.author))
+            .cloned()
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| {
+            a.open_requests
+                .cmp(&b.open_requests)
+                .then_with(|| a.login.cmp(&b.login))
+        });
+        candidates
+    }
 
-        match self.search_scope {
-            SearchScope::Diff => {
-                // Search in displayed content (delta output if available, otherwise raw diff)
-                let search_content = self
-                    .filtered_diff_cache
-                    .as_ref()
-                    .or(self.delta_cache.as_ref())
-                    .or(self.diff_cache.as_ref());
-                if let Some(content) = search_content {
-                    for (idx, line) in content.lines().enumerate() {
-                        // Strip ANSI codes for searching in delta output
-                        let clean_line = strip_ansi_codes(line);
-                        if clean_line.to_lowercase().contains(&query_lower) {
-                            self.search_matches.push(idx);
-                        }
+    fn start_workload(&mut self) {
+        if self.selected_pr().is_none() {
+            return;
+        }
+        if team_logins(&self.team).is_empty() {
+            self.set_status("Add team.members to the config to balance reviews".to_string());
+            return;
+        }
+        self.load_review_queues(false);
+        self.workload_state.select(Some(0));
+        self.input_mode = InputMode::Workload;
+    }
+
+    fn assign_selected_reviewer(&mut self) {
+        let candidates = self.workload_candidates();
+        let Some(choice) = self
+            .workload_state
+            .selected()
+            .and_then(|idx| candidates.get(idx))
+        else {
+            return;
+        };
+        let Some(pr) = self.selected_pr().cloned() else {
+            return;
+        };
+        let login = choice.login.clone();
+        self.input_mode = InputMode::Normal;
+        if self.refuse_in_read_only_mode("requesting reviews") {
+            return;
+        }
+        match self.gh.request_reviewer(&pr, &login) {
+            Ok(()) => {
+                let mut entry = AuditEntry::new("request-review", &pr);
+                entry.reason = Some(login.clone());
+                record_audit(entry);
+                // Keep the cached counts honest until the next search.
+                if let Some((_, queues)) = self.review_queues.as_mut() {
+                    if let Some(queue) = queues.iter_mut().find(|q| q.login == login) {
+                        queue.open_requests += 1;
                     }
                 }
+                self.set_status(format!(
+                    "Requested review from @{} on #{}",
+                    login, pr.number
+                ));
             }
-            SearchScope::TreeFileNames => {
-                for (idx, item) in self.file_tree_items.iter().enumerate() {
-                    if let Some(path) = item.file_path.as_ref() {
-                        if path.to_lowercase().contains(&query_lower) {
-                            self.search_matches.push(idx);
-                        }
-                    }
-                }
+            Err(e) => self.set_error("Error", &e),
+        }
+    }
+
+    fn handle_workload_key(&mut self, code: KeyCode) {
+        let len = self.workload_candidates().len();
+        let selected = self.workload_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | 'w') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.workload_state.select(Some((selected + 1) % len));
             }
-            SearchScope::TreeContents => {
-                let mut matched_paths = BTreeSet::new();
-                for section in &self.file_diff_sections {
-                    if section.diff.to_lowercase().contains(&query_lower) {
-                        matched_paths.insert(section.path.clone());
-                    }
-                }
-                for (idx, item) in self.file_tree_items.iter().enumerate() {
-                    if let Some(path) = item.file_path.as_ref() {
-                        if matched_paths.contains(path) {
-                            self.search_matches.push(idx);
-                        }
-                    }
-                }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.workload_state.select(Some((selected + len - 1) % len));
+            }
+            KeyCode::Char('R') => self.load_review_queues(true),
+            KeyCode::Enter => self.assign_selected_reviewer(),
+            _ => {}
+        }
+    }
+
+    fn handle_snooze_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c @ '1'..='3') => {
+                let idx = c as usize - '1' as usize;
+                self.confirm_snooze(SnoozeDuration::ALL[idx]);
             }
+            KeyCode::Esc | KeyCode::Char('n' | 'N' | 'q') => self.input_mode = InputMode::Normal,
+            _ => {}
         }
+    }
 
-        self.input_mode = InputMode::Normal;
+    fn toggle_show_snoozed(&mut self) {
+        self.show_snoozed = !self.show_snoozed;
+        self.snoozes = Snoozes::load();
+        let status = if self.show_snoozed {
+            "Showing snoozed PRs - refreshing..."
+        } else {
+            "Hiding snoozed PRs - refreshing..."
+        };
+        self.set_status(status.to_string());
+        self.refresh();
+    }
+
+    fn start_comment(&mut self) {
+        if self.refuse_in_read_only_mode("commenting") {
+            return;
+        }
+        self.input_mode = InputMode::Comment;
         self.input_buffer.clear();
+    }
 
-        if self.search_matches.is_empty() {
-            let scope = match self.search_scope {
-                SearchScope::Diff => "diff",
-                SearchScope::TreeFileNames => "file names",
-                SearchScope::TreeContents => "file contents",
-            };
-            self.set_status(format!("No {} matches for '{}'", scope, self.search_query));
+    fn start_line_comment(&mut self) {
+        if self.refuse_in_read_only_mode("commenting") || self.refuse_if_closed() {
+            return;
+        }
+        // Only works in diff view with a valid line selected
+        if self.detail_tab != DetailTab::Diff {
+            self.start_comment(); // Fall back to general comment
             return;
         }
 
-        // Jump to first match
-        self.apply_current_search_match();
-        self.set_status(format_search_status(
-            0,
-            self.search_matches.len(),
-            &self.search_query,
-        ));
-    }
+        if self.showing_large_diff_tree() {
+            self.set_status("Select a file first (Enter) to comment on a line".to_string());
+            return;
+        }
 
-    fn next_search_match(&mut self) {
-        if self.search_matches.is_empty() {
+        let anchor = self.diff_selection_anchor.take();
+        let Some((file_path, line_number, side)) = self.comment_target_at(self.diff_cursor) else {
+            let using_delta =
+                self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none();
+            self.set_status(if using_delta {
+                "Cannot comment on this line. Move to a code line with line numbers.".to_string()
+            } else {
+                "Cannot comment on this line. Move to an added, removed, or context line."
+                    .to_string()
+            });
             return;
+        };
+        let mut ctx = LineCommentContext {
+            file_path,
+            line_number,
+            side,
+            start_line: None,
+        };
+        if let Some(anchor) = anchor {
+            match self.comment_target_at(anchor) {
+                Some((path, line, anchor_side)) if path == ctx.file_path && anchor_side == side => {
+                    let (start, end) = (line.min(line_number), line.max(line_number));
+                    ctx.start_line = Some(start).filter(|start| *start < end);
+                    ctx.line_number = end;
+                }
+                _ => {
+                    self.set_status(
+                        "A multi-line comment must start and end on the same side of one file"
+                            .to_string(),
+                    );
+                    return;
+                }
+            }
         }
-        self.search_match_idx =
-            advance_search_idx(self.search_match_idx, self.search_matches.len());
-        self.apply_current_search_match();
-        self.set_status(format_search_status(
-            self.search_match_idx,
-            self.search_matches.len(),
-            &self.search_query,
-        ));
+        self.line_comment_ctx = Some(ctx);
+        self.input_mode = InputMode::LineComment;
+        self.input_buffer.clear();
     }
 
-    fn prev_search_match(&mut self) {
-        if self.search_matches.is_empty() {
+    /// File, line and side a comment on diff row `row` lands on. Built-in rows comment on the
+    /// new file for added and context lines and on the old file for removed lines.
+    fn comment_target_at(&self, row: u16) -> Option<(String, u32, CommentSide)> {
+        let using_delta =
+            self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none();
+        if using_delta {
+            let info = self.delta_line_info.get(row as usize)?;
+            let (line, side) = delta_comment_target(info, self.diff_column)?;
+            return Some((info.file_path.clone()?, line, side));
+        }
+        let diff_line = self.active_diff_lines().get(row as usize)?;
+        let (line, side) = diff_line
+            .line_number
+            .map(|line| (line, CommentSide::Right))
+            .or_else(|| {
+                diff_line
+                    .old_line_number
+                    .map(|line| (line, CommentSide::Left))
+            })?;
+        Some((diff_line.file_path.clone()?, line, side))
+    }
+
+    /// Start or cancel a line selection for a multi-line comment.
+    fn toggle_diff_selection(&mut self) {
+        if self.diff_selection_anchor.take().is_some() {
+            self.set_status("Selection cleared".to_string());
             return;
         }
-        self.search_match_idx =
-            retreat_search_idx(self.search_match_idx, self.search_matches.len());
-        self.apply_current_search_match();
-        self.set_status(format_search_status(
-            self.search_match_idx,
-            self.search_matches.len(),
-            &self.search_query,
-        ));
+        if self.refuse_in_read_only_mode("commenting") || self.refuse_if_closed() {
+            return;
+        }
+        self.diff_selection_anchor = Some(self.diff_cursor);
+        self.set_status(
+            "Selecting lines: j/k to extend, c to comment, v or Esc to cancel".to_string(),
+        );
     }
 
-    // List search methods
-    fn start_list_search(&mut self) {
-        self.input_mode = InputMode::ListSearch;
-        self.input_buffer.clear();
+    /// Diff rows covered by the `v` selection, cursor included
+    fn diff_selection_rows(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        self.diff_selection_anchor.map(|anchor| {
+            let (anchor, cursor) = (anchor as usize, self.diff_cursor as usize);
+            anchor.min(cursor)..=anchor.max(cursor)
+        })
     }
 
-    fn execute_list_search(&mut self) {
-        let query = self.input_buffer.trim();
-        if query.is_empty() {
-            self.input_mode = InputMode::Normal;
+    /// Pick the side-by-side column (old/new) that line comments target.
+    fn select_diff_column(&mut self, column: CommentSide) {
+        let using_delta =
+            self.use_delta && self.delta_cache.is_some() && self.filtered_diff_cache.is_none();
+        if !using_delta {
+            self.set_status(
+                "Columns apply to delta's side-by-side view; comments follow the line type here"
+                    .to_string(),
+            );
             return;
         }
+        self.diff_column = column;
+    }
 
-        self.search_query = query.to_string();
-        self.search_matches.clear();
-        self.search_match_idx = 0;
+    fn submit_line_comment(&mut self) {
+        if self.input_buffer.trim().is_empty() {
+            self.input_mode = InputMode::Normal;
+            self.line_comment_ctx = None;
+            return;
+        }
 
-        // Search in PR title/repo/author/number
-        let query_lower = self.search_query.to_lowercase();
-        for (idx, pr) in self.prs.iter().enumerate() {
-            if pr_matches_list_query(pr, &query_lower) {
-                self.search_matches.push(idx);
-            }
+        if let (Some(pr), Some(ctx)) = (self.selected_pr().cloned(), self.line_comment_ctx.take()) {
+            let body = self.input_buffer.clone();
+            self.post_line_comment(pr, ctx, body);
         }
 
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
-
-        if self.search_matches.is_empty() {
-            self.set_status(format!("No PRs matching '{}'", self.search_query));
-        } else {
-            // Jump to first match
-            self.list_state.select(Some(self.search_matches[0]));
-            self.set_status(format_search_status(
-                0,
-                self.search_matches.len(),
-                &self.search_query,
-            ));
-        }
     }
 
-    fn next_list_search_match(&mut self) {
-        if self.search_matches.is_empty() {
+    /// Post a line comment, or queue it while a review of the PR is in progress.
+    fn post_line_comment(&mut self, pr: PullRequest, ctx: LineCommentContext, body: String) {
+        let Some(draft) = self.pending_reviews.get_mut(&pr_key(&pr)) else {
+            self.start_action(pr, PrAction::LineComment(ctx, body), None);
             return;
-        }
-        self.search_match_idx =
-            advance_search_idx(self.search_match_idx, self.search_matches.len());
-        self.list_state
-            .select(Some(self.search_matches[self.search_match_idx]));
-        self.set_status(format_search_status(
-            self.search_match_idx,
-            self.search_matches.len(),
-            &self.search_query,
+        };
+        draft.comments.push(DraftComment {
+            path: ctx.file_path.clone(),
+            start_line: ctx.start_line,
+            line: ctx.line_number,
+            side: match ctx.side {
+                CommentSide::Left => "LEFT",
+                CommentSide::Right => "RIGHT",
+            }
+            .to_string(),
+            body,
+        });
+        let queued = draft.comments.len();
+        self.set_status(format!(
+            "Queued comment on {} ({} in review, s: submit)",
+            ctx.location(),
+            queued
         ));
     }
 
-    fn prev_list_search_match(&mut self) {
-        if self.search_matches.is_empty() {
+    /// Start batching line comments into a review of the open PR, or open the dialog that
+    /// submits the review once one is in progress.
+    fn start_review(&mut self) {
+        if self.refuse_in_read_only_mode("reviewing") || self.refuse_if_closed() {
             return;
         }
-        self.search_match_idx =
-            retreat_search_idx(self.search_match_idx, self.search_matches.len());
-        self.list_state
-            .select(Some(self.search_matches[self.search_match_idx]));
-        self.set_status(format_search_status(
-            self.search_match_idx,
-            self.search_matches.len(),
-            &self.search_query,
-        ));
+        let Some(key) = self.selected_pr().map(pr_key) else {
+            return;
+        };
+        match self.pending_reviews.get(&key) {
+            Some(draft) => {
+                self.input_buffer = draft.body.clone();
+                self.review_event = ReviewEvent::Comment;
+                self.input_mode = InputMode::ReviewSummary;
+            }
+            None => {
+                self.pending_reviews.insert(key, DraftReview::default());
+                self.set_status(
+                    "Review started: line comments are queued until you press s to submit"
+                        .to_string(),
+                );
+            }
+        }
     }
 
-    fn start_goto_line(&mut self) {
-        self.input_mode = InputMode::GotoLine;
-        self.input_buffer.clear();
+    fn handle_review_summary_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.submit_review(),
+            KeyCode::Esc => {
+                // Keep drafting; the summary typed so far stays with the review
+                if let Some(key) = self.selected_pr().map(pr_key) {
+                    if let Some(draft) = self.pending_reviews.get_mut(&key) {
+                        draft.body = std::mem::take(&mut self.input_buffer);
+                    }
+                }
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                let events = [
+                    ReviewEvent::Comment,
+                    ReviewEvent::Approve,
+                    ReviewEvent::RequestChanges,
+                ];
+                let idx = events
+                    .iter()
+                    .position(|event| *event == self.review_event)
+                    .unwrap_or(0);
+                let next = if code == KeyCode::Tab {
+                    idx + 1
+                } else {
+                    idx + events.len() - 1
+                };
+                self.review_event = events[next % events.len()];
+            }
+            KeyCode::Delete => {
+                if let Some(key) = self.selected_pr().map(pr_key) {
+                    if let Some(draft) = self.pending_reviews.remove(&key) {
+                        self.set_status(format!(
+                            "Discarded review with {} queued comment(s)",
+                            draft.comments.len()
+                        ));
+                    }
+                }
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
     }
 
-    fn execute_goto_line(&mut self) {
-        if let Ok(line_num) = self.input_buffer.parse::<u16>() {
-            // Find the diff line that corresponds to this line number
-            if let Some(idx) = self
-                .active_diff_lines()
-                .iter()
-                .position(|dl| dl.line_number.map(|n| n as u16) == Some(line_num))
-            {
-                self.scroll_offset = idx as u16;
-                self.set_status(format!("Jumped to line {}", line_num));
-            } else {
-                // Just scroll to that offset as fallback
-                self.scroll_offset = line_num.saturating_sub(1);
-                self.set_status(format!("Scrolled to position {}", line_num));
-            }
+    fn submit_review(&mut self) {
+        let Some(pr) = self.selected_pr().cloned() else {
+            return;
+        };
+        let body = self.input_buffer.trim().to_string();
+        if body.is_empty() && self.review_event == ReviewEvent::RequestChanges {
+            self.set_status("Requesting changes needs a summary".to_string());
+            return;
         }
-        self.input_mode = InputMode::Normal;
+        let Some(mut draft) = self.pending_reviews.remove(&pr_key(&pr)) else {
+            return;
+        };
+        if body.is_empty() && draft.comments.is_empty() && self.review_event == ReviewEvent::Comment
+        {
+            self.pending_reviews.insert(pr_key(&pr), draft);
+            self.set_status(
+                "Nothing to submit: queue line comments or write a summary".to_string(),
+            );
+            return;
+        }
+        draft.body = body;
         self.input_buffer.clear();
+        self.input_mode = InputMode::Normal;
+        self.start_action(pr, PrAction::SubmitReview(draft, self.review_event), None);
     }
 
-    fn clear_search(&mut self) {
-        self.search_query.clear();
-        self.search_matches.clear();
-        self.search_match_idx = 0;
-        self.search_scope = SearchScope::Diff;
+    /// Put a review that failed to submit back in front of anything queued since.
+    fn restore_pending_review(&mut self, pr: &PullRequest, mut draft: DraftReview) {
+        let pending = self.pending_reviews.entry(pr_key(pr)).or_default();
+        draft.comments.append(&mut pending.comments);
+        if !pending.body.is_empty() {
+            draft.body = std::mem::take(&mut pending.body);
+        }
+        *pending = draft;
     }
-}
 
-pub fn draw(frame: &mut Frame, app: &mut App) {
-    // Clear screen only when view/tab/selection changed
-    match app.view {
-        View::List => draw_list(frame, app),
-        View::Detail => draw_detail(frame, app),
+    fn launch_ai_review(&mut self) {
+        self.launch_ai_review_with(self.ai.clone(), false);
     }
 
-    // Draw status message in top right corner if present
-    if let Some(msg) = &app.status_message {
-        let area = frame.area();
-        let msg_width = (msg.len() as u16 + 4).min(area.width / 2);
-        let popup_area = Rect {
-            x: area.width.saturating_sub(msg_width + 1),
-            y: 0,
-            width: msg_width,
-            height: 1,
-        };
-        let popup =
-            Paragraph::new(msg.as_str()).style(Style::default().fg(Color::Black).bg(Color::Yellow));
-        frame.render_widget(popup, popup_area);
+    fn start_ai_choice(&mut self) {
+        if self.launching_ai || self.selected_pr().is_none() {
+            return;
+        }
+        self.ai_choices = self.ai.choices();
+        self.ai_choice_state.select(Some(0));
+        self.input_mode = InputMode::AiChoice;
     }
 
-    // Draw comment input if active
-    if app.input_mode == InputMode::Comment {
-        draw_comment_input(frame, app);
+    fn handle_ai_choice_key(&mut self, code: KeyCode) {
+        let len = self.ai_choices.len();
+        let selected = self.ai_choice_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.ai_choice_state.select(Some((selected + 1) % len));
+            }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.ai_choice_state
+                    .select(Some((selected + len - 1) % len));
+            }
+            KeyCode::Enter => {
+                let Some((_, ai)) = self.ai_choices.get(selected).cloned() else {
+                    return;
+                };
+                self.input_mode = InputMode::Normal;
+                self.launch_ai_review_with(ai, true);
+            }
+            _ => {}
+        }
     }
 
-    // Draw line comment input if active
-    if app.input_mode == InputMode::LineComment {
-        draw_line_comment_input(frame, app);
-    }
+    /// Launch a review of the selected PR with `ai`. `record` notes the run (and its provider)
+    /// in the daemon state when the daemon tracks the PR.
+    fn launch_ai_review_with(&mut self, ai: AiConfig, record: bool) {
+        if self.launching_ai {
+            return;
+        }
+        if let Some(pr) = self.selected_pr().cloned() {
+            if let Err(err) = gh::validate_ai_launch_config(&ai) {
+                self.set_status(format!("AI launch is not configured: {:#}", err));
+                return;
+            }
 
-    // Draw confirmation dialog if active
-    if app.input_mode == InputMode::ConfirmApprove {
-        draw_confirm_dialog(frame, app);
-    }
+            let ai_display_name = ai.display_name();
+            self.launching_ai = true;
+            if self.ai.launch.uses_api() {
+                self.set_status(format!(
+                    "Creating worktree and running {} API review...",
+                    ai_display_name
+                ));
+            } else {
+                self.set_status(format!(
+                    "Creating worktree and launching {}...",
+                    ai_display_name
+                ));
+            }
 
-    // Draw close dialog if active
-    if app.input_mode == InputMode::ConfirmClose {
-        draw_close_dialog(frame, app);
+            let tx = self.async_tx.clone();
+            let repos_root = self.repos_root.clone();
+            thread::spawn(move || {
+                let result = gh::create_pr_worktree(&pr, &repos_root)
+                    .and_then(|worktree_path| {
+                        gh::launch_ai(&worktree_path, &pr, &ai)?;
+                        Ok(worktree_path.display().to_string())
+                    })
+                    .map_err(|e| format!("{:#}", e));
+                if record {
+                    let outcome = result.as_ref().map(|_| ()).map_err(Clone::clone);
+                    if let Err(err) = core::record_manual_trigger(
+                        &pr.repo_name,
+                        pr.number,
+                        ai.provider_key(),
+                        outcome,
+                    ) {
+                        tracing::warn!(error = %format!("{:#}", err), "failed to update daemon state");
+                    }
+                }
+                let _ = tx.send(AsyncResult::AiLaunch(ai_display_name, result));
+            });
+        }
     }
 
-    // Draw merge dialog if active
-    if app.input_mode == InputMode::ConfirmMerge {
-        draw_merge_dialog(frame, app);
+    fn submit_comment(&mut self) {
+        if self.input_buffer.trim().is_empty() {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+
+        if let Some(pr) = self.selected_pr().cloned() {
+            let body = self.input_buffer.clone();
+            let action = match self.reply_target.take() {
+                Some(target) => PrAction::Reply(target, body),
+                None => PrAction::Comment(body),
+            };
+            self.start_action(pr, action, None);
+        }
+
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
     }
 
-    // Draw search input if active
-    if app.input_mode == InputMode::Search || app.input_mode == InputMode::ListSearch {
-        draw_search_input(frame, app);
+    /// Move the Comments tab's thread selection, scrolling the thread into view.
+    fn select_review_thread(&mut self, forward: bool) {
+        let count = self
+            .review_comments_cache
+            .as_deref()
+            .map_or(0, |comments| review_threads(comments).len());
+        if count == 0 {
+            self.set_status("No review threads to select".to_string());
+            return;
+        }
+        self.selected_thread = Some(match (self.selected_thread, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(idx), true) => (idx + 1).min(count - 1),
+            (Some(idx), false) => idx.saturating_sub(1),
+        });
+        self.scroll_to_thread = true;
     }
 
-    // Draw goto line input if active
-    if app.input_mode == InputMode::GotoLine {
-        draw_goto_input(frame, app);
+    /// Once the last thread awaiting me has a reply, point at the re-request.
+    fn prompt_rerequest_after_reply(&mut self, pr: &PullRequest, replied_thread: u64) {
+        let Some(comments) = self.review_comments_cache.as_deref() else {
+            return;
+        };
+        let waiting = threads_awaiting_reply(comments, &self.username);
+        let reviewers = rerequest_candidates(pr, &self.username);
+        if waiting.iter().all(|id| *id == replied_thread) && !reviewers.is_empty() {
+            let mentions: Vec<String> = reviewers.iter().map(|l| format!("@{}", l)).collect();
+            self.set_status(format!(
+                "All review threads answered - R re-requests review from {}",
+                mentions.join(", ")
+            ));
+        }
     }
-}
 
-fn review_state_span(state: &ReviewState) -> Span<'static> {
-    match state {
-        ReviewState::Approved => Span::styled("[✓ APPROVED] ", Style::default().fg(Color::Green)),
-        ReviewState::ChangesRequested => {
-            Span::styled("[! CHANGES] ", Style::default().fg(Color::Red))
+    /// Ask the reviewers who commented or requested changes to look again. Threads still
+    /// waiting on a reply get a warning first; a second `R` re-requests anyway.
+    fn rerequest_review(&mut self) {
+        if self.refuse_in_read_only_mode("re-requesting review") || self.refuse_if_closed() {
+            return;
         }
-        ReviewState::Pending => Span::styled("[○ PENDING] ", Style::default().fg(Color::Yellow)),
-        ReviewState::Draft => Span::styled("[DRAFT] ", Style::default().fg(Color::Magenta)),
+        let Some(pr) = self.selected_pr().cloned() else {
+            return;
+        };
+        let reviewers = rerequest_candidates(&pr, &self.username);
+        if reviewers.is_empty() {
+            self.set_status(
+                "No reviews to re-request: nobody has commented or requested changes".to_string(),
+            );
+            return;
+        }
+        let waiting = self.review_comments_cache.as_deref().map_or(0, |comments| {
+            threads_awaiting_reply(comments, &self.username).len()
+        });
+        if waiting > 0 && !std::mem::take(&mut self.rerequest_warned) {
+            self.rerequest_warned = true;
+            self.set_status(format!(
+                "{} review thread(s) still await your reply - press R again to re-request anyway",
+                waiting
+            ));
+            return;
+        }
+        self.rerequest_warned = false;
+        self.start_action(pr, PrAction::RerequestReview(reviewers), None);
     }
-}
 
-fn draw_list(frame: &mut Frame, app: &mut App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ])
-        .split(frame.area());
+    /// Resolve the selected review thread, or unresolve it if it already is.
+    fn toggle_thread_resolved(&mut self) {
+        if self.refuse_in_read_only_mode("resolving threads") || self.refuse_if_closed() {
+            return;
+        }
+        let Some(pr) = self.selected_pr().cloned() else {
+            return;
+        };
+        let thread = self.selected_thread.and_then(|idx| {
+            let comments = self.review_comments_cache.as_deref()?;
+            review_threads(comments).get(idx).copied().cloned()
+        });
+        let Some(thread) = thread else {
+            self.set_status("Select a review thread with ] or [ first".to_string());
+            return;
+        };
+        let Some(state) = self.review_thread_states.get(&thread.id) else {
+            self.set_status(
+                "Resolution of this thread is unknown (GraphQL lookup failed)".to_string(),
+            );
+            return;
+        };
+        let action = PrAction::SetThreadResolved {
+            thread_id: state.id.clone(),
+            location: match thread.line {
+                Some(line) => format!("{}:{}", thread.path, line),
+                None => thread.path.clone(),
+            },
+            resolved: !state.is_resolved,
+        };
+        self.start_action(pr, action, None);
+    }
 
-    let tab_specs = [
-        ("Involving Me", AppMode::Review),
-        ("My PRs", AppMode::MyPrs),
-        ("Watching Repos", AppMode::Watching),
-    ];
-    let mut tab_constraints: Vec<Constraint> = tab_specs
-        .iter()
-        .enumerate()
-        .flat_map(|(idx, (label, _))| {
-            let width = label.chars().count() as u16 + 4;
-            let mut parts = vec![Constraint::Length(width)];
-            if idx + 1 < tab_specs.len() {
-                parts.push(Constraint::Length(1));
-            }
-            parts
-        })
-        .collect();
-    tab_constraints.push(Constraint::Min(0));
-    let tab_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(tab_constraints)
-        .split(chunks[0]);
+    fn start_reply(&mut self) {
+        if self.refuse_in_read_only_mode("replying") || self.refuse_if_closed() {
+            return;
+        }
+        let thread = self.selected_thread.and_then(|idx| {
+            let comments = self.review_comments_cache.as_deref()?;
+            review_threads(comments)
+                .get(idx)
+                .map(|comment| ReplyTarget {
+                    comment_id: comment.id,
+                    location: match comment.line {
+                        Some(line) => format!("{}:{}", comment.path, line),
+                        None => comment.path.clone(),
+                    },
+                })
+        });
+        let Some(target) = thread else {
+            self.start_comment();
+            return;
+        };
+        self.reply_target = Some(target);
+        self.input_mode = InputMode::Comment;
+        self.input_buffer.clear();
+    }
 
-    for (idx, (label, mode)) in tab_specs.iter().enumerate() {
-        let area_idx = idx * 2;
-        let area = tab_chunks[area_idx];
-        if app.mode == *mode {
-            let selected = Paragraph::new(Line::from(Span::styled(
-                *label,
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )))
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-                    .border_style(Style::default().fg(Color::White)),
-            );
-            frame.render_widget(selected, area);
+    fn start_approve(&mut self) {
+        if self.selected_pr().is_none()
+            || self.refuse_in_read_only_mode("approving")
+            || self.refuse_if_closed()
+        {
+            return;
+        }
+        self.input_buffer.clear();
+        self.editing_approve_message = false;
+        if self.confirm.approve {
+            self.input_mode = InputMode::ConfirmApprove;
         } else {
-            let unselected = Paragraph::new(Line::from(Span::styled(
-                *label,
-                Style::default().fg(Color::DarkGray),
-            )))
-            .alignment(Alignment::Center)
-            .block(Block::default().padding(Padding::new(1, 1, 1, 1)));
-            frame.render_widget(unselected, area);
+            self.confirm_approve();
         }
     }
 
-    let selected_tab_area = tab_specs
-        .iter()
-        .position(|(_, mode)| *mode == app.mode)
-        .map(|idx| tab_chunks[idx * 2]);
-
-    let border_area = Rect {
-        x: chunks[0].x,
-        y: chunks[0]
-            .y
-            .saturating_add(chunks[0].height.saturating_sub(1)),
-        width: chunks[0].width,
-        height: 1,
-    };
-    let mut border_chars = vec!['─'; usize::from(border_area.width)];
-    if !border_chars.is_empty() {
-        border_chars[0] = '┌';
-        if border_chars.len() > 1 {
-            let last = border_chars.len() - 1;
-            border_chars[last] = '┐';
+    /// Checklist summary for the approval comment, when enabled and the repo has one.
+    fn checklist_review_body(&self) -> Option<String> {
+        if !self.checklist_config.include_in_review {
+            return None;
         }
+        self.checklist_cache
+            .as_deref()
+            .filter(|items| !items.is_empty())
+            .map(checklist::render_markdown)
     }
-    if let Some(area) = selected_tab_area {
-        if area.width >= 2 && !border_chars.is_empty() {
-            let start = usize::from(area.x.saturating_sub(chunks[0].x));
-            if start < border_chars.len() {
-                let mut end = start + usize::from(area.width.saturating_sub(1));
-                end = end.min(border_chars.len() - 1);
-                border_chars[start] = if start == 0 { '│' } else { '┘' };
-                border_chars[end] = if end == border_chars.len() - 1 {
-                    '│'
-                } else {
-                    '└'
-                };
-                if end > start + 1 {
-                    for ch in &mut border_chars[(start + 1)..end] {
-                        *ch = ' ';
-                    }
+
+    fn confirm_approve(&mut self) {
+        if let Some(pr) = self.selected_pr().cloned() {
+            let message = Some(self.input_buffer.trim().to_string()).filter(|m| !m.is_empty());
+            let body = match (message, self.checklist_review_body()) {
+                (Some(message), Some(checklist)) => Some(format!("{}\n\n{}", message, checklist)),
+                (message, checklist) => message.or(checklist),
+            };
+            if self.list_config.approved != ApprovedPrs::Remove {
+                self.keep_approved_pr(&pr);
+                self.start_action(pr, PrAction::Approve(body), None);
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                self.editing_approve_message = false;
+                return;
+            }
+            // Remove from list right away; a failed approval puts it back.
+            let removed_at = self.list_state.selected();
+            if let Some(idx) = removed_at {
+                self.prs.remove(idx);
+                if self.prs.is_empty() {
+                    self.list_state.select(None);
+                    self.view = View::List;
+                } else if idx >= self.prs.len() {
+                    self.list_state.select(Some(self.prs.len() - 1));
+                }
+                if self.view == View::Detail && !self.prs.is_empty() {
+                    self.diff_cache = None;
+                    self.comments_cache = None;
+                    self.review_comments_cache = None;
+                    self.feedback_cache = None;
+                } else if self.prs.is_empty() {
+                    self.view = View::List;
                 }
             }
+            self.start_action(pr, PrAction::Approve(body), removed_at);
         }
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        self.editing_approve_message = false;
     }
-    let border_line: String = border_chars.into_iter().collect();
-    frame.render_widget(
-        Paragraph::new(border_line).style(Style::default().fg(Color::White)),
-        border_area,
-    );
 
-    let mut items: Vec<ListItem> = app
-        .prs
-        .iter()
-        .map(|pr| {
-            let stats = if pr.details_loaded {
-                format!("+{}/-{}", pr.additions, pr.deletions)
-            } else {
-                "+?/-?".to_string()
-            };
-            let age = format_age(&pr.updated_at);
-            let mut title_spans = vec![
-                Span::styled(
-                    format!("[{}] ", pr.repo_name),
-                    Style::default().fg(Color::Cyan),
-                ),
-                Span::raw(format!("#{}: ", pr.number)),
-            ];
-            // Show review state in MyPrs mode, draft status in Review mode
-            if app.mode == AppMode::MyPrs {
-                title_spans.push(review_state_span(&pr.review_state));
-            } else if pr.is_draft {
-                title_spans.push(Span::styled(
-                    "[DRAFT] ",
-                    Style::default().fg(Color::Magenta),
-                ));
-            }
-            title_spans.push(Span::styled(
-                &pr.title,
-                Style::default().add_modifier(Modifier::BOLD),
-            ));
-            let line = Line::from(title_spans);
-            let details = Line::from(vec![
-                Span::styled(
-                    format!("  @{}", pr.author),
-                    Style::default().fg(Color::Green),
-                ),
-                Span::raw(" | "),
-                Span::styled(stats, Style::default().fg(Color::Yellow)),
-                Span::raw(" | "),
-                Span::styled(age, Style::default().fg(Color::DarkGray)),
-            ]);
-            ListItem::new(vec![line, details])
-        })
-        .collect();
+    /// Keep a PR I'm approving in the list, greyed out or moved to the approved section.
+    fn keep_approved_pr(&mut self, pr: &PullRequest) {
+        self.approved_prs.insert(pr_key(pr));
+        // From the list, the selection moves on to the next PR; in the detail view it stays put.
+        self.order_approved_last(self.view == View::Detail);
+        self.group_by_repo(self.view == View::Detail);
+    }
 
-    if app.pagination_row_count() > 0 {
-        let label = if app.loading_next_page {
-            "Loading more PRs..."
-        } else {
-            "More PRs..."
+    /// Sort the list by the active view's `order`, or the Review list by `list.order`; PRs the
+    /// rules can't tell apart fall back to `list.sort`.
+    /// `follow` keeps the selected PR selected; otherwise the selected row stays.
+    fn order_review_queue(&mut self, follow: bool) {
+        let selected = self.selected_pr().map(pr_key);
+        self.prs.append(&mut self.folded_prs);
+        let rules = match self
+            .active_view
+            .as_ref()
+            .and_then(|view| view.order.as_ref())
+        {
+            Some(order) => order.as_slice(),
+            None if self.mode == AppMode::Review => &self.list_config.order,
+            None => &[],
         };
-        items.push(ListItem::new(Line::from(vec![Span::styled(
-            label,
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::ITALIC),
-        )])));
+        let sort = self.list_config.sort;
+        let teammates = team_logins(&self.team);
+        self.prs.sort_by(|a, b| {
+            compare_by_order_rules(a, b, rules, &teammates)
+                .then_with(|| compare_by_sort(a, b, sort))
+        });
+        if !follow {
+            return;
+        }
+        if let Some(idx) = selected.and_then(|key| self.prs.iter().position(|pr| pr_key(pr) == key))
+        {
+            self.list_state.select(Some(idx));
+        }
     }
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::White)),
-        )
-        .highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("▶ ");
+    /// With `list.approved = "section"`, move approved PRs below the rest, keeping their order.
+    /// `follow` keeps the selected PR selected; otherwise the selected row stays.
+    fn order_approved_last(&mut self, follow: bool) {
+        if self.list_config.approved != ApprovedPrs::Section {
+            return;
+        }
+        let selected = self.selected_pr().map(pr_key);
+        self.prs.append(&mut self.folded_prs);
+        let (approved, username) = (&self.approved_prs, &self.username);
+        self.prs
+            .sort_by_key(|pr| approved_by(pr, approved, username));
+        if !follow {
+            return;
+        }
+        if let Some(idx) = selected.and_then(|key| self.prs.iter().position(|pr| pr_key(pr) == key))
+        {
+            self.list_state.select(Some(idx));
+        }
+    }
 
-    frame.render_stateful_widget(list, chunks[1], &mut app.list_state);
-    app.load_next_page_if_pagination_visible(chunks[1].height);
+    /// With `list.group_by_repo`, keep each repo's PRs together, in the order of their first
+    /// PR, and fold collapsed repos down to that first PR.
+    /// `follow` keeps the selected PR (or its folded group) selected; otherwise the row stays.
+    fn group_by_repo(&mut self, follow: bool) {
+        let selected = self.selected_pr().cloned();
+        self.prs.append(&mut self.folded_prs);
+        if !self.list_config.group_by_repo {
+            return;
+        }
+        let mut rank: BTreeMap<String, usize> = BTreeMap::new();
+        for pr in &self.prs {
+            let next = rank.len();
+            rank.entry(pr.repo_name.clone()).or_insert(next);
+        }
+        self.prs.sort_by_key(|pr| rank[&pr.repo_name]);
+        let mut seen = BTreeSet::new();
+        let collapsed = &self.collapsed_repos;
+        let (shown, folded) = std::mem::take(&mut self.prs).into_iter().partition(|pr| {
+            seen.insert(pr.repo_name.clone()) || !collapsed.contains(&pr.repo_name)
+        });
+        self.prs = shown;
+        self.folded_prs = folded;
+        if let Some(pr) = selected.filter(|_| follow) {
+            let idx = self
+                .prs
+                .iter()
+                .position(|row| pr_key(row) == pr_key(&pr))
+                .or_else(|| {
+                    self.prs
+                        .iter()
+                        .position(|row| row.repo_name == pr.repo_name)
+                });
+            self.list_state.select(idx);
+        } else if let Some(idx) = self.list_state.selected() {
+            self.list_state
+                .select(Some(idx.min(self.list_item_count().saturating_sub(1))));
+        }
+    }
 
-    let help = Paragraph::new(
-        " Tab/←/→: switch tabs | j/k: navigate | Ctrl+d/u: page | Enter: open | /: search | o: browser | y: copy URL | R: refresh | q: quit",
-    )
-    .style(Style::default().fg(Color::DarkGray))
-    .block(Block::default().borders(Borders::ALL).title(" Help "));
-    frame.render_widget(help, chunks[2]);
-}
+    /// Whether the list row at `idx` stands for a whole collapsed repo.
+    fn is_folded_row(&self, idx: usize) -> bool {
+        self.list_config.group_by_repo
+            && self
+                .prs
+                .get(idx)
+                .is_some_and(|pr| self.collapsed_repos.contains(&pr.repo_name))
+    }
 
-fn draw_detail(frame: &mut Frame, app: &mut App) {
-    let pr = match app.selected_pr() {
-        Some(pr) => pr,
-        None => return,
-    };
+    fn toggle_group_by_repo(&mut self) {
+        let group = !self.list_config.group_by_repo;
+        self.list_config.group_by_repo = group;
+        self.save_list_setting("grouping", |list| list.group_by_repo = group);
+        self.needs_clear = true;
+        self.group_by_repo(true);
+        if group {
+            self.set_status("Grouping PRs by repo (Space: fold a repo)".to_string());
+        } else {
+            // The fetched order is gone once grouped, so fetch it again
+            self.set_status("Showing PRs as one list - refreshing...".to_string());
+            self.refresh();
+        }
+    }
 
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ])
-        .split(frame.area());
+    /// Fold the selected PR's repo down to its header, or unfold it.
+    fn toggle_repo_fold(&mut self) {
+        if !self.list_config.group_by_repo {
+            self.set_status("Press r to group PRs by repo first".to_string());
+            return;
+        }
+        let Some(repo) = self.selected_pr().map(|pr| pr.repo_name.clone()) else {
+            return;
+        };
+        if !self.collapsed_repos.remove(&repo) {
+            self.collapsed_repos.insert(repo);
+        }
+        self.needs_clear = true;
+        self.group_by_repo(true);
+    }
 
-    // Build CI status indicator
-    let ci_status = if app.loading_checks {
-        Span::styled(" CI: ...", Style::default().fg(Color::DarkGray))
-    } else if let Some(checks) = &app.checks_cache {
-        if checks.is_empty() {
-            Span::raw("")
+    fn toggle_preview(&mut self) {
+        let preview = !self.list_config.preview;
+        self.list_config.preview = preview;
+        self.save_list_setting("layout", |list| list.preview = preview);
+        self.needs_clear = true;
+        self.set_status(if preview {
+            "Previewing the highlighted PR".to_string()
         } else {
-            let (passed, failed, pending) = checks.iter().fold((0, 0, 0), |(p, f, pe), c| {
-                match c.status {
-                    gh::CheckState::Success => (p + 1, f, pe),
-                    gh::CheckState::Failure => (p, f + 1, pe),
-                    gh::CheckState::Pending => (p, f, pe + 1),
-                    gh::CheckState::Neutral => (p + 1, f, pe), // Count neutral as passed
-                }
-            });
-            if failed > 0 {
-                Span::styled(
-                    format!(" CI: {}/{} ✗", passed, passed + failed + pending),
-                    Style::default().fg(Color::Red),
-                )
-            } else if pending > 0 {
-                Span::styled(
-                    format!(" CI: {}/{} ○", passed, passed + pending),
-                    Style::default().fg(Color::Yellow),
-                )
-            } else {
-                Span::styled(
-                    format!(" CI: {} ✓", passed),
-                    Style::default().fg(Color::Green),
-                )
-            }
+            "Preview hidden".to_string()
+        });
+    }
+
+    /// Fetch the highlighted PR's details for the preview pane, once per PR highlighted, so a
+    /// failed fetch isn't retried on every frame.
+    fn load_preview_details(&mut self) {
+        let Some(key) = self.selected_pr().map(pr_key) else {
+            return;
+        };
+        if self.loading_details || self.previewed_pr.as_ref() == Some(&key) {
+            return;
         }
-    } else {
-        Span::raw("")
-    };
+        self.previewed_pr = Some(key);
+        self.load_details();
+    }
 
-    // Header
-    let header = Paragraph::new(Line::from(vec![
-        Span::styled(
-            format!("[{}] ", pr.repo_name),
-            Style::default().fg(Color::Cyan),
-        ),
-        Span::raw(format!("#{}: ", pr.number)),
-        Span::styled(&pr.title, Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" by "),
-        Span::styled(format!("@{}", pr.author), Style::default().fg(Color::Green)),
-        ci_status,
-    ]))
-    .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
-    frame.render_widget(header, chunks[0]);
+    fn cancel_approve(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        self.editing_approve_message = false;
+    }
 
-    // Tabs
-    let tabs = Tabs::new(vec!["Description", "Diff", "Comments", "Agent"])
-        .select(match app.detail_tab {
-            DetailTab::Description => 0,
-            DetailTab::Diff => 1,
-            DetailTab::Comments => 2,
-            DetailTab::Agent => 3,
-        })
-        .style(Style::default().fg(Color::White))
-        .highlight_style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )
-        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
-    frame.render_widget(tabs, chunks[1]);
+    fn start_close(&mut self) {
+        let Some(own) = self
+            .selected_pr()
+            .map(|pr| pr.author.eq_ignore_ascii_case(&self.username))
+        else {
+            return;
+        };
+        if self.refuse_in_read_only_mode("closing") || self.refuse_if_closed() {
+            return;
+        }
+        // Authors can always close their own PRs.
+        if !own && self.refuse_if_read_only("closing others' PRs") {
+            return;
+        }
+        self.input_buffer.clear();
+        self.close_reason_idx = None;
+        if self.confirm.close {
+            self.input_mode = InputMode::ConfirmClose;
+        } else {
+            self.confirm_close();
+        }
+    }
 
-    // Build diff title with current line info
-    let diff_title = {
-        let using_delta =
-            app.use_delta && app.delta_cache.is_some() && app.filtered_diff_cache.is_none();
-        let renderer = if using_delta { "delta" } else { "built-in" };
-        let line_idx = app.scroll_offset as usize;
-        if app.showing_large_diff_tree() {
-            if app.delta_too_large {
-                format!(
-                    " Diff ({}) - file tree (large fallback, Enter to open, t to hide) ",
-                    renderer
-                )
+    fn confirm_close(&mut self) {
+        if let Some(pr) = self.selected_pr().cloned() {
+            let comment = if self.input_buffer.trim().is_empty() {
+                None
             } else {
-                format!(
-                    " Diff ({}) - file tree (Enter to open, t to hide) ",
-                    renderer
-                )
-            }
-        } else if let Some(file_path) = app.selected_file_diff_path.as_deref() {
-            format!(" Diff ({}) - {} [Esc: tree] ", renderer, file_path)
-        } else if !using_delta {
-            if let Some(dl) = app.active_diff_lines().get(line_idx) {
-                if let Some(file) = &dl.file_path {
-                    let line_info = match (dl.line_number, dl.old_line_number) {
-                        (Some(new), _) => format!(":{}", new),
-                        (None, Some(old)) => format!(":{} (old)", old),
-                        _ => String::new(),
-                    };
-                    format!(" Diff ({}) - {}{} ", renderer, file, line_info)
+                Some(self.input_buffer.clone())
+            };
+            let reason = self
+                .close_reason_idx
+                .and_then(|idx| self.confirm.close_reasons.get(idx))
+                .cloned();
+            let removed_at = self.remove_selected_pr();
+            self.exit_detail();
+            self.start_action(pr, PrAction::Close { comment, reason }, removed_at);
+        }
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        self.close_reason_idx = None;
+    }
+
+    fn cancel_close(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        self.close_reason_idx = None;
+    }
+
+    /// Replace the close comment with the next (or previous) reason template.
+    fn cycle_close_reason(&mut self, forward: bool) {
+        let count = self.confirm.close_reasons.len();
+        if count == 0 {
+            return;
+        }
+        let next = match self.close_reason_idx {
+            None if forward => 0,
+            None => count - 1,
+            Some(idx) if forward => (idx + 1) % count,
+            Some(idx) => (idx + count - 1) % count,
+        };
+        self.close_reason_idx = Some(next);
+        self.input_buffer = self.confirm.close_reasons[next].clone();
+    }
+
+    fn start_merge(&mut self) {
+        // Only allow merge in MyPrs mode
+        if self.mode != AppMode::MyPrs {
+            self.set_status("Merge only available in My PRs tab".to_string());
+            return;
+        }
+        if self.refuse_in_read_only_mode("merging")
+            || self.refuse_if_closed()
+            || self.refuse_if_read_only("merging")
+        {
+            return;
+        }
+
+        if let Some(pr) = self.selected_pr().cloned() {
+            // Check if PR can be merged
+            let status = self.gh.check_merge_status(&pr);
+            if status.can_merge {
+                self.merge_requires_typed = self.confirm.merge_requires_typed(&pr.repo_name);
+                self.merge_unmet_checks =
+                    self.unmet_required_checks(&pr, status.protection.as_ref());
+                self.merge_protection = status.protection;
+                // Without the commits GitHub's default message is used, co-authors and all
+                self.merge_message = self
+                    .gh
+                    .get_pr_commits(&pr)
+                    .ok()
+                    .filter(|commits| !commits.is_empty())
+                    .map(|commits| gh::SquashMessage::from_commits(&pr, &commits));
+                self.input_buffer.clear();
+                // Unmet required checks are explained in the dialog even without confirmation
+                if self.confirm.merge
+                    || self.merge_requires_typed
+                    || !self.merge_unmet_checks.is_empty()
+                {
+                    self.input_mode = InputMode::ConfirmMerge;
                 } else {
-                    format!(" Diff ({}) [D to toggle, t: tree] ", renderer)
+                    self.confirm_merge();
                 }
             } else {
-                format!(" Diff ({}) [D to toggle, t: tree] ", renderer)
+                let reason = status
+                    .reason
+                    .unwrap_or_else(|| "Unknown reason".to_string());
+                match gh::closed_pr_state(&reason) {
+                    Some(state) => self.mark_closed(&pr, state),
+                    None => self.set_status(format!("Cannot merge: {}", reason)),
+                }
             }
-        } else {
-            format!(" Diff ({}) [D to toggle, t: tree] ", renderer)
         }
-    };
-    let content_block = Block::default()
-        .borders(Borders::ALL)
-        .title(match app.detail_tab {
-            DetailTab::Description => " Description ".to_string(),
-            DetailTab::Diff => diff_title,
-            DetailTab::Comments => " Comments ".to_string(),
-            DetailTab::Agent => " Agent ".to_string(),
-        });
+    }
 
-    match app.detail_tab {
-        DetailTab::Description => {
-            let body = if app.loading_details && pr.body.is_empty() {
-                "Loading details...".to_string()
-            } else if pr.body.is_empty() {
-                "No description provided.".to_string()
-            } else {
-                pr.body.clone()
-            };
-            let para = Paragraph::new(body)
-                .block(content_block)
-                .wrap(Wrap { trim: false })
-                .scroll((app.scroll_offset, 0));
-            frame.render_widget(para, chunks[2]);
+    fn confirm_merge(&mut self) {
+        if let Some(pr) = self.selected_pr().cloned() {
+            let removed_at = self.remove_selected_pr();
+            self.exit_detail();
+            let message = self.merge_message.take();
+            self.start_action(pr, PrAction::Merge(message), removed_at);
         }
-        DetailTab::Diff => {
-            if app.diff_cache.is_none() && !app.loading_diff {
-                app.load_diff();
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        self.merge_requires_typed = false;
+        self.merge_protection = None;
+        self.merge_message = None;
+        self.merge_unmet_checks.clear();
+    }
+
+    /// Required checks of the base branch that haven't passed on `pr`. Fetched fresh, since CI
+    /// moves on while the PR is open; without `gh pr checks --json` nothing can be told.
+    fn unmet_required_checks(
+        &self,
+        pr: &PullRequest,
+        protection: Option<&gh::BranchProtection>,
+    ) -> Vec<String> {
+        match protection {
+            Some(protection)
+                if !protection.required_checks.is_empty()
+                    && self.gh.supports(Capability::PrChecksJson) =>
+            {
+                let checks = self.gh.get_pr_checks(pr).unwrap_or_default();
+                protection.unmet_checks(&checks)
             }
-            if app.showing_large_diff_tree() {
-                let items: Vec<ListItem> = app
-                    .file_tree_items
-                    .iter()
-                    .map(|item| {
-                        let style = if item.file_path.is_some() {
-                            Style::default().fg(Color::White)
-                        } else {
-                            Style::default()
-                                .fg(Color::DarkGray)
-                                .add_modifier(Modifier::BOLD)
-                        };
-                        ListItem::new(Line::styled(item.label.clone(), style))
-                    })
-                    .collect();
-                let tree_list = List::new(items)
-                    .block(content_block)
-                    .highlight_style(
-                        Style::default()
-                            .bg(Color::DarkGray)
-                            .add_modifier(Modifier::BOLD),
-                    )
-                    .highlight_symbol("▶ ");
-                frame.render_stateful_widget(tree_list, chunks[2], &mut app.file_tree_state);
-            } else {
-                let mut lines: Vec<Line> = if app.loading_diff {
-                    vec![Line::raw("Loading diff...")]
-                } else if let Some(diff_content) = app.filtered_diff_cache.as_deref() {
-                    // Single-file mode from tree view always uses built-in renderer
-                    diff::render_diff(diff_content, &app.syntax_highlighter)
-                } else if app.use_delta {
-                    if let Some(delta_output) = app.delta_cache.as_deref() {
-                        // Use pre-processed delta output
-                        diff::render_from_ansi(delta_output)
-                    } else if let Some(diff_content) = app.active_diff_content() {
-                        // Delta not available, fallback to built-in
-                        diff::render_diff(diff_content, &app.syntax_highlighter)
-                    } else {
-                        vec![Line::raw("Loading diff...")]
-                    }
-                } else if let Some(diff_content) = app.active_diff_content() {
-                    // Built-in rendering (delta disabled)
-                    diff::render_diff(diff_content, &app.syntax_highlighter)
+            _ => Vec::new(),
+        }
+    }
+
+    /// Hand the squash commit message to `$EDITOR`; the merge dialog stays open meanwhile.
+    fn edit_merge_message(&mut self) {
+        match &self.merge_message {
+            Some(message) => self.pending_merge_message = Some(message.to_text()),
+            None => self.set_status(
+                "No commit list to build a message from - GitHub's default will be used"
+                    .to_string(),
+            ),
+        }
+    }
+
+    fn take_pending_merge_message(&mut self) -> Option<String> {
+        self.pending_merge_message.take()
+    }
+
+    /// Use the edited message for the merge. Co-author trailers removed in the editor are
+    /// put back, so squashing never drops anyone's credit.
+    fn finish_merge_message_edit(&mut self, result: Result<String>) {
+        let text = match result {
+            Ok(text) => text,
+            Err(err) => {
+                self.set_status(format!("Failed to open editor: {:#}", err));
+                return;
+            }
+        };
+        let Some(current) = &self.merge_message else {
+            return;
+        };
+        match current.edited(&text) {
+            Some(message) => {
+                self.merge_message = Some(message);
+                self.set_status("Squash commit message updated".to_string());
+            }
+            None => self.set_status("Commit message unchanged: the title was empty".to_string()),
+        }
+    }
+
+    /// Drop the selected PR from the list, keeping the selection in range.
+    fn remove_selected_pr(&mut self) -> Option<usize> {
+        let idx = self.list_state.selected()?;
+        self.prs.remove(idx);
+        if !self.prs.is_empty() {
+            let new_idx = idx.min(self.prs.len() - 1);
+            self.list_state.select(Some(new_idx));
+        } else {
+            self.list_state.select(None);
+        }
+        Some(idx)
+    }
+
+    /// Run `action` in the background; the outcome arrives as `AsyncResult::Action`.
+    fn start_action(&mut self, pr: PullRequest, action: PrAction, removed_at: Option<usize>) {
+        let id = self.next_action_id;
+        self.next_action_id += 1;
+        self.set_status(action.progress_message(&pr));
+
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        let (task_pr, task) = (pr.clone(), action.clone());
+        thread::spawn(move || {
+            let result = task
+                .run(gh.as_ref(), &task_pr)
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Action(id, result));
+        });
+        self.pending_actions.insert(
+            id,
+            PendingAction {
+                action,
+                pr,
+                removed_at,
+            },
+        );
+    }
+
+    fn finish_action(&mut self, id: u64, result: Result<String, String>) {
+        let Some(PendingAction {
+            action,
+            pr,
+            removed_at,
+        }) = self.pending_actions.remove(&id)
+        else {
+            return;
+        };
+        let merge_type = match result {
+            Ok(merge_type) => merge_type,
+            Err(e) => {
+                if matches!(action, PrAction::Approve(_)) {
+                    self.approved_prs.remove(&pr_key(&pr));
+                }
+                if let PrAction::SubmitReview(draft, _) = &action {
+                    self.restore_pending_review(&pr, draft.clone());
+                }
+                if let Some(state) = gh::closed_pr_state(&e) {
+                    self.log_status(format!("{}: {}", action.failure_label(), e));
+                    self.mark_closed(&pr, state);
                 } else {
-                    vec![Line::raw("Loading diff...")]
+                    self.set_status(format!("{}: {}", action.failure_label(), e));
+                }
+                if let Some(idx) = removed_at {
+                    self.restore_pr(idx, pr);
+                }
+                return;
+            }
+        };
+        let refetch = matches!(
+            action,
+            PrAction::Approve(_)
+                | PrAction::Comment(_)
+                | PrAction::LineComment(..)
+                | PrAction::Reply(..)
+                | PrAction::RerequestReview(_)
+                | PrAction::SubmitReview(..)
+        );
+        match action {
+            PrAction::Approve(body) => {
+                let mut entry = AuditEntry::new("approve", &pr);
+                entry.comment = body;
+                record_audit(entry);
+                self.set_status(format!("Approved PR #{}", pr.number));
+            }
+            PrAction::Close { comment, reason } => {
+                let mut entry = AuditEntry::new("close", &pr);
+                entry.reason = reason;
+                entry.comment = comment;
+                record_audit(entry);
+                self.set_status(format!("Closed PR #{}", pr.number));
+            }
+            PrAction::Merge(_) => {
+                let mut entry = AuditEntry::new("merge", &pr);
+                entry.reason = Some(merge_type.clone());
+                record_audit(entry);
+                self.set_status(format!("Merged PR #{} ({})", pr.number, merge_type));
+            }
+            PrAction::Comment(body) => {
+                let mut entry = AuditEntry::new("comment", &pr);
+                entry.comment = Some(body);
+                record_audit(entry);
+                self.set_status("Comment added successfully".to_string());
+                self.comments_cache = None; // Force reload
+                self.review_comments_cache = None;
+                self.feedback_cache = None;
+            }
+            PrAction::LineComment(ctx, body) => {
+                let mut entry = AuditEntry::new("comment", &pr);
+                entry.reason = Some(ctx.location());
+                entry.comment = Some(body);
+                record_audit(entry);
+                let side_label = if ctx.side == CommentSide::Left {
+                    " (old)"
+                } else {
+                    ""
                 };
-
-                // Add margin prefix to all lines, with indicator on focused line
-                let focus_idx = app.scroll_offset as usize;
-                for (idx, line) in lines.iter_mut().enumerate() {
-                    let old_line = std::mem::take(line);
-                    let prefix = if idx == focus_idx {
-                        Span::styled("▶ ", Style::default().fg(Color::Yellow).bold())
-                    } else {
-                        Span::raw("  ")
-                    };
-                    let mut new_spans = vec![prefix];
-                    new_spans.extend(old_line.spans);
-                    *line = if idx == focus_idx {
-                        Line::from(new_spans).style(Style::default().bg(Color::DarkGray))
+                self.set_status(format!("Comment added at {}{}", ctx.location(), side_label));
+            }
+            PrAction::Reply(target, body) => {
+                let mut entry = AuditEntry::new("comment", &pr);
+                entry.reason = Some(target.location.clone());
+                entry.comment = Some(body);
+                record_audit(entry);
+                self.set_status(format!("Replied on {}", target.location));
+                if self.mode == AppMode::MyPrs {
+                    self.prompt_rerequest_after_reply(&pr, target.comment_id);
+                }
+                self.review_comments_cache = None;
+                self.feedback_cache = None;
+            }
+            PrAction::SetThreadResolved {
+                thread_id,
+                location,
+                resolved,
+            } => {
+                if let Some(state) = self
+                    .review_thread_states
+                    .values_mut()
+                    .find(|state| state.id == thread_id)
+                {
+                    state.is_resolved = resolved;
+                }
+                self.set_status(format!(
+                    "{} thread on {}",
+                    if resolved { "Resolved" } else { "Unresolved" },
+                    location
+                ));
+                self.feedback_cache = None;
+            }
+            PrAction::RerequestReview(logins) => {
+                for login in &logins {
+                    let mut entry = AuditEntry::new("request-review", &pr);
+                    entry.reason = Some(login.clone());
+                    record_audit(entry);
+                }
+                let mentions: Vec<String> = logins.iter().map(|l| format!("@{}", l)).collect();
+                let reviewed = logins
+                    .iter()
+                    .all(|login| pr.reviewers.iter().any(|r| &r.login == login));
+                self.set_status(format!(
+                    "{} review from {} on #{}",
+                    if reviewed {
+                        "Re-requested"
                     } else {
-                        Line::from(new_spans)
-                    };
+                        "Requested"
+                    },
+                    mentions.join(", "),
+                    pr.number
+                ));
+            }
+            PrAction::SetMilestone(milestone) => {
+                self.set_status(match milestone {
+                    Some(title) => format!("Set milestone of #{} to {}", pr.number, title),
+                    None => format!("Cleared milestone of #{}", pr.number),
+                });
+                self.reload_triage();
+            }
+            PrAction::SetLabels(labels) => {
+                let key = pr_key(&pr);
+                if let Some(listed) = self.prs.iter_mut().find(|listed| pr_key(listed) == key) {
+                    listed.labels = labels.clone();
                 }
-
-                let para = Paragraph::new(lines)
-                    .block(content_block)
-                    .scroll((app.scroll_offset, 0));
-                frame.render_widget(para, chunks[2]);
+                self.set_status(if labels.is_empty() {
+                    format!("Removed all labels from #{}", pr.number)
+                } else {
+                    let names: Vec<&str> = labels.iter().map(|label| label.name.as_str()).collect();
+                    format!("Labels of #{}: {}", pr.number, names.join(", "))
+                });
             }
-        }
-        DetailTab::Comments => {
-            if app.comments_cache.is_none() && !app.loading_comments {
-                app.load_comments();
+            PrAction::AddToProject(project) => {
+                self.set_status(format!("Added #{} to {}", pr.number, project));
+                self.reload_triage();
             }
-            if app.review_comments_cache.is_none() && !app.loading_review_comments {
-                app.load_review_comments();
+            PrAction::SubmitReview(draft, event) => {
+                for comment in &draft.comments {
+                    let mut entry = AuditEntry::new("comment", &pr);
+                    entry.reason = Some(format!("{}:{}", comment.path, comment.line));
+                    entry.comment = Some(comment.body.clone());
+                    record_audit(entry);
+                }
+                let outcome = match event {
+                    ReviewEvent::Comment => "",
+                    ReviewEvent::Approve => ", approved",
+                    ReviewEvent::RequestChanges => ", changes requested",
+                };
+                if event == ReviewEvent::Approve {
+                    let mut entry = AuditEntry::new("approve", &pr);
+                    entry.comment = Some(draft.body).filter(|body| !body.is_empty());
+                    record_audit(entry);
+                    if self.list_config.approved != ApprovedPrs::Remove {
+                        self.keep_approved_pr(&pr);
+                    }
+                }
+                self.set_status(format!(
+                    "Submitted review on #{}: {} comment(s){}",
+                    pr.number,
+                    draft.comments.len(),
+                    outcome
+                ));
+                self.review_comments_cache = None;
+                self.feedback_cache = None;
             }
-            let loading = app.loading_comments || app.loading_review_comments;
-            let text = if loading {
-                Text::raw("Loading comments...")
-            } else {
-                let mut lines: Vec<Line> = Vec::new();
-
-                // Show review comments (line-level) with diff context first
-                if let Some(review_comments) = app.review_comments_cache.as_ref() {
-                    // Filter out reply comments (in_reply_to_id is set)
-                    let top_level: Vec<_> = review_comments
-                        .iter()
-                        .filter(|c| c.in_reply_to_id.is_none())
-                        .collect();
+        }
+        if refetch {
+            self.refetch_pr(pr, removed_at);
+        }
+    }
 
-                    if !top_level.is_empty() {
-                        lines.push(Line::styled(
-                            "─── Review Comments (on code) ───",
-                            Style::default().fg(Color::Yellow).bold(),
-                        ));
-                        lines.push(Line::raw(""));
+    fn mark_closed(&mut self, pr: &PullRequest, state: &'static str) {
+        self.closed_prs.insert(pr_key(pr), state);
+        self.set_status(format!(
+            "PR #{} was {} elsewhere - press X to remove it",
+            pr.number, state
+        ));
+    }
 
-                        for comment in top_level {
-                            let author = comment
-                                .user
-                                .as_ref()
-                                .and_then(|a| a.login.as_ref())
-                                .map(|s| s.as_str())
-                                .unwrap_or("unknown");
-                            let date = comment.created_at.format("%Y-%m-%d %H:%M");
-                            let line_info =
-                                comment.line.map(|l| format!(":{}", l)).unwrap_or_default();
+    /// Refuse to act on a PR known to be merged or closed elsewhere, pointing at `X` instead.
+    fn refuse_if_closed(&mut self) -> bool {
+        let Some((pr, state)) = self.selected_pr().and_then(|pr| {
+            self.closed_prs
+                .get(&pr_key(pr))
+                .map(|state| (pr.clone(), *state))
+        }) else {
+            return false;
+        };
+        self.mark_closed(&pr, state);
+        true
+    }
 
-                            // File and line header
-                            lines.push(Line::styled(
-                                format!("📁 {}{}", comment.path, line_info),
-                                Style::default().fg(Color::Blue).bold(),
-                            ));
+    /// Drop the selected PR from the list once it is known to be merged or closed elsewhere.
+    fn remove_closed_pr(&mut self) {
+        let Some(pr) = self.selected_pr().cloned() else {
+            return;
+        };
+        if self.closed_prs.remove(&pr_key(&pr)).is_none() {
+            self.set_status(format!("PR #{} is still open", pr.number));
+            return;
+        }
+        self.remove_selected_pr();
+        if self.view == View::Detail {
+            self.exit_detail();
+        }
+        self.set_status(format!("Removed PR #{} from the list", pr.number));
+    }
 
-                            // Diff hunk context (show last few lines for context)
-                            let hunk_lines: Vec<&str> = comment.diff_hunk.lines().collect();
-                            let start = hunk_lines.len().saturating_sub(8);
-                            for hunk_line in &hunk_lines[start..] {
-                                let (style, prefix) = if hunk_line.starts_with('+') {
-                                    (Style::default().fg(Color::Green), "")
-                                } else if hunk_line.starts_with('-') {
-                                    (Style::default().fg(Color::Red), "")
-                                } else if hunk_line.starts_with("@@") {
-                                    (Style::default().fg(Color::Magenta), "")
-                                } else {
-                                    (Style::default().fg(Color::DarkGray), "")
-                                };
-                                lines.push(Line::styled(
-                                    format!("  {}{}", prefix, hunk_line),
-                                    style,
-                                ));
-                            }
+    /// Re-fetch one PR after acting on it, so its list entry shows the new review state.
+    fn refetch_pr(&mut self, pr: PullRequest, removed_at: Option<usize>) {
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        thread::spawn(move || {
+            let result = gh.fetch_pr_details(&pr).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AsyncResult::Refetched(removed_at, result));
+        });
+    }
 
-                            // Comment author and body
-                            lines.push(Line::styled(
-                                format!("  💬 @{} ({})", author, date),
-                                Style::default().fg(Color::Cyan).bold(),
-                            ));
-                            for body_line in comment.body.lines() {
-                                lines.push(Line::raw(format!("     {}", body_line)));
-                            }
+    /// Update a PR in place, or bring back one removed on approval if it still needs me.
+    fn apply_refetched_pr(&mut self, removed_at: Option<usize>, pr: PullRequest) {
+        let existing = self
+            .prs
+            .iter()
+            .position(|p| p.repo_name == pr.repo_name && p.number == pr.number);
+        match (existing, removed_at) {
+            (Some(idx), _) => self.prs[idx] = pr,
+            (None, Some(idx)) if self.still_needs_me(&pr) => self.restore_pr(idx, pr),
+            (None, _) => {}
+        }
+    }
 
-                            // Show replies
-                            let replies: Vec<_> = review_comments
-                                .iter()
-                                .filter(|r| {
-                                    r.in_reply_to_id.is_some()
-                                        && r.path == comment.path
-                                        && r.line == comment.line
-                                })
-                                .collect();
-                            for reply in replies {
-                                let reply_author = reply
-                                    .user
-                                    .as_ref()
-                                    .and_then(|a| a.login.as_ref())
-                                    .map(|s| s.as_str())
-                                    .unwrap_or("unknown");
-                                let reply_date = reply.created_at.format("%Y-%m-%d %H:%M");
-                                lines.push(Line::styled(
-                                    format!("     ↳ @{} ({})", reply_author, reply_date),
-                                    Style::default().fg(Color::Cyan),
-                                ));
-                                for body_line in reply.body.lines() {
-                                    lines.push(Line::raw(format!("       {}", body_line)));
-                                }
-                            }
+    /// Whether a PR I just approved belongs in the current list. My own PRs always do;
+    /// elsewhere it goes once GitHub shows my approval as my latest review.
+    fn still_needs_me(&self, pr: &PullRequest) -> bool {
+        self.mode == AppMode::MyPrs
+            || !pr.reviewers.iter().any(|reviewer| {
+                reviewer.login.eq_ignore_ascii_case(&self.username)
+                    && reviewer.state == gh::ReviewerState::Approved
+            })
+    }
 
-                            lines.push(Line::raw(""));
-                        }
-                    }
-                }
+    /// Put back a PR removed for an action that failed, keeping the selection on the same PR.
+    fn restore_pr(&mut self, idx: usize, pr: PullRequest) {
+        if self
+            .prs
+            .iter()
+            .any(|p| p.repo_name == pr.repo_name && p.number == pr.number)
+        {
+            return;
+        }
+        let idx = idx.min(self.prs.len());
+        self.prs.insert(idx, pr);
+        match self.list_state.selected() {
+            Some(selected) if selected >= idx => self.list_state.select(Some(selected + 1)),
+            Some(_) => {}
+            None => self.list_state.select(Some(idx)),
+        }
+    }
 
-                // Show PR-level comments
-                if let Some(pr_comments) = app.comments_cache.as_ref() {
-                    if !pr_comments.is_empty() {
-                        lines.push(Line::styled(
-                            "─── General Comments ───",
-                            Style::default().fg(Color::Yellow).bold(),
-                        ));
-                        lines.push(Line::raw(""));
+    fn cancel_merge(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        self.merge_requires_typed = false;
+        self.merge_protection = None;
+        self.merge_message = None;
+        self.merge_unmet_checks.clear();
+    }
 
-                        for comment in pr_comments {
-                            let author = comment
-                                .author
-                                .as_ref()
-                                .and_then(|a| a.login.as_ref())
-                                .map(|s| s.as_str())
-                                .unwrap_or("unknown");
-                            let date = comment.created_at.format("%Y-%m-%d %H:%M");
-                            lines.push(Line::styled(
-                                format!("@{} ({})", author, date),
-                                Style::default().fg(Color::Cyan).bold(),
-                            ));
-                            for body_line in comment.body.lines() {
-                                lines.push(Line::raw(format!("  {}", body_line)));
-                            }
-                            lines.push(Line::raw(""));
-                        }
-                    }
-                }
+    fn confirm_typed_merge(&mut self) {
+        let expected = self.selected_pr().map(|pr| pr.number.to_string());
+        if expected.as_deref() == Some(self.input_buffer.trim()) {
+            self.confirm_merge();
+        } else {
+            self.set_status("Type the PR number exactly to confirm merge".to_string());
+        }
+    }
 
-                if lines.is_empty() {
-                    Text::raw("No comments yet.")
+    fn open_in_browser(&mut self) {
+        if let Some(pr) = self.selected_pr() {
+            match gh::open_pr_in_browser(pr) {
+                Ok(()) => self.set_status(format!("Opened PR #{} in browser", pr.number)),
+                Err(e) => self.set_error("Failed to open", &e),
+            }
+        }
+    }
+
+    fn copy_pr_url(&mut self) {
+        if let Some(pr) = self.selected_pr() {
+            let url = pr.url.clone();
+            match copy_to_clipboard(&url) {
+                Ok(_) => self.set_status("Copied URL to clipboard".to_string()),
+                Err(_) => self.set_status(format!("URL: {}", url)),
+            }
+        }
+    }
+
+    /// Save a markdown summary of this session's audit log entries and copy it for a standup note.
+    fn export_session_summary(&mut self) {
+        match session_summary::write_since(self.session_started_at) {
+            Ok(None) => self.set_status("Nothing to summarize yet this session".to_string()),
+            Ok(Some((path, markdown))) => {
+                let copied = if copy_to_clipboard(&markdown).is_ok() {
+                    " and copied it"
                 } else {
-                    Text::from(lines)
+                    ""
+                };
+                self.set_status(format!(
+                    "Saved session summary to {}{}",
+                    path.display(),
+                    copied
+                ));
+            }
+            Err(err) => self.set_error("Failed to write session summary", &err),
+        }
+    }
+
+    /// Reload the first page of the list. Only one fetch runs at a time: asking again for the
+    /// same list joins the running one, while a changed list (tab, drafts, ...) drops its result
+    /// and fetches once more when it lands, however many changes came in between.
+    fn refresh(&mut self) {
+        self.loading_next_page = false;
+        self.has_next_page = false;
+        self.next_page_cursor = None;
+        self.reload_exclude_users_from_config();
+        self.set_status("Refreshing PR list...".to_string());
+        if self.refreshing {
+            if !self.refresh_queued && self.refresh_query.as_ref() != Some(&self.list_query()) {
+                self.list_generation += 1;
+                self.refresh_queued = true;
+            }
+            return;
+        }
+        self.refreshing = true;
+        self.spawn_refresh();
+    }
+
+    fn spawn_refresh(&mut self) {
+        self.refresh_queued = false;
+        self.list_generation += 1;
+        self.daemon_reviews = core::acted_on_prs().into_iter().collect();
+
+        let tx = self.async_tx.clone();
+        let gh = Arc::clone(&self.gh);
+        let query = self.list_query();
+        let generation = self.list_generation;
+        self.refresh_query = Some(query.clone());
+
+        thread::spawn(move || {
+            let page = gh.fetch_page(&query, None);
+            let _ = tx.send(AsyncResult::Refresh(generation, page));
+        });
+    }
+
+    fn list_query(&self) -> ListQuery {
+        ListQuery {
+            mode: self.mode,
+            my_approvals: self.my_approvals,
+            username: self.username.clone(),
+            // Repos in `drafts.always` need drafts fetched; `hide_excluded_drafts` trims the rest.
+            include_drafts: self.drafts_config.any_included(self.include_drafts),
+            exclude_users: self.exclude_users.clone(),
+            repos_root: self.repos_root.clone(),
+        }
+    }
+
+    fn toggle_drafts(&mut self) {
+        self.include_drafts = !self.include_drafts;
+        let status = if self.include_drafts {
+            "Including drafts - refreshing..."
+        } else {
+            "Excluding drafts - refreshing..."
+        };
+        self.set_status(status.to_string());
+        self.refresh();
+    }
+
+    fn toggle_date_style(&mut self) {
+        self.dates.style = match self.dates.style {
+            DateStyle::Relative => DateStyle::Absolute,
+            DateStyle::Absolute => DateStyle::Relative,
+        };
+        self.set_status(match self.dates.style {
+            DateStyle::Relative => "Showing relative ages".to_string(),
+            DateStyle::Absolute => "Showing timestamps".to_string(),
+        });
+    }
+
+    fn toggle_bots(&mut self) {
+        if self.mode != AppMode::Review || self.list_config.bots.is_empty() {
+            return;
+        }
+        self.show_bots = !self.show_bots;
+        let status = if self.show_bots {
+            "Showing PRs by bots - refreshing..."
+        } else {
+            "Hiding PRs by bots - refreshing..."
+        };
+        self.set_status(status.to_string());
+        self.refresh();
+    }
+
+    fn toggle_my_approvals(&mut self) {
+        if self.mode != AppMode::MyPrs {
+            return;
+        }
+        self.my_approvals = !self.my_approvals;
+        let status = if self.my_approvals {
+            "Showing PRs you approved that are not merged - refreshing..."
+        } else {
+            "Showing your PRs - refreshing..."
+        };
+        self.set_status(status.to_string());
+        self.refresh();
+    }
+
+    /// Open the comment box with a nudge to the author of a PR I approved.
+    fn start_nudge(&mut self) {
+        if self.mode != AppMode::MyPrs || !self.my_approvals || self.refuse_if_closed() {
+            return;
+        }
+        let Some(pr) = self.selected_pr() else {
+            return;
+        };
+        let message = format!(
+            "@{} friendly nudge: this was approved, is anything blocking the merge?",
+            pr.author
+        );
+        self.start_comment();
+        self.input_buffer = message;
+    }
+
+    fn start_group_approve(&mut self) {
+        if self.mode != AppMode::Dependencies || self.refuse_in_read_only_mode("approving") {
+            return;
+        }
+        let Some(key) = self.selected_pr().map(deps::group_key) else {
+            return;
+        };
+        self.dependency_group = self
+            .prs
+            .iter()
+            .filter(|pr| deps::group_key(pr) == key)
+            .cloned()
+            .collect();
+        self.input_mode = InputMode::ConfirmGroup;
+    }
+
+    fn confirm_group_approve(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let group = std::mem::take(&mut self.dependency_group);
+        let mut approved = Vec::new();
+        let mut auto_merged = 0;
+        let mut first_error = None;
+        for pr in &group {
+            if let Err(e) = self.gh.approve_pr(pr, None) {
+                first_error.get_or_insert_with(|| format!("#{}: {}", pr.number, e));
+                continue;
+            }
+            record_audit(AuditEntry::new("approve", pr));
+            approved.push((pr.repo_name.clone(), pr.number));
+            match self.gh.enable_auto_merge(pr) {
+                Ok(()) => {
+                    record_audit(AuditEntry::new("auto-merge", pr));
+                    auto_merged += 1;
                 }
-            };
-            let para = Paragraph::new(text)
-                .block(content_block)
-                .wrap(Wrap { trim: false })
-                .scroll((app.scroll_offset, 0));
-            frame.render_widget(para, chunks[2]);
+                Err(e) => {
+                    first_error.get_or_insert_with(|| format!("#{}: {}", pr.number, e));
+                }
+            }
         }
-        DetailTab::Agent => {
-            if app.agent_preview_cache.is_none() && !app.loading_agent_preview {
-                app.load_agent_preview();
+
+        self.prs
+            .retain(|pr| !approved.contains(&(pr.repo_name.clone(), pr.number)));
+        let count = self.list_item_count();
+        if count == 0 {
+            self.list_state.select(None);
+        } else if self.list_state.selected().is_none_or(|idx| idx >= count) {
+            self.list_state.select(Some(count - 1));
+        }
+        self.needs_clear = true;
+
+        let mut status = format!(
+            "Approved {}/{} PRs, auto-merge on {}",
+            approved.len(),
+            group.len(),
+            auto_merged
+        );
+        if let Some(err) = first_error {
+            status.push_str(&format!(" ({})", err));
+        }
+        self.set_status(status);
+    }
+
+    fn handle_group_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.confirm_group_approve(),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                self.dependency_group.clear();
+                self.input_mode = InputMode::Normal;
             }
+            _ => {}
+        }
+    }
 
-            let body = if app.loading_agent_preview {
-                "Loading agent session...".to_string()
-            } else if let Some(preview) = app.agent_preview_cache.as_ref() {
-                match (&preview.pane, &preview.error) {
-                    (Some(pane), None) => {
-                        let mut text = format!(
-                            "tmux target: {} | {}:{} | {} | {}\n\n",
-                            pane.target,
-                            pane.session_name,
-                            pane.window_index,
-                            pane.pane_command,
-                            pane.pane_title
-                        );
-                        if preview.output.trim().is_empty() {
-                            text.push_str("Agent pane is currently blank.");
-                        } else {
-                            text.push_str(&preview.output);
-                        }
-                        text
+    fn toggle_delta(&mut self) {
+        if !diff::delta_available() {
+            self.set_status("Delta not installed".to_string());
+            return;
+        }
+        self.use_delta = !self.use_delta;
+        self.diff_selection_anchor = None;
+        self.back_to_large_diff_tree();
+        let status = if self.use_delta {
+            "Using delta renderer"
+        } else {
+            "Using built-in renderer (line comments enabled)"
+        };
+        self.set_status(status.to_string());
+    }
+
+    pub fn handle_event(&mut self) -> Result<()> {
+        // Poll for async results (non-blocking)
+        let mut needs_redraw = self.poll_async_results();
+        needs_redraw |= self.poll_ai_results();
+        needs_redraw |= self.poll_config_reload();
+        needs_redraw |= self.poll_resize();
+        needs_redraw |= self.poll_scan_progress();
+        // Keep the spinner turning while actions or a refresh are in flight
+        needs_redraw |= !self.pending_actions.is_empty() || self.refreshing;
+
+        if event::poll(std::time::Duration::from_millis(50))? {
+            match event::read()? {
+                Event::Key(key) => {
+                    if key.kind != KeyEventKind::Press {
+                        return Ok(());
                     }
-                    (Some(pane), Some(err)) => format!(
-                        "Found tmux target {} for {}, but capture failed:\n\n{}",
-                        pane.target, preview.expected_slug, err
-                    ),
-                    (None, Some(err)) => format!(
-                        "Could not inspect tmux for {}:\n\n{}",
-                        preview.expected_slug, err
-                    ),
-                    (None, None) => format!(
-                        "No agent session found for {}.\n\nPress r to launch a review session, then R to refresh this tab.",
-                        preview.expected_slug
-                    ),
+                    self.handle_key(key);
+                    needs_redraw = true;
                 }
-            } else {
-                "No agent preview loaded.".to_string()
-            };
+                Event::Resize(width, _) => {
+                    self.needs_clear = true;
+                    needs_redraw = true;
+                    self.pending_resize = Some((width, std::time::Instant::now()));
+                }
+                _ => {}
+            }
+        }
+
+        if needs_redraw {
+            self.needs_redraw = true;
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch one key press to the handler for the current input mode.
+    fn handle_key(&mut self, key: KeyEvent) {
+        match self.input_mode {
+            InputMode::Normal => self.handle_normal_key(key.code, key.modifiers),
+            InputMode::Comment => self.handle_comment_key(key.code),
+            InputMode::LineComment => self.handle_line_comment_key(key.code),
+            InputMode::ConfirmApprove => self.handle_confirm_key(key.code),
+            InputMode::ConfirmClose => self.handle_close_key(key.code),
+            InputMode::ConfirmMerge => self.handle_merge_key(key.code),
+            InputMode::Search => self.handle_search_key(key.code),
+            InputMode::ListSearch => self.handle_list_search_key(key.code),
+            InputMode::ListFilter => self.handle_list_filter_key(key.code),
+            InputMode::GotoLine => self.handle_goto_key(key.code),
+            InputMode::Checklist => self.handle_checklist_key(key.code),
+            InputMode::Snooze => self.handle_snooze_key(key.code),
+            InputMode::Workload => self.handle_workload_key(key.code),
+            InputMode::ConfirmGroup => self.handle_group_key(key.code),
+            InputMode::GlobalSearch => self.handle_global_search_key(key.code),
+            InputMode::GlobalResults => self.handle_global_results_key(key.code),
+            InputMode::StatusLog => self.handle_status_log_key(key.code),
+            InputMode::FileView => self.handle_file_view_key(key.code, key.modifiers),
+            InputMode::Milestone => self.handle_milestone_key(key.code),
+            InputMode::Labels => self.handle_labels_key(key.code),
+            InputMode::Reviewers => self.handle_reviewers_key(key.code),
+            InputMode::AiChoice => self.handle_ai_choice_key(key.code),
+            InputMode::ViewPicker => self.handle_view_picker_key(key.code),
+            InputMode::ReviewSummary => self.handle_review_summary_key(key.code),
+        }
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let scope = match self.view {
+            View::List => KeyScope::List,
+            View::Detail => KeyScope::Detail,
+        };
+        // Rebound keys stand in for the default key of their action
+        let Some(KeyPress {
+            chord: KeyChord { code, modifiers },
+            action,
+        }) = self.keys.resolve(scope, KeyChord::new(code, modifiers))
+        else {
+            return;
+        };
+        match self.view {
+            View::List => match code {
+                KeyCode::Char('q') => self.should_quit = true,
+                KeyCode::Tab | KeyCode::Right => self.next_list_tab(),
+                KeyCode::BackTab | KeyCode::Left => self.prev_list_tab(),
+                KeyCode::Char('1') => self.select_list_tab(AppMode::Review),
+                KeyCode::Char('2') => self.select_list_tab(AppMode::MyPrs),
+                KeyCode::Char('3') => self.select_list_tab(AppMode::Watching),
+                KeyCode::Char('4') => self.select_list_tab(AppMode::Dependencies),
+                // Page navigation with Ctrl+d/u (must be before non-Ctrl)
+                KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => self.next_page(),
+                KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.previous_page()
+                }
+                // Regular navigation
+                KeyCode::Char('j') | KeyCode::Down => self.next(),
+                KeyCode::Char('k') | KeyCode::Up => self.previous(),
+                KeyCode::PageDown => self.next_page(),
+                KeyCode::PageUp => self.previous_page(),
+                KeyCode::Char('g') => self.go_to_first(),
+                KeyCode::Char('G') => self.go_to_last(),
+                KeyCode::Home => self.go_to_first(),
+                KeyCode::End => self.go_to_last(),
+                KeyCode::Enter
+                    if self
+                        .list_state
+                        .selected()
+                        .is_some_and(|idx| self.is_folded_row(idx)) =>
+                {
+                    self.toggle_repo_fold()
+                }
+                KeyCode::Enter => self.enter_detail(),
+                KeyCode::Char('R') => self.refresh(),
+                KeyCode::Char('r') => self.toggle_group_by_repo(),
+                KeyCode::Char(' ') => self.toggle_repo_fold(),
+                KeyCode::Char('p') => self.toggle_preview(),
+                KeyCode::Char('d') => self.toggle_drafts(),
+                KeyCode::Char('B') => self.toggle_bots(),
+                KeyCode::Char('S') => self.export_session_summary(),
+                KeyCode::Char('T') => self.toggle_date_style(),
+                KeyCode::Char('z') => self.start_snooze(),
+                KeyCode::Char('Z') => self.toggle_show_snoozed(),
+                KeyCode::Char('w') => self.start_workload(),
+                KeyCode::Char('v') => self.toggle_my_approvals(),
+                KeyCode::Char('V') => self.start_view_picker(),
+                KeyCode::Char('b') => self.start_nudge(),
+                KeyCode::Char('a') => self.start_group_approve(),
+                KeyCode::Char('s') => self.start_global_search(),
+                // Search in PR list
+                KeyCode::Char('/') => self.start_list_search(),
+                KeyCode::Char('f') => self.start_list_filter(),
+                KeyCode::Char('O') => self.cycle_list_sort(),
+                KeyCode::Char('n') if !self.search_query.is_empty() => {
+                    self.next_list_search_match()
+                }
+                KeyCode::Char('N') if !self.search_query.is_empty() => {
+                    self.prev_list_search_match()
+                }
+                KeyCode::Char('o') => self.open_in_browser(),
+                KeyCode::Char('y') => self.copy_pr_url(),
+                KeyCode::Char('!') => self.open_status_log(),
+                KeyCode::Char('X') => self.remove_closed_pr(),
+                _ => {}
+            },
+            View::Detail => match code {
+                KeyCode::Char('q') => self.exit_detail(),
+                KeyCode::Esc => {
+                    if self.diff_selection_anchor.take().is_some() {
+                        self.set_status("Selection cleared".to_string());
+                    } else if self.showing_single_file_diff() {
+                        self.back_to_large_diff_tree();
+                    } else if self.detail_tab == DetailTab::Commits && self.commit_diff.is_some() {
+                        self.close_commit_diff();
+                    } else if self.detail_tab == DetailTab::Files
+                        && self.changed_file_diff.is_some()
+                    {
+                        self.close_changed_file_diff();
+                    } else {
+                        self.exit_detail();
+                    }
+                }
+                KeyCode::Tab => self.next_tab(),
+                KeyCode::BackTab => self.prev_tab(),
+                // Page navigation with Ctrl+d/u
+                KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => self.page_down(),
+                KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => self.page_up(),
+                // Regular navigation
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if self.showing_large_diff_tree() {
+                        self.move_file_tree_selection(true);
+                    } else if self.detail_tab == DetailTab::Ai {
+                        self.move_ai_finding_selection(true);
+                    } else if self.detail_tab == DetailTab::Checks {
+                        self.move_check_selection(true);
+                    } else if self.detail_tab == DetailTab::Commits && self.commit_diff.is_none() {
+                        self.move_commit_selection(true);
+                    } else if self.detail_tab == DetailTab::Files
+                        && self.changed_file_diff.is_none()
+                    {
+                        self.move_changed_file_selection(true);
+                    } else {
+                        self.scroll_down();
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if self.showing_large_diff_tree() {
+                        self.move_file_tree_selection(false);
+                    } else if self.detail_tab == DetailTab::Ai {
+                        self.move_ai_finding_selection(false);
+                    } else if self.detail_tab == DetailTab::Checks {
+                        self.move_check_selection(false);
+                    } else if self.detail_tab == DetailTab::Commits && self.commit_diff.is_none() {
+                        self.move_commit_selection(false);
+                    } else if self.detail_tab == DetailTab::Files
+                        && self.changed_file_diff.is_none()
+                    {
+                        self.move_changed_file_selection(false);
+                    } else {
+                        self.scroll_up();
+                    }
+                }
+                KeyCode::PageDown => self.page_down(),
+                KeyCode::PageUp => self.page_up(),
+                KeyCode::Enter if self.detail_tab == DetailTab::Agent => {
+                    self.attach_agent_session()
+                }
+                KeyCode::Enter if self.showing_large_diff_tree() => self.open_selected_file_diff(),
+                KeyCode::Enter if self.uses_diff_cursor() => match self.file_header_at_cursor() {
+                    Some(path) => self.toggle_file_collapsed(path),
+                    None => self.toggle_inline_comments(),
+                },
+                KeyCode::Enter if self.detail_tab == DetailTab::Ai => {
+                    self.jump_to_selected_finding()
+                }
+                KeyCode::Char('R') if self.detail_tab == DetailTab::Ai => self.reload_ai_results(),
+                KeyCode::Enter if self.detail_tab == DetailTab::Checks => {
+                    self.open_selected_check()
+                }
+                KeyCode::Enter if self.detail_tab == DetailTab::Commits => {
+                    self.open_selected_commit()
+                }
+                KeyCode::Enter if self.detail_tab == DetailTab::Files => {
+                    self.open_selected_changed_file()
+                }
+                KeyCode::Char('R') if self.detail_tab == DetailTab::Checks => self.reload_checks(),
+                KeyCode::Char('A') if self.detail_tab == DetailTab::Agent => {
+                    self.attach_agent_session()
+                }
+                KeyCode::Char('R') if self.detail_tab == DetailTab::Agent => {
+                    self.agent_preview_cache = None;
+                    self.refresh_agent_preview();
+                }
+                KeyCode::Char('h') if self.uses_diff_cursor() => {
+                    self.select_diff_column(CommentSide::Left)
+                }
+                KeyCode::Char('l') if self.uses_diff_cursor() => {
+                    self.select_diff_column(CommentSide::Right)
+                }
+                KeyCode::Char('v') if self.uses_diff_cursor() => self.toggle_diff_selection(),
+                KeyCode::Char(']') if self.uses_diff_cursor() => self.jump_to_diff_file(true),
+                KeyCode::Char('[') if self.uses_diff_cursor() => self.jump_to_diff_file(false),
+                KeyCode::Char('}') if self.uses_diff_cursor() => self.jump_to_diff_hunk(true),
+                KeyCode::Char('{') if self.uses_diff_cursor() => self.jump_to_diff_hunk(false),
+                KeyCode::Char(']') if self.detail_tab == DetailTab::Comments => {
+                    self.select_review_thread(true)
+                }
+                KeyCode::Char('[') if self.detail_tab == DetailTab::Comments => {
+                    self.select_review_thread(false)
+                }
+                KeyCode::Char('c') if self.detail_tab == DetailTab::Comments => self.start_reply(),
+                KeyCode::Char('u') if self.detail_tab == DetailTab::Comments => {
+                    self.toggle_thread_resolved()
+                }
+                KeyCode::Char('R')
+                    if self.detail_tab == DetailTab::Comments && self.mode == AppMode::MyPrs =>
+                {
+                    self.rerequest_review()
+                }
+                KeyCode::Char('c') => self.start_line_comment(),
+                KeyCode::Char('s') => self.start_review(),
+                KeyCode::Char('a') => self.start_approve(),
+                KeyCode::Char('x') => self.start_close(),
+                KeyCode::Char('m') => self.start_merge(),
+                KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.start_ai_choice()
+                }
+                KeyCode::Char('r') => self.launch_ai_review(),
+                KeyCode::Char('C') => self.open_checklist(),
+                KeyCode::Char('w') => self.start_workload(),
+                KeyCode::Char('b') => self.start_nudge(),
+                KeyCode::Char('M') => self.start_milestone(),
+                KeyCode::Char('L') => self.start_labels(),
+                KeyCode::Char('W') => self.start_reviewers(),
+                KeyCode::Char('P') => self.add_to_project(),
+                // Search (only in Diff tab)
+                KeyCode::Char('/') if self.detail_tab == DetailTab::Diff => {
+                    if self.showing_large_diff_tree() {
+                        self.start_tree_name_search();
+                    } else {
+                        self.start_search();
+                    }
+                }
+                KeyCode::Char('?')
+                    if self.detail_tab == DetailTab::Diff && self.showing_large_diff_tree() =>
+                {
+                    self.start_tree_content_search();
+                }
+                // `n` steps search matches while searching and PRs otherwise, unless either one
+                // was rebound
+                KeyCode::Char('n')
+                    if !self.search_query.is_empty() && action != Some("next_pr") =>
+                {
+                    self.next_search_match()
+                }
+                KeyCode::Char('N') if !self.search_query.is_empty() => self.prev_search_match(),
+                // Goto line / diff commands (only in Diff tab)
+                KeyCode::Char(':') if self.detail_tab == DetailTab::Diff => self.start_goto_line(),
+                KeyCode::Char('F') if self.detail_tab == DetailTab::Diff => {
+                    self.toggle_path_filter()
+                }
+                KeyCode::Char('H') if self.detail_tab == DetailTab::Diff => {
+                    self.toggle_generated_files()
+                }
+                KeyCode::Char('e') if self.detail_tab == DetailTab::Diff => {
+                    self.expand_diff_context()
+                }
+                KeyCode::Char('f') if self.detail_tab == DetailTab::Diff => self.open_file_view(),
+                KeyCode::Char('B') if self.detail_tab == DetailTab::Diff => {
+                    self.blame_focused_line()
+                }
+                KeyCode::Char('E') if self.detail_tab == DetailTab::Diff => self.open_in_editor(),
+                // Next/prev PR (when not searching)
+                KeyCode::Char('n')
+                    if action == Some("next_pr")
+                        || (action.is_none() && self.search_query.is_empty()) =>
+                {
+                    self.exit_detail();
+                    self.next();
+                    self.enter_detail();
+                }
+                KeyCode::Char('p') => {
+                    self.exit_detail();
+                    self.previous();
+                    self.enter_detail();
+                }
+                // Toggle delta rendering (only in Diff tab)
+                KeyCode::Char('t') if self.detail_tab == DetailTab::Diff => self.toggle_diff_tree(),
+                KeyCode::Char('D') if self.detail_tab == DetailTab::Diff => self.toggle_delta(),
+                KeyCode::Char('o') => self.open_in_browser(),
+                KeyCode::Char('y') => self.copy_pr_url(),
+                KeyCode::Char('!') => self.open_status_log(),
+                KeyCode::Char('X') => self.remove_closed_pr(),
+                _ => {}
+            },
+        }
+    }
+
+    fn open_status_log(&mut self) {
+        self.status_log_scroll = 0;
+        self.input_mode = InputMode::StatusLog;
+    }
+
+    fn handle_status_log_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | '!') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.status_log_scroll = self.status_log_scroll.saturating_add(1)
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.status_log_scroll = self.status_log_scroll.saturating_sub(1)
+            }
+            KeyCode::PageDown => self.status_log_scroll = self.status_log_scroll.saturating_add(10),
+            KeyCode::PageUp => self.status_log_scroll = self.status_log_scroll.saturating_sub(10),
+            KeyCode::Char('g') | KeyCode::Home => self.status_log_scroll = 0,
+            _ => {}
+        }
+    }
+
+    fn handle_confirm_key(&mut self, code: KeyCode) {
+        if self.editing_approve_message {
+            match code {
+                KeyCode::Enter => self.confirm_approve(),
+                KeyCode::Esc => self.editing_approve_message = false,
+                KeyCode::Backspace => {
+                    self.input_buffer.pop();
+                }
+                KeyCode::Char(c) => self.input_buffer.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.confirm_approve(),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => self.cancel_approve(),
+            KeyCode::Char('m' | 'M') | KeyCode::Tab => self.editing_approve_message = true,
+            _ => {}
+        }
+    }
+
+    fn handle_close_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.confirm_close(),
+            KeyCode::Esc => self.cancel_close(),
+            KeyCode::Tab => self.cycle_close_reason(true),
+            KeyCode::BackTab => self.cycle_close_reason(false),
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_merge_key(&mut self, code: KeyCode) {
+        // GitHub would reject the merge, so the dialog only explains why
+        if !self.merge_unmet_checks.is_empty() {
+            if matches!(
+                code,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('n' | 'N')
+            ) {
+                self.cancel_merge();
+            }
+            return;
+        }
+        if self.merge_requires_typed {
+            match code {
+                KeyCode::Enter => self.confirm_typed_merge(),
+                KeyCode::Tab => self.edit_merge_message(),
+                KeyCode::Esc => self.cancel_merge(),
+                KeyCode::Backspace => {
+                    self.input_buffer.pop();
+                }
+                KeyCode::Char(c) => self.input_buffer.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.confirm_merge(),
+            KeyCode::Tab => self.edit_merge_message(),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => self.cancel_merge(),
+            _ => {}
+        }
+    }
+
+    fn handle_comment_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.submit_comment(),
+            KeyCode::Tab => self.compose_in_editor(),
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                self.reply_target = None;
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_line_comment_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.submit_line_comment(),
+            KeyCode::Tab => self.compose_in_editor(),
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                self.line_comment_ctx = None;
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.execute_search(),
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+            }
+            KeyCode::Up => {
+                if let Some(query) = self.diff_search_history.older() {
+                    self.input_buffer = query;
+                }
+            }
+            KeyCode::Down => {
+                if let Some(query) = self.diff_search_history.newer() {
+                    self.input_buffer = query;
+                }
+            }
+            KeyCode::Tab => self.toggle_search_case_sensitivity(),
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_list_search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.execute_list_search(),
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+            }
+            KeyCode::Up => {
+                if let Some(query) = self.list_search_history.older() {
+                    self.input_buffer = query;
+                }
+            }
+            KeyCode::Down => {
+                if let Some(query) = self.list_search_history.newer() {
+                    self.input_buffer = query;
+                }
+            }
+            KeyCode::Tab => self.toggle_search_case_sensitivity(),
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_goto_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.execute_goto_line(),
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn open_checklist(&mut self) {
+        match self.checklist_cache.as_deref() {
+            None => self.set_status("Loading review checklist...".to_string()),
+            Some([]) => self.set_status(format!(
+                "No review checklist at {}",
+                self.checklist_config.path
+            )),
+            Some(_) => {
+                if self.checklist_state.selected().is_none() {
+                    self.checklist_state.select(Some(0));
+                }
+                self.input_mode = InputMode::Checklist;
+            }
+        }
+    }
+
+    fn handle_checklist_key(&mut self, code: KeyCode) {
+        let len = self.checklist_cache.as_ref().map_or(0, Vec::len);
+        let selected = self.checklist_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | 'C') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.checklist_state.select(Some((selected + 1) % len));
+            }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.checklist_state
+                    .select(Some((selected + len - 1) % len));
+            }
+            KeyCode::Char(' ' | 'x') | KeyCode::Enter => {
+                if let Some(item) = self
+                    .checklist_cache
+                    .as_mut()
+                    .and_then(|items| items.get_mut(selected))
+                {
+                    item.checked = !item.checked;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn start_search(&mut self) {
+        self.search_scope = SearchScope::Diff;
+        self.input_mode = InputMode::Search;
+        self.input_buffer.clear();
+        self.diff_search_history.reset_cursor();
+    }
+
+    fn start_tree_name_search(&mut self) {
+        self.search_scope = SearchScope::TreeFileNames;
+        self.input_mode = InputMode::Search;
+        self.input_buffer.clear();
+        self.diff_search_history.reset_cursor();
+    }
+
+    fn start_tree_content_search(&mut self) {
+        self.search_scope = SearchScope::TreeContents;
+        self.input_mode = InputMode::Search;
+        self.input_buffer.clear();
+        self.diff_search_history.reset_cursor();
+    }
+
+    fn toggle_search_case_sensitivity(&mut self) {
+        self.search_case_sensitive = !self.search_case_sensitive;
+    }
+
+    /// Persistent indicator for the active search query while cycling with n/N.
+    fn search_indicator(&self) -> Option<String> {
+        if self.search_query.is_empty() {
+            return None;
+        }
+        let case = if self.search_case_sensitive {
+            "Aa"
+        } else {
+            "aa"
+        };
+        if self.search_matches.is_empty() {
+            Some(format!(" /{} [{}] no matches ", self.search_query, case))
+        } else {
+            Some(format!(
+                " /{} [{}] {}/{} n/N ",
+                self.search_query,
+                case,
+                self.search_match_idx + 1,
+                self.search_matches.len()
+            ))
+        }
+    }
+
+    fn apply_current_search_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let idx = self.search_matches[self.search_match_idx];
+        match self.search_scope {
+            SearchScope::Diff => {
+                self.focus_diff_line(idx as u16);
+            }
+            SearchScope::TreeFileNames | SearchScope::TreeContents => {
+                self.file_tree_state.select(Some(idx));
+            }
+        }
+    }
+
+    fn execute_search(&mut self) {
+        if self.input_buffer.is_empty() {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+
+        self.search_query = self.input_buffer.clone();
+        self.diff_search_history.record(&self.search_query);
+        self.search_matches.clear();
+        self.search_match_idx = 0;
+        let query = self.search_query.as_str();
+        let case_sensitive = self.search_case_sensitive;
+
+        match self.search_scope {
+            SearchScope::Diff => {
+                // Search in displayed content (delta output if available, otherwise raw diff)
+                let search_content = self
+                    .filtered_diff_cache
+                    .as_ref()
+                    .or(self.delta_cache.as_ref())
+                    .or(self.diff_cache.as_ref());
+                if let Some(content) = search_content {
+                    for (idx, line) in content.lines().enumerate() {
+                        // Strip ANSI codes for searching in delta output
+                        let clean_line = strip_ansi_codes(line);
+                        if text_matches_query(&clean_line, query, case_sensitive) {
+                            self.search_matches.push(idx);
+                        }
+                    }
+                }
+            }
+            SearchScope::TreeFileNames => {
+                for (idx, item) in self.file_tree_items.iter().enumerate() {
+                    if let Some(path) = item.file_path.as_ref() {
+                        if text_matches_query(path, query, case_sensitive) {
+                            self.search_matches.push(idx);
+                        }
+                    }
+                }
+            }
+            SearchScope::TreeContents => {
+                let mut matched_paths = BTreeSet::new();
+                for section in &self.file_diff_sections {
+                    if text_matches_query(&section.diff, query, case_sensitive) {
+                        matched_paths.insert(section.path.clone());
+                    }
+                }
+                for (idx, item) in self.file_tree_items.iter().enumerate() {
+                    if let Some(path) = item.file_path.as_ref() {
+                        if matched_paths.contains(path) {
+                            self.search_matches.push(idx);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+
+        if self.search_matches.is_empty() {
+            let scope = match self.search_scope {
+                SearchScope::Diff => "diff",
+                SearchScope::TreeFileNames => "file names",
+                SearchScope::TreeContents => "file contents",
+            };
+            self.set_status(format!("No {} matches for '{}'", scope, self.search_query));
+            return;
+        }
+
+        // Jump to first match
+        self.apply_current_search_match();
+        self.set_status(format_search_status(
+            0,
+            self.search_matches.len(),
+            &self.search_query,
+        ));
+    }
+
+    fn next_search_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        self.search_match_idx =
+            advance_search_idx(self.search_match_idx, self.search_matches.len());
+        self.apply_current_search_match();
+        self.set_status(format_search_status(
+            self.search_match_idx,
+            self.search_matches.len(),
+            &self.search_query,
+        ));
+    }
+
+    fn prev_search_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        self.search_match_idx =
+            retreat_search_idx(self.search_match_idx, self.search_matches.len());
+        self.apply_current_search_match();
+        self.set_status(format_search_status(
+            self.search_match_idx,
+            self.search_matches.len(),
+            &self.search_query,
+        ));
+    }
+
+    // List search methods
+    fn start_global_search(&mut self) {
+        self.input_mode = InputMode::GlobalSearch;
+        self.input_buffer = self.global_search_term.clone();
+    }
+
+    fn handle_global_search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.execute_global_search(),
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+            }
+            KeyCode::Tab => self.global_search_diffs = !self.global_search_diffs,
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Search titles and bodies now, then each PR's diff in the background when enabled.
+    fn execute_global_search(&mut self) {
+        let term = self.input_buffer.trim().to_string();
+        self.input_buffer.clear();
+        if term.is_empty() {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+        self.global_search_generation += 1;
+        self.global_search_term = term.clone();
+        self.global_matches = self
+            .prs
+            .iter()
+            .flat_map(|pr| global_search::search_text(pr, &term))
+            .collect();
+        self.global_match_state = ListState::default();
+        if !self.global_matches.is_empty() {
+            self.global_match_state.select(Some(0));
+        }
+        self.global_search_pending = 0;
+        if self.global_search_diffs && !self.prs.is_empty() {
+            self.global_search_pending = self.prs.len();
+            let prs = self.prs.clone();
+            let generation = self.global_search_generation;
+            let tx = self.async_tx.clone();
+            let gh = Arc::clone(&self.gh);
+            thread::spawn(move || {
+                for pr in prs {
+                    let matches = gh
+                        .get_pr_diff(&pr)
+                        .map(|diff| global_search::search_diff(&pr, &diff, &term))
+                        .unwrap_or_default();
+                    if tx
+                        .send(AsyncResult::GlobalSearch(generation, matches))
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+        self.input_mode = InputMode::GlobalResults;
+    }
+
+    fn handle_global_results_key(&mut self, code: KeyCode) {
+        let len = self.global_matches.len();
+        let selected = self.global_match_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.input_mode = InputMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.global_match_state.select(Some((selected + 1) % len));
+            }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.global_match_state
+                    .select(Some((selected + len - 1) % len));
+            }
+            KeyCode::Char('s') => self.start_global_search(),
+            KeyCode::Enter => self.open_global_match(),
+            _ => {}
+        }
+    }
+
+    fn open_global_match(&mut self) {
+        let Some(found) = self
+            .global_match_state
+            .selected()
+            .and_then(|idx| self.global_matches.get(idx))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(idx) = self
+            .prs
+            .iter()
+            .position(|pr| pr.repo_name == found.repo && pr.number == found.number)
+        else {
+            self.set_status(format!("#{} is no longer in the list", found.number));
+            return;
+        };
+        self.input_mode = InputMode::Normal;
+        self.list_state.select(Some(idx));
+        self.enter_detail();
+        if let MatchLocation::Diff { path, line } = found.location {
+            self.open_diff_at(path, line);
+        }
+    }
+
+    fn start_list_search(&mut self) {
+        self.input_mode = InputMode::ListSearch;
+        self.input_buffer.clear();
+        self.list_search_history.reset_cursor();
+    }
+
+    fn execute_list_search(&mut self) {
+        let query = self.input_buffer.trim();
+        if query.is_empty() {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+
+        self.search_query = query.to_string();
+        self.list_search_history.record(&self.search_query);
+        self.search_matches.clear();
+        self.search_match_idx = 0;
+
+        // Search in PR title/repo/author/number
+        for (idx, pr) in self.prs.iter().enumerate() {
+            if pr_matches_list_query(pr, &self.search_query, self.search_case_sensitive) {
+                self.search_matches.push(idx);
+            }
+        }
+
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+
+        if self.search_matches.is_empty() {
+            self.set_status(format!("No PRs matching '{}'", self.search_query));
+        } else {
+            // Jump to first match
+            self.list_state.select(Some(self.search_matches[0]));
+            self.set_status(format_search_status(
+                0,
+                self.search_matches.len(),
+                &self.search_query,
+            ));
+        }
+    }
+
+    fn next_list_search_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        self.search_match_idx =
+            advance_search_idx(self.search_match_idx, self.search_matches.len());
+        self.list_state
+            .select(Some(self.search_matches[self.search_match_idx]));
+        self.set_status(format_search_status(
+            self.search_match_idx,
+            self.search_matches.len(),
+            &self.search_query,
+        ));
+    }
+
+    fn prev_list_search_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        self.search_match_idx =
+            retreat_search_idx(self.search_match_idx, self.search_matches.len());
+        self.list_state
+            .select(Some(self.search_matches[self.search_match_idx]));
+        self.set_status(format_search_status(
+            self.search_match_idx,
+            self.search_matches.len(),
+            &self.search_query,
+        ));
+    }
+
+    fn start_goto_line(&mut self) {
+        self.input_mode = InputMode::GotoLine;
+        self.input_buffer.clear();
+    }
+
+    fn execute_goto_line(&mut self) {
+        let command = self.input_buffer.trim().to_string();
+        if let Some(spec) = command
+            .strip_prefix("only")
+            .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+        {
+            self.input_mode = InputMode::Normal;
+            self.input_buffer.clear();
+            self.set_path_filter(spec);
+            return;
+        }
+        if self.showing_large_diff_tree() {
+            self.set_status("Select a file first (Enter) to jump to lines".to_string());
+        } else if let Ok(line_num) = command.parse::<u16>() {
+            // Find the diff line that corresponds to this line number
+            if let Some(idx) = self
+                .active_diff_lines()
+                .iter()
+                .position(|dl| dl.line_number.map(|n| n as u16) == Some(line_num))
+            {
+                self.focus_diff_line(idx as u16);
+                self.set_status(format!("Jumped to line {}", line_num));
+            } else {
+                // Just scroll to that offset as fallback
+                self.focus_diff_line(line_num.saturating_sub(1));
+                self.set_status(format!("Scrolled to position {}", line_num));
+            }
+        } else if !command.is_empty() {
+            self.set_status(format!("Unknown command: {}", command));
+        }
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+    }
+
+    fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.search_matches.clear();
+        self.search_match_idx = 0;
+        self.search_scope = SearchScope::Diff;
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    // Clear screen only when view/tab/selection changed
+    match app.view {
+        View::List => draw_list(frame, app),
+        View::Detail => draw_detail(frame, app),
+    }
+
+    // Draw status message in top right corner if present
+    if let Some(msg) = &app.status_message {
+        let msg = match app.status_time {
+            Some(time) if !app.pending_actions.is_empty() || app.refreshing => {
+                let frame_idx = (time.elapsed().as_millis() / 100) as usize % SPINNER.len();
+                format!("{} {}", SPINNER[frame_idx], msg)
+            }
+            _ => msg.clone(),
+        };
+        let area = frame.area();
+        let msg_width = (msg.len() as u16 + 4).min(area.width / 2);
+        let popup_area = Rect {
+            x: area.width.saturating_sub(msg_width + 1),
+            y: 0,
+            width: msg_width,
+            height: 1,
+        };
+        let popup =
+            Paragraph::new(msg.as_str()).style(Style::default().fg(Color::Black).bg(Color::Yellow));
+        frame.render_widget(popup, popup_area);
+    }
+
+    // Draw comment input if active
+    if app.input_mode == InputMode::Comment {
+        draw_comment_input(frame, app);
+    }
+
+    // Draw line comment input if active
+    if app.input_mode == InputMode::LineComment {
+        draw_line_comment_input(frame, app);
+    }
+
+    // Draw confirmation dialog if active
+    if app.input_mode == InputMode::ConfirmApprove {
+        draw_confirm_dialog(frame, app);
+    }
+
+    // Draw close dialog if active
+    if app.input_mode == InputMode::ConfirmClose {
+        draw_close_dialog(frame, app);
+    }
+
+    // Draw merge dialog if active
+    if app.input_mode == InputMode::ConfirmMerge {
+        draw_merge_dialog(frame, app);
+    }
+
+    // Draw the review summary if active
+    if app.input_mode == InputMode::ReviewSummary {
+        draw_review_summary_dialog(frame, app);
+    }
+
+    // Draw search input if active
+    if app.input_mode == InputMode::Search || app.input_mode == InputMode::ListSearch {
+        draw_search_input(frame, app);
+    }
+
+    // Draw goto line input if active
+    if app.input_mode == InputMode::GotoLine {
+        draw_goto_input(frame, app);
+    }
+
+    // Draw the list filter input if active
+    if app.input_mode == InputMode::ListFilter {
+        draw_list_filter_input(frame, app);
+    }
+
+    // Draw snooze picker if active
+    if app.input_mode == InputMode::Snooze {
+        draw_snooze_dialog(frame, app);
+    }
+
+    // Draw global search input or results if active
+    if app.input_mode == InputMode::GlobalSearch {
+        draw_global_search_input(frame, app);
+    }
+    if app.input_mode == InputMode::GlobalResults {
+        draw_global_results(frame, app);
+    }
+
+    // Draw dependency group confirmation if active
+    if app.input_mode == InputMode::ConfirmGroup {
+        draw_group_dialog(frame, app);
+    }
+
+    // Draw teammate review queues if active
+    if app.input_mode == InputMode::Workload {
+        draw_workload_overlay(frame, app);
+    }
+
+    // Draw review checklist overlay if active
+    if app.input_mode == InputMode::Checklist {
+        draw_checklist_overlay(frame, app);
+    }
+
+    // Draw the session's status history if open
+    if app.input_mode == InputMode::StatusLog {
+        draw_status_log(frame, app);
+    }
+
+    // Draw the full-file viewer if open
+    if app.input_mode == InputMode::FileView {
+        draw_file_view(frame, app);
+    }
+
+    // Draw the milestone picker if active
+    if app.input_mode == InputMode::Milestone {
+        draw_milestone_overlay(frame, app);
+    }
+
+    // Draw the label picker if active
+    if app.input_mode == InputMode::Labels {
+        draw_labels_overlay(frame, app);
+    }
+
+    // Draw the reviewers panel if active
+    if app.input_mode == InputMode::Reviewers {
+        draw_reviewers_overlay(frame, app);
+    }
+
+    // Draw the AI provider picker if active
+    if app.input_mode == InputMode::AiChoice {
+        draw_ai_choice_overlay(frame, app);
+    }
+
+    // Draw the saved view picker if active
+    if app.input_mode == InputMode::ViewPicker {
+        draw_view_picker_overlay(frame, app);
+    }
+
+    if app.accessible {
+        high_contrast(frame.buffer_mut());
+    }
+}
+
+/// Accessible mode: drop background fills (reverse video becomes bold underline), lift dim and
+/// true-color text to the terminal's default color, and use the bright variant of named colors.
+fn high_contrast(buffer: &mut ratatui::buffer::Buffer) {
+    for cell in &mut buffer.content {
+        if cell.modifier.contains(Modifier::REVERSED) {
+            cell.modifier.remove(Modifier::REVERSED);
+            cell.modifier.insert(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        cell.modifier.remove(Modifier::DIM);
+        cell.bg = Color::Reset;
+        cell.fg = match cell.fg {
+            Color::Red => Color::LightRed,
+            Color::Green => Color::LightGreen,
+            Color::Yellow => Color::LightYellow,
+            Color::Blue => Color::LightBlue,
+            Color::Magenta => Color::LightMagenta,
+            Color::Cyan => Color::LightCyan,
+            Color::Black | Color::Gray | Color::DarkGray | Color::Rgb(..) | Color::Indexed(_) => {
+                Color::Reset
+            }
+            other => other,
+        };
+    }
+}
+
+fn review_state_span(state: &ReviewState) -> Span<'static> {
+    match state {
+        ReviewState::Approved => Span::styled("[✓ APPROVED] ", Style::default().fg(Color::Green)),
+        ReviewState::ChangesRequested => {
+            Span::styled("[! CHANGES] ", Style::default().fg(Color::Red))
+        }
+        ReviewState::Pending => Span::styled("[○ PENDING] ", Style::default().fg(Color::Yellow)),
+        ReviewState::Draft => Span::styled("[DRAFT] ", Style::default().fg(Color::Magenta)),
+    }
+}
+
+/// Help bar block, with the active search query pinned to the right while n/N cycling.
+/// Help hints for actions that change something on GitHub, longest first so that
+/// `a: approve` doesn't eat the start of `a: approve + auto-merge group`.
+const MUTATING_HINTS: [&str; 14] = [
+    "a: approve + auto-merge group",
+    "b: nudge author",
+    "a: approve",
+    "c: comment",
+    "c: reply",
+    "R: re-request review",
+    "u: resolve",
+    "v: select lines",
+    "s: review",
+    "m: merge",
+    "M: milestone",
+    "L: labels",
+    "W: reviewers",
+    "P: project",
+];
+
+/// How long the terminal width has to hold still before delta is re-rendered for it
+const RESIZE_SETTLE: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Terminals narrower than this get stacked list rows
+const NARROW_WIDTH: u16 = 100;
+
+/// Rows of the failed-repos banner above the list's help
+const MAX_FETCH_ERROR_LINES: usize = 3;
+
+/// `help_text` cut down to `width` columns: hints that don't fit are dropped from the end,
+/// except the last one (quit or back), and `…` marks the gap.
+fn fit_help(help_text: &str, width: u16) -> String {
+    let width = usize::from(width);
+    if help_text.chars().count() <= width {
+        return help_text.to_string();
+    }
+    let hints: Vec<&str> = help_text.trim_start().split(" | ").collect();
+    let Some((last, rest)) = hints.split_last() else {
+        return help_text.to_string();
+    };
+    let render = |hints: &[&str]| format!(" {} | … | {}", hints.join(" | "), last);
+    let mut kept: Vec<&str> = Vec::new();
+    for hint in rest {
+        kept.push(hint);
+        if render(&kept).chars().count() > width {
+            kept.pop();
+            break;
+        }
+    }
+    if kept.is_empty() {
+        return format!(" … | {}", last);
+    }
+    render(&kept)
+}
+
+/// `help_text` with the keys of rebound actions in place of their defaults, e.g. `A: approve`
+/// for `a: approve`; `labels` pairs each default key's label with its new keys.
+fn with_key_labels(help_text: &str, labels: &[(String, String)]) -> String {
+    if labels.is_empty() {
+        return help_text.to_string();
+    }
+    let hints: Vec<String> = help_text
+        .trim_start()
+        .split(" | ")
+        .map(|hint| {
+            let Some((key, text)) = hint.split_once(": ") else {
+                return hint.to_string();
+            };
+            match labels.iter().find(|(default, _)| default == key) {
+                Some((_, keys)) => format!("{keys}: {text}"),
+                None => hint.to_string(),
+            }
+        })
+        .collect();
+    format!(" {}", hints.join(" | "))
+}
+
+/// `help_text` without the ` | <hint>` entries in `hidden`
+fn without_hints(help_text: &str, hidden: &[&str]) -> String {
+    hidden.iter().fold(help_text.to_string(), |text, hint| {
+        text.replace(&format!(" | {}", hint), "")
+    })
+}
+
+fn help_block(app: &App) -> Block<'static> {
+    let mut block = Block::default().borders(Borders::ALL).title(" Help ");
+    if app.read_only {
+        block = block.title(Span::styled(
+            " read-only ",
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ));
+    }
+    match app.search_indicator() {
+        Some(indicator) => block.title(
+            Line::from(Span::styled(indicator, Style::default().fg(Color::Yellow))).right_aligned(),
+        ),
+        None => block,
+    }
+}
+
+/// `base ← head`, when the PR was opened and last updated, and whether it merges cleanly.
+fn pr_meta_line(pr: &PullRequest, dates: &DatesConfig, now: DateTime<Utc>) -> Line<'static> {
+    let mut spans = Vec::new();
+    if !pr.base_branch.is_empty() && !pr.head_branch.is_empty() {
+        spans.push(Span::styled(
+            format!("{} ← {}", pr.base_branch, pr.head_branch),
+            Style::default().fg(Color::Cyan),
+        ));
+        spans.push(Span::raw(" | "));
+    }
+    spans.push(Span::styled(
+        format!(
+            "opened {}, updated {}",
+            dates::ago(dates, &pr.created_at, now),
+            dates::ago(dates, &pr.updated_at, now)
+        ),
+        Style::default().fg(Color::DarkGray),
+    ));
+    match pr.mergeable {
+        Some(true) => {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled("mergeable", Style::default().fg(Color::Green)));
+        }
+        Some(false) => {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled("conflicts", Style::default().fg(Color::Red)));
+        }
+        None => {}
+    }
+    Line::from(spans)
+}
+
+/// Per-reviewer status summary, highlighting the current user's own review.
+fn reviewers_line(pr: &PullRequest, username: &str, loading: bool) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        "Reviewers:",
+        Style::default().fg(Color::DarkGray),
+    )];
+    if !pr.details_loaded {
+        let label = if loading { " loading..." } else { " unknown" };
+        spans.push(Span::styled(label, Style::default().fg(Color::DarkGray)));
+        return Line::from(spans);
+    }
+    if pr.reviewers.is_empty() {
+        spans.push(Span::styled(" none", Style::default().fg(Color::DarkGray)));
+        return Line::from(spans);
+    }
+
+    for reviewer in &pr.reviewers {
+        let (symbol, color) = reviewer_symbol(reviewer.state);
+        let mut style = Style::default().fg(color);
+        if reviewer.login == username {
+            style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!("{} @{}", symbol, reviewer.login),
+            style,
+        ));
+        if reviewer.stale {
+            spans.push(Span::styled(
+                " (stale)",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
+    Line::from(spans)
+}
+
+fn reviewer_symbol(state: gh::ReviewerState) -> (&'static str, Color) {
+    match state {
+        gh::ReviewerState::Approved => ("✓", Color::Green),
+        gh::ReviewerState::ChangesRequested => ("✗", Color::Red),
+        gh::ReviewerState::Commented => ("💬", Color::Cyan),
+        gh::ReviewerState::Pending => ("○", Color::Yellow),
+    }
+}
+
+fn draw_list(frame: &mut Frame, app: &mut App) {
+    let banner_lines = fetch_errors_lines(&app.fetch_errors);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(if banner_lines.is_empty() {
+                0
+            } else {
+                banner_lines.len() as u16 + 2
+            }),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+    // The preview pane needs room for both it and a readable list
+    let (list_area, preview_area) = if app.list_config.preview && chunks[1].width >= NARROW_WIDTH {
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(chunks[1]);
+        (panes[0], Some(panes[1]))
+    } else {
+        (chunks[1], None)
+    };
+
+    let my_prs_label = if app.my_approvals {
+        "My Approvals"
+    } else {
+        "My PRs"
+    };
+    let tab_specs: Vec<(String, AppMode)> = [
+        ("Involving Me", AppMode::Review),
+        (my_prs_label, AppMode::MyPrs),
+        ("Watching Repos", AppMode::Watching),
+        ("Dependencies", AppMode::Dependencies),
+    ]
+    .into_iter()
+    .map(|(label, mode)| {
+        // Accessible mode marks the active tab in text, not only by color and border
+        let label = if app.accessible && app.mode == mode {
+            format!("[{}]", label)
+        } else {
+            label.to_string()
+        };
+        (label, mode)
+    })
+    .collect();
+    let mut tab_constraints: Vec<Constraint> = tab_specs
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, (label, _))| {
+            let width = label.chars().count() as u16 + 4;
+            let mut parts = vec![Constraint::Length(width)];
+            if idx + 1 < tab_specs.len() {
+                parts.push(Constraint::Length(1));
+            }
+            parts
+        })
+        .collect();
+    tab_constraints.push(Constraint::Min(0));
+    let tab_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(tab_constraints)
+        .split(chunks[0]);
+
+    for (idx, (label, mode)) in tab_specs.iter().enumerate() {
+        let area_idx = idx * 2;
+        let area = tab_chunks[area_idx];
+        if app.mode == *mode {
+            let selected = Paragraph::new(Line::from(Span::styled(
+                label.as_str(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                    .border_style(Style::default().fg(Color::White)),
+            );
+            frame.render_widget(selected, area);
+        } else {
+            let unselected = Paragraph::new(Line::from(Span::styled(
+                label.as_str(),
+                Style::default().fg(Color::DarkGray),
+            )))
+            .alignment(Alignment::Center)
+            .block(Block::default().padding(Padding::new(1, 1, 1, 1)));
+            frame.render_widget(unselected, area);
+        }
+    }
+
+    let selected_tab_area = tab_specs
+        .iter()
+        .position(|(_, mode)| *mode == app.mode)
+        .map(|idx| tab_chunks[idx * 2]);
+
+    let border_area = Rect {
+        x: chunks[0].x,
+        y: chunks[0]
+            .y
+            .saturating_add(chunks[0].height.saturating_sub(1)),
+        width: chunks[0].width,
+        height: 1,
+    };
+    let mut border_chars = vec!['─'; usize::from(border_area.width)];
+    if !border_chars.is_empty() {
+        border_chars[0] = '┌';
+        if border_chars.len() > 1 {
+            let last = border_chars.len() - 1;
+            border_chars[last] = '┐';
+        }
+    }
+    if let Some(area) = selected_tab_area {
+        if area.width >= 2 && !border_chars.is_empty() {
+            let start = usize::from(area.x.saturating_sub(chunks[0].x));
+            if start < border_chars.len() {
+                let mut end = start + usize::from(area.width.saturating_sub(1));
+                end = end.min(border_chars.len() - 1);
+                border_chars[start] = if start == 0 { '│' } else { '┘' };
+                border_chars[end] = if end == border_chars.len() - 1 {
+                    '│'
+                } else {
+                    '└'
+                };
+                if end > start + 1 {
+                    for ch in &mut border_chars[(start + 1)..end] {
+                        *ch = ' ';
+                    }
+                }
+            }
+        }
+    }
+    if let Some(area) = preview_area {
+        // The preview's left edge meets the tab frame's top border
+        let split = usize::from(area.x.saturating_sub(chunks[0].x));
+        if border_chars.get(split) == Some(&'─') {
+            border_chars[split] = '┬';
+        }
+    }
+    let border_line: String = border_chars.into_iter().collect();
+    frame.render_widget(
+        Paragraph::new(border_line).style(Style::default().fg(Color::White)),
+        border_area,
+    );
+
+    let now = Utc::now();
+    let mut group_sizes: BTreeMap<String, usize> = BTreeMap::new();
+    if app.mode == AppMode::Dependencies {
+        for pr in &app.prs {
+            *group_sizes.entry(deps::group_key(pr)).or_default() += 1;
+        }
+    }
+    let keep_approved = app.list_config.approved != ApprovedPrs::Remove;
+    let narrow = list_area.width < NARROW_WIDTH;
+    let approved: Vec<bool> = app
+        .prs
+        .iter()
+        .map(|pr| keep_approved && approved_by(pr, &app.approved_prs, &app.username))
+        .collect();
+    // The approved section's header sits above its first PR
+    let grouped = app.list_config.group_by_repo;
+    let section_start = (app.list_config.approved == ApprovedPrs::Section && !grouped)
+        .then(|| approved.iter().position(|approved| *approved))
+        .flatten();
+    // Grouped by repo, each repo's header sits above its first PR
+    let mut repo_sizes: BTreeMap<&str, usize> = BTreeMap::new();
+    if grouped {
+        for pr in app.prs.iter().chain(&app.folded_prs) {
+            *repo_sizes.entry(pr.repo_name.as_str()).or_default() += 1;
+        }
+    }
+    let group_start =
+        |idx: usize| grouped && (idx == 0 || app.prs[idx - 1].repo_name != app.prs[idx].repo_name);
+    let repo_header = |pr: &PullRequest| {
+        let folded = app.collapsed_repos.contains(&pr.repo_name);
+        Line::from(Span::styled(
+            format!(
+                "{} {} ({})",
+                if folded { "▸" } else { "▾" },
+                pr.repo_name,
+                repo_sizes.get(pr.repo_name.as_str()).unwrap_or(&1)
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let mut list_block = Block::default()
+        .borders(if preview_area.is_some() {
+            Borders::LEFT | Borders::BOTTOM
+        } else {
+            Borders::LEFT | Borders::RIGHT | Borders::BOTTOM
+        })
+        .border_style(Style::default().fg(Color::White));
+    if let Some(view) = &app.active_view {
+        list_block = list_block.title_bottom(format!(" view: {} ", view.name));
+    }
+    if !app.list_config.filter.is_empty() {
+        list_block = list_block.title_bottom(format!(" filter: {} ", app.list_config.filter));
+    }
+    if app.list_config.sort != ListSort::default() {
+        list_block = list_block.title_bottom(format!(" sort: {} ", app.list_config.sort.label()));
+    }
+
+    // Only rows in view are built, so a list of hundreds of PRs draws as fast as a screenful
+    let mut heights: Vec<usize> = app
+        .prs
+        .iter()
+        .enumerate()
+        .map(|(idx, pr)| {
+            if app.is_folded_row(idx) {
+                return 1;
+            }
+            let badge_row = narrow && !list_badges(app, pr).is_empty();
+            2 + usize::from(badge_row)
+                + usize::from(section_start == Some(idx))
+                + usize::from(group_start(idx))
+        })
+        .collect();
+    heights.extend(std::iter::repeat_n(1, app.pagination_row_count()));
+    let (first, last) = visible_window(
+        &heights,
+        app.list_state.offset(),
+        app.list_state.selected(),
+        usize::from(list_block.inner(list_area).height),
+    );
+    *app.list_state.offset_mut() = first;
+    if let Some(position) = list_position(app, first, last) {
+        list_block = list_block.title_bottom(Line::from(position).right_aligned());
+    }
+
+    let mut items: Vec<ListItem> = (first..last.min(app.prs.len()))
+        .map(|idx| {
+            let pr = &app.prs[idx];
+            if app.is_folded_row(idx) {
+                return ListItem::new(repo_header(pr));
+            }
+            let approved = approved[idx];
+            let stats = if pr.details_loaded {
+                format!("+{}/-{}", pr.additions, pr.deletions)
+            } else {
+                "+?/-?".to_string()
+            };
+            let age = dates::age(&app.dates, &pr.updated_at, now);
+            let mut title_spans = vec![Span::raw(format!("#{}: ", pr.number))];
+            if !grouped {
+                title_spans.insert(
+                    0,
+                    Span::styled(
+                        format!("[{}] ", pr.repo_name),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                );
+            }
+            if let Some(size) = group_sizes.get(&deps::group_key(pr)) {
+                title_spans.insert(
+                    0,
+                    Span::styled(
+                        format!("[{} ×{}] ", deps::group_key(pr), size),
+                        Style::default().fg(Color::Magenta),
+                    ),
+                );
+            }
+            // Show review state in MyPrs mode, draft status in Review mode
+            if app.mode == AppMode::MyPrs {
+                title_spans.push(review_state_span(&pr.review_state));
+            } else if pr.is_draft {
+                title_spans.push(Span::styled(
+                    "[DRAFT] ",
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            let closed = app.closed_prs.get(&pr_key(pr));
+            if let Some(state) = closed {
+                title_spans.push(Span::styled(
+                    format!("[{}] ", state.to_uppercase()),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            if let Some(until) = app.snoozes.until(pr, now) {
+                title_spans.push(Span::styled(
+                    format!(
+                        "[SNOOZED until {}] ",
+                        until.with_timezone(&Local).format("%a %H:%M")
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            if approved {
+                title_spans.push(Span::styled(
+                    "[APPROVED] ",
+                    Style::default().fg(Color::Green),
+                ));
+            }
+            let title_style = if closed.is_some() {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else if approved {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            title_spans.push(Span::styled(&pr.title, title_style));
+            title_spans.extend(label_spans(&pr.labels));
+            let line = Line::from(title_spans);
+            let mut detail_spans = vec![
+                Span::styled(
+                    format!("  @{}", pr.author),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw(" | "),
+                Span::styled(stats, Style::default().fg(Color::Yellow)),
+                Span::raw(" | "),
+                Span::styled(age, Style::default().fg(Color::DarkGray)),
+            ];
+            let badges = list_badges(app, pr);
+            let mut lines = vec![line];
+            if narrow && !badges.is_empty() {
+                // Comment counts and daemon badges get their own line on narrow terminals
+                let mut badges = badges;
+                badges[0] = Span::raw("  ");
+                lines.push(Line::from(detail_spans));
+                lines.push(Line::from(badges));
+            } else {
+                detail_spans.extend(badges);
+                lines.push(Line::from(detail_spans));
+            }
+            if section_start == Some(idx) {
+                let header = Line::from(Span::styled(
+                    "── Approved ──",
+                    Style::default().fg(Color::DarkGray),
+                ));
+                lines.insert(0, header);
+            }
+            if group_start(idx) {
+                lines.insert(0, repo_header(pr));
+            }
+            ListItem::new(lines)
+        })
+        .collect();
+
+    if last > app.prs.len() {
+        let label = if app.loading_next_page {
+            "Loading more PRs..."
+        } else {
+            "More PRs..."
+        };
+        items.push(ListItem::new(Line::from(vec![Span::styled(
+            label,
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        )])));
+    }
+
+    let list = List::new(items)
+        .block(list_block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    let mut window_state =
+        ListState::default().with_selected(app.list_state.selected().map(|idx| idx - first));
+    frame.render_stateful_widget(list, list_area, &mut window_state);
+    app.load_next_page_if_pagination_visible(first..last);
+    if let Some(area) = preview_area {
+        draw_list_preview(frame, app, area);
+    }
+
+    let help_text = match (app.mode, app.my_approvals) {
+        (AppMode::MyPrs, true) => {
+            " Tab/←/→: switch tabs | j/k: navigate | Enter: open | v: my PRs | V: views | f: filter | O: sort | r: group by repo | p: preview | b: nudge author | z: snooze | o: browser | y: copy URL | !: messages | S: summary | R: refresh | q: quit"
+        }
+        (AppMode::Dependencies, _) => {
+            " Tab/←/→: switch tabs | j/k: navigate | Enter: open | V: views | f: filter | O: sort | r: group by repo | p: preview | a: approve + auto-merge group | o: browser | y: copy URL | !: messages | S: summary | R: refresh | q: quit"
+        }
+        (AppMode::MyPrs, false) => {
+            " Tab/←/→: switch tabs | j/k: navigate | Enter: open | v: my approvals | V: views | f: filter | O: sort | r: group by repo | p: preview | /: search | s: search all | z: snooze | w: assign | o: browser | y: copy URL | !: messages | S: summary | R: refresh | q: quit"
+        }
+        _ => {
+            " Tab/←/→: switch tabs | j/k: navigate | Ctrl+d/u: page | Enter: open | V: views | f: filter | O: sort | r: group by repo | p: preview | B: bots | /: search | s: search all | z: snooze | w: assign | o: browser | y: copy URL | !: messages | S: summary | R: refresh | q: quit"
+        }
+    };
+    let mut hidden: Vec<&str> = if app.read_only {
+        MUTATING_HINTS.to_vec()
+    } else {
+        Vec::new()
+    };
+    if app.views.is_empty() {
+        hidden.push("V: views");
+    }
+    if app.mode != AppMode::Review || app.list_config.bots.is_empty() {
+        hidden.push("B: bots");
+    }
+    let help = Paragraph::new(fit_help(
+        &with_key_labels(
+            &without_hints(help_text, &hidden),
+            &app.keys.help_labels(KeyScope::List),
+        ),
+        chunks[3].width.saturating_sub(2),
+    ))
+    .style(Style::default().fg(Color::DarkGray))
+    .block(help_block(app));
+    frame.render_widget(help, chunks[3]);
+
+    if !banner_lines.is_empty() {
+        let failed = app.fetch_errors.len();
+        let banner = Paragraph::new(banner_lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(Span::styled(
+                    format!(
+                        " {} repo{} failed to load, R: retry ",
+                        failed,
+                        if failed == 1 { "" } else { "s" }
+                    ),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+        );
+        frame.render_widget(banner, chunks[2]);
+    }
+}
+
+/// The highlighted PR's header, diff stat, reviewers and description, beside the list.
+fn draw_list_preview(frame: &mut Frame, app: &mut App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+        .border_set(ratatui::symbols::border::Set {
+            bottom_left: "┴",
+            ..ratatui::symbols::border::PLAIN
+        })
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(Color::White));
+    let pr = match app.list_state.selected() {
+        Some(idx) if !app.is_folded_row(idx) => app.prs.get(idx),
+        _ => None,
+    };
+    let Some(pr) = pr.cloned() else {
+        let hint = Paragraph::new(Span::styled(
+            "Nothing to preview",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(block);
+        frame.render_widget(hint, area);
+        return;
+    };
+    app.load_preview_details();
+
+    let stats = if pr.details_loaded {
+        Line::from(vec![
+            Span::styled(
+                format!("+{}", pr.additions),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("-{}", pr.deletions),
+                Style::default().fg(Color::Red),
+            ),
+        ])
+    } else {
+        Line::from(Span::styled("+?/-?", Style::default().fg(Color::DarkGray)))
+    };
+    let mut header = vec![Span::styled(
+        format!("#{} ", pr.number),
+        Style::default().fg(Color::Cyan),
+    )];
+    header.push(Span::styled(
+        pr.title.clone(),
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    let mut author = vec![
+        Span::styled(format!("@{}", pr.author), Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::styled(pr.repo_name.clone(), Style::default().fg(Color::Cyan)),
+    ];
+    author.extend(label_spans(&pr.labels));
+    let mut lines = vec![
+        Line::from(header),
+        Line::from(author),
+        pr_meta_line(&pr, &app.dates, Utc::now()),
+        stats,
+        reviewers_line(&pr, &app.username, app.loading_details),
+        Line::raw(""),
+    ];
+    if pr.body.trim().is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No description provided.",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        lines.extend(pr.body.lines().map(|line| Line::raw(line.to_string())));
+    }
+    let preview = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(block);
+    frame.render_widget(preview, area);
+}
+
+/// Comment counts and the daemon's verdict for a list row, each led by a `" | "` separator.
+fn list_badges(app: &App, pr: &PullRequest) -> Vec<Span<'static>> {
+    let mut badges = Vec::new();
+    if let Some(discussion) = app.discussions.get(&pr_key(pr)) {
+        if discussion.comments > 0 {
+            badges.push(Span::raw(" | "));
+            badges.push(Span::styled(
+                format!(
+                    "{} comment{}",
+                    discussion.comments,
+                    if discussion.comments == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(Color::Blue),
+            ));
+        }
+        if discussion.unresolved_threads > 0 {
+            badges.push(Span::raw(" | "));
+            badges.push(Span::styled(
+                format!("{} unresolved", discussion.unresolved_threads),
+                Style::default().fg(Color::Red),
+            ));
+        }
+    }
+    if let Some(record) = app.daemon_reviews.get(&pr_key(pr)) {
+        badges.push(Span::raw(" | "));
+        badges.push(daemon_badge(record, &app.dates));
+    }
+    badges
+}
+
+/// The rows `[first, last)` that fit in `height` lines, scrolled from `offset` just enough to
+/// show `selected`, the way ratatui's `List` scrolls.
+fn visible_window(
+    heights: &[usize],
+    offset: usize,
+    selected: Option<usize>,
+    height: usize,
+) -> (usize, usize) {
+    if heights.is_empty() {
+        return (0, 0);
+    }
+    let mut first = offset.min(heights.len() - 1);
+    let mut last = first;
+    let mut used = 0;
+    for row_height in &heights[first..] {
+        if used + row_height > height {
+            break;
+        }
+        used += row_height;
+        last += 1;
+    }
+    let selected = selected.unwrap_or(0).min(heights.len() - 1);
+    while selected >= last {
+        used += heights[last];
+        last += 1;
+        while used > height && first + 1 < last {
+            used -= heights[first];
+            first += 1;
+        }
+    }
+    while selected < first {
+        first -= 1;
+        used += heights[first];
+        while used > height && last > first + 1 {
+            last -= 1;
+            used -= heights[last];
+        }
+    }
+    (first, last)
+}
+
+/// Scroll indicator for the list border: the selected PR's index of the loaded ones (`+` while
+/// more pages remain) and how far down the view reaches.
+fn list_position(app: &App, first: usize, last: usize) -> Option<String> {
+    let count = app.prs.len();
+    if count == 0 {
+        return None;
+    }
+    let seen = last.min(count);
+    let index = app
+        .list_state
+        .selected()
+        .filter(|idx| *idx < count)
+        .map_or("-".to_string(), |idx| (idx + 1).to_string());
+    let more = if app.has_next_page { "+" } else { "" };
+    let scroll = if first == 0 && seen == count {
+        "All".to_string()
+    } else {
+        format!("{}%", seen * 100 / count)
+    };
+    Some(format!(" {}/{}{} · {} ", index, count, more, scroll))
+}
+
+/// Banner rows for repos the list could not be fetched from, at most [`MAX_FETCH_ERROR_LINES`].
+fn fetch_errors_lines(errors: &[gh::RepoFetchError]) -> Vec<Line<'static>> {
+    let shown = if errors.len() > MAX_FETCH_ERROR_LINES {
+        MAX_FETCH_ERROR_LINES - 1
+    } else {
+        errors.len()
+    };
+    let mut lines: Vec<Line> = errors[..shown]
+        .iter()
+        .map(|error| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{}: ", error.repo),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(error.reason.lines().next().unwrap_or_default().to_string()),
+            ])
+        })
+        .collect();
+    if shown < errors.len() {
+        lines.push(Line::from(Span::styled(
+            format!("...and {} more", errors.len() - shown),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines
+}
+
+/// A file's patch, widened by `context` lines from its contents at the PR head.
+fn expanded_file_diff(
+    gh: &dyn GhClient,
+    pr: &PullRequest,
+    path: &str,
+    context: usize,
+) -> Result<String> {
+    let patch = gh.get_pr_file_diff(pr, path)?;
+    let contents = gh
+        .get_repo_file(pr, path)?
+        .ok_or_else(|| anyhow::anyhow!("{} was deleted, so there is no context to add", path))?;
+    diff::expand_context(&patch, &contents, context).ok_or_else(|| {
+        anyhow::anyhow!(
+            "The diff of {} does not match its contents at the PR head",
+            path
+        )
+    })
+}
+
+fn pr_key(pr: &PullRequest) -> String {
+    format!("{}#{}", pr.repo_name, pr.number)
+}
+
+/// Cache key for a PR's unfiltered diff; `None` while the head commit is unknown.
+fn generated_head(pr: &PullRequest) -> Option<String> {
+    (!pr.head_oid.is_empty()).then(|| format!("{}@{}", pr_key(pr), pr.head_oid))
+}
+
+fn author_stats_key(pr: &PullRequest) -> String {
+    format!("{}#{}", pr.repo_name, pr.author)
+}
+
+fn author_stats_line(pr: &PullRequest, stats: Option<&Option<gh::AuthorStats>>) -> Line<'static> {
+    let summary = match stats {
+        None => "loading history...".to_string(),
+        Some(None) => "history unavailable".to_string(),
+        Some(Some(stats)) => stats.summary(),
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("@{} here: ", pr.author),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(summary, Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+fn draw_detail(frame: &mut Frame, app: &mut App) {
+    let pr = match app.selected_pr() {
+        Some(pr) => pr,
+        None => return,
+    };
+
+    let show_author_stats = app.shows_author_stats();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(if show_author_stats { 5 } else { 4 }),
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+
+    // Build CI status indicator
+    let ci_status = if !app.gh.supports(Capability::PrChecksJson) {
+        Span::styled(
+            format!(" CI: needs gh {}+", Capability::PrChecksJson.since()),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else if app.loading_checks {
+        Span::styled(" CI: ...", Style::default().fg(Color::DarkGray))
+    } else if let Some(checks) = &app.checks_cache {
+        if checks.is_empty() {
+            Span::raw("")
+        } else {
+            let (passed, failed, pending) = checks.iter().fold((0, 0, 0), |(p, f, pe), c| {
+                match c.status {
+                    gh::CheckState::Success => (p + 1, f, pe),
+                    gh::CheckState::Failure => (p, f + 1, pe),
+                    gh::CheckState::Pending => (p, f, pe + 1),
+                    gh::CheckState::Neutral => (p + 1, f, pe), // Count neutral as passed
+                }
+            });
+            let marker = |symbol: &'static str, word: &'static str| {
+                if app.accessible {
+                    word
+                } else {
+                    symbol
+                }
+            };
+            if failed > 0 {
+                Span::styled(
+                    format!(
+                        " CI: {}/{} {}",
+                        passed,
+                        passed + failed + pending,
+                        marker("✗", "failing")
+                    ),
+                    Style::default().fg(Color::Red),
+                )
+            } else if pending > 0 {
+                Span::styled(
+                    format!(
+                        " CI: {}/{} {}",
+                        passed,
+                        passed + pending,
+                        marker("○", "pending")
+                    ),
+                    Style::default().fg(Color::Yellow),
+                )
+            } else {
+                Span::styled(
+                    format!(" CI: {} {}", passed, marker("✓", "passing")),
+                    Style::default().fg(Color::Green),
+                )
+            }
+        }
+    } else {
+        Span::raw("")
+    };
+
+    let protection_status = match app.protection_cache.as_ref().and_then(Option::as_ref) {
+        Some(protection) => Span::styled(
+            format!(" | {}", protection.summary()),
+            Style::default().fg(if protection.approval_shortfall().is_some() {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            }),
+        ),
+        None => Span::raw(""),
+    };
+
+    let mut triage_status = Vec::new();
+    if app.selected_repo_read_only() {
+        triage_status.push(Span::styled(
+            " | read-only repo",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if let Some(review) = app.pending_reviews.get(&pr_key(pr)) {
+        triage_status.push(Span::styled(
+            format!(" | review: {} queued", review.comments.len()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if let Some(triage) = &app.triage_cache {
+        if let Some(milestone) = &triage.milestone {
+            triage_status.push(Span::styled(
+                format!(" | milestone: {}", milestone),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        if let Some(projects) = triage.projects.as_ref().filter(|p| !p.is_empty()) {
+            triage_status.push(Span::styled(
+                format!(" | projects: {}", projects.join(", ")),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+    }
+
+    // Header: title, then branches, dates, mergeability and checks, then reviewers
+    let mut meta = pr_meta_line(pr, &app.dates, Utc::now());
+    if !ci_status.content.is_empty() {
+        meta.spans.push(Span::raw(" |"));
+        meta.spans.push(ci_status);
+    }
+    meta.spans.push(protection_status);
+    meta.spans.extend(triage_status);
+    let mut header_lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("[{}] ", pr.repo_name),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(format!("#{}: ", pr.number)),
+            Span::styled(&pr.title, Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" by "),
+            Span::styled(format!("@{}", pr.author), Style::default().fg(Color::Green)),
+        ]),
+        meta,
+        reviewers_line(pr, &app.username, app.loading_details),
+    ];
+    header_lines[0].spans.extend(label_spans(&pr.labels));
+    if show_author_stats {
+        header_lines.push(author_stats_line(
+            pr,
+            app.author_stats_cache.get(&author_stats_key(pr)),
+        ));
+    }
+    let header = Paragraph::new(header_lines)
+        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+    frame.render_widget(header, chunks[0]);
+
+    // Tabs
+    let tabs = Tabs::new(
+        app.available_detail_tabs()
+            .into_iter()
+            .map(|tab| {
+                if app.accessible && tab == app.detail_tab {
+                    format!("[{}]", tab.title())
+                } else {
+                    tab.title().to_string()
+                }
+            })
+            .collect::<Vec<_>>(),
+    )
+    .select(app.detail_tab_index())
+    .style(Style::default().fg(Color::White))
+    .highlight_style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+    frame.render_widget(tabs, chunks[1]);
+
+    // Build diff title with current line info
+    let diff_title = {
+        let using_delta =
+            app.use_delta && app.delta_cache.is_some() && app.filtered_diff_cache.is_none();
+        let renderer = if using_delta { "delta" } else { "built-in" };
+        let line_idx = app.diff_cursor as usize;
+        if app.showing_large_diff_tree() {
+            if app.per_file_diff {
+                format!(
+                    " Diff ({}) - {} files, large PR (Enter to load a file diff) ",
+                    renderer,
+                    app.file_diff_sections.len()
+                )
+            } else if app.delta_too_large {
+                format!(
+                    " Diff ({}) - file tree (large fallback, Enter to open, t to hide) ",
+                    renderer
+                )
+            } else {
+                format!(
+                    " Diff ({}) - file tree (Enter to open, t to hide) ",
+                    renderer
+                )
+            }
+        } else if let Some(file_path) = app.selected_file_diff_path.as_deref() {
+            let context = if app.file_context_lines > 0 {
+                format!(" (+{} context)", app.file_context_lines)
+            } else {
+                String::new()
+            };
+            format!(
+                " Diff ({}) - {}{} [Esc: tree, e: more context] ",
+                renderer, file_path, context
+            )
+        } else if !using_delta {
+            if let Some(dl) = app.active_diff_lines().get(line_idx) {
+                if let Some(file) = &dl.file_path {
+                    let line_info = match (dl.line_number, dl.old_line_number) {
+                        (Some(new), _) => format!(":{}", new),
+                        (None, Some(old)) => format!(":{} (old)", old),
+                        _ => String::new(),
+                    };
+                    format!(" Diff ({}) - {}{} ", renderer, file, line_info)
+                } else {
+                    format!(" Diff ({}) [D to toggle, t: tree] ", renderer)
+                }
+            } else {
+                format!(" Diff ({}) [D to toggle, t: tree] ", renderer)
+            }
+        } else {
+            // Side-by-side delta: show where a comment on the focused row would land
+            let column = match app.diff_column {
+                CommentSide::Left => "old",
+                CommentSide::Right => "new",
+            };
+            let target = app.delta_line_info.get(line_idx).and_then(|info| {
+                let file = info.file_path.as_deref()?;
+                let (line, side) = delta_comment_target(info, app.diff_column)?;
+                let old = if side == CommentSide::Left {
+                    " (old)"
+                } else {
+                    ""
+                };
+                Some(format!("{}:{}{}", file, line, old))
+            });
+            match target {
+                Some(target) => format!(
+                    " Diff ({}) - {} [h/l: column, {}] ",
+                    renderer, target, column
+                ),
+                None => match app
+                    .delta_line_info
+                    .get(line_idx)
+                    .and_then(|info| info.file_path.as_deref())
+                {
+                    Some(file) => {
+                        format!(" Diff ({}) - {} [h/l: column, {}] ", renderer, file, column)
+                    }
+                    None => format!(
+                        " Diff ({}) [D to toggle, t: tree, h/l: column, {}] ",
+                        renderer, column
+                    ),
+                },
+            }
+        }
+    };
+    let diff_title = match app.active_path_filter() {
+        Some(filter) => format!("{}[only {}] ", diff_title, filter.label()),
+        None => diff_title,
+    };
+    let diff_title = match app.collapsed_files.len() {
+        0 => diff_title,
+        n => format!("{}[{} collapsed] ", diff_title, n),
+    };
+    let diff_title = if app.generated_collapsed > 0 {
+        format!(
+            "{}[{} generated hidden, H to show] ",
+            diff_title, app.generated_collapsed
+        )
+    } else {
+        diff_title
+    };
+    let focused_threads = if app.uses_diff_cursor() {
+        app.focused_review_threads().len()
+    } else {
+        0
+    };
+    let diff_title = match focused_threads {
+        0 => diff_title,
+        _ if app.inline_comments_expanded => format!("{}[Enter: hide comments] ", diff_title),
+        1 => format!("{}[1 review thread, Enter to show] ", diff_title),
+        n => format!("{}[{} review threads, Enter to show] ", diff_title, n),
+    };
+    // Inside the content block's borders
+    let content_width = chunks[2].width.saturating_sub(2);
+    let content_height = chunks[2].height.saturating_sub(2);
+    let content_block = Block::default()
+        .borders(Borders::ALL)
+        .title(match app.detail_tab {
+            DetailTab::Diff => diff_title,
+            DetailTab::Files => match &app.changed_file_diff {
+                Some((path, _)) => format!(" {} [Esc: files] ", path),
+                None => " Files ".to_string(),
+            },
+            DetailTab::Commits => match &app.commit_diff {
+                Some((sha, _)) => format!(" Commit {} [Esc: commits] ", short_sha(sha)),
+                None => " Commits ".to_string(),
+            },
+            tab => format!(" {} ", tab.title()),
+        });
+
+    match app.detail_tab {
+        DetailTab::Description => {
+            let body = if app.loading_details && pr.body.is_empty() {
+                "Loading details...".to_string()
+            } else if pr.body.is_empty() {
+                "No description provided.".to_string()
+            } else {
+                pr.body.clone()
+            };
+            let para = Paragraph::new(body).wrap(Wrap { trim: false });
+            app.set_scroll_bounds(para.line_count(content_width), content_height);
+            let para = para.block(content_block).scroll((app.scroll_offset, 0));
+            frame.render_widget(para, chunks[2]);
+        }
+        DetailTab::Diff => {
+            if app.diff_cache.is_none() && !app.loading_diff {
+                app.load_diff();
+            }
+            // Existing line comments are marked in the diff
+            app.load_review_comments();
+            if app.showing_large_diff_tree() {
+                let items: Vec<ListItem> = app
+                    .file_tree_items
+                    .iter()
+                    .map(|item| {
+                        let style = if item.file_path.is_some() {
+                            Style::default().fg(Color::White)
+                        } else {
+                            Style::default()
+                                .fg(Color::DarkGray)
+                                .add_modifier(Modifier::BOLD)
+                        };
+                        let mut spans = vec![Span::styled(item.label.clone(), style)];
+                        if let Some((additions, deletions)) = item
+                            .file_path
+                            .as_ref()
+                            .and_then(|path| app.file_line_stats.get(path))
+                        {
+                            spans.push(Span::styled(
+                                format!(" +{}", additions),
+                                Style::default().fg(Color::Green),
+                            ));
+                            spans.push(Span::styled(
+                                format!(" -{}", deletions),
+                                Style::default().fg(Color::Red),
+                            ));
+                        }
+                        ListItem::new(Line::from(spans))
+                    })
+                    .collect();
+                let tree_list = List::new(items)
+                    .block(content_block)
+                    .highlight_style(
+                        Style::default()
+                            .bg(Color::DarkGray)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .highlight_symbol("▶ ");
+                frame.render_stateful_widget(tree_list, chunks[2], &mut app.file_tree_state);
+            } else {
+                let loaded = !app.loading_diff
+                    && (app.filtered_diff_cache.is_some() || app.active_diff_content().is_some());
+                let mut lines: Vec<Line> = if app.loading_diff {
+                    vec![Line::raw("Loading diff...")]
+                } else if let Some(diff_content) = app.filtered_diff_cache.as_deref() {
+                    // Single-file and path-filtered views always use the built-in renderer
+                    diff::render_diff(diff_content, &app.syntax_highlighter)
+                } else if app.use_delta {
+                    if let Some(delta_output) = app.delta_cache.as_deref() {
+                        // Use pre-processed delta output
+                        diff::render_from_ansi(delta_output)
+                    } else if let Some(diff_content) = app.active_diff_content() {
+                        // Delta not available, fallback to built-in
+                        diff::render_diff(diff_content, &app.syntax_highlighter)
+                    } else {
+                        vec![Line::raw("Loading diff...")]
+                    }
+                } else if let Some(diff_content) = app.active_diff_content() {
+                    // Built-in rendering (delta disabled)
+                    diff::render_diff(diff_content, &app.syntax_highlighter)
+                } else {
+                    vec![Line::raw("Loading diff...")]
+                };
+
+                if loaded {
+                    app.set_scroll_bounds(lines.len(), content_height);
+                } else {
+                    app.clear_scroll_bounds(content_height);
+                }
+
+                // Add margin prefix to all lines, with indicator on focused line, a bar
+                // along a `v` selection and a diamond on lines with review threads
+                let focus_idx = app.diff_cursor as usize;
+                let selection = app.diff_selection_rows();
+                let threads = app
+                    .review_comments_cache
+                    .as_deref()
+                    .map(review_threads)
+                    .unwrap_or_default();
+                for (idx, line) in lines.iter_mut().enumerate() {
+                    let old_line = std::mem::take(line);
+                    let commented = !threads.is_empty()
+                        && app
+                            .diff_row_location(idx)
+                            .is_some_and(|(path, old_line, new_line)| {
+                                !threads_on_row(&threads, path, old_line, new_line).is_empty()
+                            });
+                    let prefix = if idx == focus_idx {
+                        Span::styled("▶ ", Style::default().fg(Color::Yellow).bold())
+                    } else if selection.as_ref().is_some_and(|rows| rows.contains(&idx)) {
+                        Span::styled("┃ ", Style::default().fg(Color::Yellow))
+                    } else if commented {
+                        Span::styled("◆ ", Style::default().fg(Color::Cyan))
+                    } else {
+                        Span::raw("  ")
+                    };
+                    let mut new_spans = vec![prefix];
+                    new_spans.extend(old_line.spans);
+                    *line = if idx == focus_idx {
+                        Line::from(new_spans).style(Style::default().bg(Color::DarkGray))
+                    } else {
+                        Line::from(new_spans)
+                    };
+                }
+
+                let para = Paragraph::new(lines)
+                    .block(content_block)
+                    .scroll((app.scroll_offset, 0));
+                frame.render_widget(para, chunks[2]);
+                if app.inline_comments_expanded {
+                    draw_inline_review_threads(frame, app, chunks[2].inner(Margin::new(1, 1)));
+                }
+            }
+        }
+        DetailTab::Comments => {
+            if app.comments_cache.is_none() && !app.loading_comments {
+                app.load_comments();
+            }
+            if app.review_comments_cache.is_none() && !app.loading_review_comments {
+                app.load_review_comments();
+            }
+            let loading = app.loading_comments || app.loading_review_comments;
+            let mut thread_start = None;
+            let text = if loading {
+                Text::raw("Loading comments...")
+            } else {
+                let mut lines: Vec<Line> = Vec::new();
+
+                // Show review comments (line-level) with diff context first
+                if let Some(review_comments) = app.review_comments_cache.as_ref() {
+                    // Replies are listed under the thread they answer
+                    let top_level = review_threads(review_comments);
+                    if let Some(idx) = app.selected_thread {
+                        app.selected_thread = Some(idx.min(top_level.len().saturating_sub(1)))
+                            .filter(|_| !top_level.is_empty());
+                    }
+
+                    if !top_level.is_empty() {
+                        lines.push(Line::styled(
+                            "─── Review Comments (on code) ───",
+                            Style::default().fg(Color::Yellow).bold(),
+                        ));
+                        lines.push(Line::raw(""));
+
+                        for (thread_idx, comment) in top_level.into_iter().enumerate() {
+                            let selected = app.selected_thread == Some(thread_idx);
+                            if selected {
+                                thread_start = Some(lines.len());
+                            }
+                            let line_info =
+                                comment.line.map(|l| format!(":{}", l)).unwrap_or_default();
+                            let resolution = match app.review_thread_states.get(&comment.id) {
+                                Some(state) if state.is_resolved => {
+                                    Span::styled("  [resolved]", Style::default().fg(Color::Green))
+                                }
+                                Some(_) => Span::styled(
+                                    "  [unresolved]",
+                                    Style::default().fg(Color::Yellow),
+                                ),
+                                None => Span::raw(""),
+                            };
+
+                            // File and line header
+                            let marker = if selected { "▶ " } else { "" };
+                            let header_style = Style::default().fg(Color::Blue).bold();
+                            lines.push(Line::from(vec![
+                                Span::styled(
+                                    format!("{}📁 {}{}", marker, comment.path, line_info),
+                                    if selected {
+                                        header_style.add_modifier(Modifier::REVERSED)
+                                    } else {
+                                        header_style
+                                    },
+                                ),
+                                resolution,
+                            ]));
+
+                            // Diff hunk context (show last few lines for context)
+                            let hunk_lines: Vec<&str> = comment.diff_hunk.lines().collect();
+                            let start = hunk_lines.len().saturating_sub(8);
+                            for hunk_line in &hunk_lines[start..] {
+                                let (style, prefix) = if hunk_line.starts_with('+') {
+                                    (Style::default().fg(Color::Green), "")
+                                } else if hunk_line.starts_with('-') {
+                                    (Style::default().fg(Color::Red), "")
+                                } else if hunk_line.starts_with("@@") {
+                                    (Style::default().fg(Color::Magenta), "")
+                                } else {
+                                    (Style::default().fg(Color::DarkGray), "")
+                                };
+                                lines.push(Line::styled(
+                                    format!("  {}{}", prefix, hunk_line),
+                                    style,
+                                ));
+                            }
+
+                            // Comment author and body, then the replies
+                            lines.extend(review_thread_lines(app, comment, review_comments));
+
+                            lines.push(Line::raw(""));
+                        }
+                    }
+                }
+
+                // Show PR-level comments
+                if let Some(pr_comments) = app.comments_cache.as_ref() {
+                    if !pr_comments.is_empty() {
+                        lines.push(Line::styled(
+                            "─── General Comments ───",
+                            Style::default().fg(Color::Yellow).bold(),
+                        ));
+                        lines.push(Line::raw(""));
+
+                        for comment in pr_comments {
+                            let author = comment
+                                .author
+                                .as_ref()
+                                .and_then(|a| a.login.as_ref())
+                                .map(|s| s.as_str())
+                                .unwrap_or("unknown");
+                            let date = dates::absolute(&app.dates, &comment.created_at);
+                            lines.push(Line::styled(
+                                format!("@{} ({})", author, date),
+                                Style::default().fg(Color::Cyan).bold(),
+                            ));
+                            for body_line in comment.body.lines() {
+                                lines.push(Line::raw(format!("  {}", body_line)));
+                            }
+                            lines.push(Line::raw(""));
+                        }
+                    }
+                }
+
+                if lines.is_empty() {
+                    Text::raw("No comments yet.")
+                } else {
+                    Text::from(lines)
+                }
+            };
+            if let Some(start) = thread_start.filter(|_| app.scroll_to_thread) {
+                // Wrapped rows above the selected thread
+                let above = Text::from(text.lines[..start].to_vec());
+                app.scroll_offset = Paragraph::new(above)
+                    .wrap(Wrap { trim: false })
+                    .line_count(content_width) as u16;
+                app.scroll_to_thread = false;
+            }
+            let para = Paragraph::new(text).wrap(Wrap { trim: false });
+            app.set_scroll_bounds(para.line_count(content_width), content_height);
+            let para = para.block(content_block).scroll((app.scroll_offset, 0));
+            frame.render_widget(para, chunks[2]);
+        }
+        DetailTab::Feedback => {
+            if app.feedback_cache.is_none() && !app.loading_feedback {
+                app.load_feedback();
+            }
+            let text = match app.feedback_cache.as_ref() {
+                _ if app.loading_feedback => Text::raw("Loading review feedback..."),
+                None => Text::raw("No review feedback loaded."),
+                Some(Err(err)) => Text::raw(format!("Failed to load review feedback:\n\n{}", err)),
+                Some(Ok(feedback)) => feedback_text(feedback, &app.dates),
+            };
+            let para = Paragraph::new(text).wrap(Wrap { trim: false });
+            app.set_scroll_bounds(para.line_count(content_width), content_height);
+            let para = para.block(content_block).scroll((app.scroll_offset, 0));
+            frame.render_widget(para, chunks[2]);
+        }
+        DetailTab::Files => draw_changed_files(frame, app, content_block, chunks[2]),
+        DetailTab::Commits => draw_commits(frame, app, content_block, chunks[2]),
+        DetailTab::Checks => draw_checks(frame, app, content_block, chunks[2]),
+        DetailTab::Ai => draw_ai_results(frame, app, content_block, chunks[2]),
+        DetailTab::Agent => {
+            if app.agent_preview_cache.is_none() && !app.loading_agent_preview {
+                app.load_agent_preview();
+            }
+
+            let body = if app.loading_agent_preview {
+                "Loading agent session...".to_string()
+            } else if let Some(preview) = app.agent_preview_cache.as_ref() {
+                match (&preview.pane, &preview.error) {
+                    (Some(pane), None) => {
+                        let mut text = format!(
+                            "tmux target: {} | {}:{} | {} | {}\n\n",
+                            pane.target,
+                            pane.session_name,
+                            pane.window_index,
+                            pane.pane_command,
+                            pane.pane_title
+                        );
+                        if preview.output.trim().is_empty() {
+                            text.push_str("Agent pane is currently blank.");
+                        } else {
+                            text.push_str(&preview.output);
+                        }
+                        text
+                    }
+                    (Some(pane), Some(err)) => format!(
+                        "Found tmux target {} for {}, but capture failed:\n\n{}",
+                        pane.target, preview.expected_slug, err
+                    ),
+                    (None, Some(err)) => format!(
+                        "Could not inspect tmux for {}:\n\n{}",
+                        preview.expected_slug, err
+                    ),
+                    (None, None) => format!(
+                        "No agent session found for {}.\n\nPress r to launch a review session, then R to refresh this tab.",
+                        preview.expected_slug
+                    ),
+                }
+            } else {
+                "No agent preview loaded.".to_string()
+            };
+
+            let para = Paragraph::new(body).wrap(Wrap { trim: false });
+            app.set_scroll_bounds(para.line_count(content_width), content_height);
+            let para = para.block(content_block).scroll((app.scroll_offset, 0));
+            frame.render_widget(para, chunks[2]);
+        }
+    }
+
+    // Help - context-aware based on tab and mode
+    let help_text = if app.detail_tab == DetailTab::Diff && app.showing_large_diff_tree() {
+        " j/k: navigate files | /: name search | ?: content search | Enter: open file diff | t: hide tree | q: back"
+    } else if app.detail_tab == DetailTab::Diff && app.showing_single_file_diff() {
+        match app.mode {
+            AppMode::MyPrs => {
+                " j/k: scroll | }/{: next/prev hunk | Esc: file tree | t: full diff | /: search | Enter: comments | f: file | D: delta | m: merge | o: browser | y: copy | q: back"
+            }
+            AppMode::Review | AppMode::Watching | AppMode::Dependencies => {
+                " j/k: scroll | }/{: next/prev hunk | Esc: file tree | t: full diff | /: search | Enter: comments | f: file | c: comment | v: select lines | s: review | D: delta | a: approve | o: browser | y: copy | q: back"
+            }
+        }
+    } else {
+        match (app.detail_tab, app.mode) {
+            (DetailTab::Files, _) if app.changed_file_diff.is_some() => {
+                " Tab: tabs | j/k: scroll | Esc: back to files | o: browser | y: copy | q: back"
+            }
+            (DetailTab::Files, _) => {
+                " Tab: tabs | j/k: select file | Enter: view file diff | o: browser | y: copy | q: back"
+            }
+            (DetailTab::Commits, _) if app.commit_diff.is_some() => {
+                " Tab: tabs | j/k: scroll | Esc: back to commits | o: browser | y: copy | q: back"
+            }
+            (DetailTab::Commits, _) => {
+                " Tab: tabs | j/k: select commit | Enter: view commit diff | o: browser | y: copy | q: back"
+            }
+            (DetailTab::Checks, _) => {
+                " Tab: tabs | j/k: select check | Enter: open check in browser | R: reload checks | o: browser | q: back"
+            }
+            (DetailTab::Ai, _) => {
+                " Tab: tabs | j/k: select finding | Enter: jump to diff | R: reload results | q: back"
+            }
+            (DetailTab::Agent, _) => {
+                " Tab: tabs | j/k: scroll | R: refresh agent | Enter/A: attach | r: launch | Ctrl+r: launch with... | q: back"
+            }
+            (DetailTab::Diff, AppMode::MyPrs) => {
+                " j/k: scroll | ]/[: next/prev file | }/{: next/prev hunk | /: search | Enter: comments, fold file on its header | f: file | B: blame | E: editor | :only: filter files | t: tree | D: delta | m: merge | o: browser | y: copy | q: back"
+            }
+            (DetailTab::Diff, AppMode::Review | AppMode::Watching | AppMode::Dependencies) => {
+                " j/k: scroll | ]/[: next/prev file | }/{: next/prev hunk | /: search | Enter: comments, fold file on its header | f: file | B: blame | E: editor | :only: filter files | t: tree | c: comment | v: select lines | s: review | D: delta | C: checklist | a: approve | o: browser | y: copy | q: back"
+            }
+            (DetailTab::Comments, AppMode::MyPrs) => {
+                " Tab: tabs | j/k: scroll | ]/[: select thread | c: reply | u: resolve | R: re-request review | W: reviewers | m: merge | o: browser | y: copy | !: messages | q: back"
+            }
+            (DetailTab::Comments, AppMode::Review | AppMode::Watching | AppMode::Dependencies) => {
+                " Tab: tabs | j/k: scroll | ]/[: select thread | c: reply | u: resolve | a: approve | o: browser | y: copy | !: messages | q: back"
+            }
+            (_, AppMode::MyPrs) => {
+                " Tab: tabs | j/k: scroll | m: merge | L: labels | W: reviewers | o: browser | y: copy | !: messages | q: back"
+            }
+            (_, AppMode::Review | AppMode::Watching | AppMode::Dependencies) => {
+                " Tab: tabs | j/k: scroll | C: checklist | M: milestone | L: labels | P: project | a: approve | o: browser | y: copy | !: messages | q: back"
+            }
+        }
+    };
+    let hidden: &[&str] = if app.read_only {
+        &MUTATING_HINTS
+    } else if app.selected_repo_read_only() {
+        &["m: merge"]
+    } else {
+        &[]
+    };
+    let help = Paragraph::new(fit_help(
+        &with_key_labels(
+            &without_hints(help_text, hidden),
+            &app.keys.help_labels(KeyScope::Detail),
+        ),
+        chunks[3].width.saturating_sub(2),
+    ))
+    .style(Style::default().fg(Color::DarkGray))
+    .block(help_block(app));
+    frame.render_widget(help, chunks[3]);
+}
+
+/// One row per changed file: status, path (with where a rename came from) and lines changed, or
+/// the diff of the file opened with `Enter`.
+fn draw_changed_files(frame: &mut Frame, app: &mut App, block: Block<'_>, area: Rect) {
+    if let Some(path) = &app.loading_changed_file_diff {
+        let message = format!("Loading diff of {}...", path);
+        frame.render_widget(Paragraph::new(message).block(block), area);
+        return;
+    }
+    if let Some((_, diff)) = &app.changed_file_diff {
+        let lines = match diff {
+            Ok(diff) => diff::render_diff(diff, &app.syntax_highlighter),
+            Err(err) => vec![Line::raw(format!("Failed to load the file diff: {}", err))],
+        };
+        let inner = block.inner(area);
+        app.set_scroll_bounds(lines.len(), inner.height);
+        let para = Paragraph::new(lines)
+            .block(block)
+            .scroll((app.scroll_offset, 0));
+        frame.render_widget(para, area);
+        return;
+    }
+
+    let files = match &app.changed_files_cache {
+        Some(Ok(files)) if !files.is_empty() => files,
+        Some(Ok(_)) => {
+            frame.render_widget(Paragraph::new("No changed files.").block(block), area);
+            return;
+        }
+        Some(Err(err)) => {
+            let message = format!("Failed to load changed files:\n\n{}", err);
+            let para = Paragraph::new(message).wrap(Wrap { trim: false });
+            frame.render_widget(para.block(block), area);
+            return;
+        }
+        None => {
+            frame.render_widget(
+                Paragraph::new("Loading changed files...").block(block),
+                area,
+            );
+            return;
+        }
+    };
+
+    let items: Vec<ListItem> = files
+        .iter()
+        .map(|file| {
+            let (label, color) = match file.status.as_str() {
+                "added" => ("added   ", Color::Green),
+                "removed" => ("deleted ", Color::Red),
+                "renamed" => ("renamed ", Color::Cyan),
+                "copied" => ("copied  ", Color::Cyan),
+                _ => ("modified", Color::Yellow),
+            };
+            let path = match &file.previous_filename {
+                Some(previous) => format!("{} → {}", previous, file.path),
+                None => file.path.clone(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", label), Style::default().fg(color)),
+                Span::styled(
+                    format!("+{}", file.additions),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("-{}", file.deletions),
+                    Style::default().fg(Color::Red),
+                ),
+                Span::raw(format!(" {}", path)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, area, &mut app.changed_files_state);
+}
+
+/// The abbreviated commit hash git shows by default
+fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
+/// The PR's commits (sha, author, date, subject), or the diff of the one opened with `Enter`.
+fn draw_commits(frame: &mut Frame, app: &mut App, block: Block<'_>, area: Rect) {
+    if let Some(sha) = &app.loading_commit_diff {
+        let message = format!("Loading diff of {}...", short_sha(sha));
+        frame.render_widget(Paragraph::new(message).block(block), area);
+        return;
+    }
+    if let Some((_, diff)) = &app.commit_diff {
+        let lines = match diff {
+            Ok(diff) => diff::render_diff(diff, &app.syntax_highlighter),
+            Err(err) => vec![Line::raw(format!(
+                "Failed to load the commit diff: {}",
+                err
+            ))],
+        };
+        let inner = block.inner(area);
+        app.set_scroll_bounds(lines.len(), inner.height);
+        let para = Paragraph::new(lines)
+            .block(block)
+            .scroll((app.scroll_offset, 0));
+        frame.render_widget(para, area);
+        return;
+    }
+
+    let commits = match &app.commits_cache {
+        Some(Ok(commits)) if !commits.is_empty() => commits,
+        Some(Ok(_)) => {
+            frame.render_widget(Paragraph::new("No commits.").block(block), area);
+            return;
+        }
+        Some(Err(err)) => {
+            let message = format!("Failed to load commits:\n\n{}", err);
+            let para = Paragraph::new(message).wrap(Wrap { trim: false });
+            frame.render_widget(para.block(block), area);
+            return;
+        }
+        None => {
+            frame.render_widget(Paragraph::new("Loading commits...").block(block), area);
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    let items: Vec<ListItem> = commits
+        .iter()
+        .map(|commit| {
+            let author = commit
+                .login
+                .as_ref()
+                .map(|login| format!("@{}", login))
+                .or_else(|| commit.name.clone())
+                .unwrap_or_else(|| "unknown".to_string());
+            let date = commit
+                .date
+                .map(|date| dates::age(&app.dates, &date, now))
+                .unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{} ", short_sha(&commit.sha)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(format!("{} ", author), Style::default().fg(Color::Green)),
+                Span::styled(format!("{} ", date), Style::default().fg(Color::DarkGray)),
+                Span::raw(
+                    commit
+                        .message
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, area, &mut app.commits_state);
+}
+
+/// One row per CI check: state, name, how long it ran, and its page.
+fn draw_checks(frame: &mut Frame, app: &mut App, block: Block<'_>, area: Rect) {
+    let checks = match &app.checks_cache {
+        _ if !app.gh.supports(Capability::PrChecksJson) => {
+            let message = format!(
+                "Listing checks needs gh {}+",
+                Capability::PrChecksJson.since()
+            );
+            frame.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        }
+        Some(checks) if !checks.is_empty() => checks,
+        _ => {
+            let message = if app.loading_checks {
+                "Loading checks..."
+            } else {
+                "No checks reported for this PR."
+            };
+            frame.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    let name_width = checks
+        .iter()
+        .map(|check| check.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = checks
+        .iter()
+        .map(|check| {
+            let (symbol, word, color) = match check.status {
+                gh::CheckState::Success => ("✓", "pass", Color::Green),
+                gh::CheckState::Failure => ("✗", "fail", Color::Red),
+                gh::CheckState::Pending => ("●", "pending", Color::Yellow),
+                gh::CheckState::Neutral => ("-", "skipped", Color::DarkGray),
+            };
+            let state = if app.accessible {
+                format!("{:<8}", word)
+            } else {
+                format!("{} {:<8}", symbol, word)
+            };
+            let duration = check.duration(now).map(dates::duration).unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(state, Style::default().fg(color).bold()),
+                Span::raw(format!("{:<width$}", check.name, width = name_width)),
+                Span::styled(
+                    format!("  {:>7}  ", duration),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    check.link.clone().unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, area, &mut app.checks_state);
+}
+
+fn draw_ai_results(frame: &mut Frame, app: &mut App, block: Block<'_>, area: Rect) {
+    let Some(results) = app.ai_results.as_ref() else {
+        frame.render_widget(Paragraph::new("No AI review results.").block(block), area);
+        return;
+    };
+
+    let source = Line::styled(
+        format!("{}", results.source.display()),
+        Style::default().fg(Color::DarkGray),
+    );
+    if results.findings.is_empty() {
+        let mut lines = vec![source, Line::raw("")];
+        lines.extend(
+            results
+                .summary
+                .lines()
+                .map(|line| Line::raw(line.to_string())),
+        );
+        let inner = block.inner(area);
+        let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+        app.set_scroll_bounds(para.line_count(inner.width), inner.height);
+        let para = para.block(block).scroll((app.scroll_offset, 0));
+        frame.render_widget(para, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let mut summary_lines = vec![source];
+    summary_lines.extend(
+        results
+            .summary
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .take(5)
+            .map(|line| Line::raw(line.to_string())),
+    );
+    let summary_height = (summary_lines.len() as u16 + 1).min(inner.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(summary_height), Constraint::Min(1)])
+        .split(inner);
+    frame.render_widget(
+        Paragraph::new(summary_lines).wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    let items: Vec<ListItem> = results
+        .findings
+        .iter()
+        .map(|finding| {
+            let mut spans = Vec::new();
+            if !finding.severity.is_empty() {
+                let color = match finding.severity.to_lowercase().as_str() {
+                    "high" | "critical" => Color::Red,
+                    "medium" => Color::Yellow,
+                    _ => Color::Blue,
+                };
+                spans.push(Span::styled(
+                    format!("[{}] ", finding.severity),
+                    Style::default().fg(color).bold(),
+                ));
+            }
+            if let Some(path) = &finding.path {
+                let location = match finding.line {
+                    Some(line) => format!("{}:{} ", path, line),
+                    None => format!("{} ", path),
+                };
+                spans.push(Span::styled(
+                    location,
+                    Style::default().fg(Color::Magenta).underlined(),
+                ));
+            }
+            let mut lines = vec![Line::from(spans)];
+            lines.extend(
+                finding
+                    .message
+                    .lines()
+                    .map(|line| Line::raw(format!("  {}", line))),
+            );
+            ListItem::new(lines)
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::TOP).title(format!(
+            " Findings ({}) - Enter to jump ",
+            results.findings.len()
+        )))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, chunks[1], &mut app.ai_finding_state);
+}
+
+/// Render outstanding change requests and unresolved threads for the Feedback tab.
+fn feedback_text(feedback: &gh::PrFeedback, dates: &DatesConfig) -> Text<'static> {
+    if feedback.change_requests.is_empty() && feedback.threads.is_empty() {
+        return Text::raw("No outstanding review feedback.");
+    }
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    if !feedback.change_requests.is_empty() {
+        lines.push(Line::styled(
+            "─── Changes Requested ───",
+            Style::default().fg(Color::Yellow).bold(),
+        ));
+        lines.push(Line::raw(""));
+        for request in &feedback.change_requests {
+            lines.push(Line::styled(
+                format!("@{}", request.author),
+                Style::default().fg(Color::Red).bold(),
+            ));
+            if request.body.trim().is_empty() {
+                lines.push(Line::styled(
+                    "  (no summary)",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            for body_line in request.body.lines() {
+                lines.push(Line::raw(format!("  {}", body_line)));
+            }
+            lines.push(Line::raw(""));
+        }
+    }
+
+    if !feedback.threads.is_empty() {
+        lines.push(Line::styled(
+            format!("─── Unresolved Threads ({}) ───", feedback.threads.len()),
+            Style::default().fg(Color::Yellow).bold(),
+        ));
+        lines.push(Line::raw(""));
+        for thread in &feedback.threads {
+            let location = match thread.line {
+                Some(line) => format!("📁 {}:{}", thread.path, line),
+                None => format!("📁 {}", thread.path),
+            };
+            let mut header = vec![Span::styled(
+                location,
+                Style::default().fg(Color::Magenta).bold(),
+            )];
+            if thread.is_outdated {
+                header.push(Span::styled(
+                    " (outdated)",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(header));
+
+            for (i, comment) in thread.comments.iter().enumerate() {
+                let prefix = if i == 0 { "" } else { "↳ " };
+                let date = comment
+                    .created_at
+                    .map(|d| format!(" ({})", dates::absolute(dates, &d)))
+                    .unwrap_or_default();
+                lines.push(Line::styled(
+                    format!("  {}@{}{}", prefix, comment.author, date),
+                    Style::default().fg(Color::Cyan).bold(),
+                ));
+                for body_line in comment.body.lines() {
+                    lines.push(Line::raw(format!("    {}", body_line)));
+                }
+            }
+            lines.push(Line::raw(""));
+        }
+    }
+
+    Text::from(lines)
+}
+
+fn draw_comment_input(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 8,
+        y: area.height / 3,
+        width: area.width * 3 / 4,
+        height: 5,
+    };
+
+    let title = match &app.reply_target {
+        Some(target) => format!(
+            " Reply on {} (Enter to submit, Tab for $EDITOR, Esc to cancel) ",
+            target.location
+        ),
+        None => " Add Comment (Enter to submit, Tab for $EDITOR, Esc to cancel) ".to_string(),
+    };
+    let input = Paragraph::new(app.input_buffer.as_str())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, popup_area);
+}
+
+fn draw_line_comment_input(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 8,
+        y: area.height / 3,
+        width: area.width * 3 / 4,
+        height: 6,
+    };
+
+    let title = if let Some(ctx) = &app.line_comment_ctx {
+        format!(
+            " Comment on {} (Enter to submit, Tab for $EDITOR, Esc to cancel) ",
+            ctx.location()
+        )
+    } else {
+        " Add Line Comment (Enter to submit, Tab for $EDITOR, Esc to cancel) ".to_string()
+    };
+
+    let input = Paragraph::new(app.input_buffer.as_str())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, popup_area);
+}
+
+fn draw_confirm_dialog(frame: &mut Frame, app: &App) {
+    let pr = match app.selected_pr() {
+        Some(pr) => pr,
+        None => return,
+    };
+
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 6,
+        y: area.height / 3,
+        width: area.width * 2 / 3,
+        height: 9,
+    };
+
+    let label = if app.checklist_review_body().is_some() {
+        "  Message (the checklist summary follows it):"
+    } else {
+        "  Message:"
+    };
+    let message = if app.editing_approve_message {
+        Line::from(format!("  > {}_", app.input_buffer))
+    } else if app.input_buffer.is_empty() {
+        Line::from(Span::styled(
+            "  > none (M to add one)",
+            Style::default().fg(Color::DarkGray),
+        ))
+    } else {
+        Line::from(format!("  > {}", app.input_buffer))
+    };
+    let buttons = if app.editing_approve_message {
+        Line::from(vec![
+            Span::styled("  [Enter]", Style::default().fg(Color::Green).bold()),
+            Span::raw(" Approve    "),
+            Span::styled("[Esc]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw(" Done editing"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("  [Y]", Style::default().fg(Color::Green).bold()),
+            Span::raw(" Yes    "),
+            Span::styled("[N]", Style::default().fg(Color::Red).bold()),
+            Span::raw(" No    "),
+            Span::styled("[M]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw(" Message"),
+        ])
+    };
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Approve "),
+            Span::styled(
+                format!("[{}] #{}", pr.repo_name, pr.number),
+                Style::default().fg(Color::Cyan).bold(),
+            ),
+            Span::raw("?"),
+        ]),
+        Line::from(""),
+        Line::from(label),
+        message,
+        Line::from(""),
+        buttons,
+    ];
+
+    let dialog = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Confirm Approval ")
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(dialog, popup_area);
+}
+
+fn draw_snooze_dialog(frame: &mut Frame, app: &App) {
+    let pr = match app.selected_pr() {
+        Some(pr) => pr,
+        None => return,
+    };
+
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 6,
+        y: area.height / 3,
+        width: area.width * 2 / 3,
+        height: 8,
+    };
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Snooze "),
+            Span::styled(
+                format!("[{}] #{}", pr.repo_name, pr.number),
+                Style::default().fg(Color::Cyan).bold(),
+            ),
+            Span::raw(" until:"),
+        ]),
+    ];
+    for (idx, duration) in SnoozeDuration::ALL.iter().enumerate() {
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("  [{}]", idx + 1),
+                Style::default().fg(Color::Green).bold(),
+            ),
+            Span::raw(format!(" {}", duration.label())),
+        ]));
+    }
+
+    let dialog = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Snooze PR (Esc to cancel) ")
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(dialog, popup_area);
+}
+
+fn draw_global_search_input(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(3),
+        width: area.width,
+        height: 3,
+    };
+    let diffs_hint = if app.global_search_diffs {
+        " [x] diffs  Tab: titles/bodies only "
+    } else {
+        " [ ] diffs  Tab: also search diffs "
+    };
+    let input = Paragraph::new(format!("s/{}", app.input_buffer)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Search all PRs in the list (Enter to find, Esc to cancel) ")
+            .title_bottom(Line::from(diffs_hint).right_aligned())
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, popup_area);
+}
+
+fn draw_global_results(frame: &mut Frame, app: &mut App) {
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 10,
+        y: area.height / 8,
+        width: area.width * 4 / 5,
+        height: area.height * 3 / 4,
+    };
+
+    let items = if app.global_matches.is_empty() {
+        let label = if app.global_search_pending > 0 {
+            "Searching diffs..."
+        } else {
+            "No matches"
+        };
+        vec![ListItem::new(Span::styled(
+            label,
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.global_matches
+            .iter()
+            .map(|found| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("[{}] #{} ", found.repo, found.number),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(
+                        format!("{} ", found.location_label()),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::raw(found.snippet.clone()),
+                ]))
+            })
+            .collect()
+    };
+    let progress = if app.global_search_pending > 0 {
+        format!(", {} diffs left", app.global_search_pending)
+    } else {
+        String::new()
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " Matches for '{}' ({}{}) ",
+                    app.global_search_term,
+                    app.global_matches.len(),
+                    progress
+                ))
+                .title_bottom(" Enter: open | s: new search | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut app.global_match_state);
+}
+
+fn draw_group_dialog(frame: &mut Frame, app: &App) {
+    let Some(first) = app.dependency_group.first() else {
+        return;
+    };
+    let package = deps::parse_title(&first.title)
+        .map(|update| update.package)
+        .unwrap_or_else(|| first.title.clone());
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Approve and enable auto-merge for "),
+            Span::styled(
+                format!("{} PR(s)", app.dependency_group.len()),
+                Style::default().fg(Color::Cyan).bold(),
+            ),
+            Span::raw(" updating "),
+            Span::styled(package, Style::default().fg(Color::Cyan).bold()),
+            Span::raw("?"),
+        ]),
+        Line::from(""),
+    ];
+    for pr in &app.dependency_group {
+        let versions = deps::parse_title(&pr.title)
+            .map(|update| update.versions())
+            .unwrap_or_default();
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("  {}#{}", pr.repo_name, pr.number),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(format!("  {}", versions)),
+        ]));
+    }
+
+    // Release notes repeat across repos bumping the same version; show each line once.
+    let mut notes: Vec<String> = Vec::new();
+    for pr in &app.dependency_group {
+        for line in deps::release_notes(&pr.body) {
+            if !notes.contains(&line) {
+                notes.push(line);
+            }
+        }
+    }
+    if !notes.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "  Notes:",
+            Style::default().fg(Color::DarkGray),
+        )));
+        text.extend(
+            notes
+                .into_iter()
+                .map(|line| Line::from(format!("    {}", line))),
+        );
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("  [Y]", Style::default().fg(Color::Green).bold()),
+        Span::raw(" Yes    "),
+        Span::styled("[N]", Style::default().fg(Color::Red).bold()),
+        Span::raw(" No"),
+    ]));
+
+    let area = frame.area();
+    let height = (text.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let popup_area = Rect {
+        x: area.width / 8,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width * 3 / 4,
+        height,
+    };
+    let dialog = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Approve Dependency Group ")
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(dialog, popup_area);
+}
+
+fn draw_workload_overlay(frame: &mut Frame, app: &mut App) {
+    let pr = match app.selected_pr() {
+        Some(pr) => pr.clone(),
+        None => return,
+    };
+    let candidates = app.workload_candidates();
+
+    let area = frame.area();
+    let height = (candidates.len().max(1) as u16 + 4).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width / 4,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width / 2,
+        height,
+    };
+
+    let items = if candidates.is_empty() {
+        let label = if app.loading_review_queues {
+            "Loading review queues..."
+        } else {
+            "No teammates besides the author"
+        };
+        vec![ListItem::new(Span::styled(
+            label,
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        candidates
+            .iter()
+            .enumerate()
+            .map(|(idx, queue)| {
+                let mut spans = vec![
+                    Span::styled(
+                        format!("@{:<20}", queue.login),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::raw(format!(" {:>3} open", queue.open_requests)),
+                ];
+                if idx == 0 {
+                    spans.push(Span::styled(
+                        "  least loaded",
+                        Style::default().fg(Color::Cyan),
+                    ));
+                }
+                if pr.reviewers.iter().any(|r| r.login == queue.login) {
+                    spans.push(Span::styled(
+                        "  already requested",
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Review Queues for #{} ", pr.number))
+                .title_bottom(" Enter: request review | R: recount | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut app.workload_state);
+}
+
+fn draw_ai_choice_overlay(frame: &mut Frame, app: &mut App) {
+    let Some(number) = app.selected_pr().map(|pr| pr.number) else {
+        return;
+    };
+    let items: Vec<ListItem> = app
+        .ai_choices
+        .iter()
+        .map(|(label, _)| ListItem::new(label.clone()))
+        .collect();
+
+    let area = frame.area();
+    let height = (items.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width / 4,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width / 2,
+        height,
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Re-run review of #{} with ", number))
+                .title_bottom(" Enter: launch | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut app.ai_choice_state);
+}
+
+fn draw_view_picker_overlay(frame: &mut Frame, app: &mut App) {
+    let active = app.active_view.as_ref().map(|view| view.name.as_str());
+    let mark = |is_active: bool| if is_active { "● " } else { "  " };
+    let items: Vec<ListItem> = std::iter::once(ListItem::new(format!(
+        "{}All PRs (no view)",
+        mark(active.is_none())
+    )))
+    .chain(app.views.iter().map(|view| {
+        ListItem::new(format!(
+            "{}{}",
+            mark(active == Some(view.name.as_str())),
+            view.name
+        ))
+    }))
+    .collect();
+
+    let area = frame.area();
+    let height = (items.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width / 4,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width / 2,
+        height,
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Views ")
+                .title_bottom(" Enter: apply | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut app.view_state);
+}
+
+fn draw_milestone_overlay(frame: &mut Frame, app: &mut App) {
+    let Some(number) = app.selected_pr().map(|pr| pr.number) else {
+        return;
+    };
+    let current = app
+        .triage_cache
+        .as_ref()
+        .and_then(|triage| triage.milestone.clone());
+
+    let items: Vec<ListItem> = match &app.milestone_choices {
+        None => vec![ListItem::new(Span::styled(
+            "Loading milestones...",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        Some(choices) => choices
+            .iter()
+            .map(|choice| {
+                let mut spans = vec![match choice {
+                    Some(title) => Span::raw(title.clone()),
+                    None => Span::styled("No milestone", Style::default().fg(Color::DarkGray)),
+                }];
+                if *choice == current {
+                    spans.push(Span::styled("  current", Style::default().fg(Color::Cyan)));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect(),
+    };
+
+    let area = frame.area();
+    let height = (items.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width / 4,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width / 2,
+        height,
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Milestone for #{} ", number))
+                .title_bottom(" Enter: set | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut app.milestone_state);
+}
+
+fn draw_labels_overlay(frame: &mut Frame, app: &mut App) {
+    let Some(number) = app.selected_pr().map(|pr| pr.number) else {
+        return;
+    };
+
+    let items: Vec<ListItem> = match &app.label_choices {
+        None => vec![ListItem::new(Span::styled(
+            "Loading labels...",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        Some(choices) if choices.is_empty() => vec![ListItem::new(Span::styled(
+            "This repo has no labels",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        Some(choices) => choices
+            .iter()
+            .map(|label| {
+                let checked = app.label_picks.contains(&label.name);
+                ListItem::new(Line::from(vec![
+                    Span::raw(if checked { "[x] " } else { "[ ] " }),
+                    Span::styled(label.name.clone(), Style::default().fg(label_color(label))),
+                ]))
+            })
+            .collect(),
+    };
+
+    let area = frame.area();
+    let height = (items.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width / 4,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width / 2,
+        height,
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Labels for #{} ", number))
+                .title_bottom(" Space: toggle | Enter: apply | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut app.label_state);
+}
+
+fn draw_reviewers_overlay(frame: &mut Frame, app: &mut App) {
+    let Some(number) = app.selected_pr().map(|pr| pr.number) else {
+        return;
+    };
+
+    let rows = app.reviewer_rows();
+    let items: Vec<ListItem> = if rows.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No reviewers yet - type a login to request one",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        rows.iter()
+            .map(|row| {
+                let (symbol, color, note) = match row.state {
+                    Some(gh::ReviewerState::Pending) => ("○", Color::Yellow, "requested"),
+                    Some(state) if row.stale => {
+                        let (symbol, color) = reviewer_symbol(state);
+                        (symbol, color, "reviewed an older commit, Enter: re-request")
+                    }
+                    Some(state) => {
+                        let (symbol, color) = reviewer_symbol(state);
+                        (symbol, color, "Enter: re-request")
+                    }
+                    None => (" ", Color::Reset, "Enter: request"),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} @{}", symbol, row.login),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(format!("  {}", note), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect()
+    };
+
+    let area = frame.area();
+    let height = (items.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width / 4,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width / 2,
+        height,
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " Reviewers of #{}: @{}_ ",
+                    number, app.reviewer_query
+                ))
+                .title_bottom(" type: filter or login | ↑/↓: select | Enter: request | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    if rows.is_empty() {
+        app.reviewer_state.select(None);
+    }
+    frame.render_stateful_widget(list, popup_area, &mut app.reviewer_state);
+}
+
+/// The label's GitHub color, or magenta when it has none.
+fn label_color(label: &gh::Label) -> Color {
+    let channel = |at: usize| {
+        label
+            .color
+            .get(at..at + 2)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+    };
+    match (label.color.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Color::Rgb(r, g, b),
+        _ => Color::Magenta,
+    }
+}
+
+/// A space-led chip per label, for list rows and the detail header.
+fn label_spans(labels: &[gh::Label]) -> Vec<Span<'static>> {
+    labels
+        .iter()
+        .map(|label| {
+            Span::styled(
+                format!(" {}", label.name),
+                Style::default().fg(label_color(label)),
+            )
+        })
+        .collect()
+}
+
+/// Whether I approved `pr`: this session, or as my latest review once its details are loaded.
+fn approved_by(pr: &PullRequest, approved_prs: &BTreeSet<String>, username: &str) -> bool {
+    approved_prs.contains(&pr_key(pr))
+        || pr.reviewers.iter().any(|reviewer| {
+            reviewer.login.eq_ignore_ascii_case(username)
+                && reviewer.state == gh::ReviewerState::Approved
+        })
+}
+
+/// Whether `pr` passes every term of the list filter; a key given more than once accepts any of
+/// its values.
+fn pr_matches_filter(pr: &PullRequest, filter: &ListFilter) -> bool {
+    let matches = |pattern: &String, text: &str| {
+        filters::wildcard_match(&pattern.to_ascii_lowercase(), &text.to_ascii_lowercase())
+    };
+    let any = |patterns: &[String], text: &str| {
+        patterns.is_empty() || patterns.iter().any(|pattern| matches(pattern, text))
+    };
+    let lines = pr.additions + pr.deletions;
+    any(&filter.repos, &pr.repo_name)
+        && any(&filter.authors, &pr.author)
+        && (filter.labels.is_empty()
+            || pr
+                .labels
+                .iter()
+                .any(|label| any(&filter.labels, &label.name)))
+        && filter.draft.is_none_or(|draft| pr.is_draft == draft)
+        && filter.below_lines.is_none_or(|limit| lines < limit)
+        && filter.above_lines.is_none_or(|limit| lines > limit)
+}
+
+/// Whether `pr` is in one of the view's repos and carries one of its labels (when it lists any).
+fn pr_in_view(pr: &PullRequest, view: &ViewConfig) -> bool {
+    let matches = |pattern: &String, text: &str| {
+        filters::wildcard_match(&pattern.to_ascii_lowercase(), &text.to_ascii_lowercase())
+    };
+    (view.repos.is_empty() || view.repos.iter().any(|repo| matches(repo, &pr.repo_name)))
+        && (view.labels.is_empty()
+            || view.labels.iter().any(|label| {
+                pr.labels
+                    .iter()
+                    .any(|pr_label| matches(label, &pr_label.name))
+            }))
+}
+
+/// Compare two PRs by the `list.order` rules: the first rule that tells them apart decides.
+fn compare_by_order_rules(
+    a: &PullRequest,
+    b: &PullRequest,
+    rules: &[ListOrderRule],
+    teammates: &[String],
+) -> std::cmp::Ordering {
+    let matches = |pattern: &str, text: &str| {
+        filters::wildcard_match(&pattern.to_ascii_lowercase(), &text.to_ascii_lowercase())
+    };
+    let size = |pr: &PullRequest| pr.additions + pr.deletions;
+    rules
+        .iter()
+        .map(|rule| match rule {
+            ListOrderRule::Label(pattern) => {
+                let labeled =
+                    |pr: &PullRequest| pr.labels.iter().any(|l| matches(pattern, &l.name));
+                labeled(b).cmp(&labeled(a))
+            }
+            ListOrderRule::Author(pattern) => {
+                matches(pattern, &b.author).cmp(&matches(pattern, &a.author))
+            }
+            ListOrderRule::Repo(pattern) => {
+                matches(pattern, &b.repo_name).cmp(&matches(pattern, &a.repo_name))
+            }
+            ListOrderRule::Teammates => {
+                let teammate = |pr: &PullRequest| {
+                    teammates
+                        .iter()
+                        .any(|login| login.eq_ignore_ascii_case(&pr.author))
+                };
+                teammate(b).cmp(&teammate(a))
+            }
+            ListOrderRule::Smallest => size(a).cmp(&size(b)),
+            ListOrderRule::Largest => size(b).cmp(&size(a)),
+            ListOrderRule::Oldest => a.created_at.cmp(&b.created_at),
+            ListOrderRule::Newest => b.created_at.cmp(&a.created_at),
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// `list.sort` order. `updated` is the order GitHub returns, so it and ties keep the list as
+/// fetched.
+fn compare_by_sort(a: &PullRequest, b: &PullRequest, sort: ListSort) -> std::cmp::Ordering {
+    let review_rank = |pr: &PullRequest| match pr.review_state {
+        ReviewState::Pending => 0,
+        ReviewState::ChangesRequested => 1,
+        ReviewState::Approved => 2,
+        ReviewState::Draft => 3,
+    };
+    match sort {
+        ListSort::Updated => std::cmp::Ordering::Equal,
+        ListSort::Created => b.created_at.cmp(&a.created_at),
+        ListSort::Size => (a.additions + a.deletions).cmp(&(b.additions + b.deletions)),
+        ListSort::Repo => a
+            .repo_name
+            .to_ascii_lowercase()
+            .cmp(&b.repo_name.to_ascii_lowercase()),
+        ListSort::ReviewState => review_rank(a).cmp(&review_rank(b)),
+    }
+}
+
+/// Configured teammates as bare logins.
+fn team_logins(team: &TeamConfig) -> Vec<String> {
+    team.members
+        .iter()
+        .map(|member| member.trim().trim_start_matches('@').to_string())
+        .filter(|login| !login.is_empty())
+        .collect()
+}
+
+fn draw_status_log(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 10,
+        y: area.height / 8,
+        width: area.width * 4 / 5,
+        height: area.height * 3 / 4,
+    };
+
+    // Newest first, wrapped so long error chains stay readable.
+    let lines: Vec<Line> = if app.status_log.is_empty() {
+        vec![Line::from(Span::styled(
+            "No messages yet",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.status_log
+            .iter()
+            .rev()
+            .map(|(at, msg)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", at.format("%H:%M:%S")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(msg.clone()),
+                ])
+            })
+            .collect()
+    };
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Messages ({}) ", app.status_log.len()))
+                .title_bottom(" j/k: scroll | Esc: close ")
+                .style(Style::default().fg(Color::Yellow)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((app.status_log_scroll, 0));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(para, popup_area);
+}
+
+fn draw_file_view(frame: &mut Frame, app: &App) {
+    let Some(view) = app.file_view.as_ref() else {
+        return;
+    };
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 20,
+        y: area.height / 20,
+        width: area.width * 9 / 10,
+        height: area.height * 9 / 10,
+    };
+
+    // Only the visible lines are highlighted; big files would stall the draw otherwise.
+    let extension = std::path::Path::new(&view.path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    let gutter = view.lines.len().max(1).to_string().len();
+    let visible = popup_area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = view
+        .lines
+        .iter()
+        .enumerate()
+        .skip(view.scroll)
+        .take(visible)
+        .map(|(i, text)| {
+            let number = i as u32 + 1;
+            let focused = view.focus == Some(number);
+            let marker = if focused && app.accessible {
+                '▶'
+            } else {
+                ' '
+            };
+            let mut spans = vec![Span::styled(
+                format!("{:>width$}{}", number, marker, width = gutter),
+                Style::default().fg(if focused {
+                    Color::Yellow
+                } else {
+                    Color::DarkGray
+                }),
+            )];
+            spans.extend(app.syntax_highlighter.highlight_line(text, extension));
+            let line = Line::from(spans);
+            if focused {
+                line.style(Style::default().bg(Color::Rgb(40, 40, 60)))
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    let para = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                " {} @ PR head ({} lines) ",
+                view.path,
+                view.lines.len()
+            ))
+            .title_bottom(" j/k: scroll | PgUp/PgDn: page | g/G: top/bottom | Esc: close ")
+            .style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(para, popup_area);
+}
+
+fn draw_checklist_overlay(frame: &mut Frame, app: &mut App) {
+    let items = match app.checklist_cache.as_deref() {
+        Some(items) => items,
+        None => return,
+    };
+
+    let area = frame.area();
+    let height = (items.len() as u16 + 4).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width / 6,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width * 2 / 3,
+        height,
+    };
+
+    let done = items.iter().filter(|item| item.checked).count();
+    let list_items = items
+        .iter()
+        .map(|item| {
+            let (mark, color) = if item.checked {
+                ("[x] ", Color::Green)
+            } else {
+                ("[ ] ", Color::Yellow)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(mark, Style::default().fg(color)),
+                Span::raw(item.text.clone()),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    let footer = if app.checklist_config.include_in_review {
+        " Space: toggle | Esc: close | included in approval "
+    } else {
+        " Space: toggle | Esc: close "
+    };
+    let list = List::new(list_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Review Checklist ({}/{}) ", done, items.len()))
+                .title_bottom(footer)
+                .style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut app.checklist_state);
+}
+
+/// Save `filter` as `list.filter` in the config file, returning the config as saved.
+fn save_list_config(update: impl FnOnce(&mut ListConfig)) -> Result<Option<Config>> {
+    // Tests drive the TUI with fake PRs; keep them away from the real config.
+    if cfg!(test) {
+        return Ok(None);
+    }
+    let mut cfg = config::load_config()?;
+    update(&mut cfg.list);
+    config::save_config(&cfg)?;
+    Ok(Some(cfg))
+}
+
+fn record_audit(entry: AuditEntry) {
+    // Tests drive the TUI with fake PRs; keep them out of the real audit log.
+    if cfg!(test) {
+        return;
+    }
+    if let Err(err) = audit::record(&entry) {
+        tracing::warn!(error = %err, "failed to write audit log");
+    }
+}
+
+fn draw_close_dialog(frame: &mut Frame, app: &App) {
+    let pr = match app.selected_pr() {
+        Some(pr) => pr,
+        None => return,
+    };
+
+    let area = frame.area();
+    let popup_area = Rect {
+        x: area.width / 6,
+        y: area.height / 3,
+        width: area.width * 2 / 3,
+        height: 9,
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Close "),
+            Span::styled(
+                format!("[{}] #{}", pr.repo_name, pr.number),
+                Style::default().fg(Color::Cyan).bold(),
+            ),
+            Span::raw("?"),
+        ]),
+        Line::from(""),
+        Line::from(match app.close_reason_idx {
+            Some(idx) => format!(
+                "  Reason {}/{} (Tab for next, edit to adjust):",
+                idx + 1,
+                app.confirm.close_reasons.len()
+            ),
+            None if app.confirm.close_reasons.is_empty() => "  Optional comment:".to_string(),
+            None => "  Optional comment (Tab for a reason template):".to_string(),
+        }),
+        Line::from(format!("  > {}", app.input_buffer)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  [Enter]", Style::default().fg(Color::Red).bold()),
+            Span::raw(" Close    "),
+            Span::styled("[Esc]", Style::default().fg(Color::Green).bold()),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let dialog = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Close PR ")
+            .style(Style::default().fg(Color::Red)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(dialog, popup_area);
+}
+
+fn draw_review_summary_dialog(frame: &mut Frame, app: &App) {
+    const LISTED: usize = 5;
+    let Some(pr) = app.selected_pr() else {
+        return;
+    };
+    let Some(draft) = app.pending_reviews.get(&pr_key(pr)) else {
+        return;
+    };
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Review "),
+            Span::styled(
+                format!("[{}] #{}", pr.repo_name, pr.number),
+                Style::default().fg(Color::Cyan).bold(),
+            ),
+            Span::raw(format!(": {} queued comment(s)", draft.comments.len())),
+        ]),
+    ];
+    for comment in draft.comments.iter().take(LISTED) {
+        let lines = match comment.start_line {
+            Some(start) => format!("{}-{}", start, comment.line),
+            None => comment.line.to_string(),
+        };
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("    {}:{} ", comment.path, lines),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(comment.body.lines().next().unwrap_or("").to_string()),
+        ]));
+    }
+    if draft.comments.len() > LISTED {
+        text.push(Line::from(Span::styled(
+            format!("    ... and {} more", draft.comments.len() - LISTED),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let events = [
+        (ReviewEvent::Comment, "Comment"),
+        (ReviewEvent::Approve, "Approve"),
+        (ReviewEvent::RequestChanges, "Request changes"),
+    ];
+    let mut event_spans = vec![Span::raw("  Submit as: ")];
+    for (event, label) in events {
+        event_spans.push(if event == app.review_event {
+            Span::styled(
+                format!("[{}]", label),
+                Style::default().fg(Color::Green).bold(),
+            )
+        } else {
+            Span::styled(format!(" {} ", label), Style::default().fg(Color::DarkGray))
+        });
+        event_spans.push(Span::raw(" "));
+    }
+    event_spans.push(Span::styled(
+        "(Tab to switch)",
+        Style::default().fg(Color::DarkGray),
+    ));
+    text.extend([
+        Line::from(""),
+        Line::from(event_spans),
+        Line::from("  Summary:"),
+        Line::from(format!("  > {}_", app.input_buffer)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  [Enter]", Style::default().fg(Color::Green).bold()),
+            Span::raw(" Submit    "),
+            Span::styled("[Esc]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw(" Keep drafting    "),
+            Span::styled("[Del]", Style::default().fg(Color::Red).bold()),
+            Span::raw(" Discard"),
+        ]),
+    ]);
+
+    let area = frame.area();
+    let height = (text.len() as u16 + 2).min(area.height);
+    let popup_area = Rect {
+        x: area.width / 8,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width * 3 / 4,
+        height,
+    };
+    let dialog = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Submit review ")
+            .style(Style::default().fg(Color::Green)),
+    );
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(dialog, popup_area);
+}
+
+/// The review threads on the focused diff line, in a box right under it, or above it when
+/// there is more room there. `area` is the inside of the diff's borders.
+fn draw_inline_review_threads(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(comments) = app.review_comments_cache.as_deref() else {
+        return;
+    };
+    let Some(row) = app
+        .diff_cursor
+        .checked_sub(app.scroll_offset)
+        .filter(|row| *row < area.height)
+    else {
+        return;
+    };
+    let mut lines = Vec::new();
+    for comment in app.focused_review_threads() {
+        if !lines.is_empty() {
+            lines.push(Line::raw(""));
+        }
+        lines.extend(review_thread_lines(app, comment, comments));
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    // Indented past the margin so the focused line's marker stays visible
+    let width = area.width.saturating_sub(2);
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let wanted = para.line_count(width.saturating_sub(2)) as u16 + 2;
+    let below = area.height - row - 1;
+    let above = row;
+    let (y, height) = if wanted <= below || below >= above {
+        (area.y + row + 1, wanted.min(below))
+    } else {
+        let height = wanted.min(above);
+        (area.y + row - height, height)
+    };
+    if height < 3 {
+        return;
+    }
+    let popup_area = Rect {
+        x: area.x + 2,
+        y,
+        width,
+        height,
+    };
+    let para = para.block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Review comments (Enter to hide) ")
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(para, popup_area);
+}
+
+/// Body lines of the squash commit message shown in the merge dialog
+const MERGE_MESSAGE_PREVIEW_LINES: usize = 6;
+
+fn draw_merge_dialog(frame: &mut Frame, app: &App) {
+    let pr = match app.selected_pr() {
+        Some(pr) => pr,
+        None => return,
+    };
+
+    let mut message_lines = Vec::new();
+    match &app.merge_message {
+        Some(message) => {
+            message_lines.push(Line::from(
+                "  Will squash with this message if allowed, otherwise regular merge:",
+            ));
+            message_lines.push(Line::from(Span::styled(
+                format!("    {}", message.title),
+                Style::default().bold(),
+            )));
+            let body: Vec<&str> = message.body.lines().collect();
+            for line in body.iter().take(MERGE_MESSAGE_PREVIEW_LINES) {
+                message_lines.push(Line::from(Span::styled(
+                    format!("    {}", line),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            if body.len() > MERGE_MESSAGE_PREVIEW_LINES {
+                message_lines.push(Line::from(Span::styled(
+                    format!(
+                        "    … {} more lines",
+                        body.len() - MERGE_MESSAGE_PREVIEW_LINES
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+        None => message_lines.push(Line::from(
+            "  Will squash if allowed, otherwise regular merge.",
+        )),
+    }
+
+    let protection = match &app.merge_protection {
+        Some(protection) => Line::from(Span::styled(
+            format!("  Protection: {}", protection.summary()),
+            Style::default().fg(Color::DarkGray),
+        )),
+        None => Line::from(Span::styled(
+            "  Protection: none on base branch",
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Merge "),
+            Span::styled(
+                format!("[{}] #{}", pr.repo_name, pr.number),
+                Style::default().fg(Color::Cyan).bold(),
+            ),
+            Span::raw("?"),
+        ]),
+        Line::from(""),
+    ];
+    text.extend(message_lines);
+    text.extend([
+        Line::from("  Branch will be deleted after merge."),
+        protection,
+        Line::from(""),
+    ]);
+    if !app.merge_unmet_checks.is_empty() {
+        text.push(Line::from(Span::styled(
+            "  Cannot merge: required checks have not passed",
+            Style::default().fg(Color::Red).bold(),
+        )));
+        for unmet in &app.merge_unmet_checks {
+            text.push(Line::from(Span::styled(
+                if app.accessible {
+                    format!("    {}", unmet)
+                } else {
+                    format!("    ✗ {}", unmet)
+                },
+                Style::default().fg(Color::Red),
+            )));
+        }
+        text.push(Line::from(
+            "  GitHub rejects the merge until every required check passes.",
+        ));
+        text.push(Line::from(""));
+    }
+    let mut buttons = if !app.merge_unmet_checks.is_empty() {
+        vec![
+            Span::styled("  [Esc]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw(" Close"),
+        ]
+    } else if app.merge_requires_typed {
+        vec![
+            Span::raw(format!("  Type {} to confirm: ", pr.number)),
+            Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Yellow).bold(),
+            ),
+            Span::raw("   "),
+            Span::styled("[Esc]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw(" Cancel"),
+        ]
+    } else {
+        vec![
+            Span::styled("  [y/Enter]", Style::default().fg(Color::Green).bold()),
+            Span::raw(" Merge    "),
+            Span::styled("[n/Esc]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw(" Cancel"),
+        ]
+    };
+    if app.merge_message.is_some() && app.merge_unmet_checks.is_empty() {
+        buttons.extend([
+            Span::raw("   "),
+            Span::styled("[Tab]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" Edit message"),
+        ]);
+    }
+    text.push(Line::from(buttons));
+
+    let area = frame.area();
+    let height = (text.len() as u16 + 2).min(area.height);
+    let popup_area = Rect {
+        x: area.width / 6,
+        y: area.height.saturating_sub(height) / 2,
+        width: area.width * 2 / 3,
+        height,
+    };
+    let dialog = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Merge PR ")
+            .style(Style::default().fg(Color::Green)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(dialog, popup_area);
+}
+
+fn draw_search_input(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    // Draw at bottom of screen like vim
+    let popup_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(3),
+        width: area.width,
+        height: 3,
+    };
+
+    let (prefix, title) = match app.input_mode {
+        InputMode::ListSearch => ("/", " Search PR list (Enter to find, Esc to cancel) "),
+        InputMode::Search => match app.search_scope {
+            SearchScope::Diff => ("/", " Search diff (Enter to find, Esc to cancel) "),
+            SearchScope::TreeFileNames => {
+                ("/", " Search file names (Enter to find, Esc to cancel) ")
+            }
+            SearchScope::TreeContents => {
+                ("?", " Search file contents (Enter to find, Esc to cancel) ")
+            }
+        },
+        _ => ("/", " Search (Enter to find, Esc to cancel) "),
+    };
+
+    let case_hint = if app.search_case_sensitive {
+        " [Aa] Tab: ignore case | ↑/↓: history "
+    } else {
+        " [aa] Tab: match case | ↑/↓: history "
+    };
+    let input = Paragraph::new(format!("{}{}", prefix, app.input_buffer)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_bottom(Line::from(case_hint).right_aligned())
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, popup_area);
+}
+
+fn draw_goto_input(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    // Draw at bottom of screen like vim
+    let popup_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(3),
+        width: area.width,
+        height: 3,
+    };
+
+    let input = Paragraph::new(format!(":{}", app.input_buffer)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Go to line, or :only <glob> [!<glob>] to filter files (Esc to cancel) ")
+            .style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, popup_area);
+}
+
+fn draw_list_filter_input(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(3),
+        width: area.width,
+        height: 3,
+    };
+
+    let input = Paragraph::new(format!("f {}", app.input_buffer)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Filter the list (Enter to apply and save as default, empty to clear, Esc to cancel) ")
+            .title_bottom(
+                Line::from(" repo:<pattern> author:<login> label:<name> draft:yes|no size:<N|>N ")
+                    .right_aligned(),
+            )
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, popup_area);
+}
+
+pub fn run(
+    cfg: &Config,
+    repos_root: PathBuf,
+    username: String,
+    include_drafts: bool,
+    mode: AppMode,
+    read_only: bool,
+    view: Option<ViewConfig>,
+) -> Result<()> {
+    // Setup terminal
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    crossterm::execute!(
+        stdout,
+        crossterm::terminal::EnterAlternateScreen // Mouse capture disabled to allow text selection in terminal
+    )?;
+    let backend = ratatui::backend::CrosstermBackend::new(stdout);
+    let mut terminal = ratatui::Terminal::new(backend)?;
+
+    let mut app = App::new(cfg, repos_root, username, include_drafts, mode);
+    app.read_only_forced = read_only;
+    app.read_only |= read_only;
+    if view.is_some() {
+        app.mode = app.activate_view(view);
+    }
+    if let Some(warning) = gh_version::startup_warning() {
+        app.set_status(warning);
+    }
+
+    // Start fetching PRs immediately in background
+    app.refresh();
+
+    // Main loop
+    loop {
+        // Force full terminal clear when needed
+        if app.needs_clear {
+            terminal.clear()?;
+            app.needs_clear = false;
+            app.needs_redraw = true;
+        }
+
+        // Auto-dismiss status messages after timeout
+        if app.check_status_timeout() {
+            app.needs_redraw = true;
+        }
+
+        if app.needs_redraw {
+            terminal.draw(|f| draw(f, &mut app))?;
+            app.needs_redraw = false;
+        }
+
+        app.handle_event()?;
+        if let Some(target) = app.take_pending_agent_attach_target() {
+            let attach_result = suspended(&mut terminal, || agent::switch_or_attach(&target))?;
+            app.needs_clear = true;
+            if let Err(err) = attach_result {
+                app.set_status(format!("Failed to attach agent: {:#}", err));
+            }
+        }
+        if let Some(launch) = app.take_pending_editor_launch() {
+            let editor_result = suspended(&mut terminal, || {
+                editor::open(&app.editor, &launch.worktree, &launch.file, launch.line)
+            })?;
+            app.needs_clear = true;
+            match editor_result {
+                Ok(()) => app.set_status(format!(
+                    "Opened {}:{} in {}",
+                    launch.file,
+                    launch.line,
+                    launch.worktree.display()
+                )),
+                Err(err) => app.set_status(format!("Failed to open editor: {:#}", err)),
+            }
+        }
+        if let Some(draft) = app.take_pending_comment_draft() {
+            let text = draft.text.clone();
+            let composed = suspended(&mut terminal, || editor::compose(&text))?;
+            app.needs_clear = true;
+            app.finish_comment_draft(draft, composed);
+        }
+        if let Some(text) = app.take_pending_merge_message() {
+            let composed = suspended(&mut terminal, || editor::compose(&text))?;
+            app.needs_clear = true;
+            app.finish_merge_message_edit(composed);
+        }
+
+        if app.should_quit {
+            break;
+        }
+    }
+
+    // Restore terminal
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+
+    for (key, draft) in &app.pending_reviews {
+        if draft.comments.is_empty() && draft.body.trim().is_empty() {
+            continue;
+        }
+        match save_pending_review(key, draft) {
+            Ok(path) => println!(
+                "Unsubmitted review on {} saved to {}; `reviewer draft import {}` turns it into a pending review on GitHub",
+                key,
+                path.display(),
+                key
+            ),
+            Err(err) => eprintln!("Failed to save unsubmitted review on {}: {:#}", key, err),
+        }
+    }
+
+    match session_summary::write_since(app.session_started_at) {
+        Ok(Some((path, _))) => println!("Session summary: {}", path.display()),
+        Ok(None) => {}
+        Err(err) => eprintln!("Failed to write session summary: {:#}", err),
+    }
+
+    Ok(())
+}
+
+/// Write a review still queued at exit where `reviewer draft import` looks for it.
+fn save_pending_review(key: &str, draft: &DraftReview) -> Result<PathBuf> {
+    let (repo, number) = key
+        .rsplit_once('#')
+        .and_then(|(repo, number)| Some((repo, number.parse::<u64>().ok()?)))
+        .with_context(|| format!("Unexpected PR key {}", key))?;
+    let path = drafts::default_path(repo, number);
+    if let Some(parent) = path.parent() {
+        private_fs::create_dir_all(parent)?;
+    }
+    private_fs::write_atomic(&path, draft.to_markdown(repo, number))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Pipe `text` into the platform clipboard command.
+fn copy_to_clipboard(text: &str) -> io::Result<std::process::ExitStatus> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("pbcopy");
+
+    #[cfg(target_os = "linux")]
+    let mut command = {
+        let mut command = std::process::Command::new("xclip");
+        command.args(["-selection", "clipboard"]);
+        command
+    };
+
+    #[cfg(target_os = "windows")]
+    let mut command = std::process::Command::new("clip");
+
+    command
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            if let Some(stdin) = child.stdin.as_mut() {
+                stdin.write_all(text.as_bytes())?;
+            }
+            child.wait()
+        })
+}
+
+/// Hand the terminal to `f` (tmux, an editor) and take it back afterwards.
+fn suspended<B: ratatui::backend::Backend + io::Write, T>(
+    terminal: &mut ratatui::Terminal<B>,
+    f: impl FnOnce() -> T,
+) -> Result<T> {
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+
+    let result = f();
+
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::EnterAlternateScreen
+    )?;
+    crossterm::terminal::enable_raw_mode()?;
+    terminal.clear()?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tests use output patterns captured from real `delta` CLI output
+
+    #[test]
+    fn test_parse_delta_unified_mode() {
+        // Real unified mode format from: delta --line-numbers
+        // Format: " <old>⋮ <new>│<content>"
+        let raw_diff = r#"diff --git a/src/gh.rs b/src/gh.rs
+--- a/src/gh.rs
++++ b/src/gh.rs
+@@ -303,7 +303,8 @@ pub fn add_pr_comment
+ }
+
+ /// Add a line-level comment
+-pub fn add_line_comment(old)
++/// side doc
++pub fn add_line_comment(new)"#;
+
+        // Captured from: git diff | delta --line-numbers | sed 's/\x1b\[[0-9;]*m//g'
+        let delta_output = r#"
+src/gh.rs
+────────────────────────────────────────────────────────────────────────────────
+
+────────────────────────────────────────────────────────────────────────────┐
+303: pub fn add_pr_comment(pr: &PullRequest, comment: &str) -> Result<()> { │
+────────────────────────────────────────────────────────────────────────────┘
+ 303⋮ 303│}
+ 304⋮ 304│
+ 305⋮ 305│/// Add a line-level comment to a PR using the reviews API
+ 306⋮    │pub fn add_line_comment(pr: &PullRequest, file_path: &str, line: u32, comment: &str) -> Result<()> {
+    ⋮ 306│/// `side` should be "LEFT" for removed lines (old file) or "RIGHT" for added/context lines (new file)
+    ⋮ 307│pub fn add_line_comment(pr: &PullRequest, file_path: &str, line: u32, side: &str, comment: &str) -> Result<()> {
+ 307⋮ 308│    // Use the reviews endpoint with a comments array"#;
+
+        let result = parse_delta_output(delta_output, raw_diff);
+
+        // Line 0: empty
+        assert_eq!(result[0].file_path, None);
+
+        // Line 1: file header "src/gh.rs"
+        assert_eq!(result[1].file_path.as_deref(), Some("src/gh.rs"));
+
+        // Line 5: hunk header "303: pub fn..."
+        assert_eq!(result[5].old_line_number, Some(303));
+        assert_eq!(result[5].new_line_number, Some(303));
+
+        // Line 7: " 303⋮ 303│" - context line
+        assert_eq!(result[7].file_path.as_deref(), Some("src/gh.rs"));
+        assert_eq!(result[7].old_line_number, Some(303));
+        assert_eq!(result[7].new_line_number, Some(303));
+
+        // Line 10: " 306⋮    │" - removed line (has old, no new)
+        assert_eq!(result[10].old_line_number, Some(306));
+        assert_eq!(result[10].new_line_number, None);
+
+        // Line 11: "    ⋮ 306│" - added line (no old, has new)
+        assert_eq!(result[11].old_line_number, None);
+        assert_eq!(result[11].new_line_number, Some(306));
+
+        // Line 13: " 307⋮ 308│" - context line (shifted)
+        assert_eq!(result[13].old_line_number, Some(307));
+        assert_eq!(result[13].new_line_number, Some(308));
+    }
+
+    #[test]
+    fn test_parse_delta_side_by_side_mode() {
+        // Real side-by-side format from: delta --side-by-side --line-numbers
+        // Format: "│ <old>│<content>│ <new>│<content>"
+        let raw_diff = r#"diff --git a/src/gh.rs b/src/gh.rs
+--- a/src/gh.rs
++++ b/src/gh.rs
+@@ -303,4 +303,5 @@ pub fn add_pr_comment
+ }
+
++/// new comment
+ /// Add a line-level comment"#;
+
+        // Captured from: git diff | delta --side-by-side --line-numbers | sed 's/\x1b\[[0-9;]*m//g'
+        let delta_output = r#"
+src/gh.rs
+────────────────────────────────────────────────────────────────────────────────
+
+────────────────────────────────────────────────────────────────────────────┐
+303: pub fn add_pr_comment(pr: &PullRequest, comment: &str) -> Result<()> { │
+────────────────────────────────────────────────────────────────────────────┘
+│ 303│}                                 │ 303│}
+│ 304│                                  │ 304│
+│    │                                  │ 305│/// new comment
+│ 305│/// Add a line-level comment      │ 306│/// Add a line-level comment"#;
+
+        let result = parse_delta_output(delta_output, raw_diff);
+
+        // Line 1: file header
+        assert_eq!(result[1].file_path.as_deref(), Some("src/gh.rs"));
+
+        // Line 7: "│ 303│...│ 303│..." - context line
+        assert_eq!(result[7].file_path.as_deref(), Some("src/gh.rs"));
+        assert_eq!(result[7].old_line_number, Some(303));
+        assert_eq!(result[7].new_line_number, Some(303));
+
+        // Line 9: "│    │...│ 305│..." - added line (no old, has new)
+        assert_eq!(result[9].old_line_number, None);
+        assert_eq!(result[9].new_line_number, Some(305));
+
+        // Line 10: "│ 305│...│ 306│..." - context line (shifted)
+        assert_eq!(result[10].old_line_number, Some(305));
+        assert_eq!(result[10].new_line_number, Some(306));
+    }
+
+    #[test]
+    fn test_parse_delta_side_by_side_removed_line() {
+        // Side-by-side with removed lines
+        let raw_diff = r#"diff --git a/test.rs b/test.rs
+--- a/test.rs
++++ b/test.rs
+@@ -1,3 +1,2 @@
+ keep
+-removed
+ also keep"#;
+
+        let delta_output = r#"
+test.rs
+────────────────────────────────────────
+│   1│keep                              │   1│keep
+│   2│removed                           │    │
+│   3│also keep                         │   2│also keep"#;
+
+        let result = parse_delta_output(delta_output, raw_diff);
+
+        // Line 3: context line
+        assert_eq!(result[3].old_line_number, Some(1));
+        assert_eq!(result[3].new_line_number, Some(1));
+
+        // Line 4: removed line (has old, no new)
+        assert_eq!(result[4].old_line_number, Some(2));
+        assert_eq!(result[4].new_line_number, None);
+
+        // Line 5: context line (shifted)
+        assert_eq!(result[5].old_line_number, Some(3));
+        assert_eq!(result[5].new_line_number, Some(2));
+
+        // The selected column decides the comment side when both sides have a line.
+        assert_eq!(
+            delta_comment_target(&result[5], CommentSide::Left),
+            Some((3, CommentSide::Left))
+        );
+        assert_eq!(
+            delta_comment_target(&result[5], CommentSide::Right),
+            Some((2, CommentSide::Right))
+        );
+        assert_eq!(
+            delta_comment_target(&result[4], CommentSide::Right),
+            Some((2, CommentSide::Left))
+        );
+        assert_eq!(delta_comment_target(&result[1], CommentSide::Left), None);
+    }
+
+    #[test]
+    fn test_parse_delta_hunk_header() {
+        // Hunk header format: "<num>: <content>" inside box decorations
+        let raw_diff = r#"diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -50,2 +50,3 @@ fn helper() {
+     code();
++    more();
+}"#;
+
+        let delta_output = r#"
+src/lib.rs
+────────────────────────────────────────
+────────────────┐
+50: fn helper() { │
+────────────────┘
+ 50⋮ 50│    code();
+    ⋮ 51│    more();
+ 51⋮ 52│}"#;
+
+        let result = parse_delta_output(delta_output, raw_diff);
+
+        // Line 4: hunk header "50: fn helper()"
+        assert_eq!(result[4].file_path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(result[4].old_line_number, Some(50));
+        assert_eq!(result[4].new_line_number, Some(50));
+        assert!(result[4].hunk_header);
+
+        // Line 6: code line
+        assert_eq!(result[6].old_line_number, Some(50));
+        assert_eq!(result[6].new_line_number, Some(50));
+        assert!(!result[6].hunk_header);
+    }
+
+    #[test]
+    fn test_parse_delta_multiple_files() {
+        let raw_diff = r#"diff --git a/file1.rs b/file1.rs
+--- a/file1.rs
++++ b/file1.rs
+@@ -1 +1 @@
+-old1
++new1
+diff --git a/file2.rs b/file2.rs
+--- a/file2.rs
++++ b/file2.rs
+@@ -1 +1 @@
+-old2
++new2"#;
+
+        let delta_output = r#"
+file1.rs
+────────────────────────────────────────
+  1⋮  1│new1
+
+file2.rs
+────────────────────────────────────────
+  1⋮  1│new2"#;
+
+        let result = parse_delta_output(delta_output, raw_diff);
+
+        // Find first file's code line
+        let file1_line = result
+            .iter()
+            .find(|r| r.file_path.as_deref() == Some("file1.rs") && r.new_line_number.is_some());
+        assert!(file1_line.is_some());
+        assert_eq!(file1_line.unwrap().new_line_number, Some(1));
+
+        // Find second file's code line - should switch to file2.rs
+        let file2_line = result
+            .iter()
+            .find(|r| r.file_path.as_deref() == Some("file2.rs") && r.new_line_number.is_some());
+        assert!(file2_line.is_some());
+        assert_eq!(file2_line.unwrap().new_line_number, Some(1));
+    }
+
+    #[test]
+    fn test_parse_delta_decoration_lines() {
+        // Decoration lines (separators, box corners) should have no line numbers
+        let raw_diff = r#"diff --git a/test.rs b/test.rs
+--- a/test.rs
++++ b/test.rs
+@@ -1 +1 @@
+ code"#;
+
+        let delta_output = r#"
+test.rs
+────────────────────────────────────────
+
+────────────────┐
+1: fn test() {   │
+────────────────┘
+  1⋮  1│code"#;
+
+        let result = parse_delta_output(delta_output, raw_diff);
+
+        // Line 2: separator line (────...)
+        assert_eq!(result[2].old_line_number, None);
+        assert_eq!(result[2].new_line_number, None);
+
+        // Line 3: empty line
+        assert_eq!(result[3].old_line_number, None);
+        assert_eq!(result[3].new_line_number, None);
+
+        // Line 4: box top (────...┐)
+        assert_eq!(result[4].old_line_number, None);
+        assert_eq!(result[4].new_line_number, None);
+
+        // Line 6: box bottom (────...┘)
+        assert_eq!(result[6].old_line_number, None);
+        assert_eq!(result[6].new_line_number, None);
+
+        // Line 7: actual code line
+        assert_eq!(result[7].old_line_number, Some(1));
+        assert_eq!(result[7].new_line_number, Some(1));
+    }
+
+    #[test]
+    fn test_strip_ansi_codes() {
+        // Test ANSI code stripping with real delta escape sequences
+        let with_ansi = "\x1b[34msrc/main.rs\x1b[0m";
+        assert_eq!(strip_ansi_codes(with_ansi), "src/main.rs");
+
+        // Real delta output: colored line numbers
+        let complex = "\x1b[38;2;68;68;68m 303⋮ 303\x1b[34m│\x1b[0m}";
+        let stripped = strip_ansi_codes(complex);
+        assert!(stripped.contains("303"));
+        assert!(stripped.contains("⋮"));
+        assert!(stripped.contains("│"));
+    }
+
+    // ==================== Tests for parse_diff (non-delta built-in mode) ====================
+
+    #[test]
+    fn test_parse_diff_basic() {
+        let diff = r#"diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -10,4 +10,5 @@ fn main() {
+     let x = 1;
++    let y = 2;
+     println!("test");
+ }"#;
+
+        let result = parse_diff(diff);
+
+        // Line 0: diff --git header
+        assert_eq!(result[0].file_path.as_deref(), Some("src/main.rs"));
+        assert_eq!(result[0].line_type, DiffLineType::Header);
+
+        // Line 1-2: --- and +++ headers
+        assert_eq!(result[1].line_type, DiffLineType::Header);
+        assert_eq!(result[2].line_type, DiffLineType::Header);
+
+        // Line 3: @@ hunk header
+        assert_eq!(result[3].line_type, DiffLineType::Hunk);
+
+        // Line 4: context line "     let x = 1;"
+        assert_eq!(result[4].line_type, DiffLineType::Context);
+        assert_eq!(result[4].old_line_number, Some(10));
+        assert_eq!(result[4].line_number, Some(10));
+
+        // Line 5: added line "+    let y = 2;"
+        assert_eq!(result[5].line_type, DiffLineType::Added);
+        assert_eq!(result[5].old_line_number, None);
+        assert_eq!(result[5].line_number, Some(11));
+
+        // Line 6: context line "     println!..."
+        assert_eq!(result[6].line_type, DiffLineType::Context);
+        assert_eq!(result[6].old_line_number, Some(11));
+        assert_eq!(result[6].line_number, Some(12));
+    }
+
+    #[test]
+    fn test_parse_diff_removed_lines() {
+        let diff = r#"diff --git a/test.rs b/test.rs
+--- a/test.rs
++++ b/test.rs
+@@ -1,4 +1,3 @@
+ fn main() {
+-    let old = 1;
+     let keep = 2;
+ }"#;
+
+        let result = parse_diff(diff);
+
+        // Line 4: context "fn main()"
+        assert_eq!(result[4].line_type, DiffLineType::Context);
+        assert_eq!(result[4].old_line_number, Some(1));
+        assert_eq!(result[4].line_number, Some(1));
+
+        // Line 5: removed line (has old_line_number, no line_number)
+        assert_eq!(result[5].line_type, DiffLineType::Removed);
+        assert_eq!(result[5].old_line_number, Some(2));
+        assert_eq!(result[5].line_number, None);
+
+        // Line 6: context "let keep" - shifted in new file
+        assert_eq!(result[6].line_type, DiffLineType::Context);
+        assert_eq!(result[6].old_line_number, Some(3));
+        assert_eq!(result[6].line_number, Some(2));
+    }
+
+    #[test]
+    fn test_parse_diff_multiple_files() {
+        let diff = r#"diff --git a/file1.rs b/file1.rs
+--- a/file1.rs
++++ b/file1.rs
+@@ -1,2 +1,2 @@
+-old content
++new content
+diff --git a/file2.rs b/file2.rs
+--- a/file2.rs
++++ b/file2.rs
+@@ -5,2 +5,3 @@
+ existing
++added line"#;
+
+        let result = parse_diff(diff);
+
+        // First file
+        assert_eq!(result[0].file_path.as_deref(), Some("file1.rs"));
+        assert_eq!(result[4].file_path.as_deref(), Some("file1.rs"));
+        assert_eq!(result[4].line_type, DiffLineType::Removed);
+        assert_eq!(result[5].line_type, DiffLineType::Added);
+
+        // Second file - should switch file path
+        assert_eq!(result[6].file_path.as_deref(), Some("file2.rs"));
+        assert_eq!(result[6].line_type, DiffLineType::Header);
+
+        // Lines in second file start at line 5
+        assert_eq!(result[10].line_type, DiffLineType::Context);
+        assert_eq!(result[10].old_line_number, Some(5));
+        assert_eq!(result[11].line_type, DiffLineType::Added);
+        assert_eq!(result[11].line_number, Some(6));
+    }
+
+    #[test]
+    fn test_parse_diff_hunk_header_parsing() {
+        // Test various hunk header formats
+        let diff = r#"diff --git a/test.rs b/test.rs
+--- a/test.rs
++++ b/test.rs
+@@ -100,5 +200,10 @@ fn context_function() {
+ context line"#;
+
+        let result = parse_diff(diff);
+
+        // After hunk header, lines should start at correct numbers
+        // Old file: starts at 100, new file: starts at 200
+        assert_eq!(result[4].line_type, DiffLineType::Context);
+        assert_eq!(result[4].old_line_number, Some(100));
+        assert_eq!(result[4].line_number, Some(200));
+    }
+
+    #[test]
+    fn test_parse_diff_new_deleted_and_renamed_files() {
+        let diff = r#"diff --git a/new.rs b/new.rs
+new file mode 100644
+index 0000000..e69de29
+--- /dev/null
++++ b/new.rs
+@@ -0,0 +1 @@
++fn added() {}
+diff --git a/gone.rs b/gone.rs
+deleted file mode 100644
+--- a/gone.rs
++++ /dev/null
+@@ -1,2 +0,0 @@
+-fn gone() {}
+--- not a header
+diff --git a/old/name.rs b/new/name.rs
+similarity index 90%
+rename from old/name.rs
+rename to new/name.rs
+--- a/old/name.rs
++++ b/new/name.rs
+@@ -3 +3 @@
+-old
++new"#;
+
+        let result = parse_diff(diff);
+
+        // New file: the index line carries no line numbers, the added line is new line 1
+        assert_eq!(result[2].line_type, DiffLineType::Header);
+        assert_eq!(result[2].line_number, None);
+        assert_eq!(result[6].file_path.as_deref(), Some("new.rs"));
+        assert_eq!(result[6].line_type, DiffLineType::Added);
+        assert_eq!(result[6].line_number, Some(1));
+
+        // Deleted file: `+++ /dev/null` keeps the old path, `--- not a header` is removed line 2
+        assert_eq!(result[11].file_path.as_deref(), Some("gone.rs"));
+        assert_eq!(result[12].line_type, DiffLineType::Removed);
+        assert_eq!(result[12].old_line_number, Some(1));
+        assert_eq!(result[13].line_type, DiffLineType::Removed);
+        assert_eq!(result[13].old_line_number, Some(2));
+        assert_eq!(result[13].file_path.as_deref(), Some("gone.rs"));
+
+        // Rename: comments go against the new path on either side
+        assert_eq!(result[21].file_path.as_deref(), Some("new/name.rs"));
+        assert_eq!(result[21].old_line_number, Some(3));
+        assert_eq!(result[22].line_number, Some(3));
+    }
+
+    #[test]
+    fn test_parse_diff_no_newline_marker() {
+        // "\ No newline at end of file" should be treated as Other
+        let diff = r#"diff --git a/test.rs b/test.rs
+--- a/test.rs
++++ b/test.rs
+@@ -1,2 +1,2 @@
+-old
++new
+\ No newline at end of file"#;
+
+        let result = parse_diff(diff);
+
+        // The backslash line should be Other type
+        assert_eq!(result[6].line_type, DiffLineType::Other);
+        assert_eq!(result[6].line_number, None);
+        assert_eq!(result[6].old_line_number, None);
+    }
+
+    #[test]
+    fn test_parse_diff_file_path_extraction() {
+        // Test file path extraction from various diff formats
+        let diff = r#"diff --git a/path/to/file.rs b/path/to/file.rs
+--- a/path/to/file.rs
++++ b/path/to/file.rs
+@@ -1 +1 @@
+-old
++new"#;
+
+        let result = parse_diff(diff);
+
+        // All lines should have the correct file path
+        for line in &result {
+            assert_eq!(line.file_path.as_deref(), Some("path/to/file.rs"));
+        }
+    }
+
+    #[test]
+    fn test_parse_diff_file_sections() {
+        let diff = r#"diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-old
++new
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1 @@
+-hello
++hello world
+diff --git a/notes b/old.md b/notes b/new.md
+rename from notes b/old.md
+rename to notes b/new.md"#;
+
+        let sections = parse_diff_file_sections(diff);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].path, "src/main.rs");
+        assert!(sections[0].diff.contains("@@ -1 +1 @@"));
+        assert_eq!(sections[1].path, "README.md");
+        assert!(sections[1].diff.contains("+hello world"));
+        assert_eq!(sections[2].path, "notes b/new.md");
+    }
+
+    #[test]
+    fn test_build_diff_tree_items_hierarchy() {
+        let sections = vec![
+            FileDiffSection {
+                path: "src/lib.rs".to_string(),
+                diff: String::new(),
+            },
+            FileDiffSection {
+                path: "src/utils/math.rs".to_string(),
+                diff: String::new(),
+            },
+            FileDiffSection {
+                path: "README.md".to_string(),
+                diff: String::new(),
+            },
+        ];
+
+        let items = build_diff_tree_items(&sections);
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+
+        assert_eq!(
+            labels,
+            vec!["src/", "  utils/", "    math.rs", "  lib.rs", "README.md"]
+        );
+        assert_eq!(items[2].file_path.as_deref(), Some("src/utils/math.rs"));
+        assert_eq!(items[3].file_path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(items[4].file_path.as_deref(), Some("README.md"));
+    }
+
+    #[test]
+    fn test_diff_path_filter_include_and_exclude() {
+        let filter = DiffPathFilter::parse("*.go !*_test.go").expect("filter should parse");
+        assert!(filter.matches("cmd/server/main.go"));
+        assert!(!filter.matches("cmd/server/main_test.go"));
+        assert!(!filter.matches("README.md"));
+        assert_eq!(filter.label(), "*.go !*_test.go");
+
+        let exclude_only = DiffPathFilter::parse("!Cargo.lock").expect("filter should parse");
+        assert!(exclude_only.matches("src/lib.rs"));
+        assert!(!exclude_only.matches("Cargo.lock"));
+
+        let dir = DiffPathFilter::parse("src/*").expect("filter should parse");
+        assert!(dir.matches("src/utils/math.rs"));
+        assert!(!dir.matches("docs/src/index.md"));
+
+        assert!(DiffPathFilter::parse("  ").is_none());
+    }
+
+    // ==================== Tests for search index helpers ====================
+
+    #[test]
+    fn test_advance_search_idx() {
+        // Test forward cycling through indices
+        assert_eq!(advance_search_idx(0, 5), 1);
+        assert_eq!(advance_search_idx(4, 5), 0); // wrap around
+        assert_eq!(advance_search_idx(0, 1), 0); // single element
+    }
+
+    #[test]
+    fn test_retreat_search_idx() {
+        // Test backward cycling through indices
+        assert_eq!(retreat_search_idx(1, 5), 0);
+        assert_eq!(retreat_search_idx(0, 5), 4); // wrap around
+        assert_eq!(retreat_search_idx(0, 1), 0); // single element
+    }
+
+    #[test]
+    fn test_format_search_status() {
+        assert_eq!(format_search_status(0, 5, "test"), "Match 1/5 for 'test'");
+        assert_eq!(format_search_status(4, 5, "foo"), "Match 5/5 for 'foo'");
+    }
+
+    fn make_test_pr(number: u64, title: &str, repo: &str, author: &str) -> PullRequest {
+        PullRequest {
+            number,
+            title: title.to_string(),
+            author: author.to_string(),
+            author_kind: Some("User".to_string()),
+            body: String::new(),
+            repo_path: PathBuf::from("/tmp/repo"),
+            repo_name: repo.to_string(),
+            url: String::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            additions: 0,
+            deletions: 0,
+            is_draft: false,
+            labels: Vec::new(),
+            base_branch: "main".to_string(),
+            head_branch: "feature".to_string(),
+            head_oid: String::new(),
+            mergeable: None,
+            review_state: ReviewState::Pending,
+            reviewers: Vec::new(),
+            details_loaded: true,
+        }
+    }
+
+    #[test]
+    fn test_pr_matches_list_query_matches_pr_number() {
+        let pr = make_test_pr(
+            12345,
+            "Improve cache invalidation",
+            "org/reviewer",
+            "daulet",
+        );
+
+        assert!(pr_matches_list_query(&pr, "12345", false));
+        assert!(pr_matches_list_query(&pr, "#12345", false));
+    }
+
+    #[test]
+    fn test_pr_matches_list_query_matches_title_repo_author() {
+        let pr = make_test_pr(777, "Fix daemon startup race", "org/reviewer", "alice");
+
+        assert!(pr_matches_list_query(&pr, "startup", false));
+        assert!(pr_matches_list_query(&pr, "org/reviewer", false));
+        assert!(pr_matches_list_query(&pr, "alice", false));
+        assert!(!pr_matches_list_query(&pr, "nonexistent", false));
+    }
+
+    #[test]
+    fn test_pr_matches_list_query_respects_case_sensitivity() {
+        let pr = make_test_pr(777, "Fix Daemon startup race", "org/reviewer", "alice");
 
-            let para = Paragraph::new(body)
-                .block(content_block)
-                .wrap(Wrap { trim: false })
-                .scroll((app.scroll_offset, 0));
-            frame.render_widget(para, chunks[2]);
-        }
+        assert!(pr_matches_list_query(&pr, "daemon", false));
+        assert!(!pr_matches_list_query(&pr, "daemon", true));
+        assert!(pr_matches_list_query(&pr, "Daemon", true));
     }
 
-    // Help - context-aware based on tab and mode
-    let help_text = if app.detail_tab == DetailTab::Diff && app.showing_large_diff_tree() {
-        " j/k: navigate files | /: name search | ?: content search | Enter: open file diff | t: hide tree | q: back"
-    } else if app.detail_tab == DetailTab::Diff && app.showing_single_file_diff() {
-        match app.mode {
-            AppMode::MyPrs => {
-                " j/k: scroll | Esc: file tree | t: full diff | /: search | D: delta | m: merge | o: browser | y: copy | q: back"
-            }
-            AppMode::Review | AppMode::Watching => {
-                " j/k: scroll | Esc: file tree | t: full diff | /: search | c: comment | D: delta | a: approve | o: browser | y: copy | q: back"
-            }
+    #[test]
+    fn test_search_history_recalls_and_dedups() {
+        let mut history = SearchHistory::default();
+        history.record("foo");
+        history.record("bar");
+        history.record("foo");
+
+        assert_eq!(history.older().as_deref(), Some("foo"));
+        assert_eq!(history.older().as_deref(), Some("bar"));
+        assert_eq!(history.older().as_deref(), Some("bar"));
+        assert_eq!(history.newer().as_deref(), Some("foo"));
+        assert_eq!(history.newer().as_deref(), Some(""));
+        assert_eq!(history.newer(), None);
+    }
+
+    /// Canned GitHub responses for driving the TUI without `gh`.
+    #[derive(Default)]
+    struct FakeGh {
+        prs: Vec<PullRequest>,
+        diff: String,
+        merge_blocked: Option<String>,
+        action_error: Option<String>,
+        discussions: Vec<(u64, gh::Discussion)>,
+        triage: gh::PrTriage,
+        read_only: bool,
+        review_comments: Vec<ReviewComment>,
+        review_threads: BTreeMap<u64, gh::ReviewThreadState>,
+        file_patch: Option<String>, // What GitHub's files API shows, if not the PR diff
+        files: Vec<gh::ChangedFile>,
+        commits: Vec<gh::PrCommit>,
+        checks: Vec<gh::CheckStatus>,
+        protection: Option<gh::BranchProtection>, // Base branch rules the merge check reports
+        fetch_errors: std::sync::Mutex<Vec<gh::RepoFetchError>>, // Repos the list fetch fails for
+        gitattributes: Option<String>,
+        diff_fetches: std::sync::atomic::AtomicUsize, // get_pr_diff plus get_gitattributes calls
+        scan_progress: Option<crate::repos::ScanProgress>,
+        calls: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl FakeGh {
+        fn record(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
         }
-    } else {
-        match (app.detail_tab, app.mode) {
-            (DetailTab::Agent, _) => {
-                " Tab: tabs | j/k: scroll | R: refresh agent | Enter/A: attach | r: launch | q: back"
-            }
-            (DetailTab::Diff, AppMode::MyPrs) => {
-                " j/k: scroll | /: search | t: tree | D: delta | m: merge | o: browser | y: copy | q: back"
-            }
-            (DetailTab::Diff, AppMode::Review | AppMode::Watching) => {
-                " j/k: scroll | /: search | t: tree | c: comment | D: delta | a: approve | o: browser | y: copy | q: back"
-            }
-            (_, AppMode::MyPrs) => {
-                " Tab: tabs | j/k: scroll | m: merge | o: browser | y: copy | q: back"
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn action_result(&self) -> Result<()> {
+            match &self.action_error {
+                Some(cause) => Err(anyhow::anyhow!(cause.clone()).context("gh api failed")),
+                None => Ok(()),
             }
-            (_, AppMode::Review | AppMode::Watching) => {
-                " Tab: tabs | j/k: scroll | a: approve | o: browser | y: copy | q: back"
+        }
+    }
+
+    impl GhClient for FakeGh {
+        fn supports(&self, _capability: Capability) -> bool {
+            true
+        }
+
+        fn fetch_page(&self, query: &ListQuery, cursor: Option<&str>) -> gh::PullRequestPage {
+            gh::PullRequestPage {
+                prs: if cursor.is_none() {
+                    self.prs
+                        .iter()
+                        .filter(|pr| query.include_drafts || !pr.is_draft)
+                        .cloned()
+                        .collect()
+                } else {
+                    Vec::new()
+                },
+                errors: self.fetch_errors.lock().unwrap().clone(),
+                ..Default::default()
             }
         }
-    };
-    let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::DarkGray))
-        .block(Block::default().borders(Borders::ALL).title(" Help "));
-    frame.render_widget(help, chunks[3]);
-}
 
-fn draw_comment_input(frame: &mut Frame, app: &App) {
-    let area = frame.area();
-    let popup_area = Rect {
-        x: area.width / 8,
-        y: area.height / 3,
-        width: area.width * 3 / 4,
-        height: 5,
-    };
+        fn scan_progress(&self) -> Option<crate::repos::ScanProgress> {
+            self.scan_progress
+        }
 
-    let input = Paragraph::new(app.input_buffer.as_str())
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Add Comment (Enter to submit, Esc to cancel) ")
-                .style(Style::default().fg(Color::Yellow)),
-        )
-        .wrap(Wrap { trim: false });
+        fn fetch_pr_details(&self, pr: &PullRequest) -> Result<PullRequest> {
+            Ok(pr.clone())
+        }
 
-    frame.render_widget(Clear, popup_area);
-    frame.render_widget(input, popup_area);
-}
+        fn get_pr_diff(&self, _pr: &PullRequest) -> Result<String> {
+            self.diff_fetches
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.diff.clone())
+        }
 
-fn draw_line_comment_input(frame: &mut Frame, app: &App) {
-    let area = frame.area();
-    let popup_area = Rect {
-        x: area.width / 8,
-        y: area.height / 3,
-        width: area.width * 3 / 4,
-        height: 6,
-    };
+        fn get_pr_files(&self, _pr: &PullRequest) -> Result<Vec<gh::ChangedFile>> {
+            Ok(self.files.clone())
+        }
 
-    let title = if let Some(ctx) = &app.line_comment_ctx {
-        format!(
-            " Comment on {}:{} (Enter to submit, Esc to cancel) ",
-            ctx.file_path, ctx.line_number
-        )
-    } else {
-        " Add Line Comment (Enter to submit, Esc to cancel) ".to_string()
-    };
+        fn get_pr_file_diff(&self, _pr: &PullRequest, _path: &str) -> Result<String> {
+            Ok(self.file_patch.clone().unwrap_or_else(|| self.diff.clone()))
+        }
 
-    let input = Paragraph::new(app.input_buffer.as_str())
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .style(Style::default().fg(Color::Cyan)),
-        )
-        .wrap(Wrap { trim: false });
+        fn get_gitattributes(&self, _pr: &PullRequest) -> Result<Option<String>> {
+            self.diff_fetches
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.gitattributes.clone())
+        }
 
-    frame.render_widget(Clear, popup_area);
-    frame.render_widget(input, popup_area);
-}
+        fn get_repo_file(&self, _pr: &PullRequest, path: &str) -> Result<Option<String>> {
+            Ok(Some(match path {
+                "src/lib.rs" => SAMPLE_LIB_RS.to_string(),
+                _ => "- [ ] Tests cover the change\n".to_string(),
+            }))
+        }
 
-fn draw_confirm_dialog(frame: &mut Frame, app: &App) {
-    let pr = match app.selected_pr() {
-        Some(pr) => pr,
-        None => return,
-    };
+        fn get_pr_comments(&self, _pr: &PullRequest) -> Result<Vec<Comment>> {
+            Ok(Vec::new())
+        }
 
-    let area = frame.area();
-    let popup_area = Rect {
-        x: area.width / 6,
-        y: area.height / 3,
-        width: area.width * 2 / 3,
-        height: 7,
-    };
+        fn get_review_comments(&self, _pr: &PullRequest) -> Result<Vec<ReviewComment>> {
+            Ok(self.review_comments.clone())
+        }
 
-    let text = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::raw("  Approve "),
-            Span::styled(
-                format!("[{}] #{}", pr.repo_name, pr.number),
-                Style::default().fg(Color::Cyan).bold(),
-            ),
-            Span::raw("?"),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  [Y]", Style::default().fg(Color::Green).bold()),
-            Span::raw(" Yes    "),
-            Span::styled("[N]", Style::default().fg(Color::Red).bold()),
-            Span::raw(" No"),
-        ]),
-    ];
+        fn get_review_threads(
+            &self,
+            _pr: &PullRequest,
+        ) -> Result<BTreeMap<u64, gh::ReviewThreadState>> {
+            Ok(self.review_threads.clone())
+        }
 
-    let dialog = Paragraph::new(text).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Confirm Approval ")
-            .style(Style::default().fg(Color::Yellow)),
-    );
+        fn set_review_thread_resolved(&self, thread_id: &str, resolved: bool) -> Result<()> {
+            self.record(format!(
+                "set_review_thread_resolved {} {}",
+                thread_id, resolved
+            ));
+            self.action_result()
+        }
 
-    frame.render_widget(Clear, popup_area);
-    frame.render_widget(dialog, popup_area);
-}
+        fn get_pr_feedback(&self, _pr: &PullRequest) -> Result<gh::PrFeedback> {
+            Ok(gh::PrFeedback::default())
+        }
 
-fn draw_close_dialog(frame: &mut Frame, app: &App) {
-    let pr = match app.selected_pr() {
-        Some(pr) => pr,
-        None => return,
-    };
+        fn get_pr_checks(&self, _pr: &PullRequest) -> Result<Vec<gh::CheckStatus>> {
+            Ok(self.checks.clone())
+        }
 
-    let area = frame.area();
-    let popup_area = Rect {
-        x: area.width / 6,
-        y: area.height / 3,
-        width: area.width * 2 / 3,
-        height: 9,
-    };
+        fn open_url(&self, url: &str) -> Result<()> {
+            self.record(format!("open_url {}", url));
+            self.action_result()
+        }
 
-    let text = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::raw("  Close "),
-            Span::styled(
-                format!("[{}] #{}", pr.repo_name, pr.number),
-                Style::default().fg(Color::Cyan).bold(),
-            ),
-            Span::raw("?"),
-        ]),
-        Line::from(""),
-        Line::from("  Optional comment:"),
-        Line::from(format!("  > {}", app.input_buffer)),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  [Enter]", Style::default().fg(Color::Red).bold()),
-            Span::raw(" Close    "),
-            Span::styled("[Esc]", Style::default().fg(Color::Green).bold()),
-            Span::raw(" Cancel"),
-        ]),
-    ];
+        fn get_branch_protection(&self, _pr: &PullRequest) -> Result<Option<gh::BranchProtection>> {
+            Ok(None)
+        }
 
-    let dialog = Paragraph::new(text).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Close PR ")
-            .style(Style::default().fg(Color::Red)),
-    );
+        fn get_author_stats(&self, _pr: &PullRequest) -> Result<gh::AuthorStats> {
+            Ok(gh::AuthorStats {
+                total_prs: 12,
+                merged_prs: 9,
+                recent_prs: 2,
+                median_lines: Some(120),
+                approval_rate: Some(0.5),
+            })
+        }
 
-    frame.render_widget(Clear, popup_area);
-    frame.render_widget(dialog, popup_area);
-}
+        fn get_discussions(&self, prs: &[PullRequest]) -> Result<Vec<Option<gh::Discussion>>> {
+            Ok(prs
+                .iter()
+                .map(|pr| {
+                    self.discussions
+                        .iter()
+                        .find(|(number, _)| *number == pr.number)
+                        .map(|(_, discussion)| *discussion)
+                })
+                .collect())
+        }
 
-fn draw_merge_dialog(frame: &mut Frame, app: &App) {
-    let pr = match app.selected_pr() {
-        Some(pr) => pr,
-        None => return,
-    };
+        fn get_review_queues(&self, logins: &[String]) -> Result<Vec<gh::ReviewQueue>> {
+            Ok(logins
+                .iter()
+                .map(|login| gh::ReviewQueue {
+                    login: login.clone(),
+                    open_requests: 0,
+                })
+                .collect())
+        }
 
-    let area = frame.area();
-    let popup_area = Rect {
-        x: area.width / 6,
-        y: area.height / 3,
-        width: area.width * 2 / 3,
-        height: 9,
-    };
+        fn get_pr_triage(&self, _pr: &PullRequest) -> Result<gh::PrTriage> {
+            Ok(self.triage.clone())
+        }
 
-    let text = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::raw("  Merge "),
-            Span::styled(
-                format!("[{}] #{}", pr.repo_name, pr.number),
-                Style::default().fg(Color::Cyan).bold(),
-            ),
-            Span::raw("?"),
-        ]),
-        Line::from(""),
-        Line::from("  Will squash if allowed, otherwise regular merge."),
-        Line::from("  Branch will be deleted after merge."),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  [y/Enter]", Style::default().fg(Color::Green).bold()),
-            Span::raw(" Merge    "),
-            Span::styled("[n/Esc]", Style::default().fg(Color::Yellow).bold()),
-            Span::raw(" Cancel"),
-        ]),
-    ];
+        fn get_repo_permission(&self, _repo_name: &str) -> Result<gh::RepoPermission> {
+            Ok(if self.read_only {
+                gh::RepoPermission::Read
+            } else {
+                gh::RepoPermission::Write
+            })
+        }
 
-    let dialog = Paragraph::new(text).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Merge PR ")
-            .style(Style::default().fg(Color::Green)),
-    );
+        fn get_milestones(&self, _pr: &PullRequest) -> Result<Vec<String>> {
+            Ok(vec!["v1.0".to_string(), "v1.1".to_string()])
+        }
 
-    frame.render_widget(Clear, popup_area);
-    frame.render_widget(dialog, popup_area);
-}
+        fn set_milestone(&self, pr: &PullRequest, milestone: Option<&str>) -> Result<()> {
+            self.record(format!("set_milestone #{} {:?}", pr.number, milestone));
+            self.action_result()
+        }
 
-fn draw_search_input(frame: &mut Frame, app: &App) {
-    let area = frame.area();
-    // Draw at bottom of screen like vim
-    let popup_area = Rect {
-        x: 0,
-        y: area.height.saturating_sub(3),
-        width: area.width,
-        height: 3,
-    };
+        fn get_repo_labels(&self, _pr: &PullRequest) -> Result<Vec<gh::Label>> {
+            Ok(vec![
+                gh::Label {
+                    name: "bug".to_string(),
+                    color: "d73a4a".to_string(),
+                },
+                label("docs"),
+                label("perf"),
+            ])
+        }
 
-    let (prefix, title) = match app.input_mode {
-        InputMode::ListSearch => ("/", " Search PR list (Enter to find, Esc to cancel) "),
-        InputMode::Search => match app.search_scope {
-            SearchScope::Diff => ("/", " Search diff (Enter to find, Esc to cancel) "),
-            SearchScope::TreeFileNames => {
-                ("/", " Search file names (Enter to find, Esc to cancel) ")
-            }
-            SearchScope::TreeContents => {
-                ("?", " Search file contents (Enter to find, Esc to cancel) ")
-            }
-        },
-        _ => ("/", " Search (Enter to find, Esc to cancel) "),
-    };
+        fn edit_labels(&self, pr: &PullRequest, add: &[String], remove: &[String]) -> Result<()> {
+            self.record(format!(
+                "edit_labels #{} +{:?} -{:?}",
+                pr.number, add, remove
+            ));
+            self.action_result()
+        }
 
-    let input = Paragraph::new(format!("{}{}", prefix, app.input_buffer)).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(title)
-            .style(Style::default().fg(Color::Yellow)),
-    );
+        fn add_to_project(&self, pr: &PullRequest, project: &str) -> Result<()> {
+            self.record(format!("add_to_project #{} {}", pr.number, project));
+            self.action_result()
+        }
+
+        fn request_reviewer(&self, pr: &PullRequest, login: &str) -> Result<()> {
+            self.record(format!("request_reviewer #{} {}", pr.number, login));
+            Ok(())
+        }
+
+        fn add_pr_comment(&self, pr: &PullRequest, comment: &str) -> Result<()> {
+            self.record(format!("add_pr_comment #{} {}", pr.number, comment));
+            Ok(())
+        }
 
-    frame.render_widget(Clear, popup_area);
-    frame.render_widget(input, popup_area);
-}
+        fn reply_to_review_comment(
+            &self,
+            pr: &PullRequest,
+            comment_id: u64,
+            comment: &str,
+        ) -> Result<()> {
+            self.record(format!(
+                "reply_to_review_comment #{} {} {}",
+                pr.number, comment_id, comment
+            ));
+            self.action_result()
+        }
 
-fn draw_goto_input(frame: &mut Frame, app: &App) {
-    let area = frame.area();
-    // Draw at bottom of screen like vim
-    let popup_area = Rect {
-        x: 0,
-        y: area.height.saturating_sub(3),
-        width: area.width,
-        height: 3,
-    };
+        fn add_line_comment(
+            &self,
+            pr: &PullRequest,
+            file_path: &str,
+            start_line: Option<u32>,
+            line: u32,
+            side: &str,
+            comment: &str,
+        ) -> Result<()> {
+            let lines = match start_line {
+                Some(start_line) => format!("{}-{}", start_line, line),
+                None => line.to_string(),
+            };
+            self.record(format!(
+                "add_line_comment #{} {}:{} {} {}",
+                pr.number, file_path, lines, side, comment
+            ));
+            self.action_result()
+        }
 
-    let input = Paragraph::new(format!(":{}", app.input_buffer)).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Go to line (Enter to jump, Esc to cancel) ")
-            .style(Style::default().fg(Color::Cyan)),
-    );
+        fn submit_review(
+            &self,
+            pr: &PullRequest,
+            draft: &DraftReview,
+            event: ReviewEvent,
+        ) -> Result<()> {
+            let locations: Vec<String> = draft
+                .comments
+                .iter()
+                .map(|comment| format!("{}:{}", comment.path, comment.line))
+                .collect();
+            self.record(format!(
+                "submit_review #{} {} [{}] {}",
+                pr.number,
+                event.api_name(),
+                locations.join(","),
+                draft.body
+            ));
+            self.action_result()
+        }
 
-    frame.render_widget(Clear, popup_area);
-    frame.render_widget(input, popup_area);
-}
+        fn approve_pr(&self, pr: &PullRequest, comment: Option<&str>) -> Result<()> {
+            match comment {
+                Some(comment) => self.record(format!("approve_pr #{} {}", pr.number, comment)),
+                None => self.record(format!("approve_pr #{}", pr.number)),
+            }
+            self.action_result()
+        }
 
-pub fn run(
-    repos_root: PathBuf,
-    username: String,
-    include_drafts: bool,
-    ai: AiConfig,
-    mode: AppMode,
-    exclude_users: Vec<String>,
-) -> Result<()> {
-    // Setup terminal
-    crossterm::terminal::enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    crossterm::execute!(
-        stdout,
-        crossterm::terminal::EnterAlternateScreen // Mouse capture disabled to allow text selection in terminal
-    )?;
-    let backend = ratatui::backend::CrosstermBackend::new(stdout);
-    let mut terminal = ratatui::Terminal::new(backend)?;
+        fn close_pr(&self, pr: &PullRequest, _comment: Option<&str>) -> Result<()> {
+            self.record(format!("close_pr #{}", pr.number));
+            Ok(())
+        }
 
-    let mut app = App::new(
-        repos_root,
-        username,
-        include_drafts,
-        exclude_users,
-        ai,
-        mode,
-    );
+        fn check_merge_status(&self, _pr: &PullRequest) -> gh::MergeStatus {
+            gh::MergeStatus {
+                can_merge: self.merge_blocked.is_none(),
+                reason: self.merge_blocked.clone(),
+                protection: self.protection.clone(),
+            }
+        }
 
-    // Start fetching PRs immediately in background
-    app.refresh();
+        fn get_pr_commits(&self, _pr: &PullRequest) -> Result<Vec<gh::PrCommit>> {
+            Ok(self.commits.clone())
+        }
 
-    // Main loop
-    loop {
-        // Force full terminal clear when needed
-        if app.needs_clear {
-            terminal.clear()?;
-            app.needs_clear = false;
-            app.needs_redraw = true;
+        fn get_commit_diff(&self, _pr: &PullRequest, _sha: &str) -> Result<String> {
+            Ok(self.diff.clone())
         }
 
-        // Auto-dismiss status messages after timeout
-        if app.check_status_timeout() {
-            app.needs_redraw = true;
+        fn merge_pr(
+            &self,
+            pr: &PullRequest,
+            _delete_branch: bool,
+            message: Option<&gh::SquashMessage>,
+        ) -> Result<String> {
+            match message {
+                Some(message) => self.record(format!(
+                    "merge_pr #{} {:?} {:?}",
+                    pr.number, message.title, message.body
+                )),
+                None => self.record(format!("merge_pr #{}", pr.number)),
+            }
+            Ok("squash".to_string())
         }
 
-        if app.needs_redraw {
-            terminal.draw(|f| draw(f, &mut app))?;
-            app.needs_redraw = false;
+        fn enable_auto_merge(&self, pr: &PullRequest) -> Result<()> {
+            self.record(format!("enable_auto_merge #{}", pr.number));
+            Ok(())
         }
+    }
 
-        app.handle_event()?;
-        if let Some(target) = app.take_pending_agent_attach_target() {
-            crossterm::terminal::disable_raw_mode()?;
-            crossterm::execute!(
-                terminal.backend_mut(),
-                crossterm::terminal::LeaveAlternateScreen
-            )?;
-            terminal.show_cursor()?;
-
-            let attach_result = agent::switch_or_attach(&target);
-
-            crossterm::execute!(
-                terminal.backend_mut(),
-                crossterm::terminal::EnterAlternateScreen
-            )?;
-            crossterm::terminal::enable_raw_mode()?;
-            terminal.clear()?;
-            app.needs_clear = true;
-            if let Err(err) = attach_result {
-                app.set_status(format!("Failed to attach agent: {:#}", err));
+    const SAMPLE_LIB_RS: &str =
+        "fn main() {\n    parse_config();\n}\n\nfn parse_config() {\n    todo!()\n}\n";
+    const SAMPLE_DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,3 @@\n fn main() {\n+    parse_config();\n }\n";
+
+    fn label(name: &str) -> gh::Label {
+        gh::Label {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn fake_gh() -> Arc<FakeGh> {
+        let mut first = make_test_pr(7, "Speed up parse_config", "acme/widgets", "alice");
+        first.body = "parse_config was slow.".to_string();
+        first.additions = 1;
+        let second = make_test_pr(9, "Fix flaky test", "acme/widgets", "bob");
+        Arc::new(FakeGh {
+            prs: vec![first, second],
+            diff: SAMPLE_DIFF.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// An app on the given tab, with the fake's PR list loaded.
+    fn test_app(gh: &Arc<FakeGh>, mode: AppMode) -> App {
+        let client: Arc<dyn GhClient> = gh.clone();
+        let mut app = App::with_client(
+            &Config::default(),
+            PathBuf::from("/tmp"),
+            "me".to_string(),
+            false,
+            mode,
+            client,
+        );
+        app.use_delta = false;
+        app.refresh();
+        settle(&mut app);
+        app
+    }
+
+    /// Apply async results until the background threads go quiet.
+    fn settle(app: &mut App) {
+        let mut quiet = 0;
+        for _ in 0..200 {
+            if app.poll_async_results() {
+                quiet = 0;
+            } else {
+                quiet += 1;
+                if quiet >= 5 {
+                    return;
+                }
             }
+            thread::sleep(std::time::Duration::from_millis(5));
         }
+    }
 
-        if app.should_quit {
-            break;
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        settle(app);
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for ch in text.chars() {
+            press(app, KeyCode::Char(ch));
         }
     }
 
-    // Restore terminal
-    crossterm::terminal::disable_raw_mode()?;
-    crossterm::execute!(
-        terminal.backend_mut(),
-        crossterm::terminal::LeaveAlternateScreen
-    )?;
-    terminal.show_cursor()?;
+    /// Render the app into an in-memory terminal and return its rows, right-trimmed.
+    fn screen(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| draw(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
 
-    Ok(())
-}
+    #[test]
+    fn tui_list_renders_prs_from_client() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        let rows = screen(&mut app, 100, 20);
+        assert_eq!(
+            rows[1..7].join("\n"),
+            [
+                "│ Involving Me │   My PRs     Watching Repos     Dependencies",
+                "│              └───────────────────────────────────────────────────────────────────────────────────┐",
+                "│▶ [acme/widgets] #7: Speed up parse_config                                                        │",
+                "│    @alice | +1/-0 | now                                                                          │",
+                "│  [acme/widgets] #9: Fix flaky test                                                               │",
+                "│    @bob | +0/-0 | now                                                                            │",
+            ]
+            .join("\n")
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        press(&mut app, KeyCode::Char('j'));
+        let rows = screen(&mut app, 100, 20);
+        assert!(rows[5].starts_with("│▶ [acme/widgets] #9: Fix flaky test"));
+    }
 
-    // Tests use output patterns captured from real `delta` CLI output
+    #[test]
+    fn tui_detail_shows_header_and_diff_tab() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.prs[0].created_at = Utc::now() - chrono::Duration::days(3);
+        fake.prs[0].mergeable = Some(false);
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        let rows = screen(&mut app, 100, 20);
+        assert_eq!(
+            rows[1..5].join("\n"),
+            [
+                "│[acme/widgets] #7: Speed up parse_config by @alice                                                │",
+                "│main ← feature | opened 3d ago, updated just now | conflicts                                      │",
+                "│Reviewers: none                                                                                   │",
+                "│@alice here: 12 PRs (9 merged) | 2 in 30d | ~120 lines | 50% approved                             │",
+            ]
+            .join("\n")
+        );
+        assert!(rows[9].starts_with("│parse_config was slow."));
+
+        press(&mut app, KeyCode::Tab);
+        let rows = screen(&mut app, 100, 20);
+        assert!(rows[8].starts_with("┌ Diff (built-in) - src/lib.rs"));
+        assert_eq!(
+            rows[13..16].join("\n"),
+            [
+                "│    1   1  fn main() {                                                                            │",
+                "│        2 +    parse_config();                                                                    │",
+                "│    2   3  }                                                                                      │",
+            ]
+            .join("\n")
+        );
+    }
 
     #[test]
-    fn test_parse_delta_unified_mode() {
-        // Real unified mode format from: delta --line-numbers
-        // Format: " <old>⋮ <new>│<content>"
-        let raw_diff = r#"diff --git a/src/gh.rs b/src/gh.rs
---- a/src/gh.rs
-+++ b/src/gh.rs
-@@ -303,7 +303,8 @@ pub fn add_pr_comment
- }
+    fn tui_line_comment_posts_through_client() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        press(&mut app, KeyCode::Char('c'));
+        assert_eq!(app.input_mode, InputMode::LineComment);
+        type_text(&mut app, "nit");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            gh.calls(),
+            vec!["add_line_comment #7 src/lib.rs:2 RIGHT nit"]
+        );
+    }
 
- /// Add a line-level comment
--pub fn add_line_comment(old)
-+/// side doc
-+pub fn add_line_comment(new)"#;
+    #[test]
+    fn tui_approve_dialog_cancels_without_calling_gh() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.input_mode, InputMode::ConfirmApprove);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("Confirm Approval"));
+        assert!(text.contains("Approve [acme/widgets] #7?"));
+
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(!screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("Confirm Approval"));
+        assert!(gh.calls().is_empty());
+    }
 
-        // Captured from: git diff | delta --line-numbers | sed 's/\x1b\[[0-9;]*m//g'
-        let delta_output = r#"
-src/gh.rs
-────────────────────────────────────────────────────────────────────────────────
+    #[test]
+    fn tui_merge_previews_and_edits_the_squash_message() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.commits = vec![gh::PrCommit {
+            sha: "0123456".to_string(),
+            date: None,
+            message: "Cache configs\n\nCo-authored-by: Bob <bob@example.com>".to_string(),
+            name: Some("Alice".to_string()),
+            email: Some("alice@example.com".to_string()),
+            login: Some("alice".to_string()),
+        }];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::MyPrs);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.input_mode, InputMode::ConfirmMerge);
+        let text = screen(&mut app, 120, 30).join("\n");
+        assert!(text.contains("Speed up parse_config (#7)"), "{}", text);
+        assert!(text.contains("* Cache configs"), "{}", text);
+        assert!(text.contains("[Tab] Edit message"), "{}", text);
+
+        // The editor drops the trailer; it comes back so Bob keeps the credit.
+        press(&mut app, KeyCode::Tab);
+        let draft = app.take_pending_merge_message().unwrap();
+        assert!(draft.starts_with("Speed up parse_config (#7)\n\n* Cache configs"));
+        app.finish_merge_message_edit(Ok("Cache parsed configs\n\nKeyed by path.\n".to_string()));
+        assert_eq!(app.input_mode, InputMode::ConfirmMerge);
+        press(&mut app, KeyCode::Char('y'));
+        settle(&mut app);
+        assert_eq!(
+            gh.calls(),
+            vec![
+                "merge_pr #7 \"Cache parsed configs\" \"Keyed by path.\\n\\nCo-authored-by: Bob <bob@example.com>\""
+            ]
+        );
+    }
 
-────────────────────────────────────────────────────────────────────────────┐
-303: pub fn add_pr_comment(pr: &PullRequest, comment: &str) -> Result<()> { │
-────────────────────────────────────────────────────────────────────────────┘
- 303⋮ 303│}
- 304⋮ 304│
- 305⋮ 305│/// Add a line-level comment to a PR using the reviews API
- 306⋮    │pub fn add_line_comment(pr: &PullRequest, file_path: &str, line: u32, comment: &str) -> Result<()> {
-    ⋮ 306│/// `side` should be "LEFT" for removed lines (old file) or "RIGHT" for added/context lines (new file)
-    ⋮ 307│pub fn add_line_comment(pr: &PullRequest, file_path: &str, line: u32, side: &str, comment: &str) -> Result<()> {
- 307⋮ 308│    // Use the reviews endpoint with a comments array"#;
+    #[test]
+    fn tui_checks_tab_lists_failures_first_and_opens_them() {
+        let check = |name: &str, status: gh::CheckState, link: Option<&str>| {
+            let started: DateTime<Utc> = "2026-10-18T12:00:00Z".parse().unwrap();
+            gh::CheckStatus {
+                name: name.to_string(),
+                status,
+                started_at: Some(started),
+                completed_at: Some(started + chrono::Duration::seconds(192)),
+                link: link.map(str::to_string),
+            }
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.checks = vec![
+            check("build", gh::CheckState::Success, None),
+            check(
+                "lint",
+                gh::CheckState::Failure,
+                Some("https://ci.example/lint"),
+            ),
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        while app.detail_tab != DetailTab::Checks {
+            press(&mut app, KeyCode::Tab);
+        }
+        settle(&mut app);
 
-        let result = parse_delta_output(delta_output, raw_diff);
+        let rows = screen(&mut app, 100, 20);
+        let lint = rows
+            .iter()
+            .position(|row| row.contains("✗ fail    lint"))
+            .unwrap();
+        let build = rows
+            .iter()
+            .position(|row| row.contains("✓ pass    build"))
+            .unwrap();
+        assert!(lint < build, "{:#?}", rows);
+        assert!(rows[lint].starts_with("│▶ "), "{}", rows[lint]);
+        assert!(
+            rows[lint].contains("3m 12s  https://ci.example/lint"),
+            "{}",
+            rows[lint]
+        );
 
-        // Line 0: empty
-        assert_eq!(result[0].file_path, None);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(gh.calls(), vec!["open_url https://ci.example/lint"]);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Opened lint in browser")
+        );
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("build has no page to open")
+        );
+    }
 
-        // Line 1: file header "src/gh.rs"
-        assert_eq!(result[1].file_path.as_deref(), Some("src/gh.rs"));
+    #[test]
+    fn tui_merge_dialog_refuses_when_required_checks_have_not_passed() {
+        let check = |name: &str, status: gh::CheckState| gh::CheckStatus {
+            name: name.to_string(),
+            status,
+            started_at: None,
+            completed_at: None,
+            link: None,
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.protection = Some(gh::BranchProtection {
+            branch: "main".to_string(),
+            required_approvals: 0,
+            approvals: 0,
+            required_checks: vec!["build".to_string(), "lint".to_string(), "e2e".to_string()],
+            enforce_admins: false,
+        });
+        fake.checks = vec![
+            check("build", gh::CheckState::Success),
+            check("lint", gh::CheckState::Failure),
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::MyPrs);
+        // Shown even with merge confirmation turned off
+        app.confirm.merge = false;
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.input_mode, InputMode::ConfirmMerge);
+        let text = screen(&mut app, 120, 30).join("\n");
+        assert!(
+            text.contains("Cannot merge: required checks have not passed"),
+            "{}",
+            text
+        );
+        assert!(text.contains("✗ lint: failing"), "{}", text);
+        assert!(text.contains("✗ e2e: missing"), "{}", text);
+        assert!(!text.contains("build: "), "{}", text);
+        assert!(!text.contains("[y/Enter] Merge"), "{}", text);
 
-        // Line 5: hunk header "303: pub fn..."
-        assert_eq!(result[5].old_line_number, Some(303));
-        assert_eq!(result[5].new_line_number, Some(303));
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(app.input_mode, InputMode::ConfirmMerge);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        settle(&mut app);
+        assert!(gh.calls().is_empty());
+    }
 
-        // Line 7: " 303⋮ 303│" - context line
-        assert_eq!(result[7].file_path.as_deref(), Some("src/gh.rs"));
-        assert_eq!(result[7].old_line_number, Some(303));
-        assert_eq!(result[7].new_line_number, Some(303));
+    #[test]
+    fn tui_commits_tab_lists_commits_and_opens_one_diff() {
+        let commit = |sha: &str, login: &str, message: &str| gh::PrCommit {
+            sha: sha.to_string(),
+            date: None,
+            message: message.to_string(),
+            name: None,
+            email: None,
+            login: Some(login.to_string()),
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.commits = vec![
+            commit("aaaaaaa111", "alice", "Add parser\n\nDetails."),
+            commit("bbbbbbb222", "bob", "Call parse_config"),
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        while app.detail_tab != DetailTab::Commits {
+            press(&mut app, KeyCode::Tab);
+        }
+        settle(&mut app);
 
-        // Line 10: " 306⋮    │" - removed line (has old, no new)
-        assert_eq!(result[10].old_line_number, Some(306));
-        assert_eq!(result[10].new_line_number, None);
+        // Newest commit first in focus; only subjects are listed
+        let rows = screen(&mut app, 100, 20);
+        let text = rows.join("\n");
+        assert!(text.contains("aaaaaaa @alice"), "{}", text);
+        assert!(!text.contains("Details."), "{}", text);
+        let newest = rows.iter().find(|row| row.contains("bbbbbbb")).unwrap();
+        assert!(newest.starts_with("│▶ bbbbbbb @bob"), "{}", newest);
+        assert!(newest.contains("Call parse_config"), "{}", newest);
 
-        // Line 11: "    ⋮ 306│" - added line (no old, has new)
-        assert_eq!(result[11].old_line_number, None);
-        assert_eq!(result[11].new_line_number, Some(306));
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("Commit bbbbbbb [Esc: commits]"), "{}", text);
+        assert!(text.contains("parse_config();"), "{}", text);
 
-        // Line 13: " 307⋮ 308│" - context line (shifted)
-        assert_eq!(result[13].old_line_number, Some(307));
-        assert_eq!(result[13].new_line_number, Some(308));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.view, View::Detail);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("aaaaaaa @alice"), "{}", text);
     }
 
     #[test]
-    fn test_parse_delta_side_by_side_mode() {
-        // Real side-by-side format from: delta --side-by-side --line-numbers
-        // Format: "│ <old>│<content>│ <new>│<content>"
-        let raw_diff = r#"diff --git a/src/gh.rs b/src/gh.rs
---- a/src/gh.rs
-+++ b/src/gh.rs
-@@ -303,4 +303,5 @@ pub fn add_pr_comment
- }
+    fn tui_files_tab_lists_files_and_loads_one_diff() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.files = vec![
+            gh::ChangedFile {
+                path: "src/lib.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 0,
+                previous_filename: None,
+            },
+            gh::ChangedFile {
+                path: "docs/setup.md".to_string(),
+                status: "renamed".to_string(),
+                additions: 0,
+                deletions: 0,
+                previous_filename: Some("SETUP.md".to_string()),
+            },
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        while app.detail_tab != DetailTab::Files {
+            press(&mut app, KeyCode::Tab);
+        }
+        settle(&mut app);
+
+        let rows = screen(&mut app, 100, 20);
+        let text = rows.join("\n");
+        assert!(text.contains("│▶ modified +1 -0 src/lib.rs"), "{}", text);
+        assert!(
+            text.contains("renamed  +0 -0 SETUP.md → docs/setup.md"),
+            "{}",
+            text
+        );
 
-+/// new comment
- /// Add a line-level comment"#;
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("src/lib.rs [Esc: files]"), "{}", text);
+        assert!(text.contains("parse_config();"), "{}", text);
 
-        // Captured from: git diff | delta --side-by-side --line-numbers | sed 's/\x1b\[[0-9;]*m//g'
-        let delta_output = r#"
-src/gh.rs
-────────────────────────────────────────────────────────────────────────────────
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.view, View::Detail);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("SETUP.md → docs/setup.md"), "{}", text);
+    }
 
-────────────────────────────────────────────────────────────────────────────┐
-303: pub fn add_pr_comment(pr: &PullRequest, comment: &str) -> Result<()> { │
-────────────────────────────────────────────────────────────────────────────┘
-│ 303│}                                 │ 303│}
-│ 304│                                  │ 304│
-│    │                                  │ 305│/// new comment
-│ 305│/// Add a line-level comment      │ 306│/// Add a line-level comment"#;
+    #[test]
+    fn tui_generated_toggle_refilters_without_refetching() {
+        let file = |path: &str| {
+            format!(
+                "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1 +1 @@\n-a\n+b\n"
+            )
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.prs[0].head_oid = "abc123".to_string();
+        fake.diff = ["src/lib.rs", "Cargo.lock", "gen/api.rs"]
+            .map(file)
+            .concat();
+        fake.gitattributes = Some("gen/** linguist-generated\n".to_string());
+        let gh = Arc::new(fake);
+        let fetches = || gh.diff_fetches.load(std::sync::atomic::Ordering::SeqCst);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.generated_collapsed, 2);
+        assert_eq!(fetches(), 2);
+
+        press(&mut app, KeyCode::Char('H'));
+        assert!(app.show_generated);
+        assert_eq!(app.diff_cache.as_deref(), Some(gh.diff.as_str()));
+        press(&mut app, KeyCode::Char('H'));
+        assert!(!app.show_generated);
+        assert_eq!(app.generated_collapsed, 2);
+        assert_eq!(fetches(), 2);
+    }
 
-        let result = parse_delta_output(delta_output, raw_diff);
+    #[test]
+    fn tui_diff_brackets_jump_between_files() {
+        let file = |path: &str| {
+            format!(
+                "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1,4 +1,5 @@\n a\n b\n+c\n d\n e\n"
+            )
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.diff = ["src/a.rs", "src/b.rs", "src/c.rs"].map(file).concat();
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        settle(&mut app);
+        screen(&mut app, 100, 12);
+
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!((app.diff_cursor, app.scroll_offset), (9, 9));
+        let rows = screen(&mut app, 100, 12);
+        assert!(rows.join("\n").contains("Diff (built-in) - src/b.rs"));
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.diff_cursor, 18);
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.diff_cursor, 18);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Already at the last file")
+        );
 
-        // Line 1: file header
-        assert_eq!(result[1].file_path.as_deref(), Some("src/gh.rs"));
+        // Mid-file, `[` goes back to the start of the file under the cursor first
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(app.diff_cursor, 18);
+        press(&mut app, KeyCode::Char('['));
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!((app.diff_cursor, app.scroll_offset), (0, 0));
+
+        // Delta rows start a file where they first name it
+        let row = |path: Option<&str>| DeltaLineInfo {
+            file_path: path.map(str::to_string),
+            old_line_number: None,
+            new_line_number: None,
+            hunk_header: false,
+        };
+        app.use_delta = true;
+        app.delta_cache = Some(String::new());
+        app.delta_line_info = vec![
+            row(None),
+            row(Some("src/a.rs")),
+            row(Some("src/a.rs")),
+            row(Some("src/b.rs")),
+        ];
+        assert_eq!(app.diff_file_starts(), vec![1, 3]);
+    }
 
-        // Line 7: "│ 303│...│ 303│..." - context line
-        assert_eq!(result[7].file_path.as_deref(), Some("src/gh.rs"));
-        assert_eq!(result[7].old_line_number, Some(303));
-        assert_eq!(result[7].new_line_number, Some(303));
+    #[test]
+    fn tui_diff_braces_jump_between_hunks() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n\
+                     @@ -1,2 +1,3 @@\n a\n+b\n c\n@@ -20,2 +21,3 @@ fn tail() {\n x\n+y\n z\n\
+                     diff --git a/src/b.rs b/src/b.rs\n--- a/src/b.rs\n+++ b/src/b.rs\n\
+                     @@ -5 +5 @@\n-old\n+new\n"
+            .to_string();
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        settle(&mut app);
+        screen(&mut app, 100, 12);
+
+        press(&mut app, KeyCode::Char('}'));
+        assert_eq!(app.diff_cursor, 3);
+        press(&mut app, KeyCode::Char('}'));
+        assert_eq!((app.diff_cursor, app.scroll_offset), (7, 7));
+        // Hunks run across files
+        press(&mut app, KeyCode::Char('}'));
+        assert_eq!(app.diff_cursor, 14);
+        press(&mut app, KeyCode::Char('}'));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Already at the last hunk")
+        );
+        press(&mut app, KeyCode::Char('{'));
+        assert_eq!(app.diff_cursor, 7);
+
+        // Delta marks the rows it draws for `@@` lines
+        let raw = "diff --git a/src/a.rs b/src/a.rs\n";
+        let delta = "src/a.rs\n───┐\n1: │\n───┘\n 1⋮ 1│a\n───┐\n21: fn tail() { │\n───┘\n";
+        app.use_delta = true;
+        app.delta_cache = Some(delta.to_string());
+        app.delta_line_info = parse_delta_output(delta, raw);
+        assert_eq!(app.diff_hunk_starts(), vec![2, 6]);
+    }
+
+    #[test]
+    fn tui_diff_collapses_files_from_their_header() {
+        let file = |path: &str, added: &str| {
+            format!(
+                "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1,2 +1,3 @@\n a\n+{added}\n b\n"
+            )
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.diff = [file("src/a.rs", "alpha"), file("src/b.rs", "beta")].concat();
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        settle(&mut app);
+        screen(&mut app, 100, 24);
+
+        // Enter on a code line still toggles review threads, not the file
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.collapsed_files.is_empty());
+
+        press(&mut app, KeyCode::Char('['));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.status_message.as_deref(), Some("Collapsed src/a.rs"));
+        let text = screen(&mut app, 100, 24).join("\n");
+        assert!(
+            text.contains("Collapsed (+1 -0), Enter to expand"),
+            "{}",
+            text
+        );
+        assert!(text.contains("[1 collapsed]"), "{}", text);
+        assert!(!text.contains("alpha"), "{}", text);
+        assert!(text.contains("beta"), "{}", text);
+
+        // Navigation and search see the stub, not the hidden lines
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.diff_cursor, 2);
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "alpha");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.diff_cursor, 2);
+
+        // Still collapsed after leaving and reopening the PR
+        press(&mut app, KeyCode::Char('q'));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        settle(&mut app);
+        let text = screen(&mut app, 100, 24).join("\n");
+        assert!(text.contains("Collapsed (+1 -0)"), "{}", text);
+        assert!(!text.contains("+alpha"), "{}", text);
+
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.status_message.as_deref(), Some("Expanded src/a.rs"));
+        let text = screen(&mut app, 100, 24).join("\n");
+        assert!(text.contains("+alpha"), "{}", text);
+    }
+
+    #[test]
+    fn tui_refreshes_coalesce_and_drop_superseded_results() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.prs[1].is_draft = true;
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        assert_eq!(app.prs.len(), 1);
+
+        // Asking again for the same list joins the running fetch
+        app.refresh();
+        let generation = app.list_generation;
+        app.refresh();
+        app.refresh();
+        assert_eq!(app.list_generation, generation);
+        assert!(!app.refresh_queued);
+
+        // Toggling drafts mid-fetch supersedes it; one more fetch covers both toggles
+        app.toggle_drafts();
+        app.toggle_drafts();
+        app.toggle_drafts();
+        assert!(app.refresh_queued);
+        settle(&mut app);
+        assert_eq!(app.prs.len(), 2);
+        assert!(!app.refreshing);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Refreshed: 2 PRs (incl. drafts)")
+        );
+        assert_eq!(app.list_generation, generation + 2);
+    }
 
-        // Line 9: "│    │...│ 305│..." - added line (no old, has new)
-        assert_eq!(result[9].old_line_number, None);
-        assert_eq!(result[9].new_line_number, Some(305));
+    #[test]
+    fn tui_long_list_draws_only_rows_in_view_with_a_position_indicator() {
+        let gh = Arc::new(FakeGh {
+            prs: (1..=300)
+                .map(|number| {
+                    make_test_pr(number, &format!("Change {number}"), "acme/widgets", "alice")
+                })
+                .collect(),
+            ..Default::default()
+        });
+        let mut app = test_app(&gh, AppMode::Review);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("#1: Change 1"));
+        assert!(text.contains(" 1/300 · 2% "));
 
-        // Line 10: "│ 305│...│ 306│..." - context line (shifted)
-        assert_eq!(result[10].old_line_number, Some(305));
-        assert_eq!(result[10].new_line_number, Some(306));
+        for _ in 0..10 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("#11: Change 11"));
+        assert!(!text.contains("#1: Change"));
+        assert!(text.contains(" 11/300 · 3% "));
+
+        press(&mut app, KeyCode::Char('G'));
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("#300: Change 300"));
+        assert!(text.contains(" 300/300 · 100% "));
     }
 
     #[test]
-    fn test_parse_delta_side_by_side_removed_line() {
-        // Side-by-side with removed lines
-        let raw_diff = r#"diff --git a/test.rs b/test.rs
---- a/test.rs
-+++ b/test.rs
-@@ -1,3 +1,2 @@
- keep
--removed
- also keep"#;
+    fn visible_window_scrolls_just_enough_to_show_the_selection() {
+        let heights = [2, 3, 2, 2, 1];
+        assert_eq!(visible_window(&heights, 0, Some(0), 5), (0, 2));
+        assert_eq!(visible_window(&heights, 0, Some(2), 5), (1, 3));
+        assert_eq!(visible_window(&heights, 1, Some(4), 5), (2, 5));
+        assert_eq!(visible_window(&heights, 3, Some(0), 5), (0, 2));
+        // A row taller than the view still shows on its own
+        assert_eq!(visible_window(&heights, 0, Some(1), 2), (1, 2));
+        assert_eq!(visible_window(&[], 4, None, 5), (0, 0));
+    }
 
-        let delta_output = r#"
-test.rs
-────────────────────────────────────────
-│   1│keep                              │   1│keep
-│   2│removed                           │    │
-│   3│also keep                         │   2│also keep"#;
+    #[test]
+    fn tui_watching_refresh_streams_repo_scan_progress() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.scan_progress = Some(crate::repos::ScanProgress {
+            scanned: 42,
+            total: 118,
+        });
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        app.refresh();
+        assert!(!app.poll_scan_progress());
+
+        settle(&mut app);
+        app.select_list_tab(AppMode::Watching);
+        assert!(app.refreshing);
+        assert!(app.poll_scan_progress());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Scanning repos 42/118...")
+        );
+        assert!(!app.poll_scan_progress());
+        settle(&mut app);
+        assert_eq!(app.status_message.as_deref(), Some("Refreshed: 2 PRs"));
+    }
 
-        let result = parse_delta_output(delta_output, raw_diff);
+    #[test]
+    fn tui_lists_repos_that_failed_to_load_until_a_retry_succeeds() {
+        let gh = fake_gh();
+        *gh.fetch_errors.lock().unwrap() = vec![gh::RepoFetchError {
+            repo: "acme/private".to_string(),
+            reason: "HTTP 401: Bad credentials\nTry `gh auth login`".to_string(),
+        }];
+        let mut app = test_app(&gh, AppMode::Watching);
+        app.refresh();
+        settle(&mut app);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Refreshed: 2 PRs (1 repo failed, R: retry)")
+        );
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("1 repo failed to load"));
+        assert!(text.contains("acme/private: HTTP 401: Bad credentials"));
+        assert!(!text.contains("gh auth login"));
 
-        // Line 3: context line
-        assert_eq!(result[3].old_line_number, Some(1));
-        assert_eq!(result[3].new_line_number, Some(1));
+        gh.fetch_errors.lock().unwrap().clear();
+        press(&mut app, KeyCode::Char('R'));
+        settle(&mut app);
+        assert_eq!(app.status_message.as_deref(), Some("Refreshed: 2 PRs"));
+        assert!(!screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("failed to load"));
+    }
 
-        // Line 4: removed line (has old, no new)
-        assert_eq!(result[4].old_line_number, Some(2));
-        assert_eq!(result[4].new_line_number, None);
+    #[test]
+    fn tui_blocked_merge_reports_reason() {
+        let mut gh = fake_gh();
+        Arc::get_mut(&mut gh).unwrap().merge_blocked = Some("2 unresolved threads".to_string());
+        let mut app = test_app(&gh, AppMode::MyPrs);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(screen(&mut app, 100, 20)[0].contains("Cannot merge: 2 unresolved threads"));
+        assert!(gh.calls().is_empty());
+    }
 
-        // Line 5: context line (shifted)
-        assert_eq!(result[5].old_line_number, Some(3));
-        assert_eq!(result[5].new_line_number, Some(2));
+    #[test]
+    fn tui_global_search_lists_matches_and_opens_pr() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('s'));
+        type_text(&mut app, "parse_config");
+        press(&mut app, KeyCode::Enter);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("Matches for 'parse_config' (2)"));
+        assert!(text.contains("[acme/widgets] #7 body parse_config was slow."));
+
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.view, View::Detail);
+        assert_eq!(app.selected_pr().map(|pr| pr.number), Some(7));
     }
 
     #[test]
-    fn test_parse_delta_hunk_header() {
-        // Hunk header format: "<num>: <content>" inside box decorations
-        let raw_diff = r#"diff --git a/src/lib.rs b/src/lib.rs
---- a/src/lib.rs
-+++ b/src/lib.rs
-@@ -50,2 +50,3 @@ fn helper() {
-     code();
-+    more();
-}"#;
+    fn tui_scrolling_stops_at_content_end_and_pages_by_viewport() {
+        let mut gh = fake_gh();
+        Arc::get_mut(&mut gh).unwrap().prs[0].body = (1..=100)
+            .map(|n| format!("line {}", n))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+
+        // 21 rows: 5 header, 3 tabs, 3 help, and a bordered content area of 8 lines.
+        screen(&mut app, 100, 21);
+        press(&mut app, KeyCode::PageDown);
+        assert_eq!(app.scroll_offset, 8);
+        for _ in 0..20 {
+            press(&mut app, KeyCode::PageDown);
+        }
+        assert_eq!(app.scroll_offset, 92);
+        assert!(screen(&mut app, 100, 21)[16].starts_with("│line 100"));
+        press(&mut app, KeyCode::PageUp);
+        assert_eq!(app.scroll_offset, 84);
+
+        // The diff focuses its top line, which can reach the last line but not past it.
+        press(&mut app, KeyCode::Tab);
+        screen(&mut app, 100, 21);
+        for _ in 0..20 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        assert_eq!(app.diff_cursor, 6);
+        assert_eq!(app.scroll_offset, 0);
+    }
 
-        let delta_output = r#"
-src/lib.rs
-────────────────────────────────────────
-────────────────┐
-50: fn helper() { │
-────────────────┘
- 50⋮ 50│    code();
-    ⋮ 51│    more();
- 51⋮ 52│}"#;
+    #[test]
+    fn tui_diff_cursor_moves_within_viewport_before_scrolling() {
+        let mut gh = fake_gh();
+        let added = (1..=30)
+            .map(|n| format!("+line {}\n", n))
+            .collect::<String>();
+        Arc::get_mut(&mut gh).unwrap().diff = format!(
+            "diff --git a/notes.txt b/notes.txt\n--- a/notes.txt\n+++ b/notes.txt\n@@ -0,0 +1,30 @@\n{}",
+            added
+        );
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        screen(&mut app, 100, 21);
 
-        let result = parse_delta_output(delta_output, raw_diff);
+        for _ in 0..7 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        assert_eq!((app.diff_cursor, app.scroll_offset), (7, 0));
+        let rows = screen(&mut app, 100, 21);
+        assert!(rows[16].starts_with("│▶"), "{}", rows[16]);
+
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!((app.diff_cursor, app.scroll_offset), (8, 1));
+        press(&mut app, KeyCode::PageDown);
+        assert_eq!((app.diff_cursor, app.scroll_offset), (16, 9));
+        for _ in 0..5 {
+            press(&mut app, KeyCode::PageDown);
+        }
+        assert_eq!((app.diff_cursor, app.scroll_offset), (33, 26));
+        for _ in 0..6 {
+            press(&mut app, KeyCode::Char('k'));
+        }
+        assert_eq!((app.diff_cursor, app.scroll_offset), (27, 26));
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!((app.diff_cursor, app.scroll_offset), (26, 26));
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!((app.diff_cursor, app.scroll_offset), (25, 25));
+    }
 
-        // Line 4: hunk header "50: fn helper()"
-        assert_eq!(result[4].file_path.as_deref(), Some("src/lib.rs"));
-        assert_eq!(result[4].old_line_number, Some(50));
-        assert_eq!(result[4].new_line_number, Some(50));
+    #[test]
+    fn tui_status_log_keeps_full_error_text() {
+        let mut gh = fake_gh();
+        Arc::get_mut(&mut gh).unwrap().action_error =
+            Some("HTTP 422: line must be part of the diff".to_string());
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        press(&mut app, KeyCode::Char('c'));
+        type_text(&mut app, "nit");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Comment failed: gh api failed: HTTP 422: line must be part of the diff")
+        );
 
-        // Line 6: code line
-        assert_eq!(result[6].old_line_number, Some(50));
-        assert_eq!(result[6].new_line_number, Some(50));
+        app.status_message = None;
+        press(&mut app, KeyCode::Char('!'));
+        assert_eq!(app.input_mode, InputMode::StatusLog);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains(" Messages ("));
+        assert!(text.contains("Posting comment on #7..."));
+        assert!(text.contains("Comment failed: gh api failed: HTTP 422: line must be part of"));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.input_mode, InputMode::Normal);
     }
 
     #[test]
-    fn test_parse_delta_multiple_files() {
-        let raw_diff = r#"diff --git a/file1.rs b/file1.rs
---- a/file1.rs
-+++ b/file1.rs
-@@ -1 +1 @@
--old1
-+new1
-diff --git a/file2.rs b/file2.rs
---- a/file2.rs
-+++ b/file2.rs
-@@ -1 +1 @@
--old2
-+new2"#;
-
-        let delta_output = r#"
-file1.rs
-────────────────────────────────────────
-  1⋮  1│new1
+    fn tui_failed_approve_restores_the_pr() {
+        let mut gh = fake_gh();
+        Arc::get_mut(&mut gh).unwrap().action_error = Some("HTTP 502".to_string());
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+
+        // The PR leaves the list before gh answers.
+        app.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert_eq!(app.prs.len(), 1);
+        assert_eq!(app.selected_pr().map(|pr| pr.number), Some(9));
+        assert!(screen(&mut app, 100, 20)[0].contains("Approving PR #7..."));
+
+        settle(&mut app);
+        assert_eq!(gh.calls(), vec!["approve_pr #7"]);
+        assert_eq!(app.prs.len(), 2);
+        assert_eq!(app.selected_pr().map(|pr| pr.number), Some(9));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Approve failed: gh api failed: HTTP 502")
+        );
+    }
 
-file2.rs
-────────────────────────────────────────
-  1⋮  1│new2"#;
+    #[test]
+    fn refetched_pr_updates_in_place_or_stays_removed_once_approved() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        let mut first = app.prs[0].clone();
+        first.review_state = ReviewState::ChangesRequested;
+        first.additions = 40;
+        app.apply_refetched_pr(None, first);
+        assert_eq!(app.prs[0].review_state, ReviewState::ChangesRequested);
+        assert_eq!(app.prs[0].additions, 40);
+
+        // Approved from the list: gone while my approval is my latest review.
+        let mut approved = app.prs.remove(0);
+        approved.reviewers = vec![gh::ReviewerStatus {
+            login: "me".to_string(),
+            state: gh::ReviewerState::Approved,
+            stale: false,
+        }];
+        app.apply_refetched_pr(Some(0), approved.clone());
+        assert_eq!(app.prs.len(), 1);
+
+        // Re-requested since: back where it was.
+        approved.reviewers[0].state = gh::ReviewerState::Pending;
+        app.apply_refetched_pr(Some(0), approved);
+        assert_eq!(
+            app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>(),
+            [7, 9]
+        );
+    }
 
-        let result = parse_delta_output(delta_output, raw_diff);
+    #[test]
+    fn tui_pr_merged_elsewhere_is_marked_and_removed_with_x() {
+        let mut gh = fake_gh();
+        Arc::get_mut(&mut gh).unwrap().action_error =
+            Some("GraphQL: Pull request is closed (addPullRequestReview)".to_string());
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(app.prs.len(), 2);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("PR #7 was closed elsewhere - press X to remove it")
+        );
 
-        // Find first file's code line
-        let file1_line = result
+        // The detail view moved on to #9; back in the list #7 is marked.
+        press(&mut app, KeyCode::Char('q'));
+        press(&mut app, KeyCode::Char('k'));
+        assert!(screen(&mut app, 100, 20)
             .iter()
-            .find(|r| r.file_path.as_deref() == Some("file1.rs") && r.new_line_number.is_some());
-        assert!(file1_line.is_some());
-        assert_eq!(file1_line.unwrap().new_line_number, Some(1));
+            .any(|row| row.contains("#7: [CLOSED] Speed up parse_config")));
 
-        // Find second file's code line - should switch to file2.rs
-        let file2_line = result
-            .iter()
-            .find(|r| r.file_path.as_deref() == Some("file2.rs") && r.new_line_number.is_some());
-        assert!(file2_line.is_some());
-        assert_eq!(file2_line.unwrap().new_line_number, Some(1));
+        // Further actions are refused without calling gh again.
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(gh.calls(), vec!["approve_pr #7"]);
+
+        press(&mut app, KeyCode::Char('X'));
+        assert_eq!(app.view, View::List);
+        assert_eq!(app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>(), [9]);
     }
 
     #[test]
-    fn test_parse_delta_decoration_lines() {
-        // Decoration lines (separators, box corners) should have no line numbers
-        let raw_diff = r#"diff --git a/test.rs b/test.rs
---- a/test.rs
-+++ b/test.rs
-@@ -1 +1 @@
- code"#;
+    fn tui_expand_context_refetches_the_file_with_more_lines() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        assert!(!screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("fn parse_config() {"));
+
+        press(&mut app, KeyCode::Char('e'));
+        let text = screen(&mut app, 100, 30).join("\n");
+        assert!(text.contains("src/lib.rs (+10 context)"));
+        assert!(text.contains("fn parse_config() {"));
+        assert_eq!(
+            app.diff_cursor_location(),
+            Some(("src/lib.rs".to_string(), Some(2)))
+        );
 
-        let delta_output = r#"
-test.rs
-────────────────────────────────────────
+        press(&mut app, KeyCode::Char('e'));
+        assert_eq!(app.file_context_lines, 20);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.file_context_lines, 0);
+        assert!(app.showing_large_diff_tree());
+    }
 
-────────────────┐
-1: fn test() {   │
-────────────────┘
-  1⋮  1│code"#;
+    #[test]
+    fn tui_full_file_view_opens_at_the_focused_line() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Char('j'));
+        }
 
-        let result = parse_delta_output(delta_output, raw_diff);
+        press(&mut app, KeyCode::Char('f'));
+        assert_eq!(app.input_mode, InputMode::FileView);
+        let text = screen(&mut app, 100, 30).join("\n");
+        assert!(text.contains("src/lib.rs @ PR head (7 lines)"));
+        assert!(text.contains("5 fn parse_config() {"));
+        assert!(text.contains("6     todo!()"));
 
-        // Line 2: separator line (────...)
-        assert_eq!(result[2].old_line_number, None);
-        assert_eq!(result[2].new_line_number, None);
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.file_view.as_ref().unwrap().scroll, 6);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.file_view.is_none());
+    }
 
-        // Line 3: empty line
-        assert_eq!(result[3].old_line_number, None);
-        assert_eq!(result[3].new_line_number, None);
+    #[test]
+    fn tui_list_shows_comment_and_unresolved_thread_badges() {
+        let mut first = make_test_pr(7, "Speed up parse_config", "acme/widgets", "alice");
+        first.additions = 1;
+        let gh = Arc::new(FakeGh {
+            prs: vec![
+                first,
+                make_test_pr(9, "Fix flaky test", "acme/widgets", "bob"),
+            ],
+            discussions: vec![(
+                7,
+                gh::Discussion {
+                    comments: 4,
+                    unresolved_threads: 2,
+                },
+            )],
+            ..Default::default()
+        });
+        let mut app = test_app(&gh, AppMode::Review);
 
-        // Line 4: box top (────...┐)
-        assert_eq!(result[4].old_line_number, None);
-        assert_eq!(result[4].new_line_number, None);
+        let rows = screen(&mut app, 120, 20);
+        let alice = rows.iter().find(|row| row.contains("@alice")).unwrap();
+        assert!(alice.contains("| 4 comments | 2 unresolved"), "{}", alice);
+        let bob = rows.iter().find(|row| row.contains("@bob")).unwrap();
+        assert!(!bob.contains("comment"), "{}", bob);
+    }
 
-        // Line 6: box bottom (────...┘)
-        assert_eq!(result[6].old_line_number, None);
-        assert_eq!(result[6].new_line_number, None);
+    #[test]
+    fn tui_milestone_and_projects_show_in_header_and_can_be_changed() {
+        let gh = Arc::new(FakeGh {
+            prs: fake_gh().prs.clone(),
+            triage: gh::PrTriage {
+                milestone: Some("v1.0".to_string()),
+                projects: Some(vec!["Roadmap (Todo)".to_string()]),
+            },
+            ..Default::default()
+        });
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        let rows = screen(&mut app, 140, 20);
+        assert!(
+            rows[2].contains("updated just now | milestone: v1.0 | projects: Roadmap (Todo)"),
+            "{}",
+            rows[2]
+        );
 
-        // Line 7: actual code line
-        assert_eq!(result[7].old_line_number, Some(1));
-        assert_eq!(result[7].new_line_number, Some(1));
+        press(&mut app, KeyCode::Char('M'));
+        assert_eq!(app.input_mode, InputMode::Milestone);
+        let rows = screen(&mut app, 140, 20).join("\n");
+        assert!(rows.contains("v1.0  current"), "{}", rows);
+        assert!(rows.contains("No milestone"), "{}", rows);
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, InputMode::Normal);
+
+        // No board configured: nothing is called.
+        press(&mut app, KeyCode::Char('P'));
+        app.project.board = Some("Roadmap".to_string());
+        press(&mut app, KeyCode::Char('P'));
+        assert_eq!(
+            gh.calls(),
+            vec![
+                "set_milestone #7 Some(\"v1.1\")",
+                "add_to_project #7 Roadmap",
+            ]
+        );
     }
 
     #[test]
-    fn test_strip_ansi_codes() {
-        // Test ANSI code stripping with real delta escape sequences
-        let with_ansi = "\x1b[34msrc/main.rs\x1b[0m";
-        assert_eq!(strip_ansi_codes(with_ansi), "src/main.rs");
+    fn tui_labels_show_in_list_and_header_and_can_be_edited() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.prs[0].labels = vec![label("docs")];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        assert!(screen(&mut app, 120, 20)
+            .join("\n")
+            .contains("#7: Speed up parse_config docs"));
+        press(&mut app, KeyCode::Enter);
+        assert!(screen(&mut app, 120, 20)[1].contains("by @alice docs"));
+
+        press(&mut app, KeyCode::Char('L'));
+        assert_eq!(app.input_mode, InputMode::Labels);
+        settle(&mut app);
+        let text = screen(&mut app, 120, 20).join("\n");
+        assert!(text.contains("[ ] bug"), "{}", text);
+        assert!(text.contains("[x] docs"), "{}", text);
+
+        // Check bug, uncheck docs
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(gh.calls(), vec!["edit_labels #7 +[\"bug\"] -[\"docs\"]"]);
+        assert_eq!(app.status_message.as_deref(), Some("Labels of #7: bug"));
+        assert_eq!(
+            label_color(&app.prs[0].labels[0]),
+            Color::Rgb(0xd7, 0x3a, 0x4a)
+        );
+        assert_eq!(label_color(&label("docs")), Color::Magenta);
+        assert!(screen(&mut app, 120, 20)[1].contains("by @alice bug"));
+
+        // Applying the labels it already has calls nothing
+        press(&mut app, KeyCode::Char('L'));
+        settle(&mut app);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(gh.calls().len(), 1);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Labels of #7 unchanged")
+        );
+    }
 
-        // Real delta output: colored line numbers
-        let complex = "\x1b[38;2;68;68;68m 303⋮ 303\x1b[34m│\x1b[0m}";
-        let stripped = strip_ansi_codes(complex);
-        assert!(stripped.contains("303"));
-        assert!(stripped.contains("⋮"));
-        assert!(stripped.contains("│"));
+    #[test]
+    fn tui_list_filter_narrows_the_list_until_cleared() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        assert_eq!(app.prs.len(), 2);
+
+        press(&mut app, KeyCode::Char('f'));
+        assert_eq!(app.input_mode, InputMode::ListFilter);
+        type_text(&mut app, "author:bob size:");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, InputMode::ListFilter);
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|status| status.contains("needs a key and a value")));
+
+        type_text(&mut app, "<500");
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        let numbers: Vec<u64> = app.prs.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![9]);
+        let text = screen(&mut app, 120, 20).join("\n");
+        assert!(text.contains("filter: author:bob size:<500"), "{}", text);
+
+        // The input starts from the active filter; clearing it brings everything back
+        press(&mut app, KeyCode::Char('f'));
+        assert_eq!(app.input_buffer, "author:bob size:<500");
+        for _ in 0..app.input_buffer.len() {
+            press(&mut app, KeyCode::Backspace);
+        }
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(app.prs.len(), 2);
+        assert!(app.list_config.filter.is_empty());
     }
 
-    // ==================== Tests for parse_diff (non-delta built-in mode) ====================
+    #[test]
+    fn tui_sort_cycles_through_modes_and_back_to_github_order() {
+        let mut gh = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        gh.prs[0].repo_name = "acme/zebra".to_string();
+        gh.prs[0].additions = 40;
+        gh.prs[0].created_at = Utc::now() - chrono::Duration::days(3);
+        gh.prs[1].review_state = ReviewState::Approved;
+        gh.prs
+            .push(make_test_pr(12, "Draft idea", "acme/api", "carol"));
+        gh.prs[2].additions = 5;
+        gh.prs[2].review_state = ReviewState::Draft;
+        gh.prs[2].created_at = Utc::now() - chrono::Duration::days(1);
+        let gh = Arc::new(gh);
+        let mut app = test_app(&gh, AppMode::MyPrs);
+        let numbers = |app: &App| app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&app), vec![7, 9, 12]);
+
+        let mut expected = vec![
+            ("created", vec![9, 12, 7]),
+            ("size", vec![9, 12, 7]),
+            ("repo", vec![12, 9, 7]),
+            ("review state", vec![7, 9, 12]),
+        ]
+        .into_iter();
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Char('O'));
+            let (label, order) = expected.next().unwrap();
+            assert_eq!(app.list_config.sort.label(), label);
+            assert_eq!(numbers(&app), order, "sorted by {label}");
+        }
+        let text = screen(&mut app, 140, 20).join("\n");
+        assert!(text.contains("sort: review state"), "{}", text);
+
+        press(&mut app, KeyCode::Char('O'));
+        settle(&mut app);
+        assert_eq!(app.list_config.sort, ListSort::Updated);
+        assert_eq!(numbers(&app), vec![7, 9, 12]);
+    }
 
     #[test]
-    fn test_parse_diff_basic() {
-        let diff = r#"diff --git a/src/main.rs b/src/main.rs
---- a/src/main.rs
-+++ b/src/main.rs
-@@ -10,4 +10,5 @@ fn main() {
-     let x = 1;
-+    let y = 2;
-     println!("test");
- }"#;
+    fn tui_group_by_repo_shows_headers_and_folds_repos() {
+        let mut gh = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        gh.prs
+            .insert(1, make_test_pr(12, "Add endpoint", "acme/api", "carol"));
+        let gh = Arc::new(gh);
+        let mut app = test_app(&gh, AppMode::Review);
+        let numbers = |app: &App| app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&app), vec![7, 12, 9]);
+
+        press(&mut app, KeyCode::Char('r'));
+        assert!(app.list_config.group_by_repo);
+        assert_eq!(numbers(&app), vec![7, 9, 12]);
+        let text = screen(&mut app, 120, 24).join("\n");
+        assert!(text.contains("▾ acme/widgets (2)"), "{}", text);
+        assert!(text.contains("▾ acme/api (1)"), "{}", text);
+        assert!(!text.contains("[acme/widgets]"), "{}", text);
+
+        // Folding keeps one header row for the repo; Enter on it unfolds
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(numbers(&app), vec![7, 12]);
+        let text = screen(&mut app, 120, 24).join("\n");
+        assert!(text.contains("▸ acme/widgets (2)"), "{}", text);
+        assert!(!text.contains("Fix flaky test"), "{}", text);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.view, View::List);
+        assert_eq!(numbers(&app), vec![7, 9, 12]);
+
+        press(&mut app, KeyCode::Char('r'));
+        settle(&mut app);
+        assert!(!app.list_config.group_by_repo);
+        assert_eq!(numbers(&app), vec![7, 12, 9]);
+    }
 
-        let result = parse_diff(diff);
+    #[test]
+    fn tui_rebound_keys_replace_the_defaults() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.keys = serde_json::from_str(r#"{"list": {"next_tab": ["l"], "quit": ["Q"]}}"#).unwrap();
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.mode, AppMode::Review);
+        press(&mut app, KeyCode::Char('l'));
+        assert_eq!(app.mode, AppMode::MyPrs);
+        // Arrows aren't anyone's default binding, so they keep working
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.mode, AppMode::Review);
+
+        let text = screen(&mut app, 250, 24).join("\n");
+        assert!(text.contains("| Q: quit"), "{}", text);
+        press(&mut app, KeyCode::Char('q'));
+        assert!(!app.should_quit);
+        press(&mut app, KeyCode::Char('Q'));
+        assert!(app.should_quit);
+    }
 
-        // Line 0: diff --git header
-        assert_eq!(result[0].file_path.as_deref(), Some("src/main.rs"));
-        assert_eq!(result[0].line_type, DiffLineType::Header);
+    #[test]
+    fn tui_rebound_next_pr_leaves_n_to_search_matches() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.keys = serde_json::from_str(r#"{"detail": {"next_pr": ["J"]}}"#).unwrap();
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        settle(&mut app);
+        screen(&mut app, 100, 24);
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "parse");
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.search_query.is_empty());
+
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.view, View::Detail);
+        assert_eq!(app.selected_pr().unwrap().number, 7);
+        // `J` goes to the next PR even while searching
+        press(&mut app, KeyCode::Char('J'));
+        assert_eq!(app.view, View::Detail);
+        assert_eq!(app.selected_pr().unwrap().number, 9);
+        // Without a search, `n` no longer steps PRs once `next_pr` moved off it
+        app.search_query.clear();
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(app.selected_pr().unwrap().number, 7);
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.selected_pr().unwrap().number, 7);
+    }
 
-        // Line 1-2: --- and +++ headers
-        assert_eq!(result[1].line_type, DiffLineType::Header);
-        assert_eq!(result[2].line_type, DiffLineType::Header);
+    #[test]
+    fn tui_preview_pane_follows_the_highlighted_pr() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        let text = screen(&mut app, 140, 30).join("\n");
+        assert!(!text.contains("parse_config was slow."), "{}", text);
+
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app.list_config.preview);
+        let text = screen(&mut app, 140, 30).join("\n");
+        assert!(text.contains("parse_config was slow."), "{}", text);
+        assert!(text.contains("Reviewers:"), "{}", text);
+
+        press(&mut app, KeyCode::Char('j'));
+        settle(&mut app);
+        let text = screen(&mut app, 140, 30).join("\n");
+        assert!(text.contains("No description provided."), "{}", text);
+        assert!(!text.contains("parse_config was slow."), "{}", text);
+
+        // Too narrow to split, the list keeps the whole width
+        let text = screen(&mut app, 80, 30).join("\n");
+        assert!(!text.contains("No description provided."), "{}", text);
+
+        press(&mut app, KeyCode::Char('p'));
+        assert!(!app.list_config.preview);
+        let text = screen(&mut app, 140, 30).join("\n");
+        assert!(!text.contains("No description provided."), "{}", text);
+    }
 
-        // Line 3: @@ hunk header
-        assert_eq!(result[3].line_type, DiffLineType::Hunk);
+    #[test]
+    fn pr_matches_filter_composes_terms() {
+        let mut pr = make_test_pr(9, "Fix parser", "acme/api", "bob");
+        pr.labels = vec![label("bug")];
+        pr.additions = 150;
+        pr.deletions = 50;
+        let filter = |text: &str| ListFilter::try_from(text.to_string()).unwrap();
+
+        assert!(pr_matches_filter(&pr, &filter("")));
+        assert!(pr_matches_filter(&pr, &filter("repo:acme/* author:@BOB")));
+        assert!(pr_matches_filter(
+            &pr,
+            &filter("repo:acme/web repo:acme/api")
+        ));
+        assert!(!pr_matches_filter(
+            &pr,
+            &filter("repo:acme/api author:alice")
+        ));
+        assert!(pr_matches_filter(
+            &pr,
+            &filter("label:b* draft:no size:>199")
+        ));
+        assert!(!pr_matches_filter(&pr, &filter("size:<200")));
+        assert!(!pr_matches_filter(&pr, &filter("draft:yes")));
+        assert!(!pr_matches_filter(&pr, &filter("label:docs")));
+    }
 
-        // Line 4: context line "     let x = 1;"
-        assert_eq!(result[4].line_type, DiffLineType::Context);
-        assert_eq!(result[4].old_line_number, Some(10));
-        assert_eq!(result[4].line_number, Some(10));
+    #[test]
+    fn tui_reviewers_panel_requests_and_re_requests_reviews() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.prs[0].reviewers = vec![
+            gh::ReviewerStatus {
+                login: "bob".to_string(),
+                state: gh::ReviewerState::Approved,
+                stale: true,
+            },
+            gh::ReviewerStatus {
+                login: "carol".to_string(),
+                state: gh::ReviewerState::Pending,
+                stale: false,
+            },
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        app.team.members = vec!["@dave".to_string(), "alice".to_string()];
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        let rows = screen(&mut app, 120, 24);
+        assert!(rows[3].contains("✓ @bob (stale) ○ @carol"), "{}", rows[3]);
+
+        press(&mut app, KeyCode::Char('W'));
+        assert_eq!(app.input_mode, InputMode::Reviewers);
+        let text = screen(&mut app, 120, 24).join("\n");
+        assert!(text.contains("@bob  reviewed an older commit"), "{}", text);
+        assert!(text.contains("@carol  requested"), "{}", text);
+        assert!(text.contains("@dave  Enter: request"), "{}", text);
+        // The PR's author is never offered
+        assert!(app.reviewer_rows().iter().all(|row| row.login != "alice"));
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("@carol is already requested on #7")
+        );
 
-        // Line 5: added line "+    let y = 2;"
-        assert_eq!(result[5].line_type, DiffLineType::Added);
-        assert_eq!(result[5].old_line_number, None);
-        assert_eq!(result[5].line_number, Some(11));
+        // Typing filters; a login nobody listed can be requested too
+        type_text(&mut app, "erin");
+        assert_eq!(
+            app.reviewer_rows()
+                .iter()
+                .map(|row| row.login.as_str())
+                .collect::<Vec<_>>(),
+            vec!["erin"]
+        );
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Requested review from @erin on #7")
+        );
 
-        // Line 6: context line "     println!..."
-        assert_eq!(result[6].line_type, DiffLineType::Context);
-        assert_eq!(result[6].old_line_number, Some(11));
-        assert_eq!(result[6].line_number, Some(12));
+        press(&mut app, KeyCode::Char('W'));
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(
+            gh.calls(),
+            vec!["request_reviewer #7 erin", "request_reviewer #7 bob"]
+        );
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Re-requested review from @bob on #7")
+        );
     }
 
     #[test]
-    fn test_parse_diff_removed_lines() {
-        let diff = r#"diff --git a/test.rs b/test.rs
---- a/test.rs
-+++ b/test.rs
-@@ -1,4 +1,3 @@
- fn main() {
--    let old = 1;
-     let keep = 2;
- }"#;
+    fn tui_read_only_repo_refuses_merge_and_close_upfront() {
+        let mut gh = fake_gh();
+        Arc::get_mut(&mut gh).unwrap().read_only = true;
+        let mut app = test_app(&gh, AppMode::MyPrs);
+        press(&mut app, KeyCode::Enter);
+        let rows = screen(&mut app, 120, 20);
+        assert!(
+            rows[2].contains("updated just now | read-only repo"),
+            "{}",
+            rows[2]
+        );
+        assert!(!rows[19].contains("m: merge"), "{}", rows[19]);
 
-        let result = parse_diff(diff);
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("acme/widgets is read-only for you: merging needs write access")
+        );
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("acme/widgets is read-only for you: closing others' PRs needs write access")
+        );
+        assert!(gh.calls().is_empty());
+    }
 
-        // Line 4: context "fn main()"
-        assert_eq!(result[4].line_type, DiffLineType::Context);
-        assert_eq!(result[4].old_line_number, Some(1));
-        assert_eq!(result[4].line_number, Some(1));
+    #[test]
+    fn tui_read_only_mode_refuses_mutating_actions() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.read_only = true;
+        press(&mut app, KeyCode::Enter);
+        let rows = screen(&mut app, 140, 20);
+        assert!(rows[17].contains("Help ─ read-only"), "{}", rows[17]);
+        let help = &rows[18];
+        assert!(!help.contains("a: approve"), "{}", help);
+        assert!(help.contains("o: browser"), "{}", help);
+
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Read-only mode: approving is disabled")
+        );
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('c'));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Read-only mode: commenting is disabled")
+        );
+        assert_eq!(app.view, View::Detail);
+        assert!(gh.calls().is_empty());
+    }
 
-        // Line 5: removed line (has old_line_number, no line_number)
-        assert_eq!(result[5].line_type, DiffLineType::Removed);
-        assert_eq!(result[5].old_line_number, Some(2));
-        assert_eq!(result[5].line_number, None);
+    #[test]
+    fn tui_approve_dialog_sends_an_optional_message() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+        assert!(screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("none (M to add one)"));
+
+        press(&mut app, KeyCode::Char('m'));
+        type_text(&mut app, "LGTM, one nit inline");
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("> LGTM, one nit inline_"), "{}", text);
+        assert!(text.contains("[Enter] Approve"), "{}", text);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(gh.calls(), vec!["approve_pr #7 LGTM, one nit inline"]);
+    }
 
-        // Line 6: context "let keep" - shifted in new file
-        assert_eq!(result[6].line_type, DiffLineType::Context);
-        assert_eq!(result[6].old_line_number, Some(3));
-        assert_eq!(result[6].line_number, Some(2));
+    #[test]
+    fn tui_approved_prs_can_stay_in_an_approved_section() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.list_config.approved = ApprovedPrs::Section;
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(gh.calls(), vec!["approve_pr #7"]);
+
+        // The detail view stays on the approved PR, now at the bottom.
+        let numbers: Vec<u64> = app.prs.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![9, 7]);
+        assert_eq!(app.view, View::Detail);
+        assert_eq!(app.selected_pr().map(|pr| pr.number), Some(7));
+        press(&mut app, KeyCode::Char('q'));
+        let rows = screen(&mut app, 100, 20);
+        let header = rows.iter().position(|row| row.contains("── Approved ──"));
+        let approved = rows
+            .iter()
+            .position(|row| row.contains("[APPROVED] Speed up"));
+        assert_eq!(header.map(|idx| idx + 1), approved, "{:#?}", rows);
+        assert!(!rows.iter().any(|row| row.contains("[APPROVED] Fix flaky")));
+
+        // Dimmed PRs stay where they are.
+        app.list_config.approved = ApprovedPrs::Dim;
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+        press(&mut app, KeyCode::Char('y'));
+        let numbers: Vec<u64> = app.prs.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![9, 7]);
+        press(&mut app, KeyCode::Char('q'));
+        let rows = screen(&mut app, 100, 20).join("\n");
+        assert!(rows.contains("[APPROVED] Fix flaky test"), "{}", rows);
     }
 
     #[test]
-    fn test_parse_diff_multiple_files() {
-        let diff = r#"diff --git a/file1.rs b/file1.rs
---- a/file1.rs
-+++ b/file1.rs
-@@ -1,2 +1,2 @@
--old content
-+new content
-diff --git a/file2.rs b/file2.rs
---- a/file2.rs
-+++ b/file2.rs
-@@ -5,2 +5,3 @@
- existing
-+added line"#;
+    fn tui_list_badges_prs_the_daemon_reviewed() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        let seen = Utc::now() - chrono::Duration::hours(3);
+        let record = |pr_number, trigger_status, triggered_at| ReviewedPrRecord {
+            repo: "acme/widgets".to_string(),
+            pr_number,
+            first_seen_at: seen,
+            last_seen_at: seen,
+            latest_updated_at: seen,
+            triggered_at,
+            trigger_status,
+            last_error: None,
+            triage: None,
+            provider: None,
+            last_run_dir: None,
+        };
+        app.daemon_reviews = BTreeMap::from([
+            (
+                "acme/widgets#7".to_string(),
+                record(7, TriggerStatus::Success, Some(seen)),
+            ),
+            (
+                "acme/widgets#9".to_string(),
+                record(9, TriggerStatus::Queued, None),
+            ),
+        ]);
+
+        let rows = screen(&mut app, 120, 20);
+        let alice = rows.iter().find(|row| row.contains("@alice")).unwrap();
+        assert!(
+            alice.contains("🤖") && alice.contains("AI review 3h ago"),
+            "{}",
+            alice
+        );
+        let bob = rows.iter().find(|row| row.contains("@bob")).unwrap();
+        assert!(bob.contains("AI review queued"), "{}", bob);
+    }
 
-        let result = parse_diff(diff);
+    #[test]
+    fn tui_ctrl_r_offers_configured_ai_setups() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.ai = config::AiConfig {
+            profiles: vec![config::AiProfile {
+                name: "deep".to_string(),
+                skill: Some("security-review".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        press(&mut app, KeyCode::Enter);
+        app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.input_mode, InputMode::AiChoice);
+        let text = screen(&mut app, 100, 20).join("\n");
+        assert!(text.contains("Re-run review of #7 with"), "{}", text);
+        for label in ["Claude (default)", "deep: Claude", "Codex"] {
+            assert!(text.contains(label), "{} missing from\n{}", label, text);
+        }
 
-        // First file
-        assert_eq!(result[0].file_path.as_deref(), Some("file1.rs"));
-        assert_eq!(result[4].file_path.as_deref(), Some("file1.rs"));
-        assert_eq!(result[4].line_type, DiffLineType::Removed);
-        assert_eq!(result[5].line_type, DiffLineType::Added);
+        // Nothing to launch with: the choice is validated like `r`.
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|status| status.starts_with("AI launch is not configured")));
+        assert!(!app.launching_ai);
+    }
 
-        // Second file - should switch file path
-        assert_eq!(result[6].file_path.as_deref(), Some("file2.rs"));
-        assert_eq!(result[6].line_type, DiffLineType::Header);
+    #[test]
+    fn tui_review_list_follows_list_order_rules() {
+        let mut gh = fake_gh();
+        let mut security = make_test_pr(11, "Bump openssl", "acme/widgets", "carol");
+        security.labels = vec![label("Security-High")];
+        security.additions = 400;
+        let mut large = make_test_pr(12, "Rewrite parser", "acme/widgets", "dave");
+        large.additions = 900;
+        Arc::get_mut(&mut gh).unwrap().prs.extend([security, large]);
+        let mut app = test_app(&gh, AppMode::Review);
+        let numbers = |app: &App| app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&app), vec![7, 9, 11, 12]);
+
+        app.list_config.order = vec![
+            ListOrderRule::Label("security*".to_string()),
+            ListOrderRule::Teammates,
+            ListOrderRule::Largest,
+        ];
+        app.team.members = vec!["@bob".to_string()];
+        app.refresh();
+        settle(&mut app);
+        assert_eq!(numbers(&app), vec![11, 9, 12, 7]);
 
-        // Lines in second file start at line 5
-        assert_eq!(result[10].line_type, DiffLineType::Context);
-        assert_eq!(result[10].old_line_number, Some(5));
-        assert_eq!(result[11].line_type, DiffLineType::Added);
-        assert_eq!(result[11].line_number, Some(6));
+        app.list_config.order = vec![ListOrderRule::Smallest];
+        app.refresh();
+        settle(&mut app);
+        assert_eq!(numbers(&app), vec![9, 7, 11, 12]);
     }
 
     #[test]
-    fn test_parse_diff_hunk_header_parsing() {
-        // Test various hunk header formats
-        let diff = r#"diff --git a/test.rs b/test.rs
---- a/test.rs
-+++ b/test.rs
-@@ -100,5 +200,10 @@ fn context_function() {
- context line"#;
-
-        let result = parse_diff(diff);
+    fn tui_view_picker_filters_sorts_and_switches_tab() {
+        let mut gh = fake_gh();
+        let mut hot = make_test_pr(11, "Fix deploy", "acme/platform", "carol");
+        hot.labels = vec![label("Platform-Hot")];
+        hot.additions = 400;
+        let mut small = make_test_pr(12, "Tweak CI", "acme/platform", "dave");
+        small.labels = vec![label("platform-hot")];
+        small.additions = 1;
+        let unlabeled = make_test_pr(13, "Docs", "acme/platform", "erin");
+        Arc::get_mut(&mut gh)
+            .unwrap()
+            .prs
+            .extend([hot, small, unlabeled]);
+        let mut app = test_app(&gh, AppMode::Review);
+        app.views = vec![ViewConfig {
+            name: "platform hotlist".to_string(),
+            tab: ViewTab::MyPrs,
+            repos: vec!["ACME/plat*".to_string()],
+            labels: vec!["platform-*".to_string()],
+            order: Some(vec![ListOrderRule::Smallest]),
+            drafts: Some(true),
+        }];
+        let numbers = |app: &App| app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
+
+        press(&mut app, KeyCode::Char('V'));
+        assert_eq!(app.input_mode, InputMode::ViewPicker);
+        assert!(screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("platform hotlist"));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(app.mode, AppMode::MyPrs);
+        assert!(app.include_drafts);
+        assert_eq!(numbers(&app), vec![12, 11]);
+        assert!(screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("view: platform hotlist"));
+
+        press(&mut app, KeyCode::Char('V'));
+        assert_eq!(app.view_state.selected(), Some(1));
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert!(app.active_view.is_none());
+        assert_eq!(app.mode, AppMode::MyPrs);
+        assert_eq!(numbers(&app), vec![7, 9, 11, 12, 13]);
+    }
 
-        // After hunk header, lines should start at correct numbers
-        // Old file: starts at 100, new file: starts at 200
-        assert_eq!(result[4].line_type, DiffLineType::Context);
-        assert_eq!(result[4].old_line_number, Some(100));
-        assert_eq!(result[4].line_number, Some(200));
+    #[test]
+    fn tui_per_repo_draft_overrides_pick_which_drafts_show() {
+        let mut gh = fake_gh();
+        let mut platform_draft = make_test_pr(11, "WIP deploy", "acme/platform", "carol");
+        platform_draft.is_draft = true;
+        let mut widgets_draft = make_test_pr(12, "WIP parser", "acme/widgets", "dave");
+        widgets_draft.is_draft = true;
+        Arc::get_mut(&mut gh)
+            .unwrap()
+            .prs
+            .extend([platform_draft, widgets_draft]);
+        let mut app = test_app(&gh, AppMode::Review);
+        let numbers = |app: &App| app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
+        assert!(!app.include_drafts);
+        assert!(!app.list_query().include_drafts);
+        assert_eq!(numbers(&app), vec![7, 9]);
+
+        app.drafts_config.always = vec!["ACME/plat*".to_string()];
+        assert!(app.list_query().include_drafts);
+        app.refresh();
+        settle(&mut app);
+        assert_eq!(numbers(&app), vec![7, 9, 11]);
+
+        app.include_drafts = true;
+        app.drafts_config.never = vec!["acme/platform".to_string()];
+        app.refresh();
+        settle(&mut app);
+        assert_eq!(numbers(&app), vec![7, 9, 12]);
     }
 
     #[test]
-    fn test_parse_diff_no_newline_marker() {
-        // "\ No newline at end of file" should be treated as Other
-        let diff = r#"diff --git a/test.rs b/test.rs
---- a/test.rs
-+++ b/test.rs
-@@ -1,2 +1,2 @@
--old
-+new
-\ No newline at end of file"#;
+    fn tui_review_list_hides_bot_authors_until_revealed() {
+        let mut gh = fake_gh();
+        let dependabot = make_test_pr(11, "Bump serde", "acme/widgets", "dependabot");
+        let renovate = make_test_pr(12, "Update tokio", "acme/widgets", "app/renovate");
+        Arc::get_mut(&mut gh)
+            .unwrap()
+            .prs
+            .extend([dependabot, renovate]);
+        let mut app = test_app(&gh, AppMode::Review);
+        let numbers = |app: &App| app.prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&app), vec![7, 9]);
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap_or("")
+            .contains("2 by bots hidden"));
 
-        let result = parse_diff(diff);
+        press(&mut app, KeyCode::Char('B'));
+        settle(&mut app);
+        assert_eq!(numbers(&app), vec![7, 9, 11, 12]);
 
-        // The backslash line should be Other type
-        assert_eq!(result[6].line_type, DiffLineType::Other);
-        assert_eq!(result[6].line_number, None);
-        assert_eq!(result[6].old_line_number, None);
+        press(&mut app, KeyCode::Char('B'));
+        settle(&mut app);
+        assert_eq!(numbers(&app), vec![7, 9]);
+
+        press(&mut app, KeyCode::Char('2'));
+        settle(&mut app);
+        assert_eq!(numbers(&app), vec![7, 9, 11, 12]);
     }
 
     #[test]
-    fn test_parse_diff_file_path_extraction() {
-        // Test file path extraction from various diff formats
-        let diff = r#"diff --git a/path/to/file.rs b/path/to/file.rs
---- a/path/to/file.rs
-+++ b/path/to/file.rs
-@@ -1 +1 @@
--old
-+new"#;
+    fn tui_date_style_toggle_switches_list_ages_to_timestamps() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.dates = DatesConfig {
+            timezone: crate::config::DateTimezone::Utc,
+            style: DateStyle::Relative,
+            format: "on day %j of %Y".to_string(),
+        };
+        let stamp = app.prs[0].updated_at.format("on day %j of %Y").to_string();
+        assert!(!screen(&mut app, 100, 20).join("\n").contains(&stamp));
 
-        let result = parse_diff(diff);
+        press(&mut app, KeyCode::Char('T'));
+        assert_eq!(app.dates.style, DateStyle::Absolute);
+        assert!(screen(&mut app, 100, 20).join("\n").contains(&stamp));
+    }
 
-        // All lines should have the correct file path
-        for line in &result {
-            assert_eq!(line.file_path.as_deref(), Some("path/to/file.rs"));
+    #[test]
+    fn tui_tab_in_comment_input_composes_in_editor() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.start_comment();
+        for c in "draft".chars() {
+            press(&mut app, KeyCode::Char(c));
         }
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        let draft = app.take_pending_comment_draft().unwrap();
+        assert_eq!((draft.pr.number, draft.text.as_str()), (7, "draft"));
+
+        app.finish_comment_draft(draft.clone(), Err(anyhow::anyhow!("editor exited")));
+        assert_eq!(app.input_mode, InputMode::Comment);
+        assert_eq!(app.input_buffer, "draft");
+
+        app.input_mode = InputMode::Normal;
+        app.finish_comment_draft(draft.clone(), Ok("\n  \n".to_string()));
+        settle(&mut app);
+        assert!(gh.calls().is_empty());
+
+        app.finish_comment_draft(draft, Ok("First.\n\n- second".to_string()));
+        settle(&mut app);
+        assert_eq!(gh.calls(), vec!["add_pr_comment #7 First.\n\n- second"]);
     }
 
     #[test]
-    fn test_parse_diff_file_sections() {
-        let diff = r#"diff --git a/src/main.rs b/src/main.rs
---- a/src/main.rs
-+++ b/src/main.rs
-@@ -1 +1 @@
--old
-+new
-diff --git a/README.md b/README.md
---- a/README.md
-+++ b/README.md
-@@ -1 +1 @@
--hello
-+hello world"#;
+    fn tui_accessible_mode_marks_state_in_text_and_drops_background_fills() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.set_status("Refreshed".to_string());
+        assert!(!screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("[Involving Me]"));
+
+        app.accessible = true;
+        let backend = ratatui::backend::TestBackend::new(100, 20);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| draw(f, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert!(buffer.content.iter().all(|cell| cell.bg == Color::Reset));
+        assert!(buffer
+            .content
+            .iter()
+            .all(|cell| !matches!(cell.fg, Color::DarkGray | Color::Black)));
+        let rows = screen(&mut app, 100, 20).join("\n");
+        assert!(rows.contains("[Involving Me]"));
+        assert!(rows.contains("▶ [acme/widgets] #7"));
+    }
 
-        let sections = parse_diff_file_sections(diff);
-        assert_eq!(sections.len(), 2);
-        assert_eq!(sections[0].path, "src/main.rs");
-        assert!(sections[0].diff.contains("@@ -1 +1 @@"));
-        assert_eq!(sections[1].path, "README.md");
-        assert!(sections[1].diff.contains("+hello world"));
+    #[test]
+    fn fit_help_drops_trailing_hints_but_keeps_the_last() {
+        let help = " j/k: navigate | Enter: open | o: browser | q: quit";
+        assert_eq!(fit_help(help, 60), help);
+        assert_eq!(
+            fit_help(help, 42),
+            " j/k: navigate | Enter: open | … | q: quit"
+        );
+        assert_eq!(fit_help(help, 41), " j/k: navigate | … | q: quit");
+        assert_eq!(fit_help(help, 10), " … | q: quit");
     }
 
     #[test]
-    fn test_build_diff_tree_items_hierarchy() {
-        let sections = vec![
-            FileDiffSection {
-                path: "src/lib.rs".to_string(),
-                diff: String::new(),
-            },
-            FileDiffSection {
-                path: "src/utils/math.rs".to_string(),
-                diff: String::new(),
-            },
-            FileDiffSection {
-                path: "README.md".to_string(),
-                diff: String::new(),
+    fn tui_narrow_list_stacks_discussion_badges_on_their_own_line() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        app.discussions.insert(
+            "acme/widgets#7".to_string(),
+            gh::Discussion {
+                comments: 3,
+                unresolved_threads: 1,
             },
-        ];
+        );
 
-        let items = build_diff_tree_items(&sections);
-        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+        let wide = screen(&mut app, 120, 20);
+        assert!(wide
+            .iter()
+            .any(|row| row.contains("@alice") && row.contains("3 comments")));
 
+        let narrow = screen(&mut app, 80, 20);
+        let author = narrow
+            .iter()
+            .position(|row| row.contains("@alice"))
+            .unwrap();
+        assert!(!narrow[author].contains("3 comments"));
+        assert!(narrow[author + 1].contains("3 comments | 1 unresolved"));
+        assert!(narrow.iter().any(|row| row.contains("| … | q: quit")));
+    }
+
+    #[test]
+    fn tui_visual_selection_posts_a_multi_line_comment() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        press(&mut app, KeyCode::Char('v'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        let rows = screen(&mut app, 100, 20);
+        assert!(rows[13].starts_with("│┃   1   1  fn main()"));
+        assert!(rows[14].starts_with("│┃       2 +    parse_config();"));
+        assert!(rows[15].starts_with("│▶   2   3  }"));
+
+        press(&mut app, KeyCode::Char('c'));
+        assert_eq!(app.input_mode, InputMode::LineComment);
+        assert_eq!(app.diff_selection_anchor, None);
+        assert!(screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("Comment on src/lib.rs:1-3"));
+        type_text(&mut app, "extract this");
+        press(&mut app, KeyCode::Enter);
         assert_eq!(
-            labels,
-            vec!["src/", "  utils/", "    math.rs", "  lib.rs", "README.md"]
+            gh.calls(),
+            vec!["add_line_comment #7 src/lib.rs:1-3 RIGHT extract this"]
         );
-        assert_eq!(items[2].file_path.as_deref(), Some("src/utils/math.rs"));
-        assert_eq!(items[3].file_path.as_deref(), Some("src/lib.rs"));
-        assert_eq!(items[4].file_path.as_deref(), Some("README.md"));
+
+        press(&mut app, KeyCode::Char('v'));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.diff_selection_anchor, None);
+        assert_eq!(app.view, View::Detail);
     }
 
-    // ==================== Tests for search index helpers ====================
+    #[test]
+    fn tui_queued_line_comments_submit_as_one_review() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('s'));
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        press(&mut app, KeyCode::Char('c'));
+        type_text(&mut app, "nit");
+        press(&mut app, KeyCode::Enter);
+        assert!(gh.calls().is_empty());
+        assert!(screen(&mut app, 140, 20)[2].contains("review: 1 queued"));
+
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.input_mode, InputMode::ReviewSummary);
+        assert!(screen(&mut app, 100, 20)
+            .join("\n")
+            .contains("src/lib.rs:1 nit"));
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, InputMode::ReviewSummary);
+        assert!(gh.calls().is_empty());
+
+        press(&mut app, KeyCode::BackTab);
+        type_text(&mut app, "looks good");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            gh.calls(),
+            vec!["submit_review #7 APPROVE [src/lib.rs:1] looks good"]
+        );
+        assert!(app.pending_reviews.is_empty());
+    }
 
     #[test]
-    fn test_advance_search_idx() {
-        // Test forward cycling through indices
-        assert_eq!(advance_search_idx(0, 5), 1);
-        assert_eq!(advance_search_idx(4, 5), 0); // wrap around
-        assert_eq!(advance_search_idx(0, 1), 0); // single element
+    fn tui_resize_rerenders_delta_once_the_width_settles() {
+        let gh = fake_gh();
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        app.delta_cache = Some(String::new());
+        app.delta_width = 120;
+
+        app.pending_resize = Some((90, std::time::Instant::now()));
+        assert!(!app.poll_resize());
+        assert_eq!(app.delta_width, 120);
+
+        let settled = std::time::Instant::now() - RESIZE_SETTLE;
+        app.pending_resize = Some((90, settled));
+        assert!(app.poll_resize());
+        assert_eq!(app.pending_resize, None);
+        assert_eq!(app.delta_width, 90);
+
+        app.pending_resize = Some((90, settled));
+        assert!(!app.poll_resize());
     }
 
     #[test]
-    fn test_retreat_search_idx() {
-        // Test backward cycling through indices
-        assert_eq!(retreat_search_idx(1, 5), 0);
-        assert_eq!(retreat_search_idx(0, 5), 4); // wrap around
-        assert_eq!(retreat_search_idx(0, 1), 0); // single element
+    fn tui_reply_to_the_selected_review_thread() {
+        let review_comment = |id: u64, line: u32, reply_to: Option<u64>, body: &str| {
+            serde_json::from_value::<ReviewComment>(serde_json::json!({
+                "id": id,
+                "user": { "login": "alice" },
+                "body": body,
+                "path": "src/lib.rs",
+                "line": line,
+                "original_line": line,
+                "diff_hunk": "@@ -1,2 +1,3 @@",
+                "created_at": "2024-01-01T00:00:00Z",
+                "in_reply_to_id": reply_to,
+            }))
+            .unwrap()
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.review_comments = vec![
+            review_comment(11, 2, None, "why?"),
+            review_comment(12, 2, Some(11), "because"),
+            review_comment(21, 3, None, "typo"),
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::MyPrs);
+        press(&mut app, KeyCode::Enter);
+        while app.detail_tab != DetailTab::Comments {
+            press(&mut app, KeyCode::Tab);
+        }
+        settle(&mut app);
+        screen(&mut app, 100, 30);
+
+        press(&mut app, KeyCode::Char(']'));
+        press(&mut app, KeyCode::Char(']'));
+        let marked: Vec<String> = screen(&mut app, 100, 30)
+            .into_iter()
+            .filter(|row| row.starts_with("│▶ 📁"))
+            .collect();
+        assert_eq!(marked.len(), 1);
+        assert!(marked[0].contains("src/lib.rs:3"));
+
+        press(&mut app, KeyCode::Char('['));
+        press(&mut app, KeyCode::Char('c'));
+        assert!(screen(&mut app, 100, 30)
+            .join("\n")
+            .contains("Reply on src/lib.rs:2"));
+        type_text(&mut app, "fair enough");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            gh.calls(),
+            vec!["reply_to_review_comment #7 11 fair enough"]
+        );
+        assert!(app.reply_target.is_none());
     }
 
     #[test]
-    fn test_format_search_status() {
-        assert_eq!(format_search_status(0, 5, "test"), "Match 1/5 for 'test'");
-        assert_eq!(format_search_status(4, 5, "foo"), "Match 5/5 for 'foo'");
+    fn tui_review_threads_expand_under_their_diff_line() {
+        let review_comment = |id: u64, side: &str, reply_to: Option<u64>, body: &str| {
+            serde_json::from_value::<ReviewComment>(serde_json::json!({
+                "id": id,
+                "user": { "login": "bob" },
+                "body": body,
+                "path": "src/lib.rs",
+                "line": 2,
+                "side": side,
+                "original_line": 2,
+                "diff_hunk": "@@ -1,2 +1,3 @@",
+                "created_at": "2024-01-01T00:00:00Z",
+                "in_reply_to_id": reply_to,
+            }))
+            .unwrap()
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.review_comments = vec![
+            review_comment(11, "RIGHT", None, "why a call here?"),
+            review_comment(12, "RIGHT", Some(11), "it was inlined"),
+            review_comment(21, "LEFT", None, "keep the brace"),
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        screen(&mut app, 100, 30);
+        settle(&mut app);
+
+        // Collapsed: new line 2 (the added call) and old line 2 (the brace) are marked
+        let rows = screen(&mut app, 100, 30);
+        let marked: Vec<&String> = rows.iter().filter(|row| row.starts_with("│◆ ")).collect();
+        assert_eq!(marked.len(), 2, "{:#?}", rows);
+        assert!(marked[0].contains("parse_config();"), "{}", marked[0]);
+        assert!(marked[1].contains('}'), "{}", marked[1]);
+        assert!(!rows.join("\n").contains("why a call here?"));
+
+        // On the hunk header; `Enter` on the file header folds the file instead
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No review comments on this line")
+        );
+        for _ in 0..2 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        assert!(screen(&mut app, 100, 30)
+            .join("\n")
+            .contains("[1 review thread, Enter to show]"));
+        press(&mut app, KeyCode::Enter);
+        let text = screen(&mut app, 100, 30).join("\n");
+        assert!(text.contains("Review comments (Enter to hide)"), "{}", text);
+        assert!(text.contains("@bob (2024-01-01 00:00)"), "{}", text);
+        assert!(text.contains("why a call here?"), "{}", text);
+        assert!(text.contains("↳ @bob"), "{}", text);
+        assert!(!text.contains("keep the brace"), "{}", text);
+
+        // Expanded threads follow the cursor to the next commented line
+        press(&mut app, KeyCode::Char('j'));
+        let text = screen(&mut app, 100, 30).join("\n");
+        assert!(text.contains("keep the brace"), "{}", text);
+        assert!(!text.contains("why a call here?"), "{}", text);
+        press(&mut app, KeyCode::Enter);
+        assert!(!screen(&mut app, 100, 30)
+            .join("\n")
+            .contains("keep the brace"));
     }
 
-    fn make_test_pr(number: u64, title: &str, repo: &str, author: &str) -> PullRequest {
-        PullRequest {
-            number,
-            title: title.to_string(),
-            author: author.to_string(),
-            author_kind: Some("User".to_string()),
-            body: String::new(),
-            repo_path: PathBuf::from("/tmp/repo"),
-            repo_name: repo.to_string(),
-            url: String::new(),
-            updated_at: Utc::now(),
-            additions: 0,
-            deletions: 0,
-            is_draft: false,
-            review_state: ReviewState::Pending,
-            details_loaded: true,
+    #[test]
+    fn tui_line_comment_outside_githubs_diff_is_refused() {
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        // GitHub's patch has moved on: new line 1 is no longer part of it
+        fake.file_patch = Some("@@ -2 +2,2 @@\n+    parse_config();\n }\n".to_string());
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Tab);
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Char('j'));
         }
+        press(&mut app, KeyCode::Char('c'));
+        type_text(&mut app, "why?");
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+
+        assert!(gh.calls().is_empty());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Comment failed: src/lib.rs:1 is not part of the diff; comment on a changed or context line")
+        );
     }
 
     #[test]
-    fn test_pr_matches_list_query_matches_pr_number() {
-        let pr = make_test_pr(
-            12345,
-            "Improve cache invalidation",
-            "org/reviewer",
-            "daulet",
+    fn tui_my_pr_replies_then_re_requests_review() {
+        let review_comment = |id: u64, user: &str, reply_to: Option<u64>, minute: u32| {
+            serde_json::from_value::<ReviewComment>(serde_json::json!({
+                "id": id,
+                "user": { "login": user },
+                "body": "text",
+                "path": "src/lib.rs",
+                "line": 2,
+                "diff_hunk": "@@ -1,2 +1,3 @@",
+                "created_at": format!("2024-01-01T00:{:02}:00Z", minute),
+                "in_reply_to_id": reply_to,
+            }))
+            .unwrap()
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        for pr in &mut fake.prs {
+            pr.reviewers = vec![
+                gh::ReviewerStatus {
+                    login: "bob".to_string(),
+                    state: gh::ReviewerState::ChangesRequested,
+                    stale: false,
+                },
+                gh::ReviewerStatus {
+                    login: "carol".to_string(),
+                    state: gh::ReviewerState::Approved,
+                    stale: false,
+                },
+            ];
+        }
+        fake.review_comments = vec![
+            review_comment(11, "bob", None, 1),
+            review_comment(12, "me", Some(11), 2),
+            review_comment(21, "bob", None, 3),
+        ];
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::MyPrs);
+        press(&mut app, KeyCode::Enter);
+        while app.detail_tab != DetailTab::Comments {
+            press(&mut app, KeyCode::Tab);
+        }
+        settle(&mut app);
+        screen(&mut app, 100, 30);
+
+        press(&mut app, KeyCode::Char('R'));
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|status| status.starts_with("1 review thread(s) still await your reply")));
+        assert!(gh.calls().is_empty());
+
+        press(&mut app, KeyCode::Char(']'));
+        press(&mut app, KeyCode::Char(']'));
+        press(&mut app, KeyCode::Char('c'));
+        type_text(&mut app, "fixed");
+        press(&mut app, KeyCode::Enter);
+        settle(&mut app);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("All review threads answered - R re-requests review from @bob")
         );
 
-        assert!(pr_matches_list_query(&pr, "12345"));
-        assert!(pr_matches_list_query(&pr, "#12345"));
+        press(&mut app, KeyCode::Char('R'));
+        settle(&mut app);
+        assert_eq!(
+            gh.calls(),
+            vec![
+                "reply_to_review_comment #7 21 fixed",
+                "request_reviewer #7 bob"
+            ]
+        );
     }
 
     #[test]
-    fn test_pr_matches_list_query_matches_title_repo_author() {
-        let pr = make_test_pr(777, "Fix daemon startup race", "org/reviewer", "alice");
-
-        assert!(pr_matches_list_query(&pr, "startup"));
-        assert!(pr_matches_list_query(&pr, "org/reviewer"));
-        assert!(pr_matches_list_query(&pr, "alice"));
-        assert!(!pr_matches_list_query(&pr, "nonexistent"));
+    fn tui_resolve_toggles_the_selected_thread() {
+        let review_comment = |id: u64, line: u32| {
+            serde_json::from_value::<ReviewComment>(serde_json::json!({
+                "id": id,
+                "user": { "login": "alice" },
+                "body": "text",
+                "path": "src/lib.rs",
+                "line": line,
+                "diff_hunk": "@@ -1,2 +1,3 @@",
+                "created_at": "2024-01-01T00:00:00Z",
+                "in_reply_to_id": null,
+            }))
+            .unwrap()
+        };
+        let thread = |id: &str, is_resolved: bool| gh::ReviewThreadState {
+            id: id.to_string(),
+            is_resolved,
+        };
+        let mut fake = Arc::try_unwrap(fake_gh()).ok().unwrap();
+        fake.review_comments = vec![review_comment(11, 2), review_comment(21, 3)];
+        fake.review_threads =
+            BTreeMap::from([(11, thread("PRRT_a", false)), (21, thread("PRRT_b", true))]);
+        let gh = Arc::new(fake);
+        let mut app = test_app(&gh, AppMode::Review);
+        press(&mut app, KeyCode::Enter);
+        while app.detail_tab != DetailTab::Comments {
+            press(&mut app, KeyCode::Tab);
+        }
+        settle(&mut app);
+        let text = screen(&mut app, 100, 30).join("\n");
+        assert!(text.contains("src/lib.rs:2  [unresolved]"));
+        assert!(text.contains("src/lib.rs:3  [resolved]"));
+
+        press(&mut app, KeyCode::Char('u'));
+        assert!(gh.calls().is_empty());
+
+        press(&mut app, KeyCode::Char(']'));
+        press(&mut app, KeyCode::Char('u'));
+        settle(&mut app);
+        assert_eq!(gh.calls(), vec!["set_review_thread_resolved PRRT_a true"]);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Resolved thread on src/lib.rs:2")
+        );
+        assert!(app.review_thread_states[&11].is_resolved);
     }
 }